@@ -1,9 +1,9 @@
 /// Basic usage example of iDos Games SDK with Bevy
 use bevy::prelude::*;
-use idos_game_sdk::{IdosConfig, IdosGamesPlugin};
+use idos_game_sdk::{IdosConfig, IdosGamesPlugin, IdosTasks};
 
 #[cfg(feature = "auth")]
-use idos_game_sdk::auth::{dto::AuthEvent, handler::AuthHandler};
+use idos_game_sdk::auth::dto::{AuthEvent, LoginMethod, LoginRequested};
 
 #[cfg(feature = "analytics")]
 use idos_game_sdk::analytics::handler::AnalyticsHandler;
@@ -56,39 +56,14 @@ fn setup(mut commands: Commands) {
 #[cfg(feature = "auth")]
 fn handle_input(
     keyboard: Res<ButtonInput<KeyCode>>,
-    auth: Option<Res<AuthHandler>>,
+    tasks: Res<IdosTasks>,
+    mut login: MessageWriter<LoginRequested>,
     analytics: Option<Res<AnalyticsHandler>>,
 ) {
     if keyboard.just_pressed(KeyCode::KeyL) {
-        if let Some(auth) = auth {
-            info!("Attempting guest login...");
-
-            #[cfg(target_arch = "wasm32")]
-            {
-                let auth = auth.clone();
-                wasm_bindgen_futures::spawn_local(async move {
-                    match auth.login_guest().await {
-                        Ok(response) => {
-                            info!("Guest login successful! User: {:?}", response.user);
-                        }
-                        Err(e) => error!("Guest login failed: {}", e),
-                    }
-                });
-            }
-
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                let auth = auth.clone();
-                tokio::spawn(async move {
-                    match auth.login_guest().await {
-                        Ok(response) => {
-                            info!("Guest login successful! User: {:?}", response.user);
-                        }
-                        Err(e) => error!("Guest login failed: {}", e),
-                    }
-                });
-            }
-        }
+        info!("Attempting guest login...");
+        // AuthPlugin runs the request and answers with LoginSucceeded/LoginFailed
+        login.write(LoginRequested(LoginMethod::Guest));
     }
 
     #[cfg(feature = "analytics")]
@@ -99,21 +74,10 @@ fn handle_input(
             let mut props = std::collections::HashMap::new();
             props.insert("example".to_string(), serde_json::json!("button_pressed"));
 
-            #[cfg(target_arch = "wasm32")]
-            {
-                let analytics = analytics.clone();
-                wasm_bindgen_futures::spawn_local(async move {
-                    analytics.track_event("test_event", props).await.ok();
-                });
-            }
-
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                let analytics = analytics.clone();
-                tokio::spawn(async move {
-                    analytics.track_event("test_event", props).await.ok();
-                });
-            }
+            let analytics = analytics.clone();
+            tasks.spawn(async move {
+                analytics.track_event("test_event", props).await.ok();
+            });
         }
     }
 }
@@ -6,7 +6,7 @@
 /// - Interact with smart contracts
 /// - Handle MetaMask on WASM
 use bevy::prelude::*;
-use idos_game_sdk::{IdosConfig, IdosGamesPlugin};
+use idos_game_sdk::{IdosConfig, IdosGamesPlugin, IdosTasks};
 
 #[cfg(feature = "crypto_ethereum")]
 use idos_game_sdk::crypto_ethereum::{BlockchainSettings, EthereumHandler, EthereumPlugin};
@@ -93,7 +93,11 @@ fn setup(mut commands: Commands) {
 }
 
 #[cfg(feature = "crypto_ethereum")]
-fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, ethereum: Option<Res<EthereumHandler>>) {
+fn handle_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    tasks: Res<IdosTasks>,
+    ethereum: Option<Res<EthereumHandler>>,
+) {
     if let Some(eth) = ethereum {
         // Example wallet address - replace with actual wallet address
         let wallet_address = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb"; // Example address
@@ -102,34 +106,17 @@ fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, ethereum: Option<Res<Ethere
         if keyboard.just_pressed(KeyCode::KeyB) {
             info!("Checking native token balance...");
 
-            #[cfg(target_arch = "wasm32")]
-            {
-                let eth_clone = eth.clone();
-                let addr = wallet_address.to_string();
-                wasm_bindgen_futures::spawn_local(async move {
-                    match eth_clone.get_native_balance(&addr).await {
-                        Ok(balance) => {
-                            info!("Native balance (wei): {}", balance);
-                            // Convert from wei to ETH: divide by 10^18
-                        }
-                        Err(e) => error!("Failed to get balance: {}", e),
-                    }
-                });
-            }
-
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                let eth_clone = eth.clone();
-                let addr = wallet_address.to_string();
-                tokio::spawn(async move {
-                    match eth_clone.get_native_balance(&addr).await {
-                        Ok(balance) => {
-                            info!("Native balance (wei): {}", balance);
-                        }
-                        Err(e) => error!("Failed to get balance: {}", e),
+            let eth_clone = eth.clone();
+            let addr = wallet_address.to_string();
+            tasks.spawn(async move {
+                match eth_clone.get_native_balance(&addr).await {
+                    Ok(balance) => {
+                        info!("Native balance (wei): {}", balance);
+                        // Convert from wei to ETH: divide by 10^18
                     }
-                });
-            }
+                    Err(e) => error!("Failed to get balance: {}", e),
+                }
+            });
         }
 
         // Check ERC20 token balance
@@ -138,36 +125,18 @@ fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, ethereum: Option<Res<Ethere
 
             let token_address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"; // USDC mainnet
 
-            #[cfg(target_arch = "wasm32")]
-            {
-                let eth_clone = eth.clone();
-                let addr = wallet_address.to_string();
-                let token = token_address.to_string();
-                wasm_bindgen_futures::spawn_local(async move {
-                    match eth_clone.get_erc20_balance(&addr, &token).await {
-                        Ok(balance) => {
-                            info!("USDC balance (smallest unit): {}", balance);
-                            // USDC has 6 decimals, divide by 10^6
-                        }
-                        Err(e) => error!("Failed to get token balance: {}", e),
-                    }
-                });
-            }
-
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                let eth_clone = eth.clone();
-                let addr = wallet_address.to_string();
-                let token = token_address.to_string();
-                tokio::spawn(async move {
-                    match eth_clone.get_erc20_balance(&addr, &token).await {
-                        Ok(balance) => {
-                            info!("USDC balance (smallest unit): {}", balance);
-                        }
-                        Err(e) => error!("Failed to get token balance: {}", e),
+            let eth_clone = eth.clone();
+            let addr = wallet_address.to_string();
+            let token = token_address.to_string();
+            tasks.spawn(async move {
+                match eth_clone.get_erc20_balance(&addr, &token).await {
+                    Ok(balance) => {
+                        info!("USDC balance (smallest unit): {}", balance);
+                        // USDC has 6 decimals, divide by 10^6
                     }
-                });
-            }
+                    Err(e) => error!("Failed to get token balance: {}", e),
+                }
+            });
         }
 
         // Check ERC20 allowance
@@ -177,43 +146,21 @@ fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, ethereum: Option<Res<Ethere
             let token_address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"; // USDC
             let spender_address = "0x0000000000000000000000000000000000000000"; // Replace with contract
 
-            #[cfg(target_arch = "wasm32")]
-            {
-                let eth_clone = eth.clone();
-                let owner = wallet_address.to_string();
-                let token = token_address.to_string();
-                let spender = spender_address.to_string();
-                wasm_bindgen_futures::spawn_local(async move {
-                    match eth_clone
-                        .get_erc20_allowance(&token, &owner, &spender)
-                        .await
-                    {
-                        Ok(allowance) => {
-                            info!("Token allowance: {}", allowance);
-                        }
-                        Err(e) => error!("Failed to get allowance: {}", e),
-                    }
-                });
-            }
-
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                let eth_clone = eth.clone();
-                let owner = wallet_address.to_string();
-                let token = token_address.to_string();
-                let spender = spender_address.to_string();
-                tokio::spawn(async move {
-                    match eth_clone
-                        .get_erc20_allowance(&token, &owner, &spender)
-                        .await
-                    {
-                        Ok(allowance) => {
-                            info!("Token allowance: {}", allowance);
-                        }
-                        Err(e) => error!("Failed to get allowance: {}", e),
+            let eth_clone = eth.clone();
+            let owner = wallet_address.to_string();
+            let token = token_address.to_string();
+            let spender = spender_address.to_string();
+            tasks.spawn(async move {
+                match eth_clone
+                    .get_erc20_allowance(&token, &owner, &spender)
+                    .await
+                {
+                    Ok(allowance) => {
+                        info!("Token allowance: {}", allowance);
                     }
-                });
-            }
+                    Err(e) => error!("Failed to get allowance: {}", e),
+                }
+            });
         }
 
         // Check MetaMask availability (WASM only)
@@ -230,41 +177,20 @@ fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, ethereum: Option<Res<Ethere
         if keyboard.just_pressed(KeyCode::KeyG) {
             info!("Checking if wallet has sufficient gas...");
 
-            #[cfg(target_arch = "wasm32")]
-            {
-                let eth_clone = eth.clone();
-                let addr = wallet_address.to_string();
-                wasm_bindgen_futures::spawn_local(async move {
-                    match eth_clone.has_sufficient_gas(&addr, 100000).await {
-                        Ok(has_gas) => {
-                            if has_gas {
-                                info!("✓ Wallet has sufficient gas for transaction");
-                            } else {
-                                warn!("✗ Insufficient gas! Please add more native tokens");
-                            }
+            let eth_clone = eth.clone();
+            let addr = wallet_address.to_string();
+            tasks.spawn(async move {
+                match eth_clone.has_sufficient_gas(&addr, 100000).await {
+                    Ok(has_gas) => {
+                        if has_gas {
+                            info!("✓ Wallet has sufficient gas for transaction");
+                        } else {
+                            warn!("✗ Insufficient gas! Please add more native tokens");
                         }
-                        Err(e) => error!("Failed to check gas: {}", e),
                     }
-                });
-            }
-
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                let eth_clone = eth.clone();
-                let addr = wallet_address.to_string();
-                tokio::spawn(async move {
-                    match eth_clone.has_sufficient_gas(&addr, 100000).await {
-                        Ok(has_gas) => {
-                            if has_gas {
-                                info!("✓ Wallet has sufficient gas for transaction");
-                            } else {
-                                warn!("✗ Insufficient gas! Please add more native tokens");
-                            }
-                        }
-                        Err(e) => error!("Failed to check gas: {}", e),
-                    }
-                });
-            }
+                    Err(e) => error!("Failed to check gas: {}", e),
+                }
+            });
         }
     } else {
         if keyboard.just_pressed(KeyCode::KeyB)
@@ -9,13 +9,15 @@
 ///
 /// This proves the SDK can do EVERYTHING the Unity SDK can do!
 use bevy::prelude::*;
-use idos_game_sdk::{IdosConfig, IdosGamesPlugin};
+use idos_game_sdk::{IdosConfig, IdosGamesPlugin, IdosTasks};
 
 #[cfg(feature = "wallet")]
 use idos_game_sdk::wallet::{BlockchainNetwork, WalletManager};
 
 #[cfg(feature = "crypto_ethereum")]
-use idos_game_sdk::crypto_ethereum::{BlockchainSettings, EthereumHandler, EthereumWalletService};
+use idos_game_sdk::crypto_ethereum::{
+    BlockchainSettings, EthereumHandler, EthereumSignerSource, EthereumWalletService, GasLimit,
+};
 
 fn main() {
     App::new()
@@ -124,6 +126,7 @@ fn setup(mut commands: Commands) {
 #[cfg(all(feature = "wallet", feature = "crypto_ethereum"))]
 fn handle_input(
     keyboard: Res<ButtonInput<KeyCode>>,
+    tasks: Res<IdosTasks>,
     mut wallet_manager: ResMut<WalletManager>,
     ethereum: Option<Res<EthereumHandler>>,
 ) {
@@ -183,14 +186,8 @@ fn handle_input(
             info!("  Address: {}", wallet_manager.wallet_address().unwrap());
             info!("  Network: {:?}", wallet_manager.current_network());
 
-            if let Some(key) = wallet_manager.private_key() {
-                info!("  Private Key: {}...", &key[..20]);
-            }
-            if let Some(seed) = wallet_manager.seed_phrase() {
-                info!(
-                    "  Has Seed Phrase: YES ({} words)",
-                    seed.split_whitespace().count()
-                );
+            if wallet_manager.has_seed_phrase() {
+                info!("  Has Seed Phrase: YES");
             }
         } else {
             warn!("No wallet connected. Create or import one first (press '1' or '2')");
@@ -211,7 +208,7 @@ fn handle_input(
                 let address = addr.clone();
 
                 #[cfg(not(target_arch = "wasm32"))]
-                tokio::spawn(async move {
+                tasks.spawn(async move {
                     match eth_clone.get_native_balance(&address).await {
                         Ok(balance_wei) => {
                             let balance_eth =
@@ -236,7 +233,7 @@ fn handle_input(
                 let address = addr.clone();
 
                 #[cfg(not(target_arch = "wasm32"))]
-                tokio::spawn(async move {
+                tasks.spawn(async move {
                     match eth_clone.get_erc20_balance(&address, token_addr).await {
                         Ok(balance) => {
                             info!("✅ Token Balance: {} (smallest unit)", balance);
@@ -251,7 +248,7 @@ fn handle_input(
 
         // Approve tokens - REAL TRANSACTION!
         if keyboard.just_pressed(KeyCode::KeyA) {
-            if let Some(private_key) = wallet_manager.private_key() {
+            if wallet_manager.is_connected() {
                 info!("\n=== Approving ERC20 Tokens ===");
                 info!("⚠️  This will sign and send a REAL transaction!");
 
@@ -266,21 +263,28 @@ fn handle_input(
                     let token = token_addr.to_string();
                     let spender_addr = spender.clone();
                     let amount = max_amount.to_string();
-                    let key = private_key.clone();
-                    let chain = settings.chain_id as u64;
                     let gas = settings.gas_price_gwei;
 
-                    tokio::spawn(async move {
+                    // The key never leaves the wallet module
+                    let signer = match wallet_manager.ethereum_signer(settings.chain_id as u64) {
+                        Ok(signer) => signer,
+                        Err(e) => {
+                            error!("❌ Wallet unavailable: {}", e);
+                            return;
+                        }
+                    };
+
+                    tasks.spawn(async move {
                         use idos_game_sdk::crypto_ethereum::transactions;
 
-                        match transactions::approve_erc20(
+                        match transactions::approve_erc20_with_signer(
                             &rpc,
                             &token,
                             &spender_addr,
                             &amount,
-                            &key,
-                            chain,
+                            signer,
                             gas,
+                            GasLimit::default(),
                         )
                         .await
                         {
@@ -300,7 +304,7 @@ fn handle_input(
 
         // Deposit tokens to platform pool - FULL FLOW!
         if keyboard.just_pressed(KeyCode::KeyD) {
-            if let Some(private_key) = wallet_manager.private_key() {
+            if wallet_manager.is_connected() {
                 if let Some(addr) = &wallet_address {
                     info!("\n=== Depositing Tokens to Platform Pool ===");
                     info!("⚠️  FULL TRANSACTION FLOW:");
@@ -317,15 +321,18 @@ fn handle_input(
                         let amount = 100u64; // 100 tokens (will be converted to wei)
                         let user_id = "demo_user_123".to_string();
                         let wallet_addr = addr.clone();
-                        let key = private_key.clone();
-
-                        // Create service
-                        let eth_clone = eth.clone();
-
-                        tokio::spawn(async move {
-                            let mut service = EthereumWalletService::new(eth_clone);
-                            service.set_private_key(key);
+                        // Create service signing with the in-game wallet
+                        let service = match EthereumWalletService::new(eth.clone())
+                            .with_wallet(&*wallet_manager)
+                        {
+                            Ok(service) => service,
+                            Err(e) => {
+                                error!("❌ Wallet unavailable: {}", e);
+                                return;
+                            }
+                        };
 
+                        tasks.spawn(async move {
                             match service
                                 .transfer_token_to_game(
                                     &rpc,
@@ -353,7 +360,7 @@ fn handle_input(
 
         // Transfer tokens to external address
         if keyboard.just_pressed(KeyCode::KeyX) {
-            if let Some(private_key) = wallet_manager.private_key() {
+            if wallet_manager.is_connected() {
                 if let Some(from_addr) = &wallet_address {
                     info!("\n=== Transferring Tokens to External Address ===");
                     info!("⚠️  This will send tokens to another wallet!");
@@ -366,14 +373,17 @@ fn handle_input(
                         let from = from_addr.clone();
                         let to = "0xRecipientAddress".to_string(); // Configure recipient
                         let amount = 10u64; // 10 tokens
-                        let key = private_key.clone();
-
-                        let eth_clone = eth.clone();
-
-                        tokio::spawn(async move {
-                            let mut service = EthereumWalletService::new(eth_clone);
-                            service.set_private_key(key);
+                        let service = match EthereumWalletService::new(eth.clone())
+                            .with_wallet(&*wallet_manager)
+                        {
+                            Ok(service) => service,
+                            Err(e) => {
+                                error!("❌ Wallet unavailable: {}", e);
+                                return;
+                            }
+                        };
 
+                        tasks.spawn(async move {
                             match service
                                 .transfer_token_to_external_address(
                                     &rpc,
@@ -401,7 +411,7 @@ fn handle_input(
 
         // Transfer NFT
         if keyboard.just_pressed(KeyCode::KeyN) {
-            if let Some(private_key) = wallet_manager.private_key() {
+            if wallet_manager.is_connected() {
                 if let Some(from_addr) = &wallet_address {
                     info!("\n=== Transferring NFT ===");
 
@@ -414,14 +424,17 @@ fn handle_input(
                         let to = "0xRecipientAddress".to_string();
                         let nft_id = "1".to_string(); // NFT token ID
                         let amount = 1u64;
-                        let key = private_key.clone();
-
-                        let eth_clone = eth.clone();
-
-                        tokio::spawn(async move {
-                            let mut service = EthereumWalletService::new(eth_clone);
-                            service.set_private_key(key);
+                        let service = match EthereumWalletService::new(eth.clone())
+                            .with_wallet(&*wallet_manager)
+                        {
+                            Ok(service) => service,
+                            Err(e) => {
+                                error!("❌ Wallet unavailable: {}", e);
+                                return;
+                            }
+                        };
 
+                        tasks.spawn(async move {
                             match service
                                 .transfer_nft_to_external_address(
                                     &rpc, &nft_addr, &from, &to, &nft_id, amount,
@@ -18,7 +18,7 @@
 #[tokio::main]
 async fn main() {
     use idos_game_sdk::{
-        crypto_solana::{SolanaCluster, SolanaHandler, SolanaSettings},
+        crypto_solana::{Commitment, PriorityFee, SolanaCluster, SolanaHandler, SolanaSettings},
         IdosClient, IdosConfig,
     };
 
@@ -37,8 +37,18 @@ async fn main() {
     let settings = SolanaSettings {
         cluster: SolanaCluster::Devnet,
         rpc_url: "https://api.devnet.solana.com".to_string(),
+        fallback_rpc_urls: Vec::new(),
+        rpc_routes: Default::default(),
         ws_url: None,
         program_id: String::new(),
+        address_lookup_tables: Vec::new(),
+        compute_unit_limit: None,
+        priority_fee: PriorityFee::default(),
+        max_blockhash_retries: 3,
+        nft_cache_ttl: None,
+        commitment: Commitment::Confirmed,
+        jito: None,
+        token_programs: Default::default(),
     };
 
     let solana = SolanaHandler::new(client, settings);
@@ -26,7 +26,9 @@ async fn main() -> IdosResult<()> {
     let private_key_base58 = "your_private_key_base58"; // 64-byte keypair in base58
 
     // Initialize client and handler
-    use idos_game_sdk::crypto_solana::dto::{SolanaCluster, SolanaSettings};
+    use idos_game_sdk::crypto_solana::dto::{
+        Commitment, PriorityFee, SolanaCluster, SolanaSettings,
+    };
     use idos_game_sdk::{IdosClient, IdosConfig};
 
     let config = IdosConfig {
@@ -40,8 +42,18 @@ async fn main() -> IdosResult<()> {
     let settings = SolanaSettings {
         cluster: SolanaCluster::Devnet,
         rpc_url: rpc_url.to_string(),
+        fallback_rpc_urls: Vec::new(),
+        rpc_routes: Default::default(),
         ws_url: Some("wss://api.devnet.solana.com".to_string()),
         program_id: program_id.to_string(),
+        address_lookup_tables: Vec::new(),
+        compute_unit_limit: None,
+        priority_fee: PriorityFee::default(),
+        max_blockhash_retries: 3,
+        nft_cache_ttl: None,
+        commitment: Commitment::Confirmed,
+        jito: None,
+        token_programs: Default::default(),
     };
 
     let handler = SolanaHandler::new(client, settings);
@@ -80,7 +92,7 @@ async fn main() -> IdosResult<()> {
         println!("🔗 Example 2: Get Recent Blockhash");
         println!("─────────────────────────────────");
 
-        let blockhash = get_recent_blockhash(rpc_url).await?;
+        let blockhash = get_recent_blockhash(rpc_url, Commitment::Confirmed).await?;
         println!("Recent blockhash: {}\n", blockhash);
     }
 
@@ -136,7 +148,7 @@ async fn main() -> IdosResult<()> {
         user_pubkey.copy_from_slice(&key_bytes[32..]); // Public key is second half
 
         // Get recent blockhash
-        let blockhash = get_recent_blockhash(rpc_url).await?;
+        let blockhash = get_recent_blockhash(rpc_url, Commitment::Confirmed).await?;
 
         // Create transaction builder
         let mut tx_builder = TransactionBuilder::new(user_pubkey);
@@ -6,10 +6,12 @@
 /// - Check SOL and SPL token balances
 /// - Request withdrawal signatures from backend
 use bevy::prelude::*;
-use idos_game_sdk::{IdosConfig, IdosGamesPlugin};
+use idos_game_sdk::{IdosConfig, IdosGamesPlugin, IdosTasks};
 
 #[cfg(feature = "crypto_solana")]
-use idos_game_sdk::crypto_solana::{SolanaCluster, SolanaHandler, SolanaPlugin, SolanaSettings};
+use idos_game_sdk::crypto_solana::{
+    Commitment, PriorityFee, SolanaCluster, SolanaHandler, SolanaPlugin, SolanaSettings,
+};
 
 fn main() {
     let mut app = App::new();
@@ -35,8 +37,18 @@ fn main() {
         let solana_settings = SolanaSettings {
             cluster: SolanaCluster::Devnet,
             rpc_url: "https://api.devnet.solana.com".to_string(),
+            fallback_rpc_urls: Vec::new(),
+            rpc_routes: Default::default(),
             ws_url: Some("wss://api.devnet.solana.com".to_string()),
             program_id: "YourProgramIdHere".to_string(), // Replace with your program ID
+            address_lookup_tables: Vec::new(),
+            compute_unit_limit: None,
+            priority_fee: PriorityFee::default(),
+            max_blockhash_retries: 3,
+            nft_cache_ttl: None,
+            commitment: Commitment::Confirmed,
+            jito: None,
+            token_programs: Default::default(),
         };
 
         app.add_plugins(SolanaPlugin::new(solana_settings));
@@ -78,7 +90,11 @@ fn setup(mut commands: Commands) {
 }
 
 #[cfg(feature = "crypto_solana")]
-fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, solana: Option<Res<SolanaHandler>>) {
+fn handle_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    tasks: Res<IdosTasks>,
+    solana: Option<Res<SolanaHandler>>,
+) {
     if let Some(sol) = solana {
         // Example wallet address - will be replaced by connected wallet
         let wallet_address = "11111111111111111111111111111111"; // Example address
@@ -89,7 +105,7 @@ fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, solana: Option<Res<SolanaHa
             info!("Connecting to Solana wallet...");
 
             let sol_clone = sol.clone();
-            wasm_bindgen_futures::spawn_local(async move {
+            tasks.spawn(async move {
                 match sol_clone.connect_wallet().await {
                     Ok(public_key) => {
                         info!("✓ Connected to wallet: {}", public_key);
@@ -114,25 +130,17 @@ fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, solana: Option<Res<SolanaHa
         if keyboard.just_pressed(KeyCode::KeyB) {
             info!("Checking SOL balance...");
 
-            #[cfg(target_arch = "wasm32")]
-            {
-                let sol_clone = sol.clone();
-                let addr = wallet_address.to_string();
-                wasm_bindgen_futures::spawn_local(async move {
-                    match sol_clone.get_balance(&addr).await {
-                        Ok(lamports) => {
-                            let sol_amount = SolanaHandler::lamports_to_sol(lamports);
-                            info!("SOL balance: {} ({} lamports)", sol_amount, lamports);
-                        }
-                        Err(e) => error!("Failed to get balance: {}", e),
+            let sol_clone = sol.clone();
+            let addr = wallet_address.to_string();
+            tasks.spawn(async move {
+                match sol_clone.get_balance(&addr).await {
+                    Ok(lamports) => {
+                        let sol_amount = SolanaHandler::lamports_to_sol(lamports);
+                        info!("SOL balance: {} ({} lamports)", sol_amount, lamports);
                     }
-                });
-            }
-
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                warn!("Balance check only available in WASM build");
-            }
+                    Err(e) => error!("Failed to get balance: {}", e),
+                }
+            });
         }
 
         // Check SPL token balance
@@ -142,29 +150,21 @@ fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, solana: Option<Res<SolanaHa
             // Example: USDC on devnet
             let token_mint = "Gh9ZwEmdLJ8DscKNTkTqPbNwLNNBjuSzaG9Vp2KGtKJr"; // USDC devnet
 
-            #[cfg(target_arch = "wasm32")]
-            {
-                let sol_clone = sol.clone();
-                let addr = wallet_address.to_string();
-                let mint = token_mint.to_string();
-                wasm_bindgen_futures::spawn_local(async move {
-                    match sol_clone.get_token_balance(&addr, &mint).await {
-                        Ok(token_amount) => {
-                            info!(
-                                "Token balance: {} (decimals: {})",
-                                token_amount.ui_amount_string.unwrap_or_default(),
-                                token_amount.decimals
-                            );
-                        }
-                        Err(e) => error!("Failed to get token balance: {}", e),
+            let sol_clone = sol.clone();
+            let addr = wallet_address.to_string();
+            let mint = token_mint.to_string();
+            tasks.spawn(async move {
+                match sol_clone.get_token_balance(&addr, &mint).await {
+                    Ok(token_amount) => {
+                        info!(
+                            "Token balance: {} (decimals: {})",
+                            token_amount.ui_amount_string.unwrap_or_default(),
+                            token_amount.decimals
+                        );
                     }
-                });
-            }
-
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                warn!("Token balance check only available in WASM build");
-            }
+                    Err(e) => error!("Failed to get token balance: {}", e),
+                }
+            });
         }
 
         // Request withdrawal signature
@@ -174,31 +174,23 @@ fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, solana: Option<Res<SolanaHa
             let token_mint = "Gh9ZwEmdLJ8DscKNTkTqPbNwLNNBjuSzaG9Vp2KGtKJr";
             let amount = 1000000u64; // 1 USDC (6 decimals)
 
-            #[cfg(target_arch = "wasm32")]
-            {
-                let sol_clone = sol.clone();
-                let mint = token_mint.to_string();
-                let addr = wallet_address.to_string();
-                wasm_bindgen_futures::spawn_local(async move {
-                    match sol_clone
-                        .get_withdrawal_signature(&mint, amount, &addr)
-                        .await
-                    {
-                        Ok(signature_payload) => {
-                            info!("✓ Got withdrawal signature!");
-                            info!("  Nonce: {}", signature_payload.nonce);
-                            info!("  Amount: {}", signature_payload.amount);
-                            // Now you can use this to withdraw tokens
-                        }
-                        Err(e) => error!("Failed to get withdrawal signature: {}", e),
+            let sol_clone = sol.clone();
+            let mint = token_mint.to_string();
+            let addr = wallet_address.to_string();
+            tasks.spawn(async move {
+                match sol_clone
+                    .get_withdrawal_signature(&mint, amount, &addr)
+                    .await
+                {
+                    Ok(signature_payload) => {
+                        info!("✓ Got withdrawal signature!");
+                        info!("  Nonce: {}", signature_payload.nonce);
+                        info!("  Amount: {}", signature_payload.amount);
+                        // Now you can use this to withdraw tokens
                     }
-                });
-            }
-
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                warn!("Withdrawal signature request only available in WASM build");
-            }
+                    Err(e) => error!("Failed to get withdrawal signature: {}", e),
+                }
+            });
         }
     } else {
         if keyboard.just_pressed(KeyCode::KeyC)
@@ -229,11 +229,8 @@ fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, mut wallet_manager: ResMut<
                     wallet_manager.get_display_address().unwrap()
                 );
             }
-            if let Some(seed) = wallet_manager.seed_phrase() {
-                warn!("  Seed phrase: {} (KEEP SECRET)", seed);
-            }
-            if let Some(key) = wallet_manager.private_key() {
-                warn!("  Private key: {}... (KEEP SECRET)", &key[..10]);
+            if wallet_manager.has_seed_phrase() {
+                info!("  Seed phrase: available via export_seed_phrase(password)");
             }
         } else {
             info!("No wallet connected. Create or login first.");
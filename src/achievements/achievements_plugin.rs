@@ -0,0 +1,40 @@
+/// Achievements Bevy plugin
+use super::dto::AchievementUnlocked;
+use super::handler::AchievementsHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize achievements handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = AchievementsHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<AchievementsHandler>.in_set(SessionSync),
+        );
+
+        app.add_message::<AchievementUnlocked>()
+            .add_systems(Update, forward_unlocked_achievements);
+    }
+}
+
+/// Forward unlocks recorded by the handler as messages
+fn forward_unlocked_achievements(
+    handler: Option<Res<AchievementsHandler>>,
+    mut unlocked: MessageWriter<AchievementUnlocked>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for achievement in handler.drain_unlocked() {
+        unlocked.write(achievement);
+    }
+}
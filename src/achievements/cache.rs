@@ -0,0 +1,144 @@
+/// Local achievement progress, persisted so progress made offline isn't lost
+/// Entries changed on the client stay pending until the server accepts them
+use super::dto::AchievementProgress;
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProgressCache {
+    progress: HashMap<String, AchievementProgress>,
+    /// Achievements with progress not yet sent to the server
+    pending: BTreeSet<String>,
+}
+
+impl ProgressCache {
+    /// Cache saved for `user_id`, or an empty one
+    pub fn load(storage: &Storage, user_id: &str) -> Self {
+        storage
+            .get(&Self::key(user_id))
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, storage: &Storage, user_id: &str) {
+        if let Ok(json) = serde_json::to_string(self) {
+            storage.set(&Self::key(user_id), &json).ok();
+        }
+    }
+
+    fn key(user_id: &str) -> String {
+        format!("progress_{}", user_id)
+    }
+
+    pub fn get(&self, achievement_id: &str) -> Option<&AchievementProgress> {
+        self.progress.get(achievement_id)
+    }
+
+    pub fn all(&self) -> impl Iterator<Item = &AchievementProgress> {
+        self.progress.values()
+    }
+
+    /// Add `amount` to an achievement; returns true if this unlocked it
+    pub fn increment(&mut self, achievement_id: &str, amount: u32, target: u32) -> bool {
+        let entry = self.entry(achievement_id);
+        let new_progress = entry.progress.saturating_add(amount);
+        self.set_progress(achievement_id, new_progress, target)
+    }
+
+    /// Raise progress to at least `value` (reach-value achievements); returns true if this unlocked it
+    pub fn set_progress(&mut self, achievement_id: &str, value: u32, target: u32) -> bool {
+        let entry = self.entry(achievement_id);
+        if value <= entry.progress && (entry.unlocked || value < target) {
+            return false;
+        }
+
+        entry.progress = entry.progress.max(value);
+        let unlocked_now = !entry.unlocked && entry.progress >= target;
+        entry.unlocked |= unlocked_now;
+        self.pending.insert(achievement_id.to_string());
+        unlocked_now
+    }
+
+    fn entry(&mut self, achievement_id: &str) -> &mut AchievementProgress {
+        self.progress
+            .entry(achievement_id.to_string())
+            .or_insert_with(|| AchievementProgress {
+                achievement_id: achievement_id.to_string(),
+                ..Default::default()
+            })
+    }
+
+    /// Progress waiting to be sent to the server
+    pub fn pending(&self) -> Vec<AchievementProgress> {
+        self.pending
+            .iter()
+            .filter_map(|id| self.progress.get(id).cloned())
+            .collect()
+    }
+
+    /// Apply progress from the server, keeping local gains that haven't been accepted yet
+    /// Entries the server has caught up with stop being pending
+    pub fn merge_server(&mut self, server: Vec<AchievementProgress>) {
+        for remote in server {
+            let id = remote.achievement_id.clone();
+            match self.progress.get_mut(&id) {
+                Some(local) if self.pending.contains(&id) => {
+                    if remote.progress >= local.progress && (remote.unlocked || !local.unlocked) {
+                        self.pending.remove(&id);
+                    }
+                    local.progress = local.progress.max(remote.progress);
+                    local.unlocked |= remote.unlocked;
+                    local.claimed = remote.claimed;
+                }
+                _ => {
+                    self.progress.insert(id, remote);
+                }
+            }
+        }
+    }
+
+    pub fn mark_claimed(&mut self, achievement_id: &str) {
+        if let Some(entry) = self.progress.get_mut(achievement_id) {
+            entry.claimed = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_progress_survives_sync() {
+        let mut cache = ProgressCache::default();
+        assert!(!cache.increment("win_10", 4, 10));
+        assert!(!cache.increment("win_10", 5, 10));
+        assert!(cache.increment("win_10", 3, 10));
+        assert!(!cache.increment("win_10", 1, 10));
+        assert_eq!(cache.get("win_10").unwrap().progress, 13);
+
+        // Server hasn't seen the offline wins yet
+        cache.merge_server(vec![AchievementProgress {
+            achievement_id: "win_10".to_string(),
+            progress: 2,
+            ..Default::default()
+        }]);
+        let local = cache.get("win_10").unwrap();
+        assert!(local.unlocked);
+        assert_eq!(local.progress, 13);
+        assert_eq!(cache.pending().len(), 1);
+
+        // Accepted
+        cache.merge_server(vec![AchievementProgress {
+            achievement_id: "win_10".to_string(),
+            progress: 14,
+            unlocked: true,
+            claimed: false,
+        }]);
+        assert!(cache.pending().is_empty());
+        assert_eq!(cache.get("win_10").unwrap().progress, 14);
+    }
+}
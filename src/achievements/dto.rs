@@ -0,0 +1,106 @@
+/// Data Transfer Objects for Achievements
+use bevy::prelude::Message;
+use serde::{Deserialize, Serialize};
+
+/// Achievement configured for the title
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AchievementDefinition {
+    #[serde(rename = "AchievementID")]
+    pub achievement_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    /// Progress needed to unlock; 1 for one-off achievements
+    pub target_progress: u32,
+    /// Hidden until unlocked
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub rewards: Vec<AchievementReward>,
+}
+
+/// Item or virtual currency granted when an achievement's reward is claimed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct AchievementReward {
+    #[serde(rename = "ItemID")]
+    pub item_id: String,
+    pub amount: u32,
+}
+
+/// Player's progress on one achievement
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct AchievementProgress {
+    #[serde(rename = "AchievementID")]
+    pub achievement_id: String,
+    pub progress: u32,
+    pub unlocked: bool,
+    /// Reward already claimed
+    pub claimed: bool,
+}
+
+/// Achievement definitions with the player's progress
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetAchievementsResult {
+    #[serde(default)]
+    pub achievements: Vec<AchievementDefinition>,
+    #[serde(default)]
+    pub progress: Vec<AchievementProgress>,
+}
+
+/// Request to get achievements or claim a reward
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AchievementsRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(rename = "AchievementID", skip_serializing_if = "Option::is_none")]
+    pub achievement_id: Option<String>,
+}
+
+/// Request to submit progress and unlocks recorded on the client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UpdateAchievementProgressRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    pub progress: Vec<AchievementProgress>,
+}
+
+/// Progress accepted by the server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UpdateAchievementProgressResult {
+    #[serde(default)]
+    pub progress: Vec<AchievementProgress>,
+}
+
+/// Rewards granted for a claimed achievement
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ClaimAchievementRewardResult {
+    #[serde(default)]
+    pub rewards: Vec<AchievementReward>,
+}
+
+/// An achievement reached its target (possibly while offline; synced later)
+#[derive(Message, Debug, Clone)]
+pub struct AchievementUnlocked {
+    pub achievement_id: String,
+    pub name: Option<String>,
+}
@@ -0,0 +1,255 @@
+/// Achievements handler: definitions, progress reporting and reward claims
+/// Progress is recorded locally first, so it works offline and is synced later
+use super::cache::ProgressCache;
+use super::dto::*;
+use crate::{storage::Storage, IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Resource, Clone)]
+pub struct AchievementsHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    storage: Storage,
+    definitions: Arc<Mutex<HashMap<String, AchievementDefinition>>>,
+    progress: Arc<Mutex<ProgressCache>>,
+    /// Unlocks waiting for AchievementsPlugin
+    unlocked: Arc<Mutex<Vec<AchievementUnlocked>>>,
+}
+
+impl AchievementsHandler {
+    pub fn new(client: IdosClient) -> Self {
+        let storage = Storage::cache(client.config(), "achievements_");
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            storage,
+            definitions: Arc::new(Mutex::new(HashMap::new())),
+            progress: Arc::new(Mutex::new(ProgressCache::default())),
+            unlocked: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    /// Loads the player's cached progress, including anything recorded offline
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        if let Ok(mut progress) = self.progress.lock() {
+            *progress = ProgressCache::load(&self.storage, &user_id);
+        }
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        if let Ok(mut progress) = self.progress.lock() {
+            *progress = ProgressCache::default();
+        }
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    fn request(
+        &self,
+        function_name: &str,
+        achievement_id: Option<&str>,
+    ) -> IdosResult<AchievementsRequest> {
+        Ok(AchievementsRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            achievement_id: achievement_id.map(str::to_string),
+        })
+    }
+
+    /// Fetch achievement definitions and the player's progress from the server
+    /// Local progress the server hasn't accepted yet is kept
+    pub async fn fetch_achievements(&self) -> IdosResult<GetAchievementsResult> {
+        let request = self.request("GetAchievements", None)?;
+        let endpoint = "achievements/GetAchievements";
+        let result: GetAchievementsResult = self.client.post(endpoint, &request).await?;
+
+        if let Ok(mut definitions) = self.definitions.lock() {
+            *definitions = result
+                .achievements
+                .iter()
+                .map(|a| (a.achievement_id.clone(), a.clone()))
+                .collect();
+        }
+        self.update_progress(|cache| cache.merge_server(result.progress.clone()));
+
+        Ok(result)
+    }
+
+    /// Definition fetched by `fetch_achievements`
+    pub fn definition(&self, achievement_id: &str) -> Option<AchievementDefinition> {
+        self.definitions
+            .lock()
+            .ok()
+            .and_then(|definitions| definitions.get(achievement_id).cloned())
+    }
+
+    /// Cached progress of an achievement
+    pub fn progress(&self, achievement_id: &str) -> Option<AchievementProgress> {
+        self.progress
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(achievement_id).cloned())
+    }
+
+    /// Add to an achievement's progress locally; call `sync_progress` to submit it
+    /// Returns true if this unlocked the achievement
+    pub fn increment_progress(&self, achievement_id: &str, amount: u32) -> IdosResult<bool> {
+        let target = self.target(achievement_id)?;
+        let unlocked =
+            self.update_progress(|cache| cache.increment(achievement_id, amount, target));
+        self.on_progress(achievement_id, unlocked);
+        Ok(unlocked)
+    }
+
+    /// Raise progress to `value` (e.g. highest level reached); never lowers it
+    pub fn set_progress(&self, achievement_id: &str, value: u32) -> IdosResult<bool> {
+        let target = self.target(achievement_id)?;
+        let unlocked =
+            self.update_progress(|cache| cache.set_progress(achievement_id, value, target));
+        self.on_progress(achievement_id, unlocked);
+        Ok(unlocked)
+    }
+
+    /// Unlock a one-off achievement
+    pub fn unlock(&self, achievement_id: &str) -> IdosResult<bool> {
+        let target = self.target(achievement_id)?;
+        self.set_progress(achievement_id, target)
+    }
+
+    fn target(&self, achievement_id: &str) -> IdosResult<u32> {
+        self.definition(achievement_id)
+            .map(|definition| definition.target_progress.max(1))
+            .ok_or_else(|| {
+                IdosError::InvalidInput(format!(
+                    "Unknown achievement '{}' (call fetch_achievements first)",
+                    achievement_id
+                ))
+            })
+    }
+
+    fn on_progress(&self, achievement_id: &str, unlocked: bool) {
+        if !unlocked {
+            return;
+        }
+        if let Ok(mut events) = self.unlocked.lock() {
+            events.push(AchievementUnlocked {
+                achievement_id: achievement_id.to_string(),
+                name: self.definition(achievement_id).map(|d| d.name),
+            });
+        }
+    }
+
+    /// Apply a change to the progress cache and persist it
+    fn update_progress<T>(&self, change: impl FnOnce(&mut ProgressCache) -> T) -> T {
+        let mut cache = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        let result = change(&mut cache);
+        if let Some(user_id) = &self.user_id {
+            cache.save(&self.storage, user_id);
+        }
+        result
+    }
+
+    /// Submit progress and unlocks recorded locally (e.g. while offline)
+    /// Returns the number of achievements submitted; failed submissions stay pending
+    pub async fn sync_progress(&self) -> IdosResult<usize> {
+        let pending = self
+            .progress
+            .lock()
+            .map(|cache| cache.pending())
+            .unwrap_or_default();
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let request = UpdateAchievementProgressRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: "UpdateProgress".to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            progress: pending.clone(),
+        };
+        let endpoint = "achievements/UpdateProgress";
+        let result: UpdateAchievementProgressResult = self.client.post(endpoint, &request).await?;
+        self.update_progress(|cache| cache.merge_server(result.progress));
+
+        Ok(pending.len())
+    }
+
+    /// Unlocked achievements whose reward hasn't been claimed
+    pub fn claimable(&self) -> Vec<AchievementProgress> {
+        self.progress
+            .lock()
+            .map(|cache| {
+                cache
+                    .all()
+                    .filter(|p| p.unlocked && !p.claimed)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Claim an unlocked achievement's reward; pending progress is synced first
+    pub async fn claim_reward(
+        &self,
+        achievement_id: &str,
+    ) -> IdosResult<ClaimAchievementRewardResult> {
+        match self.progress(achievement_id) {
+            Some(progress) if progress.claimed => {
+                return Err(IdosError::InvalidInput(format!(
+                    "Reward for '{}' already claimed",
+                    achievement_id
+                )))
+            }
+            Some(progress) if progress.unlocked => {}
+            _ => {
+                return Err(IdosError::InvalidInput(format!(
+                    "Achievement '{}' is not unlocked",
+                    achievement_id
+                )))
+            }
+        }
+        self.sync_progress().await?;
+
+        let request = self.request("ClaimReward", Some(achievement_id))?;
+        let endpoint = "achievements/ClaimReward";
+        let result: ClaimAchievementRewardResult = self.client.post(endpoint, &request).await?;
+        self.update_progress(|cache| cache.mark_claimed(achievement_id));
+
+        Ok(result)
+    }
+
+    /// Take the unlocks recorded since the last call
+    pub fn drain_unlocked(&self) -> Vec<AchievementUnlocked> {
+        self.unlocked
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default()
+    }
+}
+
+crate::session::session_aware!(AchievementsHandler);
@@ -0,0 +1,9 @@
+/// Achievements module: definitions, progress with offline caching, and rewards
+pub mod achievements_plugin;
+pub mod cache;
+pub mod dto;
+pub mod handler;
+
+pub use achievements_plugin::AchievementsPlugin;
+pub use dto::*;
+pub use handler::AchievementsHandler;
@@ -0,0 +1,69 @@
+/// Ads Bevy plugin
+use super::dto::AdRewardGranted;
+use super::handler::AdsHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+
+pub struct AdsPlugin;
+
+impl Plugin for AdsPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize ads handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = AdsHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(PreUpdate, sync_session::<AdsHandler>.in_set(SessionSync));
+
+        app.add_message::<AdRewardGranted>()
+            .add_systems(Update, forward_ad_rewards);
+
+        #[cfg(feature = "inventory")]
+        app.add_systems(Update, credit_ad_rewards.after(forward_ad_rewards));
+    }
+}
+
+/// Forward verified rewards as messages
+fn forward_ad_rewards(
+    handler: Option<Res<AdsHandler>>,
+    mut granted: MessageWriter<AdRewardGranted>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for reward in handler.drain_granted() {
+        granted.write(reward);
+    }
+}
+
+/// Add verified rewards to the cached inventory; the backend granted them already
+#[cfg(feature = "inventory")]
+fn credit_ad_rewards(
+    inventory: Option<Res<crate::inventory::InventoryHandler>>,
+    mut granted: MessageReader<AdRewardGranted>,
+) {
+    use super::dto::AdReward;
+
+    let Some(inventory) = inventory else {
+        return;
+    };
+
+    for event in granted.read() {
+        for reward in &event.rewards {
+            match reward {
+                AdReward::Item { item_id, amount } => {
+                    inventory.credit_item(item_id, *amount);
+                }
+                AdReward::Currency {
+                    currency_id,
+                    amount,
+                } => {
+                    inventory.credit_currency(currency_id, *amount);
+                }
+            }
+        }
+    }
+}
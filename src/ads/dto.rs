@@ -0,0 +1,116 @@
+/// Data Transfer Objects for Ads
+use bevy::prelude::Message;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AdFormat {
+    Rewarded,
+    Interstitial,
+}
+
+/// Ad unit configured in the mediation dashboard
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AdPlacement {
+    pub placement_id: String,
+    pub format: AdFormat,
+}
+
+impl AdPlacement {
+    pub fn rewarded(placement_id: impl Into<String>) -> Self {
+        Self {
+            placement_id: placement_id.into(),
+            format: AdFormat::Rewarded,
+        }
+    }
+
+    pub fn interstitial(placement_id: impl Into<String>) -> Self {
+        Self {
+            placement_id: placement_id.into(),
+            format: AdFormat::Interstitial,
+        }
+    }
+}
+
+/// How the player left the ad, as reported by the mediation SDK
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdShowOutcome {
+    /// Watched to the end (rewarded) or closed normally (interstitial)
+    Completed,
+    /// Closed before the reward was earned
+    Skipped,
+    Failed(String),
+}
+
+/// Item or virtual currency granted for a rewarded ad
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "Type")]
+pub enum AdReward {
+    #[serde(rename_all = "PascalCase")]
+    Item {
+        #[serde(rename = "ItemID")]
+        item_id: String,
+        amount: i32,
+    },
+    #[serde(rename_all = "PascalCase")]
+    Currency {
+        #[serde(rename = "CurrencyID")]
+        currency_id: String,
+        amount: i32,
+    },
+}
+
+/// Request to the ads endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AdsRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(rename = "PlacementID")]
+    pub placement_id: String,
+    #[serde(rename = "AdSessionID", skip_serializing_if = "Option::is_none")]
+    pub ad_session_id: Option<String>,
+}
+
+/// Rewarded view opened on the backend before the ad is shown
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AdSession {
+    #[serde(rename = "AdSessionID")]
+    pub ad_session_id: String,
+    /// Custom data for the network's SSV callback
+    pub ssv_custom_data: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AdRewardStatus {
+    /// The network's SSV callback arrived and the rewards were granted
+    Verified,
+    /// Callback not received yet; verify again later
+    Pending,
+    /// The callback failed validation or the view was never completed
+    Rejected,
+}
+
+/// Result of checking an ad session's server-side verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AdRewardVerification {
+    #[serde(rename = "AdSessionID")]
+    pub ad_session_id: String,
+    pub status: AdRewardStatus,
+    #[serde(default)]
+    pub rewards: Vec<AdReward>,
+}
+
+/// The backend verified a rewarded view and granted its rewards to the inventory
+#[derive(Message, Debug, Clone)]
+pub struct AdRewardGranted {
+    pub placement_id: String,
+    pub ad_session_id: String,
+    pub rewards: Vec<AdReward>,
+}
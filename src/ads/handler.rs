@@ -0,0 +1,212 @@
+/// Ads handler: shows ads through an `AdProvider` and verifies rewarded views with the backend
+use super::dto::*;
+use super::provider::AdProvider;
+use crate::{IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Resource, Clone)]
+pub struct AdsHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    provider: Option<Arc<dyn AdProvider>>,
+    /// Completed rewarded views still waiting for the SSV callback: session ID -> placement ID
+    pending: Arc<Mutex<HashMap<String, String>>>,
+    /// Verified rewards waiting for AdsPlugin
+    granted: Arc<Mutex<Vec<AdRewardGranted>>>,
+}
+
+impl AdsHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            provider: None,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            granted: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.clear();
+        }
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    /// Use `provider` to load and show ads
+    pub fn set_provider(&mut self, provider: Arc<dyn AdProvider>) {
+        self.provider = Some(provider);
+    }
+
+    fn provider(&self) -> IdosResult<&Arc<dyn AdProvider>> {
+        self.provider
+            .as_ref()
+            .ok_or_else(|| IdosError::Config("No ad provider set".to_string()))
+    }
+
+    pub async fn load(&self, placement: &AdPlacement) -> IdosResult<()> {
+        self.provider()?.load(placement).await
+    }
+
+    pub fn is_loaded(&self, placement: &AdPlacement) -> bool {
+        self.provider
+            .as_ref()
+            .is_some_and(|provider| provider.is_loaded(placement))
+    }
+
+    /// Show an interstitial; nothing is granted
+    pub async fn show_interstitial(&self, placement: &AdPlacement) -> IdosResult<AdShowOutcome> {
+        if placement.format != AdFormat::Interstitial {
+            return Err(IdosError::InvalidInput(format!(
+                "{} is not an interstitial placement",
+                placement.placement_id
+            )));
+        }
+        self.provider()?.show(placement, None).await
+    }
+
+    /// Show a rewarded ad and verify the view with the backend
+    /// Rewards are only granted once the network's SSV callback reached the backend;
+    /// a `Pending` result is retried by `verify_pending_rewards`
+    pub async fn show_rewarded(
+        &self,
+        placement: &AdPlacement,
+    ) -> IdosResult<(AdShowOutcome, Option<AdRewardVerification>)> {
+        if placement.format != AdFormat::Rewarded {
+            return Err(IdosError::InvalidInput(format!(
+                "{} is not a rewarded placement",
+                placement.placement_id
+            )));
+        }
+        let provider = self.provider()?.clone();
+
+        let session: AdSession = self
+            .post("StartAdSession", &placement.placement_id, None)
+            .await?;
+        let outcome = provider
+            .show(placement, Some(&session.ssv_custom_data))
+            .await?;
+        if outcome != AdShowOutcome::Completed {
+            return Ok((outcome, None));
+        }
+
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(
+                session.ad_session_id.clone(),
+                placement.placement_id.clone(),
+            );
+        }
+        let verification = self
+            .verify_reward(&placement.placement_id, &session.ad_session_id)
+            .await?;
+        Ok((outcome, Some(verification)))
+    }
+
+    /// Ask the backend whether a rewarded view passed server-side verification
+    pub async fn verify_reward(
+        &self,
+        placement_id: &str,
+        ad_session_id: &str,
+    ) -> IdosResult<AdRewardVerification> {
+        let verification: AdRewardVerification = self
+            .post(
+                "VerifyAdReward",
+                placement_id,
+                Some(ad_session_id.to_string()),
+            )
+            .await?;
+
+        if verification.status != AdRewardStatus::Pending {
+            if let Ok(mut pending) = self.pending.lock() {
+                pending.remove(ad_session_id);
+            }
+        }
+        if verification.status == AdRewardStatus::Verified {
+            if let Ok(mut granted) = self.granted.lock() {
+                granted.push(AdRewardGranted {
+                    placement_id: placement_id.to_string(),
+                    ad_session_id: ad_session_id.to_string(),
+                    rewards: verification.rewards.clone(),
+                });
+            }
+        }
+
+        Ok(verification)
+    }
+
+    /// Re-check views whose SSV callback hadn't arrived yet
+    pub async fn verify_pending_rewards(&self) -> IdosResult<Vec<AdRewardVerification>> {
+        let pending: Vec<(String, String)> = self
+            .pending
+            .lock()
+            .map(|pending| pending.clone().into_iter().collect())
+            .unwrap_or_default();
+
+        let mut results = Vec::new();
+        for (ad_session_id, placement_id) in pending {
+            results.push(self.verify_reward(&placement_id, &ad_session_id).await?);
+        }
+        Ok(results)
+    }
+
+    pub fn has_pending_rewards(&self) -> bool {
+        self.pending
+            .lock()
+            .map(|pending| !pending.is_empty())
+            .unwrap_or(false)
+    }
+
+    async fn post<R: serde::de::DeserializeOwned>(
+        &self,
+        function_name: &str,
+        placement_id: &str,
+        ad_session_id: Option<String>,
+    ) -> IdosResult<R> {
+        let request = AdsRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            placement_id: placement_id.to_string(),
+            ad_session_id,
+        };
+
+        let endpoint = format!("ads/{}", function_name);
+        self.client.post(&endpoint, &request).await
+    }
+
+    /// Take the verified rewards collected since the last call
+    pub fn drain_granted(&self) -> Vec<AdRewardGranted> {
+        self.granted
+            .lock()
+            .map(|mut granted| std::mem::take(&mut *granted))
+            .unwrap_or_default()
+    }
+}
+
+crate::session::session_aware!(AdsHandler);
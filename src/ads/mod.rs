@@ -0,0 +1,11 @@
+/// Ads module: mediation-agnostic rewarded and interstitial ads
+/// Rewards are granted by the backend after server-side verification (SSV)
+pub mod ads_plugin;
+pub mod dto;
+pub mod handler;
+pub mod provider;
+
+pub use ads_plugin::AdsPlugin;
+pub use dto::*;
+pub use handler::AdsHandler;
+pub use provider::AdProvider;
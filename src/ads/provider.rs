@@ -0,0 +1,24 @@
+/// Hook into the ad mediation SDK (AppLovin, LevelPlay, AdMob, ...)
+use super::dto::{AdPlacement, AdShowOutcome};
+use crate::IdosResult;
+use async_trait::async_trait;
+
+/// Implemented by the game for the mediation SDK it ships with
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait AdProvider: Send + Sync {
+    /// Start loading an ad for the placement
+    async fn load(&self, placement: &AdPlacement) -> IdosResult<()>;
+
+    /// Whether an ad for the placement is loaded and can be shown
+    fn is_loaded(&self, placement: &AdPlacement) -> bool;
+
+    /// Show the ad and resolve when it is closed
+    /// For rewarded ads, pass `ssv_custom_data` to the network's server-side verification
+    /// options so the backend can match its callback to this view
+    async fn show(
+        &self,
+        placement: &AdPlacement,
+        ssv_custom_data: Option<&str>,
+    ) -> IdosResult<AdShowOutcome>;
+}
@@ -1,6 +1,8 @@
 /// Data Transfer Objects for Analytics
+use bevy::prelude::Resource;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalyticsEvent {
@@ -8,6 +10,21 @@ pub struct AnalyticsEvent {
     pub properties: HashMap<String, serde_json::Value>,
     pub timestamp: i64,
     pub session_id: String,
+    /// Who and what sent the event; None for events queued by older SDK versions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<AnalyticsContext>,
+}
+
+/// Attached to every event sent by `AnalyticsHandler`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsContext {
+    pub session_id: String,
+    /// Logged-in player, None before login
+    pub user_id: Option<String>,
+    /// Game version from `IdosConfig::app_version`
+    pub app_version: Option<String>,
+    pub sdk_version: String,
+    pub device: DeviceInfo,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,3 +41,64 @@ pub struct DeviceInfo {
     pub screen_resolution: Option<String>,
     pub language: Option<String>,
 }
+
+/// Several events sent in one request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsBatch {
+    pub events: Vec<AnalyticsEvent>,
+}
+
+/// Batching for `AnalyticsHandler::track`
+/// Insert before AnalyticsPlugin starts to override the defaults
+#[derive(Resource, Debug, Clone)]
+pub struct AnalyticsBatchSettings {
+    /// Buffered events are sent at least this often
+    pub flush_interval: Duration,
+    /// Send as soon as this many events are buffered; also the most events per request
+    pub batch_size: usize,
+}
+
+impl Default for AnalyticsBatchSettings {
+    fn default() -> Self {
+        Self {
+            flush_interval: Duration::from_secs(10),
+            batch_size: 50,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_context_is_optional() {
+        // Events persisted by older SDK versions have no context
+        let queued: AnalyticsEvent = serde_json::from_str(
+            r#"{"event_name":"a","properties":{},"timestamp":1,"session_id":"s"}"#,
+        )
+        .unwrap();
+        assert!(queued.context.is_none());
+        assert!(!serde_json::to_string(&queued).unwrap().contains("context"));
+
+        let event = AnalyticsEvent {
+            context: Some(AnalyticsContext {
+                session_id: "s".to_string(),
+                user_id: Some("player1".to_string()),
+                app_version: Some("1.4.2".to_string()),
+                sdk_version: "0.1.0".to_string(),
+                device: DeviceInfo {
+                    platform: "linux".to_string(),
+                    browser: None,
+                    os: Some("linux".to_string()),
+                    screen_resolution: None,
+                    language: None,
+                },
+            }),
+            ..queued
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["context"]["user_id"], "player1");
+        assert_eq!(json["context"]["device"]["os"], "linux");
+    }
+}
@@ -1,71 +1,177 @@
 /// Analytics handler - tracks events and user behavior
+/// Events go through a persistent queue so they survive being offline;
+/// `track` buffers gameplay events in memory and AnalyticsPlugin sends them in batches
 use super::dto::*;
-use crate::{IdosClient, IdosResult};
+use super::queue::{AnalyticsBatchItem, EventQueue, QueuedAnalytics};
+use crate::{storage::Storage, IdosClient, IdosError, IdosResult};
 use bevy::prelude::Resource;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 #[derive(Resource, Clone)]
 pub struct AnalyticsHandler {
     client: IdosClient,
     session_id: String,
+    /// Unix timestamp (seconds) the session started, for `session_end`
+    session_started_at: i64,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    app_version: Option<String>,
+    device: DeviceInfo,
+    /// Start times of levels in progress, by level name
+    level_starts: Arc<Mutex<HashMap<String, i64>>>,
     enabled: bool,
+    storage: Storage,
+    queue: Arc<Mutex<EventQueue>>,
+    /// Events from `track` not yet moved to the queue
+    buffer: Arc<Mutex<Vec<AnalyticsEvent>>>,
+    batch_settings: AnalyticsBatchSettings,
+    /// Set while a flush is running so sends don't overlap
+    flushing: Arc<AtomicBool>,
+    /// `error` properties from the client's error hook, tracked by AnalyticsPlugin
+    reported_errors: Arc<Mutex<Vec<HashMap<String, serde_json::Value>>>>,
 }
 
 impl AnalyticsHandler {
+    /// Loads events left unsent by a previous run
     pub fn new(client: IdosClient, enabled: bool) -> Self {
+        let storage = Storage::cache(client.config(), "analytics_");
+        let queue = EventQueue::load(&storage);
+        let app_version = client.config().app_version.clone();
         Self {
             client,
             session_id: Uuid::new_v4().to_string(),
+            session_started_at: chrono::Utc::now().timestamp(),
+            user_id: None,
+            session_ticket: None,
+            app_version,
+            device: Self::get_device_info(),
+            level_starts: Arc::new(Mutex::new(HashMap::new())),
             enabled,
+            storage,
+            queue: Arc::new(Mutex::new(queue)),
+            buffer: Arc::new(Mutex::new(Vec::new())),
+            batch_settings: AnalyticsBatchSettings::default(),
+            flushing: Arc::new(AtomicBool::new(false)),
+            reported_errors: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
-    /// Track a custom event
-    pub async fn track_event(
+    /// Use custom batching for `track`
+    pub fn with_batch_settings(mut self, settings: AnalyticsBatchSettings) -> Self {
+        self.batch_settings = settings;
+        self
+    }
+
+    pub fn batch_settings(&self) -> &AnalyticsBatchSettings {
+        &self.batch_settings
+    }
+
+    /// Set user authentication info (call after login)
+    /// Events from now on carry the player's ID
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+    }
+
+    /// Override the game version sent with events (defaults to `IdosConfig::app_version`)
+    pub fn set_app_version(&mut self, app_version: impl Into<String>) {
+        self.app_version = Some(app_version.into());
+    }
+
+    /// Context attached to every event
+    pub fn context(&self) -> AnalyticsContext {
+        AnalyticsContext {
+            session_id: self.session_id.clone(),
+            user_id: self.user_id.clone(),
+            app_version: self.app_version.clone(),
+            sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+            device: self.device.clone(),
+        }
+    }
+
+    fn new_event(
+        &self,
+        event_name: String,
+        properties: HashMap<String, serde_json::Value>,
+    ) -> AnalyticsEvent {
+        AnalyticsEvent {
+            event_name,
+            properties,
+            timestamp: chrono::Utc::now().timestamp(),
+            session_id: self.session_id.clone(),
+            context: Some(self.context()),
+        }
+    }
+
+    /// Record a gameplay event without sending it right away
+    /// Buffered events go out as one batched request every `flush_interval`,
+    /// or sooner once `batch_size` events have piled up
+    pub fn track(
         &self,
         event_name: impl Into<String>,
         properties: HashMap<String, serde_json::Value>,
-    ) -> IdosResult<()> {
+    ) {
         if !self.enabled {
-            return Ok(());
+            return;
         }
 
-        let event = AnalyticsEvent {
-            event_name: event_name.into(),
-            properties,
-            timestamp: chrono::Utc::now().timestamp(),
-            session_id: self.session_id.clone(),
-        };
+        let event = self.new_event(event_name.into(), properties);
+        self.buffer
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(event);
+    }
 
-        // Fire and forget - don't wait for response
-        let client = self.client.clone();
-        #[cfg(target_arch = "wasm32")]
-        {
-            wasm_bindgen_futures::spawn_local(async move {
-                let _: Result<serde_json::Value, _> = client.post("analytics/event", &event).await;
-            });
+    /// Events recorded by `track` and not yet handed to the queue
+    pub fn buffered_events(&self) -> usize {
+        self.buffer.lock().map(|buffer| buffer.len()).unwrap_or(0)
+    }
+
+    /// Whether enough events are buffered to send a full batch
+    pub fn batch_full(&self) -> bool {
+        self.buffered_events() >= self.batch_settings.batch_size
+    }
+
+    /// Move buffered events to the persistent queue and send them in the background
+    pub fn flush_buffer(&self) {
+        let events = std::mem::take(&mut *self.buffer.lock().unwrap_or_else(|e| e.into_inner()));
+        if events.is_empty() {
+            return;
         }
 
-        #[cfg(not(target_arch = "wasm32"))]
         {
-            // Try to use existing runtime, otherwise spawn thread with new runtime
-            if let Ok(handle) = tokio::runtime::Handle::try_current() {
-                handle.spawn(async move {
-                    let _: Result<serde_json::Value, _> =
-                        client.post("analytics/event", &event).await;
-                });
-            } else {
-                std::thread::spawn(move || {
-                    let rt = tokio::runtime::Runtime::new().unwrap();
-                    rt.block_on(async move {
-                        let _: Result<serde_json::Value, _> =
-                            client.post("analytics/event", &event).await;
-                    });
-                });
+            let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+            for event in events {
+                queue.push(QueuedAnalytics::Event(event));
             }
+            queue.save(&self.storage);
         }
+        self.spawn_flush();
+    }
 
+    /// Track a custom event
+    /// The event is queued and sent in the background; it's kept until the backend accepts it
+    pub async fn track_event(
+        &self,
+        event_name: impl Into<String>,
+        properties: HashMap<String, serde_json::Value>,
+    ) -> IdosResult<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let event = self.new_event(event_name.into(), properties);
+        self.enqueue(QueuedAnalytics::Event(event));
+        self.spawn_flush();
         Ok(())
     }
 
@@ -75,56 +181,197 @@ impl AnalyticsHandler {
             return Ok(());
         }
 
-        let device_info = self.get_device_info();
         let event = SessionStartEvent {
-            platform: self.get_platform_name(),
-            device_info,
+            platform: self.device.platform.clone(),
+            device_info: self.device.clone(),
         };
 
-        let client = self.client.clone();
-        #[cfg(target_arch = "wasm32")]
-        {
-            wasm_bindgen_futures::spawn_local(async move {
-                let _: Result<serde_json::Value, _> =
-                    client.post("analytics/session/start", &event).await;
-            });
+        self.enqueue(QueuedAnalytics::SessionStart(event));
+        self.spawn_flush();
+        Ok(())
+    }
+
+    /// Track `session_end` with the session length and persist every buffered event
+    /// AnalyticsPlugin calls this on `AppExit`; events that can't be sent before the game
+    /// closes go out on the next launch
+    pub fn track_session_end(&self) {
+        let duration = chrono::Utc::now().timestamp() - self.session_started_at;
+        self.track(
+            "session_end",
+            HashMap::from([("duration_secs".to_string(), duration.into())]),
+        );
+        self.flush_buffer();
+    }
+
+    /// Track `level_start`
+    pub fn track_level_start(&self, level: &str) {
+        if let Ok(mut starts) = self.level_starts.lock() {
+            starts.insert(level.to_string(), chrono::Utc::now().timestamp());
         }
+        self.track(
+            "level_start",
+            HashMap::from([("level".to_string(), level.into())]),
+        );
+    }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            // Try to use existing runtime, otherwise spawn thread with new runtime
-            if let Ok(handle) = tokio::runtime::Handle::try_current() {
-                handle.spawn(async move {
-                    let _: Result<serde_json::Value, _> =
-                        client.post("analytics/session/start", &event).await;
-                });
-            } else {
-                std::thread::spawn(move || {
-                    let rt = tokio::runtime::Runtime::new().unwrap();
-                    rt.block_on(async move {
-                        let _: Result<serde_json::Value, _> =
-                            client.post("analytics/session/start", &event).await;
-                    });
-                });
+    /// Track `level_complete`, with the time since `track_level_start` for the same level
+    pub fn track_level_complete(&self, level: &str, score: Option<i64>) {
+        let mut properties = HashMap::from([("level".to_string(), level.into())]);
+        if let Some(score) = score {
+            properties.insert("score".to_string(), score.into());
+        }
+        let started = self
+            .level_starts
+            .lock()
+            .ok()
+            .and_then(|mut starts| starts.remove(level));
+        if let Some(started) = started {
+            let duration = chrono::Utc::now().timestamp() - started;
+            properties.insert("duration_secs".to_string(), duration.into());
+        }
+        self.track("level_complete", properties);
+    }
+
+    /// Track `purchase` (AnalyticsPlugin does this for verified store purchases)
+    pub fn track_purchase(&self, product_id: &str, store: &str, transaction_id: Option<&str>) {
+        let mut properties = HashMap::from([
+            ("product_id".to_string(), product_id.into()),
+            ("store".to_string(), store.into()),
+        ]);
+        if let Some(transaction_id) = transaction_id {
+            properties.insert("transaction_id".to_string(), transaction_id.into());
+        }
+        self.track("purchase", properties);
+    }
+
+    /// Track `error` for an SDK call that failed
+    /// `operation` names what was attempted ("login", "buy_item", ...)
+    pub fn track_error(&self, operation: &str, error: &IdosError) {
+        self.track("error", error_properties(operation, error));
+    }
+
+    /// Hook for `IdosClient::set_error_hook` that turns failed SDK calls into `error` events
+    /// Events are tracked by `track_reported_errors`, so they carry the current context;
+    /// failures of analytics requests themselves are skipped
+    pub fn error_hook(&self) -> crate::ErrorHook {
+        let reported_errors = self.reported_errors.clone();
+        Arc::new(move |operation: &str, error: &IdosError| {
+            if operation.starts_with("analytics/") {
+                return;
             }
+            reported_errors
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(error_properties(operation, error));
+        })
+    }
+
+    /// Track the errors reported through `error_hook` since the last call
+    pub fn track_reported_errors(&self) {
+        let reported = std::mem::take(
+            &mut *self
+                .reported_errors
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()),
+        );
+        for properties in reported {
+            self.track("error", properties);
         }
+    }
 
-        Ok(())
+    fn enqueue(&self, event: QueuedAnalytics) {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        queue.push(event);
+        queue.save(&self.storage);
     }
 
-    fn get_platform_name(&self) -> String {
-        #[cfg(target_arch = "wasm32")]
-        {
-            "web".to_string()
+    /// Events waiting to be sent
+    pub fn queued_events(&self) -> usize {
+        self.queue.lock().map(|queue| queue.len()).unwrap_or(0)
+    }
+
+    /// Whether queued events can be sent now (not backing off after a failure)
+    pub fn flush_due(&self) -> bool {
+        let now = chrono::Utc::now().timestamp();
+        self.queue
+            .lock()
+            .map(|queue| queue.is_due(now))
+            .unwrap_or(false)
+    }
+
+    /// Send queued events in batches until the queue is empty or a send fails
+    /// A failure schedules a retry with exponential backoff and returns the error
+    pub async fn flush(&self) -> IdosResult<()> {
+        if self.flushing.swap(true, Ordering::AcqRel) {
+            return Ok(());
         }
+        let result = self.flush_batches().await;
+        self.flushing.store(false, Ordering::Release);
+        result
+    }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            std::env::consts::OS.to_string()
+    async fn flush_batches(&self) -> IdosResult<()> {
+        loop {
+            let now = chrono::Utc::now().timestamp();
+            let batch = {
+                let queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+                if !queue.is_due(now) {
+                    return Ok(());
+                }
+                queue.next_batch(self.batch_settings.batch_size.max(1))
+            };
+            let Some((batch, last_seq)) = batch else {
+                return Ok(());
+            };
+
+            let sent = self.send(&batch).await;
+
+            let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+            match sent {
+                Ok(()) => queue.ack(last_seq),
+                Err(err) => {
+                    let delay = queue.record_failure(chrono::Utc::now().timestamp());
+                    queue.save(&self.storage);
+                    if self.client.config().debug {
+                        bevy::log::debug!(
+                            "Analytics send failed, {} events queued, retrying in {}s: {}",
+                            queue.len(),
+                            delay,
+                            err
+                        );
+                    }
+                    return Err(err);
+                }
+            }
+            queue.save(&self.storage);
         }
     }
 
-    fn get_device_info(&self) -> DeviceInfo {
+    async fn send(&self, batch: &AnalyticsBatchItem) -> IdosResult<()> {
+        match batch {
+            AnalyticsBatchItem::SessionStart(event) => {
+                let _: serde_json::Value =
+                    self.client.post("analytics/session/start", event).await?;
+            }
+            AnalyticsBatchItem::Events(events) => {
+                let batch = AnalyticsBatch {
+                    events: events.clone(),
+                };
+                let _: serde_json::Value = self.client.post("analytics/events", &batch).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush in the background
+    pub fn spawn_flush(&self) {
+        let h = self.clone();
+        crate::tasks::spawn(async move {
+            h.flush().await.ok();
+        });
+    }
+
+    fn get_device_info() -> DeviceInfo {
         #[cfg(target_arch = "wasm32")]
         {
             use web_sys::window;
@@ -168,3 +415,17 @@ impl AnalyticsHandler {
         }
     }
 }
+
+/// Properties of an `error` event
+fn error_properties(operation: &str, error: &IdosError) -> HashMap<String, serde_json::Value> {
+    let mut properties = HashMap::from([
+        ("operation".to_string(), operation.into()),
+        ("message".to_string(), error.to_string().into()),
+    ]);
+    if let Some(code) = error.api_code() {
+        properties.insert("code".to_string(), format!("{:?}", code).into());
+    }
+    properties
+}
+
+crate::session::session_aware!(AnalyticsHandler);
@@ -1,17 +1,52 @@
 /// Analytics module - track events and user behavior
 pub mod dto;
 pub mod handler;
+pub mod queue;
 
+use crate::session::{sync_session, SessionSync};
 use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
 use handler::AnalyticsHandler;
+use std::time::Duration;
 
 pub use dto::*;
+pub use queue::EventQueue;
+
+/// How often queued events are retried; the queue's own backoff decides whether to send
+const RETRY_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Time since `track` events were last flushed
+#[derive(Default)]
+struct SinceFlush(Duration);
 
 pub struct AnalyticsPlugin;
 
 impl Plugin for AnalyticsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_analytics);
+        app.init_resource::<AnalyticsBatchSettings>()
+            .add_systems(Startup, setup_analytics)
+            .add_systems(FixedUpdate, flush_tracked_events)
+            .add_systems(
+                Update,
+                retry_queued_events.run_if(on_timer(RETRY_CHECK_INTERVAL)),
+            )
+            .add_systems(Last, track_session_end);
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<AnalyticsHandler>.in_set(SessionSync),
+        );
+
+        // Standard events from other SDK modules
+        #[cfg(feature = "iap")]
+        app.add_message::<crate::iap::PurchaseVerified>()
+            .add_systems(Update, track_verified_purchases);
+
+        app.add_systems(Update, track_reported_errors);
+
+        #[cfg(any(feature = "crypto_ethereum", feature = "crypto_solana"))]
+        app.add_message::<crate::transaction_history::TransactionStatusChanged>()
+            .add_systems(Update, track_failed_transactions);
     }
 }
 
@@ -19,35 +54,111 @@ fn setup_analytics(
     mut commands: Commands,
     client: Res<crate::IdosClient>,
     config: Res<crate::IdosConfig>,
+    batch_settings: Res<AnalyticsBatchSettings>,
 ) {
-    let handler = AnalyticsHandler::new(client.clone(), config.enable_analytics);
+    let handler = AnalyticsHandler::new(client.clone(), config.enable_analytics)
+        .with_batch_settings(batch_settings.clone());
+
+    // Every handler shares the client, so this covers failed requests from all modules
+    client.set_error_hook(Some(handler.error_hook()));
 
     // Track session start
-    #[cfg(target_arch = "wasm32")]
-    {
-        let h = handler.clone();
-        wasm_bindgen_futures::spawn_local(async move {
-            h.track_session_start().await.ok();
-        });
-    }
-
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        let h = handler.clone();
-        // Try to use existing runtime, otherwise spawn thread with new runtime
-        if let Ok(handle) = tokio::runtime::Handle::try_current() {
-            handle.spawn(async move {
-                h.track_session_start().await.ok();
-            });
-        } else {
-            std::thread::spawn(move || {
-                let rt = tokio::runtime::Runtime::new().unwrap();
-                rt.block_on(async move {
-                    h.track_session_start().await.ok();
-                });
-            });
+    let h = handler.clone();
+    crate::tasks::spawn(async move {
+        h.track_session_start().await.ok();
+    });
+
+    commands.insert_resource(handler);
+}
+
+/// Send `track` events once the interval has passed or a batch is full
+fn flush_tracked_events(
+    handler: Option<Res<AnalyticsHandler>>,
+    time: Res<Time>,
+    mut since_flush: Local<SinceFlush>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    since_flush.0 += time.delta();
+    if since_flush.0 >= handler.batch_settings().flush_interval || handler.batch_full() {
+        since_flush.0 = Duration::ZERO;
+        handler.flush_buffer();
+    }
+}
+
+/// Send events queued while offline once the backoff has passed
+fn retry_queued_events(handler: Option<Res<AnalyticsHandler>>) {
+    if let Some(handler) = handler {
+        if handler.flush_due() {
+            handler.spawn_flush();
         }
     }
+}
 
-    commands.insert_resource(handler);
+/// Track `session_end` when the app exits
+fn track_session_end(handler: Option<Res<AnalyticsHandler>>, mut exits: MessageReader<AppExit>) {
+    if exits.read().last().is_none() {
+        return;
+    }
+    if let Some(handler) = handler {
+        handler.track_session_end();
+    }
+}
+
+/// Track `purchase` for store purchases the backend verified
+#[cfg(feature = "iap")]
+fn track_verified_purchases(
+    handler: Option<Res<AnalyticsHandler>>,
+    mut verified: MessageReader<crate::iap::PurchaseVerified>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for purchase in verified.read() {
+        let store = serde_json::to_value(purchase.store)
+            .ok()
+            .and_then(|store| store.as_str().map(str::to_string))
+            .unwrap_or_default();
+        handler.track_purchase(
+            &purchase.product_id,
+            &store,
+            purchase.transaction_id.as_deref(),
+        );
+    }
+}
+
+/// Track `error` for failed SDK calls reported to the client's error hook
+fn track_reported_errors(handler: Option<Res<AnalyticsHandler>>) {
+    if let Some(handler) = handler {
+        handler.track_reported_errors();
+    }
+}
+
+/// Track `error` for Ethereum and Solana transactions that reverted or never landed
+#[cfg(any(feature = "crypto_ethereum", feature = "crypto_solana"))]
+fn track_failed_transactions(
+    handler: Option<Res<AnalyticsHandler>>,
+    mut changed: MessageReader<crate::transaction_history::TransactionStatusChanged>,
+) {
+    use crate::transaction_history::TransactionStatus;
+
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for change in changed.read() {
+        let tx = &change.transaction;
+        if tx.status != TransactionStatus::Failed
+            || change.previous == Some(TransactionStatus::Failed)
+        {
+            continue;
+        }
+        let error = crate::IdosError::TransactionReverted(
+            tx.error.clone().unwrap_or_else(|| tx.id.clone()),
+        );
+        handler.track_error(tx.label.as_deref().unwrap_or("transaction"), &error);
+    }
 }
@@ -0,0 +1,268 @@
+/// Offline analytics queue, persisted so events survive lost connections and restarts
+/// Failed sends back off exponentially; the queue drops its oldest events when full
+use super::dto::{AnalyticsEvent, SessionStartEvent};
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Events kept while offline; older ones are dropped first
+const MAX_QUEUED_EVENTS: usize = 1000;
+/// First retry delay after a failed send
+const BASE_BACKOFF_SECONDS: i64 = 2;
+/// Retry delay never grows beyond this
+const MAX_BACKOFF_SECONDS: i64 = 300;
+const STORAGE_KEY: &str = "queue";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "Kind")]
+pub enum QueuedAnalytics {
+    SessionStart(SessionStartEvent),
+    Event(AnalyticsEvent),
+}
+
+/// Queued entry with its sequence id
+/// Ids aren't persisted: `load` numbers entries again, so the stored format is unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QueuedEntry {
+    #[serde(skip)]
+    seq: u64,
+    #[serde(flatten)]
+    item: QueuedAnalytics,
+}
+
+/// What to send next, in queue order
+#[derive(Debug, Clone)]
+pub enum AnalyticsBatchItem {
+    SessionStart(SessionStartEvent),
+    Events(Vec<AnalyticsEvent>),
+}
+
+impl AnalyticsBatchItem {
+    /// Number of queued entries this covers
+    pub fn len(&self) -> usize {
+        match self {
+            AnalyticsBatchItem::SessionStart(_) => 1,
+            AnalyticsBatchItem::Events(events) => events.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventQueue {
+    events: VecDeque<QueuedEntry>,
+    /// Sequence id of the next pushed entry
+    #[serde(skip)]
+    next_seq: u64,
+    /// Consecutive failed sends
+    failures: u32,
+    /// Unix seconds before which no send is attempted
+    retry_at: i64,
+}
+
+impl EventQueue {
+    /// Queue saved by a previous run, or an empty one
+    pub fn load(storage: &Storage) -> Self {
+        let mut queue: Self = storage
+            .get(STORAGE_KEY)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        for (seq, entry) in queue.events.iter_mut().enumerate() {
+            entry.seq = seq as u64;
+        }
+        queue.next_seq = queue.events.len() as u64;
+        queue
+    }
+
+    pub fn save(&self, storage: &Storage) {
+        if let Ok(json) = serde_json::to_string(self) {
+            storage.set(STORAGE_KEY, &json).ok();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn push(&mut self, event: QueuedAnalytics) {
+        if self.events.len() >= MAX_QUEUED_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(QueuedEntry {
+            seq: self.next_seq,
+            item: event,
+        });
+        self.next_seq += 1;
+    }
+
+    /// Whether there is something to send and the backoff has passed
+    pub fn is_due(&self, now: i64) -> bool {
+        !self.events.is_empty() && now >= self.retry_at
+    }
+
+    /// The next thing to send: a session start on its own, or up to `max_events` events
+    /// Returned with the sequence id of its last entry, which is passed to `ack` once sent
+    pub fn next_batch(&self, max_events: usize) -> Option<(AnalyticsBatchItem, u64)> {
+        let front = self.events.front()?;
+        match &front.item {
+            QueuedAnalytics::SessionStart(event) => {
+                Some((AnalyticsBatchItem::SessionStart(event.clone()), front.seq))
+            }
+            QueuedAnalytics::Event(_) => {
+                let mut last_seq = front.seq;
+                let events = self
+                    .events
+                    .iter()
+                    .take(max_events)
+                    .map_while(|entry| match &entry.item {
+                        QueuedAnalytics::Event(event) => {
+                            last_seq = entry.seq;
+                            Some(event.clone())
+                        }
+                        QueuedAnalytics::SessionStart(_) => None,
+                    })
+                    .collect();
+                Some((AnalyticsBatchItem::Events(events), last_seq))
+            }
+        }
+    }
+
+    /// Drop the delivered entries, up to and including sequence id `last_seq`
+    /// Entries pushed (or dropped because the queue was full) during the send are unaffected
+    pub fn ack(&mut self, last_seq: u64) {
+        while self
+            .events
+            .front()
+            .is_some_and(|entry| entry.seq <= last_seq)
+        {
+            self.events.pop_front();
+        }
+        self.failures = 0;
+        self.retry_at = 0;
+    }
+
+    /// Schedule the next attempt with exponential backoff; returns the delay in seconds
+    pub fn record_failure(&mut self, now: i64) -> i64 {
+        let delay = BASE_BACKOFF_SECONDS
+            .saturating_mul(1 << self.failures.min(16))
+            .min(MAX_BACKOFF_SECONDS);
+        self.failures = self.failures.saturating_add(1);
+        self.retry_at = now + delay;
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn event(name: &str) -> QueuedAnalytics {
+        QueuedAnalytics::Event(AnalyticsEvent {
+            event_name: name.to_string(),
+            properties: HashMap::new(),
+            timestamp: 0,
+            session_id: "s".to_string(),
+            context: None,
+        })
+    }
+
+    #[test]
+    fn test_batches_and_backoff() {
+        let mut queue = EventQueue::default();
+        queue.push(event("a"));
+        queue.push(event("b"));
+        queue.push(QueuedAnalytics::SessionStart(SessionStartEvent {
+            platform: "linux".to_string(),
+            device_info: crate::analytics::DeviceInfo {
+                platform: "linux".to_string(),
+                browser: None,
+                os: None,
+                screen_resolution: None,
+                language: None,
+            },
+        }));
+        queue.push(event("c"));
+
+        // Batches stop at a session start
+        let (batch, last_seq) = queue.next_batch(50).unwrap();
+        assert_eq!(batch.len(), 2);
+
+        assert_eq!(queue.record_failure(100), 2);
+        assert_eq!(queue.record_failure(100), 4);
+        assert!(!queue.is_due(103));
+        assert!(queue.is_due(104));
+        for _ in 0..20 {
+            queue.record_failure(100);
+        }
+        assert!(queue.is_due(100 + MAX_BACKOFF_SECONDS));
+
+        queue.ack(last_seq);
+        assert!(queue.is_due(0));
+        assert!(matches!(
+            queue.next_batch(50),
+            Some((AnalyticsBatchItem::SessionStart(_), _))
+        ));
+    }
+
+    #[test]
+    fn test_queue_drops_oldest_when_full() {
+        let mut queue = EventQueue::default();
+        for i in 0..MAX_QUEUED_EVENTS + 5 {
+            queue.push(event(&i.to_string()));
+        }
+        assert_eq!(queue.len(), MAX_QUEUED_EVENTS);
+        let Some((AnalyticsBatchItem::Events(events), _)) = queue.next_batch(1) else {
+            panic!("expected events");
+        };
+        assert_eq!(events[0].event_name, "5");
+    }
+
+    fn event_names(queue: &EventQueue) -> Vec<String> {
+        queue
+            .events
+            .iter()
+            .map(|entry| match &entry.item {
+                QueuedAnalytics::Event(event) => event.event_name.clone(),
+                QueuedAnalytics::SessionStart(_) => "session".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ack_after_queue_filled_during_send() {
+        let mut queue = EventQueue::default();
+        for i in 0..MAX_QUEUED_EVENTS {
+            queue.push(event(&i.to_string()));
+        }
+        let (batch, last_seq) = queue.next_batch(10).unwrap();
+        assert_eq!(batch.len(), 10);
+
+        // Pushed while the batch is being sent: "0" to "2" are dropped to make room
+        for name in ["new-1", "new-2", "new-3"] {
+            queue.push(event(name));
+        }
+        queue.ack(last_seq);
+
+        // Exactly the rest of the sent batch ("3" to "9") is removed, nothing unsent
+        let names = event_names(&queue);
+        assert_eq!(names.len(), MAX_QUEUED_EVENTS - 7);
+        assert_eq!(names[0], "10");
+        assert_eq!(names[names.len() - 3..], ["new-1", "new-2", "new-3"]);
+
+        // Sequence ids stay out of the stored queue
+        let json = serde_json::to_string(&queue).unwrap();
+        assert!(!json.contains("seq"));
+        let stored: EventQueue = serde_json::from_str(&json).unwrap();
+        assert_eq!(event_names(&stored), names);
+    }
+}
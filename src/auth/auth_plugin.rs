@@ -1,13 +1,165 @@
-use super::dto::AuthEvent;
+use super::dto::{
+    AuthEvent, LoginFailed, LoginRequested, LoginSucceeded, LogoutRequested, SessionExpired,
+    SessionRefreshed,
+};
+use super::handler::AuthHandler;
 use super::helper::setup_auth;
+use crate::session::{AuthSession, SessionSync};
+use crate::IdosError;
 /// Authentication plugin
 use bevy::prelude::*;
 
+/// Refresh this long before the session expires
+const REFRESH_MARGIN_SECONDS: i64 = 300;
+/// Wait before retrying a failed refresh that still has time left
+const REFRESH_RETRY_SECONDS: i64 = 30;
+/// Shortest wait between successful refreshes, for tickets that live less than the margin
+const MIN_REFRESH_INTERVAL_SECONDS: i64 = 10;
+
 pub struct AuthPlugin;
 
 impl Plugin for AuthPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<AuthEvent>()
-            .add_systems(Startup, setup_auth);
+            .add_message::<LoginRequested>()
+            .add_message::<LoginSucceeded>()
+            .add_message::<LoginFailed>()
+            .add_message::<LogoutRequested>()
+            .add_message::<SessionRefreshed>()
+            .add_message::<SessionExpired>()
+            .init_resource::<AuthSession>()
+            .add_systems(Startup, setup_auth)
+            // Results land before handlers copy the session, so they are ready for `LoginSucceeded`
+            .add_systems(
+                PreUpdate,
+                (forward_login_results, keep_session_fresh)
+                    .chain()
+                    .before(SessionSync),
+            )
+            .add_systems(
+                Update,
+                (handle_login_requests, handle_logout_requests).chain(),
+            );
+    }
+}
+
+/// Start a background login for each `LoginRequested`
+fn handle_login_requests(
+    auth: Option<Res<AuthHandler>>,
+    mut requests: MessageReader<LoginRequested>,
+    mut failed: MessageWriter<LoginFailed>,
+    mut auth_events: MessageWriter<AuthEvent>,
+) {
+    for LoginRequested(method) in requests.read() {
+        match &auth {
+            Some(auth) => auth.spawn_login(method.clone()),
+            None => {
+                let err = IdosError::Config("AuthHandler is not initialized".to_string());
+                auth_events.write(AuthEvent::LoginFailed(err.to_string()));
+                failed.write(LoginFailed(err));
+            }
+        }
+    }
+}
+
+/// Write finished logins back as messages
+fn forward_login_results(
+    auth: Option<Res<AuthHandler>>,
+    mut session: ResMut<AuthSession>,
+    mut succeeded: MessageWriter<LoginSucceeded>,
+    mut failed: MessageWriter<LoginFailed>,
+    mut auth_events: MessageWriter<AuthEvent>,
+) {
+    let Some(auth) = auth else {
+        return;
+    };
+
+    for result in auth.drain_login_results() {
+        match result {
+            Ok(response) => {
+                session.set_if_neq(auth.session());
+                if let Ok(user) = response.to_user() {
+                    auth_events.write(AuthEvent::LoginSuccess(user));
+                }
+                succeeded.write(LoginSucceeded(response));
+            }
+            Err(err) => {
+                auth_events.write(AuthEvent::LoginFailed(err.to_string()));
+                failed.write(LoginFailed(err));
+            }
+        }
+    }
+}
+
+fn handle_logout_requests(
+    auth: Option<Res<AuthHandler>>,
+    mut session: ResMut<AuthSession>,
+    mut requests: MessageReader<LogoutRequested>,
+    mut auth_events: MessageWriter<AuthEvent>,
+) {
+    let Some(auth) = auth else {
+        return;
+    };
+
+    for _ in requests.read() {
+        match auth.logout() {
+            Ok(()) => {
+                session.set_if_neq(AuthSession::default());
+                auth_events.write(AuthEvent::LogoutSuccess);
+            }
+            Err(err) => error!("Logout failed: {err}"),
+        }
+    }
+}
+
+/// Refresh the session ticket shortly before `SessionExpiration` and report the outcome
+/// A failed refresh is retried while time is left; past expiry the player is logged out
+/// Tickets shorter than the margin are refreshed halfway through their lifetime
+fn keep_session_fresh(
+    auth: Option<Res<AuthHandler>>,
+    mut session: ResMut<AuthSession>,
+    mut retry_at: Local<i64>,
+    mut refreshed: MessageWriter<SessionRefreshed>,
+    mut expired: MessageWriter<SessionExpired>,
+    mut auth_events: MessageWriter<AuthEvent>,
+) {
+    let Some(auth) = auth else {
+        return;
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    for result in auth.drain_refresh_results() {
+        match result {
+            Ok(response) => {
+                // A ticket shorter than the margin is already due again; wait half its
+                // lifetime instead of refreshing every frame
+                let remaining = auth.session_expires_at().map_or(0, |at| at - now);
+                *retry_at = now + (remaining / 2).max(MIN_REFRESH_INTERVAL_SECONDS);
+                session.set_if_neq(auth.session());
+                auth_events.write(AuthEvent::TokenRefreshed);
+                refreshed.write(SessionRefreshed {
+                    expires_at: response.session_expires_at(),
+                });
+            }
+            Err(err) if auth.session_expires_at().is_none_or(|at| now >= at) => {
+                warn!("Session expired, refresh failed: {err}");
+                auth.logout().ok();
+                session.set_if_neq(AuthSession::default());
+                auth_events.write(AuthEvent::LogoutSuccess);
+                expired.write(SessionExpired {
+                    reason: err.to_string(),
+                });
+            }
+            Err(err) => {
+                warn!("Session refresh failed, retrying in {REFRESH_RETRY_SECONDS}s: {err}");
+                *retry_at = now + REFRESH_RETRY_SECONDS;
+            }
+        }
+    }
+
+    if let Some(expires_at) = auth.session_expires_at() {
+        if now >= *retry_at && expires_at - now <= REFRESH_MARGIN_SECONDS {
+            auth.spawn_refresh();
+        }
     }
 }
@@ -96,6 +96,18 @@ impl AuthResponse {
         })
     }
 
+    /// `SessionExpiration` as unix seconds
+    /// Accepts RFC 3339 and the backend's zone-less UTC format
+    pub fn session_expires_at(&self) -> Option<i64> {
+        let raw = self.session_expiration.as_deref()?.trim();
+        if let Ok(at) = chrono::DateTime::parse_from_rfc3339(raw) {
+            return Some(at.timestamp());
+        }
+        chrono::NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f")
+            .ok()
+            .map(|at| at.and_utc().timestamp())
+    }
+
     pub fn refresh_token(&self) -> Option<String> {
         self.additional_fields
             .get("RefreshToken")
@@ -103,6 +115,16 @@ impl AuthResponse {
             .map(|value| value.to_owned())
     }
 
+    /// Base64 key for signing leaderboard submissions, when the title requires signed scores
+    pub fn statistic_signing_key(&self) -> Option<String> {
+        self.auth_context
+            .as_ref()
+            .and_then(|ctx| ctx.additional_fields.get("StatisticSigningKey"))
+            .or_else(|| self.additional_fields.get("StatisticSigningKey"))
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_owned())
+    }
+
     pub fn client_session_ticket(&self) -> Option<String> {
         self.auth_context
             .as_ref()
@@ -221,6 +243,29 @@ pub enum WalletChain {
     BinanceSmartChain,
 }
 
+impl WalletChain {
+    /// Chain of an EVM chain ID (Ethereum for unknown chains and testnets)
+    pub fn from_chain_id(chain_id: u64) -> Self {
+        match chain_id {
+            137 => WalletChain::Polygon,
+            56 => WalletChain::BinanceSmartChain,
+            _ => WalletChain::Ethereum,
+        }
+    }
+}
+
+/// Request for the one-time nonce signed in a wallet login message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletNonceRequest {
+    pub wallet_address: String,
+    pub chain: WalletChain,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletNonceResponse {
+    pub nonce: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerProfile {
     #[serde(rename = "PlayerId")]
@@ -231,6 +276,66 @@ pub struct PlayerProfile {
     pub additional_fields: HashMap<String, Value>,
 }
 
+/// How to log in, for `LoginRequested`
+#[derive(Debug, Clone)]
+pub enum LoginMethod {
+    Email {
+        email: String,
+        password: String,
+    },
+    Register {
+        email: String,
+        password: String,
+        username: String,
+    },
+    Guest,
+    Social {
+        provider: SocialProvider,
+        access_token: String,
+    },
+    Wallet {
+        wallet_address: String,
+        signature: String,
+        message: String,
+        chain: WalletChain,
+    },
+    /// Sign-In With Ethereum, see `AuthHandler::login_siwe`
+    #[cfg(feature = "crypto_ethereum")]
+    Siwe {
+        signer: crate::crypto_ethereum::SiweSigner,
+        domain: String,
+        uri: String,
+        statement: Option<String>,
+    },
+}
+
+/// Ask AuthPlugin to log in; the result arrives as `LoginSucceeded` or `LoginFailed`
+#[derive(Message, Debug, Clone)]
+pub struct LoginRequested(pub LoginMethod);
+
+#[derive(Message, Debug, Clone)]
+pub struct LoginSucceeded(pub AuthResponse);
+
+#[derive(Message, Debug)]
+pub struct LoginFailed(pub IdosError);
+
+/// Ask AuthPlugin to log out; answered with `AuthEvent::LogoutSuccess`
+#[derive(Message, Debug, Clone)]
+pub struct LogoutRequested;
+
+/// The session ticket was renewed before it expired
+#[derive(Message, Debug, Clone)]
+pub struct SessionRefreshed {
+    /// New expiry in unix seconds, if the backend sent one
+    pub expires_at: Option<i64>,
+}
+
+/// The session ran out before it could be refreshed; the player has been logged out
+#[derive(Message, Debug, Clone)]
+pub struct SessionExpired {
+    pub reason: String,
+}
+
 /// Authentication events
 #[derive(Message, Debug)]
 pub enum AuthEvent {
@@ -239,3 +344,30 @@ pub enum AuthEvent {
     LogoutSuccess,
     TokenRefreshed,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_session_expiration_formats() {
+        let response = |expiration: &str| -> AuthResponse {
+            serde_json::from_value(json!({ "SessionExpiration": expiration })).unwrap()
+        };
+
+        assert_eq!(
+            response("2024-01-01T00:00:00Z").session_expires_at(),
+            Some(1704067200)
+        );
+        assert_eq!(
+            response("2024-01-01T02:00:00+02:00").session_expires_at(),
+            Some(1704067200)
+        );
+        assert_eq!(
+            response("2024-01-01T00:00:00.1234567").session_expires_at(),
+            Some(1704067200)
+        );
+        assert_eq!(response("soon").session_expires_at(), None);
+    }
+}
@@ -1,12 +1,19 @@
 /// Authentication handler logic
 use super::dto::*;
 use crate::storage::Storage;
-use crate::{IdosClient, IdosError, IdosResult};
+use crate::{AuthSession, IdosClient, IdosError, IdosResult};
 use bevy::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 const TOKEN_KEY: &str = "auth_token";
 const REFRESH_TOKEN_KEY: &str = "auth_refresh_token";
 const USER_KEY: &str = "auth_user";
+const SESSION_EXPIRATION_KEY: &str = "auth_session_expiration";
+
+/// How long a SIWE message stays valid after it is signed
+#[cfg(any(feature = "crypto_ethereum", feature = "crypto_solana"))]
+const WALLET_MESSAGE_TTL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
 
 #[derive(Resource, Clone)]
 pub struct AuthHandler {
@@ -14,6 +21,13 @@ pub struct AuthHandler {
     storage: Storage,
     template_title_id: String,
     title_id: String,
+    /// Results of `spawn_login` waiting for AuthPlugin
+    login_results: Arc<Mutex<Vec<IdosResult<AuthResponse>>>>,
+    /// Results of `spawn_refresh` waiting for AuthPlugin
+    refresh_results: Arc<Mutex<Vec<IdosResult<AuthResponse>>>>,
+    refreshing: Arc<AtomicBool>,
+    /// Session expiry in unix seconds, mirrored to storage
+    session_expires_at: Arc<Mutex<Option<i64>>>,
 }
 
 impl AuthHandler {
@@ -25,11 +39,22 @@ impl AuthHandler {
             IdosError::Config("Missing IDOS_TITLE_ID environment variable".to_string())
         })?;
 
+        let storage = Storage::new(storage_prefix);
+        let session_expires_at = storage
+            .get(SESSION_EXPIRATION_KEY)
+            .ok()
+            .flatten()
+            .and_then(|value| value.parse().ok());
+
         Ok(Self {
             client,
-            storage: Storage::new(storage_prefix),
+            storage,
             template_title_id,
             title_id,
+            login_results: Arc::new(Mutex::new(Vec::new())),
+            refresh_results: Arc::new(Mutex::new(Vec::new())),
+            refreshing: Arc::new(AtomicBool::new(false)),
+            session_expires_at: Arc::new(Mutex::new(session_expires_at)),
         })
     }
 
@@ -172,6 +197,149 @@ impl AuthHandler {
         Ok(response)
     }
 
+    /// Request the one-time nonce to include in a wallet login message
+    pub async fn get_wallet_nonce(
+        &self,
+        wallet_address: &str,
+        chain: WalletChain,
+    ) -> IdosResult<String> {
+        let request = WalletNonceRequest {
+            wallet_address: wallet_address.to_string(),
+            chain,
+        };
+        let response: WalletNonceResponse = self.client.post("auth/wallet/nonce", &request).await?;
+
+        Ok(response.nonce)
+    }
+
+    /// Sign-In With Ethereum (EIP-4361)
+    /// Fetches a nonce, has `signer` sign a message for `domain` and `uri`, then logs in with it
+    #[cfg(feature = "crypto_ethereum")]
+    pub async fn login_siwe(
+        &self,
+        signer: &crate::crypto_ethereum::SiweSigner,
+        domain: &str,
+        uri: &str,
+        statement: Option<&str>,
+    ) -> IdosResult<AuthResponse> {
+        let (address, chain_id) = signer.account().await?;
+        let chain = WalletChain::from_chain_id(chain_id);
+        let nonce = self.get_wallet_nonce(&address, chain.clone()).await?;
+
+        let mut message =
+            crate::crypto_ethereum::SiweMessage::new(domain, &address, uri, chain_id, &nonce)?
+                .expires_in(WALLET_MESSAGE_TTL);
+        if let Some(statement) = statement {
+            message = message.with_statement(statement);
+        }
+
+        let signature = signer.sign(&message).await?;
+        // Catches browser wallets that signed with a different account than they reported
+        message.verify(&signature)?;
+
+        self.login_wallet(
+            message.address.clone(),
+            signature,
+            message.to_string(),
+            chain,
+        )
+        .await
+    }
+
+    /// Sign in with a Solana wallet
+    /// Fetches a nonce, has `signer` sign a message for `domain` and `uri`, then logs in with it
+    #[cfg(feature = "crypto_solana")]
+    pub async fn login_solana_wallet<S: crate::crypto_solana::SolanaMessageSigner + ?Sized>(
+        &self,
+        signer: &S,
+        domain: &str,
+        uri: &str,
+        statement: Option<&str>,
+    ) -> IdosResult<AuthResponse> {
+        let address = signer.solana_address().await?;
+        let nonce = self.get_wallet_nonce(&address, WalletChain::Solana).await?;
+
+        let mut message =
+            crate::crypto_solana::SolanaSignInMessage::new(domain, &address, uri, &nonce)?
+                .expires_in(WALLET_MESSAGE_TTL);
+        if let Some(statement) = statement {
+            message = message.with_statement(statement);
+        }
+
+        let signed = signer
+            .sign_solana_message(message.to_string().as_bytes())
+            .await?;
+        // Catches browser wallets that signed with a different account than they reported
+        message.verify(&signed)?;
+
+        self.login_wallet(
+            address,
+            signed.signature,
+            message.to_string(),
+            WalletChain::Solana,
+        )
+        .await
+    }
+
+    /// Log in (or register) with any `LoginMethod`
+    pub async fn login_with(&self, method: LoginMethod) -> IdosResult<AuthResponse> {
+        match method {
+            LoginMethod::Email { email, password } => self.login(email, password).await,
+            LoginMethod::Register {
+                email,
+                password,
+                username,
+            } => self.register(email, password, username).await,
+            LoginMethod::Guest => self.login_guest().await,
+            LoginMethod::Social {
+                provider,
+                access_token,
+            } => self.login_social(provider, access_token).await,
+            LoginMethod::Wallet {
+                wallet_address,
+                signature,
+                message,
+                chain,
+            } => {
+                self.login_wallet(wallet_address, signature, message, chain)
+                    .await
+            }
+            #[cfg(feature = "crypto_ethereum")]
+            LoginMethod::Siwe {
+                signer,
+                domain,
+                uri,
+                statement,
+            } => {
+                self.login_siwe(&signer, &domain, &uri, statement.as_deref())
+                    .await
+            }
+        }
+    }
+
+    /// Run `login_with` in the background; the result is picked up with `drain_login_results`
+    pub fn spawn_login(&self, method: LoginMethod) {
+        let h = self.clone();
+        crate::tasks::spawn(async move {
+            let result = h.login_with(method).await;
+            h.push_login_result(result);
+        });
+    }
+
+    fn push_login_result(&self, result: IdosResult<AuthResponse>) {
+        if let Ok(mut results) = self.login_results.lock() {
+            results.push(result);
+        }
+    }
+
+    /// Take the background login results finished since the last call
+    pub fn drain_login_results(&self) -> Vec<IdosResult<AuthResponse>> {
+        self.login_results
+            .lock()
+            .map(|mut results| std::mem::take(&mut *results))
+            .unwrap_or_default()
+    }
+
     /// Refresh access token
     pub async fn refresh_token(&self) -> IdosResult<AuthResponse> {
         let session_ticket = self
@@ -192,11 +360,58 @@ impl AuthHandler {
         Ok(response)
     }
 
+    /// Run `refresh_token` in the background; the result is picked up with `drain_refresh_results`
+    /// Does nothing while a refresh is already running
+    pub fn spawn_refresh(&self) {
+        if self.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let h = self.clone();
+        crate::tasks::spawn(async move {
+            let result = h.refresh_token().await;
+            h.push_refresh_result(result);
+        });
+    }
+
+    fn push_refresh_result(&self, result: IdosResult<AuthResponse>) {
+        if let Ok(mut results) = self.refresh_results.lock() {
+            results.push(result);
+        }
+        self.refreshing.store(false, Ordering::Release);
+    }
+
+    /// Take the background refresh results finished since the last call
+    pub fn drain_refresh_results(&self) -> Vec<IdosResult<AuthResponse>> {
+        self.refresh_results
+            .lock()
+            .map(|mut results| std::mem::take(&mut *results))
+            .unwrap_or_default()
+    }
+
+    /// When the current session ticket expires (unix seconds), if the backend said
+    pub fn session_expires_at(&self) -> Option<i64> {
+        self.session_expires_at.lock().ok().and_then(|at| *at)
+    }
+
+    fn set_session_expires_at(&self, expires_at: Option<i64>) -> IdosResult<()> {
+        if let Ok(mut at) = self.session_expires_at.lock() {
+            *at = expires_at;
+        }
+        match expires_at {
+            Some(expires_at) => self
+                .storage
+                .set(SESSION_EXPIRATION_KEY, &expires_at.to_string()),
+            None => self.storage.remove(SESSION_EXPIRATION_KEY),
+        }
+    }
+
     /// Logout
     pub fn logout(&self) -> IdosResult<()> {
         self.storage.remove(TOKEN_KEY)?;
         self.storage.remove(REFRESH_TOKEN_KEY)?;
         self.storage.remove(USER_KEY)?;
+        self.set_session_expires_at(None)?;
         Ok(())
     }
 
@@ -217,6 +432,15 @@ impl AuthHandler {
         self.storage.get(TOKEN_KEY)
     }
 
+    /// Credentials of the logged-in player, as shared through `AuthSession`
+    pub fn session(&self) -> AuthSession {
+        let user_id = self.get_current_user().ok().flatten().map(|user| user.id);
+        match (user_id, self.get_token().ok().flatten()) {
+            (Some(user_id), Some(session_ticket)) => AuthSession::new(user_id, session_ticket),
+            _ => AuthSession::default(),
+        }
+    }
+
     /// Check if user is authenticated
     pub fn is_authenticated(&self) -> bool {
         self.get_token().ok().flatten().is_some()
@@ -257,6 +481,8 @@ impl AuthHandler {
             .unwrap_or_else(|| session_ticket.clone());
         self.storage.set(REFRESH_TOKEN_KEY, &refresh_token)?;
 
+        self.set_session_expires_at(response.session_expires_at())?;
+
         let user = response.to_user()?;
         let user_json = serde_json::to_string(&user)?;
         self.storage.set(USER_KEY, &user_json)?;
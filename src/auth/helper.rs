@@ -1,10 +1,16 @@
 use super::handler::AuthHandler;
+use crate::session::AuthSession;
 use crate::IdosClient;
 use crate::IdosConfig;
 use bevy::log::error;
 use bevy::prelude::*;
 
-pub fn setup_auth(mut commands: Commands, client: Res<IdosClient>, _config: Res<IdosConfig>) {
+pub fn setup_auth(
+    mut commands: Commands,
+    client: Res<IdosClient>,
+    _config: Res<IdosConfig>,
+    mut session: ResMut<AuthSession>,
+) {
     #[cfg(target_arch = "wasm32")]
     let storage_prefix = _config.platform.wasm.storage_prefix.clone();
 
@@ -13,6 +19,8 @@ pub fn setup_auth(mut commands: Commands, client: Res<IdosClient>, _config: Res<
 
     match AuthHandler::new(client.clone(), storage_prefix) {
         Ok(handler) => {
+            // Session stored by a previous run
+            session.set_if_neq(handler.session());
             commands.insert_resource(handler);
         }
         Err(err) => {
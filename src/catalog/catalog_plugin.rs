@@ -0,0 +1,63 @@
+/// Catalog Bevy plugin
+use super::dto::ItemPurchased;
+use super::handler::CatalogHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+
+pub struct CatalogPlugin;
+
+impl Plugin for CatalogPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize catalog handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = CatalogHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<CatalogHandler>.in_set(SessionSync),
+        );
+
+        app.add_message::<ItemPurchased>()
+            .add_systems(Update, forward_purchases);
+
+        #[cfg(feature = "inventory")]
+        app.add_systems(Update, apply_purchases.after(forward_purchases));
+    }
+}
+
+/// Forward completed purchases as messages
+fn forward_purchases(
+    handler: Option<Res<CatalogHandler>>,
+    mut purchased: MessageWriter<ItemPurchased>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for purchase in handler.drain_purchases() {
+        purchased.write(purchase);
+    }
+}
+
+/// Apply purchases to the cached inventory so it matches the server without a refetch
+#[cfg(feature = "inventory")]
+fn apply_purchases(
+    inventory: Option<Res<crate::inventory::InventoryHandler>>,
+    mut purchased: MessageReader<ItemPurchased>,
+) {
+    let Some(inventory) = inventory else {
+        return;
+    };
+
+    for purchase in purchased.read() {
+        let granted: Vec<(String, Option<String>)> = purchase
+            .granted
+            .iter()
+            .map(|item| (item.item_id.clone(), item.item_instance_id.clone()))
+            .collect();
+        inventory.record_purchase(&purchase.currency_id, purchase.price, &granted);
+    }
+}
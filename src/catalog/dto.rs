@@ -0,0 +1,248 @@
+/// Data Transfer Objects for the Catalog
+use bevy::prelude::Message;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Price of an item in one currency
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemPrice {
+    /// In-game currency, paid through `CatalogHandler::purchase_item`
+    VirtualCurrency { currency_id: String, amount: i32 },
+    /// Store currency (e.g. "USD"), paid through IAP with the item's `product_id`
+    RealMoney { currency: String, amount: f64 },
+}
+
+/// Item defined in the game's catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CatalogItem {
+    #[serde(rename = "ItemID")]
+    pub item_id: String,
+    #[serde(default)]
+    pub item_class: Option<String>,
+    #[serde(default)]
+    pub catalog_version: Option<String>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(rename = "ItemImageUrl", default)]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// currency_id -> price
+    #[serde(default)]
+    pub virtual_currency_prices: HashMap<String, i32>,
+    /// Store currency code (e.g. "USD") -> price
+    #[serde(default)]
+    pub real_currency_prices: HashMap<String, f64>,
+    /// IAP product that sells this item for real money
+    #[serde(rename = "ProductID", default)]
+    pub product_id: Option<String>,
+    /// Uses granted per purchase for consumables
+    #[serde(default)]
+    pub usage_count: Option<i32>,
+    /// Items granted along with this one (bundles)
+    #[serde(default)]
+    pub bundled_items: Vec<String>,
+    /// Currency granted along with this item (bundles, currency packs)
+    #[serde(default)]
+    pub bundled_virtual_currencies: HashMap<String, i32>,
+    #[serde(default)]
+    pub custom_data: Option<String>,
+}
+
+impl CatalogItem {
+    /// Every price of the item, virtual currencies first
+    pub fn prices(&self) -> Vec<ItemPrice> {
+        prices(&self.virtual_currency_prices, &self.real_currency_prices)
+    }
+
+    /// Price in a virtual currency, if the item can be bought with it
+    pub fn price_in(&self, currency_id: &str) -> Option<i32> {
+        self.virtual_currency_prices.get(currency_id).copied()
+    }
+}
+
+/// Item in a store, priced by the store rather than the catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StoreItem {
+    #[serde(rename = "ItemID")]
+    pub item_id: String,
+    #[serde(default)]
+    pub virtual_currency_prices: HashMap<String, i32>,
+    #[serde(default)]
+    pub real_currency_prices: HashMap<String, f64>,
+    /// Position in the store's layout
+    #[serde(default)]
+    pub display_position: Option<i32>,
+}
+
+impl StoreItem {
+    pub fn prices(&self) -> Vec<ItemPrice> {
+        prices(&self.virtual_currency_prices, &self.real_currency_prices)
+    }
+
+    pub fn price_in(&self, currency_id: &str) -> Option<i32> {
+        self.virtual_currency_prices.get(currency_id).copied()
+    }
+}
+
+fn prices(
+    virtual_prices: &HashMap<String, i32>,
+    real_prices: &HashMap<String, f64>,
+) -> Vec<ItemPrice> {
+    let mut virtual_prices: Vec<_> = virtual_prices.iter().collect();
+    virtual_prices.sort();
+    let mut real_prices: Vec<_> = real_prices.iter().collect();
+    real_prices.sort_by(|a, b| a.0.cmp(b.0));
+
+    virtual_prices
+        .into_iter()
+        .map(|(currency_id, amount)| ItemPrice::VirtualCurrency {
+            currency_id: currency_id.clone(),
+            amount: *amount,
+        })
+        .chain(
+            real_prices
+                .into_iter()
+                .map(|(currency, amount)| ItemPrice::RealMoney {
+                    currency: currency.clone(),
+                    amount: *amount,
+                }),
+        )
+        .collect()
+}
+
+/// Virtual-currency store (e.g. the daily shop or a sale)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Store {
+    #[serde(rename = "StoreID")]
+    pub store_id: String,
+    #[serde(default)]
+    pub catalog_version: Option<String>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub items: Vec<StoreItem>,
+    /// Unix timestamp at which the store rotates
+    #[serde(default)]
+    pub ends_at: Option<i64>,
+}
+
+/// Request to the catalog endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CatalogRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catalog_version: Option<String>,
+    #[serde(rename = "StoreID", skip_serializing_if = "Option::is_none")]
+    pub store_id: Option<String>,
+}
+
+/// Catalog items of one version
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetCatalogItemsResult {
+    #[serde(default)]
+    pub catalog: Vec<CatalogItem>,
+}
+
+/// Request to buy an item with virtual currency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PurchaseItemRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(rename = "ItemID")]
+    pub item_id: String,
+    /// Currency the player pays with
+    pub virtual_currency: String,
+    /// Price the player saw; the backend refuses the purchase if it changed
+    pub price: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catalog_version: Option<String>,
+    #[serde(rename = "StoreID", skip_serializing_if = "Option::is_none")]
+    pub store_id: Option<String>,
+}
+
+/// Item instance granted by a purchase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PurchasedItem {
+    #[serde(rename = "ItemID")]
+    pub item_id: String,
+    #[serde(rename = "ItemInstanceID", default)]
+    pub item_instance_id: Option<String>,
+    #[serde(default)]
+    pub remaining_uses: Option<i32>,
+}
+
+/// Items granted by a purchase (the item plus any bundle contents)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PurchaseItemResult {
+    #[serde(default)]
+    pub items: Vec<PurchasedItem>,
+}
+
+/// An item was bought with virtual currency; the backend has already updated the inventory
+#[derive(Message, Debug, Clone)]
+pub struct ItemPurchased {
+    pub item_id: String,
+    pub store_id: Option<String>,
+    pub currency_id: String,
+    pub price: i32,
+    pub granted: Vec<PurchasedItem>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_catalog_item_prices() {
+        let item: CatalogItem = serde_json::from_value(json!({
+            "ItemID": "starter_pack",
+            "DisplayName": "Starter Pack",
+            "VirtualCurrencyPrices": { "GEMS": 100, "GOLD": 5000 },
+            "RealCurrencyPrices": { "USD": 4.99 },
+            "ProductID": "com.game.starter_pack",
+            "BundledItems": ["sword", "shield"]
+        }))
+        .unwrap();
+
+        assert_eq!(item.price_in("GEMS"), Some(100));
+        assert_eq!(item.price_in("TICKETS"), None);
+        assert_eq!(
+            item.prices(),
+            vec![
+                ItemPrice::VirtualCurrency {
+                    currency_id: "GEMS".to_string(),
+                    amount: 100
+                },
+                ItemPrice::VirtualCurrency {
+                    currency_id: "GOLD".to_string(),
+                    amount: 5000
+                },
+                ItemPrice::RealMoney {
+                    currency: "USD".to_string(),
+                    amount: 4.99
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,205 @@
+/// Catalog handler: item catalog, virtual-currency stores and purchases
+use super::dto::*;
+use crate::{IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Resource, Clone)]
+pub struct CatalogHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    /// Items from the last catalog fetch, by item ID
+    items: Arc<Mutex<HashMap<String, CatalogItem>>>,
+    /// Purchases waiting for CatalogPlugin
+    purchases: Arc<Mutex<Vec<ItemPurchased>>>,
+}
+
+impl CatalogHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            items: Arc::new(Mutex::new(HashMap::new())),
+            purchases: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    fn request(
+        &self,
+        catalog_version: Option<&str>,
+        store_id: Option<&str>,
+    ) -> IdosResult<CatalogRequest> {
+        Ok(CatalogRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            catalog_version: catalog_version.map(str::to_string),
+            store_id: store_id.map(str::to_string),
+        })
+    }
+
+    /// Fetch every item of a catalog version (None = the game's current catalog)
+    pub async fn get_catalog_items(
+        &self,
+        catalog_version: Option<&str>,
+    ) -> IdosResult<Vec<CatalogItem>> {
+        let request = self.request(catalog_version, None)?;
+        let result: GetCatalogItemsResult = self.client.post("catalog/items", &request).await?;
+
+        if let Ok(mut items) = self.items.lock() {
+            *items = result
+                .catalog
+                .iter()
+                .map(|item| (item.item_id.clone(), item.clone()))
+                .collect();
+        }
+
+        Ok(result.catalog)
+    }
+
+    /// Item from the last `get_catalog_items` call
+    pub fn cached_item(&self, item_id: &str) -> Option<CatalogItem> {
+        self.items
+            .lock()
+            .ok()
+            .and_then(|items| items.get(item_id).cloned())
+    }
+
+    /// Fetch a store with its own prices
+    pub async fn get_store(
+        &self,
+        store_id: &str,
+        catalog_version: Option<&str>,
+    ) -> IdosResult<Store> {
+        let request = self.request(catalog_version, Some(store_id))?;
+        self.client.post("catalog/store", &request).await
+    }
+
+    /// Buy a catalog item with virtual currency at the catalog price
+    pub async fn purchase_item(
+        &self,
+        item: &CatalogItem,
+        currency_id: &str,
+    ) -> IdosResult<PurchaseItemResult> {
+        let price = item.price_in(currency_id).ok_or_else(|| {
+            IdosError::InvalidInput(format!(
+                "{} can't be bought with {}",
+                item.item_id, currency_id
+            ))
+        })?;
+        self.purchase(
+            &item.item_id,
+            currency_id,
+            price,
+            item.catalog_version.as_deref(),
+            None,
+        )
+        .await
+    }
+
+    /// Buy a store item with virtual currency at the store price
+    pub async fn purchase_store_item(
+        &self,
+        store: &Store,
+        item_id: &str,
+        currency_id: &str,
+    ) -> IdosResult<PurchaseItemResult> {
+        let price = store
+            .items
+            .iter()
+            .find(|item| item.item_id == item_id)
+            .and_then(|item| item.price_in(currency_id))
+            .ok_or_else(|| {
+                IdosError::InvalidInput(format!(
+                    "{} is not sold for {} in store {}",
+                    item_id, currency_id, store.store_id
+                ))
+            })?;
+        self.purchase(
+            item_id,
+            currency_id,
+            price,
+            store.catalog_version.as_deref(),
+            Some(&store.store_id),
+        )
+        .await
+    }
+
+    /// Buy an item with virtual currency
+    /// `price` is the price shown to the player; the backend refuses the purchase when it
+    /// changed or the balance is too low (`ApiErrorCode::InsufficientFunds`).
+    /// On success `ItemPurchased` is written and, with the `inventory` feature,
+    /// InventoryHandler's cache is updated
+    pub async fn purchase(
+        &self,
+        item_id: &str,
+        currency_id: &str,
+        price: i32,
+        catalog_version: Option<&str>,
+        store_id: Option<&str>,
+    ) -> IdosResult<PurchaseItemResult> {
+        let request = PurchaseItemRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            item_id: item_id.to_string(),
+            virtual_currency: currency_id.to_string(),
+            price,
+            catalog_version: catalog_version.map(str::to_string),
+            store_id: store_id.map(str::to_string),
+        };
+
+        let result: PurchaseItemResult = self.client.post("catalog/purchase", &request).await?;
+
+        if let Ok(mut purchases) = self.purchases.lock() {
+            purchases.push(ItemPurchased {
+                item_id: item_id.to_string(),
+                store_id: store_id.map(str::to_string),
+                currency_id: currency_id.to_string(),
+                price,
+                granted: result.items.clone(),
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// Take the purchases made since the last call
+    pub fn drain_purchases(&self) -> Vec<ItemPurchased> {
+        self.purchases
+            .lock()
+            .map(|mut purchases| std::mem::take(&mut *purchases))
+            .unwrap_or_default()
+    }
+}
+
+crate::session::session_aware!(CatalogHandler);
@@ -0,0 +1,8 @@
+/// Catalog module: item catalog, virtual-currency stores and purchases
+pub mod catalog_plugin;
+pub mod dto;
+pub mod handler;
+
+pub use catalog_plugin::CatalogPlugin;
+pub use dto::*;
+pub use handler::CatalogHandler;
@@ -2,22 +2,34 @@
 use crate::{IdosConfig, IdosError, IdosResult};
 use bevy::prelude::*;
 use serde::{de::DeserializeOwned, Serialize};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+/// Called with the operation (endpoint, or a name given to `report_error`) and the error
+/// of every failed SDK call; AnalyticsPlugin installs one that tracks `error` events
+pub type ErrorHook = Arc<dyn Fn(&str, &IdosError) + Send + Sync>;
 
 #[derive(Resource, Clone)]
 pub struct IdosClient {
     http_client: reqwest::Client,
     config: IdosConfig,
+    /// Shared by every clone, so all handlers stop calling a backend that is down
+    breaker: Arc<Mutex<CircuitBreaker>>,
+    /// Shared by every clone, so failures from all handlers reach it
+    error_hook: Arc<RwLock<Option<ErrorHook>>>,
+}
+
+/// Consecutive request failures, and when the breaker lets requests through again
+#[derive(Default)]
+struct CircuitBreaker {
+    failures: u32,
+    /// Unix timestamp (milliseconds)
+    open_until: Option<i64>,
 }
 
 impl IdosClient {
     pub fn new(config: IdosConfig) -> Self {
-        #[cfg(not(target_arch = "wasm32"))]
-        let http_client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-
-        #[cfg(target_arch = "wasm32")]
+        // Timeouts are applied per request from `config.request_timeout_secs`
         let http_client = reqwest::Client::builder()
             .build()
             .expect("Failed to create HTTP client");
@@ -25,9 +37,38 @@ impl IdosClient {
         Self {
             http_client,
             config,
+            breaker: Arc::new(Mutex::new(CircuitBreaker::default())),
+            error_hook: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Install (or remove) the hook failed SDK calls are reported to
+    pub fn set_error_hook(&self, hook: Option<ErrorHook>) {
+        if let Ok(mut error_hook) = self.error_hook.write() {
+            *error_hook = hook;
+        }
+    }
+
+    /// Report a failure to the error hook
+    /// Backend requests report themselves; use this for failures outside the client,
+    /// e.g. a wallet that couldn't be unlocked
+    pub fn report_error(&self, operation: &str, error: &IdosError) {
+        let hook = self.error_hook.read().ok().and_then(|hook| hook.clone());
+        if let Some(hook) = hook {
+            hook(operation, error);
+        }
+    }
+
+    /// Report a failed request under its endpoint and hand the error back
+    fn request_failed(&self, url: &str, error: IdosError) -> IdosError {
+        let endpoint = url
+            .strip_prefix(self.config.api_url.as_str())
+            .unwrap_or(url)
+            .trim_start_matches('/');
+        self.report_error(endpoint, &error);
+        error
+    }
+
     /// Make a GET request
     pub async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> IdosResult<T> {
         let url = format!("{}/{}", self.config.api_url, endpoint);
@@ -37,21 +78,9 @@ impl IdosClient {
         }
 
         let response = self
-            .http_client
-            .get(&url)
-            .header("X-API-Key", &self.config.api_key)
-            .header("X-Game-ID", &self.config.game_id)
-            .send()
+            .send("GET", &url, true, || self.http_client.get(&url))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(IdosError::Api(format!(
-                "HTTP {} for {}",
-                response.status(),
-                url
-            )));
-        }
-
         Ok(response.json().await?)
     }
 
@@ -68,24 +97,11 @@ impl IdosClient {
         }
 
         let response = self
-            .http_client
-            .post(&url)
-            .header("X-API-Key", &self.config.api_key)
-            .header("X-Game-ID", &self.config.game_id)
-            .json(body)
-            .send()
+            .send("POST", &url, false, || {
+                self.http_client.post(&url).json(body)
+            })
             .await?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_else(|_| "<unreadable body>".to_string());
-            error!(
-                "POST {} failed with status {}. Body: {}",
-                url, status, text
-            );
-            return Err(IdosError::Api(format!("HTTP {} for {}", status, url)));
-        }
-
         let bytes = response.bytes().await?;
         if self.config.debug {
             debug!(
@@ -102,10 +118,13 @@ impl IdosClient {
                 err,
                 String::from_utf8_lossy(&bytes)
             );
-            IdosError::SerializationError(format!(
-                "Failed to decode response from {}: {}",
-                url, err
-            ))
+            self.request_failed(
+                &url,
+                IdosError::SerializationError(format!(
+                    "Failed to decode response from {}: {}",
+                    url, err
+                )),
+            )
         })
     }
 
@@ -122,22 +141,9 @@ impl IdosClient {
         }
 
         let response = self
-            .http_client
-            .put(&url)
-            .header("X-API-Key", &self.config.api_key)
-            .header("X-Game-ID", &self.config.game_id)
-            .json(body)
-            .send()
+            .send("PUT", &url, true, || self.http_client.put(&url).json(body))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(IdosError::Api(format!(
-                "HTTP {} for {}",
-                response.status(),
-                url
-            )));
-        }
-
         Ok(response.json().await?)
     }
 
@@ -150,22 +156,134 @@ impl IdosClient {
         }
 
         let response = self
-            .http_client
-            .delete(&url)
-            .header("X-API-Key", &self.config.api_key)
-            .header("X-Game-ID", &self.config.game_id)
-            .send()
+            .send("DELETE", &url, true, || self.http_client.delete(&url))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(IdosError::Api(format!(
-                "HTTP {} for {}",
-                response.status(),
-                url
-            )));
+        Ok(response.json().await?)
+    }
+
+    /// Send a request with the SDK headers and the configured timeout
+    /// Idempotent requests are retried with exponential backoff after timeouts,
+    /// connection errors and 5xx responses; failures feed the circuit breaker
+    async fn send(
+        &self,
+        method: &str,
+        url: &str,
+        idempotent: bool,
+        build: impl Fn() -> reqwest::RequestBuilder,
+    ) -> IdosResult<reqwest::Response> {
+        // Not reported: the failures that opened the breaker already were
+        self.check_breaker()?;
+
+        let retries = if idempotent {
+            self.config.max_retries
+        } else {
+            0
+        };
+        let mut attempt = 0;
+        loop {
+            let result = build()
+                .header("X-API-Key", &self.config.api_key)
+                .header("X-Game-ID", &self.config.game_id)
+                .timeout(Duration::from_secs(self.config.request_timeout_secs))
+                .send()
+                .await;
+
+            let failure = match result {
+                Ok(response) if response.status().is_success() => {
+                    self.record_success();
+                    return Ok(response);
+                }
+                Ok(response) if !response.status().is_server_error() => {
+                    // The backend is up; the request itself was rejected
+                    self.record_success();
+                    let error = Self::error_response(method, url, response).await;
+                    return Err(self.request_failed(url, error));
+                }
+                Ok(response) => Self::error_response(method, url, response).await,
+                Err(e) if e.is_timeout() => IdosError::TimeoutError(format!(
+                    "{} {} timed out after {}s",
+                    method, url, self.config.request_timeout_secs
+                )),
+                Err(e) if e.is_connect() || e.is_request() => e.into(),
+                Err(e) => return Err(self.request_failed(url, e.into())),
+            };
+
+            if attempt >= retries {
+                self.record_failure();
+                return Err(self.request_failed(url, failure));
+            }
+
+            let delay = self
+                .config
+                .retry_backoff_ms
+                .saturating_mul(1 << attempt.min(10));
+            warn!(
+                "{} {} failed ({}), retrying in {}ms",
+                method, url, failure, delay
+            );
+            crate::tasks::sleep(Duration::from_millis(delay)).await;
+            attempt += 1;
         }
+    }
 
-        Ok(response.json().await?)
+    /// Fail fast while the circuit breaker is open
+    fn check_breaker(&self) -> IdosResult<()> {
+        let breaker = self.breaker.lock().unwrap_or_else(|e| e.into_inner());
+        match breaker.open_until {
+            Some(open_until) if chrono::Utc::now().timestamp_millis() < open_until => {
+                Err(IdosError::NetworkError(format!(
+                    "Backend unavailable after {} failed requests; retrying in {}s",
+                    breaker.failures,
+                    (open_until - chrono::Utc::now().timestamp_millis()) / 1000 + 1
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn record_success(&self) {
+        let mut breaker = self.breaker.lock().unwrap_or_else(|e| e.into_inner());
+        *breaker = CircuitBreaker::default();
+    }
+
+    /// Open the breaker once `circuit_breaker_threshold` requests in a row have failed
+    /// A failure after the cooldown (the trial request) opens it again straight away
+    fn record_failure(&self) {
+        let threshold = self.config.circuit_breaker_threshold;
+        if threshold == 0 {
+            return;
+        }
+
+        let mut breaker = self.breaker.lock().unwrap_or_else(|e| e.into_inner());
+        breaker.failures = breaker.failures.saturating_add(1);
+        if breaker.failures >= threshold {
+            let cooldown = (self.config.circuit_breaker_cooldown_secs as i64).saturating_mul(1000);
+            breaker.open_until = Some(chrono::Utc::now().timestamp_millis() + cooldown);
+            warn!(
+                "{} backend requests failed in a row; pausing requests for {}s",
+                breaker.failures, self.config.circuit_breaker_cooldown_secs
+            );
+        }
+    }
+
+    /// Whether requests currently fail fast because the backend kept failing
+    pub fn is_circuit_open(&self) -> bool {
+        self.check_breaker().is_err()
+    }
+
+    /// Turn a failed response into `IdosError::Api`, keeping the backend's error code
+    async fn error_response(method: &str, url: &str, response: reqwest::Response) -> IdosError {
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<unreadable body>".to_string());
+        error!(
+            "{} {} failed with status {}. Body: {}",
+            method, url, status, text
+        );
+        IdosError::from_response(status.as_u16(), &text)
     }
 
     /// Get the game ID from config
@@ -183,3 +301,40 @@ impl IdosClient {
         &self.config
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_circuit_breaker_opens_after_failures() {
+        // Nothing listens on port 1, so every request fails to connect
+        let client = IdosClient::new(IdosConfig {
+            api_url: "http://127.0.0.1:1".to_string(),
+            max_retries: 1,
+            retry_backoff_ms: 1,
+            circuit_breaker_threshold: 2,
+            ..Default::default()
+        });
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        client.set_error_hook(Some(Arc::new(move |operation: &str, _: &IdosError| {
+            sink.lock().unwrap().push(operation.to_string());
+        })));
+
+        for _ in 0..2 {
+            let result: IdosResult<serde_json::Value> = client.get("ping").await;
+            assert!(matches!(result, Err(IdosError::Http(_))));
+        }
+        assert!(client.is_circuit_open());
+
+        let result: IdosResult<serde_json::Value> = client.get("ping").await;
+        assert!(matches!(result, Err(IdosError::NetworkError(_))));
+
+        // Clones share the breaker
+        assert!(client.clone().is_circuit_open());
+
+        // Each request reported once, after its retries; fast failures aren't reported
+        assert_eq!(*reported.lock().unwrap(), ["ping", "ping"]);
+    }
+}
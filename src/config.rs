@@ -3,6 +3,7 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct IdosConfig {
     /// Your iDos Games API key
     pub api_key: String,
@@ -22,6 +23,25 @@ pub struct IdosConfig {
     /// Enable crash reporting
     pub enable_crash_reporting: bool,
 
+    /// Game version sent with every analytics event (e.g. "1.4.2")
+    pub app_version: Option<String>,
+
+    /// Seconds before a backend request is abandoned with `IdosError::TimeoutError`
+    pub request_timeout_secs: u64,
+
+    /// Retries of GET, PUT and DELETE requests after a timeout, connection error or 5xx
+    /// POST requests are never retried
+    pub max_retries: u32,
+
+    /// Delay before the first retry, doubled for each further one
+    pub retry_backoff_ms: u64,
+
+    /// Consecutive failed requests that open the circuit breaker (0 disables it)
+    pub circuit_breaker_threshold: u32,
+
+    /// Seconds requests fail fast once the circuit breaker is open
+    pub circuit_breaker_cooldown_secs: u64,
+
     /// Platform-specific settings
     pub platform: PlatformConfig,
 }
@@ -35,6 +55,12 @@ impl Default for IdosConfig {
             debug: cfg!(debug_assertions),
             enable_analytics: true,
             enable_crash_reporting: true,
+            app_version: None,
+            request_timeout_secs: 30,
+            max_retries: 2,
+            retry_backoff_ms: 500,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 30,
             platform: PlatformConfig::default(),
         }
     }
@@ -157,6 +157,189 @@ pub struct EthTransactionReceipt {
     pub to: Option<String>,
 }
 
+/// Safety margin added on top of eth_estimateGas, in percent
+pub const DEFAULT_GAS_MARGIN_PERCENT: u64 = 20;
+
+/// How the gas limit is chosen for outgoing transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GasLimit {
+    /// eth_estimateGas plus a safety margin
+    Estimate { margin_percent: u64 },
+    /// Fixed gas limit, skips estimation
+    Fixed(u64),
+}
+
+impl Default for GasLimit {
+    fn default() -> Self {
+        GasLimit::Estimate {
+            margin_percent: DEFAULT_GAS_MARGIN_PERCENT,
+        }
+    }
+}
+
+/// Gas price tier picked from the `GasOracle`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GasSpeed {
+    Slow,
+    Standard,
+    Fast,
+}
+
+/// Gas prices cached by the `GasOracle`, in gwei
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasPriceTiers {
+    pub slow_gwei: f64,
+    pub standard_gwei: f64,
+    pub fast_gwei: f64,
+    /// Unix seconds of the refresh that produced these prices
+    pub updated_at: i64,
+}
+
+impl GasPriceTiers {
+    pub fn gwei(&self, speed: GasSpeed) -> f64 {
+        match speed {
+            GasSpeed::Slow => self.slow_gwei,
+            GasSpeed::Standard => self.standard_gwei,
+            GasSpeed::Fast => self.fast_gwei,
+        }
+    }
+}
+
+/// Signed transaction ready to be stored, relayed or broadcast later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    /// RLP-encoded signed transaction, 0x-prefixed hex (eth_sendRawTransaction input)
+    pub raw_transaction: String,
+    pub transaction_hash: String,
+    pub from: String,
+    pub nonce: String,
+}
+
+/// ERC20 token metadata read from the contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenInfo {
+    pub address: String,
+    pub symbol: String,
+    pub name: String,
+    pub decimals: u8,
+}
+
+/// Full cost of a transaction, including rollup L1 data fees (all amounts in wei)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotalCostEstimate {
+    pub gas_limit: u64,
+    pub gas_price_wei: String,
+    /// Gas limit × gas price
+    pub execution_fee_wei: String,
+    /// L1 data fee on rollups (on Arbitrum this is already part of the execution fee)
+    pub l1_data_fee_wei: String,
+    /// What the wallet must hold to send the transaction
+    pub total_cost_wei: String,
+}
+
+/// ERC20 allowance granted by the player's wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenAllowance {
+    /// Key of the token in `BlockchainSettings::token_contract_addresses`
+    pub token_name: String,
+    pub token_address: String,
+    pub spender_address: String,
+    /// Allowance in wei
+    pub allowance: String,
+    /// True for max-uint style approvals
+    pub is_unlimited: bool,
+}
+
+/// NFT indexer API flavour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NftIndexerProvider {
+    /// Alchemy NFT API v3, e.g. `https://eth-mainnet.g.alchemy.com/nft/v3/<api-key>`
+    Alchemy,
+    /// Moralis Web3 Data API, e.g. `https://deep-index.moralis.io/api/v2.2`
+    Moralis,
+}
+
+/// NFT indexer used to enumerate owned NFTs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftIndexerConfig {
+    pub provider: NftIndexerProvider,
+    pub base_url: String,
+    /// Sent as `X-API-Key` (Alchemy keys usually live in the base URL instead)
+    pub api_key: Option<String>,
+    /// Chain name or hex ID for Moralis, e.g. "eth", "polygon", "0x2105"
+    pub chain: Option<String>,
+}
+
+/// NFT owned by a wallet, as reported by the indexer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnedNft {
+    pub contract_address: String,
+    pub token_id: String,
+    pub balance: String,
+    /// "ERC721" or "ERC1155"
+    pub token_type: Option<String>,
+    pub name: Option<String>,
+    pub image_url: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Transaction sent by the SDK that still needs confirming or reporting to the backend
+/// Persisted so it survives restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingEthTransaction {
+    pub transaction_hash: String,
+    pub chain_id: i64,
+    pub transaction_type: CryptoTransactionType,
+    pub direction: TransactionDirection,
+    pub token_address: Option<String>,
+    pub nft_id: Option<String>,
+    /// Amount in wei for tokens, count for NFTs
+    pub amount: Option<String>,
+    /// Whether the backend still has to be told about this transaction
+    pub submit_to_backend: bool,
+    /// Unix timestamp (seconds) when the transaction was sent
+    pub created_at: i64,
+}
+
+impl PendingEthTransaction {
+    pub fn new(
+        transaction_hash: &str,
+        chain_id: i64,
+        transaction_type: CryptoTransactionType,
+        direction: TransactionDirection,
+    ) -> Self {
+        Self {
+            transaction_hash: transaction_hash.to_string(),
+            chain_id,
+            transaction_type,
+            direction,
+            token_address: None,
+            nft_id: None,
+            amount: None,
+            submit_to_backend: false,
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// Where a pending transaction stands after a resume pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PendingTransactionStatus {
+    /// Mined successfully and reported to the backend if needed
+    Confirmed,
+    /// Mined but reverted; nothing was reported
+    Reverted,
+    /// Not mined yet (or the backend was unreachable); kept for the next pass
+    Pending,
+}
+
+/// Result of resuming one pending transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingTransactionResult {
+    pub transaction: PendingEthTransaction,
+    pub status: PendingTransactionStatus,
+}
+
 /// Blockchain settings
 #[derive(Debug, Clone)]
 pub struct BlockchainSettings {
@@ -165,7 +348,12 @@ pub struct BlockchainSettings {
     pub platform_pool_contract_address: String,
     pub token_contract_addresses: std::collections::HashMap<String, String>,
     pub nft_contract_address: String,
+    /// Used when no `GasSpeed` is requested or the `GasOracle` has no prices yet
     pub gas_price_gwei: f64,
+    /// How often EthereumPlugin refreshes the `GasOracle`; `None` disables it
+    pub gas_oracle_interval: Option<std::time::Duration>,
+    /// Optional indexer for listing owned NFTs
+    pub nft_indexer: Option<NftIndexerConfig>,
 }
 
 impl Default for BlockchainSettings {
@@ -177,6 +365,8 @@ impl Default for BlockchainSettings {
             token_contract_addresses: std::collections::HashMap::new(),
             nft_contract_address: String::new(),
             gas_price_gwei: 20.0,
+            gas_oracle_interval: Some(std::time::Duration::from_secs(30)),
+            nft_indexer: None,
         }
     }
 }
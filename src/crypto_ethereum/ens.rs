@@ -0,0 +1,151 @@
+/// Ethereum Name Service
+/// Resolves names like "alice.eth" to addresses and addresses back to their primary name
+use crate::{IdosError, IdosResult};
+use ethers::types::Address;
+use ethers::utils::{keccak256, to_checksum};
+use serde_json::{json, Value};
+
+/// ENS registry, deployed at the same address on mainnet and the testnets
+pub const ENS_REGISTRY: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+// resolver(bytes32), addr(bytes32), name(bytes32)
+const RESOLVER_SELECTOR: &str = "0178b8bf";
+const ADDR_SELECTOR: &str = "3b3b57de";
+const NAME_SELECTOR: &str = "691f3431";
+
+/// EIP-137 namehash
+pub fn namehash(name: &str) -> [u8; 32] {
+    let name = name.trim().to_lowercase();
+    let mut node = [0u8; 32];
+    if name.is_empty() {
+        return node;
+    }
+    for label in name.rsplit('.') {
+        let mut buffer = node.to_vec();
+        buffer.extend_from_slice(&keccak256(label.as_bytes()));
+        node = keccak256(buffer);
+    }
+    node
+}
+
+/// Whether `value` is a 0x-prefixed 20-byte hex address
+pub fn is_address(value: &str) -> bool {
+    value.len() == 42 && value.starts_with("0x") && hex::decode(&value[2..]).is_ok()
+}
+
+async fn call(rpc_url: &str, to: &str, selector: &str, node: &[u8; 32]) -> IdosResult<Vec<u8>> {
+    let data = format!("0x{}{}", selector, hex::encode(node));
+    let result = rpc(
+        rpc_url,
+        "eth_call",
+        json!([{ "to": to, "data": data }, "latest"]),
+    )
+    .await?;
+    let result = result
+        .as_str()
+        .ok_or_else(|| IdosError::SerializationError("Invalid eth_call result".to_string()))?;
+
+    hex::decode(result.trim_start_matches("0x"))
+        .map_err(|e| IdosError::SerializationError(format!("Invalid eth_call result: {}", e)))
+}
+
+/// Address in the last 20 bytes of an ABI word, None for the zero address
+fn decode_address(word: &[u8]) -> Option<Address> {
+    let address = Address::from_slice(word.get(12..32)?);
+    (!address.is_zero()).then_some(address)
+}
+
+/// ABI-encoded `string` return value
+fn decode_string(data: &[u8]) -> Option<String> {
+    let word = |offset: usize| -> Option<usize> {
+        let word = data.get(offset..offset.checked_add(32)?)?;
+        usize::try_from(u64::from_be_bytes(word[24..32].try_into().ok()?)).ok()
+    };
+    let offset = word(0)?;
+    let len = word(offset)?;
+    let bytes = data.get(offset + 32..(offset + 32).checked_add(len)?)?;
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+async fn resolver(rpc_url: &str, node: &[u8; 32]) -> IdosResult<Option<Address>> {
+    let result = call(rpc_url, ENS_REGISTRY, RESOLVER_SELECTOR, node).await?;
+    Ok(decode_address(&result))
+}
+
+/// Address a name points to (checksummed), or None when it isn't set
+pub async fn resolve_name(rpc_url: &str, name: &str) -> IdosResult<Option<String>> {
+    let node = namehash(name);
+    let Some(resolver) = resolver(rpc_url, &node).await? else {
+        return Ok(None);
+    };
+    let result = call(rpc_url, &format!("{:?}", resolver), ADDR_SELECTOR, &node).await?;
+    Ok(decode_address(&result).map(|address| to_checksum(&address, None)))
+}
+
+/// Primary name of an address, or None when it has none
+/// The name is only returned when it resolves back to the same address
+pub async fn lookup_address(rpc_url: &str, address: &str) -> IdosResult<Option<String>> {
+    let parsed: Address = address
+        .parse()
+        .map_err(|_| IdosError::InvalidInput(format!("Invalid address: {}", address)))?;
+    let node = namehash(&format!("{}.addr.reverse", hex::encode(parsed)));
+    let Some(resolver) = resolver(rpc_url, &node).await? else {
+        return Ok(None);
+    };
+    let result = call(rpc_url, &format!("{:?}", resolver), NAME_SELECTOR, &node).await?;
+    let Some(name) = decode_string(&result).filter(|name| !name.is_empty()) else {
+        return Ok(None);
+    };
+
+    match resolve_name(rpc_url, &name).await? {
+        Some(resolved) if resolved.eq_ignore_ascii_case(address) => Ok(Some(name)),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn rpc(rpc_url: &str, method: &str, params: Value) -> IdosResult<Value> {
+    use ethers::providers::{Http, Provider};
+
+    let provider = Provider::<Http>::try_from(rpc_url)
+        .map_err(|e| IdosError::ConfigurationError(format!("Invalid RPC URL: {}", e)))?;
+    provider
+        .request(method, params)
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("{} failed: {}", method, e)))
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn rpc(rpc_url: &str, method: &str, params: Value) -> IdosResult<Value> {
+    super::helper::send_rpc_request(rpc_url, method, params).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namehash() {
+        assert_eq!(namehash(""), [0u8; 32]);
+        assert_eq!(
+            hex::encode(namehash("eth")),
+            "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+        );
+        assert_eq!(
+            hex::encode(namehash("foo.eth")),
+            "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+        );
+        assert_eq!(namehash("Foo.ETH"), namehash("foo.eth"));
+    }
+
+    #[test]
+    fn test_decode_string() {
+        let mut data = vec![0u8; 64];
+        data[31] = 0x20;
+        data[63] = 9;
+        data.extend_from_slice(b"alice.eth");
+        data.resize(96, 0);
+        assert_eq!(decode_string(&data).as_deref(), Some("alice.eth"));
+        assert_eq!(decode_string(&[]), None);
+    }
+}
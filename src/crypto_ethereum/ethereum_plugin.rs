@@ -1,6 +1,11 @@
 use super::{BlockchainSettings, EthereumHandler};
 use crate::IdosClient;
 use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+/// How often pending transactions in the transaction history are checked
+const HISTORY_POLL_INTERVAL: Duration = Duration::from_secs(15);
 
 pub struct EthereumPlugin {
     pub settings: BlockchainSettings,
@@ -14,14 +19,81 @@ impl EthereumPlugin {
 
 impl Plugin for EthereumPlugin {
     fn build(&self, app: &mut App) {
+        let history = crate::transaction_history::shared_history(app);
+
         // Get the IdosClient resource if it exists
         if let Some(client) = app.world().get_resource::<IdosClient>() {
-            let handler = EthereumHandler::new(client.clone(), self.settings.clone());
+            let mut handler = EthereumHandler::new(client.clone(), self.settings.clone());
+            if let Some(history) = history {
+                handler.set_transaction_history(history);
+            }
             app.insert_resource(handler);
         } else {
             warn!("IdosClient not found. EthereumHandler will not be initialized.");
         }
 
+        app.add_systems(
+            Startup,
+            (resume_pending_transactions, refresh_transaction_history),
+        )
+        .add_systems(
+            Update,
+            refresh_transaction_history.run_if(on_timer(HISTORY_POLL_INTERVAL)),
+        );
+
+        if let Some(interval) = self.settings.gas_oracle_interval {
+            app.add_systems(Startup, refresh_gas_oracle)
+                .add_systems(Update, refresh_gas_oracle.run_if(on_timer(interval)));
+        }
+
         info!("Ethereum Wallet Plugin initialized");
     }
 }
+
+/// Pick up transactions left unresolved by a previous run
+fn resume_pending_transactions(handler: Option<Res<EthereumHandler>>) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    let h = handler.clone();
+    let resume = async move {
+        match h.resume_pending_transactions(20).await {
+            Ok(results) if !results.is_empty() => {
+                info!("Resumed {} pending Ethereum transaction(s)", results.len())
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to resume pending Ethereum transactions: {}", e),
+        }
+    };
+
+    crate::tasks::spawn(resume);
+}
+
+/// Keep the handler's gas price tiers fresh
+fn refresh_gas_oracle(handler: Option<Res<EthereumHandler>>) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    let h = handler.clone();
+    crate::tasks::spawn(async move {
+        if let Err(e) = h.refresh_gas_prices().await {
+            warn!("Failed to refresh gas prices: {}", e);
+        }
+    });
+}
+
+/// Resolve pending transactions in the transaction history, including ones left by a previous run
+fn refresh_transaction_history(handler: Option<Res<EthereumHandler>>) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    let h = handler.clone();
+    crate::tasks::spawn(async move {
+        if let Err(e) = h.refresh_transaction_history().await {
+            warn!("Failed to refresh Ethereum transaction history: {}", e);
+        }
+    });
+}
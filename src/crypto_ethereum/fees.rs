@@ -0,0 +1,150 @@
+/// Chain-aware fee estimation
+/// Adds the L1 data fee on OP-stack rollups and handles Arbitrum's L1-inclusive gas limits
+use super::dto::TotalCostEstimate;
+use crate::{IdosError, IdosResult};
+use ethers::{
+    contract::abigen,
+    prelude::*,
+    types::{transaction::eip2718::TypedTransaction, Address, U256},
+};
+use std::sync::Arc;
+
+/// OP-stack GasPriceOracle predeploy
+const OP_GAS_PRICE_ORACLE: &str = "0x420000000000000000000000000000000000000F";
+/// Arbitrum NodeInterface virtual contract (only callable via eth_call)
+const ARBITRUM_NODE_INTERFACE: &str = "0x00000000000000000000000000000000000000C8";
+
+abigen!(
+    GasPriceOracle,
+    r#"[
+        function getL1Fee(bytes memory _data) external view returns (uint256)
+    ]"#,
+);
+
+abigen!(
+    NodeInterface,
+    r#"[
+        function gasEstimateL1Component(address to, bool contractCreation, bytes calldata data) external payable returns (uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate)
+    ]"#,
+);
+
+/// How a chain charges for transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainFeeModel {
+    /// Ethereum mainnet and other L1s: gas limit × gas price
+    Standard,
+    /// Optimism, Base and other OP-stack chains: execution fee plus a separate L1 data fee
+    OpStack,
+    /// Arbitrum One/Nova: the gas limit already includes the L1 component
+    Arbitrum,
+}
+
+impl ChainFeeModel {
+    /// Pick the fee model for a chain ID
+    pub fn from_chain_id(chain_id: u64) -> Self {
+        match chain_id {
+            // Optimism, OP Sepolia, Base, Base Sepolia, Zora, Zora Sepolia, Mode
+            10 | 11155420 | 8453 | 84532 | 7777777 | 999999999 | 34443 => ChainFeeModel::OpStack,
+            // Arbitrum One, Arbitrum Nova, Arbitrum Sepolia
+            42161 | 42170 | 421614 => ChainFeeModel::Arbitrum,
+            _ => ChainFeeModel::Standard,
+        }
+    }
+}
+
+/// Estimate everything a transaction will cost, including rollup L1 fees
+///
+/// `gas_price_wei` is the price the transaction will be sent with; `None` uses
+/// the node's current eth_gasPrice. If the transaction has no gas limit it is
+/// estimated first.
+pub async fn estimate_total_cost(
+    rpc_url: &str,
+    chain_id: u64,
+    tx: &TypedTransaction,
+    gas_price_wei: Option<U256>,
+) -> IdosResult<TotalCostEstimate> {
+    let provider = Arc::new(
+        Provider::<Http>::try_from(rpc_url)
+            .map_err(|e| IdosError::NetworkError(format!("Provider error: {}", e)))?,
+    );
+
+    let mut tx = tx.clone();
+    tx.set_chain_id(chain_id);
+
+    let gas_price = match gas_price_wei {
+        Some(price) => price,
+        None => provider
+            .get_gas_price()
+            .await
+            .map_err(|e| IdosError::NetworkError(format!("Gas price query failed: {}", e)))?,
+    };
+    tx.set_gas_price(gas_price);
+
+    let gas_limit = match tx.gas() {
+        Some(gas) => *gas,
+        None => provider
+            .estimate_gas(&tx, None)
+            .await
+            .map_err(|e| IdosError::NetworkError(format!("Gas estimation failed: {}", e)))?,
+    };
+    tx.set_gas(gas_limit);
+
+    let execution_fee = gas_limit * gas_price;
+
+    let fee_model = ChainFeeModel::from_chain_id(chain_id);
+    let l1_data_fee = match fee_model {
+        ChainFeeModel::Standard => U256::zero(),
+        ChainFeeModel::OpStack => op_stack_l1_fee(provider.clone(), &tx).await?,
+        ChainFeeModel::Arbitrum => arbitrum_l1_fee(provider.clone(), &tx).await?,
+    };
+
+    // Arbitrum's gas limit already pays for the L1 component
+    let total_cost = match fee_model {
+        ChainFeeModel::OpStack => execution_fee + l1_data_fee,
+        ChainFeeModel::Standard | ChainFeeModel::Arbitrum => execution_fee,
+    };
+
+    Ok(TotalCostEstimate {
+        gas_limit: gas_limit.as_u64(),
+        gas_price_wei: gas_price.to_string(),
+        execution_fee_wei: execution_fee.to_string(),
+        l1_data_fee_wei: l1_data_fee.to_string(),
+        total_cost_wei: total_cost.to_string(),
+    })
+}
+
+/// L1 data fee from the OP-stack GasPriceOracle, based on the unsigned RLP
+async fn op_stack_l1_fee(provider: Arc<Provider<Http>>, tx: &TypedTransaction) -> IdosResult<U256> {
+    let oracle_address: Address = OP_GAS_PRICE_ORACLE
+        .parse()
+        .map_err(|_| IdosError::InvalidInput("Invalid oracle address".to_string()))?;
+    let oracle = GasPriceOracle::new(oracle_address, provider);
+
+    oracle
+        .get_l1_fee(tx.rlp())
+        .call()
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("L1 fee query failed: {}", e)))
+}
+
+/// L1 share of an Arbitrum transaction (informational, already in the gas limit)
+async fn arbitrum_l1_fee(provider: Arc<Provider<Http>>, tx: &TypedTransaction) -> IdosResult<U256> {
+    let node_interface_address: Address = ARBITRUM_NODE_INTERFACE
+        .parse()
+        .map_err(|_| IdosError::InvalidInput("Invalid NodeInterface address".to_string()))?;
+    let node_interface = NodeInterface::new(node_interface_address, provider);
+
+    let to = match tx.to() {
+        Some(NameOrAddress::Address(address)) => *address,
+        _ => Address::zero(),
+    };
+    let data = tx.data().cloned().unwrap_or_default();
+
+    let (gas_for_l1, base_fee, _) = node_interface
+        .gas_estimate_l1_component(to, tx.to().is_none(), data)
+        .call()
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("L1 fee query failed: {}", e)))?;
+
+    Ok(U256::from(gas_for_l1) * base_fee)
+}
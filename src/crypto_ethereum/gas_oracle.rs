@@ -0,0 +1,152 @@
+/// Gas price oracle
+/// Keeps slow/standard/fast gas prices from eth_gasPrice and eth_feeHistory, so transactions
+/// can ask for a `GasSpeed` instead of a fixed gwei value
+use super::dto::{GasPriceTiers, GasSpeed};
+use crate::{IdosError, IdosResult};
+use serde_json::{json, Value};
+use std::sync::{Arc, RwLock};
+
+/// Blocks sampled by eth_feeHistory
+const FEE_HISTORY_BLOCKS: u64 = 20;
+/// Priority fee percentiles for the slow, standard and fast tiers
+const REWARD_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+const WEI_PER_GWEI: f64 = 1_000_000_000.0;
+
+/// Latest gas price tiers for one chain, shared by clones
+#[derive(Clone, Default)]
+pub struct GasOracle {
+    tiers: Arc<RwLock<Option<GasPriceTiers>>>,
+}
+
+impl GasOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tiers from the last successful refresh
+    pub fn tiers(&self) -> Option<GasPriceTiers> {
+        self.tiers.read().ok().and_then(|tiers| tiers.clone())
+    }
+
+    /// Gas price for `speed` in gwei, or `None` before the first refresh
+    pub fn price_gwei(&self, speed: GasSpeed) -> Option<f64> {
+        self.tiers().map(|tiers| tiers.gwei(speed))
+    }
+
+    /// Query the node and replace the cached tiers
+    pub async fn refresh(&self, rpc_url: &str) -> IdosResult<GasPriceTiers> {
+        let gas_price = parse_quantity(&rpc(rpc_url, "eth_gasPrice", json!([])).await?)?;
+
+        // Pre-London chains and some L2s don't support eth_feeHistory
+        let fee_history = rpc(
+            rpc_url,
+            "eth_feeHistory",
+            json!([
+                format!("{:#x}", FEE_HISTORY_BLOCKS),
+                "latest",
+                REWARD_PERCENTILES
+            ]),
+        )
+        .await
+        .ok();
+
+        let tiers = tiers_from(
+            gas_price,
+            fee_history.as_ref(),
+            chrono::Utc::now().timestamp(),
+        );
+        if let Ok(mut cached) = self.tiers.write() {
+            *cached = Some(tiers.clone());
+        }
+        Ok(tiers)
+    }
+}
+
+/// Next block's base fee plus the median priority fee of each percentile;
+/// without fee history, eth_gasPrice spread by -10%/+25%
+fn tiers_from(gas_price_wei: u128, fee_history: Option<&Value>, updated_at: i64) -> GasPriceTiers {
+    let from_history = fee_history.and_then(|history| {
+        // One entry more than the blocks sampled; the last is the next block's base fee
+        let base_fee = parse_quantity(history["baseFeePerGas"].as_array()?.last()?).ok()?;
+        let rewards = history["reward"].as_array()?;
+
+        let mut tiers = [0u128; 3];
+        for (i, tier) in tiers.iter_mut().enumerate() {
+            let mut column: Vec<u128> = rewards
+                .iter()
+                .filter_map(|block| parse_quantity(block.get(i)?).ok())
+                .collect();
+            if column.is_empty() {
+                return None;
+            }
+            column.sort_unstable();
+            *tier = base_fee + column[column.len() / 2];
+        }
+        Some(tiers)
+    });
+
+    let [slow, standard, fast] =
+        from_history.unwrap_or([gas_price_wei * 9 / 10, gas_price_wei, gas_price_wei * 5 / 4]);
+
+    GasPriceTiers {
+        slow_gwei: slow as f64 / WEI_PER_GWEI,
+        standard_gwei: standard as f64 / WEI_PER_GWEI,
+        fast_gwei: fast as f64 / WEI_PER_GWEI,
+        updated_at,
+    }
+}
+
+/// Hex JSON-RPC quantity ("0x3b9aca00") to u128
+fn parse_quantity(value: &Value) -> IdosResult<u128> {
+    value
+        .as_str()
+        .and_then(|hex| u128::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+        .ok_or_else(|| IdosError::SerializationError(format!("Invalid quantity: {}", value)))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn rpc(rpc_url: &str, method: &str, params: Value) -> IdosResult<Value> {
+    use ethers::providers::{Http, Provider};
+
+    let provider = Provider::<Http>::try_from(rpc_url)
+        .map_err(|e| IdosError::ConfigurationError(format!("Invalid RPC URL: {}", e)))?;
+    provider
+        .request(method, params)
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("{} failed: {}", method, e)))
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn rpc(rpc_url: &str, method: &str, params: Value) -> IdosResult<Value> {
+    super::helper::send_rpc_request(rpc_url, method, params).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiers_from_fee_history() {
+        let history = json!({
+            "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00", "0x77359400"],
+            "reward": [
+                ["0x5f5e100", "0x3b9aca00", "0x77359400"],
+                ["0x5f5e100", "0x3b9aca00", "0xb2d05e00"],
+            ],
+        });
+
+        let tiers = tiers_from(30_000_000_000, Some(&history), 1700000000);
+        assert_eq!(tiers.slow_gwei, 2.1);
+        assert_eq!(tiers.standard_gwei, 3.0);
+        assert_eq!(tiers.fast_gwei, 5.0);
+        assert_eq!(tiers.gwei(GasSpeed::Standard), 3.0);
+    }
+
+    #[test]
+    fn test_tiers_without_fee_history() {
+        let tiers = tiers_from(20_000_000_000, None, 1700000000);
+        assert_eq!(tiers.slow_gwei, 18.0);
+        assert_eq!(tiers.standard_gwei, 20.0);
+        assert_eq!(tiers.fast_gwei, 25.0);
+    }
+}
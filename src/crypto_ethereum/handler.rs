@@ -1,7 +1,15 @@
 /// Ethereum wallet handler - WASM compatible
-use super::dto::*;
-use crate::{IdosClient, IdosError, IdosResult};
+use super::{
+    dto::*, gas_oracle::GasOracle, nft_indexer::NftIndexer, pending::PendingTransactionStore,
+    permit::PermitSupport,
+};
+use crate::transaction_history::{TransactionChain, TransactionHistory, TransactionStatus};
+use crate::{storage::Storage, IdosClient, IdosError, IdosResult};
 use bevy::prelude::Resource;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 
 #[cfg(not(target_arch = "wasm32"))]
 use ethers::{
@@ -18,27 +26,57 @@ use web_sys::window;
 
 #[cfg(target_arch = "wasm32")]
 use super::helper::{
-    eth_call_allowance, eth_call_balance_of, eth_get_balance, eth_get_transaction_receipt,
+    eth_call, eth_call_allowance, eth_call_balance_of, eth_get_balance,
+    eth_get_transaction_receipt, eth_send_raw_transaction,
 };
 
+// ERC20 metadata selectors
+const NAME_SELECTOR: [u8; 4] = [0x06, 0xfd, 0xde, 0x03];
+const SYMBOL_SELECTOR: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+const DECIMALS_SELECTOR: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+
+// EIP-2612 selectors
+const DOMAIN_SEPARATOR_SELECTOR: [u8; 4] = [0x36, 0x44, 0xe5, 0x15];
+const NONCES_SELECTOR: [u8; 4] = [0x7e, 0xce, 0xbe, 0x00];
+
+/// Transactions not mined after a day were dropped or replaced
+const PENDING_TIMEOUT_SECS: i64 = 24 * 60 * 60;
+
 #[derive(Resource, Clone)]
 pub struct EthereumHandler {
     client: IdosClient,
     settings: BlockchainSettings,
     #[cfg(not(target_arch = "wasm32"))]
     provider: Option<Provider<Http>>,
+    token_info_cache: Arc<RwLock<HashMap<String, TokenInfo>>>,
+    cache_storage: Storage,
+    nft_indexer: Option<NftIndexer>,
+    pending_store: PendingTransactionStore,
+    gas_oracle: GasOracle,
+    /// Shared history of sent transactions, set by EthereumPlugin
+    history: Option<TransactionHistory>,
 }
 
 impl EthereumHandler {
     pub fn new(client: IdosClient, settings: BlockchainSettings) -> Self {
         #[cfg(not(target_arch = "wasm32"))]
         let provider = Provider::<Http>::try_from(&settings.rpc_url).ok();
+        let cache_storage = Storage::cache(client.config(), "eth_token_info_");
+        let nft_indexer = settings.nft_indexer.clone().map(NftIndexer::new);
+        let pending_store =
+            PendingTransactionStore::new(Storage::cache(client.config(), "eth_pending_tx_"));
 
         Self {
             client,
             settings,
             #[cfg(not(target_arch = "wasm32"))]
             provider,
+            token_info_cache: Arc::new(RwLock::new(HashMap::new())),
+            cache_storage,
+            nft_indexer,
+            pending_store,
+            gas_oracle: GasOracle::new(),
+            history: None,
         }
     }
 
@@ -62,6 +100,25 @@ impl EthereumHandler {
         wallet_address.map_or(false, |addr| !addr.is_empty())
     }
 
+    /// Resolve an ENS name ("alice.eth") to a checksummed address
+    /// Addresses are returned unchanged, so withdrawal forms can accept either
+    pub async fn resolve_address(&self, name: &str) -> IdosResult<String> {
+        let name = name.trim();
+        if super::ens::is_address(name) {
+            return Ok(name.to_string());
+        }
+        super::ens::resolve_name(&self.settings.rpc_url, name)
+            .await?
+            .ok_or_else(|| {
+                IdosError::InvalidInput(format!("{} does not resolve to an address", name))
+            })
+    }
+
+    /// Primary ENS name of an address, or None when it has none
+    pub async fn lookup_address(&self, address: &str) -> IdosResult<Option<String>> {
+        super::ens::lookup_address(&self.settings.rpc_url, address).await
+    }
+
     /// Get native token balance (ETH, MATIC, BNB, etc.)
     pub async fn get_native_balance(&self, wallet_address: &str) -> IdosResult<String> {
         #[cfg(not(target_arch = "wasm32"))]
@@ -261,7 +318,417 @@ impl EthereumHandler {
         self.client.post("wallet/transaction", &request).await
     }
 
+    /// Broadcast a raw signed transaction (0x-prefixed hex), returns the transaction hash
+    pub async fn broadcast_raw_transaction(&self, raw_transaction: &str) -> IdosResult<String> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(provider) = &self.provider {
+                let raw = hex::decode(raw_transaction.trim_start_matches("0x")).map_err(|e| {
+                    IdosError::InvalidInput(format!("Invalid raw transaction: {}", e))
+                })?;
+
+                let pending_tx = provider
+                    .send_raw_transaction(Bytes::from(raw))
+                    .await
+                    .map_err(|e| IdosError::NetworkError(e.to_string()))?;
+
+                let tx_hash = format!("{:?}", pending_tx.tx_hash());
+                self.record_transaction(&tx_hash, "transaction");
+                Ok(tx_hash)
+            } else {
+                Err(IdosError::ConfigurationError(
+                    "Provider not initialized".to_string(),
+                ))
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let tx_hash = eth_send_raw_transaction(&self.settings.rpc_url, raw_transaction).await?;
+            self.record_transaction(&tx_hash, "transaction");
+            Ok(tx_hash)
+        }
+    }
+
+    /// Gas prices refreshed by EthereumPlugin every `gas_oracle_interval`
+    pub fn gas_oracle(&self) -> &GasOracle {
+        &self.gas_oracle
+    }
+
+    /// Query the node for current gas prices
+    pub async fn refresh_gas_prices(&self) -> IdosResult<GasPriceTiers> {
+        self.gas_oracle.refresh(&self.settings.rpc_url).await
+    }
+
+    /// Gas price to send with, in gwei
+    /// Uses the oracle tier for `speed` when there is one, otherwise `gas_price_gwei` from the settings
+    pub fn gas_price_gwei(&self, speed: Option<GasSpeed>) -> f64 {
+        speed
+            .and_then(|speed| self.gas_oracle.price_gwei(speed))
+            .unwrap_or(self.settings.gas_price_gwei)
+    }
+
+    /// Record transactions sent by the SDK in `history` (EthereumPlugin shares the app's history)
+    pub fn set_transaction_history(&mut self, history: TransactionHistory) {
+        self.history = Some(history);
+    }
+
+    /// History of transactions sent by the SDK, when EthereumPlugin set one
+    pub fn transaction_history(&self) -> Option<&TransactionHistory> {
+        self.history.as_ref()
+    }
+
+    /// Track a transaction sent by the SDK until it is mined
+    pub(crate) fn record_transaction(&self, transaction_hash: &str, label: &str) {
+        if let Some(history) = &self.history {
+            history
+                .record(
+                    &transaction_hash.to_lowercase(),
+                    TransactionChain::Ethereum(self.settings.chain_id),
+                    Some(label),
+                )
+                .ok();
+        }
+    }
+
+    /// Check every pending transaction of this chain in the history once
+    /// Transactions not mined within a day are marked failed
+    pub async fn refresh_transaction_history(&self) -> IdosResult<()> {
+        let Some(history) = &self.history else {
+            return Ok(());
+        };
+        let now = chrono::Utc::now().timestamp();
+
+        for transaction in history.pending(TransactionChain::Ethereum(self.settings.chain_id)) {
+            let (status, error) = match self.wait_for_transaction(&transaction.id, 1).await {
+                Ok(receipt) if receipt_succeeded(&receipt) => (TransactionStatus::Confirmed, None),
+                Ok(_) => (
+                    TransactionStatus::Failed,
+                    Some("Transaction reverted".to_string()),
+                ),
+                Err(_) if now - transaction.sent_at > PENDING_TIMEOUT_SECS => (
+                    TransactionStatus::Failed,
+                    Some("Transaction was not mined".to_string()),
+                ),
+                Err(_) => continue,
+            };
+            history.set_status(&transaction.id, status, error)?;
+        }
+
+        Ok(())
+    }
+
+    /// Persist a sent transaction so it can be resumed after a restart
+    pub fn track_transaction(&self, transaction: PendingEthTransaction) -> IdosResult<()> {
+        self.pending_store.insert(transaction)
+    }
+
+    /// Stop tracking a transaction once it has been fully handled
+    pub fn complete_transaction(&self, transaction_hash: &str) -> IdosResult<()> {
+        self.pending_store.remove(transaction_hash)
+    }
+
+    /// Transactions sent on this chain that aren't resolved yet
+    pub fn pending_transactions(&self) -> IdosResult<Vec<PendingEthTransaction>> {
+        Ok(self
+            .pending_store
+            .list()?
+            .into_iter()
+            .filter(|t| t.chain_id == self.settings.chain_id)
+            .collect())
+    }
+
+    /// Wait for every tracked transaction and finish its backend submission
+    /// Called on startup; anything still unmined stays tracked for the next pass
+    pub async fn resume_pending_transactions(
+        &self,
+        max_attempts: u32,
+    ) -> IdosResult<Vec<PendingTransactionResult>> {
+        let mut results = Vec::new();
+
+        for transaction in self.pending_transactions()? {
+            let status = match self
+                .wait_for_transaction(&transaction.transaction_hash, max_attempts)
+                .await
+            {
+                Ok(receipt) if receipt_succeeded(&receipt) => {
+                    let submitted = !transaction.submit_to_backend
+                        || self
+                            .submit_transaction(
+                                &transaction.transaction_hash,
+                                transaction.transaction_type,
+                                transaction.direction,
+                            )
+                            .await
+                            .is_ok();
+
+                    if submitted {
+                        self.complete_transaction(&transaction.transaction_hash)?;
+                        PendingTransactionStatus::Confirmed
+                    } else {
+                        PendingTransactionStatus::Pending
+                    }
+                }
+                Ok(_) => {
+                    self.complete_transaction(&transaction.transaction_hash)?;
+                    PendingTransactionStatus::Reverted
+                }
+                Err(_) => PendingTransactionStatus::Pending,
+            };
+
+            results.push(PendingTransactionResult {
+                transaction,
+                status,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Get ERC20 symbol, name and decimals
+    /// Cached in memory and in the SDK cache (cache_dir on native, localStorage on WASM)
+    pub async fn get_token_info(&self, token_address: &str) -> IdosResult<TokenInfo> {
+        let cache_key = format!(
+            "{}_{}",
+            self.settings.chain_id,
+            token_address.to_lowercase()
+        );
+
+        if let Some(info) = self
+            .token_info_cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&cache_key).cloned())
+        {
+            return Ok(info);
+        }
+
+        if let Some(info) = self
+            .cache_storage
+            .get(&cache_key)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str::<TokenInfo>(&json).ok())
+        {
+            if let Ok(mut cache) = self.token_info_cache.write() {
+                cache.insert(cache_key, info.clone());
+            }
+            return Ok(info);
+        }
+
+        let name = self.call_contract(token_address, &NAME_SELECTOR).await?;
+        let symbol = self.call_contract(token_address, &SYMBOL_SELECTOR).await?;
+        let decimals = self
+            .call_contract(token_address, &DECIMALS_SELECTOR)
+            .await?;
+
+        let info = TokenInfo {
+            address: token_address.to_string(),
+            symbol: decode_string_result(&symbol)?,
+            name: decode_string_result(&name)?,
+            decimals: decimals
+                .get(..32)
+                .map(|word| word[31])
+                .ok_or_else(|| IdosError::InvalidInput("Invalid decimals result".to_string()))?,
+        };
+
+        if let Ok(json) = serde_json::to_string(&info) {
+            // Cache write failures only cost a refetch
+            self.cache_storage.set(&cache_key, &json).ok();
+        }
+        if let Ok(mut cache) = self.token_info_cache.write() {
+            cache.insert(cache_key, info.clone());
+        }
+
+        Ok(info)
+    }
+
+    /// Get metadata for every token in `BlockchainSettings.token_contract_addresses`
+    /// Keyed the same way as the settings map
+    pub async fn get_configured_token_infos(&self) -> IdosResult<HashMap<String, TokenInfo>> {
+        let mut infos = HashMap::new();
+        for (key, address) in &self.settings.token_contract_addresses {
+            infos.insert(key.clone(), self.get_token_info(address).await?);
+        }
+        Ok(infos)
+    }
+
+    /// Domain separator and `owner`'s permit nonce, or None if the token doesn't implement EIP-2612
+    pub async fn get_permit_support(
+        &self,
+        token_address: &str,
+        owner_address: &str,
+    ) -> IdosResult<Option<PermitSupport>> {
+        let owner: ethers::types::Address = owner_address
+            .parse()
+            .map_err(|_| IdosError::InvalidInput("Invalid owner address".to_string()))?;
+
+        // Tokens without permit revert (or return nothing) on these calls;
+        // network errors are passed up so a flaky RPC doesn't silently switch flows
+        let domain_separator = match self
+            .call_contract(token_address, &DOMAIN_SEPARATOR_SELECTOR)
+            .await
+        {
+            Err(IdosError::TransactionReverted(_)) => return Ok(None),
+            result => result?,
+        };
+        let mut nonces_call = NONCES_SELECTOR.to_vec();
+        nonces_call.extend_from_slice(&[0u8; 12]);
+        nonces_call.extend_from_slice(owner.as_bytes());
+        let nonce = match self.call_contract(token_address, &nonces_call).await {
+            Err(IdosError::TransactionReverted(_)) => return Ok(None),
+            result => result?,
+        };
+
+        match (domain_separator.get(..32), nonce.get(..32)) {
+            (Some(domain_separator), Some(nonce)) => Ok(Some(PermitSupport {
+                domain_separator: domain_separator.try_into().unwrap_or_default(),
+                nonce: ethers::types::U256::from_big_endian(nonce),
+            })),
+            _ => Ok(None),
+        }
+    }
+
+    /// List NFTs owned by a wallet through the configured indexer
+    /// Limited to `contract_addresses`, or the configured NFT contract when empty
+    pub async fn get_owned_nfts(
+        &self,
+        owner_address: &str,
+        contract_addresses: &[String],
+    ) -> IdosResult<Vec<OwnedNft>> {
+        let indexer = self.nft_indexer.as_ref().ok_or_else(|| {
+            IdosError::ConfigurationError("NFT indexer not configured".to_string())
+        })?;
+
+        let mut contracts = contract_addresses.to_vec();
+        if contracts.is_empty() && !self.settings.nft_contract_address.is_empty() {
+            contracts.push(self.settings.nft_contract_address.clone());
+        }
+
+        indexer.get_owned_nfts(owner_address, &contracts).await
+    }
+
+    /// Raw eth_call against a contract
+    /// A revert comes back as `IdosError::TransactionReverted`, other failures as `NetworkError`
+    async fn call_contract(&self, contract_address: &str, data: &[u8]) -> IdosResult<Vec<u8>> {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let result = eth_call(
+                &self.settings.rpc_url,
+                contract_address,
+                &format!("0x{}", hex::encode(data)),
+            )
+            .await
+            .map_err(|e| match e {
+                IdosError::NetworkError(message) if message.contains("revert") => {
+                    IdosError::TransactionReverted(
+                        message.trim_start_matches("RPC Error: ").to_string(),
+                    )
+                }
+                other => other,
+            })?;
+
+            hex::decode(result.trim_start_matches("0x"))
+                .map_err(|e| IdosError::SerializationError(format!("Invalid call result: {}", e)))
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let provider = self.provider.as_ref().ok_or_else(|| {
+                IdosError::ConfigurationError("Provider not initialized".to_string())
+            })?;
+
+            let contract: Address = contract_address
+                .parse()
+                .map_err(|_| IdosError::InvalidInput("Invalid contract address".to_string()))?;
+
+            let call_data = ethers::types::transaction::eip2718::TypedTransaction::Legacy(
+                ethers::types::TransactionRequest {
+                    to: Some(ethers::types::NameOrAddress::Address(contract)),
+                    data: Some(Bytes::from(data.to_vec())),
+                    ..Default::default()
+                },
+            );
+
+            let result = provider.call(&call_data, None).await.map_err(|e| {
+                if super::simulation::is_revert(&e) {
+                    IdosError::TransactionReverted(e.to_string())
+                } else {
+                    IdosError::NetworkError(e.to_string())
+                }
+            })?;
+
+            Ok(result.to_vec())
+        }
+    }
+
+    /// Estimate the full cost of a transaction at the configured gas price
+    /// Includes the L1 data fee on OP-stack rollups, see `ChainFeeModel`
+    pub async fn estimate_total_cost(
+        &self,
+        from_address: &str,
+        to_address: &str,
+        data: Option<&str>,
+        value_wei: Option<&str>,
+    ) -> IdosResult<TotalCostEstimate> {
+        use ethers::types::{Address, TransactionRequest, U256};
+
+        let from: Address = from_address
+            .parse()
+            .map_err(|_| IdosError::InvalidInput("Invalid from address".to_string()))?;
+        let to: Address = to_address
+            .parse()
+            .map_err(|_| IdosError::InvalidInput("Invalid to address".to_string()))?;
+
+        let mut tx = TransactionRequest::new().from(from).to(to);
+
+        if let Some(data_hex) = data {
+            let bytes = hex::decode(data_hex.trim_start_matches("0x"))
+                .map_err(|e| IdosError::InvalidInput(format!("Invalid data hex: {}", e)))?;
+            tx = tx.data(bytes);
+        }
+
+        if let Some(value_str) = value_wei {
+            let value: U256 = value_str
+                .parse()
+                .map_err(|_| IdosError::InvalidInput("Invalid value".to_string()))?;
+            tx = tx.value(value);
+        }
+
+        let gas_price = ethers::utils::parse_units(self.settings.gas_price_gwei, "gwei")
+            .map_err(|e| IdosError::InvalidInput(format!("Invalid gas price: {}", e)))?;
+
+        super::fees::estimate_total_cost(
+            &self.settings.rpc_url,
+            self.settings.chain_id as u64,
+            &tx.into(),
+            Some(gas_price.into()),
+        )
+        .await
+    }
+
+    /// Check if the wallet can pay for a transaction estimated with `estimate_total_cost`
+    /// Prefer this over `has_sufficient_gas` on rollups
+    pub async fn has_sufficient_balance_for_cost(
+        &self,
+        wallet_address: &str,
+        estimate: &TotalCostEstimate,
+    ) -> IdosResult<bool> {
+        let balance_wei = self.get_native_balance(wallet_address).await?;
+
+        let balance: u128 = balance_wei
+            .parse()
+            .map_err(|_| IdosError::InvalidInput("Invalid balance format".to_string()))?;
+        let required: u128 = estimate
+            .total_cost_wei
+            .parse()
+            .map_err(|_| IdosError::InvalidInput("Invalid cost estimate".to_string()))?;
+
+        Ok(balance >= required)
+    }
+
     /// Check if sufficient balance for gas
+    /// Ignores rollup L1 data fees; use `estimate_total_cost` for L2s
     pub async fn has_sufficient_gas(
         &self,
         wallet_address: &str,
@@ -348,3 +815,29 @@ impl EthereumHandler {
         }
     }
 }
+
+/// Receipt status is "1" natively and "0x1" from raw JSON-RPC; pre-Byzantium receipts have none
+fn receipt_succeeded(receipt: &EthTransactionReceipt) -> bool {
+    matches!(receipt.status.as_deref(), None | Some("1") | Some("0x1"))
+}
+
+/// Decode a `string` return value, falling back to `bytes32` (used by e.g. MKR)
+fn decode_string_result(data: &[u8]) -> IdosResult<String> {
+    use ethers::abi::{decode, ParamType, Token};
+
+    if let Ok(tokens) = decode(&[ParamType::String], data) {
+        if let Some(Token::String(value)) = tokens.into_iter().next() {
+            return Ok(value);
+        }
+    }
+
+    if data.len() == 32 {
+        let end = data.iter().position(|b| *b == 0).unwrap_or(32);
+        return String::from_utf8(data[..end].to_vec())
+            .map_err(|_| IdosError::InvalidInput("Invalid token string".to_string()));
+    }
+
+    Err(IdosError::InvalidInput(
+        "Contract did not return a string".to_string(),
+    ))
+}
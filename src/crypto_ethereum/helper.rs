@@ -80,6 +80,18 @@ pub async fn eth_get_balance(rpc_url: &str, address: &str) -> IdosResult<String>
     send_rpc_request::<String>(rpc_url, "eth_getBalance", params).await
 }
 
+/// Generic eth_call returning the raw hex result (WASM only)
+#[cfg(target_arch = "wasm32")]
+pub async fn eth_call(rpc_url: &str, to: &str, data: &str) -> IdosResult<String> {
+    let call_data = serde_json::json!({
+        "to": to,
+        "data": data
+    });
+
+    let params = serde_json::json!([call_data, "latest"]);
+    send_rpc_request::<String>(rpc_url, "eth_call", params).await
+}
+
 /// Call ERC20 balanceOf function (WASM only)
 #[cfg(target_arch = "wasm32")]
 pub async fn eth_call_balance_of(
@@ -135,12 +147,33 @@ pub async fn eth_get_transaction_receipt(
 }
 
 /// Get transaction count (nonce) (WASM only)
+/// Counts pending transactions so back-to-back sends don't reuse a nonce
 #[cfg(target_arch = "wasm32")]
 pub async fn eth_get_transaction_count(rpc_url: &str, address: &str) -> IdosResult<String> {
-    let params = serde_json::json!([address, "latest"]);
+    let params = serde_json::json!([address, "pending"]);
     send_rpc_request::<String>(rpc_url, "eth_getTransactionCount", params).await
 }
 
+/// Estimate gas for a call (WASM only)
+#[cfg(target_arch = "wasm32")]
+pub async fn eth_estimate_gas(
+    rpc_url: &str,
+    from: &str,
+    to: &str,
+    data: &str,
+    value: &str,
+) -> IdosResult<String> {
+    let call_data = serde_json::json!({
+        "from": from,
+        "to": to,
+        "data": data,
+        "value": value
+    });
+
+    let params = serde_json::json!([call_data]);
+    send_rpc_request::<String>(rpc_url, "eth_estimateGas", params).await
+}
+
 /// Send raw transaction (WASM only)
 #[cfg(target_arch = "wasm32")]
 pub async fn eth_send_raw_transaction(
@@ -250,6 +283,34 @@ pub async fn metamask_send_transaction(transaction: EthTransaction) -> IdosResul
     Ok(tx_hash)
 }
 
+/// Sign a text message with MetaMask's `personal_sign` (WASM only)
+#[cfg(target_arch = "wasm32")]
+pub async fn metamask_personal_sign(message: &str, address: &str) -> IdosResult<String> {
+    if !is_metamask_available() {
+        return Err(IdosError::PlatformNotSupported(
+            "MetaMask not available".to_string(),
+        ));
+    }
+
+    let request = serde_json::json!({
+        "method": "personal_sign",
+        "params": [format!("0x{}", hex::encode(message.as_bytes())), address]
+    });
+
+    let request_js = serde_wasm_bindgen::to_value(&request)
+        .map_err(|e| IdosError::SerializationError(e.to_string()))?;
+
+    let promise = ethereum_request(request_js);
+    let result = wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map_err(|e| IdosError::Wallet(format!("MetaMask signing failed: {:?}", e)))?;
+
+    let signature: String = serde_wasm_bindgen::from_value(result)
+        .map_err(|e| IdosError::SerializationError(e.to_string()))?;
+
+    Ok(signature)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn placeholder_for_native() {
     // This module is primarily for WASM, native implementations are in handler.rs
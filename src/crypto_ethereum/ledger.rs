@@ -0,0 +1,237 @@
+/// Ledger hardware wallet signer
+/// Speaks the Ledger Ethereum app APDU protocol over a pluggable HID/WebUSB transport
+use crate::{IdosError, IdosResult};
+use async_trait::async_trait;
+use ethers::{
+    signers::Signer,
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Address, Signature, U256,
+    },
+};
+use std::sync::Arc;
+
+use crate::ledger::{build_apdu, check_status, exchange_chunked};
+pub use crate::ledger::{
+    encode_derivation_path, unwrap_hid_response, wrap_hid_apdu, LedgerTransport, LEDGER_PACKET_SIZE,
+};
+
+/// Default Ledger Live derivation path for the first Ethereum account
+pub const LEDGER_DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+
+// Ethereum app APDU constants
+const INS_GET_PUBLIC_KEY: u8 = 0x02;
+const INS_SIGN: u8 = 0x04;
+const INS_SIGN_PERSONAL_MESSAGE: u8 = 0x08;
+const INS_SIGN_EIP712: u8 = 0x0C;
+const P1_NON_CONFIRM: u8 = 0x00;
+const P1_CONFIRM: u8 = 0x01;
+const P2_NO_CHAINCODE: u8 = 0x00;
+
+/// Ethereum signer backed by a Ledger device
+#[derive(Clone)]
+pub struct LedgerSigner {
+    transport: Arc<dyn LedgerTransport>,
+    derivation_path: String,
+    address: Address,
+    chain_id: u64,
+}
+
+impl std::fmt::Debug for LedgerSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LedgerSigner")
+            .field("derivation_path", &self.derivation_path)
+            .field("address", &self.address)
+            .field("chain_id", &self.chain_id)
+            .finish()
+    }
+}
+
+impl LedgerSigner {
+    /// Connect to the Ethereum app and resolve the address for `derivation_path`
+    pub async fn connect(
+        transport: Arc<dyn LedgerTransport>,
+        derivation_path: &str,
+        chain_id: u64,
+    ) -> IdosResult<Self> {
+        let address = get_ledger_address(transport.as_ref(), derivation_path, false).await?;
+
+        Ok(Self {
+            transport,
+            derivation_path: derivation_path.to_string(),
+            address,
+            chain_id,
+        })
+    }
+
+    /// Signer for an address already read from the device (e.g. a paired in-game hardware wallet)
+    pub(crate) fn from_paired(
+        transport: Arc<dyn LedgerTransport>,
+        derivation_path: &str,
+        address: Address,
+        chain_id: u64,
+    ) -> Self {
+        Self {
+            transport,
+            derivation_path: derivation_path.to_string(),
+            address,
+            chain_id,
+        }
+    }
+
+    /// Derivation path used for signing
+    pub fn derivation_path(&self) -> &str {
+        &self.derivation_path
+    }
+
+    /// Ask the device to display the address so the player can verify it
+    pub async fn verify_address_on_device(&self) -> IdosResult<Address> {
+        get_ledger_address(self.transport.as_ref(), &self.derivation_path, true).await
+    }
+
+    async fn sign_payload(&self, ins: u8, payload: &[u8]) -> IdosResult<Signature> {
+        let response =
+            exchange_chunked(self.transport.as_ref(), ins, P2_NO_CHAINCODE, payload).await?;
+        parse_signature(&response)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for LedgerSigner {
+    type Error = IdosError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        let message = message.as_ref();
+
+        let mut payload = encode_derivation_path(&self.derivation_path)?;
+        payload.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        payload.extend_from_slice(message);
+
+        self.sign_payload(INS_SIGN_PERSONAL_MESSAGE, &payload).await
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        let mut tx_with_chain = tx.clone();
+        if tx_with_chain.chain_id().is_none() {
+            tx_with_chain.set_chain_id(self.chain_id);
+        }
+
+        let mut payload = encode_derivation_path(&self.derivation_path)?;
+        payload.extend_from_slice(tx_with_chain.rlp().as_ref());
+
+        let mut signature = self.sign_payload(INS_SIGN, &payload).await?;
+
+        // The device only returns one byte for `v`, so rebuild it for large chain IDs
+        // (same logic as Ledger's hw-app-eth)
+        let eip155_chain_id = self.chain_id * 2 + 35;
+        if eip155_chain_id + 1 > 255 {
+            let one_byte_chain_id = eip155_chain_id % 256;
+            let ecc_parity = signature.v.abs_diff(one_byte_chain_id);
+
+            signature.v = match tx {
+                TypedTransaction::Legacy(_) => eip155_chain_id + ecc_parity,
+                _ => (ecc_parity % 2 != 1) as u64,
+            };
+        }
+
+        Ok(signature)
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        let domain_separator = payload
+            .domain_separator()
+            .map_err(|e| IdosError::InvalidInput(format!("Invalid EIP-712 domain: {}", e)))?;
+        let struct_hash = payload
+            .struct_hash()
+            .map_err(|e| IdosError::InvalidInput(format!("Invalid EIP-712 struct: {}", e)))?;
+
+        let mut data = encode_derivation_path(&self.derivation_path)?;
+        data.extend_from_slice(&domain_separator);
+        data.extend_from_slice(&struct_hash);
+
+        self.sign_payload(INS_SIGN_EIP712, &data).await
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn with_chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+}
+
+/// Read the Ethereum address for a derivation path, optionally confirming it on screen
+pub async fn get_ledger_address(
+    transport: &dyn LedgerTransport,
+    derivation_path: &str,
+    display: bool,
+) -> IdosResult<Address> {
+    let path = encode_derivation_path(derivation_path)?;
+    let p1 = if display { P1_CONFIRM } else { P1_NON_CONFIRM };
+    let apdu = build_apdu(INS_GET_PUBLIC_KEY, p1, P2_NO_CHAINCODE, &path);
+
+    let response = check_status(transport.exchange(&apdu).await?)?;
+    parse_address_response(&response)
+}
+
+fn parse_address_response(response: &[u8]) -> IdosResult<Address> {
+    let short = || IdosError::Wallet("Short address response from Ledger".to_string());
+
+    let pubkey_len = *response.first().ok_or_else(short)? as usize;
+    let offset = 1 + pubkey_len;
+    let address_len = *response.get(offset).ok_or_else(short)? as usize;
+    let address_str = response
+        .get(offset + 1..offset + 1 + address_len)
+        .ok_or_else(short)?;
+
+    let address_str = std::str::from_utf8(address_str)
+        .map_err(|_| IdosError::Wallet("Invalid address encoding from Ledger".to_string()))?;
+
+    format!("0x{}", address_str)
+        .parse()
+        .map_err(|_| IdosError::Wallet("Invalid address from Ledger".to_string()))
+}
+
+fn parse_signature(response: &[u8]) -> IdosResult<Signature> {
+    if response.len() < 65 {
+        return Err(IdosError::Wallet(format!(
+            "Short signature response from Ledger: {} bytes",
+            response.len()
+        )));
+    }
+
+    Ok(Signature {
+        v: response[0] as u64,
+        r: U256::from_big_endian(&response[1..33]),
+        s: U256::from_big_endian(&response[33..65]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_derivation_path() {
+        let bytes = encode_derivation_path(LEDGER_DEFAULT_DERIVATION_PATH).unwrap();
+        assert_eq!(bytes[0], 5);
+        assert_eq!(&bytes[1..5], &0x8000_002Cu32.to_be_bytes());
+        assert_eq!(&bytes[5..9], &0x8000_003Cu32.to_be_bytes());
+        assert_eq!(&bytes[17..21], &0u32.to_be_bytes());
+
+        assert!(encode_derivation_path("m/44'/abc").is_err());
+    }
+}
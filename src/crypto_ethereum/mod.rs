@@ -1,13 +1,40 @@
 /// Ethereum wallet integration module
 pub mod dto;
+pub mod ens;
 pub mod ethereum_plugin;
+pub mod fees;
+pub mod gas_oracle;
 pub mod handler;
 mod helper;
+pub mod ledger;
+pub mod nft_indexer;
+pub mod pending;
+pub mod permit;
 pub mod service;
+pub mod signer;
+pub mod simulation;
+pub mod siwe;
 pub mod transactions;
+#[cfg(all(feature = "walletconnect", not(target_arch = "wasm32")))]
+pub mod walletconnect;
+#[cfg(all(feature = "walletconnect", not(target_arch = "wasm32")))]
+mod walletconnect_crypto;
 
 pub use dto::*;
 pub use ethereum_plugin::EthereumPlugin;
+pub use fees::ChainFeeModel;
+pub use gas_oracle::GasOracle;
 pub use handler::EthereumHandler;
+pub use ledger::{LedgerSigner, LedgerTransport};
+pub use nft_indexer::NftIndexer;
+pub use pending::PendingTransactionStore;
+pub use permit::{Erc2612Permit, PermitSignature, PermitSupport};
 pub use service::EthereumWalletService;
+pub use signer::{EthereumSigner, EthereumSignerSource};
+pub use siwe::{SiweMessage, SiweSigner};
 pub use transactions::*;
+#[cfg(all(feature = "walletconnect", not(target_arch = "wasm32")))]
+pub use walletconnect::{
+    WalletConnectClient, WalletConnectConfig, WalletConnectEvent, WalletConnectMetadata,
+    WalletConnectPlugin, WalletConnectSession,
+};
@@ -0,0 +1,241 @@
+/// NFT inventory via indexer APIs (Alchemy / Moralis style)
+/// ERC-1155 has no on-chain enumeration, so "my NFTs" screens need an indexer
+use super::dto::*;
+use crate::{IdosError, IdosResult};
+use serde::Deserialize;
+
+/// Max pages fetched per inventory request
+const MAX_PAGES: usize = 20;
+
+/// Client for an NFT indexer API
+#[derive(Clone)]
+pub struct NftIndexer {
+    http_client: reqwest::Client,
+    config: NftIndexerConfig,
+}
+
+impl NftIndexer {
+    pub fn new(config: NftIndexerConfig) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &NftIndexerConfig {
+        &self.config
+    }
+
+    /// List NFTs owned by `owner_address`, optionally limited to some contracts
+    pub async fn get_owned_nfts(
+        &self,
+        owner_address: &str,
+        contract_addresses: &[String],
+    ) -> IdosResult<Vec<OwnedNft>> {
+        let mut nfts = Vec::new();
+        let mut page_key: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let (page, next) = match self.config.provider {
+                NftIndexerProvider::Alchemy => {
+                    self.fetch_alchemy_page(owner_address, contract_addresses, page_key.as_deref())
+                        .await?
+                }
+                NftIndexerProvider::Moralis => {
+                    self.fetch_moralis_page(owner_address, contract_addresses, page_key.as_deref())
+                        .await?
+                }
+            };
+
+            nfts.extend(page);
+
+            match next {
+                Some(next) if !next.is_empty() => page_key = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(nfts)
+    }
+
+    /// Alchemy NFT API v3 `getNFTsForOwner`
+    async fn fetch_alchemy_page(
+        &self,
+        owner_address: &str,
+        contract_addresses: &[String],
+        page_key: Option<&str>,
+    ) -> IdosResult<(Vec<OwnedNft>, Option<String>)> {
+        let url = format!(
+            "{}/getNFTsForOwner",
+            self.config.base_url.trim_end_matches('/')
+        );
+
+        let mut query: Vec<(&str, &str)> = vec![("owner", owner_address), ("withMetadata", "true")];
+        for contract in contract_addresses {
+            query.push(("contractAddresses[]", contract));
+        }
+        if let Some(page_key) = page_key {
+            query.push(("pageKey", page_key));
+        }
+
+        let mut request = self.http_client.get(&url).query(&query);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response: AlchemyOwnedNftsResponse = send(request, &url).await?;
+
+        let nfts = response
+            .owned_nfts
+            .into_iter()
+            .map(|nft| OwnedNft {
+                contract_address: nft.contract.address,
+                token_id: nft.token_id,
+                balance: nft.balance.unwrap_or_else(|| "1".to_string()),
+                token_type: nft.token_type,
+                name: nft.name,
+                image_url: nft
+                    .image
+                    .and_then(|image| image.cached_url.or(image.original_url)),
+                metadata: nft.raw.and_then(|raw| raw.metadata),
+            })
+            .collect();
+
+        Ok((nfts, response.page_key))
+    }
+
+    /// Moralis `GET /{address}/nft`
+    async fn fetch_moralis_page(
+        &self,
+        owner_address: &str,
+        contract_addresses: &[String],
+        cursor: Option<&str>,
+    ) -> IdosResult<(Vec<OwnedNft>, Option<String>)> {
+        let url = format!(
+            "{}/{}/nft",
+            self.config.base_url.trim_end_matches('/'),
+            owner_address
+        );
+
+        let mut query: Vec<(&str, &str)> = vec![("normalizeMetadata", "true")];
+        if let Some(chain) = &self.config.chain {
+            query.push(("chain", chain));
+        }
+        for contract in contract_addresses {
+            query.push(("token_addresses[]", contract));
+        }
+        if let Some(cursor) = cursor {
+            query.push(("cursor", cursor));
+        }
+
+        let mut request = self.http_client.get(&url).query(&query);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response: MoralisNftsResponse = send(request, &url).await?;
+
+        let nfts = response
+            .result
+            .into_iter()
+            .map(|nft| {
+                let metadata = nft
+                    .metadata
+                    .as_deref()
+                    .and_then(|json| serde_json::from_str(json).ok());
+                let normalized = nft.normalized_metadata.unwrap_or_default();
+
+                OwnedNft {
+                    contract_address: nft.token_address,
+                    token_id: nft.token_id,
+                    balance: nft.amount.unwrap_or_else(|| "1".to_string()),
+                    token_type: nft.contract_type,
+                    name: normalized.name.or(nft.name),
+                    image_url: normalized.image,
+                    metadata,
+                }
+            })
+            .collect();
+
+        Ok((nfts, response.cursor))
+    }
+}
+
+async fn send<T: serde::de::DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+    url: &str,
+) -> IdosResult<T> {
+    let response = request.send().await?;
+
+    if !response.status().is_success() {
+        return Err(IdosError::from_response(
+            response.status().as_u16(),
+            &response.text().await.unwrap_or_default(),
+        ));
+    }
+
+    Ok(response.json().await?)
+}
+
+// ==================== INDEXER RESPONSES ====================
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlchemyOwnedNftsResponse {
+    #[serde(default)]
+    owned_nfts: Vec<AlchemyNft>,
+    page_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlchemyNft {
+    contract: AlchemyContract,
+    token_id: String,
+    token_type: Option<String>,
+    balance: Option<String>,
+    name: Option<String>,
+    image: Option<AlchemyImage>,
+    raw: Option<AlchemyRaw>,
+}
+
+#[derive(Deserialize)]
+struct AlchemyContract {
+    address: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlchemyImage {
+    cached_url: Option<String>,
+    original_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AlchemyRaw {
+    metadata: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct MoralisNftsResponse {
+    #[serde(default)]
+    result: Vec<MoralisNft>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MoralisNft {
+    token_address: String,
+    token_id: String,
+    amount: Option<String>,
+    contract_type: Option<String>,
+    name: Option<String>,
+    metadata: Option<String>,
+    normalized_metadata: Option<MoralisNormalizedMetadata>,
+}
+
+#[derive(Deserialize, Default)]
+struct MoralisNormalizedMetadata {
+    name: Option<String>,
+    image: Option<String>,
+}
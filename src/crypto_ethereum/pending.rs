@@ -0,0 +1,114 @@
+/// Persistent store of in-flight Ethereum transactions
+/// Lets a crash mid-deposit be resumed on the next start instead of orphaning funds
+use super::dto::PendingEthTransaction;
+use crate::{storage::Storage, IdosError, IdosResult};
+use std::sync::{Arc, Mutex};
+
+const PENDING_KEY: &str = "all";
+
+/// Pending transactions, kept as a single JSON list in `Storage`
+#[derive(Clone)]
+pub struct PendingTransactionStore {
+    storage: Storage,
+    lock: Arc<Mutex<()>>,
+}
+
+impl PendingTransactionStore {
+    pub fn new(storage: Storage) -> Self {
+        Self {
+            storage,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// All tracked transactions, oldest first
+    pub fn list(&self) -> IdosResult<Vec<PendingEthTransaction>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        self.load()
+    }
+
+    /// Track a transaction, replacing any entry with the same hash
+    pub fn insert(&self, transaction: PendingEthTransaction) -> IdosResult<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut transactions = self.load()?;
+        transactions.retain(|t| {
+            !t.transaction_hash
+                .eq_ignore_ascii_case(&transaction.transaction_hash)
+        });
+        transactions.push(transaction);
+        self.save(&transactions)
+    }
+
+    /// Stop tracking a transaction
+    pub fn remove(&self, transaction_hash: &str) -> IdosResult<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut transactions = self.load()?;
+        transactions.retain(|t| !t.transaction_hash.eq_ignore_ascii_case(transaction_hash));
+        self.save(&transactions)
+    }
+
+    fn load(&self) -> IdosResult<Vec<PendingEthTransaction>> {
+        match self.storage.get(PENDING_KEY)? {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn save(&self, transactions: &[PendingEthTransaction]) -> IdosResult<()> {
+        if transactions.is_empty() {
+            return self.storage.remove(PENDING_KEY);
+        }
+
+        let json = serde_json::to_string(transactions)
+            .map_err(|e| IdosError::SerializationError(e.to_string()))?;
+        self.storage.set(PENDING_KEY, &json)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::crypto_ethereum::dto::{CryptoTransactionType, TransactionDirection};
+
+    #[test]
+    fn test_pending_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("idos_pending_test_{}", std::process::id()));
+        let store = PendingTransactionStore::new(Storage::with_directory(
+            "pending_".to_string(),
+            dir.clone(),
+        ));
+
+        let mut deposit = PendingEthTransaction::new(
+            "0xAB",
+            1,
+            CryptoTransactionType::Token,
+            TransactionDirection::Game,
+        );
+        deposit.submit_to_backend = true;
+        store.insert(deposit.clone()).unwrap();
+        store.insert(deposit).unwrap();
+        store
+            .insert(PendingEthTransaction::new(
+                "0xcd",
+                1,
+                CryptoTransactionType::NFT,
+                TransactionDirection::UsersCryptoWallet,
+            ))
+            .unwrap();
+
+        // A new store over the same directory sees the same entries
+        let reopened = PendingTransactionStore::new(Storage::with_directory(
+            "pending_".to_string(),
+            dir.clone(),
+        ));
+        let pending = reopened.list().unwrap();
+        assert_eq!(pending.len(), 2);
+        assert!(pending[0].submit_to_backend);
+
+        reopened.remove("0xab").unwrap();
+        reopened.remove("0xCD").unwrap();
+        assert!(store.list().unwrap().is_empty());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
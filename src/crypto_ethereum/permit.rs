@@ -0,0 +1,132 @@
+/// EIP-2612 permits
+/// Lets the player approve the platform pool with an off-chain signature instead of an
+/// `approve` transaction, so a deposit costs a single transaction
+use crate::IdosError;
+use ethers::{
+    abi::{encode, Token as AbiToken},
+    types::{transaction::eip712::EIP712Domain, transaction::eip712::Eip712, Address, U256},
+    utils::keccak256,
+};
+
+/// `Permit` struct type as defined by EIP-2612
+const PERMIT_TYPE: &str =
+    "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+
+/// What the token reports about its permit support for one owner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PermitSupport {
+    /// `DOMAIN_SEPARATOR()` of the token
+    pub domain_separator: [u8; 32],
+    /// `nonces(owner)`, the nonce the next permit must use
+    pub nonce: U256,
+}
+
+/// EIP-2612 permit signed by the token owner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Erc2612Permit {
+    pub token: Address,
+    pub chain_id: u64,
+    /// Domain separator read from the token, so tokens with custom names or versions work
+    pub domain_separator: [u8; 32],
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub nonce: U256,
+    /// Unix timestamp (seconds) after which the permit is rejected
+    pub deadline: U256,
+}
+
+impl Eip712 for Erc2612Permit {
+    type Error = IdosError;
+
+    fn domain_separator(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(self.domain_separator)
+    }
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(EIP712Domain {
+            name: None,
+            version: None,
+            chain_id: Some(U256::from(self.chain_id)),
+            verifying_contract: Some(self.token),
+            salt: None,
+        })
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(keccak256(PERMIT_TYPE))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(keccak256(encode(&[
+            AbiToken::FixedBytes(Self::type_hash()?.to_vec()),
+            AbiToken::Address(self.owner),
+            AbiToken::Address(self.spender),
+            AbiToken::Uint(self.value),
+            AbiToken::Uint(self.nonce),
+            AbiToken::Uint(self.deadline),
+        ])))
+    }
+}
+
+/// Split permit signature passed to `depositERC20WithPermit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PermitSignature {
+    pub deadline: U256,
+    pub v: u8,
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+}
+
+impl PermitSignature {
+    pub fn new(deadline: U256, signature: &ethers::types::Signature) -> Self {
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        signature.r.to_big_endian(&mut r);
+        signature.s.to_big_endian(&mut s);
+        Self {
+            deadline,
+            v: signature.v as u8,
+            r,
+            s,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::H256;
+
+    #[tokio::test]
+    async fn test_permit_signature() {
+        assert_eq!(
+            format!("{:?}", H256::from(Erc2612Permit::type_hash().unwrap())),
+            "0x6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9"
+        );
+
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe512961708279f8b1a3e79e5c8c4f8f"
+                .parse()
+                .unwrap();
+        let permit = Erc2612Permit {
+            token: Address::repeat_byte(0x11),
+            chain_id: 1,
+            domain_separator: [0x22; 32],
+            owner: wallet.address(),
+            spender: Address::repeat_byte(0x33),
+            value: U256::from(1_000u64),
+            nonce: U256::zero(),
+            deadline: U256::from(1_700_000_000u64),
+        };
+
+        let signature = wallet.sign_typed_data(&permit).await.unwrap();
+        let digest = H256::from(permit.encode_eip712().unwrap());
+        assert_eq!(signature.recover(digest).unwrap(), wallet.address());
+
+        let split = PermitSignature::new(permit.deadline, &signature);
+        assert!(split.v == 27 || split.v == 28);
+        assert_eq!(U256::from_big_endian(&split.r), signature.r);
+    }
+}
@@ -1,13 +1,36 @@
 /// High-level Ethereum wallet service
 /// Matches Unity SDK's WalletService.cs API exactly
-use super::{dto::*, handler::EthereumHandler, transactions};
+use super::{
+    dto::*,
+    handler::EthereumHandler,
+    ledger::LedgerSigner,
+    permit::{Erc2612Permit, PermitSignature},
+    signer::{EthereumSigner, EthereumSignerSource},
+    transactions,
+};
 use crate::{IdosError, IdosResult};
+use bevy::log::warn;
+use ethers::{
+    signers::Signer,
+    types::{Address, U256},
+};
+
+/// Max uint256, used for unlimited approvals (matches Unity SDK)
+const MAX_ALLOWANCE: &str =
+    "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+/// How long a deposit permit stays valid
+const PERMIT_DEADLINE_SECS: i64 = 20 * 60;
 
 /// High-level service for Ethereum wallet operations
 /// Provides the same API as Unity SDK's WalletService.cs
 pub struct EthereumWalletService {
     handler: EthereumHandler,
     private_key: Option<String>,
+    signer: Option<EthereumSigner>,
+    spenders: Vec<String>,
+    gas_limit: GasLimit,
+    gas_speed: Option<GasSpeed>,
 }
 
 impl EthereumWalletService {
@@ -15,11 +38,40 @@ impl EthereumWalletService {
         Self {
             handler,
             private_key: None,
+            signer: None,
+            spenders: Vec::new(),
+            gas_limit: GasLimit::default(),
+            gas_speed: None,
         }
     }
 
+    /// Sign with the unlocked in-game wallet
+    /// The private key stays inside the wallet module; a locked wallet returns `IdosError::WalletLocked`
+    pub fn with_wallet<W: EthereumSignerSource>(mut self, wallet: &W) -> IdosResult<Self> {
+        self.set_wallet(wallet)?;
+        Ok(self)
+    }
+
+    /// Switch signing to the in-game wallet
+    /// Call again after the player unlocks or switches wallets
+    pub fn set_wallet<W: EthereumSignerSource>(&mut self, wallet: &W) -> IdosResult<()> {
+        let chain_id = self.handler.settings().chain_id as u64;
+        let signer = wallet.ethereum_signer(chain_id)?;
+        self.set_signer(signer);
+        Ok(())
+    }
+
+    /// Use an explicit signer (in-game wallet, Ledger, ...)
+    /// Clears any private key held by the service
+    pub fn set_signer(&mut self, signer: EthereumSigner) {
+        self.private_key = None;
+        self.signer = Some(signer);
+    }
+
     /// Set private key for signing transactions
+    /// Replaces any signer previously set
     pub fn set_private_key(&mut self, private_key: String) {
+        self.signer = None;
         self.private_key = Some(private_key);
     }
 
@@ -28,10 +80,54 @@ impl EthereumWalletService {
         self.private_key = None;
     }
 
-    fn get_private_key(&self) -> IdosResult<&str> {
-        self.private_key
+    /// Sign transactions with a connected Ledger instead of a private key
+    pub fn set_ledger_signer(&mut self, ledger: LedgerSigner) {
+        self.set_signer(EthereumSigner::Ledger(ledger));
+    }
+
+    /// Drop the signer set with `set_signer`, `set_wallet` or `set_ledger_signer`
+    pub fn clear_signer(&mut self) {
+        self.signer = None;
+    }
+
+    /// Whether transactions are signed on a Ledger device
+    pub fn is_using_ledger(&self) -> bool {
+        self.signer.as_ref().is_some_and(|s| s.is_hardware())
+    }
+
+    /// Override how gas limits are chosen for outgoing transactions
+    /// Defaults to eth_estimateGas plus a 20% safety margin
+    pub fn set_gas_limit(&mut self, gas_limit: GasLimit) {
+        self.gas_limit = gas_limit;
+    }
+
+    /// Price outgoing transactions from the handler's `GasOracle`
+    /// `None` (the default) uses `gas_price_gwei` from the settings
+    pub fn set_gas_speed(&mut self, speed: Option<GasSpeed>) {
+        self.gas_speed = speed;
+    }
+
+    fn gas_price_gwei(&self) -> f64 {
+        self.handler.gas_price_gwei(self.gas_speed)
+    }
+
+    /// Build the active signer bound to the configured chain
+    fn signer(&self) -> IdosResult<EthereumSigner> {
+        let chain_id = self.handler.settings().chain_id as u64;
+
+        if let Some(signer) = &self.signer {
+            return Ok(signer.clone().with_chain_id(chain_id));
+        }
+
+        let private_key = self
+            .private_key
             .as_deref()
-            .ok_or_else(|| IdosError::Wallet("Private key not set".to_string()))
+            .ok_or_else(|| IdosError::Wallet("Private key not set".to_string()))?;
+
+        Ok(EthereumSigner::Local(transactions::local_wallet(
+            private_key,
+            chain_id,
+        )?))
     }
 
     /// Transfer tokens to game platform pool
@@ -45,70 +141,204 @@ impl EthereumWalletService {
         user_id: &str,
         wallet_address: &str,
     ) -> IdosResult<String> {
-        let private_key = self.get_private_key()?;
-        let settings = self.handler.settings();
-        let chain_id = settings.chain_id as u64;
-        let gas_price_gwei = settings.gas_price_gwei;
-        let platform_pool = &settings.platform_pool_contract_address;
-
         // Convert amount to wei (assuming 18 decimals)
         let amount_wei = (amount as u128 * 1_000_000_000_000_000_000).to_string();
 
+        self.approve_and_deposit(rpc_url, token_address, amount_wei, user_id, wallet_address)
+            .await
+    }
+
+    /// Check allowance -> approve if needed -> deposit -> submit to backend
+    async fn approve_and_deposit(
+        &self,
+        rpc_url: &str,
+        token_address: &str,
+        amount_wei: String,
+        user_id: &str,
+        wallet_address: &str,
+    ) -> IdosResult<String> {
+        let signer = self.signer()?;
+        let settings = self.handler.settings();
+        let gas_price_gwei = self.gas_price_gwei();
+        let platform_pool = &settings.platform_pool_contract_address;
+
         // 1. Check current allowance
         let current_allowance = self
             .handler
             .get_erc20_allowance(token_address, wallet_address, platform_pool)
             .await?;
 
-        let current_allowance_u128: u128 = current_allowance
-            .parse()
+        let current_allowance = U256::from_dec_str(&current_allowance)
             .map_err(|_| IdosError::InvalidInput("Invalid allowance".to_string()))?;
-        let required_allowance: u128 = amount_wei.parse().unwrap();
+        let required_allowance = U256::from_dec_str(&amount_wei)
+            .map_err(|_| IdosError::InvalidInput("Invalid amount".to_string()))?;
 
         // 2. Approve if needed
-        if current_allowance_u128 < required_allowance {
+        if current_allowance < required_allowance {
             // Use max uint256 for unlimited approval (matches Unity SDK)
-            let max_allowance =
-                "115792089237316195423570985008687907853269984665640564039457584007913129639935";
-
-            let approve_hash = transactions::approve_erc20(
+            let approve_hash = transactions::approve_erc20_with_signer(
                 rpc_url,
                 token_address,
                 platform_pool,
-                max_allowance,
-                private_key,
-                chain_id,
+                MAX_ALLOWANCE,
+                signer.clone(),
                 gas_price_gwei,
+                self.gas_limit,
             )
             .await?;
+            self.handler.record_transaction(&approve_hash, "approval");
 
             // Wait for approval confirmation
             self.handler.wait_for_transaction(&approve_hash, 20).await?;
         }
 
         // 3. Deposit tokens to platform pool
-        let deposit_hash = transactions::deposit_erc20(
+        let deposit_hash = transactions::deposit_erc20_with_signer(
             rpc_url,
             platform_pool,
             token_address,
             &amount_wei,
             user_id,
-            private_key,
-            chain_id,
+            signer,
             gas_price_gwei,
+            self.gas_limit,
         )
         .await?;
 
         // 4. Submit transaction to backend
+        self.finish_token_deposit(&deposit_hash, token_address, amount_wei)
+            .await
+    }
+
+    /// Transfer tokens to the game platform pool in a single transaction
+    /// Signs an EIP-2612 permit with the local wallet and submits it together with the deposit,
+    /// so the player pays gas once instead of for approve + deposit
+    /// `amount` is in whole tokens, scaled by the token's `decimals()`
+    /// Falls back to approve + deposit when the token doesn't support permit, or when the permit
+    /// deposit reverts in the pre-flight simulation (pool without `depositERC20WithPermit`,
+    /// non-standard permit); nothing has been sent at that point
+    pub async fn permit_and_deposit(
+        &self,
+        rpc_url: &str,
+        token_address: &str,
+        amount: u64,
+        user_id: &str,
+        wallet_address: &str,
+    ) -> IdosResult<String> {
+        let signer = self.signer()?;
+        check_signer_address(&signer, wallet_address)?;
+        let owner = signer.address();
+
+        let decimals = self.handler.get_token_info(token_address).await?.decimals;
+        let amount_wei = U256::exp10(decimals as usize)
+            .checked_mul(U256::from(amount))
+            .ok_or_else(|| IdosError::InvalidInput("Amount too large".to_string()))?;
+
+        let Some(support) = self
+            .handler
+            .get_permit_support(token_address, &format!("{:?}", owner))
+            .await?
+        else {
+            return self
+                .approve_and_deposit(
+                    rpc_url,
+                    token_address,
+                    amount_wei.to_string(),
+                    user_id,
+                    wallet_address,
+                )
+                .await;
+        };
+
+        let settings = self.handler.settings();
+        let gas_price_gwei = self.gas_price_gwei();
+        let platform_pool = &settings.platform_pool_contract_address;
+
+        let permit = Erc2612Permit {
+            token: token_address
+                .parse()
+                .map_err(|_| IdosError::InvalidInput("Invalid token address".to_string()))?,
+            chain_id: settings.chain_id as u64,
+            domain_separator: support.domain_separator,
+            owner,
+            spender: platform_pool
+                .parse()
+                .map_err(|_| IdosError::InvalidInput("Invalid pool address".to_string()))?,
+            value: amount_wei,
+            nonce: support.nonce,
+            deadline: U256::from(chrono::Utc::now().timestamp() + PERMIT_DEADLINE_SECS),
+        };
+        let signature = signer.sign_typed_data(&permit).await?;
+
+        let result = transactions::deposit_erc20_with_permit_with_signer(
+            rpc_url,
+            platform_pool,
+            token_address,
+            &amount_wei.to_string(),
+            user_id,
+            &PermitSignature::new(permit.deadline, &signature),
+            signer,
+            gas_price_gwei,
+            self.gas_limit,
+        )
+        .await;
+
+        match result {
+            Ok(deposit_hash) => {
+                self.finish_token_deposit(&deposit_hash, token_address, amount_wei.to_string())
+                    .await
+            }
+            Err(IdosError::TransactionReverted(reason)) => {
+                warn!(
+                    "Permit deposit rejected ({}), using approve + deposit",
+                    reason
+                );
+                self.approve_and_deposit(
+                    rpc_url,
+                    token_address,
+                    amount_wei.to_string(),
+                    user_id,
+                    wallet_address,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Track a sent deposit and report it to the backend
+    async fn finish_token_deposit(
+        &self,
+        deposit_hash: &str,
+        token_address: &str,
+        amount_wei: String,
+    ) -> IdosResult<String> {
+        self.handler.record_transaction(deposit_hash, "deposit");
+
+        // Persist before reporting so a crash here is picked up on the next start
+        // (best effort: the deposit is already on its way)
+        let mut pending = PendingEthTransaction::new(
+            deposit_hash,
+            self.handler.settings().chain_id,
+            CryptoTransactionType::Token,
+            TransactionDirection::Game,
+        );
+        pending.token_address = Some(token_address.to_string());
+        pending.amount = Some(amount_wei);
+        pending.submit_to_backend = true;
+        self.handler.track_transaction(pending).ok();
+
         let result = self
             .handler
             .submit_transaction(
-                &deposit_hash,
+                deposit_hash,
                 CryptoTransactionType::Token,
                 TransactionDirection::Game,
             )
             .await?;
 
+        self.handler.complete_transaction(deposit_hash).ok();
+
         Ok(result)
     }
 
@@ -119,20 +349,32 @@ impl EthereumWalletService {
         rpc_url: &str,
         withdrawal_signature: WithdrawalSignatureResult,
     ) -> IdosResult<String> {
-        let private_key = self.get_private_key()?;
+        let signer = self.signer()?;
         let settings = self.handler.settings();
-        let chain_id = settings.chain_id as u64;
-        let gas_price_gwei = settings.gas_price_gwei;
+        let gas_price_gwei = self.gas_price_gwei();
 
         // Execute withdrawal with backend signature
-        let tx_hash = transactions::withdraw_erc20(
+        let tx_hash = transactions::withdraw_erc20_with_signer(
             rpc_url,
             &withdrawal_signature,
-            private_key,
-            chain_id,
+            signer,
             gas_price_gwei,
+            self.gas_limit,
         )
         .await?;
+        self.handler.record_transaction(&tx_hash, "withdrawal");
+
+        // Tracked until confirmed by `EthereumHandler::resume_pending_transactions`
+        let mut pending = PendingEthTransaction::new(
+            &tx_hash,
+            settings.chain_id,
+            CryptoTransactionType::Token,
+            TransactionDirection::UsersCryptoWallet,
+        );
+        pending.token_address = Some(withdrawal_signature.token_address.clone());
+        pending.nft_id = withdrawal_signature.token_id.clone();
+        pending.amount = Some(withdrawal_signature.amount.clone());
+        self.handler.track_transaction(pending).ok();
 
         Ok(tx_hash)
     }
@@ -148,14 +390,13 @@ impl EthereumWalletService {
         amount: u64,
         user_id: &str,
     ) -> IdosResult<String> {
-        let private_key = self.get_private_key()?;
+        let signer = self.signer()?;
         let settings = self.handler.settings();
-        let chain_id = settings.chain_id as u64;
-        let gas_price_gwei = settings.gas_price_gwei;
+        let gas_price_gwei = self.gas_price_gwei();
         let platform_pool = &settings.platform_pool_contract_address;
 
         // Transfer NFT to platform pool
-        let tx_hash = transactions::transfer_nft_erc1155(
+        let tx_hash = transactions::transfer_nft_erc1155_with_signer(
             rpc_url,
             nft_contract_address,
             wallet_address,
@@ -163,11 +404,24 @@ impl EthereumWalletService {
             nft_id,
             amount,
             Some(user_id),
-            private_key,
-            chain_id,
+            signer,
             gas_price_gwei,
+            self.gas_limit,
         )
         .await?;
+        self.handler.record_transaction(&tx_hash, "nft_deposit");
+
+        let mut pending = PendingEthTransaction::new(
+            &tx_hash,
+            settings.chain_id,
+            CryptoTransactionType::NFT,
+            TransactionDirection::Game,
+        );
+        pending.token_address = Some(nft_contract_address.to_string());
+        pending.nft_id = Some(nft_id.to_string());
+        pending.amount = Some(amount.to_string());
+        pending.submit_to_backend = true;
+        self.handler.track_transaction(pending).ok();
 
         // Submit to backend
         self.handler
@@ -178,6 +432,8 @@ impl EthereumWalletService {
             )
             .await?;
 
+        self.handler.complete_transaction(&tx_hash).ok();
+
         Ok(tx_hash)
     }
 
@@ -188,26 +444,39 @@ impl EthereumWalletService {
         rpc_url: &str,
         withdrawal_signature: WithdrawalSignatureResult,
     ) -> IdosResult<String> {
-        let private_key = self.get_private_key()?;
+        let signer = self.signer()?;
         let settings = self.handler.settings();
-        let chain_id = settings.chain_id as u64;
-        let gas_price_gwei = settings.gas_price_gwei;
+        let gas_price_gwei = self.gas_price_gwei();
 
         // Execute NFT withdrawal with backend signature
-        let tx_hash = transactions::withdraw_nft_erc1155(
+        let tx_hash = transactions::withdraw_nft_erc1155_with_signer(
             rpc_url,
             &withdrawal_signature,
-            private_key,
-            chain_id,
+            signer,
             gas_price_gwei,
+            self.gas_limit,
         )
         .await?;
+        self.handler.record_transaction(&tx_hash, "nft_withdrawal");
+
+        // Tracked until confirmed by `EthereumHandler::resume_pending_transactions`
+        let mut pending = PendingEthTransaction::new(
+            &tx_hash,
+            settings.chain_id,
+            CryptoTransactionType::NFT,
+            TransactionDirection::UsersCryptoWallet,
+        );
+        pending.token_address = Some(withdrawal_signature.token_address.clone());
+        pending.nft_id = withdrawal_signature.token_id.clone();
+        pending.amount = Some(withdrawal_signature.amount.clone());
+        self.handler.track_transaction(pending).ok();
 
         Ok(tx_hash)
     }
 
     /// Transfer tokens to external address
     /// Matches Unity SDK's TransferTokenToExternalAddress
+    /// `from_address` must be the signer's address
     pub async fn transfer_token_to_external_address(
         &self,
         rpc_url: &str,
@@ -216,22 +485,24 @@ impl EthereumWalletService {
         to_address: &str,
         amount: u64,
     ) -> IdosResult<String> {
-        let private_key = self.get_private_key()?;
+        let signer = self.signer()?;
+        check_signer_address(&signer, from_address)?;
         let settings = self.handler.settings();
-        let chain_id = settings.chain_id as u64;
-        let gas_price_gwei = settings.gas_price_gwei;
+        let gas_price_gwei = self.gas_price_gwei();
 
-        transactions::transfer_erc20(
+        let tx_hash = transactions::transfer_erc20_with_signer(
             rpc_url,
             token_address,
-            from_address,
             to_address,
             amount,
-            private_key,
-            chain_id,
+            signer,
             gas_price_gwei,
+            self.gas_limit,
         )
-        .await
+        .await?;
+        self.handler.record_transaction(&tx_hash, "transfer");
+
+        Ok(tx_hash)
     }
 
     /// Transfer NFT to external address
@@ -245,12 +516,11 @@ impl EthereumWalletService {
         nft_id: &str,
         amount: u64,
     ) -> IdosResult<String> {
-        let private_key = self.get_private_key()?;
+        let signer = self.signer()?;
         let settings = self.handler.settings();
-        let chain_id = settings.chain_id as u64;
-        let gas_price_gwei = settings.gas_price_gwei;
+        let gas_price_gwei = self.gas_price_gwei();
 
-        transactions::transfer_nft_erc1155(
+        let tx_hash = transactions::transfer_nft_erc1155_with_signer(
             rpc_url,
             nft_contract_address,
             from_address,
@@ -258,13 +528,212 @@ impl EthereumWalletService {
             nft_id,
             amount,
             None, // No userID for external transfers
-            private_key,
-            chain_id,
+            signer,
             gas_price_gwei,
+            self.gas_limit,
+        )
+        .await?;
+        self.handler.record_transaction(&tx_hash, "nft_transfer");
+
+        Ok(tx_hash)
+    }
+
+    /// Sign a token withdrawal without sending it, e.g. for a relayer
+    pub async fn sign_token_to_user(
+        &self,
+        rpc_url: &str,
+        withdrawal_signature: WithdrawalSignatureResult,
+    ) -> IdosResult<SignedTransaction> {
+        let signer = self.signer()?;
+        let gas_price_gwei = self.gas_price_gwei();
+
+        transactions::build_and_sign_withdraw_erc20(
+            rpc_url,
+            &withdrawal_signature,
+            signer,
+            gas_price_gwei,
+            self.gas_limit,
+        )
+        .await
+    }
+
+    /// Sign an NFT withdrawal without sending it, e.g. for a relayer
+    pub async fn sign_nft_to_user(
+        &self,
+        rpc_url: &str,
+        withdrawal_signature: WithdrawalSignatureResult,
+    ) -> IdosResult<SignedTransaction> {
+        let signer = self.signer()?;
+        let gas_price_gwei = self.gas_price_gwei();
+
+        transactions::build_and_sign_withdraw_nft_erc1155(
+            rpc_url,
+            &withdrawal_signature,
+            signer,
+            gas_price_gwei,
+            self.gas_limit,
+        )
+        .await
+    }
+
+    /// Sign a token transfer to an external address without sending it
+    pub async fn sign_token_to_external_address(
+        &self,
+        rpc_url: &str,
+        token_address: &str,
+        to_address: &str,
+        amount: u64,
+    ) -> IdosResult<SignedTransaction> {
+        let signer = self.signer()?;
+        let gas_price_gwei = self.gas_price_gwei();
+
+        transactions::build_and_sign_transfer_erc20(
+            rpc_url,
+            token_address,
+            to_address,
+            amount,
+            signer,
+            gas_price_gwei,
+            self.gas_limit,
+        )
+        .await
+    }
+
+    /// Sign an NFT transfer to an external address without sending it
+    pub async fn sign_nft_to_external_address(
+        &self,
+        rpc_url: &str,
+        nft_contract_address: &str,
+        from_address: &str,
+        to_address: &str,
+        nft_id: &str,
+        amount: u64,
+    ) -> IdosResult<SignedTransaction> {
+        let signer = self.signer()?;
+        let gas_price_gwei = self.gas_price_gwei();
+
+        transactions::build_and_sign_transfer_nft_erc1155(
+            rpc_url,
+            nft_contract_address,
+            from_address,
+            to_address,
+            nft_id,
+            amount,
+            None,
+            signer,
+            gas_price_gwei,
+            self.gas_limit,
         )
         .await
     }
 
+    /// Broadcast a transaction signed earlier with one of the `sign_*` methods
+    pub async fn broadcast_raw_transaction(
+        &self,
+        rpc_url: &str,
+        raw_transaction: &str,
+    ) -> IdosResult<String> {
+        let tx_hash = transactions::broadcast_raw(rpc_url, raw_transaction).await?;
+        self.handler.record_transaction(&tx_hash, "transaction");
+
+        Ok(tx_hash)
+    }
+
+    /// Register an extra spender to include in `list_known_allowances`
+    /// The platform pool is always included
+    pub fn register_spender(&mut self, spender_address: String) {
+        if !self
+            .spenders
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(&spender_address))
+        {
+            self.spenders.push(spender_address);
+        }
+    }
+
+    /// Spenders checked by `list_known_allowances`
+    pub fn known_spenders(&self) -> Vec<String> {
+        let platform_pool = &self.handler.settings().platform_pool_contract_address;
+
+        let mut spenders = Vec::new();
+        if !platform_pool.is_empty() {
+            spenders.push(platform_pool.clone());
+        }
+        for spender in &self.spenders {
+            if !spenders.iter().any(|s| s.eq_ignore_ascii_case(spender)) {
+                spenders.push(spender.clone());
+            }
+        }
+        spenders
+    }
+
+    /// Revoke a spender's allowance by approving zero
+    pub async fn revoke_allowance(
+        &self,
+        rpc_url: &str,
+        token_address: &str,
+        spender_address: &str,
+    ) -> IdosResult<String> {
+        let signer = self.signer()?;
+        let gas_price_gwei = self.gas_price_gwei();
+
+        let tx_hash = transactions::approve_erc20_with_signer(
+            rpc_url,
+            token_address,
+            spender_address,
+            "0",
+            signer,
+            gas_price_gwei,
+            self.gas_limit,
+        )
+        .await?;
+        self.handler.record_transaction(&tx_hash, "approval");
+
+        Ok(tx_hash)
+    }
+
+    /// List non-zero allowances the signer's wallet has granted to known spenders
+    /// Checks every configured token against the platform pool and registered spenders
+    pub async fn list_known_allowances(&self) -> IdosResult<Vec<TokenAllowance>> {
+        let owner = format!("{:?}", self.signer()?.address());
+        let unlimited_threshold = U256::MAX >> 1;
+
+        let mut allowances = Vec::new();
+        for (token_name, token_address) in &self.handler.settings().token_contract_addresses {
+            for spender in self.known_spenders() {
+                let allowance = self
+                    .handler
+                    .get_erc20_allowance(token_address, &owner, &spender)
+                    .await?;
+
+                // Native returns decimal, WASM returns the raw eth_call hex
+                let value = match allowance.strip_prefix("0x") {
+                    Some(hex) => U256::from_str_radix(hex, 16).ok(),
+                    None => U256::from_dec_str(&allowance).ok(),
+                }
+                .ok_or_else(|| IdosError::InvalidInput("Invalid allowance".to_string()))?;
+                if value.is_zero() {
+                    continue;
+                }
+
+                allowances.push(TokenAllowance {
+                    token_name: token_name.clone(),
+                    token_address: token_address.clone(),
+                    spender_address: spender,
+                    allowance: value.to_string(),
+                    is_unlimited: value >= unlimited_threshold,
+                });
+            }
+        }
+
+        Ok(allowances)
+    }
+
+    /// Get ERC20 symbol, name and decimals (cached)
+    pub async fn get_token_info(&self, token_address: &str) -> IdosResult<TokenInfo> {
+        self.handler.get_token_info(token_address).await
+    }
+
     /// Get token balance
     /// Matches Unity SDK's GetTokenBalance
     pub async fn get_token_balance(
@@ -289,6 +758,11 @@ impl EthereumWalletService {
         transactions::get_nft_balance(rpc_url, nft_contract_address, wallet_address, nft_ids).await
     }
 
+    /// List all NFTs owned by a wallet via the configured indexer
+    pub async fn get_owned_nfts(&self, wallet_address: &str) -> IdosResult<Vec<OwnedNft>> {
+        self.handler.get_owned_nfts(wallet_address, &[]).await
+    }
+
     /// Get native token balance in wei
     /// Matches Unity SDK's GetNativeTokenBalanceInWei
     pub async fn get_native_token_balance_in_wei(
@@ -298,6 +772,19 @@ impl EthereumWalletService {
         self.handler.get_native_balance(wallet_address).await
     }
 
+    /// Estimate the full cost of a transaction, including rollup L1 fees
+    pub async fn estimate_total_cost(
+        &self,
+        from_address: &str,
+        to_address: &str,
+        data: Option<&str>,
+        value_wei: Option<&str>,
+    ) -> IdosResult<TotalCostEstimate> {
+        self.handler
+            .estimate_total_cost(from_address, to_address, data, value_wei)
+            .await
+    }
+
     /// Check if has sufficient balance for gas
     /// Matches Unity SDK's HasSufficientBalanceForGas
     pub async fn has_sufficient_balance_for_gas(
@@ -310,3 +797,19 @@ impl EthereumWalletService {
             .await
     }
 }
+
+/// Tokens always leave from the signer's account; reject a different address instead of
+/// silently sending from the signer
+fn check_signer_address(signer: &EthereumSigner, address: &str) -> IdosResult<()> {
+    let parsed: Address = address
+        .parse()
+        .map_err(|_| IdosError::InvalidInput(format!("Invalid address: {}", address)))?;
+    if parsed != signer.address() {
+        return Err(IdosError::InvalidInput(format!(
+            "{} is not the signing account {:?}",
+            address,
+            signer.address()
+        )));
+    }
+    Ok(())
+}
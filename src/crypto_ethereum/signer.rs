@@ -0,0 +1,112 @@
+/// Signer backends for Ethereum transactions
+/// Lets the wallet service sign with a raw private key or a hardware wallet
+use super::ledger::LedgerSigner;
+use crate::{IdosError, IdosResult};
+use async_trait::async_trait;
+use ethers::{
+    signers::{LocalWallet, Signer},
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Address, Signature,
+    },
+};
+
+/// Something that can hand out a transaction signer without exposing its key
+/// Implemented by the in-game `WalletManager`
+pub trait EthereumSignerSource {
+    /// Signer bound to `chain_id`, or `IdosError::WalletLocked` if the wallet isn't unlocked
+    fn ethereum_signer(&self, chain_id: u64) -> IdosResult<EthereumSigner>;
+}
+
+/// Signer selected for an `EthereumWalletService`
+#[derive(Clone, Debug)]
+pub enum EthereumSigner {
+    /// In-memory private key
+    Local(LocalWallet),
+    /// Ledger hardware wallet
+    Ledger(LedgerSigner),
+}
+
+impl EthereumSigner {
+    /// Whether this signer lives on a hardware device
+    pub fn is_hardware(&self) -> bool {
+        matches!(self, EthereumSigner::Ledger(_))
+    }
+}
+
+impl From<LocalWallet> for EthereumSigner {
+    fn from(wallet: LocalWallet) -> Self {
+        EthereumSigner::Local(wallet)
+    }
+}
+
+impl From<LedgerSigner> for EthereumSigner {
+    fn from(ledger: LedgerSigner) -> Self {
+        EthereumSigner::Ledger(ledger)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for EthereumSigner {
+    type Error = IdosError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            EthereumSigner::Local(wallet) => wallet
+                .sign_message(message)
+                .await
+                .map_err(|e| IdosError::Wallet(format!("Signing failed: {}", e))),
+            EthereumSigner::Ledger(ledger) => ledger.sign_message(message).await,
+        }
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        match self {
+            EthereumSigner::Local(wallet) => wallet
+                .sign_transaction(tx)
+                .await
+                .map_err(|e| IdosError::Wallet(format!("Signing failed: {}", e))),
+            EthereumSigner::Ledger(ledger) => ledger.sign_transaction(tx).await,
+        }
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            EthereumSigner::Local(wallet) => wallet
+                .sign_typed_data(payload)
+                .await
+                .map_err(|e| IdosError::Wallet(format!("Signing failed: {}", e))),
+            EthereumSigner::Ledger(ledger) => ledger.sign_typed_data(payload).await,
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            EthereumSigner::Local(wallet) => wallet.address(),
+            EthereumSigner::Ledger(ledger) => ledger.address(),
+        }
+    }
+
+    fn chain_id(&self) -> u64 {
+        match self {
+            EthereumSigner::Local(wallet) => wallet.chain_id(),
+            EthereumSigner::Ledger(ledger) => ledger.chain_id(),
+        }
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        match self {
+            EthereumSigner::Local(wallet) => EthereumSigner::Local(wallet.with_chain_id(chain_id)),
+            EthereumSigner::Ledger(ledger) => {
+                EthereumSigner::Ledger(ledger.with_chain_id(chain_id))
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+/// Pre-flight transaction simulation
+/// Runs the exact calldata through eth_call before sending and decodes revert reasons
+use crate::{IdosError, IdosResult};
+use ethers::{
+    abi::{decode, ParamType, Token},
+    providers::{
+        call_raw::{spoof, RawCall},
+        Middleware, ProviderError, RpcError,
+    },
+    types::{transaction::eip2718::TypedTransaction, Bytes, U256},
+    utils::hex,
+};
+
+/// Selector of `Error(string)`
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of `Panic(uint256)`
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Simulate a transaction with eth_call against the latest block
+///
+/// The sender's balance is overridden so the call reaches the contract even when
+/// the wallet can't cover gas yet; RPCs without state override support fall back
+/// to a plain eth_call. Reverts come back as `IdosError::TransactionReverted`.
+pub async fn simulate_transaction<M: Middleware>(
+    client: &M,
+    tx: &TypedTransaction,
+) -> IdosResult<Bytes> {
+    let mut tx = tx.clone();
+    if tx.from().is_none() {
+        if let Some(sender) = client.default_sender() {
+            tx.set_from(sender);
+        }
+    }
+
+    let provider = client.provider();
+    let result = match tx.from().copied() {
+        Some(from) => {
+            let state = spoof::balance(from, U256::MAX >> 1);
+            match provider.call_raw(&tx).state(&state).await {
+                Err(e) if !is_revert(&e) => provider.call_raw(&tx).await,
+                other => other,
+            }
+        }
+        None => provider.call_raw(&tx).await,
+    };
+
+    result.map_err(simulation_error)
+}
+
+/// Decode revert data into a readable reason
+/// Handles `Error(string)`, `Panic(uint256)` and custom errors
+pub fn decode_revert_reason(data: &[u8]) -> String {
+    if data.len() < 4 {
+        return "execution reverted".to_string();
+    }
+
+    let (selector, payload) = data.split_at(4);
+
+    if selector == ERROR_STRING_SELECTOR {
+        if let Ok(tokens) = decode(&[ParamType::String], payload) {
+            if let Some(Token::String(reason)) = tokens.into_iter().next() {
+                return reason;
+            }
+        }
+    }
+
+    if selector == PANIC_SELECTOR {
+        if let Ok(tokens) = decode(&[ParamType::Uint(256)], payload) {
+            if let Some(Token::Uint(code)) = tokens.into_iter().next() {
+                return panic_reason(code);
+            }
+        }
+    }
+
+    format!("custom error 0x{}", hex::encode(selector))
+}
+
+fn panic_reason(code: U256) -> String {
+    let reason = match code.low_u64() {
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to uninitialized function",
+        _ => "unknown panic",
+    };
+
+    format!("panic 0x{:02x}: {}", code.low_u64(), reason)
+}
+
+/// Whether the node answered with an execution revert (not a transport failure)
+pub(crate) fn is_revert(error: &ProviderError) -> bool {
+    error
+        .as_error_response()
+        .map(|rpc| rpc.is_revert())
+        .unwrap_or(false)
+}
+
+fn simulation_error(error: ProviderError) -> IdosError {
+    match error.as_error_response() {
+        Some(rpc) if rpc.is_revert() => {
+            let data = rpc.as_revert_data().unwrap_or_default();
+            let reason = if data.is_empty() {
+                // Some nodes only put the reason in the message
+                rpc.message
+                    .trim_start_matches("execution reverted")
+                    .trim_start_matches(':')
+                    .trim()
+                    .to_string()
+            } else {
+                decode_revert_reason(&data)
+            };
+
+            if reason.is_empty() {
+                IdosError::TransactionReverted("execution reverted".to_string())
+            } else {
+                IdosError::TransactionReverted(reason)
+            }
+        }
+        _ => IdosError::NetworkError(format!("Simulation failed: {}", error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::encode;
+
+    #[test]
+    fn test_decode_error_string() {
+        let mut data = ERROR_STRING_SELECTOR.to_vec();
+        data.extend(encode(&[Token::String(
+            "ERC20: transfer amount exceeds balance".to_string(),
+        )]));
+
+        assert_eq!(
+            decode_revert_reason(&data),
+            "ERC20: transfer amount exceeds balance"
+        );
+    }
+
+    #[test]
+    fn test_decode_panic_and_custom_error() {
+        let mut data = PANIC_SELECTOR.to_vec();
+        data.extend(encode(&[Token::Uint(U256::from(0x11))]));
+        assert_eq!(
+            decode_revert_reason(&data),
+            "panic 0x11: arithmetic overflow or underflow"
+        );
+
+        assert_eq!(
+            decode_revert_reason(&[0xde, 0xad, 0xbe, 0xef]),
+            "custom error 0xdeadbeef"
+        );
+        assert_eq!(decode_revert_reason(&[]), "execution reverted");
+    }
+}
@@ -0,0 +1,334 @@
+/// Sign-In With Ethereum (EIP-4361)
+/// Builds, parses and verifies the login message the player signs with their wallet
+use super::signer::EthereumSigner;
+use crate::{IdosError, IdosResult};
+use chrono::{DateTime, SecondsFormat, Utc};
+use ethers::{
+    signers::Signer,
+    types::{Address, Signature},
+    utils::to_checksum,
+};
+use std::fmt;
+use std::str::FromStr;
+
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+/// EIP-4361 message
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiweMessage {
+    /// Host requesting the sign-in ("mygame.example")
+    pub domain: String,
+    /// EIP-55 checksummed address
+    pub address: String,
+    /// Human-readable line shown by the wallet
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    /// One-time nonce from the backend (`AuthHandler::get_wallet_nonce`)
+    pub nonce: String,
+    /// RFC 3339 timestamps
+    pub issued_at: String,
+    pub expiration_time: Option<String>,
+    pub not_before: Option<String>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Message issued now, for `address` on `chain_id`
+    pub fn new(
+        domain: &str,
+        address: &str,
+        uri: &str,
+        chain_id: u64,
+        nonce: &str,
+    ) -> IdosResult<Self> {
+        Ok(Self {
+            domain: domain.to_string(),
+            address: checksum_address(address)?,
+            statement: None,
+            uri: uri.to_string(),
+            version: "1".to_string(),
+            chain_id,
+            nonce: nonce.to_string(),
+            issued_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        })
+    }
+
+    pub fn with_statement(mut self, statement: &str) -> Self {
+        self.statement = Some(statement.to_string());
+        self
+    }
+
+    /// Make the message expire `duration` after now
+    pub fn expires_in(mut self, duration: std::time::Duration) -> Self {
+        let expires_at = Utc::now() + chrono::Duration::from_std(duration).unwrap_or_default();
+        self.expiration_time = Some(expires_at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        self
+    }
+
+    /// Parse a message produced by `to_string` or another EIP-4361 implementation
+    pub fn parse(message: &str) -> IdosResult<Self> {
+        let invalid =
+            |reason: &str| IdosError::InvalidInput(format!("Invalid SIWE message: {}", reason));
+        let mut lines = message.lines();
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(PREAMBLE))
+            .ok_or_else(|| invalid("missing preamble"))?;
+        let address = lines.next().ok_or_else(|| invalid("missing address"))?;
+        if lines.next() != Some("") {
+            return Err(invalid("missing blank line after address"));
+        }
+
+        // Optional statement, then a blank line before the fields
+        let mut statement = None;
+        match lines.next() {
+            Some("") => {}
+            Some(line) => {
+                statement = Some(line.to_string());
+                if lines.next() != Some("") {
+                    return Err(invalid("missing blank line after statement"));
+                }
+            }
+            None => return Err(invalid("missing fields")),
+        }
+
+        let mut fields = std::collections::HashMap::new();
+        let mut resources = Vec::new();
+        let mut in_resources = false;
+        for line in lines {
+            if in_resources {
+                let resource = line
+                    .strip_prefix("- ")
+                    .ok_or_else(|| invalid("malformed resource"))?;
+                resources.push(resource.to_string());
+            } else if line == "Resources:" {
+                in_resources = true;
+            } else {
+                let (key, value) = line.split_once(": ").ok_or_else(|| invalid(line))?;
+                fields.insert(key, value.to_string());
+            }
+        }
+        let expiration_time = fields.remove("Expiration Time");
+        let not_before = fields.remove("Not Before");
+        let request_id = fields.remove("Request ID");
+        let mut required = |key: &str| {
+            fields
+                .remove(key)
+                .ok_or_else(|| invalid(&format!("missing {}", key)))
+        };
+
+        let parsed = Self {
+            domain: domain.to_string(),
+            address: checksum_address(address)?,
+            statement,
+            uri: required("URI")?,
+            version: required("Version")?,
+            chain_id: required("Chain ID")?
+                .parse()
+                .map_err(|_| invalid("bad chain ID"))?,
+            nonce: required("Nonce")?,
+            issued_at: required("Issued At")?,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        };
+
+        // Addresses must be checksummed as written
+        if parsed.address != address {
+            return Err(invalid("address is not EIP-55 checksummed"));
+        }
+        Ok(parsed)
+    }
+
+    /// Check that `signature` (0x-prefixed hex) was made by `address` over this message,
+    /// and that the message is within its validity window
+    pub fn verify(&self, signature: &str) -> IdosResult<()> {
+        let now = Utc::now();
+        if let Some(expiration_time) = &self.expiration_time {
+            if parse_time(expiration_time)? <= now {
+                return Err(IdosError::Auth("SIWE message has expired".to_string()));
+            }
+        }
+        if let Some(not_before) = &self.not_before {
+            if parse_time(not_before)? > now {
+                return Err(IdosError::Auth("SIWE message is not valid yet".to_string()));
+            }
+        }
+
+        let signature = Signature::from_str(signature.trim_start_matches("0x"))
+            .map_err(|e| IdosError::InvalidInput(format!("Invalid signature: {}", e)))?;
+        let signer = signature
+            .recover(self.to_string())
+            .map_err(|e| IdosError::Auth(format!("Invalid SIWE signature: {}", e)))?;
+
+        if to_checksum(&signer, None) != self.address {
+            return Err(IdosError::Auth(format!(
+                "SIWE message was signed by {} instead of {}",
+                to_checksum(&signer, None),
+                self.address
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}{}", self.domain, PREAMBLE)?;
+        writeln!(f, "{}", self.address)?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{}", statement)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "URI: {}", self.uri)?;
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Chain ID: {}", self.chain_id)?;
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {}", expiration_time)?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\nNot Before: {}", not_before)?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\nRequest ID: {}", request_id)?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in &self.resources {
+                write!(f, "\n- {}", resource)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wallet that signs the SIWE message
+#[derive(Clone, Debug)]
+pub enum SiweSigner {
+    /// In-game wallet (`WalletManager::ethereum_signer`), Ledger or private key
+    Signer(EthereumSigner),
+    /// Browser wallet through `personal_sign` (WASM only)
+    #[cfg(target_arch = "wasm32")]
+    MetaMask,
+}
+
+impl SiweSigner {
+    /// Address and chain ID to put in the message
+    pub async fn account(&self) -> IdosResult<(String, u64)> {
+        match self {
+            SiweSigner::Signer(signer) => {
+                Ok((format!("{:?}", signer.address()), signer.chain_id()))
+            }
+            #[cfg(target_arch = "wasm32")]
+            SiweSigner::MetaMask => {
+                let address = super::helper::metamask_request_accounts()
+                    .await?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| IdosError::Wallet("No MetaMask account".to_string()))?;
+                let chain_id = super::helper::metamask_get_chain_id().await?;
+                let chain_id = u64::from_str_radix(chain_id.trim_start_matches("0x"), 16)
+                    .map_err(|_| IdosError::Wallet(format!("Invalid chain ID {}", chain_id)))?;
+                Ok((address, chain_id))
+            }
+        }
+    }
+
+    /// EIP-191 signature of the message, 0x-prefixed hex
+    pub async fn sign(&self, message: &SiweMessage) -> IdosResult<String> {
+        match self {
+            SiweSigner::Signer(signer) => {
+                let signature = signer.sign_message(message.to_string()).await?;
+                Ok(format!("0x{}", hex::encode(signature.to_vec())))
+            }
+            #[cfg(target_arch = "wasm32")]
+            SiweSigner::MetaMask => {
+                super::helper::metamask_personal_sign(&message.to_string(), &message.address).await
+            }
+        }
+    }
+}
+
+impl From<EthereumSigner> for SiweSigner {
+    fn from(signer: EthereumSigner) -> Self {
+        SiweSigner::Signer(signer)
+    }
+}
+
+fn checksum_address(address: &str) -> IdosResult<String> {
+    let parsed: Address = address
+        .parse()
+        .map_err(|_| IdosError::InvalidInput(format!("Invalid address: {}", address)))?;
+    Ok(to_checksum(&parsed, None))
+}
+
+fn parse_time(value: &str) -> IdosResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| IdosError::InvalidInput(format!("Invalid SIWE timestamp: {}", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::LocalWallet;
+
+    #[tokio::test]
+    async fn test_siwe_sign_and_verify() {
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe512961708279f8b1a3e79e5c8c4f8f"
+                .parse()
+                .unwrap();
+        let address = format!("{:?}", wallet.address());
+
+        let mut message = SiweMessage::new(
+            "mygame.example",
+            &address,
+            "https://mygame.example/login",
+            1,
+            "32891756",
+        )
+        .unwrap()
+        .with_statement("Sign in to My Game");
+        message.issued_at = "2021-09-30T16:25:24Z".to_string();
+        assert_eq!(
+            message.to_string(),
+            "mygame.example wants you to sign in with your Ethereum account:\n\
+             0x2c7536E3605D9C16a7a3D7b1898e529396a65c23\n\
+             \n\
+             Sign in to My Game\n\
+             \n\
+             URI: https://mygame.example/login\n\
+             Version: 1\n\
+             Chain ID: 1\n\
+             Nonce: 32891756\n\
+             Issued At: 2021-09-30T16:25:24Z"
+        );
+        assert_eq!(SiweMessage::parse(&message.to_string()).unwrap(), message);
+
+        let signature = SiweSigner::from(EthereumSigner::Local(wallet))
+            .sign(&message)
+            .await
+            .unwrap();
+        message.verify(&signature).unwrap();
+
+        let mut tampered = message.clone();
+        tampered.nonce = "00000000".to_string();
+        assert!(tampered.verify(&signature).is_err());
+
+        message.expiration_time = Some("2021-10-01T00:00:00Z".to_string());
+        assert!(message.verify(&signature).is_err());
+    }
+}
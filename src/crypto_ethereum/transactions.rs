@@ -3,11 +3,17 @@
 use super::dto::*;
 use crate::{IdosError, IdosResult};
 
+#[cfg(not(target_arch = "wasm32"))]
+use super::simulation::simulate_transaction;
+
+#[cfg(target_arch = "wasm32")]
+use super::helper;
+
 #[cfg(feature = "crypto_ethereum")]
 use ethers::{
     abi::{encode, Token as AbiToken},
     contract::abigen,
-    core::types::{Bytes, TransactionRequest, U256},
+    core::types::{transaction::eip2718::TypedTransaction, Bytes, TransactionRequest, U256},
     prelude::*,
     signers::{LocalWallet, Signer},
     utils::{hex, keccak256},
@@ -31,6 +37,7 @@ abigen!(
     PlatformPool,
     r#"[
         function depositERC20(address token, uint256 amount, string memory userID) external returns (bool)
+        function depositERC20WithPermit(address token, uint256 amount, string memory userID, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external returns (bool)
         function withdrawERC20(address token, address to, uint256 amount, uint256 nonce, bytes memory signature) external returns (bool)
     ]"#,
 );
@@ -46,6 +53,269 @@ abigen!(
     ]"#,
 );
 
+/// Parse a hex private key into a chain-bound local wallet
+#[cfg(feature = "crypto_ethereum")]
+pub fn local_wallet(private_key: &str, chain_id: u64) -> IdosResult<LocalWallet> {
+    let wallet: LocalWallet = private_key
+        .parse()
+        .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
+    Ok(wallet.with_chain_id(chain_id))
+}
+
+/// Create a signing client for the given RPC endpoint
+#[cfg(all(feature = "crypto_ethereum", not(target_arch = "wasm32")))]
+fn signer_client<S: Signer>(
+    rpc_url: &str,
+    signer: S,
+) -> IdosResult<SignerMiddleware<Provider<Http>, S>> {
+    let provider = Provider::<Http>::try_from(rpc_url)
+        .map_err(|e| IdosError::NetworkError(format!("Provider error: {}", e)))?;
+
+    Ok(SignerMiddleware::new(provider, signer))
+}
+
+/// Set the transaction's gas limit according to the gas policy
+/// Estimates against the signer's address so token balances and allowances apply
+#[cfg(feature = "crypto_ethereum")]
+pub async fn apply_gas_limit<M: Middleware>(
+    client: &M,
+    tx: &mut TypedTransaction,
+    gas_limit: GasLimit,
+) -> IdosResult<()> {
+    let gas = match gas_limit {
+        GasLimit::Fixed(limit) => U256::from(limit),
+        GasLimit::Estimate { margin_percent } => {
+            if tx.from().is_none() {
+                if let Some(sender) = client.default_sender() {
+                    tx.set_from(sender);
+                }
+            }
+
+            let estimate = client
+                .estimate_gas(tx, None)
+                .await
+                .map_err(|e| IdosError::NetworkError(format!("Gas estimation failed: {}", e)))?;
+
+            estimate * (100 + margin_percent) / 100
+        }
+    };
+
+    tx.set_gas(gas);
+    Ok(())
+}
+
+/// Simulate, apply the gas policy and send a prepared transaction
+#[cfg(all(feature = "crypto_ethereum", not(target_arch = "wasm32")))]
+async fn send_prepared<S: Signer + 'static>(
+    rpc_url: &str,
+    signer: S,
+    mut tx: TypedTransaction,
+    gas_limit: GasLimit,
+    failure: &str,
+) -> IdosResult<String> {
+    let client = signer_client(rpc_url, signer)?;
+
+    simulate_transaction(&client, &tx).await?;
+    apply_gas_limit(&client, &mut tx, gas_limit).await?;
+
+    let pending_tx = client
+        .send_transaction(tx, None)
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("{}: {}", failure, e)))?;
+
+    Ok(format!("{:?}", pending_tx.tx_hash()))
+}
+
+/// Sign a prepared transaction in Rust and send it with eth_sendRawTransaction
+/// Lets browser games use in-game wallets without a MetaMask-style extension
+#[cfg(all(feature = "crypto_ethereum", target_arch = "wasm32"))]
+async fn send_prepared<S: Signer + 'static>(
+    rpc_url: &str,
+    signer: S,
+    tx: TypedTransaction,
+    gas_limit: GasLimit,
+    failure: &str,
+) -> IdosResult<String> {
+    let signed = sign_prepared(rpc_url, signer, tx, gas_limit).await?;
+
+    helper::eth_send_raw_transaction(rpc_url, &signed.raw_transaction)
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("{}: {}", failure, e)))
+}
+
+/// Simulate, fill nonce/gas/chain ID and sign a prepared transaction without sending it
+#[cfg(all(feature = "crypto_ethereum", not(target_arch = "wasm32")))]
+async fn sign_prepared<S: Signer + 'static>(
+    rpc_url: &str,
+    signer: S,
+    mut tx: TypedTransaction,
+    gas_limit: GasLimit,
+) -> IdosResult<SignedTransaction> {
+    let client = signer_client(rpc_url, signer)?;
+    let from = client.address();
+    tx.set_from(from);
+
+    simulate_transaction(&client, &tx).await?;
+    apply_gas_limit(&client, &mut tx, gas_limit).await?;
+
+    if tx.nonce().is_none() {
+        let nonce = client
+            .get_transaction_count(from, Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(|e| IdosError::NetworkError(format!("Nonce query failed: {}", e)))?;
+        tx.set_nonce(nonce);
+    }
+
+    sign_transaction_offline(client.signer(), tx).await
+}
+
+/// Fill nonce/gas over the fetch-based JSON-RPC helpers and sign without sending
+#[cfg(all(feature = "crypto_ethereum", target_arch = "wasm32"))]
+async fn sign_prepared<S: Signer + 'static>(
+    rpc_url: &str,
+    signer: S,
+    mut tx: TypedTransaction,
+    gas_limit: GasLimit,
+) -> IdosResult<SignedTransaction> {
+    let from = format!("{:?}", signer.address());
+    let to = match tx.to() {
+        Some(NameOrAddress::Address(address)) => format!("{:?}", address),
+        _ => {
+            return Err(IdosError::InvalidInput(
+                "Transaction recipient not set".to_string(),
+            ))
+        }
+    };
+    let data = format!("0x{}", hex::encode(tx.data().cloned().unwrap_or_default()));
+    let value = format!("{:#x}", tx.value().copied().unwrap_or_default());
+
+    // eth_estimateGas doubles as the pre-flight simulation, so it runs even with a fixed limit
+    let estimate = helper::eth_estimate_gas(rpc_url, &from, &to, &data, &value)
+        .await
+        .map_err(|e| match e {
+            IdosError::NetworkError(message) if message.contains("execution reverted") => {
+                IdosError::TransactionReverted(
+                    message.trim_start_matches("RPC Error: ").to_string(),
+                )
+            }
+            other => other,
+        })?;
+    let estimate = parse_quantity(&estimate)?;
+
+    let gas = match gas_limit {
+        GasLimit::Fixed(limit) => U256::from(limit),
+        GasLimit::Estimate { margin_percent } => estimate * (100 + margin_percent) / 100,
+    };
+    tx.set_gas(gas);
+
+    if tx.nonce().is_none() {
+        let nonce = helper::eth_get_transaction_count(rpc_url, &from).await?;
+        tx.set_nonce(parse_quantity(&nonce)?);
+    }
+
+    sign_transaction_offline(&signer, tx).await
+}
+
+/// Parse a JSON-RPC hex quantity
+#[cfg(all(feature = "crypto_ethereum", target_arch = "wasm32"))]
+fn parse_quantity(value: &str) -> IdosResult<U256> {
+    U256::from_str_radix(value.trim_start_matches("0x"), 16)
+        .map_err(|e| IdosError::SerializationError(format!("Invalid quantity {}: {}", value, e)))
+}
+
+/// Sign a fully populated transaction without touching the network
+///
+/// The transaction must already carry nonce, gas limit and gas price; the chain ID
+/// is taken from the signer. Used for air-gapped signing.
+#[cfg(feature = "crypto_ethereum")]
+pub async fn sign_transaction_offline<S: Signer>(
+    signer: &S,
+    mut tx: TypedTransaction,
+) -> IdosResult<SignedTransaction> {
+    let nonce = *tx
+        .nonce()
+        .ok_or_else(|| IdosError::InvalidInput("Transaction nonce not set".to_string()))?;
+    if tx.gas().is_none() {
+        return Err(IdosError::InvalidInput(
+            "Transaction gas limit not set".to_string(),
+        ));
+    }
+    if tx.gas_price().is_none() {
+        return Err(IdosError::InvalidInput(
+            "Transaction gas price not set".to_string(),
+        ));
+    }
+
+    let from = signer.address();
+    tx.set_from(from);
+    tx.set_chain_id(signer.chain_id());
+
+    let signature = signer
+        .sign_transaction(&tx)
+        .await
+        .map_err(|e| IdosError::Wallet(format!("Signing failed: {}", e)))?;
+    let raw = tx.rlp_signed(&signature);
+
+    Ok(SignedTransaction {
+        raw_transaction: format!("0x{}", hex::encode(&raw)),
+        transaction_hash: format!("{:?}", H256::from(keccak256(&raw))),
+        from: format!("{:?}", from),
+        nonce: nonce.to_string(),
+    })
+}
+
+/// Broadcast a raw signed transaction (e.g. from [`SignedTransaction::raw_transaction`])
+/// Returns the transaction hash
+#[cfg(all(feature = "crypto_ethereum", not(target_arch = "wasm32")))]
+pub async fn broadcast_raw(rpc_url: &str, raw_transaction: &str) -> IdosResult<String> {
+    let provider = Provider::<Http>::try_from(rpc_url)
+        .map_err(|e| IdosError::NetworkError(format!("Provider error: {}", e)))?;
+
+    let raw = hex::decode(raw_transaction.trim_start_matches("0x"))
+        .map_err(|e| IdosError::InvalidInput(format!("Invalid raw transaction: {}", e)))?;
+
+    let pending_tx = provider
+        .send_raw_transaction(Bytes::from(raw))
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("Broadcast failed: {}", e)))?;
+
+    Ok(format!("{:?}", pending_tx.tx_hash()))
+}
+
+/// Broadcast a raw signed transaction (e.g. from [`SignedTransaction::raw_transaction`])
+/// Returns the transaction hash
+#[cfg(all(feature = "crypto_ethereum", target_arch = "wasm32"))]
+pub async fn broadcast_raw(rpc_url: &str, raw_transaction: &str) -> IdosResult<String> {
+    helper::eth_send_raw_transaction(rpc_url, raw_transaction)
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("Broadcast failed: {}", e)))
+}
+
+/// Function selector followed by ABI-encoded arguments
+#[cfg(feature = "crypto_ethereum")]
+fn encode_call(function_sig: &str, tokens: &[AbiToken]) -> Vec<u8> {
+    let mut calldata = keccak256(function_sig.as_bytes())[0..4].to_vec();
+    calldata.extend_from_slice(&encode(tokens));
+    calldata
+}
+
+/// Unsigned contract call with the given gas price
+#[cfg(feature = "crypto_ethereum")]
+fn contract_call(
+    to: Address,
+    calldata: Vec<u8>,
+    gas_price_gwei: f64,
+) -> IdosResult<TypedTransaction> {
+    let gas_price = ethers::utils::parse_units(gas_price_gwei, "gwei")
+        .map_err(|e| IdosError::InvalidInput(format!("Invalid gas price: {}", e)))?;
+
+    Ok(TransactionRequest::new()
+        .to(to)
+        .data(Bytes::from(calldata))
+        .gas_price(gas_price)
+        .into())
+}
+
 /// Approve ERC20 token for spending
 /// Matches Unity SDK's ApproveERC20Token
 #[cfg(feature = "crypto_ethereum")]
@@ -58,16 +328,59 @@ pub async fn approve_erc20(
     chain_id: u64,
     gas_price_gwei: f64,
 ) -> IdosResult<String> {
-    let provider = Provider::<Http>::try_from(rpc_url)
-        .map_err(|e| IdosError::NetworkError(format!("Provider error: {}", e)))?;
+    let wallet = local_wallet(private_key, chain_id)?;
+    approve_erc20_with_signer(
+        rpc_url,
+        token_address,
+        spender_address,
+        amount_wei,
+        wallet,
+        gas_price_gwei,
+        GasLimit::default(),
+    )
+    .await
+}
 
-    let wallet: LocalWallet = private_key
-        .parse()
-        .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
-    let wallet = wallet.with_chain_id(chain_id);
+/// Same as [`approve_erc20`], signing with any ethers signer (e.g. a Ledger)
+#[cfg(feature = "crypto_ethereum")]
+pub async fn approve_erc20_with_signer<S: Signer + 'static>(
+    rpc_url: &str,
+    token_address: &str,
+    spender_address: &str,
+    amount_wei: &str,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<String> {
+    let tx = build_approve_erc20(token_address, spender_address, amount_wei, gas_price_gwei)?;
+
+    send_prepared(rpc_url, signer, tx, gas_limit, "Transaction failed").await
+}
 
-    let client = SignerMiddleware::new(provider, wallet);
+/// Same as [`approve_erc20_with_signer`], returning the signed transaction instead of sending it
+#[cfg(feature = "crypto_ethereum")]
+pub async fn build_and_sign_approve_erc20<S: Signer + 'static>(
+    rpc_url: &str,
+    token_address: &str,
+    spender_address: &str,
+    amount_wei: &str,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<SignedTransaction> {
+    let tx = build_approve_erc20(token_address, spender_address, amount_wei, gas_price_gwei)?;
 
+    sign_prepared(rpc_url, signer, tx, gas_limit).await
+}
+
+/// Unsigned `approve(spender, amount)` transaction
+#[cfg(feature = "crypto_ethereum")]
+pub fn build_approve_erc20(
+    token_address: &str,
+    spender_address: &str,
+    amount_wei: &str,
+    gas_price_gwei: f64,
+) -> IdosResult<TypedTransaction> {
     let token_addr: Address = token_address
         .parse()
         .map_err(|_| IdosError::InvalidInput("Invalid token address".to_string()))?;
@@ -78,22 +391,12 @@ pub async fn approve_erc20(
         .parse()
         .map_err(|_| IdosError::InvalidInput("Invalid amount".to_string()))?;
 
-    let erc20 = ERC20::new(token_addr, std::sync::Arc::new(client));
-
-    let gas_price = ethers::utils::parse_units(gas_price_gwei, "gwei")
-        .map_err(|e| IdosError::InvalidInput(format!("Invalid gas price: {}", e)))?;
+    let calldata = encode_call(
+        "approve(address,uint256)",
+        &[AbiToken::Address(spender), AbiToken::Uint(amount)],
+    );
 
-    let tx = erc20
-        .approve(spender, amount)
-        .gas_price(gas_price)
-        .gas(50000u64);
-
-    let pending_tx = tx
-        .send()
-        .await
-        .map_err(|e| IdosError::NetworkError(format!("Transaction failed: {}", e)))?;
-
-    Ok(format!("{:?}", pending_tx.tx_hash()))
+    contract_call(token_addr, calldata, gas_price_gwei)
 }
 
 /// Deposit ERC20 tokens to platform pool
@@ -109,16 +412,77 @@ pub async fn deposit_erc20(
     chain_id: u64,
     gas_price_gwei: f64,
 ) -> IdosResult<String> {
-    let provider = Provider::<Http>::try_from(rpc_url)
-        .map_err(|e| IdosError::NetworkError(format!("Provider error: {}", e)))?;
+    let wallet = local_wallet(private_key, chain_id)?;
+    deposit_erc20_with_signer(
+        rpc_url,
+        platform_pool_address,
+        token_address,
+        amount_wei,
+        user_id,
+        wallet,
+        gas_price_gwei,
+        GasLimit::default(),
+    )
+    .await
+}
 
-    let wallet: LocalWallet = private_key
-        .parse()
-        .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
-    let wallet = wallet.with_chain_id(chain_id);
+/// Same as [`deposit_erc20`], signing with any ethers signer (e.g. a Ledger)
+#[cfg(feature = "crypto_ethereum")]
+#[allow(clippy::too_many_arguments)]
+pub async fn deposit_erc20_with_signer<S: Signer + 'static>(
+    rpc_url: &str,
+    platform_pool_address: &str,
+    token_address: &str,
+    amount_wei: &str,
+    user_id: &str,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<String> {
+    let tx = build_deposit_erc20(
+        platform_pool_address,
+        token_address,
+        amount_wei,
+        user_id,
+        gas_price_gwei,
+    )?;
+
+    send_prepared(rpc_url, signer, tx, gas_limit, "Deposit failed").await
+}
 
-    let client = SignerMiddleware::new(provider, wallet);
+/// Same as [`deposit_erc20_with_signer`], returning the signed transaction instead of sending it
+#[cfg(feature = "crypto_ethereum")]
+#[allow(clippy::too_many_arguments)]
+pub async fn build_and_sign_deposit_erc20<S: Signer + 'static>(
+    rpc_url: &str,
+    platform_pool_address: &str,
+    token_address: &str,
+    amount_wei: &str,
+    user_id: &str,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<SignedTransaction> {
+    let tx = build_deposit_erc20(
+        platform_pool_address,
+        token_address,
+        amount_wei,
+        user_id,
+        gas_price_gwei,
+    )?;
+
+    sign_prepared(rpc_url, signer, tx, gas_limit).await
+}
 
+/// Unsigned `depositERC20(token, amount, userID)` transaction
+#[cfg(feature = "crypto_ethereum")]
+pub fn build_deposit_erc20(
+    platform_pool_address: &str,
+    token_address: &str,
+    amount_wei: &str,
+    user_id: &str,
+    gas_price_gwei: f64,
+) -> IdosResult<TypedTransaction> {
     let pool_addr: Address = platform_pool_address
         .parse()
         .map_err(|_| IdosError::InvalidInput("Invalid pool address".to_string()))?;
@@ -129,22 +493,78 @@ pub async fn deposit_erc20(
         .parse()
         .map_err(|_| IdosError::InvalidInput("Invalid amount".to_string()))?;
 
-    let pool = PlatformPool::new(pool_addr, std::sync::Arc::new(client));
+    let calldata = encode_call(
+        "depositERC20(address,uint256,string)",
+        &[
+            AbiToken::Address(token_addr),
+            AbiToken::Uint(amount),
+            AbiToken::String(user_id.to_string()),
+        ],
+    );
 
-    let gas_price = ethers::utils::parse_units(gas_price_gwei, "gwei")
-        .map_err(|e| IdosError::InvalidInput(format!("Invalid gas price: {}", e)))?;
+    contract_call(pool_addr, calldata, gas_price_gwei)
+}
 
-    let tx = pool
-        .deposit_erc20(token_addr, amount, user_id.to_string())
-        .gas_price(gas_price)
-        .gas(90000u64);
+/// Deposit ERC20 tokens with an EIP-2612 permit instead of a prior approval
+#[cfg(feature = "crypto_ethereum")]
+#[allow(clippy::too_many_arguments)]
+pub async fn deposit_erc20_with_permit_with_signer<S: Signer + 'static>(
+    rpc_url: &str,
+    platform_pool_address: &str,
+    token_address: &str,
+    amount_wei: &str,
+    user_id: &str,
+    permit: &super::permit::PermitSignature,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<String> {
+    let tx = build_deposit_erc20_with_permit(
+        platform_pool_address,
+        token_address,
+        amount_wei,
+        user_id,
+        permit,
+        gas_price_gwei,
+    )?;
+
+    send_prepared(rpc_url, signer, tx, gas_limit, "Deposit failed").await
+}
 
-    let pending_tx = tx
-        .send()
-        .await
-        .map_err(|e| IdosError::NetworkError(format!("Deposit failed: {}", e)))?;
+/// Unsigned `depositERC20WithPermit(token, amount, userID, deadline, v, r, s)` transaction
+#[cfg(feature = "crypto_ethereum")]
+pub fn build_deposit_erc20_with_permit(
+    platform_pool_address: &str,
+    token_address: &str,
+    amount_wei: &str,
+    user_id: &str,
+    permit: &super::permit::PermitSignature,
+    gas_price_gwei: f64,
+) -> IdosResult<TypedTransaction> {
+    let pool_addr: Address = platform_pool_address
+        .parse()
+        .map_err(|_| IdosError::InvalidInput("Invalid pool address".to_string()))?;
+    let token_addr: Address = token_address
+        .parse()
+        .map_err(|_| IdosError::InvalidInput("Invalid token address".to_string()))?;
+    let amount: U256 = amount_wei
+        .parse()
+        .map_err(|_| IdosError::InvalidInput("Invalid amount".to_string()))?;
 
-    Ok(format!("{:?}", pending_tx.tx_hash()))
+    let calldata = encode_call(
+        "depositERC20WithPermit(address,uint256,string,uint256,uint8,bytes32,bytes32)",
+        &[
+            AbiToken::Address(token_addr),
+            AbiToken::Uint(amount),
+            AbiToken::String(user_id.to_string()),
+            AbiToken::Uint(permit.deadline),
+            AbiToken::Uint(U256::from(permit.v)),
+            AbiToken::FixedBytes(permit.r.to_vec()),
+            AbiToken::FixedBytes(permit.s.to_vec()),
+        ],
+    );
+
+    contract_call(pool_addr, calldata, gas_price_gwei)
 }
 
 /// Withdraw ERC20 tokens with backend signature
@@ -157,16 +577,57 @@ pub async fn withdraw_erc20(
     chain_id: u64,
     gas_price_gwei: f64,
 ) -> IdosResult<String> {
-    let provider = Provider::<Http>::try_from(rpc_url)
-        .map_err(|e| IdosError::NetworkError(format!("Provider error: {}", e)))?;
+    let wallet = local_wallet(private_key, chain_id)?;
+    withdraw_erc20_with_signer(
+        rpc_url,
+        withdrawal_data,
+        wallet,
+        gas_price_gwei,
+        GasLimit::default(),
+    )
+    .await
+}
 
-    let wallet: LocalWallet = private_key
-        .parse()
-        .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
-    let wallet = wallet.with_chain_id(chain_id);
+/// Same as [`withdraw_erc20`], signing with any ethers signer (e.g. a Ledger)
+#[cfg(feature = "crypto_ethereum")]
+pub async fn withdraw_erc20_with_signer<S: Signer + 'static>(
+    rpc_url: &str,
+    withdrawal_data: &WithdrawalSignatureResult,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<String> {
+    let tx = build_withdraw_erc20(withdrawal_data, gas_price_gwei)?;
+
+    let failure = if withdrawal_data.user_id.is_some() {
+        "Withdrawal V2 failed"
+    } else {
+        "Withdrawal failed"
+    };
 
-    let client = SignerMiddleware::new(provider, wallet);
+    send_prepared(rpc_url, signer, tx, gas_limit, failure).await
+}
+
+/// Same as [`withdraw_erc20_with_signer`], returning the signed transaction instead of sending it
+#[cfg(feature = "crypto_ethereum")]
+pub async fn build_and_sign_withdraw_erc20<S: Signer + 'static>(
+    rpc_url: &str,
+    withdrawal_data: &WithdrawalSignatureResult,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<SignedTransaction> {
+    let tx = build_withdraw_erc20(withdrawal_data, gas_price_gwei)?;
 
+    sign_prepared(rpc_url, signer, tx, gas_limit).await
+}
+
+/// Unsigned `withdrawERC20` transaction from a backend withdrawal signature
+#[cfg(feature = "crypto_ethereum")]
+pub fn build_withdraw_erc20(
+    withdrawal_data: &WithdrawalSignatureResult,
+    gas_price_gwei: f64,
+) -> IdosResult<TypedTransaction> {
     let pool_addr: Address = withdrawal_data
         .contract_address
         .parse()
@@ -192,66 +653,29 @@ pub async fn withdraw_erc20(
     let signature_bytes = hex::decode(withdrawal_data.signature.trim_start_matches("0x"))
         .map_err(|e| IdosError::InvalidInput(format!("Invalid signature: {}", e)))?;
 
-    let gas_price = ethers::utils::parse_units(gas_price_gwei, "gwei")
-        .map_err(|e| IdosError::InvalidInput(format!("Invalid gas price: {}", e)))?;
-
-    let signature_bytes_ethers = Bytes::from(signature_bytes.clone());
+    let mut tokens = vec![
+        AbiToken::Address(token_addr),
+        AbiToken::Address(to_addr),
+        AbiToken::Uint(amount),
+        AbiToken::Uint(nonce),
+        AbiToken::Bytes(signature_bytes),
+    ];
 
     // Handle both versions: with and without userID
     // Unity SDK has WithdrawERC20Function (5 params) and WithdrawERC20FunctionV2 (6 params)
-    let tx_hash = if let Some(user_id) = &withdrawal_data.user_id {
-        // V2: withdrawERC20(address token, address to, uint256 amount, uint256 nonce, bytes signature, string userID)
-        // Manually encode calldata since Solidity function overloading needs different signatures
-
-        // Calculate function selector from signature
-        let function_sig = "withdrawERC20(address,address,uint256,uint256,bytes,string)";
-        let selector_hash = keccak256(function_sig.as_bytes());
-        let selector = &selector_hash[0..4];
-
-        let tokens = vec![
-            AbiToken::Address(token_addr),
-            AbiToken::Address(to_addr),
-            AbiToken::Uint(amount),
-            AbiToken::Uint(nonce),
-            AbiToken::Bytes(signature_bytes.clone()),
-            AbiToken::String(user_id.clone()),
-        ];
-
-        let encoded = encode(&tokens);
-        let mut calldata = selector.to_vec();
-        calldata.extend_from_slice(&encoded);
-
-        // Send transaction with manual calldata
-        let tx_request = TransactionRequest::new()
-            .to(pool_addr)
-            .data(Bytes::from(calldata))
-            .gas_price(gas_price)
-            .gas(150000u64);
-
-        let pending_tx = client
-            .send_transaction(tx_request, None)
-            .await
-            .map_err(|e| IdosError::NetworkError(format!("Withdrawal V2 failed: {}", e)))?;
-
-        format!("{:?}", pending_tx.tx_hash())
+    // Manually encode calldata since Solidity function overloading needs different signatures
+    let function_sig = if let Some(user_id) = &withdrawal_data.user_id {
+        tokens.push(AbiToken::String(user_id.clone()));
+        "withdrawERC20(address,address,uint256,uint256,bytes,string)"
     } else {
-        // V1: withdrawERC20(address token, address to, uint256 amount, uint256 nonce, bytes signature)
-        let pool = PlatformPool::new(pool_addr, std::sync::Arc::new(client.clone()));
-
-        let tx = pool
-            .withdraw_erc20(token_addr, to_addr, amount, nonce, signature_bytes_ethers)
-            .gas_price(gas_price)
-            .gas(150000u64);
-
-        let pending_tx = tx
-            .send()
-            .await
-            .map_err(|e| IdosError::NetworkError(format!("Withdrawal failed: {}", e)))?;
-
-        format!("{:?}", pending_tx.tx_hash())
+        "withdrawERC20(address,address,uint256,uint256,bytes)"
     };
 
-    Ok(tx_hash)
+    contract_call(
+        pool_addr,
+        encode_call(function_sig, &tokens),
+        gas_price_gwei,
+    )
 }
 
 /// Transfer ERC20 tokens to external address
@@ -267,16 +691,59 @@ pub async fn transfer_erc20(
     chain_id: u64,
     gas_price_gwei: f64,
 ) -> IdosResult<String> {
-    let provider = Provider::<Http>::try_from(rpc_url)
-        .map_err(|e| IdosError::NetworkError(format!("Provider error: {}", e)))?;
+    let wallet = local_wallet(private_key, chain_id)?;
+    transfer_erc20_with_signer(
+        rpc_url,
+        token_address,
+        to_address,
+        amount,
+        wallet,
+        gas_price_gwei,
+        GasLimit::default(),
+    )
+    .await
+}
 
-    let wallet: LocalWallet = private_key
-        .parse()
-        .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
-    let wallet = wallet.with_chain_id(chain_id);
+/// Same as [`transfer_erc20`], signing with any ethers signer (e.g. a Ledger)
+#[cfg(feature = "crypto_ethereum")]
+pub async fn transfer_erc20_with_signer<S: Signer + 'static>(
+    rpc_url: &str,
+    token_address: &str,
+    to_address: &str,
+    amount: u64,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<String> {
+    let tx = build_transfer_erc20(token_address, to_address, amount, gas_price_gwei)?;
 
-    let client = SignerMiddleware::new(provider, wallet);
+    send_prepared(rpc_url, signer, tx, gas_limit, "Transfer failed").await
+}
 
+/// Same as [`transfer_erc20_with_signer`], returning the signed transaction instead of sending it
+#[cfg(feature = "crypto_ethereum")]
+pub async fn build_and_sign_transfer_erc20<S: Signer + 'static>(
+    rpc_url: &str,
+    token_address: &str,
+    to_address: &str,
+    amount: u64,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<SignedTransaction> {
+    let tx = build_transfer_erc20(token_address, to_address, amount, gas_price_gwei)?;
+
+    sign_prepared(rpc_url, signer, tx, gas_limit).await
+}
+
+/// Unsigned `transfer(to, amount)` transaction, `amount` in whole tokens (18 decimals)
+#[cfg(feature = "crypto_ethereum")]
+pub fn build_transfer_erc20(
+    token_address: &str,
+    to_address: &str,
+    amount: u64,
+    gas_price_gwei: f64,
+) -> IdosResult<TypedTransaction> {
     let token_addr: Address = token_address
         .parse()
         .map_err(|_| IdosError::InvalidInput("Invalid token address".to_string()))?;
@@ -289,22 +756,12 @@ pub async fn transfer_erc20(
         .map_err(|e| IdosError::InvalidInput(format!("Invalid amount: {}", e)))?
         .into();
 
-    let erc20 = ERC20::new(token_addr, std::sync::Arc::new(client));
+    let calldata = encode_call(
+        "transfer(address,uint256)",
+        &[AbiToken::Address(to_addr), AbiToken::Uint(amount_wei)],
+    );
 
-    let gas_price = ethers::utils::parse_units(gas_price_gwei, "gwei")
-        .map_err(|e| IdosError::InvalidInput(format!("Invalid gas price: {}", e)))?;
-
-    let tx = erc20
-        .transfer(to_addr, amount_wei)
-        .gas_price(gas_price)
-        .gas(100000u64);
-
-    let pending_tx = tx
-        .send()
-        .await
-        .map_err(|e| IdosError::NetworkError(format!("Transfer failed: {}", e)))?;
-
-    Ok(format!("{:?}", pending_tx.tx_hash()))
+    contract_call(token_addr, calldata, gas_price_gwei)
 }
 
 /// Get ERC1155 NFT balance for multiple token IDs
@@ -362,16 +819,89 @@ pub async fn transfer_nft_erc1155(
     chain_id: u64,
     gas_price_gwei: f64,
 ) -> IdosResult<String> {
-    let provider = Provider::<Http>::try_from(rpc_url)
-        .map_err(|e| IdosError::NetworkError(format!("Provider error: {}", e)))?;
+    let wallet = local_wallet(private_key, chain_id)?;
+    transfer_nft_erc1155_with_signer(
+        rpc_url,
+        nft_contract_address,
+        from_address,
+        to_address,
+        token_id,
+        amount,
+        user_id,
+        wallet,
+        gas_price_gwei,
+        GasLimit::default(),
+    )
+    .await
+}
 
-    let wallet: LocalWallet = private_key
-        .parse()
-        .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
-    let wallet = wallet.with_chain_id(chain_id);
+/// Same as [`transfer_nft_erc1155`], signing with any ethers signer (e.g. a Ledger)
+#[cfg(feature = "crypto_ethereum")]
+#[allow(clippy::too_many_arguments)]
+pub async fn transfer_nft_erc1155_with_signer<S: Signer + 'static>(
+    rpc_url: &str,
+    nft_contract_address: &str,
+    from_address: &str,
+    to_address: &str,
+    token_id: &str,
+    amount: u64,
+    user_id: Option<&str>,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<String> {
+    let tx = build_transfer_nft_erc1155(
+        nft_contract_address,
+        from_address,
+        to_address,
+        token_id,
+        amount,
+        user_id,
+        gas_price_gwei,
+    )?;
+
+    send_prepared(rpc_url, signer, tx, gas_limit, "NFT transfer failed").await
+}
 
-    let client = SignerMiddleware::new(provider, wallet);
+/// Same as [`transfer_nft_erc1155_with_signer`], returning the signed transaction instead of sending it
+#[cfg(feature = "crypto_ethereum")]
+#[allow(clippy::too_many_arguments)]
+pub async fn build_and_sign_transfer_nft_erc1155<S: Signer + 'static>(
+    rpc_url: &str,
+    nft_contract_address: &str,
+    from_address: &str,
+    to_address: &str,
+    token_id: &str,
+    amount: u64,
+    user_id: Option<&str>,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<SignedTransaction> {
+    let tx = build_transfer_nft_erc1155(
+        nft_contract_address,
+        from_address,
+        to_address,
+        token_id,
+        amount,
+        user_id,
+        gas_price_gwei,
+    )?;
+
+    sign_prepared(rpc_url, signer, tx, gas_limit).await
+}
 
+/// Unsigned `safeTransferFrom(from, to, id, amount, data)` transaction
+#[cfg(feature = "crypto_ethereum")]
+pub fn build_transfer_nft_erc1155(
+    nft_contract_address: &str,
+    from_address: &str,
+    to_address: &str,
+    token_id: &str,
+    amount: u64,
+    user_id: Option<&str>,
+    gas_price_gwei: f64,
+) -> IdosResult<TypedTransaction> {
     let nft_addr: Address = nft_contract_address
         .parse()
         .map_err(|_| IdosError::InvalidInput("Invalid NFT contract address".to_string()))?;
@@ -385,29 +915,23 @@ pub async fn transfer_nft_erc1155(
         .parse()
         .map_err(|_| IdosError::InvalidInput("Invalid token ID".to_string()))?;
 
-    let erc1155 = ERC1155::new(nft_addr, std::sync::Arc::new(client));
-
-    let gas_price = ethers::utils::parse_units(gas_price_gwei, "gwei")
-        .map_err(|e| IdosError::InvalidInput(format!("Invalid gas price: {}", e)))?;
-
     // Data field: encode userID if present (matches Unity SDK)
-    let data = if let Some(uid) = user_id {
-        Bytes::from(uid.as_bytes().to_vec())
-    } else {
-        Bytes::from(vec![])
-    };
-
-    let tx = erc1155
-        .safe_transfer_from(from_addr, to_addr, id, amount.into(), data)
-        .gas_price(gas_price)
-        .gas(100000u64);
-
-    let pending_tx = tx
-        .send()
-        .await
-        .map_err(|e| IdosError::NetworkError(format!("NFT transfer failed: {}", e)))?;
+    let data = user_id
+        .map(|uid| uid.as_bytes().to_vec())
+        .unwrap_or_default();
+
+    let calldata = encode_call(
+        "safeTransferFrom(address,address,uint256,uint256,bytes)",
+        &[
+            AbiToken::Address(from_addr),
+            AbiToken::Address(to_addr),
+            AbiToken::Uint(id),
+            AbiToken::Uint(amount.into()),
+            AbiToken::Bytes(data),
+        ],
+    );
 
-    Ok(format!("{:?}", pending_tx.tx_hash()))
+    contract_call(nft_addr, calldata, gas_price_gwei)
 }
 
 /// Withdraw ERC1155 NFT with backend signature
@@ -420,16 +944,57 @@ pub async fn withdraw_nft_erc1155(
     chain_id: u64,
     gas_price_gwei: f64,
 ) -> IdosResult<String> {
-    let provider = Provider::<Http>::try_from(rpc_url)
-        .map_err(|e| IdosError::NetworkError(format!("Provider error: {}", e)))?;
+    let wallet = local_wallet(private_key, chain_id)?;
+    withdraw_nft_erc1155_with_signer(
+        rpc_url,
+        withdrawal_data,
+        wallet,
+        gas_price_gwei,
+        GasLimit::default(),
+    )
+    .await
+}
 
-    let wallet: LocalWallet = private_key
-        .parse()
-        .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
-    let wallet = wallet.with_chain_id(chain_id);
+/// Same as [`withdraw_nft_erc1155`], signing with any ethers signer (e.g. a Ledger)
+#[cfg(feature = "crypto_ethereum")]
+pub async fn withdraw_nft_erc1155_with_signer<S: Signer + 'static>(
+    rpc_url: &str,
+    withdrawal_data: &WithdrawalSignatureResult,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<String> {
+    let tx = build_withdraw_nft_erc1155(withdrawal_data, gas_price_gwei)?;
+
+    let failure = if withdrawal_data.user_id.is_some() {
+        "NFT withdrawal V2 failed"
+    } else {
+        "NFT withdrawal failed"
+    };
 
-    let client = SignerMiddleware::new(provider, wallet);
+    send_prepared(rpc_url, signer, tx, gas_limit, failure).await
+}
 
+/// Same as [`withdraw_nft_erc1155_with_signer`], returning the signed transaction instead of sending it
+#[cfg(feature = "crypto_ethereum")]
+pub async fn build_and_sign_withdraw_nft_erc1155<S: Signer + 'static>(
+    rpc_url: &str,
+    withdrawal_data: &WithdrawalSignatureResult,
+    signer: S,
+    gas_price_gwei: f64,
+    gas_limit: GasLimit,
+) -> IdosResult<SignedTransaction> {
+    let tx = build_withdraw_nft_erc1155(withdrawal_data, gas_price_gwei)?;
+
+    sign_prepared(rpc_url, signer, tx, gas_limit).await
+}
+
+/// Unsigned `withdrawERC1155` transaction from a backend withdrawal signature
+#[cfg(feature = "crypto_ethereum")]
+pub fn build_withdraw_nft_erc1155(
+    withdrawal_data: &WithdrawalSignatureResult,
+    gas_price_gwei: f64,
+) -> IdosResult<TypedTransaction> {
     let pool_addr: Address = withdrawal_data
         .contract_address
         .parse()
@@ -460,78 +1025,28 @@ pub async fn withdraw_nft_erc1155(
     let signature_bytes = hex::decode(withdrawal_data.signature.trim_start_matches("0x"))
         .map_err(|e| IdosError::InvalidInput(format!("Invalid signature: {}", e)))?;
 
-    let gas_price = ethers::utils::parse_units(gas_price_gwei, "gwei")
-        .map_err(|e| IdosError::InvalidInput(format!("Invalid gas price: {}", e)))?;
+    let mut tokens = vec![
+        AbiToken::Address(token_addr),
+        AbiToken::Address(to_addr),
+        AbiToken::Uint(token_id),
+        AbiToken::Uint(amount),
+        AbiToken::Uint(nonce),
+        AbiToken::Bytes(signature_bytes),
+    ];
 
     // Handle both V1 and V2 (with userID)
-    let tx_hash = if let Some(user_id) = &withdrawal_data.user_id {
-        // V2: withdrawERC1155(address token, address to, uint256 id, uint256 amount, uint256 nonce, bytes signature, string userID)
-
-        let function_sig = "withdrawERC1155(address,address,uint256,uint256,uint256,bytes,string)";
-        let selector_hash = keccak256(function_sig.as_bytes());
-        let selector = &selector_hash[0..4];
-
-        let tokens = vec![
-            AbiToken::Address(token_addr),
-            AbiToken::Address(to_addr),
-            AbiToken::Uint(token_id),
-            AbiToken::Uint(amount),
-            AbiToken::Uint(nonce),
-            AbiToken::Bytes(signature_bytes),
-            AbiToken::String(user_id.clone()),
-        ];
-
-        let encoded = encode(&tokens);
-        let mut calldata = selector.to_vec();
-        calldata.extend_from_slice(&encoded);
-
-        let tx_request = TransactionRequest::new()
-            .to(pool_addr)
-            .data(Bytes::from(calldata))
-            .gas_price(gas_price)
-            .gas(150000u64);
-
-        let pending_tx = client
-            .send_transaction(tx_request, None)
-            .await
-            .map_err(|e| IdosError::NetworkError(format!("NFT withdrawal V2 failed: {}", e)))?;
-
-        format!("{:?}", pending_tx.tx_hash())
+    let function_sig = if let Some(user_id) = &withdrawal_data.user_id {
+        tokens.push(AbiToken::String(user_id.clone()));
+        "withdrawERC1155(address,address,uint256,uint256,uint256,bytes,string)"
     } else {
-        // V1: withdrawERC1155(address token, address to, uint256 id, uint256 amount, uint256 nonce, bytes signature)
-
-        let function_sig = "withdrawERC1155(address,address,uint256,uint256,uint256,bytes)";
-        let selector_hash = keccak256(function_sig.as_bytes());
-        let selector = &selector_hash[0..4];
-
-        let tokens = vec![
-            AbiToken::Address(token_addr),
-            AbiToken::Address(to_addr),
-            AbiToken::Uint(token_id),
-            AbiToken::Uint(amount),
-            AbiToken::Uint(nonce),
-            AbiToken::Bytes(signature_bytes),
-        ];
-
-        let encoded = encode(&tokens);
-        let mut calldata = selector.to_vec();
-        calldata.extend_from_slice(&encoded);
-
-        let tx_request = TransactionRequest::new()
-            .to(pool_addr)
-            .data(Bytes::from(calldata))
-            .gas_price(gas_price)
-            .gas(150000u64);
-
-        let pending_tx = client
-            .send_transaction(tx_request, None)
-            .await
-            .map_err(|e| IdosError::NetworkError(format!("NFT withdrawal failed: {}", e)))?;
-
-        format!("{:?}", pending_tx.tx_hash())
+        "withdrawERC1155(address,address,uint256,uint256,uint256,bytes)"
     };
 
-    Ok(tx_hash)
+    contract_call(
+        pool_addr,
+        encode_call(function_sig, &tokens),
+        gas_price_gwei,
+    )
 }
 
 // ==================== GAS ESTIMATION ====================
@@ -704,3 +1219,46 @@ pub async fn estimate_gas_erc20_approval(
 
     Ok(gas_estimate.as_u64())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::utils::rlp::Rlp;
+
+    #[tokio::test]
+    async fn test_sign_transaction_offline_round_trip() {
+        let wallet = local_wallet(
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            137,
+        )
+        .unwrap();
+        let mut tx = build_approve_erc20(
+            "0x0000000000000000000000000000000000000001",
+            "0x0000000000000000000000000000000000000002",
+            "1000",
+            30.0,
+        )
+        .unwrap();
+
+        // Nonce and gas are required without an RPC
+        assert!(sign_transaction_offline(&wallet, tx.clone()).await.is_err());
+
+        tx.set_nonce(7);
+        tx.set_gas(60_000);
+        let signed = sign_transaction_offline(&wallet, tx).await.unwrap();
+
+        let raw = hex::decode(signed.raw_transaction.trim_start_matches("0x")).unwrap();
+        let (decoded, signature) = TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+
+        assert_eq!(signed.nonce, "7");
+        assert_eq!(decoded.chain_id(), Some(137.into()));
+        assert_eq!(
+            signature.recover(decoded.sighash()).unwrap(),
+            wallet.address()
+        );
+        assert_eq!(
+            signed.transaction_hash,
+            format!("{:?}", H256::from(keccak256(&raw)))
+        );
+    }
+}
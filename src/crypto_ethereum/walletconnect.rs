@@ -0,0 +1,937 @@
+/// WalletConnect v2 for Ethereum on native targets
+/// Pairs with a phone wallet through a `wc:` URI (shown as a QR code), keeps the session
+/// across restarts and forwards signing requests to the wallet, so keys never enter the game
+use super::walletconnect_crypto::*;
+use crate::{storage::Storage, IdosClient, IdosError, IdosResult};
+use bevy::prelude::*;
+use ethers::types::{transaction::eip2718::TypedTransaction, Address, Signature, H256};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+use x25519_dalek::StaticSecret;
+
+pub const DEFAULT_RELAY_URL: &str = "wss://relay.walletconnect.org";
+
+/// How long a pairing URI stays valid
+const PAIRING_TTL_SECONDS: i64 = 300;
+/// How long the player has to approve a request on their phone
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+const SESSION_KEY: &str = "session";
+const IDENTITY_KEY: &str = "identity";
+
+// Relay message tags and TTLs from the Sign API
+const TAG_SESSION_PROPOSE: u32 = 1100;
+const TAG_SESSION_REQUEST: u32 = 1108;
+const TAG_SESSION_DELETE: u32 = 1112;
+const TTL_SESSION_PROPOSE: i64 = 300;
+const TTL_SESSION_REQUEST: i64 = 300;
+const TTL_SESSION_DELETE: i64 = 86400;
+const TTL_RESPONSE: i64 = 86400;
+
+/// App details shown in the wallet's approval screen
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletConnectMetadata {
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    pub icons: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WalletConnectConfig {
+    /// Project id from the WalletConnect cloud dashboard
+    pub project_id: String,
+    pub relay_url: String,
+    pub metadata: WalletConnectMetadata,
+    /// EIP-155 chain ids offered to the wallet
+    pub chains: Vec<u64>,
+    /// JSON-RPC methods the game will ask the wallet for
+    pub methods: Vec<String>,
+}
+
+impl WalletConnectConfig {
+    pub fn new(project_id: impl Into<String>, metadata: WalletConnectMetadata) -> Self {
+        Self {
+            project_id: project_id.into(),
+            relay_url: DEFAULT_RELAY_URL.to_string(),
+            metadata,
+            chains: vec![1],
+            methods: vec![
+                "eth_sendTransaction".to_string(),
+                "personal_sign".to_string(),
+                "eth_signTypedData_v4".to_string(),
+            ],
+        }
+    }
+}
+
+/// Session approved by a wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletConnectSession {
+    pub topic: String,
+    /// Hex session key
+    sym_key: String,
+    /// CAIP-10 accounts (`eip155:<chain>:<address>`)
+    pub accounts: Vec<String>,
+    pub peer: WalletConnectMetadata,
+    /// Unix seconds
+    pub expiry: i64,
+}
+
+impl WalletConnectSession {
+    /// Address of the first account
+    pub fn address(&self) -> Option<Address> {
+        self.accounts
+            .first()
+            .and_then(|account| account.rsplit(':').next())
+            .and_then(|address| address.parse().ok())
+    }
+
+    /// Chain of the first account
+    pub fn chain_id(&self) -> Option<u64> {
+        self.accounts
+            .first()
+            .and_then(|account| account.split(':').nth(1))
+            .and_then(|chain| chain.parse().ok())
+    }
+
+    fn key(&self) -> Option<SymKey> {
+        hex::decode(&self.sym_key)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+    }
+}
+
+/// WalletConnect progress, written as a message by WalletConnectPlugin
+#[derive(Message, Debug, Clone)]
+pub enum WalletConnectEvent {
+    /// Show this `wc:` URI as a QR code, or open it as a deep link on mobile
+    PairingUri(String),
+    SessionApproved(WalletConnectSession),
+    SessionRejected(String),
+    AccountsChanged(Vec<String>),
+    ChainChanged(u64),
+    Disconnected(String),
+}
+
+enum Command {
+    Request {
+        method: String,
+        params: Value,
+        reply: oneshot::Sender<IdosResult<Value>>,
+    },
+    Disconnect(WalletConnectSession),
+}
+
+#[derive(Resource, Clone)]
+pub struct WalletConnectClient {
+    config: WalletConnectConfig,
+    storage: Storage,
+    session: Arc<Mutex<Option<WalletConnectSession>>>,
+    /// Channel to the running relay connection
+    commands: Arc<Mutex<Option<mpsc::UnboundedSender<Command>>>>,
+    events: Arc<Mutex<Vec<WalletConnectEvent>>>,
+}
+
+impl WalletConnectClient {
+    /// Restores the session saved by a previous run, unless it has expired
+    pub fn new(config: WalletConnectConfig, storage: Storage) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        let session = storage
+            .get(SESSION_KEY)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str::<WalletConnectSession>(&json).ok())
+            .filter(|session| session.expiry > now);
+
+        Self {
+            config,
+            storage,
+            session: Arc::new(Mutex::new(session)),
+            commands: Arc::new(Mutex::new(None)),
+            events: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn config(&self) -> &WalletConnectConfig {
+        &self.config
+    }
+
+    pub fn session(&self) -> Option<WalletConnectSession> {
+        self.session.lock().ok().and_then(|session| session.clone())
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.session().is_some()
+    }
+
+    pub fn address(&self) -> Option<Address> {
+        self.session().and_then(|session| session.address())
+    }
+
+    /// Start pairing with a wallet
+    /// Returns the `wc:` URI to show as a QR code; it's also written as `WalletConnectEvent::PairingUri`
+    pub fn pair(&self) -> IdosResult<String> {
+        if self.config.project_id.is_empty() {
+            return Err(IdosError::ConfigurationError(
+                "WalletConnect needs a project_id".to_string(),
+            ));
+        }
+
+        let sym_key = random_key();
+        let topic = topic_for(&sym_key);
+        let expiry = chrono::Utc::now().timestamp() + PAIRING_TTL_SECONDS;
+        let uri = pairing_uri(&topic, &sym_key, expiry);
+
+        self.spawn_driver(Some(Pairing {
+            topic,
+            sym_key,
+            secret: StaticSecret::from(random_key()),
+            propose_id: payload_id(),
+            expiry,
+            proposed: false,
+            session: None,
+        }));
+        self.push_event(WalletConnectEvent::PairingUri(uri.clone()));
+        Ok(uri)
+    }
+
+    /// Reconnect to the relay for a restored session
+    pub fn resume(&self) {
+        let running = self
+            .commands
+            .lock()
+            .map(|commands| commands.is_some())
+            .unwrap_or(false);
+        if self.is_connected() && !running {
+            self.spawn_driver(None);
+        }
+    }
+
+    /// End the session on both sides
+    pub fn disconnect(&self) {
+        let commands = self.commands.lock().ok().and_then(|mut c| c.take());
+        if let (Some(commands), Some(session)) = (commands, self.session()) {
+            commands.send(Command::Disconnect(session)).ok();
+        }
+        self.set_session(None);
+        self.push_event(WalletConnectEvent::Disconnected(
+            "Disconnected by player".to_string(),
+        ));
+    }
+
+    /// Send a JSON-RPC request to the wallet and wait for the player to answer it
+    pub async fn request(&self, method: &str, params: Value) -> IdosResult<Value> {
+        let not_connected = || IdosError::Wallet("WalletConnect is not connected".to_string());
+        let commands = self
+            .commands
+            .lock()
+            .ok()
+            .and_then(|commands| commands.clone())
+            .ok_or_else(not_connected)?;
+
+        let (reply, response) = oneshot::channel();
+        commands
+            .send(Command::Request {
+                method: method.to_string(),
+                params,
+                reply,
+            })
+            .map_err(|_| not_connected())?;
+
+        match tokio::time::timeout(REQUEST_TIMEOUT, response).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(not_connected()),
+            Err(_) => Err(IdosError::TimeoutError(
+                "Wallet did not answer the request".to_string(),
+            )),
+        }
+    }
+
+    /// `personal_sign` on the wallet
+    pub async fn personal_sign(&self, message: &[u8]) -> IdosResult<Signature> {
+        let address = self.require_address()?;
+        let result = self
+            .request(
+                "personal_sign",
+                json!([
+                    format!("0x{}", hex::encode(message)),
+                    format!("{:?}", address)
+                ]),
+            )
+            .await?;
+        parse_signature(&result)
+    }
+
+    /// `eth_signTypedData_v4` on the wallet
+    pub async fn sign_typed_data(&self, typed_data: &Value) -> IdosResult<Signature> {
+        let address = self.require_address()?;
+        let result = self
+            .request(
+                "eth_signTypedData_v4",
+                json!([format!("{:?}", address), typed_data.to_string()]),
+            )
+            .await?;
+        parse_signature(&result)
+    }
+
+    /// Have the wallet sign and broadcast a transaction
+    pub async fn send_transaction(&self, tx: &TypedTransaction) -> IdosResult<H256> {
+        let mut tx = tx.clone();
+        tx.set_from(self.require_address()?);
+
+        let result = self.request("eth_sendTransaction", json!([tx])).await?;
+        result
+            .as_str()
+            .and_then(|hash| hash.parse().ok())
+            .ok_or_else(|| IdosError::Wallet(format!("Unexpected transaction hash: {}", result)))
+    }
+
+    /// Take the events collected since the last call
+    pub fn drain_events(&self) -> Vec<WalletConnectEvent> {
+        self.events
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default()
+    }
+
+    fn require_address(&self) -> IdosResult<Address> {
+        self.address()
+            .ok_or_else(|| IdosError::Wallet("WalletConnect is not connected".to_string()))
+    }
+
+    fn push_event(&self, event: WalletConnectEvent) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
+    }
+
+    fn set_session(&self, session: Option<WalletConnectSession>) {
+        match &session {
+            Some(session) => {
+                if let Ok(json) = serde_json::to_string(session) {
+                    self.storage.set(SESSION_KEY, &json).ok();
+                }
+            }
+            None => {
+                self.storage.remove(SESSION_KEY).ok();
+            }
+        }
+        if let Ok(mut current) = self.session.lock() {
+            *current = session;
+        }
+    }
+
+    /// Ed25519 key identifying this install to the relay
+    fn identity(&self) -> ed25519_dalek::SigningKey {
+        let saved = self
+            .storage
+            .get(IDENTITY_KEY)
+            .ok()
+            .flatten()
+            .and_then(|seed| hex::decode(seed).ok())
+            .and_then(|seed| <[u8; 32]>::try_from(seed).ok());
+
+        let seed = saved.unwrap_or_else(|| {
+            let seed = random_key();
+            self.storage.set(IDENTITY_KEY, &hex::encode(seed)).ok();
+            seed
+        });
+        ed25519_dalek::SigningKey::from_bytes(&seed)
+    }
+
+    fn spawn_driver(&self, pairing: Option<Pairing>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        // Replacing the sender stops a previous connection
+        if let Ok(mut commands) = self.commands.lock() {
+            *commands = Some(sender);
+        }
+
+        let driver = Driver {
+            client: self.clone(),
+            pairing,
+            commands: receiver,
+            pending: HashMap::new(),
+            closed: false,
+        };
+
+        crate::tasks::spawn(driver.run());
+    }
+}
+
+fn parse_signature(result: &Value) -> IdosResult<Signature> {
+    result
+        .as_str()
+        .and_then(|signature| signature.parse().ok())
+        .ok_or_else(|| IdosError::Wallet(format!("Unexpected signature: {}", result)))
+}
+
+/// Pairing in progress
+struct Pairing {
+    topic: String,
+    sym_key: SymKey,
+    secret: StaticSecret,
+    propose_id: u64,
+    expiry: i64,
+    proposed: bool,
+    /// Session topic and key, once the wallet has answered the proposal
+    session: Option<(String, SymKey)>,
+}
+
+/// Owns the relay connection: publishes requests and handles what the wallet sends back
+struct Driver {
+    client: WalletConnectClient,
+    pairing: Option<Pairing>,
+    commands: mpsc::UnboundedReceiver<Command>,
+    /// Replies for requests waiting on the wallet, by JSON-RPC id
+    pending: HashMap<u64, oneshot::Sender<IdosResult<Value>>>,
+    /// Set when the session or pairing has ended and the connection shouldn't come back
+    closed: bool,
+}
+
+impl Driver {
+    async fn run(mut self) {
+        loop {
+            let result = self.drive().await;
+            if self.closed {
+                return;
+            }
+
+            match result {
+                Ok(()) => return,
+                Err(e) if self.pairing.is_some() => {
+                    self.client
+                        .push_event(WalletConnectEvent::SessionRejected(e.to_string()));
+                    return;
+                }
+                Err(_) if !self.client.is_connected() => return,
+                Err(e) => {
+                    warn!("WalletConnect relay connection lost, reconnecting: {}", e);
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        }
+    }
+
+    async fn drive(&mut self) -> IdosResult<()> {
+        let config = &self.client.config;
+        let jwt = relay_jwt(
+            &self.client.identity(),
+            &config.relay_url,
+            chrono::Utc::now().timestamp(),
+        );
+        let url = format!(
+            "{}/?auth={}&projectId={}&ua=wc-2/rust-idos_game_sdk/{}",
+            config.relay_url,
+            jwt,
+            config.project_id,
+            std::env::consts::OS
+        );
+
+        let (mut socket, _) = connect_async(url)
+            .await
+            .map_err(|e| IdosError::NetworkError(format!("WalletConnect relay: {}", e)))?;
+
+        let mut outgoing: Vec<String> = self
+            .topics()
+            .into_iter()
+            .map(|topic| relay_request("irn_subscribe", json!({ "topic": topic })))
+            .collect();
+        if let Some(pairing) = self.pairing.as_mut().filter(|pairing| !pairing.proposed) {
+            pairing.proposed = true;
+            outgoing.push(proposal(&self.client.config, pairing)?);
+        }
+        for message in outgoing {
+            send(&mut socket, message).await?;
+        }
+
+        loop {
+            tokio::select! {
+                message = socket.next() => {
+                    let message = match message {
+                        Some(Ok(message)) => message,
+                        Some(Err(e)) => {
+                            return Err(IdosError::NetworkError(format!("WalletConnect relay: {}", e)))
+                        }
+                        None => {
+                            return Err(IdosError::NetworkError("WalletConnect relay closed".to_string()))
+                        }
+                    };
+
+                    match message {
+                        WsMessage::Text(text) => {
+                            for reply in self.handle_relay_message(&text) {
+                                send(&mut socket, reply).await?;
+                            }
+                        }
+                        WsMessage::Close(_) => {
+                            return Err(IdosError::NetworkError("WalletConnect relay closed".to_string()))
+                        }
+                        _ => {}
+                    }
+                }
+                command = self.commands.recv() => {
+                    match command {
+                        // The client started a new pairing or was dropped
+                        None => return Ok(()),
+                        Some(Command::Request { method, params, reply }) => {
+                            match self.session_request(&method, params) {
+                                Ok((id, message)) => {
+                                    self.pending.insert(id, reply);
+                                    send(&mut socket, message).await?;
+                                }
+                                Err(e) => {
+                                    reply.send(Err(e)).ok();
+                                }
+                            }
+                        }
+                        Some(Command::Disconnect(session)) => {
+                            if let Some(key) = session.key() {
+                                let delete = json!({
+                                    "id": payload_id(),
+                                    "jsonrpc": "2.0",
+                                    "method": "wc_sessionDelete",
+                                    "params": { "code": 6000, "message": "User disconnected." },
+                                });
+                                let message = seal(&key, &delete.to_string())?;
+                                send(
+                                    &mut socket,
+                                    relay_publish(&session.topic, &message, TAG_SESSION_DELETE, TTL_SESSION_DELETE, false),
+                                )
+                                .await
+                                .ok();
+                            }
+                            self.closed = true;
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+
+            if self.closed {
+                socket.close(None).await.ok();
+                return Ok(());
+            }
+        }
+    }
+
+    /// Topics to listen on
+    fn topics(&self) -> Vec<String> {
+        let mut topics = Vec::new();
+        if let Some(pairing) = &self.pairing {
+            topics.push(pairing.topic.clone());
+            if let Some((topic, _)) = &pairing.session {
+                topics.push(topic.clone());
+            }
+        }
+        if let Some(session) = self.client.session() {
+            topics.push(session.topic);
+        }
+        topics
+    }
+
+    fn key_for(&self, topic: &str) -> Option<SymKey> {
+        if let Some(pairing) = &self.pairing {
+            if pairing.topic == topic {
+                return Some(pairing.sym_key);
+            }
+            if let Some((session_topic, key)) = &pairing.session {
+                if session_topic == topic {
+                    return Some(*key);
+                }
+            }
+        }
+        self.client
+            .session()
+            .filter(|session| session.topic == topic)
+            .and_then(|session| session.key())
+    }
+
+    /// Handle a relay message, returning the messages to send back
+    fn handle_relay_message(&mut self, text: &str) -> Vec<String> {
+        let Ok(message) = serde_json::from_str::<Value>(text) else {
+            return Vec::new();
+        };
+        // Anything else is the relay acknowledging our own publishes and subscriptions
+        if message["method"] != "irn_subscription" {
+            return Vec::new();
+        }
+
+        let mut replies =
+            vec![json!({ "id": message["id"], "jsonrpc": "2.0", "result": true }).to_string()];
+
+        let data = &message["params"]["data"];
+        let topic = data["topic"].as_str().unwrap_or_default();
+        let Some(key) = self.key_for(topic) else {
+            return replies;
+        };
+        let payload = open(&key, data["message"].as_str().unwrap_or_default())
+            .ok()
+            .and_then(|payload| serde_json::from_str::<Value>(&payload).ok());
+
+        if let Some(payload) = payload {
+            replies.extend(self.handle_payload(topic, &key, &payload));
+        }
+        replies
+    }
+
+    fn handle_payload(&mut self, topic: &str, key: &SymKey, payload: &Value) -> Vec<String> {
+        let Some(method) = payload["method"].as_str() else {
+            return self.handle_response(payload);
+        };
+
+        let params = &payload["params"];
+        match method {
+            "wc_sessionSettle" => self.settle(topic, key, params),
+            "wc_sessionUpdate" => {
+                let accounts = namespace_accounts(&params["namespaces"]);
+                if let Some(mut session) = self.client.session() {
+                    session.accounts = accounts.clone();
+                    self.client.set_session(Some(session));
+                }
+                self.client
+                    .push_event(WalletConnectEvent::AccountsChanged(accounts));
+            }
+            "wc_sessionExtend" => {
+                if let (Some(mut session), Some(expiry)) =
+                    (self.client.session(), params["expiry"].as_i64())
+                {
+                    session.expiry = expiry;
+                    self.client.set_session(Some(session));
+                }
+            }
+            "wc_sessionEvent" => self.session_event(&params["event"]),
+            "wc_sessionDelete" => {
+                self.client.set_session(None);
+                self.client.push_event(WalletConnectEvent::Disconnected(
+                    params["message"]
+                        .as_str()
+                        .unwrap_or("Disconnected by wallet")
+                        .to_string(),
+                ));
+                self.closed = true;
+            }
+            // Pings only need the acknowledgement below
+            _ => {}
+        }
+
+        let Some(tag) = response_tag(method) else {
+            return Vec::new();
+        };
+        let response = json!({ "id": payload["id"], "jsonrpc": "2.0", "result": true });
+        match seal(key, &response.to_string()) {
+            Ok(message) => vec![relay_publish(topic, &message, tag, TTL_RESPONSE, false)],
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Answers to our proposal and session requests
+    fn handle_response(&mut self, payload: &Value) -> Vec<String> {
+        let Some(id) = payload["id"].as_u64() else {
+            return Vec::new();
+        };
+        let error = payload.get("error").map(|error| {
+            error["message"]
+                .as_str()
+                .unwrap_or("Rejected by wallet")
+                .to_string()
+        });
+
+        if let Some(pairing) = self.pairing.as_mut().filter(|p| p.propose_id == id) {
+            let responder = payload["result"]["responderPublicKey"]
+                .as_str()
+                .unwrap_or_default();
+            let session_key = match error {
+                Some(reason) => Err(IdosError::Wallet(reason)),
+                None => derive_session_key(&pairing.secret, responder),
+            };
+
+            return match session_key {
+                Ok(key) => {
+                    let topic = topic_for(&key);
+                    pairing.session = Some((topic.clone(), key));
+                    vec![relay_request("irn_subscribe", json!({ "topic": topic }))]
+                }
+                Err(e) => {
+                    self.client
+                        .push_event(WalletConnectEvent::SessionRejected(e.to_string()));
+                    self.closed = true;
+                    Vec::new()
+                }
+            };
+        }
+
+        if let Some(reply) = self.pending.remove(&id) {
+            let result = match error {
+                Some(reason) => Err(IdosError::Wallet(reason)),
+                None => Ok(payload["result"].clone()),
+            };
+            reply.send(result).ok();
+        }
+        Vec::new()
+    }
+
+    fn settle(&mut self, topic: &str, key: &SymKey, params: &Value) {
+        let session = WalletConnectSession {
+            topic: topic.to_string(),
+            sym_key: hex::encode(key),
+            accounts: namespace_accounts(&params["namespaces"]),
+            peer: serde_json::from_value(params["controller"]["metadata"].clone())
+                .unwrap_or_default(),
+            expiry: params["expiry"].as_i64().unwrap_or_default(),
+        };
+
+        self.pairing = None;
+        self.client.set_session(Some(session.clone()));
+        self.client
+            .push_event(WalletConnectEvent::SessionApproved(session));
+    }
+
+    fn session_event(&mut self, event: &Value) {
+        match event["name"].as_str() {
+            Some("accountsChanged") => {
+                let Some(mut session) = self.client.session() else {
+                    return;
+                };
+                let chain = session.chain_id().unwrap_or(1);
+                let accounts: Vec<String> = event["data"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|address| address.as_str())
+                    .map(|address| {
+                        if address.starts_with("eip155:") {
+                            address.to_string()
+                        } else {
+                            format!("eip155:{}:{}", chain, address)
+                        }
+                    })
+                    .collect();
+
+                session.accounts = accounts.clone();
+                self.client.set_session(Some(session));
+                self.client
+                    .push_event(WalletConnectEvent::AccountsChanged(accounts));
+            }
+            Some("chainChanged") => {
+                let chain = match &event["data"] {
+                    Value::Number(chain) => chain.as_u64(),
+                    Value::String(chain) => u64::from_str_radix(chain.trim_start_matches("0x"), 16)
+                        .ok()
+                        .or_else(|| chain.parse().ok()),
+                    _ => None,
+                };
+                if let Some(chain) = chain {
+                    self.client
+                        .push_event(WalletConnectEvent::ChainChanged(chain));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn session_request(&self, method: &str, params: Value) -> IdosResult<(u64, String)> {
+        let session = self
+            .client
+            .session()
+            .ok_or_else(|| IdosError::Wallet("WalletConnect is not connected".to_string()))?;
+        let key = session
+            .key()
+            .ok_or_else(|| IdosError::Wallet("Corrupt WalletConnect session".to_string()))?;
+        let chain = session
+            .chain_id()
+            .or_else(|| self.client.config.chains.first().copied())
+            .unwrap_or(1);
+
+        let id = payload_id();
+        let request = json!({
+            "id": id,
+            "jsonrpc": "2.0",
+            "method": "wc_sessionRequest",
+            "params": {
+                "request": { "method": method, "params": params },
+                "chainId": format!("eip155:{}", chain),
+            },
+        });
+        let message = seal(&key, &request.to_string())?;
+        Ok((
+            id,
+            relay_publish(
+                &session.topic,
+                &message,
+                TAG_SESSION_REQUEST,
+                TTL_SESSION_REQUEST,
+                true,
+            ),
+        ))
+    }
+}
+
+/// Session proposal published on the pairing topic
+fn proposal(config: &WalletConnectConfig, pairing: &Pairing) -> IdosResult<String> {
+    let chains: Vec<String> = config
+        .chains
+        .iter()
+        .map(|chain| format!("eip155:{}", chain))
+        .collect();
+    let propose = json!({
+        "id": pairing.propose_id,
+        "jsonrpc": "2.0",
+        "method": "wc_sessionPropose",
+        "params": {
+            "requiredNamespaces": {},
+            "optionalNamespaces": {
+                "eip155": {
+                    "chains": chains,
+                    "methods": config.methods,
+                    "events": ["chainChanged", "accountsChanged"],
+                },
+            },
+            "relays": [{ "protocol": "irn" }],
+            "proposer": {
+                "publicKey": public_key_hex(&pairing.secret),
+                "metadata": config.metadata,
+            },
+            "expiryTimestamp": pairing.expiry,
+        },
+    });
+
+    let message = seal(&pairing.sym_key, &propose.to_string())?;
+    Ok(relay_publish(
+        &pairing.topic,
+        &message,
+        TAG_SESSION_PROPOSE,
+        TTL_SESSION_PROPOSE,
+        true,
+    ))
+}
+
+/// Tag for acknowledging a request from the wallet
+fn response_tag(method: &str) -> Option<u32> {
+    match method {
+        "wc_pairingDelete" => Some(1001),
+        "wc_pairingPing" => Some(1003),
+        "wc_sessionSettle" => Some(1103),
+        "wc_sessionUpdate" => Some(1105),
+        "wc_sessionExtend" => Some(1107),
+        "wc_sessionEvent" => Some(1111),
+        "wc_sessionDelete" => Some(1113),
+        "wc_sessionPing" => Some(1115),
+        _ => None,
+    }
+}
+
+/// CAIP-10 accounts across all approved namespaces
+fn namespace_accounts(namespaces: &Value) -> Vec<String> {
+    namespaces
+        .as_object()
+        .into_iter()
+        .flat_map(|namespaces| namespaces.values())
+        .filter_map(|namespace| namespace["accounts"].as_array())
+        .flatten()
+        .filter_map(|account| account.as_str().map(str::to_string))
+        .collect()
+}
+
+fn relay_request(method: &str, params: Value) -> String {
+    json!({ "id": payload_id(), "jsonrpc": "2.0", "method": method, "params": params }).to_string()
+}
+
+fn relay_publish(topic: &str, message: &str, tag: u32, ttl: i64, prompt: bool) -> String {
+    relay_request(
+        "irn_publish",
+        json!({ "topic": topic, "message": message, "ttl": ttl, "tag": tag, "prompt": prompt }),
+    )
+}
+
+async fn send<S>(socket: &mut S, message: String) -> IdosResult<()>
+where
+    S: futures_util::Sink<WsMessage> + Unpin,
+    S::Error: std::fmt::Display,
+{
+    socket
+        .send(WsMessage::Text(message))
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("WalletConnect relay: {}", e)))
+}
+
+/// Adds `WalletConnectClient` and writes its progress as `WalletConnectEvent` messages
+pub struct WalletConnectPlugin {
+    pub config: WalletConnectConfig,
+}
+
+impl WalletConnectPlugin {
+    pub fn new(config: WalletConnectConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Plugin for WalletConnectPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let storage = Storage::cache(client.config(), "walletconnect_");
+            app.insert_resource(WalletConnectClient::new(self.config.clone(), storage));
+        } else {
+            warn!("IdosClient not found. WalletConnectClient will not be initialized.");
+        }
+
+        app.add_message::<WalletConnectEvent>()
+            .add_systems(Startup, resume_session)
+            .add_systems(Update, forward_walletconnect_events);
+    }
+}
+
+/// Reconnect a session saved by a previous run
+fn resume_session(client: Option<Res<WalletConnectClient>>) {
+    if let Some(client) = client {
+        client.resume();
+    }
+}
+
+fn forward_walletconnect_events(
+    client: Option<Res<WalletConnectClient>>,
+    mut events: MessageWriter<WalletConnectEvent>,
+) {
+    let Some(client) = client else {
+        return;
+    };
+
+    for event in client.drain_events() {
+        events.write(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_accounts() {
+        let namespaces = json!({
+            "eip155": {
+                "accounts": ["eip155:137:0x52908400098527886E0F7030069857D2E4169EE7"],
+                "methods": ["personal_sign"],
+                "events": [],
+            }
+        });
+        let session = WalletConnectSession {
+            topic: String::new(),
+            sym_key: String::new(),
+            accounts: namespace_accounts(&namespaces),
+            peer: WalletConnectMetadata::default(),
+            expiry: 0,
+        };
+
+        assert_eq!(session.chain_id(), Some(137));
+        assert_eq!(
+            session.address(),
+            "0x52908400098527886E0F7030069857D2E4169EE7".parse().ok()
+        );
+    }
+}
@@ -0,0 +1,186 @@
+/// WalletConnect v2 crypto
+/// Pairing URIs, topic derivation, type-0 envelopes (ChaCha20-Poly1305) and relay auth JWTs
+use crate::{IdosError, IdosResult};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Nonce,
+};
+use ed25519_dalek::{Signer as _, SigningKey, VerifyingKey};
+use hkdf::Hkdf;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// Symmetric key shared with the wallet (pairing or session)
+pub type SymKey = [u8; 32];
+
+const ENVELOPE_TYPE_0: u8 = 0;
+const IV_LENGTH: usize = 12;
+const TAG_LENGTH: usize = 16;
+/// Multicodec prefix of an Ed25519 public key in a did:key
+const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+
+pub fn random_key() -> SymKey {
+    let mut key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut key);
+    key
+}
+
+/// Topic a symmetric key is used on: hex(sha256(key))
+pub fn topic_for(key: &SymKey) -> String {
+    hex::encode(Sha256::digest(key))
+}
+
+/// `wc:` URI the wallet scans (or opens as a deep link) to pair
+pub fn pairing_uri(topic: &str, sym_key: &SymKey, expiry: i64) -> String {
+    format!(
+        "wc:{}@2?relay-protocol=irn&symKey={}&expiryTimestamp={}",
+        topic,
+        hex::encode(sym_key),
+        expiry
+    )
+}
+
+/// Hex X25519 public key sent to the wallet in the session proposal
+pub fn public_key_hex(secret: &StaticSecret) -> String {
+    hex::encode(PublicKey::from(secret).as_bytes())
+}
+
+/// Session key agreed with the wallet: HKDF-SHA256 over the X25519 shared secret
+pub fn derive_session_key(secret: &StaticSecret, peer_public_hex: &str) -> IdosResult<SymKey> {
+    let peer: [u8; 32] = hex::decode(peer_public_hex)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| IdosError::Wallet("Invalid WalletConnect peer public key".to_string()))?;
+
+    let shared = secret.diffie_hellman(&PublicKey::from(peer));
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, shared.as_bytes())
+        .expand(&[], &mut key)
+        .map_err(|e| IdosError::Wallet(format!("Session key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+/// Encrypt a JSON-RPC payload into a base64 type-0 envelope
+pub fn seal(key: &SymKey, plaintext: &str) -> IdosResult<String> {
+    let mut iv = [0u8; IV_LENGTH];
+    rand::thread_rng().fill_bytes(&mut iv);
+
+    let sealed = ChaCha20Poly1305::new(key.into())
+        .encrypt(Nonce::from_slice(&iv), plaintext.as_bytes())
+        .map_err(|_| IdosError::Wallet("WalletConnect encryption failed".to_string()))?;
+
+    let mut envelope = Vec::with_capacity(1 + IV_LENGTH + sealed.len());
+    envelope.push(ENVELOPE_TYPE_0);
+    envelope.extend_from_slice(&iv);
+    envelope.extend_from_slice(&sealed);
+    Ok(STANDARD.encode(envelope))
+}
+
+/// Decrypt a base64 type-0 envelope
+pub fn open(key: &SymKey, message: &str) -> IdosResult<String> {
+    let envelope = STANDARD
+        .decode(message)
+        .map_err(|e| IdosError::SerializationError(format!("Invalid envelope: {}", e)))?;
+
+    if envelope.len() < 1 + IV_LENGTH + TAG_LENGTH || envelope[0] != ENVELOPE_TYPE_0 {
+        return Err(IdosError::SerializationError(
+            "Unsupported WalletConnect envelope".to_string(),
+        ));
+    }
+
+    let (iv, sealed) = envelope[1..].split_at(IV_LENGTH);
+    let plaintext = ChaCha20Poly1305::new(key.into())
+        .decrypt(Nonce::from_slice(iv), sealed)
+        .map_err(|_| IdosError::Wallet("WalletConnect decryption failed".to_string()))?;
+
+    String::from_utf8(plaintext)
+        .map_err(|e| IdosError::SerializationError(format!("Invalid envelope payload: {}", e)))
+}
+
+/// did:key for an Ed25519 public key (multicodec 0xed01, base58btc)
+pub fn did_key(key: &VerifyingKey) -> String {
+    let mut bytes = ED25519_MULTICODEC.to_vec();
+    bytes.extend_from_slice(key.as_bytes());
+    format!("did:key:z{}", bs58::encode(bytes).into_string())
+}
+
+/// Relay auth JWT signed with the client's Ed25519 identity
+pub fn relay_jwt(identity: &SigningKey, audience: &str, now: i64) -> String {
+    let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"EdDSA","typ":"JWT"}"#);
+    let claims = serde_json::json!({
+        "iss": did_key(&identity.verifying_key()),
+        "sub": hex::encode(random_key()),
+        "aud": audience,
+        "iat": now,
+        "exp": now + 24 * 60 * 60,
+    });
+    let claims = URL_SAFE_NO_PAD.encode(claims.to_string());
+
+    let data = format!("{}.{}", header, claims);
+    let signature = identity.sign(data.as_bytes());
+    format!("{}.{}", data, URL_SAFE_NO_PAD.encode(signature.to_bytes()))
+}
+
+/// JSON-RPC id in the format WalletConnect uses (millisecond timestamp plus 3 random digits)
+pub fn payload_id() -> u64 {
+    let millis = chrono::Utc::now().timestamp_millis() as u64;
+    millis * 1000 + (rand::thread_rng().next_u32() % 1000) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let key = random_key();
+        let message = seal(&key, r#"{"id":1,"jsonrpc":"2.0","result":true}"#).unwrap();
+        assert_eq!(
+            open(&key, &message).unwrap(),
+            r#"{"id":1,"jsonrpc":"2.0","result":true}"#
+        );
+        assert!(open(&random_key(), &message).is_err());
+    }
+
+    #[test]
+    fn test_session_key_agreement() {
+        let dapp = StaticSecret::from(random_key());
+        let wallet = StaticSecret::from(random_key());
+
+        let dapp_key = derive_session_key(&dapp, &public_key_hex(&wallet)).unwrap();
+        let wallet_key = derive_session_key(&wallet, &public_key_hex(&dapp)).unwrap();
+        assert_eq!(dapp_key, wallet_key);
+        assert!(derive_session_key(&dapp, "abcd").is_err());
+    }
+
+    #[test]
+    fn test_pairing_uri() {
+        let key = [7u8; 32];
+        let topic = topic_for(&key);
+        assert_eq!(topic.len(), 64);
+
+        let uri = pairing_uri(&topic, &key, 1700000300);
+        assert!(uri.starts_with(&format!("wc:{}@2?relay-protocol=irn&symKey=", topic)));
+        assert!(uri.ends_with(&format!("{}&expiryTimestamp=1700000300", hex::encode(key))));
+    }
+
+    #[test]
+    fn test_relay_jwt() {
+        let identity = SigningKey::from_bytes(&[1u8; 32]);
+        assert!(did_key(&identity.verifying_key()).starts_with("did:key:z6Mk"));
+
+        let jwt = relay_jwt(&identity, "wss://relay.walletconnect.org", 1700000000);
+        let parts: Vec<&str> = jwt.split('.').collect();
+        assert_eq!(parts.len(), 3);
+
+        let claims: serde_json::Value =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(parts[1]).unwrap()).unwrap();
+        assert_eq!(claims["aud"], "wss://relay.walletconnect.org");
+        assert_eq!(claims["exp"], 1700086400);
+    }
+}
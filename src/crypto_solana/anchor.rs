@@ -20,6 +20,17 @@ pub fn anchor_discriminator(method_name: &str) -> [u8; 8] {
     discriminator
 }
 
+/// Generate Anchor account discriminator
+/// Uses SHA256("account:{AccountName}") and takes first 8 bytes
+#[cfg(feature = "crypto_solana")]
+pub fn anchor_account_discriminator(account_name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("account:{}", account_name).as_bytes());
+
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash[..8]);
+    discriminator
+}
+
 /// Encode u64 in little-endian (Borsh format)
 /// Matches Unity SDK's EncodeU64
 #[cfg(feature = "crypto_solana")]
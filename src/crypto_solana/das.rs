@@ -0,0 +1,158 @@
+/// Digital Asset Standard (DAS) API client
+/// Used for compressed NFTs, which have no token account or metadata account to read
+use super::dto::*;
+use super::helper::send_solana_rpc_request;
+use crate::IdosResult;
+
+/// Page size used when listing every asset of an owner
+const DAS_PAGE_LIMIT: u64 = 1000;
+
+/// Get a single asset by id
+pub async fn get_asset(rpc_url: &str, asset_id: &str) -> IdosResult<DasAsset> {
+    let params = serde_json::json!({ "id": asset_id });
+    send_solana_rpc_request(rpc_url, "getAsset", params).await
+}
+
+/// Get one page of assets owned by a wallet (pages start at 1)
+pub async fn get_assets_by_owner(
+    rpc_url: &str,
+    owner_address: &str,
+    page: u64,
+    limit: u64,
+) -> IdosResult<DasAssetList> {
+    let params = serde_json::json!({
+        "ownerAddress": owner_address,
+        "page": page,
+        "limit": limit,
+    });
+    send_solana_rpc_request(rpc_url, "getAssetsByOwner", params).await
+}
+
+/// Get every compressed NFT owned by a wallet
+pub async fn get_compressed_assets_by_owner(
+    rpc_url: &str,
+    owner_address: &str,
+) -> IdosResult<Vec<DasAsset>> {
+    let mut assets = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let list = get_assets_by_owner(rpc_url, owner_address, page, DAS_PAGE_LIMIT).await?;
+        let fetched = list.items.len() as u64;
+
+        assets.extend(list.items.into_iter().filter(|asset| {
+            !asset.burnt && asset.compression.as_ref().is_some_and(|c| c.compressed)
+        }));
+
+        if fetched < DAS_PAGE_LIMIT {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(assets)
+}
+
+/// Get the merkle proof of a compressed asset
+pub async fn get_asset_proof(rpc_url: &str, asset_id: &str) -> IdosResult<DasAssetProof> {
+    let params = serde_json::json!({ "id": asset_id });
+    send_solana_rpc_request(rpc_url, "getAssetProof", params).await
+}
+
+/// Convert a DAS asset into the SDK's NFT representation
+pub fn das_asset_to_nft(asset: DasAsset) -> Nft {
+    let content = asset.content.unwrap_or(DasContent {
+        json_uri: String::new(),
+        metadata: None,
+        links: None,
+    });
+    let metadata = content.metadata;
+    let links = content.links;
+
+    let name = metadata
+        .as_ref()
+        .map(|m| m.name.clone())
+        .unwrap_or_default();
+    let symbol = metadata
+        .as_ref()
+        .map(|m| m.symbol.clone())
+        .unwrap_or_default();
+
+    let collection = asset
+        .grouping
+        .iter()
+        .find(|g| g.group_key == "collection")
+        .map(|g| NftCollection {
+            verified: true,
+            key: g.group_value.clone(),
+        });
+
+    let json_metadata = metadata.map(|m| NftJsonMetadata {
+        name: m.name,
+        symbol: m.symbol,
+        description: m.description,
+        image: links.as_ref().and_then(|l| l.image.clone()),
+        animation_url: links.as_ref().and_then(|l| l.animation_url.clone()),
+        external_url: links.as_ref().and_then(|l| l.external_url.clone()),
+        attributes: m.attributes,
+        properties: None,
+    });
+
+    Nft {
+        metadata: NftMetadata {
+            mint: asset.id,
+            name,
+            symbol,
+            uri: content.json_uri,
+            seller_fee_basis_points: 0,
+            creators: Some(asset.creators),
+            primary_sale_happened: false,
+            is_mutable: asset.mutable,
+            update_authority: String::new(),
+            collection,
+            uses: None,
+            token_standard: Some(asset.interface),
+            rule_set: None,
+        },
+        json_metadata,
+        owner: asset.ownership.owner,
+        compressed: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_das_asset_to_nft() {
+        let asset: DasAsset = serde_json::from_value(serde_json::json!({
+            "id": "Asset1111111111111111111111111111111111111",
+            "interface": "V1_NFT",
+            "content": {
+                "json_uri": "https://example.com/1.json",
+                "metadata": { "name": "Sword", "symbol": "ITEM" },
+                "links": { "image": "https://example.com/1.png" }
+            },
+            "compression": {
+                "compressed": true,
+                "data_hash": "h",
+                "creator_hash": "c",
+                "leaf_id": 4,
+                "tree": "t"
+            },
+            "ownership": { "owner": "Owner", "delegate": null },
+            "grouping": [{ "group_key": "collection", "group_value": "Coll" }]
+        }))
+        .unwrap();
+
+        let nft = das_asset_to_nft(asset);
+        assert!(nft.compressed);
+        assert_eq!(nft.metadata.name, "Sword");
+        assert_eq!(nft.metadata.collection.unwrap().key, "Coll");
+        assert_eq!(
+            nft.json_metadata.unwrap().image.as_deref(),
+            Some("https://example.com/1.png")
+        );
+    }
+}
@@ -1,4 +1,6 @@
 /// Data Transfer Objects for Solana Wallet
+use crate::{IdosError, IdosResult};
+use bevy::prelude::Message;
 use serde::{Deserialize, Serialize};
 
 /// Solana cluster types
@@ -20,6 +22,64 @@ impl SolanaCluster {
             SolanaCluster::Custom => "",
         }
     }
+
+    /// Whether the cluster's faucet hands out SOL through `requestAirdrop`
+    pub fn supports_airdrop(&self) -> bool {
+        matches!(self, SolanaCluster::Devnet | SolanaCluster::Testnet)
+    }
+
+    /// Chain identifier used by wallet-standard wallets
+    pub fn wallet_chain(&self) -> &str {
+        match self {
+            SolanaCluster::Mainnet => "solana:mainnet",
+            SolanaCluster::Devnet => "solana:devnet",
+            SolanaCluster::Testnet => "solana:testnet",
+            SolanaCluster::Custom => "solana:localnet",
+        }
+    }
+}
+
+/// Commitment level of RPC reads, preflight checks and confirmation waits
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Commitment {
+    /// Seen by the node, may still be rolled back
+    Processed,
+    /// Voted on by a supermajority of the cluster
+    #[default]
+    Confirmed,
+    /// Rooted, cannot be rolled back
+    Finalized,
+}
+
+impl Commitment {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Commitment::Processed => "processed",
+            Commitment::Confirmed => "confirmed",
+            Commitment::Finalized => "finalized",
+        }
+    }
+}
+
+/// Browser wallet that can be connected on WASM
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SolanaBrowserWallet {
+    /// Wallet name, also used to select it (e.g. "Phantom", "Solflare")
+    pub name: String,
+    /// Icon as a data URL, when the wallet provides one
+    pub icon: Option<String>,
+    /// Discovered through the wallet-standard registry rather than an injected provider
+    pub wallet_standard: bool,
+}
+
+/// Off-chain message signature, in the format of the backend's wallet login
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SolanaSignedMessage {
+    /// Signer address (base58)
+    pub public_key: String,
+    /// Ed25519 signature of the raw message bytes (base58)
+    pub signature: String,
 }
 
 /// Solana blockchain settings
@@ -27,8 +87,28 @@ impl SolanaCluster {
 pub struct SolanaSettings {
     pub cluster: SolanaCluster,
     pub rpc_url: String,
+    /// Endpoints tried in order when `rpc_url` is rate limited, times out or is unreachable
+    pub fallback_rpc_urls: Vec<String>,
+    /// Per-method endpoints, e.g. DAS queries to a DAS provider and sends to a staked endpoint
+    pub rpc_routes: std::collections::HashMap<String, String>,
     pub ws_url: Option<String>,
     pub program_id: String, // Platform pool program ID
+    /// Address lookup tables used to compile v0 transactions (base58 addresses)
+    pub address_lookup_tables: Vec<String>,
+    /// Compute unit limit requested for platform pool transactions (None = runtime default)
+    pub compute_unit_limit: Option<u32>,
+    /// Priority fee strategy for platform pool transactions
+    pub priority_fee: PriorityFee,
+    /// Times a transaction is re-signed with a fresh blockhash after the old one expired
+    pub max_blockhash_retries: u32,
+    /// How long cached NFT metadata stays valid (None = no persistent cache)
+    pub nft_cache_ttl: Option<std::time::Duration>,
+    /// Default commitment of balance queries, blockhashes, preflight checks and confirmations
+    pub commitment: Commitment,
+    /// Submit platform pool transactions as Jito bundles (None = regular sends)
+    pub jito: Option<JitoSettings>,
+    /// Token program by mint address; other mints are detected from the mint account's owner
+    pub token_programs: std::collections::HashMap<String, TokenProgram>,
 }
 
 impl Default for SolanaSettings {
@@ -36,12 +116,143 @@ impl Default for SolanaSettings {
         Self {
             cluster: SolanaCluster::Devnet,
             rpc_url: SolanaCluster::Devnet.rpc_url().to_string(),
+            fallback_rpc_urls: Vec::new(),
+            rpc_routes: std::collections::HashMap::new(),
             ws_url: None,
             program_id: String::new(),
+            address_lookup_tables: Vec::new(),
+            compute_unit_limit: None,
+            priority_fee: PriorityFee::default(),
+            max_blockhash_retries: 3,
+            nft_cache_ttl: Some(std::time::Duration::from_secs(24 * 60 * 60)),
+            commitment: Commitment::default(),
+            jito: None,
+            token_programs: std::collections::HashMap::new(),
+        }
+    }
+}
+
+/// Jito block engine settings
+/// Bundles land more reliably during congestion; if the block engine rejects a bundle,
+/// the transaction is sent through `rpc_url` without a tip. When its answer is unclear
+/// (timeout, dropped connection), the tipped transaction itself goes through `rpc_url`.
+/// Applies to transactions signed with the service key; browser wallets send their own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JitoSettings {
+    /// Bundle endpoint, e.g. https://mainnet.block-engine.jito.wtf/api/v1/bundles
+    pub block_engine_url: String,
+    /// Tip paid to the validator that lands the bundle
+    pub tip_lamports: u64,
+    /// Tip account (base58); None picks one of the published tip accounts
+    pub tip_account: Option<String>,
+}
+
+impl Default for JitoSettings {
+    fn default() -> Self {
+        Self {
+            block_engine_url: "https://mainnet.block-engine.jito.wtf/api/v1/bundles".to_string(),
+            tip_lamports: 10_000,
+            tip_account: None,
         }
     }
 }
 
+/// Priority fee strategy (compute unit price in micro-lamports)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityFee {
+    /// No SetComputeUnitPrice instruction
+    None,
+    /// Fixed price per compute unit
+    Fixed(u64),
+    /// Estimated from getRecentPrioritizationFees
+    Auto {
+        percentile: u8,
+        max_micro_lamports: u64,
+    },
+}
+
+impl Default for PriorityFee {
+    fn default() -> Self {
+        PriorityFee::Auto {
+            percentile: 75,
+            max_micro_lamports: 1_000_000,
+        }
+    }
+}
+
+/// Program that owns a token mint and its token accounts
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum TokenProgram {
+    /// Original SPL Token program
+    #[default]
+    Spl,
+    /// Token-2022 (Token Extensions); mints may carry extensions such as transfer fees
+    Token2022,
+}
+
+impl TokenProgram {
+    /// Program ID (base58)
+    pub fn program_id(&self) -> &'static str {
+        match self {
+            TokenProgram::Spl => super::transactions::TOKEN_PROGRAM_ID,
+            TokenProgram::Token2022 => super::transactions::TOKEN_2022_PROGRAM_ID,
+        }
+    }
+
+    /// Token program owning an account, `None` when the owner is another program
+    pub fn from_owner(owner: &str) -> Option<Self> {
+        [TokenProgram::Spl, TokenProgram::Token2022]
+            .into_iter()
+            .find(|program| program.program_id() == owner)
+    }
+}
+
+/// Token-2022 transfer fee, withheld from the amount the recipient receives
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransferFee {
+    /// First epoch the fee applies to
+    pub epoch: u64,
+    pub maximum_fee: u64,
+    pub basis_points: u16,
+}
+
+impl TransferFee {
+    /// Fee on a transfer of `amount` base units (rounded up, capped at `maximum_fee`)
+    pub fn fee_for(&self, amount: u64) -> u64 {
+        let fee = (amount as u128 * self.basis_points as u128).div_ceil(10_000);
+        fee.min(self.maximum_fee as u128) as u64
+    }
+}
+
+/// Token-2022 TransferFeeConfig extension
+/// Fee changes take effect two epochs after they're set, so both schedules are kept
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransferFeeConfig {
+    pub older: TransferFee,
+    pub newer: TransferFee,
+}
+
+impl TransferFeeConfig {
+    /// Fee schedule in effect during `epoch`
+    pub fn fee_at(&self, epoch: u64) -> &TransferFee {
+        if epoch >= self.newer.epoch {
+            &self.newer
+        } else {
+            &self.older
+        }
+    }
+}
+
+/// Token mint read from its on-chain account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMint {
+    pub address: String,
+    pub program: TokenProgram,
+    pub decimals: u8,
+    /// Transfer fee extension (Token-2022 mints only)
+    pub transfer_fee: Option<TransferFeeConfig>,
+}
+
 /// SPL Token deposit request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DepositSplRequest {
@@ -105,6 +316,50 @@ impl From<ServerWithdrawPayload> for WithdrawSplRequest {
     }
 }
 
+impl ServerWithdrawPayload {
+    /// Convert into a withdrawal after checking it matches what was requested
+    /// (unlike `From`, malformed numbers are errors rather than 0)
+    pub fn to_checked_request(
+        &self,
+        mint: &str,
+        amount: u64,
+        wallet_address: &str,
+    ) -> IdosResult<WithdrawSplRequest> {
+        let mismatch = |field: &str, value: &str| {
+            IdosError::api(format!(
+                "Withdrawal payload {} does not match the request: {}",
+                field, value
+            ))
+        };
+
+        if self.mint != mint {
+            return Err(mismatch("mint", &self.mint));
+        }
+        if self.wallet_address != wallet_address {
+            return Err(mismatch("wallet", &self.wallet_address));
+        }
+        if self.amount.parse::<u64>().ok() != Some(amount) {
+            return Err(mismatch("amount", &self.amount));
+        }
+        let nonce = self
+            .nonce
+            .parse::<u64>()
+            .map_err(|_| IdosError::api(format!("Invalid withdrawal nonce: {}", self.nonce)))?;
+        let sig_ix_index = u8::try_from(self.sig_ix_index).map_err(|_| {
+            IdosError::api(format!(
+                "Invalid withdrawal signature index: {}",
+                self.sig_ix_index
+            ))
+        })?;
+
+        Ok(WithdrawSplRequest {
+            nonce,
+            sig_ix_index,
+            ..self.clone().into()
+        })
+    }
+}
+
 /// JSON-RPC request for Solana
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SolanaRpcRequest<T> {
@@ -163,6 +418,7 @@ pub struct RpcContext {
 pub struct TokenAccountInfo {
     pub mint: String,
     pub owner: String,
+    #[serde(rename = "tokenAmount")]
     pub token_amount: TokenAmount,
 }
 
@@ -240,7 +496,7 @@ pub struct SimulateTransactionRequest {
 pub struct SimulateConfig {
     pub encoding: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub commitment: Option<String>,
+    pub commitment: Option<Commitment>,
 }
 
 /// Simulate transaction RPC response
@@ -310,7 +566,7 @@ pub struct SendTransactionRequest {
 pub struct SendTransactionConfig {
     pub encoding: String,
     pub skip_preflight: bool,
-    pub preflight_commitment: String,
+    pub preflight_commitment: Commitment,
 }
 
 /// Send transaction RPC response
@@ -330,9 +586,24 @@ pub struct TokenAccountsResponse {
 /// Token account value wrapper
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenAccountValue {
+    /// Address of the token account
+    #[serde(default)]
+    pub pubkey: String,
     pub account: TokenAccountData,
 }
 
+/// SPL token account held by a wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolanaTokenAccount {
+    /// Token account address
+    pub address: String,
+    pub mint: String,
+    pub amount: TokenAmount,
+    /// Program that owns the account
+    #[serde(default)]
+    pub program: TokenProgram,
+}
+
 /// Token account data wrapper
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenAccountData {
@@ -342,7 +613,13 @@ pub struct TokenAccountData {
 /// Token account parsed data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenAccountParsed {
-    pub parsed: TokenAccountInfo,
+    pub parsed: TokenAccountParsedInfo,
+}
+
+/// `jsonParsed` token account payload (`{ "info": ..., "type": "account" }`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenAccountParsedInfo {
+    pub info: TokenAccountInfo,
 }
 
 // Transaction details
@@ -355,6 +632,254 @@ pub struct TransactionDetailResponse {
     pub block_time: Option<i64>,
 }
 
+// Account info
+
+/// `getAccountInfo` RPC response (base64 encoding)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountInfoResponse {
+    pub context: RpcContext,
+    pub value: Option<AccountInfoValue>,
+}
+
+/// Raw account returned by `getAccountInfo`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountInfoValue {
+    /// `[data, encoding]` pair
+    pub data: (String, String),
+    pub owner: String,
+    pub lamports: u64,
+    pub executable: bool,
+}
+
+/// `getMultipleAccounts` RPC response (same order as the requested addresses)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultipleAccountsResponse {
+    pub context: RpcContext,
+    pub value: Vec<Option<AccountInfoValue>>,
+}
+
+// Priority fees
+
+/// Entry of the `getRecentPrioritizationFees` RPC response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrioritizationFee {
+    pub slot: u64,
+    #[serde(rename = "prioritizationFee")]
+    pub prioritization_fee: u64,
+}
+
+/// `getTokenSupply` RPC response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenSupplyResponse {
+    pub value: TokenAmount,
+}
+
+// Signature confirmation
+
+/// Final outcome of a watched transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureOutcome {
+    Confirmed,
+    Failed(String),
+    TimedOut,
+}
+
+/// Confirmation update for a signature passed to `SolanaHandler::watch_transaction`
+#[derive(Message, Debug, Clone)]
+pub struct SolanaSignatureUpdate {
+    pub signature: String,
+    pub outcome: SignatureOutcome,
+}
+
+// Solana Pay
+
+/// Solana Pay transfer request (`solana:<recipient>?amount=...`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolanaPayTransferRequest {
+    pub recipient: String,
+    /// Decimal amount in SOL or token units (e.g. "1.5")
+    pub amount: Option<String>,
+    /// SPL token mint, None for SOL
+    pub spl_token: Option<String>,
+    /// Read-only keys added to the payment transaction, used to find it later
+    pub references: Vec<String>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub memo: Option<String>,
+}
+
+/// Parsed Solana Pay URL
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolanaPayRequest {
+    Transfer(SolanaPayTransferRequest),
+    /// Transaction request: the wallet fetches the transaction from `link`
+    Transaction {
+        link: String,
+    },
+}
+
+/// Entry of the `getSignaturesForAddress` RPC response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub err: Option<serde_json::Value>,
+    pub memo: Option<String>,
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<i64>,
+    #[serde(rename = "confirmationStatus")]
+    pub confirmation_status: Option<String>,
+}
+
+/// `getTransaction` RPC response (json encoding) with balances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionWithMeta {
+    pub slot: u64,
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<i64>,
+    pub transaction: EncodedTransaction,
+    pub meta: Option<TransactionMeta>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodedTransaction {
+    pub signatures: Vec<String>,
+    pub message: EncodedMessage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodedMessage {
+    #[serde(rename = "accountKeys")]
+    pub account_keys: Vec<String>,
+}
+
+/// Execution result and balance changes of a transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionMeta {
+    pub err: Option<serde_json::Value>,
+    pub fee: u64,
+    #[serde(rename = "preBalances")]
+    pub pre_balances: Vec<u64>,
+    #[serde(rename = "postBalances")]
+    pub post_balances: Vec<u64>,
+    #[serde(rename = "preTokenBalances", default)]
+    pub pre_token_balances: Option<Vec<TokenBalance>>,
+    #[serde(rename = "postTokenBalances", default)]
+    pub post_token_balances: Option<Vec<TokenBalance>>,
+    #[serde(rename = "loadedAddresses", default)]
+    pub loaded_addresses: Option<LoadedAddresses>,
+    #[serde(rename = "logMessages", default)]
+    pub log_messages: Option<Vec<String>>,
+}
+
+/// Token account balance of a transaction (by account index)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenBalance {
+    #[serde(rename = "accountIndex")]
+    pub account_index: usize,
+    pub mint: String,
+    pub owner: Option<String>,
+    #[serde(rename = "uiTokenAmount")]
+    pub ui_token_amount: TokenAmount,
+}
+
+/// Accounts loaded from address lookup tables (v0 transactions)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoadedAddresses {
+    pub writable: Vec<String>,
+    pub readonly: Vec<String>,
+}
+
+impl TransactionWithMeta {
+    /// Static account keys followed by lookup table accounts, the order balances are indexed in
+    pub fn account_keys(&self) -> Vec<String> {
+        let mut keys = self.transaction.message.account_keys.clone();
+        if let Some(loaded) = self.meta.as_ref().and_then(|m| m.loaded_addresses.as_ref()) {
+            keys.extend(loaded.writable.iter().cloned());
+            keys.extend(loaded.readonly.iter().cloned());
+        }
+        keys
+    }
+}
+
+// Transaction history
+
+/// Direction of a history entry, seen from the queried wallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransferDirection {
+    Incoming,
+    Outgoing,
+    /// No SOL or token balance change besides the fee (program calls, failed transactions)
+    Other,
+}
+
+/// Entry of a wallet's transaction history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolanaHistoryEntry {
+    pub signature: String,
+    pub slot: u64,
+    /// Unix timestamp (seconds)
+    pub timestamp: Option<i64>,
+    pub success: bool,
+    pub direction: TransferDirection,
+    /// Wallet on the other side of the transfer
+    pub counterparty: Option<String>,
+    /// Token mint, None for SOL
+    pub token: Option<String>,
+    /// Amount in base units (lamports or raw token amount), excluding the fee
+    pub amount: u64,
+    pub decimals: u8,
+    /// Fee in lamports
+    pub fee: u64,
+    pub memo: Option<String>,
+}
+
+// Transaction lifecycle
+
+/// A transaction passed to `SolanaHandler::watch_transaction` is being tracked
+#[derive(Message, Debug, Clone)]
+pub struct SolanaTxSubmitted {
+    pub signature: String,
+}
+
+/// A watched transaction landed without errors
+#[derive(Message, Debug, Clone)]
+pub struct SolanaTxConfirmed {
+    pub signature: String,
+    pub slot: u64,
+}
+
+/// A watched transaction failed on-chain or never landed
+#[derive(Message, Debug, Clone)]
+pub struct SolanaTxFailed {
+    pub signature: String,
+    pub error: String,
+    /// Program logs, when the transaction landed
+    pub logs: Vec<String>,
+}
+
+/// Lifecycle event queued by the handler until SolanaPlugin writes it as a message
+#[derive(Debug, Clone)]
+pub enum SolanaTxEvent {
+    Submitted(SolanaTxSubmitted),
+    Confirmed(SolanaTxConfirmed),
+    Failed(SolanaTxFailed),
+}
+
+// Account subscriptions
+
+/// Balance or data change of an account passed to `SolanaHandler::watch_account`
+#[derive(Message, Debug, Clone)]
+pub struct SolanaAccountChanged {
+    pub address: String,
+    pub slot: u64,
+    pub lamports: u64,
+    /// Lamports at the previous notification (None for the first one)
+    pub previous_lamports: Option<u64>,
+    /// UI amount for SPL token accounts
+    pub token_amount: Option<String>,
+}
+
 // Transaction status checking (for examples)
 
 /// Transaction status request
@@ -388,6 +913,12 @@ pub struct NftMetadata {
     pub update_authority: String,
     pub collection: Option<NftCollection>,
     pub uses: Option<NftUses>,
+    /// Metaplex token standard (e.g. "NonFungible", "ProgrammableNonFungible")
+    #[serde(default)]
+    pub token_standard: Option<String>,
+    /// Authorization rule set of a programmable NFT
+    #[serde(default)]
+    pub rule_set: Option<String>,
 }
 
 /// NFT Creator information
@@ -441,6 +972,9 @@ pub struct Nft {
     pub metadata: NftMetadata,
     pub json_metadata: Option<NftJsonMetadata>,
     pub owner: String,
+    /// Compressed (Bubblegum) NFT - `metadata.mint` holds the DAS asset id
+    #[serde(default)]
+    pub compressed: bool,
 }
 
 /// NFT loading result
@@ -449,3 +983,144 @@ pub struct NftLoadResult {
     pub nfts: Vec<Nft>,
     pub count: usize,
 }
+
+// ==================== Metaplex Core ====================
+
+/// Update authority of a Metaplex Core asset
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CoreUpdateAuthority {
+    None,
+    Address(String),
+    /// The asset belongs to this collection, which holds the authority
+    Collection(String),
+}
+
+/// Metaplex Core asset (AssetV1 account, plugins not included)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoreAsset {
+    pub address: String,
+    pub owner: String,
+    pub update_authority: CoreUpdateAuthority,
+    pub name: String,
+    pub uri: String,
+}
+
+/// Entry of the `getProgramAccounts` RPC response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgramAccount {
+    pub pubkey: String,
+    pub account: AccountInfoValue,
+}
+
+/// Filter of a `getProgramAccounts` query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountFilter {
+    /// Account data at `offset` equals `bytes` (at most 128 bytes)
+    Memcmp { offset: usize, bytes: Vec<u8> },
+    /// Account data is exactly this many bytes
+    DataSize(u64),
+}
+
+/// Program account with its decoded data
+#[derive(Debug, Clone)]
+pub struct DecodedAccount<T> {
+    pub pubkey: String,
+    pub lamports: u64,
+    pub data: T,
+}
+
+// ==================== DAS (Digital Asset Standard) API ====================
+
+/// Asset returned by DAS `getAsset` / `getAssetsByOwner`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasAsset {
+    pub id: String,
+    #[serde(default)]
+    pub interface: String,
+    pub content: Option<DasContent>,
+    pub compression: Option<DasCompression>,
+    pub ownership: DasOwnership,
+    #[serde(default)]
+    pub creators: Vec<NftCreator>,
+    #[serde(default)]
+    pub grouping: Vec<DasGrouping>,
+    #[serde(default)]
+    pub mutable: bool,
+    #[serde(default)]
+    pub burnt: bool,
+}
+
+/// DAS asset content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasContent {
+    #[serde(default)]
+    pub json_uri: String,
+    pub metadata: Option<DasContentMetadata>,
+    pub links: Option<DasLinks>,
+}
+
+/// DAS asset metadata (mirrors the off-chain JSON)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasContentMetadata {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub symbol: String,
+    pub description: Option<String>,
+    pub attributes: Option<Vec<NftAttribute>>,
+}
+
+/// DAS asset links
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasLinks {
+    pub image: Option<String>,
+    pub animation_url: Option<String>,
+    pub external_url: Option<String>,
+}
+
+/// DAS compression info (leaf location in the merkle tree)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasCompression {
+    pub compressed: bool,
+    #[serde(default)]
+    pub data_hash: String,
+    #[serde(default)]
+    pub creator_hash: String,
+    #[serde(default)]
+    pub leaf_id: u64,
+    #[serde(default)]
+    pub tree: String,
+}
+
+/// DAS ownership info
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasOwnership {
+    pub owner: String,
+    pub delegate: Option<String>,
+}
+
+/// DAS grouping (e.g. collection)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasGrouping {
+    pub group_key: String,
+    pub group_value: String,
+}
+
+/// Page of DAS assets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasAssetList {
+    pub total: u64,
+    pub limit: u64,
+    pub page: u64,
+    pub items: Vec<DasAsset>,
+}
+
+/// DAS `getAssetProof` result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasAssetProof {
+    pub root: String,
+    pub proof: Vec<String>,
+    pub node_index: u64,
+    pub leaf: String,
+    pub tree_id: String,
+}
@@ -1,26 +1,67 @@
 /// Solana wallet handler - WASM compatible
 use super::dto::*;
-use crate::{IdosClient, IdosError, IdosResult};
+use super::nft_cache::NftMetadataCache;
+use crate::transaction_history::{TransactionChain, TransactionHistory, TransactionStatus};
+use crate::{storage::Storage, IdosClient, IdosError, IdosResult};
 use bevy::prelude::Resource;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
-#[cfg(target_arch = "wasm32")]
 use super::helper::{
-    is_solana_wallet_available, solana_connect_wallet, solana_deposit_spl, solana_get_balance,
-    solana_get_token_balance, solana_get_transaction, solana_send_transaction, solana_withdraw_spl,
+    sleep, solana_account_exists, solana_get_balance, solana_get_token_accounts,
+    solana_get_token_balance, solana_get_token_decimals, solana_get_transaction,
+    solana_get_transaction_with_meta, solana_request_airdrop,
 };
+use super::subscription::{subscribe_account, subscribe_signature};
+
+#[cfg(target_arch = "wasm32")]
+use super::wallet_adapter;
 
 #[derive(Resource, Clone)]
 pub struct SolanaHandler {
     client: IdosClient,
     settings: SolanaSettings,
+    /// Updates from `watch_transaction`, drained into Bevy messages by SolanaPlugin
+    signature_updates: Arc<Mutex<Vec<SolanaSignatureUpdate>>>,
+    /// Lifecycle of watched transactions, drained into Bevy messages by SolanaPlugin
+    tx_events: Arc<Mutex<Vec<SolanaTxEvent>>>,
+    /// Changes from `watch_account`, drained into Bevy messages by SolanaPlugin
+    account_updates: Arc<Mutex<Vec<SolanaAccountChanged>>>,
+    /// Stop flags of the running account subscriptions, by address
+    account_watches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    /// Browser wallet picked by the player (WASM)
+    selected_wallet: Arc<Mutex<Option<String>>>,
+    /// Persistent NFT metadata cache (None when `nft_cache_ttl` is unset)
+    nft_cache: Option<NftMetadataCache>,
+    /// Shared history of sent transactions, set by SolanaPlugin
+    history: Option<TransactionHistory>,
 }
 
+/// Signatures that haven't landed after this long were dropped (their blockhash expired)
+const PENDING_TIMEOUT_SECS: i64 = 5 * 60;
+
 impl SolanaHandler {
     pub fn new(client: IdosClient, settings: SolanaSettings) -> Self {
-        Self { client, settings }
+        let nft_cache = settings
+            .nft_cache_ttl
+            .map(|ttl| NftMetadataCache::new(Storage::cache(client.config(), "sol_nft_"), ttl));
+        super::rpc::configure(&settings);
+
+        Self {
+            client,
+            settings,
+            signature_updates: Arc::new(Mutex::new(Vec::new())),
+            tx_events: Arc::new(Mutex::new(Vec::new())),
+            account_updates: Arc::new(Mutex::new(Vec::new())),
+            account_watches: Arc::new(Mutex::new(HashMap::new())),
+            selected_wallet: Arc::new(Mutex::new(None)),
+            nft_cache,
+            history: None,
+        }
     }
 
     /// Get Solana settings
@@ -28,10 +69,10 @@ impl SolanaHandler {
         &self.settings
     }
 
-    /// Check if Phantom/Solflare wallet is available (WASM only)
+    /// Check if any browser wallet (Phantom, Solflare, Backpack, OKX...) is available (WASM only)
     #[cfg(target_arch = "wasm32")]
     pub fn is_wallet_available(&self) -> bool {
-        is_solana_wallet_available()
+        !self.available_wallets().is_empty()
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -39,47 +80,182 @@ impl SolanaHandler {
         false // Native wallet support would need solana-sdk
     }
 
-    /// Get SOL balance for a wallet address
-    pub async fn get_balance(&self, address: &str) -> IdosResult<u64> {
-        #[cfg(target_arch = "wasm32")]
+    /// List browser wallets the player can pick from (WASM only)
+    #[cfg(target_arch = "wasm32")]
+    pub fn available_wallets(&self) -> Vec<SolanaBrowserWallet> {
+        wallet_adapter::available_wallets()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn available_wallets(&self) -> Vec<SolanaBrowserWallet> {
+        Vec::new()
+    }
+
+    /// Pick the wallet used by `connect_wallet` and transaction signing (WASM only)
+    #[cfg(target_arch = "wasm32")]
+    pub fn select_wallet(&self, name: &str) -> IdosResult<()> {
+        if !self
+            .available_wallets()
+            .iter()
+            .any(|wallet| wallet.name == name)
         {
-            solana_get_balance(&self.settings.rpc_url, address).await
+            return Err(IdosError::PlatformNotSupported(format!(
+                "Solana wallet {} is not available",
+                name
+            )));
         }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            // Native implementation would use solana_client
-            let _ = address;
-            Err(IdosError::PlatformNotSupported(
-                "Native Solana support requires solana-client crate".to_string(),
-            ))
+        *self.selected_wallet.lock().unwrap() = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Name of the selected wallet, if the player picked one
+    pub fn selected_wallet(&self) -> Option<String> {
+        self.selected_wallet.lock().unwrap().clone()
+    }
+
+    /// Selected wallet, or the first available one
+    #[cfg(target_arch = "wasm32")]
+    fn active_wallet(&self) -> IdosResult<String> {
+        self.selected_wallet()
+            .or_else(|| self.available_wallets().into_iter().next().map(|w| w.name))
+            .ok_or_else(|| {
+                IdosError::PlatformNotSupported(
+                    "Solana wallet not available. Please install Phantom, Solflare, Backpack or OKX Wallet"
+                        .to_string(),
+                )
+            })
+    }
+
+    /// Get SOL balance for a wallet address
+    pub async fn get_balance(&self, address: &str) -> IdosResult<u64> {
+        self.get_balance_with_commitment(address, self.settings.commitment)
+            .await
+    }
+
+    /// Get SOL balance at a specific commitment
+    pub async fn get_balance_with_commitment(
+        &self,
+        address: &str,
+        commitment: Commitment,
+    ) -> IdosResult<u64> {
+        solana_get_balance(&self.settings.rpc_url, address, commitment).await
+    }
+
+    /// Fund a test wallet from the faucet (devnet and testnet only)
+    /// Returns the airdrop signature, which can be passed to `confirm_transaction`
+    pub async fn request_airdrop(&self, address: &str, lamports: u64) -> IdosResult<String> {
+        if !self.settings.cluster.supports_airdrop() {
+            return Err(IdosError::ConfigurationError(format!(
+                "Airdrops are only available on devnet and testnet, not {:?}",
+                self.settings.cluster
+            )));
         }
+
+        solana_request_airdrop(&self.settings.rpc_url, address, lamports).await
+    }
+
+    /// Get every SPL token account the wallet holds, with balances
+    pub async fn get_token_accounts(
+        &self,
+        wallet_address: &str,
+    ) -> IdosResult<Vec<SolanaTokenAccount>> {
+        solana_get_token_accounts(
+            &self.settings.rpc_url,
+            wallet_address,
+            self.settings.commitment,
+        )
+        .await
     }
 
-    /// Get SPL token balance
+    /// Get SPL token balance (SPL Token or Token-2022 mint)
     pub async fn get_token_balance(
         &self,
         wallet_address: &str,
         mint_address: &str,
     ) -> IdosResult<TokenAmount> {
-        #[cfg(target_arch = "wasm32")]
-        {
-            solana_get_token_balance(&self.settings.rpc_url, wallet_address, mint_address).await
+        self.get_token_balance_with_commitment(
+            wallet_address,
+            mint_address,
+            self.settings.commitment,
+        )
+        .await
+    }
+
+    /// Get SPL token balance at a specific commitment
+    pub async fn get_token_balance_with_commitment(
+        &self,
+        wallet_address: &str,
+        mint_address: &str,
+        commitment: Commitment,
+    ) -> IdosResult<TokenAmount> {
+        solana_get_token_balance(
+            &self.settings.rpc_url,
+            wallet_address,
+            mint_address,
+            commitment,
+        )
+        .await
+    }
+
+    /// Get the decimals of an SPL token mint
+    pub async fn get_token_decimals(&self, mint_address: &str) -> IdosResult<u8> {
+        solana_get_token_decimals(&self.settings.rpc_url, mint_address).await
+    }
+
+    /// Get a token mint: owning program, decimals and Token-2022 transfer fee
+    pub async fn get_token_mint(&self, mint_address: &str) -> IdosResult<TokenMint> {
+        super::token_mint::get_token_mint(&self.settings.rpc_url, mint_address).await
+    }
+
+    /// Token program of a mint, from `SolanaSettings::token_programs` or the mint account
+    pub async fn token_program(&self, mint_address: &str) -> IdosResult<TokenProgram> {
+        match self.settings.token_programs.get(mint_address) {
+            Some(program) => Ok(*program),
+            None => Ok(self.get_token_mint(mint_address).await?.program),
         }
+    }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            let _ = (wallet_address, mint_address);
-            Err(IdosError::PlatformNotSupported(
-                "Native Solana support requires solana-client crate".to_string(),
-            ))
+    /// Transfer fee on sending `amount` of a token in the current epoch (0 without the extension)
+    pub async fn get_transfer_fee(&self, mint_address: &str, amount: u64) -> IdosResult<u64> {
+        let mint = self.get_token_mint(mint_address).await?;
+        self.transfer_fee(&mint, amount).await
+    }
+
+    pub(crate) async fn transfer_fee(&self, mint: &TokenMint, amount: u64) -> IdosResult<u64> {
+        match &mint.transfer_fee {
+            Some(config) => {
+                let epoch = super::token_mint::get_epoch(&self.settings.rpc_url).await?;
+                Ok(config.fee_at(epoch).fee_for(amount))
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Resolve a .sol domain ("alice.sol") to its owner's address
+    /// Addresses are returned unchanged, so withdrawal forms can accept either
+    pub async fn resolve_address(&self, name: &str) -> IdosResult<String> {
+        let name = name.trim();
+        if super::transactions::decode_pubkey(name).is_ok() {
+            return Ok(name.to_string());
         }
+        super::sns::resolve_domain(&self.settings.rpc_url, name).await
+    }
+
+    /// Primary .sol domain of an address, or None when it hasn't set one
+    pub async fn lookup_address(&self, address: &str) -> IdosResult<Option<String>> {
+        super::sns::reverse_lookup(&self.settings.rpc_url, address).await
+    }
+
+    /// Check whether an account (e.g. an associated token account) exists
+    pub async fn account_exists(&self, address: &str) -> IdosResult<bool> {
+        solana_account_exists(&self.settings.rpc_url, address).await
     }
 
-    /// Connect wallet (WASM only - Phantom/Solflare)
+    /// Connect the selected wallet and return its address (WASM only)
     #[cfg(target_arch = "wasm32")]
     pub async fn connect_wallet(&self) -> IdosResult<String> {
-        solana_connect_wallet().await
+        wallet_adapter::connect_wallet(&self.active_wallet()?).await
     }
 
     /// Request withdrawal signature from backend
@@ -122,6 +298,24 @@ impl SolanaHandler {
         self.client.post("solana/deposit", &request).await
     }
 
+    /// Submit NFT deposit transaction to backend
+    pub async fn submit_nft_deposit(
+        &self,
+        transaction_signature: &str,
+        mint: &str,
+    ) -> IdosResult<String> {
+        let request = PlatformPoolTransactionRequest {
+            transaction_type: "NFT".to_string(),
+            direction: "Game".to_string(),
+            transaction_hash: Some(transaction_signature.to_string()),
+            currency_id: Some(mint.to_string()),
+            amount: Some(1),
+            wallet_address: String::new(),
+        };
+
+        self.client.post("solana/deposit", &request).await
+    }
+
     /// Submit withdrawal transaction to backend
     pub async fn submit_withdrawal(&self, transaction_signature: &str) -> IdosResult<String> {
         let request = PlatformPoolTransactionRequest {
@@ -139,10 +333,34 @@ impl SolanaHandler {
     /// Send transaction (WASM - via wallet adapter)
     #[cfg(target_arch = "wasm32")]
     pub async fn send_transaction(&self, transaction_base64: &str) -> IdosResult<String> {
-        solana_send_transaction(transaction_base64).await
+        let signature = wallet_adapter::send_transaction(
+            &self.active_wallet()?,
+            self.settings.cluster.wallet_chain(),
+            transaction_base64,
+        )
+        .await?;
+        self.record_transaction(&signature, "transaction");
+        Ok(signature)
+    }
+
+    /// Have the selected wallet sign and send an unsigned legacy transaction (WASM only)
+    #[cfg(target_arch = "wasm32")]
+    pub async fn sign_and_send_unsigned(&self, unsigned_transaction: &[u8]) -> IdosResult<String> {
+        wallet_adapter::sign_and_send_unsigned(
+            &self.active_wallet()?,
+            self.settings.cluster.wallet_chain(),
+            unsigned_transaction,
+        )
+        .await
+    }
+
+    /// Have the selected wallet sign an off-chain message (WASM only)
+    #[cfg(target_arch = "wasm32")]
+    pub async fn sign_message(&self, message: &[u8]) -> IdosResult<SolanaSignedMessage> {
+        wallet_adapter::sign_message(&self.active_wallet()?, message).await
     }
 
-    /// Sign and send deposit transaction (WASM only)
+    /// Sign and send deposit transaction through the connected wallet (WASM only)
     #[cfg(target_arch = "wasm32")]
     pub async fn deposit_spl_token(
         &self,
@@ -150,80 +368,129 @@ impl SolanaHandler {
         amount: u64,
         user_id: &str,
     ) -> IdosResult<String> {
-        solana_deposit_spl(
-            &self.settings.rpc_url,
-            &self.settings.program_id,
-            mint,
-            amount,
-            user_id,
-        )
-        .await
+        super::service::SolanaPlatformPoolService::new(self.clone())
+            .deposit_spl(mint, amount, user_id)
+            .await
     }
 
-    /// Sign and send withdrawal transaction (WASM only)
+    /// Sign and send NFT deposit transaction through the connected wallet (WASM only)
+    #[cfg(target_arch = "wasm32")]
+    pub async fn deposit_nft(&self, mint: &str, user_id: &str) -> IdosResult<String> {
+        super::service::SolanaPlatformPoolService::new(self.clone())
+            .deposit_nft(mint, user_id)
+            .await
+    }
+
+    /// Sign and send withdrawal transaction through the connected wallet (WASM only)
     #[cfg(target_arch = "wasm32")]
     pub async fn withdraw_spl_token(
         &self,
         withdraw_request: WithdrawSplRequest,
     ) -> IdosResult<String> {
-        solana_withdraw_spl(
-            &self.settings.rpc_url,
-            &self.settings.program_id,
-            withdraw_request,
-        )
-        .await
+        super::service::SolanaPlatformPoolService::new(self.clone())
+            .withdraw_spl(withdraw_request)
+            .await
     }
 
     /// Get transaction status
     pub async fn get_transaction_status(&self, signature: &str) -> IdosResult<TransactionResult> {
-        #[cfg(target_arch = "wasm32")]
-        {
-            solana_get_transaction(&self.settings.rpc_url, signature).await
-        }
-
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            let _ = signature;
-            Err(IdosError::PlatformNotSupported(
-                "Native Solana support requires solana-client crate".to_string(),
-            ))
-        }
+        solana_get_transaction(&self.settings.rpc_url, signature, self.settings.commitment).await
     }
 
-    /// Wait for transaction confirmation
+    /// Wait for transaction confirmation at the configured commitment
+    /// Uses `signatureSubscribe` when `ws_url` is configured, polling otherwise
     pub async fn confirm_transaction(
         &self,
         signature: &str,
         max_attempts: u32,
     ) -> IdosResult<bool> {
-        for _ in 0..max_attempts {
-            match self.get_transaction_status(signature).await {
-                Ok(result) => {
-                    if result.confirmed {
-                        return Ok(true);
-                    }
+        self.confirm_transaction_with_commitment(signature, max_attempts, self.settings.commitment)
+            .await
+    }
+
+    /// Wait for a transaction to reach a specific commitment
+    pub async fn confirm_transaction_with_commitment(
+        &self,
+        signature: &str,
+        max_attempts: u32,
+        commitment: Commitment,
+    ) -> IdosResult<bool> {
+        self.wait_for_slot(signature, max_attempts, commitment)
+            .await
+            .map(|_| true)
+    }
+
+    /// Wait for a transaction and return the slot it landed in
+    /// Fails with `TransactionReverted` when it landed with an error
+    async fn wait_for_slot(
+        &self,
+        signature: &str,
+        max_attempts: u32,
+        commitment: Commitment,
+    ) -> IdosResult<u64> {
+        if let Some(ws_url) = &self.settings.ws_url {
+            let timeout = std::time::Duration::from_secs(2 * max_attempts as u64);
+            match subscribe_signature(ws_url, signature, commitment.as_str(), timeout).await {
+                Ok(notification) => {
+                    return match notification.err {
+                        None => Ok(notification.slot),
+                        Some(err) => Err(IdosError::TransactionReverted(err.to_string())),
+                    };
+                }
+                Err(IdosError::TimeoutError(_)) => {
+                    // The notification may have been missed - check once before giving up
+                    return match self.landed_slot(signature, commitment).await {
+                        Ok(Some(slot)) => Ok(slot),
+                        Err(e @ IdosError::TransactionReverted(_)) => Err(e),
+                        _ => Err(IdosError::TimeoutError(
+                            "Transaction not confirmed".to_string(),
+                        )),
+                    };
                 }
                 Err(_) => {
-                    // Transaction not found yet, continue waiting
+                    // WebSocket unavailable, fall back to polling
                 }
             }
+        }
 
-            #[cfg(target_arch = "wasm32")]
-            {
-                // Wait 2 seconds
-                let promise = js_sys::Promise::new(&mut |resolve, _| {
-                    let window = web_sys::window().unwrap();
-                    window
-                        .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 2000)
-                        .ok();
-                });
-                wasm_bindgen_futures::JsFuture::from(promise).await.ok();
-            }
+        self.poll_confirmation(signature, max_attempts, commitment)
+            .await
+    }
 
-            #[cfg(not(target_arch = "wasm32"))]
+    /// Slot of a landed transaction, None while it isn't visible at `commitment`
+    async fn landed_slot(
+        &self,
+        signature: &str,
+        commitment: Commitment,
+    ) -> IdosResult<Option<u64>> {
+        let transaction =
+            match solana_get_transaction_with_meta(&self.settings.rpc_url, signature, commitment)
+                .await
             {
-                tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                Ok(transaction) => transaction,
+                // Not found yet
+                Err(_) => return Ok(None),
+            };
+
+        match transaction.meta.and_then(|meta| meta.err) {
+            None => Ok(Some(transaction.slot)),
+            Some(err) => Err(IdosError::TransactionReverted(err.to_string())),
+        }
+    }
+
+    /// Poll `getTransaction` every 2 seconds until the transaction lands
+    async fn poll_confirmation(
+        &self,
+        signature: &str,
+        max_attempts: u32,
+        commitment: Commitment,
+    ) -> IdosResult<u64> {
+        for _ in 0..max_attempts {
+            if let Some(slot) = self.landed_slot(signature, commitment).await? {
+                return Ok(slot);
             }
+
+            sleep(std::time::Duration::from_secs(2)).await;
         }
 
         Err(IdosError::TimeoutError(
@@ -231,6 +498,245 @@ impl SolanaHandler {
         ))
     }
 
+    /// Program logs of a landed transaction (best effort, empty when unavailable)
+    async fn transaction_logs(&self, signature: &str) -> Vec<String> {
+        solana_get_transaction_with_meta(&self.settings.rpc_url, signature, Commitment::Confirmed)
+            .await
+            .ok()
+            .and_then(|transaction| transaction.meta)
+            .and_then(|meta| meta.log_messages)
+            .unwrap_or_default()
+    }
+
+    /// Watch a transaction in the background
+    /// Its lifecycle is delivered as `SolanaTxSubmitted`, then `SolanaTxConfirmed` or
+    /// `SolanaTxFailed` messages (plus a `SolanaSignatureUpdate`) by SolanaPlugin
+    pub fn watch_transaction(&self, signature: &str, max_attempts: u32) {
+        let handler = self.clone();
+        let signature = signature.to_string();
+        self.push_tx_event(SolanaTxEvent::Submitted(SolanaTxSubmitted {
+            signature: signature.clone(),
+        }));
+
+        let watch = async move {
+            let commitment = handler.settings.commitment;
+            let (outcome, event) = match handler
+                .wait_for_slot(&signature, max_attempts, commitment)
+                .await
+            {
+                Ok(slot) => (
+                    SignatureOutcome::Confirmed,
+                    SolanaTxEvent::Confirmed(SolanaTxConfirmed {
+                        signature: signature.clone(),
+                        slot,
+                    }),
+                ),
+                Err(e @ IdosError::TimeoutError(_)) => (
+                    SignatureOutcome::TimedOut,
+                    SolanaTxEvent::Failed(SolanaTxFailed {
+                        signature: signature.clone(),
+                        error: e.to_string(),
+                        logs: Vec::new(),
+                    }),
+                ),
+                Err(e) => (
+                    SignatureOutcome::Failed(e.to_string()),
+                    SolanaTxEvent::Failed(SolanaTxFailed {
+                        signature: signature.clone(),
+                        error: e.to_string(),
+                        logs: handler.transaction_logs(&signature).await,
+                    }),
+                ),
+            };
+
+            if let Some(history) = &handler.history {
+                match &outcome {
+                    SignatureOutcome::Confirmed => {
+                        history.set_status(&signature, TransactionStatus::Confirmed, None)
+                    }
+                    SignatureOutcome::Failed(error) => history.set_status(
+                        &signature,
+                        TransactionStatus::Failed,
+                        Some(error.clone()),
+                    ),
+                    // It may still land; the history poll decides
+                    SignatureOutcome::TimedOut => Ok(()),
+                }
+                .ok();
+            }
+
+            handler.push_tx_event(event);
+            if let Ok(mut updates) = handler.signature_updates.lock() {
+                updates.push(SolanaSignatureUpdate { signature, outcome });
+            }
+        };
+
+        crate::tasks::spawn(watch);
+    }
+
+    /// Record signatures sent by the SDK in `history` (SolanaPlugin shares the app's history)
+    pub fn set_transaction_history(&mut self, history: TransactionHistory) {
+        self.history = Some(history);
+    }
+
+    /// History of transactions sent by the SDK, when SolanaPlugin set one
+    pub fn transaction_history(&self) -> Option<&TransactionHistory> {
+        self.history.as_ref()
+    }
+
+    /// Track a signature sent by the SDK until it confirms or fails
+    pub(crate) fn record_transaction(&self, signature: &str, label: &str) {
+        if let Some(history) = &self.history {
+            history
+                .record(signature, TransactionChain::Solana, Some(label))
+                .ok();
+        }
+    }
+
+    /// Check every pending signature in the history once
+    /// Signatures that never land within 5 minutes are marked failed
+    pub async fn refresh_transaction_history(&self) -> IdosResult<()> {
+        let Some(history) = &self.history else {
+            return Ok(());
+        };
+        let now = chrono::Utc::now().timestamp();
+
+        for transaction in history.pending(TransactionChain::Solana) {
+            let (status, error) = match self
+                .landed_slot(&transaction.id, self.settings.commitment)
+                .await
+            {
+                Ok(Some(_)) => (TransactionStatus::Confirmed, None),
+                Err(IdosError::TransactionReverted(error)) => {
+                    (TransactionStatus::Failed, Some(error))
+                }
+                _ if now - transaction.sent_at > PENDING_TIMEOUT_SECS => (
+                    TransactionStatus::Failed,
+                    Some("Transaction never landed".to_string()),
+                ),
+                _ => continue,
+            };
+            history.set_status(&transaction.id, status, error)?;
+        }
+
+        Ok(())
+    }
+
+    fn push_tx_event(&self, event: SolanaTxEvent) {
+        if let Ok(mut events) = self.tx_events.lock() {
+            events.push(event);
+        }
+    }
+
+    /// Take the transaction lifecycle events collected since the last call
+    pub fn drain_tx_events(&self) -> Vec<SolanaTxEvent> {
+        self.tx_events
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default()
+    }
+
+    /// Take the signature updates collected since the last call
+    pub fn drain_signature_updates(&self) -> Vec<SolanaSignatureUpdate> {
+        self.signature_updates
+            .lock()
+            .map(|mut updates| std::mem::take(&mut *updates))
+            .unwrap_or_default()
+    }
+
+    /// Subscribe to an account over `ws_url` (`accountSubscribe`)
+    /// Every change is delivered as a `SolanaAccountChanged` message by SolanaPlugin;
+    /// the subscription reconnects until `unwatch_account` is called
+    pub fn watch_account(&self, address: &str) -> IdosResult<()> {
+        let ws_url = self.settings.ws_url.clone().ok_or_else(|| {
+            IdosError::ConfigurationError("Account subscriptions need a ws_url".to_string())
+        })?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        {
+            let mut watches = self
+                .account_watches
+                .lock()
+                .map_err(|_| IdosError::Unknown("Account watch lock poisoned".to_string()))?;
+            if watches.contains_key(address) {
+                return Ok(());
+            }
+            watches.insert(address.to_string(), stop.clone());
+        }
+
+        let updates = self.account_updates.clone();
+        let address = address.to_string();
+        let commitment = self.settings.commitment;
+
+        let watch = async move {
+            let previous_lamports = Arc::new(Mutex::new(None::<u64>));
+            while !stop.load(Ordering::Acquire) {
+                let updates = updates.clone();
+                let previous_lamports = previous_lamports.clone();
+                let changed_address = address.clone();
+                let result = subscribe_account(
+                    &ws_url,
+                    &address,
+                    commitment.as_str(),
+                    stop.clone(),
+                    move |notification| {
+                        let previous = previous_lamports
+                            .lock()
+                            .ok()
+                            .and_then(|mut last| last.replace(notification.lamports));
+                        if let Ok(mut updates) = updates.lock() {
+                            updates.push(SolanaAccountChanged {
+                                address: changed_address.clone(),
+                                slot: notification.slot,
+                                lamports: notification.lamports,
+                                previous_lamports: previous,
+                                token_amount: notification.token_amount,
+                            });
+                        }
+                    },
+                )
+                .await;
+
+                if result.is_err() && !stop.load(Ordering::Acquire) {
+                    // Dropped connection - reconnect after a short pause
+                    sleep(std::time::Duration::from_secs(5)).await;
+                }
+            }
+        };
+
+        crate::tasks::spawn(watch);
+
+        Ok(())
+    }
+
+    /// Stop a subscription started by `watch_account`
+    pub fn unwatch_account(&self, address: &str) {
+        if let Some(stop) = self
+            .account_watches
+            .lock()
+            .ok()
+            .and_then(|mut watches| watches.remove(address))
+        {
+            stop.store(true, Ordering::Release);
+        }
+    }
+
+    /// Addresses with a running account subscription
+    pub fn watched_accounts(&self) -> Vec<String> {
+        self.account_watches
+            .lock()
+            .map(|watches| watches.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Take the account changes collected since the last call
+    pub fn drain_account_updates(&self) -> Vec<SolanaAccountChanged> {
+        self.account_updates
+            .lock()
+            .map(|mut updates| std::mem::take(&mut *updates))
+            .unwrap_or_default()
+    }
+
     /// Convert lamports to SOL
     pub fn lamports_to_sol(lamports: u64) -> f64 {
         lamports as f64 / 1_000_000_000.0
@@ -247,13 +753,126 @@ impl SolanaHandler {
     }
 
     /// Load all NFTs owned by a wallet address
-    /// Uses Metaplex Token Metadata to fetch NFT data
+    /// Uses Metaplex Token Metadata to fetch NFT data, served from the NFT cache when fresh
     pub async fn load_nfts(&self, owner_address: &str) -> IdosResult<NftLoadResult> {
-        super::nft::load_nfts_by_owner(&self.settings.rpc_url, owner_address).await
+        super::nft::load_nfts_by_owner_cached(
+            &self.settings.rpc_url,
+            owner_address,
+            self.nft_cache.as_ref(),
+        )
+        .await
+    }
+
+    /// Load a single asset (including compressed NFTs) through the DAS API
+    pub async fn get_asset(&self, asset_id: &str) -> IdosResult<DasAsset> {
+        super::das::get_asset(&self.settings.rpc_url, asset_id).await
+    }
+
+    /// Load a Metaplex Core asset
+    pub async fn get_core_asset(&self, address: &str) -> IdosResult<CoreAsset> {
+        super::mpl_core::get_core_asset(&self.settings.rpc_url, address).await
+    }
+
+    /// Accounts of a program matching every filter, with raw data
+    pub async fn get_program_accounts(
+        &self,
+        program_id: &str,
+        filters: &[AccountFilter],
+    ) -> IdosResult<Vec<DecodedAccount<Vec<u8>>>> {
+        super::program_accounts::get_program_accounts(
+            &self.settings.rpc_url,
+            program_id,
+            filters,
+            self.settings.commitment,
+        )
+        .await
+    }
+
+    /// Accounts of a program decoded as Borsh (accounts that fail to decode are skipped)
+    pub async fn get_program_accounts_as<T: borsh::BorshDeserialize>(
+        &self,
+        program_id: &str,
+        filters: &[AccountFilter],
+    ) -> IdosResult<Vec<DecodedAccount<T>>> {
+        super::program_accounts::get_borsh_accounts(
+            &self.settings.rpc_url,
+            program_id,
+            filters,
+            self.settings.commitment,
+        )
+        .await
+    }
+
+    /// Anchor accounts of one type, e.g. `get_anchor_accounts::<Player>(program, "Player", &[])`
+    pub async fn get_anchor_accounts<T: borsh::BorshDeserialize>(
+        &self,
+        program_id: &str,
+        account_name: &str,
+        filters: &[AccountFilter],
+    ) -> IdosResult<Vec<DecodedAccount<T>>> {
+        super::program_accounts::get_anchor_accounts(
+            &self.settings.rpc_url,
+            program_id,
+            account_name,
+            filters,
+            self.settings.commitment,
+        )
+        .await
+    }
+
+    /// Latest transactions of a wallet for an activity screen, newest first
+    pub async fn get_transaction_history(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> IdosResult<Vec<SolanaHistoryEntry>> {
+        super::history::get_transaction_history(&self.settings.rpc_url, address, limit).await
+    }
+
+    /// Find the payment of a Solana Pay request by its reference key
+    pub async fn find_solana_pay_payment(&self, reference: &str) -> IdosResult<Option<String>> {
+        let found = super::solana_pay::find_reference(&self.settings.rpc_url, reference).await?;
+        Ok(found.map(|info| info.signature))
+    }
+
+    /// Check that a transaction pays a Solana Pay transfer request
+    pub async fn validate_solana_pay_payment(
+        &self,
+        signature: &str,
+        request: &SolanaPayTransferRequest,
+    ) -> IdosResult<()> {
+        super::solana_pay::validate_transfer(&self.settings.rpc_url, signature, request).await?;
+        Ok(())
     }
 
     /// Load metadata for a specific NFT mint
     pub async fn load_nft(&self, mint_address: &str, owner_address: &str) -> IdosResult<Nft> {
-        super::nft::load_nft_metadata(&self.settings.rpc_url, mint_address, owner_address).await
+        super::nft::load_nft_metadata_cached(
+            &self.settings.rpc_url,
+            mint_address,
+            owner_address,
+            self.nft_cache.as_ref(),
+        )
+        .await
+    }
+
+    /// Forget cached NFT metadata, e.g. after the player asks to refresh
+    pub fn clear_nft_cache(&self) {
+        if let Some(cache) = &self.nft_cache {
+            cache.clear();
+        }
+    }
+}
+
+/// Signs with the selected browser wallet
+#[cfg(target_arch = "wasm32")]
+#[async_trait::async_trait(?Send)]
+impl super::sign_in::SolanaMessageSigner for SolanaHandler {
+    async fn solana_address(&self) -> IdosResult<String> {
+        self.connect_wallet().await
+    }
+
+    async fn sign_solana_message(&self, message: &[u8]) -> IdosResult<SolanaSignedMessage> {
+        self.sign_message(message).await
     }
 }
@@ -1,7 +1,5 @@
-/// Helper functions for WASM Solana operations
-#[cfg(target_arch = "wasm32")]
+/// Helper functions for Solana RPC queries
 use super::dto::*;
-#[cfg(target_arch = "wasm32")]
 use crate::{IdosError, IdosResult};
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
@@ -10,84 +8,200 @@ use wasm_bindgen::JsCast;
 #[cfg(target_arch = "wasm32")]
 use web_sys::{Request, RequestInit, RequestMode, Response};
 
-/// Send Solana JSON-RPC request (WASM only)
-#[cfg(target_arch = "wasm32")]
+/// Failure of a single RPC attempt
+enum RpcFailure {
+    /// Rate limited, timed out or unreachable - the next endpoint may succeed
+    Retryable(IdosError),
+    /// The endpoint answered (e.g. a JSON-RPC error) - retrying elsewhere won't help
+    Fatal(IdosError),
+}
+
+/// Read a JSON-RPC response body
+fn rpc_result<T>(response: SolanaRpcResponse<T>) -> IdosResult<T> {
+    if let Some(error) = response.error {
+        return Err(IdosError::NetworkError(format!(
+            "Solana RPC Error: {}",
+            error.message
+        )));
+    }
+
+    response
+        .result
+        .ok_or_else(|| IdosError::NetworkError("No result in response".to_string()))
+}
+
+/// HTTP statuses worth retrying on another endpoint
+fn is_retryable_status(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+/// Error for a response body that isn't JSON-RPC
+/// A 4xx without a JSON-RPC body is still an explicit refusal, reported as `HTTP <status>`
+fn unreadable_response(status: u16, error: IdosError) -> IdosError {
+    if (400..500).contains(&status) {
+        IdosError::NetworkError(format!("HTTP {}", status))
+    } else {
+        error
+    }
+}
+
+/// Send Solana JSON-RPC request
+/// Fails over to the endpoints configured for `rpc_url` on 429s, timeouts and connection errors
 pub async fn send_solana_rpc_request<T: serde::de::DeserializeOwned>(
     rpc_url: &str,
     method: &str,
     params: serde_json::Value,
 ) -> IdosResult<T> {
-    let request_body = SolanaRpcRequest {
-        jsonrpc: "2.0".to_string(),
-        method: method.to_string(),
-        params,
-        id: 1,
-    };
-
+    let request_body = SolanaRpcRequest::new(method.to_string(), params, 1);
     let body = serde_json::to_string(&request_body)
         .map_err(|e| IdosError::SerializationError(e.to_string()))?;
 
+    let mut last_error = None;
+    for endpoint in super::rpc::candidates(rpc_url, method) {
+        match send_rpc_once(&endpoint, &body).await {
+            Ok(response) => {
+                super::rpc::report(rpc_url, &endpoint, true);
+                return rpc_result(response);
+            }
+            Err(RpcFailure::Retryable(e)) => {
+                log::warn!("Solana RPC {} failed on {}: {}", method, endpoint, e);
+                super::rpc::report(rpc_url, &endpoint, false);
+                last_error = Some(e);
+            }
+            Err(RpcFailure::Fatal(e)) => return Err(e),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| IdosError::NetworkError("No Solana RPC endpoint".to_string())))
+}
+
+/// Send a JSON-RPC body to one endpoint (WASM)
+#[cfg(target_arch = "wasm32")]
+async fn send_rpc_once<T: serde::de::DeserializeOwned>(
+    endpoint: &str,
+    body: &str,
+) -> Result<SolanaRpcResponse<T>, RpcFailure> {
+    let fatal = |message: String| RpcFailure::Fatal(IdosError::NetworkError(message));
+
     let mut opts = RequestInit::new();
     opts.method("POST");
     opts.mode(RequestMode::Cors);
-    opts.body(Some(&JsValue::from_str(&body)));
+    opts.body(Some(&JsValue::from_str(body)));
 
-    let request = Request::new_with_str_and_init(rpc_url, &opts)
-        .map_err(|e| IdosError::NetworkError(format!("Request creation failed: {:?}", e)))?;
+    let request = Request::new_with_str_and_init(endpoint, &opts)
+        .map_err(|e| fatal(format!("Request creation failed: {:?}", e)))?;
 
     request
         .headers()
         .set("Content-Type", "application/json")
-        .map_err(|e| IdosError::NetworkError(format!("Header set failed: {:?}", e)))?;
+        .map_err(|e| fatal(format!("Header set failed: {:?}", e)))?;
 
-    let window = web_sys::window()
-        .ok_or_else(|| IdosError::PlatformNotSupported("No window object".to_string()))?;
+    let window = web_sys::window().ok_or_else(|| {
+        RpcFailure::Fatal(IdosError::PlatformNotSupported(
+            "No window object".to_string(),
+        ))
+    })?;
 
     let resp_value = wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request))
         .await
-        .map_err(|e| IdosError::NetworkError(format!("Fetch failed: {:?}", e)))?;
+        .map_err(|e| {
+            RpcFailure::Retryable(IdosError::NetworkError(format!("Fetch failed: {:?}", e)))
+        })?;
 
     let resp: Response = resp_value
         .dyn_into()
-        .map_err(|_| IdosError::NetworkError("Response cast failed".to_string()))?;
+        .map_err(|_| fatal("Response cast failed".to_string()))?;
 
+    if is_retryable_status(resp.status()) {
+        return Err(RpcFailure::Retryable(IdosError::NetworkError(format!(
+            "HTTP {}",
+            resp.status()
+        ))));
+    }
+
+    let status = resp.status();
     let json = wasm_bindgen_futures::JsFuture::from(
         resp.json()
-            .map_err(|e| IdosError::NetworkError(format!("JSON parse failed: {:?}", e)))?,
+            .map_err(|e| fatal(format!("JSON parse failed: {:?}", e)))?,
     )
     .await
-    .map_err(|e| IdosError::NetworkError(format!("JSON future failed: {:?}", e)))?;
+    .map_err(|e| {
+        RpcFailure::Fatal(unreadable_response(
+            status,
+            IdosError::NetworkError(format!("JSON future failed: {:?}", e)),
+        ))
+    })?;
+
+    serde_wasm_bindgen::from_value(json)
+        .map_err(|e| RpcFailure::Fatal(IdosError::SerializationError(e.to_string())))
+}
 
-    let response: SolanaRpcResponse<T> = serde_wasm_bindgen::from_value(json)
-        .map_err(|e| IdosError::SerializationError(e.to_string()))?;
+/// Send a JSON-RPC body to one endpoint (native)
+#[cfg(not(target_arch = "wasm32"))]
+async fn send_rpc_once<T: serde::de::DeserializeOwned>(
+    endpoint: &str,
+    body: &str,
+) -> Result<SolanaRpcResponse<T>, RpcFailure> {
+    let response = reqwest::Client::new()
+        .post(endpoint)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .timeout(std::time::Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e| {
+            let error = IdosError::NetworkError(e.to_string());
+            if e.is_timeout() || e.is_connect() || e.is_request() {
+                RpcFailure::Retryable(error)
+            } else {
+                RpcFailure::Fatal(error)
+            }
+        })?;
 
-    if let Some(error) = response.error {
-        return Err(IdosError::NetworkError(format!(
-            "Solana RPC Error: {}",
-            error.message
-        )));
+    let status = response.status().as_u16();
+    if is_retryable_status(status) {
+        return Err(RpcFailure::Retryable(IdosError::NetworkError(format!(
+            "HTTP {}",
+            status
+        ))));
     }
 
-    response
-        .result
-        .ok_or_else(|| IdosError::NetworkError("No result in response".to_string()))
+    response.json().await.map_err(|e| {
+        RpcFailure::Fatal(unreadable_response(
+            status,
+            IdosError::SerializationError(e.to_string()),
+        ))
+    })
 }
 
-/// Get SOL balance (WASM only)
-#[cfg(target_arch = "wasm32")]
-pub async fn solana_get_balance(rpc_url: &str, address: &str) -> IdosResult<u64> {
-    let params = serde_json::json!([address]);
+/// Get SOL balance
+pub async fn solana_get_balance(
+    rpc_url: &str,
+    address: &str,
+    commitment: Commitment,
+) -> IdosResult<u64> {
+    let params = serde_json::json!([address, { "commitment": commitment }]);
     let balance_response: BalanceResponse =
         send_solana_rpc_request(rpc_url, "getBalance", params).await?;
     Ok(balance_response.value)
 }
 
-/// Get SPL token balance (WASM only)
-#[cfg(target_arch = "wasm32")]
+/// Request an airdrop from the cluster faucet, returns the airdrop transaction signature
+pub async fn solana_request_airdrop(
+    rpc_url: &str,
+    address: &str,
+    lamports: u64,
+) -> IdosResult<String> {
+    let params = serde_json::json!([address, lamports]);
+    send_solana_rpc_request(rpc_url, "requestAirdrop", params).await
+}
+
+/// Get SPL token balance
 pub async fn solana_get_token_balance(
     rpc_url: &str,
     wallet_address: &str,
     mint_address: &str,
+    commitment: Commitment,
 ) -> IdosResult<TokenAmount> {
     // First, get token accounts by owner
     let params = serde_json::json!([
@@ -96,7 +210,8 @@ pub async fn solana_get_token_balance(
             "mint": mint_address
         },
         {
-            "encoding": "jsonParsed"
+            "encoding": "jsonParsed",
+            "commitment": commitment
         }
     ]);
 
@@ -104,28 +219,110 @@ pub async fn solana_get_token_balance(
         send_solana_rpc_request(rpc_url, "getTokenAccountsByOwner", params).await?;
 
     if let Some(account) = response.value.first() {
-        Ok(account.account.data.parsed.token_amount.clone())
+        Ok(account.account.data.parsed.info.token_amount.clone())
     } else {
-        // No token account found, balance is 0
+        // No token account found, balance is 0; decimals still come from the mint
         Ok(TokenAmount {
             amount: "0".to_string(),
-            decimals: 9,
+            decimals: solana_get_token_decimals(rpc_url, mint_address).await?,
             ui_amount: Some(0.0),
             ui_amount_string: Some("0".to_string()),
         })
     }
 }
 
-/// Get transaction status (WASM only)
-#[cfg(target_arch = "wasm32")]
+/// Get every SPL token account owned by a wallet, with balances
+/// Covers accounts of both the SPL Token and Token-2022 programs
+pub async fn solana_get_token_accounts(
+    rpc_url: &str,
+    wallet_address: &str,
+    commitment: Commitment,
+) -> IdosResult<Vec<SolanaTokenAccount>> {
+    let mut accounts = Vec::new();
+
+    for program in [TokenProgram::Spl, TokenProgram::Token2022] {
+        let params = serde_json::json!([
+            wallet_address,
+            {
+                "programId": program.program_id()
+            },
+            {
+                "encoding": "jsonParsed",
+                "commitment": commitment
+            }
+        ]);
+
+        let response: super::dto::TokenAccountsResponse =
+            send_solana_rpc_request(rpc_url, "getTokenAccountsByOwner", params).await?;
+
+        accounts.extend(response.value.into_iter().map(|account| {
+            let info = account.account.data.parsed.info;
+            SolanaTokenAccount {
+                address: account.pubkey,
+                mint: info.mint,
+                amount: info.token_amount,
+                program,
+            }
+        }));
+    }
+
+    Ok(accounts)
+}
+
+/// Get the decimals of an SPL token mint
+pub async fn solana_get_token_decimals(rpc_url: &str, mint_address: &str) -> IdosResult<u8> {
+    let params = serde_json::json!([mint_address]);
+    let supply: TokenSupplyResponse =
+        send_solana_rpc_request(rpc_url, "getTokenSupply", params).await?;
+    Ok(supply.value.decimals)
+}
+
+/// Check whether an account exists on chain
+pub async fn solana_account_exists(rpc_url: &str, address: &str) -> IdosResult<bool> {
+    let params = serde_json::json!([address, { "encoding": "base64", "dataSlice": { "offset": 0, "length": 0 } }]);
+    let response: AccountInfoResponse =
+        send_solana_rpc_request(rpc_url, "getAccountInfo", params).await?;
+    Ok(response.value.is_some())
+}
+
+/// Get a landed transaction with its execution result and balance changes
+pub async fn solana_get_transaction_with_meta(
+    rpc_url: &str,
+    signature: &str,
+    commitment: Commitment,
+) -> IdosResult<TransactionWithMeta> {
+    // getTransaction has no processed level
+    let commitment = match commitment {
+        Commitment::Processed => Commitment::Confirmed,
+        other => other,
+    };
+    let params = serde_json::json!([
+        signature,
+        {
+            "encoding": "json",
+            "commitment": commitment,
+            "maxSupportedTransactionVersion": 0
+        }
+    ]);
+    send_solana_rpc_request(rpc_url, "getTransaction", params).await
+}
+
+/// Get transaction status
+/// getTransaction has no processed level, so processed is read as confirmed
 pub async fn solana_get_transaction(
     rpc_url: &str,
     signature: &str,
+    commitment: Commitment,
 ) -> IdosResult<TransactionResult> {
+    let commitment = match commitment {
+        Commitment::Processed => Commitment::Confirmed,
+        other => other,
+    };
     let params = serde_json::json!([
         signature,
         {
             "encoding": "json",
+            "commitment": commitment,
             "maxSupportedTransactionVersion": 0
         }
     ]);
@@ -150,124 +347,4 @@ pub async fn solana_get_transaction(
     }
 }
 
-/// Solana wallet integration (Phantom, Solflare) (WASM only)
-#[cfg(target_arch = "wasm32")]
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = window, js_name = solana)]
-    pub static SOLANA: JsValue;
-
-    #[wasm_bindgen(js_namespace = ["window", "solana"], js_name = connect)]
-    pub fn solana_wallet_connect() -> js_sys::Promise;
-
-    #[wasm_bindgen(js_namespace = ["window", "solana"], js_name = disconnect)]
-    pub fn solana_wallet_disconnect() -> js_sys::Promise;
-
-    #[wasm_bindgen(js_namespace = ["window", "solana"], js_name = signAndSendTransaction)]
-    pub fn solana_wallet_sign_and_send(transaction: JsValue) -> js_sys::Promise;
-
-    #[wasm_bindgen(js_namespace = ["window", "solana"], js_name = signTransaction)]
-    pub fn solana_wallet_sign(transaction: JsValue) -> js_sys::Promise;
-}
-
-/// Check if Solana wallet is available (Phantom/Solflare) (WASM only)
-#[cfg(target_arch = "wasm32")]
-pub fn is_solana_wallet_available() -> bool {
-    !SOLANA.is_undefined() && !SOLANA.is_null()
-}
-
-/// Connect to Solana wallet (WASM only)
-#[cfg(target_arch = "wasm32")]
-pub async fn solana_connect_wallet() -> IdosResult<String> {
-    if !is_solana_wallet_available() {
-        return Err(IdosError::PlatformNotSupported(
-            "Solana wallet not available. Please install Phantom or Solflare".to_string(),
-        ));
-    }
-
-    let promise = solana_wallet_connect();
-    let result = wasm_bindgen_futures::JsFuture::from(promise)
-        .await
-        .map_err(|e| IdosError::NetworkError(format!("Wallet connect failed: {:?}", e)))?;
-
-    // Extract publicKey from result
-    let public_key = js_sys::Reflect::get(&result, &JsValue::from_str("publicKey"))
-        .map_err(|_| IdosError::NetworkError("Failed to get public key".to_string()))?;
-
-    let public_key_str = js_sys::Reflect::get(&public_key, &JsValue::from_str("toString"))
-        .and_then(|to_string| {
-            let func = to_string
-                .dyn_ref::<js_sys::Function>()
-                .ok_or(JsValue::NULL)?;
-            func.call0(&public_key)
-        })
-        .and_then(|s| s.as_string().ok_or(JsValue::NULL))
-        .map_err(|_| {
-            IdosError::NetworkError("Failed to convert public key to string".to_string())
-        })?;
-
-    Ok(public_key_str)
-}
-
-/// Send transaction via wallet (WASM only)
-#[cfg(target_arch = "wasm32")]
-pub async fn solana_send_transaction(transaction_base64: &str) -> IdosResult<String> {
-    if !is_solana_wallet_available() {
-        return Err(IdosError::PlatformNotSupported(
-            "Solana wallet not available".to_string(),
-        ));
-    }
-
-    let tx_obj = serde_json::json!({
-        "transaction": transaction_base64
-    });
-
-    let tx_js = serde_wasm_bindgen::to_value(&tx_obj)
-        .map_err(|e| IdosError::SerializationError(e.to_string()))?;
-
-    let promise = solana_wallet_sign_and_send(tx_js);
-    let result = wasm_bindgen_futures::JsFuture::from(promise)
-        .await
-        .map_err(|e| IdosError::NetworkError(format!("Send transaction failed: {:?}", e)))?;
-
-    let signature = js_sys::Reflect::get(&result, &JsValue::from_str("signature"))
-        .and_then(|s| s.as_string().ok_or(JsValue::NULL))
-        .map_err(|_| IdosError::NetworkError("Failed to get signature".to_string()))?;
-
-    Ok(signature)
-}
-
-/// Deposit SPL token (WASM only - simplified version)
-#[cfg(target_arch = "wasm32")]
-pub async fn solana_deposit_spl(
-    _rpc_url: &str,
-    _program_id: &str,
-    _mint: &str,
-    _amount: u64,
-    _user_id: &str,
-) -> IdosResult<String> {
-    // This would need full transaction building logic
-    // For now, return placeholder
-    Err(IdosError::PlatformNotSupported(
-        "Direct deposit requires transaction building - use backend API or full SDK".to_string(),
-    ))
-}
-
-/// Withdraw SPL token (WASM only - simplified version)
-#[cfg(target_arch = "wasm32")]
-pub async fn solana_withdraw_spl(
-    _rpc_url: &str,
-    _program_id: &str,
-    _withdraw_request: WithdrawSplRequest,
-) -> IdosResult<String> {
-    // This would need full transaction building logic with Ed25519 instruction
-    // For now, return placeholder
-    Err(IdosError::PlatformNotSupported(
-        "Direct withdrawal requires transaction building - use backend API or full SDK".to_string(),
-    ))
-}
-
-#[cfg(not(target_arch = "wasm32"))]
-pub fn placeholder_for_native() {
-    // This module is primarily for WASM, native implementations would use solana-client
-}
+pub use crate::tasks::sleep;
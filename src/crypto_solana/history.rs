@@ -0,0 +1,281 @@
+/// Wallet transaction history for activity screens
+/// Entries are derived from balance changes, so they cover transfers made by any program
+use super::dto::*;
+use super::helper::{send_solana_rpc_request, solana_get_transaction_with_meta};
+use crate::IdosResult;
+use futures_util::{stream, StreamExt};
+
+/// Concurrent getTransaction requests
+const TRANSACTION_FETCH_CONCURRENCY: usize = 8;
+
+/// Largest page `getSignaturesForAddress` returns
+const MAX_HISTORY_LIMIT: usize = 1000;
+
+/// Token balance change of one token account
+struct TokenDelta {
+    mint: String,
+    owner: Option<String>,
+    delta: i128,
+    decimals: u8,
+}
+
+fn token_deltas(meta: &TransactionMeta) -> Vec<TokenDelta> {
+    let pre = meta.pre_token_balances.as_deref().unwrap_or_default();
+    let post = meta.post_token_balances.as_deref().unwrap_or_default();
+    let raw = |balance: &TokenBalance| balance.ui_token_amount.amount.parse::<i128>().unwrap_or(0);
+
+    let mut deltas: Vec<(usize, TokenDelta)> = Vec::new();
+    for balance in post {
+        deltas.push((
+            balance.account_index,
+            TokenDelta {
+                mint: balance.mint.clone(),
+                owner: balance.owner.clone(),
+                delta: raw(balance),
+                decimals: balance.ui_token_amount.decimals,
+            },
+        ));
+    }
+    for balance in pre {
+        match deltas
+            .iter_mut()
+            .find(|(index, _)| *index == balance.account_index)
+        {
+            Some((_, delta)) => delta.delta -= raw(balance),
+            // Account closed by the transaction
+            None => deltas.push((
+                balance.account_index,
+                TokenDelta {
+                    mint: balance.mint.clone(),
+                    owner: balance.owner.clone(),
+                    delta: -raw(balance),
+                    decimals: balance.ui_token_amount.decimals,
+                },
+            )),
+        }
+    }
+
+    deltas.into_iter().map(|(_, delta)| delta).collect()
+}
+
+/// Build the history entry of a transaction as seen from `address`
+pub fn history_entry(
+    address: &str,
+    info: &SignatureInfo,
+    transaction: &TransactionWithMeta,
+) -> SolanaHistoryEntry {
+    let mut entry = SolanaHistoryEntry {
+        signature: info.signature.clone(),
+        slot: transaction.slot,
+        timestamp: transaction.block_time.or(info.block_time),
+        success: info.err.is_none(),
+        direction: TransferDirection::Other,
+        counterparty: None,
+        token: None,
+        amount: 0,
+        decimals: 9,
+        fee: 0,
+        memo: info.memo.clone(),
+    };
+    let Some(meta) = &transaction.meta else {
+        return entry;
+    };
+    entry.success = meta.err.is_none();
+
+    let keys = transaction.account_keys();
+    let index = keys.iter().position(|key| key == address);
+    // The fee payer is always the first account
+    if index == Some(0) {
+        entry.fee = meta.fee;
+    }
+
+    // Token movements take precedence: the SOL change next to them is usually fees and rent
+    let deltas = token_deltas(meta);
+    let mut own: Vec<(&String, i128, u8)> = Vec::new();
+    for delta in deltas
+        .iter()
+        .filter(|d| d.owner.as_deref() == Some(address))
+    {
+        match own.iter_mut().find(|(mint, _, _)| **mint == delta.mint) {
+            Some((_, sum, _)) => *sum += delta.delta,
+            None => own.push((&delta.mint, delta.delta, delta.decimals)),
+        }
+    }
+
+    if let Some((mint, delta, decimals)) = own
+        .into_iter()
+        .filter(|(_, delta, _)| *delta != 0)
+        .max_by_key(|(_, delta, _)| delta.unsigned_abs())
+    {
+        entry.token = Some(mint.clone());
+        entry.decimals = decimals;
+        entry.amount = delta.unsigned_abs() as u64;
+        entry.direction = direction(delta);
+        entry.counterparty = deltas
+            .iter()
+            .filter(|d| d.mint == *mint && d.owner.as_deref() != Some(address))
+            .filter(|d| d.delta.signum() == -delta.signum())
+            .max_by_key(|d| d.delta.unsigned_abs())
+            .and_then(|d| d.owner.clone());
+        return entry;
+    }
+
+    let Some(index) = index else {
+        return entry;
+    };
+    let sol_delta = |i: usize| {
+        let pre = meta.pre_balances.get(i).copied().unwrap_or(0) as i128;
+        let post = meta.post_balances.get(i).copied().unwrap_or(0) as i128;
+        let fee = if i == 0 { meta.fee as i128 } else { 0 };
+        post - pre + fee
+    };
+
+    let delta = sol_delta(index);
+    if delta != 0 {
+        entry.amount = delta.unsigned_abs() as u64;
+        entry.direction = direction(delta);
+        entry.counterparty = (0..keys.len())
+            .filter(|i| *i != index)
+            .map(|i| (i, sol_delta(i)))
+            .filter(|(_, d)| d.signum() == -delta.signum())
+            .max_by_key(|(_, d)| d.unsigned_abs())
+            .map(|(i, _)| keys[i].clone());
+    }
+
+    entry
+}
+
+fn direction(delta: i128) -> TransferDirection {
+    if delta > 0 {
+        TransferDirection::Incoming
+    } else {
+        TransferDirection::Outgoing
+    }
+}
+
+/// Latest transactions of a wallet, newest first
+/// Transactions that can't be fetched are skipped
+pub async fn get_transaction_history(
+    rpc_url: &str,
+    address: &str,
+    limit: usize,
+) -> IdosResult<Vec<SolanaHistoryEntry>> {
+    let params = serde_json::json!([
+        address,
+        {
+            "limit": limit.clamp(1, MAX_HISTORY_LIMIT),
+            "commitment": "confirmed"
+        }
+    ]);
+    let signatures: Vec<SignatureInfo> =
+        send_solana_rpc_request(rpc_url, "getSignaturesForAddress", params).await?;
+
+    let entries = stream::iter(signatures)
+        .map(|info| async move {
+            match solana_get_transaction_with_meta(rpc_url, &info.signature, Commitment::Confirmed)
+                .await
+            {
+                Ok(transaction) => Some(history_entry(address, &info, &transaction)),
+                Err(e) => {
+                    log::warn!("Failed to load transaction {}: {}", info.signature, e);
+                    None
+                }
+            }
+        })
+        .buffered(TRANSACTION_FETCH_CONCURRENCY)
+        .filter_map(|entry| async move { entry })
+        .collect()
+        .await;
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_balance(account_index: usize, owner: &str, amount: u64) -> TokenBalance {
+        TokenBalance {
+            account_index,
+            mint: "Gem".to_string(),
+            owner: Some(owner.to_string()),
+            ui_token_amount: TokenAmount {
+                amount: amount.to_string(),
+                decimals: 6,
+                ui_amount: None,
+                ui_amount_string: None,
+            },
+        }
+    }
+
+    fn transaction(meta: TransactionMeta) -> TransactionWithMeta {
+        TransactionWithMeta {
+            slot: 7,
+            block_time: Some(1_700_000_000),
+            transaction: EncodedTransaction {
+                signatures: vec!["Sig".to_string()],
+                message: EncodedMessage {
+                    account_keys: ["Alice", "Bob", "AliceAta", "BobAta", "Program"]
+                        .map(String::from)
+                        .to_vec(),
+                },
+            },
+            meta: Some(meta),
+        }
+    }
+
+    #[test]
+    fn test_history_entry() {
+        let info = SignatureInfo {
+            signature: "Sig".to_string(),
+            slot: 7,
+            err: None,
+            memo: None,
+            block_time: None,
+            confirmation_status: None,
+        };
+
+        // Alice pays Bob 1 SOL and the fee
+        let sol = transaction(TransactionMeta {
+            err: None,
+            fee: 5000,
+            pre_balances: vec![3_000_000_000, 0, 0, 0, 1],
+            post_balances: vec![1_999_995_000, 1_000_000_000, 0, 0, 1],
+            pre_token_balances: None,
+            post_token_balances: None,
+            loaded_addresses: None,
+            log_messages: None,
+        });
+        let entry = history_entry("Alice", &info, &sol);
+        assert_eq!(entry.direction, TransferDirection::Outgoing);
+        assert_eq!(entry.amount, 1_000_000_000);
+        assert_eq!(entry.fee, 5000);
+        assert_eq!(entry.counterparty.as_deref(), Some("Bob"));
+
+        let entry = history_entry("Bob", &info, &sol);
+        assert_eq!(entry.direction, TransferDirection::Incoming);
+        assert_eq!(entry.fee, 0);
+        assert_eq!(entry.counterparty.as_deref(), Some("Alice"));
+
+        // Alice sends Bob 2.5 gems
+        let token = transaction(TransactionMeta {
+            err: None,
+            fee: 5000,
+            pre_balances: vec![5000, 0, 0, 0, 1],
+            post_balances: vec![0, 0, 0, 0, 1],
+            pre_token_balances: Some(vec![token_balance(2, "Alice", 10_000_000)]),
+            post_token_balances: Some(vec![
+                token_balance(2, "Alice", 7_500_000),
+                token_balance(3, "Bob", 2_500_000),
+            ]),
+            loaded_addresses: None,
+            log_messages: None,
+        });
+        let entry = history_entry("Bob", &info, &token);
+        assert_eq!(entry.direction, TransferDirection::Incoming);
+        assert_eq!(entry.token.as_deref(), Some("Gem"));
+        assert_eq!(entry.amount, 2_500_000);
+        assert_eq!(entry.decimals, 6);
+        assert_eq!(entry.counterparty.as_deref(), Some("Alice"));
+    }
+}
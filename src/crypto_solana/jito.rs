@@ -0,0 +1,294 @@
+/// Jito bundle submission (https://docs.jito.wtf/lowlatencytxnsend/)
+/// A bundle carries a tip to the validator that lands it, which keeps
+/// transactions landing while regular sends are dropped during congestion
+use super::dto::{Commitment, JitoSettings};
+use super::helper::send_solana_rpc_request;
+use super::transactions::{
+    build_transfer_sol_instruction, decode_pubkey, send_transaction, TransactionInstruction,
+};
+use crate::{IdosError, IdosResult};
+use base64::Engine;
+
+/// Tip accounts published by Jito (getTipAccounts)
+pub const JITO_TIP_ACCOUNTS: [&str; 8] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+
+/// Smallest tip the block engine accepts
+pub const MIN_TIP_LAMPORTS: u64 = 1_000;
+
+/// Build the tip transfer of a bundle
+/// A random tip account is used unless one is configured, which spreads write locks
+pub fn build_tip_instruction(
+    payer: &[u8; 32],
+    settings: &JitoSettings,
+) -> IdosResult<TransactionInstruction> {
+    if settings.tip_lamports < MIN_TIP_LAMPORTS {
+        return Err(IdosError::ConfigurationError(format!(
+            "Jito tip must be at least {} lamports",
+            MIN_TIP_LAMPORTS
+        )));
+    }
+
+    let tip_account = match &settings.tip_account {
+        Some(account) => account.as_str(),
+        None => {
+            let index = uuid::Uuid::new_v4().as_bytes()[0] as usize % JITO_TIP_ACCOUNTS.len();
+            JITO_TIP_ACCOUNTS[index]
+        }
+    };
+
+    Ok(build_transfer_sol_instruction(
+        payer,
+        &decode_pubkey(tip_account)?,
+        settings.tip_lamports,
+    ))
+}
+
+/// First signature of a signed, base64 encoded transaction (base58)
+/// Bundles are tracked by ID, but callers confirm transactions by signature
+pub fn transaction_signature(transaction_base64: &str) -> IdosResult<String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(transaction_base64)
+        .map_err(|e| IdosError::SerializationError(format!("Invalid transaction: {}", e)))?;
+
+    // The signature count is a compact-u16, one byte below 128 signatures
+    let count_len = bytes
+        .iter()
+        .take(3)
+        .position(|b| b & 0x80 == 0)
+        .unwrap_or(0)
+        + 1;
+    let signature = bytes
+        .get(count_len..count_len + 64)
+        .ok_or_else(|| IdosError::SerializationError("Transaction has no signature".to_string()))?;
+
+    Ok(bs58::encode(signature).into_string())
+}
+
+/// Send signed, base64 encoded transactions as one bundle (at most 5, executed in order)
+/// Returns the bundle ID
+pub async fn send_bundle(block_engine_url: &str, transactions: &[String]) -> IdosResult<String> {
+    if transactions.is_empty() || transactions.len() > 5 {
+        return Err(IdosError::InvalidInput(format!(
+            "Bundles hold 1 to 5 transactions, got {}",
+            transactions.len()
+        )));
+    }
+
+    let params = serde_json::json!([transactions, { "encoding": "base64" }]);
+    send_solana_rpc_request(block_engine_url, "sendBundle", params).await
+}
+
+/// Whether the block engine explicitly refused a bundle (a JSON-RPC error or an HTTP 4xx)
+/// Timeouts, dropped connections, 5xx and unreadable answers don't tell whether it was taken
+pub fn is_bundle_rejected(error: &IdosError) -> bool {
+    let IdosError::NetworkError(message) = error else {
+        return false;
+    };
+    if message.starts_with("Solana RPC Error:") {
+        return true;
+    }
+
+    message
+        .strip_prefix("HTTP ")
+        .and_then(|status| status.parse::<u16>().ok())
+        .is_some_and(|status| (400..500).contains(&status))
+}
+
+/// Send a signed, tipped transaction as a single-transaction bundle
+/// Returns `Ok(None)` when the block engine rejected the bundle, so it can't land through it.
+/// When the outcome is unclear the bundle may still land, so the same transaction is sent
+/// through `rpc_url`: both carry one signature, so it lands at most once
+pub async fn send_tipped_transaction(
+    block_engine_url: &str,
+    rpc_url: &str,
+    signed_tx: &str,
+    commitment: Commitment,
+) -> IdosResult<Option<String>> {
+    let signature = transaction_signature(signed_tx)?;
+
+    match send_bundle(block_engine_url, &[signed_tx.to_string()]).await {
+        Ok(bundle_id) => {
+            log::debug!(
+                "Sent transaction {} in Jito bundle {}",
+                signature,
+                bundle_id
+            );
+            Ok(Some(signature))
+        }
+        Err(e) if is_bundle_rejected(&e) => {
+            log::warn!("Jito bundle rejected: {}", e);
+            Ok(None)
+        }
+        Err(e) => {
+            log::warn!(
+                "Jito bundle outcome unknown, sending transaction {} through RPC: {}",
+                signature,
+                e
+            );
+            // Preflight would fail once the bundle has landed
+            send_transaction(rpc_url, signed_tx, true, commitment).await?;
+            Ok(Some(signature))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tip_instruction_and_signature() {
+        for account in JITO_TIP_ACCOUNTS {
+            assert!(decode_pubkey(account).is_ok());
+        }
+
+        let payer = [1u8; 32];
+        let settings = JitoSettings {
+            tip_account: Some(JITO_TIP_ACCOUNTS[2].to_string()),
+            ..Default::default()
+        };
+
+        let tip = build_tip_instruction(&payer, &settings).unwrap();
+        assert_eq!(
+            tip.accounts[1].pubkey,
+            decode_pubkey(JITO_TIP_ACCOUNTS[2]).unwrap()
+        );
+        assert_eq!(tip.data[4..], settings.tip_lamports.to_le_bytes());
+
+        let too_small = JitoSettings {
+            tip_lamports: 1,
+            ..Default::default()
+        };
+        assert!(build_tip_instruction(&payer, &too_small).is_err());
+
+        // One signature followed by the message
+        let mut transaction = vec![1u8];
+        transaction.extend_from_slice(&[9u8; 64]);
+        transaction.extend_from_slice(&[0u8; 10]);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&transaction);
+        assert_eq!(
+            transaction_signature(&encoded).unwrap(),
+            bs58::encode([9u8; 64]).into_string()
+        );
+    }
+
+    /// Answer one connection per canned response (`None` drops it unanswered)
+    /// and collect the request bodies
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn serve(
+        responses: Vec<Option<(u16, String)>>,
+    ) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut bodies = Vec::new();
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                let body = loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                line.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break body.to_string();
+                        }
+                    }
+                };
+                bodies.push(body);
+
+                if let Some((status, body)) = response {
+                    let answer = format!(
+                        "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    socket.write_all(answer.as_bytes()).await.unwrap();
+                }
+            }
+            bodies
+        });
+
+        (url, handle)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn test_send_tipped_transaction() {
+        let mut transaction = vec![1u8];
+        transaction.extend_from_slice(&[7u8; 64]);
+        transaction.extend_from_slice(&[0u8; 10]);
+        let signed_tx = base64::engine::general_purpose::STANDARD.encode(&transaction);
+        let signature = bs58::encode([7u8; 64]).into_string();
+
+        assert!(is_bundle_rejected(&IdosError::NetworkError(
+            "Solana RPC Error: bundle rejected".to_string()
+        )));
+        assert!(is_bundle_rejected(&IdosError::NetworkError(
+            "HTTP 400".to_string()
+        )));
+        assert!(!is_bundle_rejected(&IdosError::NetworkError(
+            "error sending request: operation timed out".to_string()
+        )));
+        assert!(!is_bundle_rejected(&IdosError::NetworkError(
+            "HTTP 503".to_string()
+        )));
+        assert!(!is_bundle_rejected(&IdosError::SerializationError(
+            "expected value".to_string()
+        )));
+
+        // Rejected, by a JSON-RPC error or a bare 4xx: nothing is sent through RPC
+        for rejection in [
+            (
+                200,
+                r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"bundle rejected"}}"#,
+            ),
+            (400, "Bad Request"),
+        ] {
+            let rejection = (rejection.0, rejection.1.to_string());
+            let (engine, engine_requests) = serve(vec![Some(rejection)]).await;
+            let (rpc, rpc_requests) = serve(Vec::new()).await;
+            let sent = send_tipped_transaction(&engine, &rpc, &signed_tx, Commitment::default())
+                .await
+                .unwrap();
+            assert_eq!(sent, None);
+            assert_eq!(engine_requests.await.unwrap().len(), 1);
+            assert!(rpc_requests.await.unwrap().is_empty());
+        }
+
+        // No answer (the block engine may have taken the bundle): the same transaction goes
+        // through RPC, so it can't land twice
+        let (engine, engine_requests) = serve(vec![None]).await;
+        let rpc_answer = format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#, signature);
+        let (rpc, rpc_requests) = serve(vec![Some((200, rpc_answer))]).await;
+        let sent = send_tipped_transaction(&engine, &rpc, &signed_tx, Commitment::default())
+            .await
+            .unwrap();
+        assert_eq!(sent, Some(signature));
+        assert!(engine_requests.await.unwrap()[0].contains(&signed_tx));
+        let rpc_requests = rpc_requests.await.unwrap();
+        assert!(rpc_requests[0].contains("sendTransaction"));
+        assert!(rpc_requests[0].contains(&signed_tx));
+    }
+}
@@ -1,17 +1,33 @@
 /// Solana wallet integration module
 pub mod anchor;
+pub mod das;
 pub mod dto;
 pub mod handler;
 mod helper;
+pub mod history;
+pub mod jito;
+pub mod mpl_core;
 pub mod nft;
+pub mod nft_cache;
+pub mod program_accounts;
+pub mod rpc;
 pub mod service;
+pub mod sign_in;
+pub mod sns;
+pub mod solana_pay;
 pub mod solana_plugin;
+pub mod subscription;
+pub mod token_mint;
 pub mod transactions;
+#[cfg(target_arch = "wasm32")]
+mod wallet_adapter;
 
 pub use anchor::*;
 pub use dto::*;
 pub use handler::SolanaHandler;
-pub use nft::{load_nft_metadata, load_nfts_by_owner};
+pub use nft::{load_nft_metadata, load_nfts_by_owner, load_onchain_metadata};
+pub use nft_cache::{NftImages, NftMetadataCache};
 pub use service::SolanaPlatformPoolService;
+pub use sign_in::{SolanaMessageSigner, SolanaSignInMessage};
 pub use solana_plugin::SolanaPlugin;
 pub use transactions::*;
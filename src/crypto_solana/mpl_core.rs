@@ -0,0 +1,162 @@
+/// Metaplex Core (mpl-core) assets
+/// A Core asset is a single account owned by the Core program - no mint, token account or metadata PDA
+use super::dto::*;
+use super::program_accounts::get_program_accounts_with;
+use super::transactions::MPL_CORE_PROGRAM_ID;
+use crate::{IdosError, IdosResult};
+
+/// Account key of AssetV1 accounts (first byte of the account data)
+const ASSET_V1_KEY: u8 = 1;
+
+/// Offset of the owner in an AssetV1 account
+const OWNER_OFFSET: usize = 1;
+
+/// Read `len` bytes at `offset` and advance it
+fn take<'a>(data: &'a [u8], offset: &mut usize, len: usize) -> IdosResult<&'a [u8]> {
+    let bytes = data
+        .get(*offset..*offset + len)
+        .ok_or_else(|| IdosError::SerializationError("Core asset data too short".to_string()))?;
+    *offset += len;
+    Ok(bytes)
+}
+
+fn read_pubkey(data: &[u8], offset: &mut usize) -> IdosResult<String> {
+    Ok(bs58::encode(take(data, offset, 32)?).into_string())
+}
+
+/// Borsh string: u32 length followed by UTF-8 bytes
+fn read_string(data: &[u8], offset: &mut usize) -> IdosResult<String> {
+    let len = u32::from_le_bytes(take(data, offset, 4)?.try_into().unwrap()) as usize;
+    String::from_utf8(take(data, offset, len)?.to_vec())
+        .map_err(|e| IdosError::SerializationError(format!("Invalid Core asset string: {}", e)))
+}
+
+/// Parse an AssetV1 account
+pub fn parse_core_asset(address: &str, data: &[u8]) -> IdosResult<CoreAsset> {
+    let mut offset = 0;
+
+    if take(data, &mut offset, 1)?[0] != ASSET_V1_KEY {
+        return Err(IdosError::InvalidInput(format!(
+            "{} is not a Metaplex Core asset",
+            address
+        )));
+    }
+
+    let owner = read_pubkey(data, &mut offset)?;
+    let update_authority = match take(data, &mut offset, 1)?[0] {
+        0 => CoreUpdateAuthority::None,
+        1 => CoreUpdateAuthority::Address(read_pubkey(data, &mut offset)?),
+        2 => CoreUpdateAuthority::Collection(read_pubkey(data, &mut offset)?),
+        tag => {
+            return Err(IdosError::SerializationError(format!(
+                "Unknown Core update authority: {}",
+                tag
+            )))
+        }
+    };
+    let name = read_string(data, &mut offset)?;
+    let uri = read_string(data, &mut offset)?;
+
+    Ok(CoreAsset {
+        address: address.to_string(),
+        owner,
+        update_authority,
+        name,
+        uri,
+    })
+}
+
+/// Get a single Core asset
+pub async fn get_core_asset(rpc_url: &str, address: &str) -> IdosResult<CoreAsset> {
+    let data = super::nft::get_account_data(rpc_url, address).await?;
+    parse_core_asset(address, &data)
+}
+
+/// Get every Core asset owned by a wallet (getProgramAccounts on the Core program)
+pub async fn get_core_assets_by_owner(
+    rpc_url: &str,
+    owner_address: &str,
+) -> IdosResult<Vec<CoreAsset>> {
+    let filters = [
+        AccountFilter::Memcmp {
+            offset: 0,
+            bytes: vec![ASSET_V1_KEY],
+        },
+        AccountFilter::memcmp_pubkey(OWNER_OFFSET, owner_address)?,
+    ];
+    let accounts = get_program_accounts_with(
+        rpc_url,
+        MPL_CORE_PROGRAM_ID,
+        &filters,
+        Commitment::Confirmed,
+        parse_core_asset,
+    )
+    .await?;
+
+    Ok(accounts.into_iter().map(|account| account.data).collect())
+}
+
+/// Convert a Core asset into the SDK's NFT metadata (`token_standard` is "MplCoreAsset")
+pub fn core_asset_metadata(asset: &CoreAsset) -> NftMetadata {
+    let (update_authority, collection) = match &asset.update_authority {
+        CoreUpdateAuthority::None => (String::new(), None),
+        CoreUpdateAuthority::Address(address) => (address.clone(), None),
+        CoreUpdateAuthority::Collection(collection) => (
+            collection.clone(),
+            Some(NftCollection {
+                verified: true,
+                key: collection.clone(),
+            }),
+        ),
+    };
+
+    NftMetadata {
+        mint: asset.address.clone(),
+        name: asset.name.clone(),
+        symbol: String::new(),
+        uri: asset.uri.clone(),
+        seller_fee_basis_points: 0,
+        creators: None,
+        primary_sale_happened: false,
+        is_mutable: asset.update_authority != CoreUpdateAuthority::None,
+        update_authority,
+        collection,
+        uses: None,
+        token_standard: Some("MplCoreAsset".to_string()),
+        rule_set: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_core_asset() {
+        let mut data = vec![ASSET_V1_KEY];
+        data.extend_from_slice(&[3u8; 32]); // owner
+        data.push(2); // UpdateAuthority::Collection
+        data.extend_from_slice(&[4u8; 32]);
+        data.extend_from_slice(&5u32.to_le_bytes());
+        data.extend_from_slice(b"Sword");
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(b"uri");
+        data.push(0); // seq: None, plugins follow
+
+        let asset = parse_core_asset("Asset", &data).unwrap();
+        assert_eq!(asset.owner, bs58::encode([3u8; 32]).into_string());
+        assert_eq!(
+            asset.update_authority,
+            CoreUpdateAuthority::Collection(bs58::encode([4u8; 32]).into_string())
+        );
+        assert_eq!(asset.name, "Sword");
+        assert_eq!(asset.uri, "uri");
+
+        let metadata = core_asset_metadata(&asset);
+        assert_eq!(metadata.token_standard.as_deref(), Some("MplCoreAsset"));
+        assert!(metadata.collection.is_some());
+
+        data[0] = 5; // CollectionV1
+        assert!(parse_core_asset("Collection", &data).is_err());
+    }
+}
@@ -1,10 +1,18 @@
 /// NFT loading with Metaplex Token Metadata
 use super::dto::*;
+use super::nft_cache::NftMetadataCache;
 use crate::{IdosError, IdosResult};
+use futures_util::{stream, StreamExt};
 use serde_json;
 
+/// Accounts per `getMultipleAccounts` call (RPC limit)
+const MULTIPLE_ACCOUNTS_BATCH: usize = 100;
+
+/// Off-chain JSON metadata requests in flight at once
+const JSON_FETCH_CONCURRENCY: usize = 8;
+
 #[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
-use mpl_token_metadata::accounts::Metadata;
+use mpl_token_metadata::{accounts::Metadata, types::ProgrammableConfig};
 
 /// Get the Metaplex metadata PDA (Program Derived Address) for a mint
 #[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
@@ -62,36 +70,89 @@ pub fn parse_metadata_account(data: &[u8]) -> IdosResult<NftMetadata> {
             remaining: u.remaining,
             total: u.total,
         }),
+        token_standard: metadata.token_standard.map(|t| format!("{:?}", t)),
+        rule_set: metadata
+            .programmable_config
+            .and_then(|config| match config {
+                ProgrammableConfig::V1 { rule_set } => rule_set.map(|r| r.to_string()),
+            }),
     })
 }
 
 /// Load NFTs for a wallet using RPC (WASM compatible via RPC)
+/// Metadata accounts are fetched in batches and JSON metadata concurrently
 pub async fn load_nfts_by_owner(rpc_url: &str, owner_address: &str) -> IdosResult<NftLoadResult> {
+    load_nfts_by_owner_cached(rpc_url, owner_address, None).await
+}
+
+/// `load_nfts_by_owner`, reading and filling `cache` when given
+pub(crate) async fn load_nfts_by_owner_cached(
+    rpc_url: &str,
+    owner_address: &str,
+    cache: Option<&NftMetadataCache>,
+) -> IdosResult<NftLoadResult> {
     // Get all token accounts owned by this wallet
     let token_accounts = get_token_accounts_by_owner(rpc_url, owner_address).await?;
 
-    let mut nfts = Vec::new();
-
-    for account in token_accounts {
-        // Check if this is an NFT (amount = 1, decimals = 0)
-        if let Some(ui_amount) = account.token_amount.ui_amount {
-            if ui_amount == 1.0 && account.token_amount.decimals == 0 {
-                // This is likely an NFT
-                match load_nft_metadata(rpc_url, &account.mint, owner_address).await {
-                    Ok(nft) => nfts.push(nft),
-                    Err(e) => {
-                        // Log error but continue with other NFTs
-                        log::warn!(
-                            "Failed to load NFT metadata for mint {}: {}",
-                            account.mint,
-                            e
-                        );
-                    }
+    // NFTs hold exactly one token with 0 decimals
+    let mints: Vec<String> = token_accounts
+        .into_iter()
+        .filter(|account| {
+            account.token_amount.ui_amount == Some(1.0) && account.token_amount.decimals == 0
+        })
+        .map(|account| account.mint)
+        .collect();
+
+    let mut onchain = Vec::new();
+    let mut uncached = Vec::new();
+    for mint in mints {
+        match cache.and_then(|cache| cache.get_onchain(&mint)) {
+            Some(metadata) => onchain.push(metadata),
+            None => uncached.push(mint),
+        }
+    }
+
+    let mut metadata_addresses = Vec::with_capacity(uncached.len());
+    for mint in &uncached {
+        metadata_addresses.push(metadata_address(mint)?);
+    }
+    let accounts = get_multiple_account_data(rpc_url, &metadata_addresses).await?;
+
+    for (mint, data) in uncached.iter().zip(accounts) {
+        // Log errors but continue with other NFTs
+        match data.map(|data| parse_metadata(&data)) {
+            Some(Ok(metadata)) => {
+                if let Some(cache) = cache {
+                    cache.put_onchain(&metadata);
                 }
+                onchain.push(metadata);
             }
+            Some(Err(e)) => log::warn!("Failed to load NFT metadata for mint {}: {}", mint, e),
+            None => log::warn!(
+                "Failed to load NFT metadata for mint {}: account not found",
+                mint
+            ),
         }
     }
 
+    // Metaplex Core assets are plain accounts of the Core program
+    match super::mpl_core::get_core_assets_by_owner(rpc_url, owner_address).await {
+        Ok(assets) => onchain.extend(assets.iter().map(super::mpl_core::core_asset_metadata)),
+        Err(e) => log::debug!("Skipping Metaplex Core assets: {}", e),
+    }
+
+    let mut nfts: Vec<Nft> = stream::iter(onchain)
+        .map(|metadata| with_json_metadata(metadata, owner_address, cache))
+        .buffered(JSON_FETCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    // Compressed NFTs only exist in the merkle tree - they're indexed by DAS-capable RPCs
+    match super::das::get_compressed_assets_by_owner(rpc_url, owner_address).await {
+        Ok(assets) => nfts.extend(assets.into_iter().map(super::das::das_asset_to_nft)),
+        Err(e) => log::debug!("Skipping compressed NFTs (DAS unavailable): {}", e),
+    }
+
     Ok(NftLoadResult {
         count: nfts.len(),
         nfts,
@@ -143,7 +204,7 @@ async fn get_token_accounts_by_owner(
     Ok(result
         .value
         .into_iter()
-        .map(|v| v.account.data.parsed)
+        .map(|v| v.account.data.parsed.info)
         .collect())
 }
 
@@ -153,27 +214,49 @@ pub async fn load_nft_metadata(
     mint_address: &str,
     owner_address: &str,
 ) -> IdosResult<Nft> {
-    // Get metadata PDA
-    #[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
-    let metadata_address = get_metadata_pda(mint_address)?;
-
-    #[cfg(not(all(feature = "crypto_solana", not(target_arch = "wasm32"))))]
-    let metadata_address = derive_metadata_pda_string(mint_address)?;
+    load_nft_metadata_cached(rpc_url, mint_address, owner_address, None).await
+}
 
-    // Get account data from RPC
-    let account_data = get_account_data(rpc_url, &metadata_address).await?;
+/// `load_nft_metadata`, reading and filling `cache` when given
+pub(crate) async fn load_nft_metadata_cached(
+    rpc_url: &str,
+    mint_address: &str,
+    owner_address: &str,
+    cache: Option<&NftMetadataCache>,
+) -> IdosResult<Nft> {
+    let metadata = match cache.and_then(|cache| cache.get_onchain(mint_address)) {
+        Some(metadata) => metadata,
+        None => {
+            let metadata = load_onchain_metadata(rpc_url, mint_address).await?;
+            if let Some(cache) = cache {
+                cache.put_onchain(&metadata);
+            }
+            metadata
+        }
+    };
 
-    // Parse metadata
-    #[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
-    let metadata = parse_metadata_account(&account_data)?;
+    Ok(with_json_metadata(metadata, owner_address, cache).await)
+}
 
-    #[cfg(not(all(feature = "crypto_solana", not(target_arch = "wasm32"))))]
-    let metadata = parse_metadata_from_raw(&account_data)?;
+/// Attach the off-chain JSON metadata (best effort) to on-chain metadata
+async fn with_json_metadata(
+    metadata: NftMetadata,
+    owner_address: &str,
+    cache: Option<&NftMetadataCache>,
+) -> Nft {
+    let cached = cache.and_then(|cache| cache.get_json(&metadata.mint, &metadata.uri));
 
     // Fetch JSON metadata from URI
-    let json_metadata = if !metadata.uri.is_empty() {
+    let json_metadata = if cached.is_some() {
+        cached
+    } else if !metadata.uri.is_empty() {
         match fetch_json_metadata(&metadata.uri).await {
-            Ok(json) => Some(json),
+            Ok(json) => {
+                if let Some(cache) = cache {
+                    cache.put_json(&metadata.mint, &metadata.uri, &json);
+                }
+                Some(json)
+            }
             Err(e) => {
                 log::warn!("Failed to fetch JSON metadata from {}: {}", metadata.uri, e);
                 None
@@ -183,15 +266,85 @@ pub async fn load_nft_metadata(
         None
     };
 
-    Ok(Nft {
+    Nft {
         metadata,
         json_metadata,
         owner: owner_address.to_string(),
-    })
+        compressed: false,
+    }
+}
+
+/// Load only the on-chain Metaplex metadata account for a mint
+pub async fn load_onchain_metadata(rpc_url: &str, mint_address: &str) -> IdosResult<NftMetadata> {
+    // Get metadata PDA
+    let metadata_address = metadata_address(mint_address)?;
+
+    // Get account data from RPC
+    let account_data = get_account_data(rpc_url, &metadata_address).await?;
+
+    parse_metadata(&account_data)
+}
+
+/// Metadata PDA of a mint
+fn metadata_address(mint_address: &str) -> IdosResult<String> {
+    #[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
+    {
+        get_metadata_pda(mint_address)
+    }
+
+    #[cfg(not(all(feature = "crypto_solana", not(target_arch = "wasm32"))))]
+    {
+        derive_metadata_pda_string(mint_address)
+    }
+}
+
+/// Parse a metadata account
+fn parse_metadata(account_data: &[u8]) -> IdosResult<NftMetadata> {
+    #[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
+    {
+        parse_metadata_account(account_data)
+    }
+
+    #[cfg(not(all(feature = "crypto_solana", not(target_arch = "wasm32"))))]
+    {
+        parse_metadata_from_raw(account_data)
+    }
+}
+
+/// Get the data of many accounts with `getMultipleAccounts`
+/// Entries are None for accounts that don't exist
+pub(crate) async fn get_multiple_account_data(
+    rpc_url: &str,
+    addresses: &[String],
+) -> IdosResult<Vec<Option<Vec<u8>>>> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let mut accounts = Vec::with_capacity(addresses.len());
+
+    for batch in addresses.chunks(MULTIPLE_ACCOUNTS_BATCH) {
+        let params = serde_json::json!([batch, { "encoding": "base64" }]);
+        let response: MultipleAccountsResponse =
+            super::helper::send_solana_rpc_request(rpc_url, "getMultipleAccounts", params).await?;
+
+        for account in response.value {
+            let data = account
+                .map(|account| {
+                    general_purpose::STANDARD
+                        .decode(&account.data.0)
+                        .map_err(|e| {
+                            IdosError::SerializationError(format!("Failed to decode base64: {}", e))
+                        })
+                })
+                .transpose()?;
+            accounts.push(data);
+        }
+    }
+
+    Ok(accounts)
 }
 
 /// Get account data from RPC
-async fn get_account_data(rpc_url: &str, address: &str) -> IdosResult<Vec<u8>> {
+pub(crate) async fn get_account_data(rpc_url: &str, address: &str) -> IdosResult<Vec<u8>> {
     let client = reqwest::Client::new();
 
     let request = serde_json::json!({
@@ -251,14 +404,7 @@ async fn get_account_data(rpc_url: &str, address: &str) -> IdosResult<Vec<u8>> {
 
 /// Fetch JSON metadata from URI (IPFS, Arweave, etc.)
 async fn fetch_json_metadata(uri: &str) -> IdosResult<NftJsonMetadata> {
-    // Convert IPFS/Arweave URIs to HTTP gateways
-    let http_uri = if uri.starts_with("ipfs://") {
-        format!("https://ipfs.io/ipfs/{}", &uri[7..])
-    } else if uri.starts_with("ar://") {
-        format!("https://arweave.net/{}", &uri[5..])
-    } else {
-        uri.to_string()
-    };
+    let http_uri = gateway_url(uri);
 
     let client = reqwest::Client::new();
     let response = client
@@ -275,6 +421,17 @@ async fn fetch_json_metadata(uri: &str) -> IdosResult<NftJsonMetadata> {
     Ok(json)
 }
 
+/// Convert IPFS/Arweave URIs to HTTP gateways
+pub(crate) fn gateway_url(uri: &str) -> String {
+    if let Some(path) = uri.strip_prefix("ipfs://") {
+        format!("https://ipfs.io/ipfs/{}", path)
+    } else if let Some(path) = uri.strip_prefix("ar://") {
+        format!("https://arweave.net/{}", path)
+    } else {
+        uri.to_string()
+    }
+}
+
 /// Derive Metaplex metadata PDA without solana-sdk (WASM fallback)
 #[cfg(not(all(feature = "crypto_solana", not(target_arch = "wasm32"))))]
 fn derive_metadata_pda_string(mint_address: &str) -> IdosResult<String> {
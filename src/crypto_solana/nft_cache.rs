@@ -0,0 +1,163 @@
+/// Persistent NFT metadata cache and image pre-fetching
+/// Re-opening an NFT inventory reads metadata from `Storage` instead of the RPC and IPFS gateways
+use super::dto::{Nft, NftJsonMetadata, NftMetadata};
+use crate::storage::Storage;
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Cache entry with the time it was written (unix seconds)
+#[derive(Serialize, Deserialize)]
+struct CachedEntry<T> {
+    cached_at: i64,
+    value: T,
+}
+
+/// On-chain metadata keyed by mint, JSON metadata keyed by mint + URI hash
+#[derive(Clone)]
+pub struct NftMetadataCache {
+    storage: Storage,
+    ttl: Duration,
+}
+
+impl NftMetadataCache {
+    pub fn new(storage: Storage, ttl: Duration) -> Self {
+        Self { storage, ttl }
+    }
+
+    /// Cached on-chain metadata of a mint
+    pub fn get_onchain(&self, mint: &str) -> Option<NftMetadata> {
+        self.read(&Self::onchain_key(mint))
+    }
+
+    pub fn put_onchain(&self, metadata: &NftMetadata) {
+        self.write(&Self::onchain_key(&metadata.mint), metadata);
+    }
+
+    /// Cached JSON metadata of a mint, valid only while its URI is unchanged
+    pub fn get_json(&self, mint: &str, uri: &str) -> Option<NftJsonMetadata> {
+        self.read(&Self::json_key(mint, uri))
+    }
+
+    pub fn put_json(&self, mint: &str, uri: &str, json: &NftJsonMetadata) {
+        self.write(&Self::json_key(mint, uri), json);
+    }
+
+    /// Drop every cached entry
+    pub fn clear(&self) {
+        self.storage.clear().ok();
+    }
+
+    fn onchain_key(mint: &str) -> String {
+        format!("onchain_{}", mint)
+    }
+
+    fn json_key(mint: &str, uri: &str) -> String {
+        let uri_hash = hex::encode(&Sha256::digest(uri.as_bytes())[..8]);
+        format!("json_{}_{}", mint, uri_hash)
+    }
+
+    fn read<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let json = self.storage.get(key).ok().flatten()?;
+        let entry: CachedEntry<T> = serde_json::from_str(&json).ok()?;
+
+        let age = chrono::Utc::now().timestamp() - entry.cached_at;
+        if age < 0 || age as u64 > self.ttl.as_secs() {
+            self.storage.remove(key).ok();
+            return None;
+        }
+
+        Some(entry.value)
+    }
+
+    fn write<T: Serialize>(&self, key: &str, value: &T) {
+        let entry = CachedEntry {
+            cached_at: chrono::Utc::now().timestamp(),
+            value,
+        };
+
+        // Cache write failures only cost a refetch
+        if let Ok(json) = serde_json::to_string(&entry) {
+            self.storage.set(key, &json).ok();
+        }
+    }
+}
+
+/// NFT images loaded through Bevy's asset server, keyed by mint
+/// Loading http(s) URLs requires Bevy's `https` feature
+#[derive(Resource, Default)]
+pub struct NftImages {
+    handles: HashMap<String, Handle<Image>>,
+}
+
+impl NftImages {
+    /// Start loading the images of `nfts` that aren't loaded yet
+    pub fn prefetch(&mut self, asset_server: &AssetServer, nfts: &[Nft]) {
+        for nft in nfts {
+            if self.handles.contains_key(&nft.metadata.mint) {
+                continue;
+            }
+
+            let Some(image) = nft.json_metadata.as_ref().and_then(|j| j.image.as_deref()) else {
+                continue;
+            };
+
+            let handle = asset_server.load(super::nft::gateway_url(image));
+            self.handles.insert(nft.metadata.mint.clone(), handle);
+        }
+    }
+
+    /// Image handle of an NFT, once prefetched
+    pub fn get(&self, mint: &str) -> Option<Handle<Image>> {
+        self.handles.get(mint).cloned()
+    }
+
+    /// Release every image handle
+    pub fn clear(&mut self) {
+        self.handles.clear();
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nft_cache_round_trip_and_expiry() {
+        let dir = std::env::temp_dir().join(format!("idos_nft_cache_test_{}", std::process::id()));
+        let storage = Storage::with_directory("nft_".to_string(), dir.clone());
+        let json = NftJsonMetadata {
+            name: "Sword".to_string(),
+            symbol: "ITEM".to_string(),
+            description: None,
+            image: Some("ipfs://image".to_string()),
+            animation_url: None,
+            external_url: None,
+            attributes: None,
+            properties: None,
+        };
+
+        let cache = NftMetadataCache::new(storage.clone(), Duration::from_secs(60));
+        cache.put_json("Mint1", "ipfs://a", &json);
+        assert_eq!(cache.get_json("Mint1", "ipfs://a").unwrap().name, "Sword");
+        // A new URI means the metadata moved
+        assert!(cache.get_json("Mint1", "ipfs://b").is_none());
+
+        // Entries older than the TTL are dropped
+        let expired = CachedEntry {
+            cached_at: chrono::Utc::now().timestamp() - 120,
+            value: &json,
+        };
+        storage
+            .set(
+                &NftMetadataCache::json_key("Mint2", "ipfs://a"),
+                &serde_json::to_string(&expired).unwrap(),
+            )
+            .unwrap();
+        assert!(cache.get_json("Mint2", "ipfs://a").is_none());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,214 @@
+/// Typed `getProgramAccounts` queries
+/// Games use these to load their own program state (player accounts, match PDAs)
+use super::anchor::anchor_account_discriminator;
+use super::dto::*;
+use super::helper::send_solana_rpc_request;
+use super::transactions::decode_pubkey;
+use crate::{IdosError, IdosResult};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::BorshDeserialize;
+
+/// Longest byte string a memcmp filter accepts
+const MAX_MEMCMP_LEN: usize = 128;
+
+impl AccountFilter {
+    /// Match a public key at `offset`, e.g. the owner field of a player account
+    pub fn memcmp_pubkey(offset: usize, address: &str) -> IdosResult<Self> {
+        Ok(AccountFilter::Memcmp {
+            offset,
+            bytes: decode_pubkey(address)?.to_vec(),
+        })
+    }
+
+    /// Match Anchor accounts of one type by their discriminator
+    pub fn anchor_account(account_name: &str) -> Self {
+        AccountFilter::Memcmp {
+            offset: 0,
+            bytes: anchor_account_discriminator(account_name).to_vec(),
+        }
+    }
+
+    fn to_json(&self) -> IdosResult<serde_json::Value> {
+        match self {
+            AccountFilter::Memcmp { bytes, .. } if bytes.len() > MAX_MEMCMP_LEN => {
+                Err(IdosError::InvalidInput(format!(
+                    "memcmp filters compare at most {} bytes, got {}",
+                    MAX_MEMCMP_LEN,
+                    bytes.len()
+                )))
+            }
+            AccountFilter::Memcmp { offset, bytes } => Ok(serde_json::json!({
+                "memcmp": { "offset": offset, "bytes": bs58::encode(bytes).into_string() }
+            })),
+            AccountFilter::DataSize(size) => Ok(serde_json::json!({ "dataSize": size })),
+        }
+    }
+}
+
+/// Decode Borsh account data; trailing bytes (padding, reserved space) are ignored
+pub fn decode_borsh<T: BorshDeserialize>(data: &[u8]) -> IdosResult<T> {
+    T::deserialize(&mut &data[..])
+        .map_err(|e| IdosError::SerializationError(format!("Invalid account data: {}", e)))
+}
+
+/// Decode an Anchor account after checking its discriminator
+pub fn decode_anchor_account<T: BorshDeserialize>(
+    account_name: &str,
+    data: &[u8],
+) -> IdosResult<T> {
+    match data.split_at_checked(8) {
+        Some((discriminator, rest))
+            if discriminator == anchor_account_discriminator(account_name) =>
+        {
+            decode_borsh(rest)
+        }
+        _ => Err(IdosError::InvalidInput(format!(
+            "Account is not a {}",
+            account_name
+        ))),
+    }
+}
+
+/// Accounts owned by `program_id` that match every filter, with raw data
+pub async fn get_program_accounts(
+    rpc_url: &str,
+    program_id: &str,
+    filters: &[AccountFilter],
+    commitment: Commitment,
+) -> IdosResult<Vec<DecodedAccount<Vec<u8>>>> {
+    let filters = filters
+        .iter()
+        .map(AccountFilter::to_json)
+        .collect::<IdosResult<Vec<_>>>()?;
+    let params = serde_json::json!([
+        program_id,
+        {
+            "encoding": "base64",
+            "commitment": commitment,
+            "filters": filters
+        }
+    ]);
+    let accounts: Vec<ProgramAccount> =
+        send_solana_rpc_request(rpc_url, "getProgramAccounts", params).await?;
+
+    accounts
+        .into_iter()
+        .map(|account| {
+            let data = general_purpose::STANDARD
+                .decode(&account.account.data.0)
+                .map_err(|e| {
+                    IdosError::SerializationError(format!("Failed to decode base64: {}", e))
+                })?;
+            Ok(DecodedAccount {
+                pubkey: account.pubkey,
+                lamports: account.account.lamports,
+                data,
+            })
+        })
+        .collect()
+}
+
+/// Accounts owned by `program_id`, decoded with `decode`
+/// Accounts that fail to decode are skipped
+pub async fn get_program_accounts_with<T>(
+    rpc_url: &str,
+    program_id: &str,
+    filters: &[AccountFilter],
+    commitment: Commitment,
+    decode: impl Fn(&str, &[u8]) -> IdosResult<T>,
+) -> IdosResult<Vec<DecodedAccount<T>>> {
+    let accounts = get_program_accounts(rpc_url, program_id, filters, commitment).await?;
+
+    let mut decoded = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        match decode(&account.pubkey, &account.data) {
+            Ok(data) => decoded.push(DecodedAccount {
+                pubkey: account.pubkey,
+                lamports: account.lamports,
+                data,
+            }),
+            Err(e) => log::warn!("Failed to decode account {}: {}", account.pubkey, e),
+        }
+    }
+
+    Ok(decoded)
+}
+
+/// Accounts owned by `program_id`, decoded as Borsh
+pub async fn get_borsh_accounts<T: BorshDeserialize>(
+    rpc_url: &str,
+    program_id: &str,
+    filters: &[AccountFilter],
+    commitment: Commitment,
+) -> IdosResult<Vec<DecodedAccount<T>>> {
+    get_program_accounts_with(rpc_url, program_id, filters, commitment, |_, data| {
+        decode_borsh(data)
+    })
+    .await
+}
+
+/// Anchor accounts of one type owned by `program_id`
+/// The discriminator filter is added in front of `filters`
+pub async fn get_anchor_accounts<T: BorshDeserialize>(
+    rpc_url: &str,
+    program_id: &str,
+    account_name: &str,
+    filters: &[AccountFilter],
+    commitment: Commitment,
+) -> IdosResult<Vec<DecodedAccount<T>>> {
+    let mut all_filters = vec![AccountFilter::anchor_account(account_name)];
+    all_filters.extend_from_slice(filters);
+
+    get_program_accounts_with(rpc_url, program_id, &all_filters, commitment, |_, data| {
+        decode_anchor_account(account_name, data)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+
+    #[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+    struct Player {
+        owner: [u8; 32],
+        level: u16,
+        name: String,
+    }
+
+    #[test]
+    fn test_filters_and_anchor_decoding() {
+        let owner = bs58::encode([3u8; 32]).into_string();
+        let filter = AccountFilter::memcmp_pubkey(8, &owner).unwrap();
+        assert_eq!(
+            filter.to_json().unwrap(),
+            serde_json::json!({ "memcmp": { "offset": 8, "bytes": owner } })
+        );
+        assert_eq!(
+            AccountFilter::DataSize(165).to_json().unwrap(),
+            serde_json::json!({ "dataSize": 165 })
+        );
+        let too_long = AccountFilter::Memcmp {
+            offset: 0,
+            bytes: vec![0; 129],
+        };
+        assert!(too_long.to_json().is_err());
+
+        let player = Player {
+            owner: [3u8; 32],
+            level: 12,
+            name: "ada".to_string(),
+        };
+        let mut data = anchor_account_discriminator("Player").to_vec();
+        data.extend(borsh::to_vec(&player).unwrap());
+        // Reserved space after the account fields
+        data.extend([0u8; 16]);
+
+        assert_eq!(
+            decode_anchor_account::<Player>("Player", &data).unwrap(),
+            player
+        );
+        assert!(decode_anchor_account::<Player>("Match", &data).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+/// RPC endpoint failover and per-method routing
+/// Pools are keyed by the primary `rpc_url`, so every function taking an `rpc_url`
+/// picks up the fallbacks and routes configured in the handler's settings
+use super::dto::SolanaSettings;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Cooldown after the first failure, doubled for each consecutive one
+const BASE_COOLDOWN_MS: i64 = 1_000;
+
+/// Longest an endpoint is skipped for
+const MAX_COOLDOWN_MS: i64 = 60_000;
+
+#[derive(Debug, Clone)]
+struct EndpointHealth {
+    url: String,
+    /// Serves every method, not only the ones routed to it
+    general: bool,
+    consecutive_failures: u32,
+    /// Unix millis until which the endpoint is tried last
+    cooldown_until: i64,
+}
+
+/// Endpoints of one primary RPC URL and their health
+#[derive(Debug, Clone)]
+pub struct RpcEndpointPool {
+    endpoints: Vec<EndpointHealth>,
+    routes: HashMap<String, String>,
+}
+
+impl RpcEndpointPool {
+    pub fn new(primary: &str, fallbacks: &[String], routes: HashMap<String, String>) -> Self {
+        let mut endpoints: Vec<EndpointHealth> = Vec::new();
+        let general = std::iter::once(primary).chain(fallbacks.iter().map(String::as_str));
+        let routed = routes.values().map(String::as_str);
+
+        for (url, is_general) in general.map(|u| (u, true)).chain(routed.map(|u| (u, false))) {
+            if !endpoints.iter().any(|e| e.url == url) {
+                endpoints.push(EndpointHealth {
+                    url: url.to_string(),
+                    general: is_general,
+                    consecutive_failures: 0,
+                    cooldown_until: 0,
+                });
+            }
+        }
+
+        Self { endpoints, routes }
+    }
+
+    /// Endpoints to try for `method`, best first
+    /// Healthy endpoints come before cooling down ones, the routed endpoint before the rest
+    pub fn candidates(&self, method: &str, now_ms: i64) -> Vec<String> {
+        let routed = self.routes.get(method);
+        let mut endpoints: Vec<(usize, &EndpointHealth)> = self
+            .endpoints
+            .iter()
+            .enumerate()
+            .filter(|(_, endpoint)| endpoint.general || Some(&endpoint.url) == routed)
+            .collect();
+        endpoints.sort_by_key(|(index, endpoint)| {
+            (
+                endpoint.cooldown_until > now_ms,
+                Some(&endpoint.url) != routed,
+                *index,
+            )
+        });
+
+        endpoints
+            .into_iter()
+            .map(|(_, endpoint)| endpoint.url.clone())
+            .collect()
+    }
+
+    pub fn report_success(&mut self, url: &str) {
+        if let Some(endpoint) = self.endpoints.iter_mut().find(|e| e.url == url) {
+            endpoint.consecutive_failures = 0;
+            endpoint.cooldown_until = 0;
+        }
+    }
+
+    /// Record a 429, timeout or connection failure
+    pub fn report_failure(&mut self, url: &str, now_ms: i64) {
+        if let Some(endpoint) = self.endpoints.iter_mut().find(|e| e.url == url) {
+            endpoint.consecutive_failures += 1;
+            let backoff = BASE_COOLDOWN_MS
+                .saturating_mul(1 << (endpoint.consecutive_failures - 1).min(16))
+                .min(MAX_COOLDOWN_MS);
+            endpoint.cooldown_until = now_ms + backoff;
+        }
+    }
+}
+
+fn pools() -> &'static Mutex<HashMap<String, RpcEndpointPool>> {
+    static POOLS: OnceLock<Mutex<HashMap<String, RpcEndpointPool>>> = OnceLock::new();
+    POOLS.get_or_init(Default::default)
+}
+
+/// Register the fallbacks and routes of `settings` (called by `SolanaHandler::new`)
+pub fn configure(settings: &SolanaSettings) {
+    let mut pools = pools().lock().unwrap();
+    if settings.fallback_rpc_urls.is_empty() && settings.rpc_routes.is_empty() {
+        pools.remove(&settings.rpc_url);
+        return;
+    }
+
+    pools.insert(
+        settings.rpc_url.clone(),
+        RpcEndpointPool::new(
+            &settings.rpc_url,
+            &settings.fallback_rpc_urls,
+            settings.rpc_routes.clone(),
+        ),
+    );
+}
+
+/// Endpoints to try for a request to `rpc_url`
+pub(crate) fn candidates(rpc_url: &str, method: &str) -> Vec<String> {
+    match pools().lock().unwrap().get(rpc_url) {
+        Some(pool) => pool.candidates(method, chrono::Utc::now().timestamp_millis()),
+        None => vec![rpc_url.to_string()],
+    }
+}
+
+/// Record the outcome of a request sent to `endpoint`
+pub(crate) fn report(rpc_url: &str, endpoint: &str, success: bool) {
+    if let Some(pool) = pools().lock().unwrap().get_mut(rpc_url) {
+        if success {
+            pool.report_success(endpoint);
+        } else {
+            pool.report_failure(endpoint, chrono::Utc::now().timestamp_millis());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failover_order() {
+        let routes = HashMap::from([("getAsset".to_string(), "https://das".to_string())]);
+        let mut pool = RpcEndpointPool::new("https://a", &["https://b".to_string()], routes);
+
+        // Routed endpoints only serve their methods
+        assert_eq!(pool.candidates("getBalance", 0), ["https://a", "https://b"]);
+        assert_eq!(
+            pool.candidates("getAsset", 0),
+            ["https://das", "https://a", "https://b"]
+        );
+
+        // A rate-limited endpoint moves to the back until its cooldown ends
+        pool.report_failure("https://a", 0);
+        assert_eq!(
+            pool.candidates("getBalance", 500),
+            ["https://b", "https://a"]
+        );
+        assert_eq!(
+            pool.candidates("getBalance", 1_000),
+            ["https://a", "https://b"]
+        );
+
+        pool.report_failure("https://a", 0);
+        assert_eq!(pool.candidates("getBalance", 1_500)[0], "https://b");
+        pool.report_success("https://a");
+        assert_eq!(pool.candidates("getBalance", 1_500)[0], "https://a");
+    }
+}
@@ -1,8 +1,16 @@
 /// High-level Solana platform pool service  
 /// Matches Unity SDK's SolanaPlatformPoolService API exactly
-use super::{anchor::*, dto::*, handler::SolanaHandler, transactions::*};
+use super::{anchor::*, dto::*, handler::SolanaHandler, jito, transactions::*};
 use crate::{IdosError, IdosResult};
 
+/// Confirmation polls (2 seconds apart) before a platform pool transfer is reported as pending
+#[cfg(feature = "crypto_solana")]
+const POOL_CONFIRM_ATTEMPTS: u32 = 30;
+
+/// Retries of the backend report after a platform pool transfer landed
+#[cfg(feature = "crypto_solana")]
+const POOL_REPORT_RETRIES: u32 = 4;
+
 /// Solana Platform Pool Service
 /// Provides deposit and withdrawal functionality for SPL tokens
 /// Matches Unity SDK's SolanaPlatformPoolService.cs
@@ -19,11 +27,18 @@ impl SolanaPlatformPoolService {
         }
     }
 
-    /// Set private key for signing transactions (base58 format)
-    pub fn set_private_key(&mut self, private_key_base58: &str) -> IdosResult<()> {
-        let key_bytes = bs58::decode(private_key_base58)
-            .into_vec()
-            .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
+    /// Set private key for signing transactions
+    /// Accepts base58 or the Solana CLI keypair file format (`id.json`, a JSON byte array)
+    pub fn set_private_key(&mut self, private_key: &str) -> IdosResult<()> {
+        let private_key = private_key.trim();
+        let key_bytes = if private_key.starts_with('[') {
+            serde_json::from_str::<Vec<u8>>(private_key)
+                .map_err(|e| IdosError::Wallet(format!("Invalid keypair file: {}", e)))?
+        } else {
+            bs58::decode(private_key)
+                .into_vec()
+                .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?
+        };
 
         if key_bytes.len() != 64 {
             return Err(IdosError::Wallet(
@@ -53,10 +68,46 @@ impl SolanaPlatformPoolService {
         Ok(pubkey)
     }
 
+    /// Sign an off-chain message (e.g. a wallet login challenge)
+    /// Uses the service key when set, otherwise the connected browser wallet (WASM)
+    #[cfg(feature = "crypto_solana")]
+    pub async fn sign_message(&self, message: &[u8]) -> IdosResult<SolanaSignedMessage> {
+        if let Some(private_key) = self.private_key.as_deref() {
+            use ed25519_dalek::Signer as _;
+
+            let secret: [u8; 32] = private_key[..32]
+                .try_into()
+                .map_err(|_| IdosError::Wallet("Invalid private key".to_string()))?;
+            let signing_key = ed25519_dalek::SigningKey::from_bytes(&secret);
+
+            return Ok(SolanaSignedMessage {
+                public_key: bs58::encode(signing_key.verifying_key().to_bytes()).into_string(),
+                signature: bs58::encode(signing_key.sign(message).to_bytes()).into_string(),
+            });
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.handler.sign_message(message).await
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Err(IdosError::Wallet("Private key not set".to_string()))
+        }
+    }
+
+    #[cfg(not(feature = "crypto_solana"))]
+    pub async fn sign_message(&self, _message: &[u8]) -> IdosResult<SolanaSignedMessage> {
+        Err(IdosError::PlatformNotSupported(
+            "Solana message signing requires the crypto_solana feature".to_string(),
+        ))
+    }
+
     /// Deposit SPL tokens to platform pool
     /// Matches Unity SDK's DepositSplAsync
     /// Returns transaction signature
-    #[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
+    #[cfg(feature = "crypto_solana")]
     pub async fn deposit_spl(
         &self,
         mint_address: &str,
@@ -64,7 +115,6 @@ impl SolanaPlatformPoolService {
         user_id: &str,
     ) -> IdosResult<String> {
         let settings = self.handler.settings();
-        let rpc_url = &settings.rpc_url;
         let program_id_str = &settings.program_id;
 
         // Parse addresses
@@ -80,15 +130,16 @@ impl SolanaPlatformPoolService {
         let mut mint = [0u8; 32];
         mint.copy_from_slice(&mint_bytes);
 
-        let user_pubkey = self.get_public_key()?;
+        let user_pubkey = self.signer_pubkey().await?;
 
         // Derive PDAs
         let (config_pda, _) = find_program_address(&[b"config"], &program_id)?;
         let (vault_pda, _) = find_program_address(&[b"vault"], &program_id)?;
 
         // Derive ATAs
-        let user_ata = derive_associated_token_account(&user_pubkey, &mint)?;
-        let vault_ata = derive_associated_token_account(&vault_pda, &mint)?;
+        let token_program = self.handler.token_program(mint_address).await?;
+        let user_ata = derive_associated_token_account(&user_pubkey, &mint, token_program)?;
+        let vault_ata = derive_associated_token_account(&vault_pda, &mint, token_program)?;
 
         // Build deposit instruction
         let deposit_ix = build_deposit_spl_instruction(
@@ -101,25 +152,21 @@ impl SolanaPlatformPoolService {
             &vault_ata,
             amount,
             user_id,
+            token_program,
         );
 
-        // Build, sign, and send transaction
-        let blockhash = get_recent_blockhash(rpc_url).await?;
-
-        let mut tx_builder = TransactionBuilder::new(user_pubkey);
-        tx_builder
-            .add_instruction(deposit_ix)
-            .set_recent_blockhash(&blockhash);
+        // The vault may not hold this token yet
+        let mut instructions = self
+            .create_ata_if_missing(&user_pubkey, &vault_pda, &mint, &vault_ata, token_program)
+            .await?;
+        instructions.push(deposit_ix);
 
-        let signed_tx = tx_builder.sign_and_serialize(self.get_private_key()?)?;
-
-        // Send transaction (with preflight checks)
-        let signature = send_transaction(rpc_url, &signed_tx, false).await?;
-
-        Ok(signature)
+        // Build, sign, and send transaction
+        self.sign_and_send(&user_pubkey, instructions, "deposit")
+            .await
     }
 
-    #[cfg(any(not(feature = "crypto_solana"), target_arch = "wasm32"))]
+    #[cfg(not(feature = "crypto_solana"))]
     pub async fn deposit_spl(
         &self,
         _mint_address: &str,
@@ -127,16 +174,15 @@ impl SolanaPlatformPoolService {
         _user_id: &str,
     ) -> IdosResult<String> {
         Err(IdosError::PlatformNotSupported(
-            "Native Solana transaction building requires full solana-sdk. Use WASM wallet adapter or backend API.".to_string(),
+            "Solana transaction building requires the crypto_solana feature".to_string(),
         ))
     }
 
     /// Withdraw SPL tokens from platform pool with backend signature
     /// Matches Unity SDK's WithdrawSplAsync
-    #[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
+    #[cfg(feature = "crypto_solana")]
     pub async fn withdraw_spl(&self, withdraw_request: WithdrawSplRequest) -> IdosResult<String> {
         let settings = self.handler.settings();
-        let rpc_url = &settings.rpc_url;
         let program_id_str = &settings.program_id;
 
         // Parse program ID
@@ -159,7 +205,7 @@ impl SolanaPlatformPoolService {
         let mut to_pubkey = [0u8; 32];
         to_pubkey.copy_from_slice(&to_bytes);
 
-        let payer_pubkey = self.get_public_key()?;
+        let payer_pubkey = self.signer_pubkey().await?;
 
         // Derive PDAs
         let (config_pda, _) = find_program_address(&[b"config"], &program_id)?;
@@ -170,8 +216,9 @@ impl SolanaPlatformPoolService {
         let (nonce_marker_pda, _) = find_program_address(&[b"nonce", &nonce_bytes], &program_id)?;
 
         // Derive ATAs
-        let vault_ata = derive_associated_token_account(&vault_pda, &mint)?;
-        let to_ata = derive_associated_token_account(&to_pubkey, &mint)?;
+        let token_program = self.handler.token_program(&withdraw_request.mint).await?;
+        let vault_ata = derive_associated_token_account(&vault_pda, &mint, token_program)?;
+        let to_ata = derive_associated_token_account(&to_pubkey, &mint, token_program)?;
 
         // Build Ed25519 verification instruction
         let ed25519_pubkey = hex_to_bytes(&withdraw_request.ed25519_public_key_hex)?;
@@ -197,49 +244,725 @@ impl SolanaPlatformPoolService {
         };
 
         // Build withdraw instruction
-        let withdraw_ix = build_withdraw_spl_instruction(
+        let build_withdraw_ix = |sig_ix_index: u8| {
+            build_withdraw_spl_instruction(
+                &program_id,
+                &config_pda,
+                &payer_pubkey,
+                &vault_pda,
+                &nonce_marker_pda,
+                &mint,
+                &to_pubkey,
+                &vault_ata,
+                &to_ata,
+                withdraw_request.amount,
+                withdraw_request.nonce,
+                &withdraw_request.user_id,
+                sig_ix_index,
+                token_program,
+            )
+        };
+
+        // The recipient may never have held this token
+        let mut instructions = self
+            .create_ata_if_missing(&payer_pubkey, &to_pubkey, &mint, &to_ata, token_program)
+            .await?;
+        let ata_ix_count = instructions.len();
+        instructions.push(ed25519_tx_ix);
+        instructions.push(build_withdraw_ix(withdraw_request.sig_ix_index));
+
+        // Build, sign, and send transaction
+        let mut tx_builder = self
+            .prepare_transaction(&payer_pubkey, instructions)
+            .await?;
+
+        // Compute budget and ATA instructions come first, which moves the Ed25519 instruction back
+        let shift = tx_builder.compute_budget_instruction_count() + ata_ix_count;
+        if shift > 0 {
+            let sig_ix_index = u8::try_from(shift)
+                .ok()
+                .and_then(|shift| withdraw_request.sig_ix_index.checked_add(shift))
+                .ok_or_else(|| {
+                    IdosError::InvalidInput(format!(
+                        "Signature instruction index {} is out of range",
+                        withdraw_request.sig_ix_index
+                    ))
+                })?;
+            tx_builder.instructions[ata_ix_count + 1] = build_withdraw_ix(sig_ix_index);
+        }
+
+        // Withdrawals reference many accounts; compress them through lookup tables when configured
+        // (v0 messages are only compiled on native builds)
+        #[cfg(not(target_arch = "wasm32"))]
+        for table in
+            fetch_address_lookup_tables(&settings.rpc_url, &settings.address_lookup_tables).await?
+        {
+            tx_builder.add_address_lookup_table(table);
+        }
+
+        self.submit(&mut tx_builder, "withdrawal").await
+    }
+
+    #[cfg(not(feature = "crypto_solana"))]
+    pub async fn withdraw_spl(&self, _withdraw_request: WithdrawSplRequest) -> IdosResult<String> {
+        Err(IdosError::PlatformNotSupported(
+            "Solana transaction building requires the crypto_solana feature".to_string(),
+        ))
+    }
+
+    /// Withdraw tokens from the platform pool to a player's wallet
+    /// Requests the backend signature, sends the withdrawal, waits for it to land and
+    /// reports it to the backend. Returns the transaction signature
+    ///
+    /// Failures before the send leave nothing on-chain and can be retried from scratch.
+    /// If the transaction landed but the backend could not be told, the error carries
+    /// the signature so it can be passed to `SolanaHandler::submit_withdrawal` later
+    #[cfg(feature = "crypto_solana")]
+    pub async fn withdraw_to_user(
+        &self,
+        mint: &str,
+        amount: u64,
+        wallet_address: &str,
+    ) -> IdosResult<String> {
+        let payload = self
+            .handler
+            .get_withdrawal_signature(mint, amount, wallet_address)
+            .await?;
+        let settings = self.handler.settings();
+        if !payload.program_id.is_empty() && payload.program_id != settings.program_id {
+            return Err(IdosError::api(format!(
+                "Withdrawal payload is for program {}, not {}",
+                payload.program_id, settings.program_id
+            )));
+        }
+        let request = payload.to_checked_request(mint, amount, wallet_address)?;
+
+        let signature = self.withdraw_spl(request).await?;
+
+        let handler = &self.handler;
+        self.confirm_and_report("Withdrawal", signature, |signature| async move {
+            handler.submit_withdrawal(&signature).await
+        })
+        .await
+    }
+
+    #[cfg(not(feature = "crypto_solana"))]
+    pub async fn withdraw_to_user(
+        &self,
+        _mint: &str,
+        _amount: u64,
+        _wallet_address: &str,
+    ) -> IdosResult<String> {
+        Err(IdosError::PlatformNotSupported(
+            "Solana transaction building requires the crypto_solana feature".to_string(),
+        ))
+    }
+
+    /// Deposit an NFT into the platform pool and report it to the backend
+    /// Matches the ERC-1155 flow of `transfer_nft_to_game`; programmable NFTs and
+    /// compressed or Core assets are not supported by the pool program
+    /// Returns transaction signature
+    #[cfg(feature = "crypto_solana")]
+    pub async fn deposit_nft(&self, mint_address: &str, user_id: &str) -> IdosResult<String> {
+        let program_id = decode_pubkey(&self.handler.settings().program_id)?;
+        let mint = decode_pubkey(mint_address)?;
+
+        // Fungible tokens go through deposit_spl
+        let token_mint = self.handler.get_token_mint(mint_address).await?;
+        if token_mint.decimals != 0 {
+            return Err(IdosError::InvalidInput(format!(
+                "{} is not an NFT mint",
+                mint_address
+            )));
+        }
+
+        let user_pubkey = self.signer_pubkey().await?;
+
+        let (config_pda, _) = find_program_address(&[b"config"], &program_id)?;
+        let (vault_pda, _) = find_program_address(&[b"vault"], &program_id)?;
+
+        let user_ata = derive_associated_token_account(&user_pubkey, &mint, token_mint.program)?;
+        let vault_ata = derive_associated_token_account(&vault_pda, &mint, token_mint.program)?;
+
+        let deposit_ix = build_deposit_nft_instruction(
             &program_id,
             &config_pda,
-            &payer_pubkey,
             &vault_pda,
-            &nonce_marker_pda,
             &mint,
-            &to_pubkey,
+            &user_pubkey,
+            &user_ata,
             &vault_ata,
-            &to_ata,
-            withdraw_request.amount,
-            withdraw_request.nonce,
-            &withdraw_request.user_id,
-            withdraw_request.sig_ix_index,
+            user_id,
+            token_mint.program,
         );
 
-        // Build, sign, and send transaction with both instructions
-        let blockhash = get_recent_blockhash(rpc_url).await?;
+        // Each NFT has its own mint, so the vault never holds it yet
+        let mut instructions = self
+            .create_ata_if_missing(
+                &user_pubkey,
+                &vault_pda,
+                &mint,
+                &vault_ata,
+                token_mint.program,
+            )
+            .await?;
+        instructions.push(deposit_ix);
+
+        let signature = self
+            .sign_and_send(&user_pubkey, instructions, "nft_deposit")
+            .await?;
+
+        let handler = &self.handler;
+        self.confirm_and_report("NFT deposit", signature, |signature| async move {
+            handler.submit_nft_deposit(&signature, mint_address).await
+        })
+        .await
+    }
+
+    #[cfg(not(feature = "crypto_solana"))]
+    pub async fn deposit_nft(&self, _mint_address: &str, _user_id: &str) -> IdosResult<String> {
+        Err(IdosError::PlatformNotSupported(
+            "Solana transaction building requires the crypto_solana feature".to_string(),
+        ))
+    }
+
+    /// Transfer native SOL to another wallet
+    /// Returns transaction signature
+    #[cfg(feature = "crypto_solana")]
+    pub async fn transfer_sol(&self, to: &str, lamports: u64) -> IdosResult<String> {
+        let to_pubkey = decode_pubkey(to)?;
+        let from_pubkey = self.signer_pubkey().await?;
+
+        let transfer_ix = build_transfer_sol_instruction(&from_pubkey, &to_pubkey, lamports);
+        self.sign_and_send(&from_pubkey, vec![transfer_ix], "transfer")
+            .await
+    }
 
-        let mut tx_builder = TransactionBuilder::new(payer_pubkey);
-        tx_builder
-            .add_instruction(ed25519_tx_ix)
-            .add_instruction(withdraw_ix)
-            .set_recent_blockhash(&blockhash);
+    #[cfg(not(feature = "crypto_solana"))]
+    pub async fn transfer_sol(&self, _to: &str, _lamports: u64) -> IdosResult<String> {
+        Err(IdosError::PlatformNotSupported(
+            "Solana transaction building requires the crypto_solana feature".to_string(),
+        ))
+    }
 
-        let signed_tx = tx_builder.sign_and_serialize(self.get_private_key()?)?;
+    /// Transfer SPL tokens to another wallet
+    /// Creates the recipient's associated token account when it doesn't exist yet.
+    /// Token-2022 mints with a transfer fee deliver `amount` minus the fee
+    /// (see `SolanaHandler::get_transfer_fee`)
+    /// Returns transaction signature
+    #[cfg(feature = "crypto_solana")]
+    pub async fn transfer_spl_token(
+        &self,
+        mint: &str,
+        to: &str,
+        amount: u64,
+    ) -> IdosResult<String> {
+        let mint_pubkey = decode_pubkey(mint)?;
+        let to_pubkey = decode_pubkey(to)?;
+        let owner_pubkey = self.signer_pubkey().await?;
+
+        let token_mint = self.handler.get_token_mint(mint).await?;
+        let source_ata =
+            derive_associated_token_account(&owner_pubkey, &mint_pubkey, token_mint.program)?;
+        let destination_ata =
+            derive_associated_token_account(&to_pubkey, &mint_pubkey, token_mint.program)?;
+
+        let mut instructions = self
+            .create_ata_if_missing(
+                &owner_pubkey,
+                &to_pubkey,
+                &mint_pubkey,
+                &destination_ata,
+                token_mint.program,
+            )
+            .await?;
+
+        // Pin the fee so a fee change between quote and send fails instead of taking more
+        let transfer_ix = if token_mint.transfer_fee.is_some() {
+            let fee = self.handler.transfer_fee(&token_mint, amount).await?;
+            build_transfer_checked_with_fee_instruction(
+                &source_ata,
+                &mint_pubkey,
+                &destination_ata,
+                &owner_pubkey,
+                amount,
+                token_mint.decimals,
+                fee,
+            )?
+        } else {
+            build_transfer_checked_instruction(
+                &source_ata,
+                &mint_pubkey,
+                &destination_ata,
+                &owner_pubkey,
+                amount,
+                token_mint.decimals,
+                token_mint.program,
+            )?
+        };
+        instructions.push(transfer_ix);
 
-        // Send transaction (with preflight checks)
-        let signature = send_transaction(rpc_url, &signed_tx, false).await?;
+        self.sign_and_send(&owner_pubkey, instructions, "transfer")
+            .await
+    }
 
-        Ok(signature)
+    #[cfg(not(feature = "crypto_solana"))]
+    pub async fn transfer_spl_token(
+        &self,
+        _mint: &str,
+        _to: &str,
+        _amount: u64,
+    ) -> IdosResult<String> {
+        Err(IdosError::PlatformNotSupported(
+            "Solana transaction building requires the crypto_solana feature".to_string(),
+        ))
     }
 
-    #[cfg(any(not(feature = "crypto_solana"), target_arch = "wasm32"))]
-    pub async fn withdraw_spl(&self, _withdraw_request: WithdrawSplRequest) -> IdosResult<String> {
+    /// Transfer an NFT to another wallet
+    /// Programmable NFTs go through Token Metadata's Transfer (token records, rule sets),
+    /// regular NFTs use transferChecked with amount 1 and 0 decimals
+    /// Returns transaction signature
+    #[cfg(feature = "crypto_solana")]
+    pub async fn transfer_nft(&self, mint: &str, to: &str) -> IdosResult<String> {
+        let mint_pubkey = decode_pubkey(mint)?;
+        let to_pubkey = decode_pubkey(to)?;
+        let owner_pubkey = self.signer_pubkey().await?;
+
+        let metadata =
+            super::nft::load_onchain_metadata(&self.handler.settings().rpc_url, mint).await?;
+
+        if metadata.token_standard.as_deref() == Some("ProgrammableNonFungible") {
+            let rule_set = metadata
+                .rule_set
+                .as_deref()
+                .map(decode_pubkey)
+                .transpose()?;
+            let transfer_ix = build_transfer_programmable_nft_instruction(
+                &owner_pubkey,
+                &to_pubkey,
+                &mint_pubkey,
+                rule_set.as_ref(),
+            )?;
+            return self
+                .sign_and_send(&owner_pubkey, vec![transfer_ix], "nft_transfer")
+                .await;
+        }
+
+        let token_program = self.handler.token_program(mint).await?;
+        let source_ata =
+            derive_associated_token_account(&owner_pubkey, &mint_pubkey, token_program)?;
+        let destination_ata =
+            derive_associated_token_account(&to_pubkey, &mint_pubkey, token_program)?;
+
+        let mut instructions = self
+            .create_ata_if_missing(
+                &owner_pubkey,
+                &to_pubkey,
+                &mint_pubkey,
+                &destination_ata,
+                token_program,
+            )
+            .await?;
+
+        instructions.push(build_transfer_checked_instruction(
+            &source_ata,
+            &mint_pubkey,
+            &destination_ata,
+            &owner_pubkey,
+            1,
+            0,
+            token_program,
+        )?);
+
+        self.sign_and_send(&owner_pubkey, instructions, "nft_transfer")
+            .await
+    }
+
+    #[cfg(not(feature = "crypto_solana"))]
+    pub async fn transfer_nft(&self, _mint: &str, _to: &str) -> IdosResult<String> {
+        Err(IdosError::PlatformNotSupported(
+            "Solana transaction building requires the crypto_solana feature".to_string(),
+        ))
+    }
+
+    /// Transfer a compressed (Bubblegum) NFT to another wallet
+    /// Requires a DAS-capable RPC endpoint for the asset and its merkle proof
+    /// Returns transaction signature
+    #[cfg(feature = "crypto_solana")]
+    pub async fn transfer_compressed_nft(&self, asset_id: &str, to: &str) -> IdosResult<String> {
+        let rpc_url = &self.handler.settings().rpc_url;
+        let to_pubkey = decode_pubkey(to)?;
+        let owner_pubkey = self.signer_pubkey().await?;
+
+        let asset = super::das::get_asset(rpc_url, asset_id).await?;
+        let compression = asset.compression.filter(|c| c.compressed).ok_or_else(|| {
+            IdosError::InvalidInput(format!("{} is not a compressed NFT", asset_id))
+        })?;
+        let proof = super::das::get_asset_proof(rpc_url, asset_id).await?;
+
+        let delegate = match asset.ownership.delegate.as_deref() {
+            Some(delegate) => decode_pubkey(delegate)?,
+            None => owner_pubkey,
+        };
+
+        // Proof nodes covered by the on-chain canopy must be left out
+        let tree_data = super::nft::get_account_data(rpc_url, &proof.tree_id).await?;
+        let canopy_depth = merkle_tree_canopy_depth(&tree_data)? as usize;
+        let proof_nodes = proof
+            .proof
+            .iter()
+            .take(proof.proof.len().saturating_sub(canopy_depth))
+            .map(|node| decode_pubkey(node))
+            .collect::<IdosResult<Vec<_>>>()?;
+
+        let leaf = CompressedNftLeaf {
+            merkle_tree: decode_pubkey(&proof.tree_id)?,
+            root: decode_pubkey(&proof.root)?,
+            data_hash: decode_pubkey(&compression.data_hash)?,
+            creator_hash: decode_pubkey(&compression.creator_hash)?,
+            nonce: compression.leaf_id,
+            index: proof.node_index.saturating_sub(1u64 << proof.proof.len()) as u32,
+            proof: proof_nodes,
+        };
+
+        let transfer_ix =
+            build_bubblegum_transfer_instruction(&owner_pubkey, &delegate, &to_pubkey, &leaf)?;
+        self.sign_and_send(&owner_pubkey, vec![transfer_ix], "nft_transfer")
+            .await
+    }
+
+    #[cfg(not(feature = "crypto_solana"))]
+    pub async fn transfer_compressed_nft(&self, _asset_id: &str, _to: &str) -> IdosResult<String> {
+        Err(IdosError::PlatformNotSupported(
+            "Solana transaction building requires the crypto_solana feature".to_string(),
+        ))
+    }
+
+    /// Transfer a Metaplex Core asset to another wallet
+    /// Returns transaction signature
+    #[cfg(feature = "crypto_solana")]
+    pub async fn transfer_core_asset(&self, asset_address: &str, to: &str) -> IdosResult<String> {
+        let asset_pubkey = decode_pubkey(asset_address)?;
+        let to_pubkey = decode_pubkey(to)?;
+        let owner_pubkey = self.signer_pubkey().await?;
+
+        let asset = self.handler.get_core_asset(asset_address).await?;
+        if decode_pubkey(&asset.owner)? != owner_pubkey {
+            return Err(IdosError::Wallet(format!(
+                "{} is not owned by the signing wallet",
+                asset_address
+            )));
+        }
+
+        let collection = match &asset.update_authority {
+            CoreUpdateAuthority::Collection(collection) => Some(decode_pubkey(collection)?),
+            _ => None,
+        };
+
+        let transfer_ix = build_core_transfer_instruction(
+            &asset_pubkey,
+            collection.as_ref(),
+            &owner_pubkey,
+            &to_pubkey,
+        )?;
+        self.sign_and_send(&owner_pubkey, vec![transfer_ix], "nft_transfer")
+            .await
+    }
+
+    #[cfg(not(feature = "crypto_solana"))]
+    pub async fn transfer_core_asset(&self, _asset_address: &str, _to: &str) -> IdosResult<String> {
         Err(IdosError::PlatformNotSupported(
-            "Native Solana transaction building requires full solana-sdk. Use WASM wallet adapter or backend API.".to_string(),
+            "Solana transaction building requires the crypto_solana feature".to_string(),
         ))
     }
 
+    /// Helper: Instructions creating `ata` for `owner` when it doesn't exist yet
+    #[cfg(feature = "crypto_solana")]
+    async fn create_ata_if_missing(
+        &self,
+        payer: &[u8; 32],
+        owner: &[u8; 32],
+        mint: &[u8; 32],
+        ata: &[u8; 32],
+        token_program: TokenProgram,
+    ) -> IdosResult<Vec<TransactionInstruction>> {
+        let ata_address = bs58::encode(ata).into_string();
+        if self.handler.account_exists(&ata_address).await? {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![build_create_associated_token_account_instruction(
+            payer,
+            owner,
+            mint,
+            token_program,
+        )?])
+    }
+
+    /// Helper: Wait for a platform pool transfer to land, then report it to the backend
+    /// The transfer can't be undone once it landed, so the report is retried on network
+    /// errors; if it still fails the error carries the signature for a later report
+    #[cfg(feature = "crypto_solana")]
+    async fn confirm_and_report<F, Fut>(
+        &self,
+        kind: &str,
+        signature: String,
+        report: F,
+    ) -> IdosResult<String>
+    where
+        F: Fn(String) -> Fut,
+        Fut: std::future::Future<Output = IdosResult<String>>,
+    {
+        match self
+            .handler
+            .confirm_transaction(&signature, POOL_CONFIRM_ATTEMPTS)
+            .await
+        {
+            Ok(_) => {}
+            Err(IdosError::TimeoutError(_)) => {
+                return Err(IdosError::TimeoutError(format!(
+                    "{} {} not confirmed yet",
+                    kind, signature
+                )))
+            }
+            Err(e) => return Err(e),
+        }
+
+        let mut attempt = 0;
+        loop {
+            match report(signature.clone()).await {
+                Ok(_) => return Ok(signature),
+                Err(
+                    e @ (IdosError::Network(_)
+                    | IdosError::Http(_)
+                    | IdosError::NetworkError(_)
+                    | IdosError::TimeoutError(_)),
+                ) if attempt < POOL_REPORT_RETRIES => {
+                    attempt += 1;
+                    log::warn!(
+                        "Failed to report {} {} ({}/{}): {}",
+                        kind,
+                        signature,
+                        attempt,
+                        POOL_REPORT_RETRIES,
+                        e
+                    );
+                    super::helper::sleep(std::time::Duration::from_secs(1 << attempt)).await;
+                }
+                Err(e) => {
+                    return Err(IdosError::api(format!(
+                        "{} {} landed but was not recorded: {}",
+                        kind, signature, e
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Helper: Build, sign and send a transaction paid by `payer`
+    #[cfg(feature = "crypto_solana")]
+    async fn sign_and_send(
+        &self,
+        payer: &[u8; 32],
+        instructions: Vec<TransactionInstruction>,
+        label: &str,
+    ) -> IdosResult<String> {
+        let mut tx_builder = self.prepare_transaction(payer, instructions).await?;
+        self.submit(&mut tx_builder, label).await
+    }
+
+    /// Helper: Transaction with a fresh blockhash and the configured compute budget
+    #[cfg(feature = "crypto_solana")]
+    async fn prepare_transaction(
+        &self,
+        payer: &[u8; 32],
+        instructions: Vec<TransactionInstruction>,
+    ) -> IdosResult<TransactionBuilder> {
+        let settings = self.handler.settings();
+        let blockhash = get_recent_blockhash(&settings.rpc_url, settings.commitment).await?;
+
+        let mut tx_builder = TransactionBuilder::new(*payer);
+        for instruction in instructions {
+            tx_builder.add_instruction(instruction);
+        }
+        tx_builder.set_recent_blockhash(&blockhash);
+
+        self.apply_compute_budget(&mut tx_builder).await;
+        Ok(tx_builder)
+    }
+
+    /// Helper: Submit a prepared transaction and record it in the transaction history
+    /// When its blockhash expires before landing, it is re-signed with a fresh one
+    /// up to `max_blockhash_retries` times
+    #[cfg(feature = "crypto_solana")]
+    async fn submit(&self, tx_builder: &mut TransactionBuilder, label: &str) -> IdosResult<String> {
+        let settings = self.handler.settings();
+        self.check_fee_balance(tx_builder).await?;
+        let mut retries = 0;
+
+        loop {
+            match self.submit_once(tx_builder).await {
+                Err(e) if retries < settings.max_blockhash_retries && is_blockhash_expired(&e) => {
+                    retries += 1;
+                    log::debug!(
+                        "Blockhash expired, retrying with a fresh one ({}/{})",
+                        retries,
+                        settings.max_blockhash_retries
+                    );
+
+                    let blockhash =
+                        get_recent_blockhash(&settings.rpc_url, settings.commitment).await?;
+                    tx_builder.set_recent_blockhash(&blockhash);
+                }
+                Ok(signature) => {
+                    self.handler.record_transaction(&signature, label);
+                    return Ok(signature);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Helper: Fail early when the payer can't cover the fee and the rent of created token accounts
+    #[cfg(feature = "crypto_solana")]
+    async fn check_fee_balance(&self, tx_builder: &TransactionBuilder) -> IdosResult<()> {
+        let rpc_url = &self.handler.settings().rpc_url;
+
+        let mut required = tx_builder.estimate_fee();
+        if let Some(jito) = &self.handler.settings().jito {
+            required += jito.tip_lamports;
+        }
+        let created_accounts = tx_builder.created_token_account_count() as u64;
+        if created_accounts > 0 {
+            let rent = get_minimum_balance_for_rent_exemption(rpc_url, TOKEN_ACCOUNT_SIZE).await?;
+            required += rent * created_accounts;
+        }
+
+        let payer = bs58::encode(tx_builder.fee_payer).into_string();
+        let available = self.handler.get_balance(&payer).await?;
+        if available < required {
+            return Err(IdosError::InsufficientSolForFees {
+                required,
+                available,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Helper: Sign with the service key and send, or hand the message to the browser wallet
+    #[cfg(feature = "crypto_solana")]
+    async fn submit_once(&self, tx_builder: &TransactionBuilder) -> IdosResult<String> {
+        if let Some(private_key) = self.private_key.as_deref() {
+            let settings = self.handler.settings();
+            if let Some(jito) = &settings.jito {
+                // Only an explicitly rejected bundle falls through to an untipped send
+                if let Some(signature) = self.send_bundle(tx_builder, private_key, jito).await? {
+                    return Ok(signature);
+                }
+            }
+
+            let signed_tx = tx_builder.sign_and_serialize(private_key)?;
+
+            // Send transaction (with preflight checks)
+            return send_transaction(&settings.rpc_url, &signed_tx, false, settings.commitment)
+                .await;
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let unsigned = tx_builder.serialize_unsigned()?;
+            self.handler.sign_and_send_unsigned(&unsigned).await
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Err(IdosError::Wallet("Private key not set".to_string()))
+        }
+    }
+
+    /// Helper: Sign with a Jito tip appended and send as a single-transaction bundle
+    /// Returns `Ok(None)` when the block engine rejected the bundle
+    #[cfg(feature = "crypto_solana")]
+    async fn send_bundle(
+        &self,
+        tx_builder: &TransactionBuilder,
+        private_key: &[u8],
+        jito: &JitoSettings,
+    ) -> IdosResult<Option<String>> {
+        let settings = self.handler.settings();
+        let mut tipped = tx_builder.clone();
+        tipped.add_instruction(jito::build_tip_instruction(&tx_builder.fee_payer, jito)?);
+
+        let signed_tx = tipped.sign_and_serialize(private_key)?;
+        jito::send_tipped_transaction(
+            &jito.block_engine_url,
+            &settings.rpc_url,
+            &signed_tx,
+            settings.commitment,
+        )
+        .await
+    }
+
+    /// Helper: Public key that signs service transactions
+    /// The service key when set, otherwise the connected browser wallet (WASM)
+    #[cfg(feature = "crypto_solana")]
+    async fn signer_pubkey(&self) -> IdosResult<[u8; 32]> {
+        #[cfg(target_arch = "wasm32")]
+        if self.private_key.is_none() {
+            let address = self.handler.connect_wallet().await?;
+            return decode_pubkey(&address);
+        }
+
+        self.get_public_key()
+    }
+
+    /// Helper: Apply the configured compute unit limit and priority fee
+    #[cfg(feature = "crypto_solana")]
+    async fn apply_compute_budget(&self, tx_builder: &mut TransactionBuilder) {
+        let settings = self.handler.settings();
+
+        if let Some(units) = settings.compute_unit_limit {
+            tx_builder.set_compute_unit_limit(units);
+        }
+
+        let price = match settings.priority_fee {
+            PriorityFee::None => return,
+            PriorityFee::Fixed(price) => price,
+            PriorityFee::Auto {
+                percentile,
+                max_micro_lamports,
+            } => {
+                // Estimation is best effort - send without a priority fee if the RPC refuses
+                estimate_priority_fee(
+                    &settings.rpc_url,
+                    &tx_builder.writable_accounts(),
+                    percentile,
+                    max_micro_lamports,
+                )
+                .await
+                .unwrap_or(0)
+            }
+        };
+        tx_builder.set_compute_unit_price(price);
+    }
+
     /// Helper: Derive PDA from string seeds
     fn derive_pda_from_seeds(seeds: &[&str], program_id: &[u8; 32]) -> IdosResult<([u8; 32], u8)> {
         let byte_seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_bytes()).collect();
         find_program_address(&byte_seeds, program_id)
     }
 }
+
+#[cfg(feature = "crypto_solana")]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl super::sign_in::SolanaMessageSigner for SolanaPlatformPoolService {
+    async fn solana_address(&self) -> IdosResult<String> {
+        Ok(bs58::encode(self.signer_pubkey().await?).into_string())
+    }
+
+    async fn sign_solana_message(&self, message: &[u8]) -> IdosResult<SolanaSignedMessage> {
+        self.sign_message(message).await
+    }
+}
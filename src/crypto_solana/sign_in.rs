@@ -0,0 +1,170 @@
+/// Sign In With Solana
+/// Login message proving ownership of a Solana wallet, in the same layout as EIP-4361
+use super::dto::SolanaSignedMessage;
+use super::transactions::decode_pubkey;
+use crate::{IdosError, IdosResult};
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use std::fmt;
+
+/// Login message signed by a Solana wallet
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolanaSignInMessage {
+    /// Host requesting the sign-in ("mygame.example")
+    pub domain: String,
+    /// Wallet address (base58)
+    pub address: String,
+    /// Human-readable line shown by the wallet
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    /// Cluster, e.g. "mainnet" or "devnet"
+    pub chain_id: Option<String>,
+    /// One-time nonce from the backend (`AuthHandler::get_wallet_nonce`)
+    pub nonce: String,
+    /// RFC 3339 timestamps
+    pub issued_at: String,
+    pub expiration_time: Option<String>,
+}
+
+impl SolanaSignInMessage {
+    /// Message issued now for `address`
+    pub fn new(domain: &str, address: &str, uri: &str, nonce: &str) -> IdosResult<Self> {
+        decode_pubkey(address)?;
+
+        Ok(Self {
+            domain: domain.to_string(),
+            address: address.to_string(),
+            statement: None,
+            uri: uri.to_string(),
+            version: "1".to_string(),
+            chain_id: None,
+            nonce: nonce.to_string(),
+            issued_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            expiration_time: None,
+        })
+    }
+
+    pub fn with_statement(mut self, statement: &str) -> Self {
+        self.statement = Some(statement.to_string());
+        self
+    }
+
+    /// Make the message expire `duration` after now
+    pub fn expires_in(mut self, duration: std::time::Duration) -> Self {
+        let expires_at = Utc::now() + chrono::Duration::from_std(duration).unwrap_or_default();
+        self.expiration_time = Some(expires_at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        self
+    }
+
+    /// Check that `signed` is `address`'s Ed25519 signature of this message
+    pub fn verify(&self, signed: &SolanaSignedMessage) -> IdosResult<()> {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        if signed.public_key != self.address {
+            return Err(IdosError::Auth(format!(
+                "Message was signed by {} instead of {}",
+                signed.public_key, self.address
+            )));
+        }
+
+        let invalid = || IdosError::Auth("Invalid Solana signature".to_string());
+        let public_key =
+            VerifyingKey::from_bytes(&decode_pubkey(&self.address)?).map_err(|_| invalid())?;
+        let signature = bs58::decode(&signed.signature)
+            .into_vec()
+            .ok()
+            .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+            .ok_or_else(invalid)?;
+
+        public_key
+            .verify(
+                self.to_string().as_bytes(),
+                &Signature::from_bytes(&signature),
+            )
+            .map_err(|_| invalid())
+    }
+}
+
+impl fmt::Display for SolanaSignInMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} wants you to sign in with your Solana account:",
+            self.domain
+        )?;
+        writeln!(f, "{}", self.address)?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{}", statement)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "URI: {}", self.uri)?;
+        writeln!(f, "Version: {}", self.version)?;
+        if let Some(chain_id) = &self.chain_id {
+            writeln!(f, "Chain ID: {}", chain_id)?;
+        }
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {}", expiration_time)?;
+        }
+        Ok(())
+    }
+}
+
+/// Something that can sign off-chain messages with a Solana key
+/// Implemented by the in-game `WalletManager`, `SolanaPlatformPoolService` and, on WASM,
+/// `SolanaHandler` (the selected browser wallet)
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait SolanaMessageSigner {
+    /// Address (base58) that will sign
+    async fn solana_address(&self) -> IdosResult<String>;
+
+    /// Ed25519 signature of the raw message bytes
+    async fn sign_solana_message(&self, message: &[u8]) -> IdosResult<SolanaSignedMessage>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn test_sign_in_message() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let address = bs58::encode(key.verifying_key().to_bytes()).into_string();
+
+        let mut message = SolanaSignInMessage::new(
+            "mygame.example",
+            &address,
+            "https://mygame.example",
+            "k3j5h2",
+        )
+        .unwrap()
+        .with_statement("Sign in to My Game");
+        message.issued_at = "2024-01-01T00:00:00Z".to_string();
+        assert_eq!(
+            message.to_string(),
+            format!(
+                "mygame.example wants you to sign in with your Solana account:\n{}\n\n\
+                 Sign in to My Game\n\n\
+                 URI: https://mygame.example\nVersion: 1\nNonce: k3j5h2\n\
+                 Issued At: 2024-01-01T00:00:00Z",
+                address
+            )
+        );
+
+        let signed = SolanaSignedMessage {
+            public_key: address.clone(),
+            signature: bs58::encode(key.sign(message.to_string().as_bytes()).to_bytes())
+                .into_string(),
+        };
+        message.verify(&signed).unwrap();
+
+        message.nonce = "other".to_string();
+        assert!(message.verify(&signed).is_err());
+        assert!(SolanaSignInMessage::new("mygame.example", "not-an-address", "", "n").is_err());
+    }
+}
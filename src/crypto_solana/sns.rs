@@ -0,0 +1,157 @@
+/// Solana Name Service
+/// Resolves .sol domains to their owner and wallets back to their primary domain
+use super::anchor::find_program_address;
+use super::dto::AccountInfoResponse;
+use super::helper::send_solana_rpc_request;
+use super::transactions::decode_pubkey;
+use crate::{IdosError, IdosResult};
+use base64::{engine::general_purpose, Engine as _};
+use sha2::{Digest, Sha256};
+
+/// SPL Name Service program
+pub const NAME_PROGRAM_ID: &str = "namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX";
+
+/// Parent account of every .sol domain
+pub const SOL_TLD_AUTHORITY: &str = "58PwtjSDuFHuUkYjH9BYnnQKHfwo9reZhC2zMJv9JPkx";
+
+/// Class of the reverse lookup accounts (domain account -> domain name)
+const REVERSE_LOOKUP_CLASS: &str = "33m47vH6Eav6jr7QRvQyrLGT6ddkgCc4YpwmLsqDBCPG";
+
+/// Program storing each wallet's primary ("favourite") domain
+const NAME_OFFERS_PROGRAM_ID: &str = "85iDfUvr3HJyLM2zcq5BXSiDvUWfw6cSE1FfNBo8Ap29";
+
+const HASH_PREFIX: &str = "SPL Name Service";
+
+/// Name registry header: parent, owner and class
+const HEADER_SIZE: usize = 96;
+const OWNER_OFFSET: usize = 32;
+
+fn hashed_name(name: &str) -> [u8; 32] {
+    Sha256::digest(format!("{}{}", HASH_PREFIX, name).as_bytes()).into()
+}
+
+fn name_account_key(
+    hashed_name: &[u8; 32],
+    class: &[u8; 32],
+    parent: &[u8; 32],
+) -> IdosResult<[u8; 32]> {
+    let program_id = decode_pubkey(NAME_PROGRAM_ID)?;
+    let (key, _bump) = find_program_address(&[hashed_name, class, parent], &program_id)?;
+    Ok(key)
+}
+
+/// Address of the name account of a domain ("alice.sol", "alice" or a subdomain "pay.alice.sol")
+pub fn domain_key(domain: &str) -> IdosResult<[u8; 32]> {
+    let domain = domain.trim().trim_end_matches(".sol").to_lowercase();
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.iter().any(|label| label.is_empty()) || labels.len() > 2 {
+        return Err(IdosError::InvalidInput(format!(
+            "Invalid .sol domain: {}",
+            domain
+        )));
+    }
+
+    let parent = decode_pubkey(SOL_TLD_AUTHORITY)?;
+    let key = name_account_key(&hashed_name(labels[labels.len() - 1]), &[0; 32], &parent)?;
+    match labels.as_slice() {
+        // Subdomain names are prefixed with a zero byte
+        [sub, _] => name_account_key(&hashed_name(&format!("\0{}", sub)), &[0; 32], &key),
+        _ => Ok(key),
+    }
+}
+
+async fn get_account_data(rpc_url: &str, address: &str) -> IdosResult<Option<Vec<u8>>> {
+    let params = serde_json::json!([address, { "encoding": "base64" }]);
+    let response: AccountInfoResponse =
+        send_solana_rpc_request(rpc_url, "getAccountInfo", params).await?;
+
+    response
+        .value
+        .map(|account| {
+            general_purpose::STANDARD
+                .decode(&account.data.0)
+                .map_err(|e| IdosError::SerializationError(format!("Invalid account data: {}", e)))
+        })
+        .transpose()
+}
+
+/// Owner of a .sol domain
+pub async fn resolve_domain(rpc_url: &str, domain: &str) -> IdosResult<String> {
+    let key = bs58::encode(domain_key(domain)?).into_string();
+    let data = get_account_data(rpc_url, &key)
+        .await?
+        .ok_or_else(|| IdosError::InvalidInput(format!("Domain {} is not registered", domain)))?;
+    let owner = data
+        .get(OWNER_OFFSET..OWNER_OFFSET + 32)
+        .ok_or_else(|| IdosError::SerializationError(format!("Invalid name account {}", key)))?;
+
+    Ok(bs58::encode(owner).into_string())
+}
+
+/// Primary domain of a wallet ("alice.sol"), or None when it hasn't set one
+pub async fn reverse_lookup(rpc_url: &str, address: &str) -> IdosResult<Option<String>> {
+    let owner = decode_pubkey(address)?;
+    let (favourite, _bump) = find_program_address(
+        &[b"favourite_domain", &owner],
+        &decode_pubkey(NAME_OFFERS_PROGRAM_ID)?,
+    )?;
+
+    // Tag byte followed by the domain's name account
+    let Some(data) = get_account_data(rpc_url, &bs58::encode(favourite).into_string()).await?
+    else {
+        return Ok(None);
+    };
+    let Some(domain_account) = data.get(1..33) else {
+        return Ok(None);
+    };
+
+    let reverse_key = name_account_key(
+        &hashed_name(&bs58::encode(domain_account).into_string()),
+        &decode_pubkey(REVERSE_LOOKUP_CLASS)?,
+        &[0; 32],
+    )?;
+    let Some(data) = get_account_data(rpc_url, &bs58::encode(reverse_key).into_string()).await?
+    else {
+        return Ok(None);
+    };
+
+    // Borsh string: u32 length, then the name without ".sol"
+    let name = data
+        .get(HEADER_SIZE..HEADER_SIZE + 4)
+        .map(|len| u32::from_le_bytes(len.try_into().unwrap()) as usize)
+        .and_then(|len| data.get(HEADER_SIZE + 4..HEADER_SIZE + 4 + len))
+        .and_then(|name| String::from_utf8(name.to_vec()).ok());
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    let domain = format!("{}.sol", name);
+
+    // The primary domain may have been transferred since it was set
+    match resolve_domain(rpc_url, &domain).await {
+        Ok(resolved) if resolved == address => Ok(Some(domain)),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_key() {
+        assert_eq!(
+            bs58::encode(domain_key("bonfida.sol").unwrap()).into_string(),
+            "Crf8hzfthWGbGbLTVCiqRqV5MVnbpHB1L9KQMd6gsinb"
+        );
+        assert_eq!(
+            domain_key("bonfida").unwrap(),
+            domain_key("Bonfida.sol").unwrap()
+        );
+        assert_ne!(
+            domain_key("dex.bonfida.sol").unwrap(),
+            domain_key("bonfida.sol").unwrap()
+        );
+        assert!(domain_key("a.b.c.sol").is_err());
+        assert!(domain_key(".sol").is_err());
+    }
+}
@@ -0,0 +1,354 @@
+/// Solana Pay URLs (https://docs.solanapay.com/spec)
+/// Games render the URL as a QR code, the player pays from any wallet and the
+/// payment is found on-chain through its reference key
+use super::dto::*;
+use super::helper::{send_solana_rpc_request, solana_get_transaction_with_meta};
+use super::transactions::decode_pubkey;
+use crate::{IdosError, IdosResult};
+
+const SCHEME: &str = "solana:";
+
+/// Decimals of SOL amounts
+const SOL_DECIMALS: u8 = 9;
+
+/// Percent-encode a URL component (everything but RFC 3986 unreserved characters)
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Decode a percent-encoded URL component (`+` is a space in query values)
+fn percent_decode(value: &str) -> IdosResult<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let byte = value
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| {
+                        IdosError::InvalidInput(format!("Invalid percent-encoding in {}", value))
+                    })?;
+                decoded.push(byte);
+                i += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded)
+        .map_err(|e| IdosError::InvalidInput(format!("Invalid UTF-8 in URL: {}", e)))
+}
+
+/// Amounts are non-negative decimals with a leading digit ("0.5", not ".5")
+fn validate_amount(amount: &str) -> IdosResult<()> {
+    let mut parts = amount.splitn(2, '.');
+    let whole = parts.next().unwrap_or_default();
+    let valid_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+
+    if valid_digits(whole) && parts.next().is_none_or(valid_digits) {
+        Ok(())
+    } else {
+        Err(IdosError::InvalidInput(format!(
+            "Invalid Solana Pay amount: {}",
+            amount
+        )))
+    }
+}
+
+/// Convert a decimal amount into base units (lamports or raw token amount)
+pub fn amount_to_base_units(amount: &str, decimals: u8) -> IdosResult<u64> {
+    validate_amount(amount)?;
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+
+    if fraction.len() > decimals as usize {
+        return Err(IdosError::InvalidInput(format!(
+            "Amount {} has more than {} decimals",
+            amount, decimals
+        )));
+    }
+
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+    digits
+        .parse::<u64>()
+        .map_err(|_| IdosError::InvalidInput(format!("Amount {} is too large", amount)))
+}
+
+/// Random reference key to attach to a payment request
+pub fn new_reference() -> String {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+    bytes[16..].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+    bs58::encode(bytes).into_string()
+}
+
+/// Build a transfer request URL
+pub fn encode_transfer_url(request: &SolanaPayTransferRequest) -> IdosResult<String> {
+    decode_pubkey(&request.recipient)?;
+
+    let mut params = Vec::new();
+    if let Some(amount) = &request.amount {
+        validate_amount(amount)?;
+        params.push(format!("amount={}", amount));
+    }
+    if let Some(mint) = &request.spl_token {
+        decode_pubkey(mint)?;
+        params.push(format!("spl-token={}", mint));
+    }
+    for reference in &request.references {
+        decode_pubkey(reference)?;
+        params.push(format!("reference={}", reference));
+    }
+    for (key, value) in [
+        ("label", &request.label),
+        ("message", &request.message),
+        ("memo", &request.memo),
+    ] {
+        if let Some(value) = value {
+            params.push(format!("{}={}", key, percent_encode(value)));
+        }
+    }
+
+    let mut url = format!("{}{}", SCHEME, request.recipient);
+    if !params.is_empty() {
+        url.push('?');
+        url.push_str(&params.join("&"));
+    }
+    Ok(url)
+}
+
+/// Build a transaction request URL pointing at an HTTPS endpoint
+pub fn encode_transaction_url(link: &str) -> IdosResult<String> {
+    if !link.starts_with("https://") {
+        return Err(IdosError::InvalidInput(format!(
+            "Solana Pay transaction links must use https: {}",
+            link
+        )));
+    }
+
+    // Links with query parameters are encoded so they don't mix with the URL's own
+    if link.contains('?') {
+        Ok(format!("{}{}", SCHEME, percent_encode(link)))
+    } else {
+        Ok(format!("{}{}", SCHEME, link))
+    }
+}
+
+/// Parse a Solana Pay URL
+pub fn parse_url(url: &str) -> IdosResult<SolanaPayRequest> {
+    let rest = url
+        .strip_prefix(SCHEME)
+        .ok_or_else(|| IdosError::InvalidInput(format!("Not a Solana Pay URL: {}", url)))?;
+
+    // Recipients are base58, so a scheme separator means a transaction request
+    if rest.contains(':') || rest.to_ascii_lowercase().starts_with("https%3a") {
+        let link = percent_decode(rest)?;
+        if !link.starts_with("https://") {
+            return Err(IdosError::InvalidInput(format!(
+                "Solana Pay transaction links must use https: {}",
+                link
+            )));
+        }
+        return Ok(SolanaPayRequest::Transaction { link });
+    }
+
+    let (recipient, query) = rest.split_once('?').unwrap_or((rest, ""));
+    decode_pubkey(recipient)?;
+
+    let mut request = SolanaPayTransferRequest {
+        recipient: recipient.to_string(),
+        ..Default::default()
+    };
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = percent_decode(value)?;
+
+        match key {
+            "amount" => {
+                validate_amount(&value)?;
+                request.amount = Some(value);
+            }
+            "spl-token" => {
+                decode_pubkey(&value)?;
+                request.spl_token = Some(value);
+            }
+            "reference" => {
+                decode_pubkey(&value)?;
+                request.references.push(value);
+            }
+            "label" => request.label = Some(value),
+            "message" => request.message = Some(value),
+            "memo" => request.memo = Some(value),
+            // Unknown parameters are ignored for forward compatibility
+            _ => {}
+        }
+    }
+
+    Ok(SolanaPayRequest::Transfer(request))
+}
+
+/// Find the oldest transaction that includes a reference key
+/// Returns None while the payment hasn't landed
+pub async fn find_reference(rpc_url: &str, reference: &str) -> IdosResult<Option<SignatureInfo>> {
+    let params = serde_json::json!([
+        reference,
+        {
+            "limit": 1000,
+            "commitment": "confirmed"
+        }
+    ]);
+    let mut signatures: Vec<SignatureInfo> =
+        send_solana_rpc_request(rpc_url, "getSignaturesForAddress", params).await?;
+
+    // Results are newest first
+    Ok(signatures.pop())
+}
+
+/// Check that a transaction pays a transfer request: it succeeded, includes
+/// every reference and moved at least the requested amount to the recipient
+pub async fn validate_transfer(
+    rpc_url: &str,
+    signature: &str,
+    request: &SolanaPayTransferRequest,
+) -> IdosResult<TransactionWithMeta> {
+    let transaction =
+        solana_get_transaction_with_meta(rpc_url, signature, Commitment::Confirmed).await?;
+
+    check_transfer(&transaction, request)?;
+    Ok(transaction)
+}
+
+/// Balance checks of `validate_transfer`
+fn check_transfer(
+    transaction: &TransactionWithMeta,
+    request: &SolanaPayTransferRequest,
+) -> IdosResult<()> {
+    let meta = transaction
+        .meta
+        .as_ref()
+        .ok_or_else(|| IdosError::NetworkError("Transaction has no metadata".to_string()))?;
+    if let Some(err) = &meta.err {
+        return Err(IdosError::TransactionReverted(format!(
+            "Payment transaction failed: {}",
+            err
+        )));
+    }
+
+    let keys = transaction.account_keys();
+    if let Some(missing) = request.references.iter().find(|r| !keys.contains(r)) {
+        return Err(IdosError::InvalidInput(format!(
+            "Transaction does not include reference {}",
+            missing
+        )));
+    }
+
+    let (received, decimals) = match &request.spl_token {
+        None => {
+            let index = keys
+                .iter()
+                .position(|key| *key == request.recipient)
+                .ok_or_else(|| IdosError::InvalidInput("Recipient not found".to_string()))?;
+            let pre = meta.pre_balances.get(index).copied().unwrap_or(0);
+            let post = meta.post_balances.get(index).copied().unwrap_or(0);
+            (post.saturating_sub(pre), SOL_DECIMALS)
+        }
+        Some(mint) => {
+            let find = |balances: &Option<Vec<TokenBalance>>| {
+                balances
+                    .iter()
+                    .flatten()
+                    .find(|balance| {
+                        balance.mint == *mint
+                            && balance.owner.as_deref() == Some(&request.recipient)
+                    })
+                    .cloned()
+            };
+            let post = find(&meta.post_token_balances)
+                .ok_or_else(|| IdosError::InvalidInput("Recipient not found".to_string()))?;
+            let raw =
+                |balance: &TokenBalance| balance.ui_token_amount.amount.parse::<u64>().unwrap_or(0);
+            let pre = find(&meta.pre_token_balances).map(|b| raw(&b)).unwrap_or(0);
+            (
+                raw(&post).saturating_sub(pre),
+                post.ui_token_amount.decimals,
+            )
+        }
+    };
+
+    if let Some(amount) = &request.amount {
+        let expected = amount_to_base_units(amount, decimals)?;
+        if received < expected {
+            return Err(IdosError::InvalidInput(format!(
+                "Payment too small: expected {} base units, received {}",
+                expected, received
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_url_round_trip() {
+        let recipient = bs58::encode([1u8; 32]).into_string();
+        let request = SolanaPayTransferRequest {
+            recipient: recipient.clone(),
+            amount: Some("0.5".to_string()),
+            spl_token: None,
+            references: vec![new_reference()],
+            label: Some("Gem Shop".to_string()),
+            message: Some("100 gems & a bonus".to_string()),
+            memo: None,
+        };
+
+        let url = encode_transfer_url(&request).unwrap();
+        assert!(url.starts_with(&format!("solana:{}?amount=0.5&reference=", recipient)));
+        assert!(url.contains("label=Gem%20Shop"));
+        assert_eq!(
+            parse_url(&url).unwrap(),
+            SolanaPayRequest::Transfer(request)
+        );
+
+        assert_eq!(amount_to_base_units("0.5", 9).unwrap(), 500_000_000);
+        assert_eq!(amount_to_base_units("12", 6).unwrap(), 12_000_000);
+        assert!(amount_to_base_units(".5", 9).is_err());
+        assert!(amount_to_base_units("0.1234567", 6).is_err());
+    }
+
+    #[test]
+    fn test_transaction_url() {
+        let link = "https://example.com/pay?order=42";
+        let url = encode_transaction_url(link).unwrap();
+        assert_eq!(url, "solana:https%3A%2F%2Fexample.com%2Fpay%3Forder%3D42");
+        assert_eq!(
+            parse_url(&url).unwrap(),
+            SolanaPayRequest::Transaction {
+                link: link.to_string()
+            }
+        );
+        assert!(encode_transaction_url("http://example.com").is_err());
+    }
+}
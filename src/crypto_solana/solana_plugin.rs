@@ -1,6 +1,14 @@
-use super::{SolanaHandler, SolanaSettings};
+use super::{
+    NftImages, SolanaAccountChanged, SolanaHandler, SolanaSettings, SolanaSignatureUpdate,
+    SolanaTxConfirmed, SolanaTxEvent, SolanaTxFailed, SolanaTxSubmitted,
+};
 use crate::IdosClient;
 use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+/// How often pending signatures in the transaction history are checked
+const HISTORY_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 pub struct SolanaPlugin {
     pub settings: SolanaSettings,
@@ -14,14 +22,107 @@ impl SolanaPlugin {
 
 impl Plugin for SolanaPlugin {
     fn build(&self, app: &mut App) {
+        let history = crate::transaction_history::shared_history(app);
+
         // Get the IdosClient resource if it exists
         if let Some(client) = app.world().get_resource::<IdosClient>() {
-            let handler = SolanaHandler::new(client.clone(), self.settings.clone());
+            let mut handler = SolanaHandler::new(client.clone(), self.settings.clone());
+            if let Some(history) = history {
+                handler.set_transaction_history(history);
+            }
             app.insert_resource(handler);
         } else {
             warn!("IdosClient not found. SolanaHandler will not be initialized.");
         }
 
+        app.add_message::<SolanaSignatureUpdate>()
+            .add_message::<SolanaTxSubmitted>()
+            .add_message::<SolanaTxConfirmed>()
+            .add_message::<SolanaTxFailed>()
+            .add_message::<SolanaAccountChanged>()
+            .init_resource::<NftImages>()
+            .add_systems(
+                Update,
+                (
+                    forward_signature_updates,
+                    forward_tx_events,
+                    forward_account_updates,
+                ),
+            )
+            .add_systems(Startup, refresh_transaction_history)
+            .add_systems(
+                Update,
+                refresh_transaction_history.run_if(on_timer(HISTORY_POLL_INTERVAL)),
+            );
+
         info!("Solana Wallet Plugin initialized");
     }
 }
+
+/// Forward confirmations collected by `SolanaHandler::watch_transaction` as messages
+fn forward_signature_updates(
+    handler: Option<Res<SolanaHandler>>,
+    mut updates: MessageWriter<SolanaSignatureUpdate>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for update in handler.drain_signature_updates() {
+        updates.write(update);
+    }
+}
+
+/// Forward the lifecycle of watched transactions as messages
+fn forward_tx_events(
+    handler: Option<Res<SolanaHandler>>,
+    mut submitted: MessageWriter<SolanaTxSubmitted>,
+    mut confirmed: MessageWriter<SolanaTxConfirmed>,
+    mut failed: MessageWriter<SolanaTxFailed>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for event in handler.drain_tx_events() {
+        match event {
+            SolanaTxEvent::Submitted(event) => {
+                submitted.write(event);
+            }
+            SolanaTxEvent::Confirmed(event) => {
+                confirmed.write(event);
+            }
+            SolanaTxEvent::Failed(event) => {
+                failed.write(event);
+            }
+        }
+    }
+}
+
+/// Forward changes of accounts passed to `SolanaHandler::watch_account` as messages
+fn forward_account_updates(
+    handler: Option<Res<SolanaHandler>>,
+    mut changed: MessageWriter<SolanaAccountChanged>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for update in handler.drain_account_updates() {
+        changed.write(update);
+    }
+}
+
+/// Resolve pending signatures in the transaction history, including ones left by a previous run
+fn refresh_transaction_history(handler: Option<Res<SolanaHandler>>) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    let h = handler.clone();
+    crate::tasks::spawn(async move {
+        if let Err(e) = h.refresh_transaction_history().await {
+            warn!("Failed to refresh Solana transaction history: {}", e);
+        }
+    });
+}
@@ -0,0 +1,382 @@
+/// Solana WebSocket subscriptions (signatureSubscribe, accountSubscribe)
+/// Native uses tokio-tungstenite, WASM uses the browser WebSocket
+use crate::{IdosError, IdosResult};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+/// Payload of a `signatureNotification`
+#[derive(Debug, Clone)]
+pub struct SignatureNotification {
+    pub slot: u64,
+    /// Transaction error reported by the cluster (None when it succeeded)
+    pub err: Option<serde_json::Value>,
+}
+
+/// Build the `signatureSubscribe` request
+fn subscribe_request(signature: &str, commitment: &str) -> String {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "signatureSubscribe",
+        "params": [signature, { "commitment": commitment }]
+    })
+    .to_string()
+}
+
+/// Parse a pubsub message
+/// Returns None for messages that don't settle the subscription (e.g. the subscription id ack)
+fn parse_message(text: &str) -> IdosResult<Option<SignatureNotification>> {
+    let message: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| IdosError::SerializationError(format!("Invalid pubsub message: {}", e)))?;
+
+    if let Some(error) = message.get("error") {
+        return Err(IdosError::NetworkError(format!(
+            "Solana RPC Error: {}",
+            error["message"].as_str().unwrap_or("unknown")
+        )));
+    }
+
+    if message["method"] != "signatureNotification" {
+        return Ok(None);
+    }
+
+    let result = &message["params"]["result"];
+    let err = match &result["value"]["err"] {
+        serde_json::Value::Null => None,
+        err => Some(err.clone()),
+    };
+
+    Ok(Some(SignatureNotification {
+        slot: result["context"]["slot"].as_u64().unwrap_or(0),
+        err,
+    }))
+}
+
+/// Wait for a signature to reach `commitment` through `signatureSubscribe` (native)
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn subscribe_signature(
+    ws_url: &str,
+    signature: &str,
+    commitment: &str,
+    timeout: Duration,
+) -> IdosResult<SignatureNotification> {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+
+    let wait = async {
+        let (mut socket, _) = connect_async(ws_url)
+            .await
+            .map_err(|e| IdosError::NetworkError(format!("WebSocket connect failed: {}", e)))?;
+
+        socket
+            .send(WsMessage::Text(subscribe_request(signature, commitment)))
+            .await
+            .map_err(|e| IdosError::NetworkError(format!("WebSocket send failed: {}", e)))?;
+
+        while let Some(message) = socket.next().await {
+            let message = message
+                .map_err(|e| IdosError::NetworkError(format!("WebSocket read failed: {}", e)))?;
+
+            if let WsMessage::Text(text) = message {
+                if let Some(notification) = parse_message(&text)? {
+                    socket.close(None).await.ok();
+                    return Ok(notification);
+                }
+            }
+        }
+
+        Err(IdosError::NetworkError(
+            "WebSocket closed before confirmation".to_string(),
+        ))
+    };
+
+    tokio::time::timeout(timeout, wait)
+        .await
+        .map_err(|_| IdosError::TimeoutError("Signature subscription timed out".to_string()))?
+}
+
+/// Wait for a signature to reach `commitment` through `signatureSubscribe` (WASM)
+#[cfg(target_arch = "wasm32")]
+pub async fn subscribe_signature(
+    ws_url: &str,
+    signature: &str,
+    commitment: &str,
+    timeout: Duration,
+) -> IdosResult<SignatureNotification> {
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{MessageEvent, WebSocket};
+
+    let ws = WebSocket::new(ws_url)
+        .map_err(|e| IdosError::NetworkError(format!("WebSocket connect failed: {:?}", e)))?;
+    let request = subscribe_request(signature, commitment);
+    let timeout_ms = timeout.as_millis() as i32;
+
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let open_socket = ws.clone();
+        let request = request.clone();
+        let onopen = Closure::once_into_js(move || {
+            open_socket.send_with_str(&request).ok();
+        });
+        ws.set_onopen(Some(onopen.unchecked_ref()));
+
+        // Settle on the first message that isn't the subscription ack
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            if let Some(text) = event.data().as_string() {
+                if !matches!(parse_message(&text), Ok(None)) {
+                    resolve
+                        .call1(&JsValue::NULL, &JsValue::from_str(&text))
+                        .ok();
+                }
+            }
+        });
+        ws.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        onmessage.forget();
+
+        let error_reject = reject.clone();
+        let onerror = Closure::once_into_js(move |_: JsValue| {
+            error_reject
+                .call1(&JsValue::NULL, &JsValue::from_str("error"))
+                .ok();
+        });
+        ws.set_onerror(Some(onerror.unchecked_ref()));
+
+        if let Some(window) = web_sys::window() {
+            let ontimeout = Closure::once_into_js(move || {
+                reject
+                    .call1(&JsValue::NULL, &JsValue::from_str("timeout"))
+                    .ok();
+            });
+            window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    ontimeout.unchecked_ref(),
+                    timeout_ms,
+                )
+                .ok();
+        }
+    });
+
+    let result = wasm_bindgen_futures::JsFuture::from(promise).await;
+    ws.close().ok();
+
+    match result {
+        Ok(text) => parse_message(&text.as_string().unwrap_or_default())?.ok_or_else(|| {
+            IdosError::NetworkError("Unexpected signature subscription message".to_string())
+        }),
+        Err(reason) if reason.as_string().as_deref() == Some("timeout") => Err(
+            IdosError::TimeoutError("Signature subscription timed out".to_string()),
+        ),
+        Err(_) => Err(IdosError::NetworkError(
+            "WebSocket subscription failed".to_string(),
+        )),
+    }
+}
+
+/// Payload of an `accountNotification`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountNotification {
+    pub slot: u64,
+    pub lamports: u64,
+    pub owner: String,
+    /// UI amount string for SPL token accounts (from `jsonParsed` data)
+    pub token_amount: Option<String>,
+}
+
+/// Build the `accountSubscribe` request
+fn account_subscribe_request(address: &str, commitment: &str) -> String {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "accountSubscribe",
+        "params": [address, { "commitment": commitment, "encoding": "jsonParsed" }]
+    })
+    .to_string()
+}
+
+/// Parse an account pubsub message
+/// Returns None for anything but an `accountNotification` (e.g. the subscription id ack)
+fn parse_account_message(text: &str) -> IdosResult<Option<AccountNotification>> {
+    let message: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| IdosError::SerializationError(format!("Invalid pubsub message: {}", e)))?;
+
+    if let Some(error) = message.get("error") {
+        return Err(IdosError::NetworkError(format!(
+            "Solana RPC Error: {}",
+            error["message"].as_str().unwrap_or("unknown")
+        )));
+    }
+
+    if message["method"] != "accountNotification" {
+        return Ok(None);
+    }
+
+    let result = &message["params"]["result"];
+    let value = &result["value"];
+    Ok(Some(AccountNotification {
+        slot: result["context"]["slot"].as_u64().unwrap_or(0),
+        lamports: value["lamports"].as_u64().unwrap_or(0),
+        owner: value["owner"].as_str().unwrap_or_default().to_string(),
+        token_amount: value["data"]["parsed"]["info"]["tokenAmount"]["uiAmountString"]
+            .as_str()
+            .map(str::to_string),
+    }))
+}
+
+/// Stream `accountSubscribe` notifications into `on_update` (native)
+/// Returns when `stop` is set (Ok) or the connection drops (Err)
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn subscribe_account(
+    ws_url: &str,
+    address: &str,
+    commitment: &str,
+    stop: Arc<AtomicBool>,
+    mut on_update: impl FnMut(AccountNotification),
+) -> IdosResult<()> {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+
+    let (mut socket, _) = connect_async(ws_url)
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("WebSocket connect failed: {}", e)))?;
+
+    socket
+        .send(WsMessage::Text(account_subscribe_request(
+            address, commitment,
+        )))
+        .await
+        .map_err(|e| IdosError::NetworkError(format!("WebSocket send failed: {}", e)))?;
+
+    while !stop.load(Ordering::Acquire) {
+        // Wake up regularly so `stop` is noticed on quiet accounts
+        let Ok(message) = tokio::time::timeout(Duration::from_secs(1), socket.next()).await else {
+            continue;
+        };
+        let Some(message) = message else {
+            break;
+        };
+        let message = message
+            .map_err(|e| IdosError::NetworkError(format!("WebSocket read failed: {}", e)))?;
+
+        if let WsMessage::Text(text) = message {
+            if let Some(notification) = parse_account_message(&text)? {
+                on_update(notification);
+            }
+        }
+    }
+
+    if stop.load(Ordering::Acquire) {
+        socket.close(None).await.ok();
+        return Ok(());
+    }
+    Err(IdosError::NetworkError(
+        "Account subscription closed".to_string(),
+    ))
+}
+
+/// Stream `accountSubscribe` notifications into `on_update` (WASM)
+/// Returns when `stop` is set (Ok) or the connection drops (Err)
+#[cfg(target_arch = "wasm32")]
+pub async fn subscribe_account(
+    ws_url: &str,
+    address: &str,
+    commitment: &str,
+    stop: Arc<AtomicBool>,
+    on_update: impl FnMut(AccountNotification) + 'static,
+) -> IdosResult<()> {
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{MessageEvent, WebSocket};
+
+    let ws = WebSocket::new(ws_url)
+        .map_err(|e| IdosError::NetworkError(format!("WebSocket connect failed: {:?}", e)))?;
+    let request = account_subscribe_request(address, commitment);
+    // Promise::new takes an FnMut, so hand the callback over through an Option
+    let mut on_update = Some(on_update);
+
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let open_socket = ws.clone();
+        let request = request.clone();
+        let onopen = Closure::once_into_js(move || {
+            open_socket.send_with_str(&request).ok();
+        });
+        ws.set_onopen(Some(onopen.unchecked_ref()));
+
+        let message_socket = ws.clone();
+        let stop = stop.clone();
+        let Some(mut on_update) = on_update.take() else {
+            return;
+        };
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            if stop.load(Ordering::Acquire) {
+                message_socket.close().ok();
+                return;
+            }
+            if let Some(text) = event.data().as_string() {
+                if let Ok(Some(notification)) = parse_account_message(&text) {
+                    on_update(notification);
+                }
+            }
+        });
+        ws.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        onmessage.forget();
+
+        let onclose = Closure::once_into_js(move |_: JsValue| {
+            resolve.call0(&JsValue::NULL).ok();
+        });
+        ws.set_onclose(Some(onclose.unchecked_ref()));
+
+        let onerror = Closure::once_into_js(move |_: JsValue| {
+            reject
+                .call1(&JsValue::NULL, &JsValue::from_str("error"))
+                .ok();
+        });
+        ws.set_onerror(Some(onerror.unchecked_ref()));
+    });
+
+    let result = wasm_bindgen_futures::JsFuture::from(promise).await;
+    ws.close().ok();
+
+    match result {
+        Ok(_) if stop.load(Ordering::Acquire) => Ok(()),
+        _ => Err(IdosError::NetworkError(
+            "Account subscription closed".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_signature_notification() {
+        let ack = r#"{"jsonrpc":"2.0","result":24006,"id":1}"#;
+        assert!(parse_message(ack).unwrap().is_none());
+
+        let ok = r#"{"jsonrpc":"2.0","method":"signatureNotification","params":{"result":{"context":{"slot":5207624},"value":{"err":null}},"subscription":24006}}"#;
+        let notification = parse_message(ok).unwrap().unwrap();
+        assert_eq!(notification.slot, 5207624);
+        assert!(notification.err.is_none());
+
+        let failed = r#"{"jsonrpc":"2.0","method":"signatureNotification","params":{"result":{"context":{"slot":7},"value":{"err":{"InstructionError":[0,"Custom"]}}},"subscription":1}}"#;
+        assert!(parse_message(failed).unwrap().unwrap().err.is_some());
+    }
+
+    #[test]
+    fn test_parse_account_notification() {
+        let ack = r#"{"jsonrpc":"2.0","result":23784,"id":1}"#;
+        assert!(parse_account_message(ack).unwrap().is_none());
+
+        let sol = r#"{"jsonrpc":"2.0","method":"accountNotification","params":{"result":{"context":{"slot":5199307},"value":{"data":["","base64"],"executable":false,"lamports":33594,"owner":"11111111111111111111111111111111","rentEpoch":635}},"subscription":23784}}"#;
+        let notification = parse_account_message(sol).unwrap().unwrap();
+        assert_eq!(notification.slot, 5199307);
+        assert_eq!(notification.lamports, 33594);
+        assert_eq!(notification.token_amount, None);
+
+        let token = r#"{"jsonrpc":"2.0","method":"accountNotification","params":{"result":{"context":{"slot":9},"value":{"data":{"parsed":{"info":{"tokenAmount":{"amount":"1500000","decimals":6,"uiAmountString":"1.5"}},"type":"account"},"program":"spl-token","space":165},"lamports":2039280,"owner":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"}},"subscription":1}}"#;
+        let notification = parse_account_message(token).unwrap().unwrap();
+        assert_eq!(notification.token_amount.as_deref(), Some("1.5"));
+    }
+}
@@ -0,0 +1,155 @@
+/// SPL token mints
+/// Reads the owning token program, decimals and Token-2022 transfer fees from mint accounts
+use super::dto::*;
+use super::helper::send_solana_rpc_request;
+use crate::{IdosError, IdosResult};
+use base64::{engine::general_purpose, Engine as _};
+
+/// Size of the mint layout shared by both token programs
+const MINT_SIZE: usize = 82;
+
+/// Offset of `decimals` in the mint layout
+const DECIMALS_OFFSET: usize = 44;
+
+/// Token-2022 pads mints with extensions to the token account size, then stores the account type
+const ACCOUNT_TYPE_OFFSET: usize = super::transactions::TOKEN_ACCOUNT_SIZE;
+
+/// Account type byte of mints
+const ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// Extension type of TransferFeeConfig
+const TRANSFER_FEE_CONFIG: u16 = 1;
+
+/// Offset of the fee schedules in TransferFeeConfig (two authorities and the withheld amount)
+const TRANSFER_FEES_OFFSET: usize = 32 + 32 + 8;
+
+/// Size of a TransferFee (epoch, maximum fee, basis points)
+const TRANSFER_FEE_SIZE: usize = 8 + 8 + 2;
+
+fn invalid_mint(address: &str) -> IdosError {
+    IdosError::SerializationError(format!("Invalid mint account data for {}", address))
+}
+
+fn parse_transfer_fee(data: &[u8]) -> TransferFee {
+    TransferFee {
+        epoch: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+        maximum_fee: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+        basis_points: u16::from_le_bytes(data[16..18].try_into().unwrap()),
+    }
+}
+
+/// Walk the Token-2022 extension TLVs (u16 type, u16 length, value) looking for `extension_type`
+fn find_extension(data: &[u8], extension_type: u16) -> Option<&[u8]> {
+    let mut offset = ACCOUNT_TYPE_OFFSET + 1;
+    while let Some(header) = data.get(offset..offset + 4) {
+        let kind = u16::from_le_bytes([header[0], header[1]]);
+        let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        // Type 0 is unused space after the last extension
+        if kind == 0 {
+            return None;
+        }
+        let value = data.get(offset + 4..offset + 4 + len)?;
+        if kind == extension_type {
+            return Some(value);
+        }
+        offset += 4 + len;
+    }
+    None
+}
+
+/// Parse a mint account owned by `owner`
+pub fn parse_token_mint(address: &str, owner: &str, data: &[u8]) -> IdosResult<TokenMint> {
+    let program = TokenProgram::from_owner(owner).ok_or_else(|| {
+        IdosError::InvalidInput(format!("{} is not a token mint (owner {})", address, owner))
+    })?;
+    if data.len() < MINT_SIZE {
+        return Err(invalid_mint(address));
+    }
+
+    let mut transfer_fee = None;
+    if program == TokenProgram::Token2022 && data.len() > ACCOUNT_TYPE_OFFSET {
+        if data[ACCOUNT_TYPE_OFFSET] != ACCOUNT_TYPE_MINT {
+            return Err(invalid_mint(address));
+        }
+        if let Some(config) = find_extension(data, TRANSFER_FEE_CONFIG) {
+            let fees = config
+                .get(TRANSFER_FEES_OFFSET..TRANSFER_FEES_OFFSET + 2 * TRANSFER_FEE_SIZE)
+                .ok_or_else(|| invalid_mint(address))?;
+            transfer_fee = Some(TransferFeeConfig {
+                older: parse_transfer_fee(&fees[..TRANSFER_FEE_SIZE]),
+                newer: parse_transfer_fee(&fees[TRANSFER_FEE_SIZE..]),
+            });
+        }
+    }
+
+    Ok(TokenMint {
+        address: address.to_string(),
+        program,
+        decimals: data[DECIMALS_OFFSET],
+        transfer_fee,
+    })
+}
+
+/// Get a mint account and the token program that owns it
+pub async fn get_token_mint(rpc_url: &str, address: &str) -> IdosResult<TokenMint> {
+    let params = serde_json::json!([address, { "encoding": "base64" }]);
+    let response: AccountInfoResponse =
+        send_solana_rpc_request(rpc_url, "getAccountInfo", params).await?;
+
+    let account = response
+        .value
+        .ok_or_else(|| IdosError::InvalidInput(format!("Mint {} not found", address)))?;
+    let data = general_purpose::STANDARD
+        .decode(&account.data.0)
+        .map_err(|e| IdosError::SerializationError(format!("Invalid account data: {}", e)))?;
+
+    parse_token_mint(address, &account.owner, &data)
+}
+
+/// Current epoch, which selects the transfer fee schedule in effect
+pub async fn get_epoch(rpc_url: &str) -> IdosResult<u64> {
+    #[derive(serde::Deserialize)]
+    struct EpochInfo {
+        epoch: u64,
+    }
+
+    let info: EpochInfo =
+        send_solana_rpc_request(rpc_url, "getEpochInfo", serde_json::json!([])).await?;
+    Ok(info.epoch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto_solana::transactions::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+    #[test]
+    fn test_parse_token_2022_transfer_fee() {
+        let mut data = vec![0u8; ACCOUNT_TYPE_OFFSET];
+        data[DECIMALS_OFFSET] = 6;
+        data.push(ACCOUNT_TYPE_MINT);
+
+        // TransferFeeConfig: authorities, withheld amount, older and newer fees
+        data.extend_from_slice(&TRANSFER_FEE_CONFIG.to_le_bytes());
+        data.extend_from_slice(&108u16.to_le_bytes());
+        data.extend_from_slice(&[0u8; TRANSFER_FEES_OFFSET]);
+        for (epoch, maximum_fee, basis_points) in [(0u64, 1_000u64, 50u16), (500, 5_000, 100)] {
+            data.extend_from_slice(&epoch.to_le_bytes());
+            data.extend_from_slice(&maximum_fee.to_le_bytes());
+            data.extend_from_slice(&basis_points.to_le_bytes());
+        }
+
+        let mint = parse_token_mint("mint", TOKEN_2022_PROGRAM_ID, &data).unwrap();
+        assert_eq!(mint.program, TokenProgram::Token2022);
+        assert_eq!(mint.decimals, 6);
+
+        let fees = mint.transfer_fee.unwrap();
+        assert_eq!(fees.fee_at(499).fee_for(10_001), 51);
+        assert_eq!(fees.fee_at(500).fee_for(10_000), 100);
+        assert_eq!(fees.fee_at(500).fee_for(10_000_000), 5_000);
+
+        let legacy = parse_token_mint("mint", TOKEN_PROGRAM_ID, &data[..MINT_SIZE]).unwrap();
+        assert_eq!(legacy.program, TokenProgram::Spl);
+        assert!(legacy.transfer_fee.is_none());
+    }
+}
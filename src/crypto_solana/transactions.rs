@@ -4,13 +4,14 @@ use super::anchor::*;
 use super::dto::*;
 use crate::{IdosError, IdosResult};
 
-#[cfg(feature = "crypto_solana")]
+#[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
 use solana_sdk::{
     hash::Hash,
     instruction::AccountMeta as SdkAccountMeta,
+    message::{v0, AddressLookupTableAccount, VersionedMessage},
     pubkey::Pubkey,
     signature::{Keypair, Signer},
-    transaction::Transaction as SolanaTransaction,
+    transaction::{Transaction as SolanaTransaction, VersionedTransaction},
 };
 
 #[cfg(feature = "crypto_solana")]
@@ -21,9 +22,37 @@ use base64::{engine::general_purpose, Engine as _};
 // Reference: https://docs.solana.com/developing/runtime-facilities/programs
 pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
 pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"; // SPL Token program
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VZK9TqgxvTpRjrR1DVTSpuTKhTAcM"; // SPL Token-2022 (Token Extensions)
 pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"; // ATA program
 pub const SYSVAR_INSTRUCTIONS_ID: &str = "Sysvar1nstructions1111111111111111111111111"; // Sysvar for instruction introspection
 pub const ED25519_PROGRAM_ID: &str = "Ed25519SigVerify111111111111111111111111111"; // Ed25519 signature verification
+pub const TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"; // Metaplex Token Metadata
+pub const TOKEN_AUTH_RULES_PROGRAM_ID: &str = "auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg"; // Metaplex Token Auth Rules
+pub const BUBBLEGUM_PROGRAM_ID: &str = "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"; // Metaplex Bubblegum (compressed NFTs)
+pub const MPL_CORE_PROGRAM_ID: &str = "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d"; // Metaplex Core
+pub const SPL_NOOP_PROGRAM_ID: &str = "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV"; // SPL Noop (log wrapper)
+pub const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID: &str = "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK"; // SPL Account Compression
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111"; // Compute budget program
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: &str = "AddressLookupTab1e1111111111111111111111111"; // Address lookup table program
+
+/// Maximum serialized transaction size accepted by the network (IPv6 MTU minus headers)
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// Size of an SPL token account, used for its rent-exempt minimum
+pub const TOKEN_ACCOUNT_SIZE: usize = 165;
+
+/// Compute units granted per instruction when no limit is set
+const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u64 = 200_000;
+
+/// Most compute units a transaction may request
+const MAX_COMPUTE_UNIT_LIMIT: u64 = 1_400_000;
+
+/// Size of the lookup table account header that precedes the address list
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// Size of the concurrent merkle tree account header (account type, version, max buffer size,
+/// max depth, authority, creation slot, padding)
+const MERKLE_TREE_HEADER_SIZE: usize = 56;
 
 /// Account metadata for Solana instructions
 #[derive(Debug, Clone)]
@@ -59,12 +88,111 @@ pub struct TransactionInstruction {
     pub data: Vec<u8>,
 }
 
+/// Address lookup table resolved from chain, used to compile v0 messages
+#[derive(Debug, Clone)]
+pub struct AddressLookupTable {
+    pub key: [u8; 32],
+    pub addresses: Vec<[u8; 32]>,
+}
+
+impl AddressLookupTable {
+    /// Parse a lookup table from raw account data
+    pub fn from_account_data(key: [u8; 32], data: &[u8]) -> IdosResult<Self> {
+        if data.len() < LOOKUP_TABLE_META_SIZE || (data.len() - LOOKUP_TABLE_META_SIZE) % 32 != 0 {
+            return Err(IdosError::SerializationError(format!(
+                "Invalid address lookup table data length: {}",
+                data.len()
+            )));
+        }
+
+        let addresses = data[LOOKUP_TABLE_META_SIZE..]
+            .chunks_exact(32)
+            .map(|chunk| {
+                let mut address = [0u8; 32];
+                address.copy_from_slice(chunk);
+                address
+            })
+            .collect();
+
+        Ok(Self { key, addresses })
+    }
+
+    pub fn contains(&self, address: &[u8; 32]) -> bool {
+        self.addresses.contains(address)
+    }
+}
+
+/// Solana transaction message format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransactionVersion {
+    #[default]
+    Legacy,
+    /// Versioned message that can reference accounts through lookup tables
+    V0,
+}
+
+/// Decode a base58 public key
+pub fn decode_pubkey(address: &str) -> IdosResult<[u8; 32]> {
+    bs58::decode(address)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| IdosError::InvalidInput(format!("Invalid Solana address: {}", address)))
+}
+
+/// Build a SystemProgram transfer instruction
+pub fn build_transfer_sol_instruction(
+    from: &[u8; 32],
+    to: &[u8; 32],
+    lamports: u64,
+) -> TransactionInstruction {
+    // SystemInstruction::Transfer = 2 (u32 LE), followed by lamports (u64 LE)
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    TransactionInstruction {
+        program_id: [0u8; 32], // System program is the all-zero key
+        accounts: vec![
+            AccountMeta::writable(*from, true),
+            AccountMeta::writable(*to, false),
+        ],
+        data,
+    }
+}
+
+/// Build a ComputeBudget SetComputeUnitLimit instruction
+pub fn build_set_compute_unit_limit_instruction(units: u32) -> TransactionInstruction {
+    let mut data = vec![2u8];
+    data.extend_from_slice(&units.to_le_bytes());
+    compute_budget_instruction(data)
+}
+
+/// Build a ComputeBudget SetComputeUnitPrice instruction (price in micro-lamports per CU)
+pub fn build_set_compute_unit_price_instruction(micro_lamports: u64) -> TransactionInstruction {
+    let mut data = vec![3u8];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    compute_budget_instruction(data)
+}
+
+fn compute_budget_instruction(data: Vec<u8>) -> TransactionInstruction {
+    let mut program_id = [0u8; 32];
+    program_id.copy_from_slice(&bs58::decode(COMPUTE_BUDGET_PROGRAM_ID).into_vec().unwrap());
+
+    TransactionInstruction {
+        program_id,
+        accounts: vec![],
+        data,
+    }
+}
+
 /// Derive Associated Token Account address
 /// Simplified version - matches Unity's AssociatedTokenAccountProgram.DeriveAssociatedTokenAccount
+/// The token program is part of the seeds, so Token-2022 mints get a different address
 #[cfg(feature = "crypto_solana")]
 pub fn derive_associated_token_account(
     wallet_address: &[u8; 32],
     mint_address: &[u8; 32],
+    token_program: TokenProgram,
 ) -> IdosResult<[u8; 32]> {
     let ata_program_id = bs58::decode(ASSOCIATED_TOKEN_PROGRAM_ID)
         .into_vec()
@@ -74,7 +202,7 @@ pub fn derive_associated_token_account(
     ata_program_id_bytes.copy_from_slice(&ata_program_id);
 
     // PDA seeds: [wallet, token_program, mint]
-    let token_program_id = bs58::decode(TOKEN_PROGRAM_ID)
+    let token_program_id = bs58::decode(token_program.program_id())
         .into_vec()
         .map_err(|e| IdosError::Wallet(format!("Invalid token program ID: {}", e)))?;
 
@@ -86,9 +214,308 @@ pub fn derive_associated_token_account(
     Ok(pda)
 }
 
+/// Build an Associated Token Account program CreateIdempotent instruction
+/// Unlike Create, it doesn't fail when the account was created in the meantime
+#[cfg(feature = "crypto_solana")]
+pub fn build_create_associated_token_account_instruction(
+    payer: &[u8; 32],
+    wallet: &[u8; 32],
+    mint: &[u8; 32],
+    token_program: TokenProgram,
+) -> IdosResult<TransactionInstruction> {
+    let ata = derive_associated_token_account(wallet, mint, token_program)?;
+
+    Ok(TransactionInstruction {
+        program_id: decode_pubkey(ASSOCIATED_TOKEN_PROGRAM_ID)?,
+        accounts: vec![
+            AccountMeta::writable(*payer, true),
+            AccountMeta::writable(ata, false),
+            AccountMeta::read_only(*wallet, false),
+            AccountMeta::read_only(*mint, false),
+            AccountMeta::read_only(decode_pubkey(SYSTEM_PROGRAM_ID)?, false),
+            AccountMeta::read_only(decode_pubkey(token_program.program_id())?, false),
+        ],
+        data: vec![1], // CreateIdempotent
+    })
+}
+
+/// Build an SPL Token TransferChecked instruction
+#[cfg(feature = "crypto_solana")]
+#[allow(clippy::too_many_arguments)]
+pub fn build_transfer_checked_instruction(
+    source_ata: &[u8; 32],
+    mint: &[u8; 32],
+    destination_ata: &[u8; 32],
+    owner: &[u8; 32],
+    amount: u64,
+    decimals: u8,
+    token_program: TokenProgram,
+) -> IdosResult<TransactionInstruction> {
+    // TokenInstruction::TransferChecked = 12, followed by amount (u64 LE) and decimals (u8)
+    let mut data = vec![12u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+
+    token_transfer_instruction(
+        source_ata,
+        mint,
+        destination_ata,
+        owner,
+        token_program,
+        data,
+    )
+}
+
+/// Build a Token-2022 TransferCheckedWithFee instruction
+/// Fails on-chain unless `fee` matches the mint's current transfer fee, so the recipient
+/// gets exactly `amount - fee`
+#[cfg(feature = "crypto_solana")]
+pub fn build_transfer_checked_with_fee_instruction(
+    source_ata: &[u8; 32],
+    mint: &[u8; 32],
+    destination_ata: &[u8; 32],
+    owner: &[u8; 32],
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+) -> IdosResult<TransactionInstruction> {
+    // TransferFeeExtension = 26, TransferCheckedWithFee = 1, then amount, decimals and fee
+    let mut data = vec![26u8, 1];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    data.extend_from_slice(&fee.to_le_bytes());
+
+    token_transfer_instruction(
+        source_ata,
+        mint,
+        destination_ata,
+        owner,
+        TokenProgram::Token2022,
+        data,
+    )
+}
+
+#[cfg(feature = "crypto_solana")]
+fn token_transfer_instruction(
+    source_ata: &[u8; 32],
+    mint: &[u8; 32],
+    destination_ata: &[u8; 32],
+    owner: &[u8; 32],
+    token_program: TokenProgram,
+    data: Vec<u8>,
+) -> IdosResult<TransactionInstruction> {
+    Ok(TransactionInstruction {
+        program_id: decode_pubkey(token_program.program_id())?,
+        accounts: vec![
+            AccountMeta::writable(*source_ata, false),
+            AccountMeta::read_only(*mint, false),
+            AccountMeta::writable(*destination_ata, false),
+            AccountMeta::read_only(*owner, true),
+        ],
+        data,
+    })
+}
+
+/// Build a Token Metadata TransferV1 instruction for a programmable NFT
+/// Token records, edition and destination ATA are derived here; the program creates
+/// the destination token account when it doesn't exist
+#[cfg(feature = "crypto_solana")]
+pub fn build_transfer_programmable_nft_instruction(
+    owner: &[u8; 32],
+    destination_owner: &[u8; 32],
+    mint: &[u8; 32],
+    rule_set: Option<&[u8; 32]>,
+) -> IdosResult<TransactionInstruction> {
+    let metadata_program = decode_pubkey(TOKEN_METADATA_PROGRAM_ID)?;
+
+    let source_ata = derive_associated_token_account(owner, mint, TokenProgram::Spl)?;
+    let destination_ata =
+        derive_associated_token_account(destination_owner, mint, TokenProgram::Spl)?;
+
+    let (metadata, _) =
+        find_program_address(&[b"metadata", &metadata_program, mint], &metadata_program)?;
+    let (edition, _) = find_program_address(
+        &[b"metadata", &metadata_program, mint, b"edition"],
+        &metadata_program,
+    )?;
+    let (token_record, _) = find_program_address(
+        &[
+            b"metadata",
+            &metadata_program,
+            mint,
+            b"token_record",
+            &source_ata,
+        ],
+        &metadata_program,
+    )?;
+    let (destination_token_record, _) = find_program_address(
+        &[
+            b"metadata",
+            &metadata_program,
+            mint,
+            b"token_record",
+            &destination_ata,
+        ],
+        &metadata_program,
+    )?;
+
+    // Optional accounts that are absent are passed as the Token Metadata program itself
+    let (auth_rules_program, auth_rules) = match rule_set {
+        Some(rule_set) => (decode_pubkey(TOKEN_AUTH_RULES_PROGRAM_ID)?, *rule_set),
+        None => (metadata_program, metadata_program),
+    };
+
+    // Transfer = 49, TransferArgs::V1 = 0, amount (u64 LE), authorization_data = None
+    let mut data = vec![49u8, 0];
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.push(0);
+
+    Ok(TransactionInstruction {
+        program_id: metadata_program,
+        accounts: vec![
+            AccountMeta::writable(source_ata, false),
+            AccountMeta::read_only(*owner, false),
+            AccountMeta::writable(destination_ata, false),
+            AccountMeta::read_only(*destination_owner, false),
+            AccountMeta::read_only(*mint, false),
+            AccountMeta::writable(metadata, false),
+            AccountMeta::read_only(edition, false),
+            AccountMeta::writable(token_record, false),
+            AccountMeta::writable(destination_token_record, false),
+            AccountMeta::read_only(*owner, true), // authority
+            AccountMeta::writable(*owner, true),  // payer
+            AccountMeta::read_only(decode_pubkey(SYSTEM_PROGRAM_ID)?, false),
+            AccountMeta::read_only(decode_pubkey(SYSVAR_INSTRUCTIONS_ID)?, false),
+            AccountMeta::read_only(decode_pubkey(TOKEN_PROGRAM_ID)?, false),
+            AccountMeta::read_only(decode_pubkey(ASSOCIATED_TOKEN_PROGRAM_ID)?, false),
+            AccountMeta::read_only(auth_rules_program, false),
+            AccountMeta::read_only(auth_rules, false),
+        ],
+        data,
+    })
+}
+
+/// Leaf of a compressed NFT as reported by DAS (`getAsset` + `getAssetProof`)
+#[derive(Debug, Clone)]
+pub struct CompressedNftLeaf {
+    pub merkle_tree: [u8; 32],
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    /// Proof nodes, already trimmed by the tree's canopy depth
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Build a Bubblegum transfer instruction for a compressed NFT
+#[cfg(feature = "crypto_solana")]
+pub fn build_bubblegum_transfer_instruction(
+    leaf_owner: &[u8; 32],
+    leaf_delegate: &[u8; 32],
+    new_leaf_owner: &[u8; 32],
+    leaf: &CompressedNftLeaf,
+) -> IdosResult<TransactionInstruction> {
+    let bubblegum_program = decode_pubkey(BUBBLEGUM_PROGRAM_ID)?;
+    let (tree_authority, _) = find_program_address(&[&leaf.merkle_tree], &bubblegum_program)?;
+
+    // Args: root, data_hash, creator_hash, nonce (u64), index (u32)
+    let data = borsh_cat(&[
+        &anchor_discriminator("transfer"),
+        &leaf.root,
+        &leaf.data_hash,
+        &leaf.creator_hash,
+        &encode_u64(leaf.nonce),
+        &leaf.index.to_le_bytes(),
+    ]);
+
+    let mut accounts = vec![
+        AccountMeta::read_only(tree_authority, false),
+        AccountMeta::read_only(*leaf_owner, true),
+        AccountMeta::read_only(*leaf_delegate, false),
+        AccountMeta::read_only(*new_leaf_owner, false),
+        AccountMeta::writable(leaf.merkle_tree, false),
+        AccountMeta::read_only(decode_pubkey(SPL_NOOP_PROGRAM_ID)?, false),
+        AccountMeta::read_only(decode_pubkey(SPL_ACCOUNT_COMPRESSION_PROGRAM_ID)?, false),
+        AccountMeta::read_only(decode_pubkey(SYSTEM_PROGRAM_ID)?, false),
+    ];
+    // Proof nodes are passed as remaining accounts
+    accounts.extend(
+        leaf.proof
+            .iter()
+            .map(|node| AccountMeta::read_only(*node, false)),
+    );
+
+    Ok(TransactionInstruction {
+        program_id: bubblegum_program,
+        accounts,
+        data,
+    })
+}
+
+/// Build a Metaplex Core TransferV1 instruction
+/// Assets that belong to a collection must pass the collection account
+#[cfg(feature = "crypto_solana")]
+pub fn build_core_transfer_instruction(
+    asset: &[u8; 32],
+    collection: Option<&[u8; 32]>,
+    owner: &[u8; 32],
+    new_owner: &[u8; 32],
+) -> IdosResult<TransactionInstruction> {
+    let core_program = decode_pubkey(MPL_CORE_PROGRAM_ID)?;
+    // Omitted optional accounts are passed as the Core program id
+    let omitted = AccountMeta::read_only(core_program, false);
+
+    Ok(TransactionInstruction {
+        program_id: core_program,
+        accounts: vec![
+            AccountMeta::writable(*asset, false),
+            collection
+                .map(|collection| AccountMeta::read_only(*collection, false))
+                .unwrap_or_else(|| omitted.clone()),
+            AccountMeta::writable(*owner, true), // payer, also the transfer authority
+            omitted.clone(),                     // authority (defaults to payer)
+            AccountMeta::read_only(*new_owner, false),
+            omitted.clone(), // system program
+            omitted,         // log wrapper
+        ],
+        // TransferV1 = 14, followed by compression_proof: Option<CompressionProof> = None
+        data: vec![14, 0],
+    })
+}
+
+/// Canopy depth of a concurrent merkle tree, computed from its account data
+/// The canopy caches the top of the tree on chain, so that many proof nodes can be omitted
+pub fn merkle_tree_canopy_depth(tree_account_data: &[u8]) -> IdosResult<u32> {
+    if tree_account_data.len() < MERKLE_TREE_HEADER_SIZE {
+        return Err(IdosError::SerializationError(
+            "Invalid merkle tree account data".to_string(),
+        ));
+    }
+
+    let max_buffer_size = u32::from_le_bytes(tree_account_data[2..6].try_into().unwrap()) as usize;
+    let max_depth = u32::from_le_bytes(tree_account_data[6..10].try_into().unwrap()) as usize;
+
+    // sequence number + active index + buffer size, changelog buffer, rightmost proof
+    let path_size = 32 * max_depth + 32 + 4 + 4;
+    let tree_size = 8 + 8 + 8 + max_buffer_size * path_size + path_size;
+
+    let canopy_bytes = tree_account_data
+        .len()
+        .checked_sub(MERKLE_TREE_HEADER_SIZE + tree_size)
+        .ok_or_else(|| {
+            IdosError::SerializationError("Invalid merkle tree account size".to_string())
+        })?;
+
+    // The canopy stores 2^(depth+1) - 2 nodes
+    let nodes = canopy_bytes / 32 + 2;
+    Ok(nodes.trailing_zeros().saturating_sub(1))
+}
+
 /// Build Anchor instruction for deposit_spl
 /// Matches Unity SDK's DepositSplAsync instruction building
 #[cfg(feature = "crypto_solana")]
+#[allow(clippy::too_many_arguments)]
 pub fn build_deposit_spl_instruction(
     program_id: &[u8; 32],
     config_pda: &[u8; 32],
@@ -99,6 +526,7 @@ pub fn build_deposit_spl_instruction(
     vault_ata: &[u8; 32],
     amount: u64,
     user_id: &str,
+    token_program: TokenProgram,
 ) -> TransactionInstruction {
     // Anchor discriminator for "deposit_spl"
     let discriminator = anchor_discriminator("deposit_spl");
@@ -110,7 +538,7 @@ pub fn build_deposit_spl_instruction(
     let data = borsh_cat(&[&discriminator, &amount_bytes, &user_id_bytes]);
 
     // System program IDs
-    let token_program = bs58::decode(TOKEN_PROGRAM_ID).into_vec().unwrap();
+    let token_program = bs58::decode(token_program.program_id()).into_vec().unwrap();
     let ata_program = bs58::decode(ASSOCIATED_TOKEN_PROGRAM_ID)
         .into_vec()
         .unwrap();
@@ -143,9 +571,47 @@ pub fn build_deposit_spl_instruction(
     }
 }
 
+/// Build Anchor instruction for deposit_nft
+/// Same accounts as deposit_spl; the whole supply of 1 is moved into the vault
+#[cfg(feature = "crypto_solana")]
+#[allow(clippy::too_many_arguments)]
+pub fn build_deposit_nft_instruction(
+    program_id: &[u8; 32],
+    config_pda: &[u8; 32],
+    vault_pda: &[u8; 32],
+    mint: &[u8; 32],
+    user_pubkey: &[u8; 32],
+    user_ata: &[u8; 32],
+    vault_ata: &[u8; 32],
+    user_id: &str,
+    token_program: TokenProgram,
+) -> TransactionInstruction {
+    let discriminator = anchor_discriminator("deposit_nft");
+    let data = borsh_cat(&[&discriminator, &encode_string(user_id)]);
+
+    let accounts = vec![
+        AccountMeta::read_only(*config_pda, false),
+        AccountMeta::writable(*vault_pda, false),
+        AccountMeta::read_only(*mint, false),
+        AccountMeta::read_only(*user_pubkey, true), // user signer
+        AccountMeta::writable(*user_ata, false),
+        AccountMeta::writable(*vault_ata, false),
+        AccountMeta::read_only(decode_pubkey(token_program.program_id()).unwrap(), false),
+        AccountMeta::read_only(decode_pubkey(ASSOCIATED_TOKEN_PROGRAM_ID).unwrap(), false),
+        AccountMeta::read_only(decode_pubkey(SYSTEM_PROGRAM_ID).unwrap(), false),
+    ];
+
+    TransactionInstruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
 /// Build Anchor instruction for withdraw_spl
 /// Matches Unity SDK's WithdrawSplAsync instruction building
 #[cfg(feature = "crypto_solana")]
+#[allow(clippy::too_many_arguments)]
 pub fn build_withdraw_spl_instruction(
     program_id: &[u8; 32],
     config_pda: &[u8; 32],
@@ -160,6 +626,7 @@ pub fn build_withdraw_spl_instruction(
     nonce: u64,
     user_id: &str,
     sig_ix_index: u8,
+    token_program: TokenProgram,
 ) -> TransactionInstruction {
     // Anchor discriminator for "withdraw_spl"
     let discriminator = anchor_discriminator("withdraw_spl");
@@ -180,7 +647,7 @@ pub fn build_withdraw_spl_instruction(
 
     // System program IDs
     let sysvar_instructions = bs58::decode(SYSVAR_INSTRUCTIONS_ID).into_vec().unwrap();
-    let token_program = bs58::decode(TOKEN_PROGRAM_ID).into_vec().unwrap();
+    let token_program = bs58::decode(token_program.program_id()).into_vec().unwrap();
     let ata_program = bs58::decode(ASSOCIATED_TOKEN_PROGRAM_ID)
         .into_vec()
         .unwrap();
@@ -219,10 +686,10 @@ pub fn build_withdraw_spl_instruction(
 }
 
 // ==================== TRANSACTION SERIALIZATION & SIGNING ====================
-// Full transaction support using solana-sdk
+// Native builds sign through solana-sdk; WASM builds compile legacy messages in pure Rust
 
 /// Convert our TransactionInstruction to solana-sdk Instruction
-#[cfg(feature = "crypto_solana")]
+#[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
 fn to_solana_instruction(ix: &TransactionInstruction) -> solana_sdk::instruction::Instruction {
     let program_id = Pubkey::new_from_array(ix.program_id);
     let accounts: Vec<SdkAccountMeta> = ix
@@ -242,13 +709,63 @@ fn to_solana_instruction(ix: &TransactionInstruction) -> solana_sdk::instruction
     }
 }
 
+/// Parse a keypair from a 64-byte keypair or a 32-byte secret key
+#[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
+fn parse_keypair(keypair_bytes: &[u8]) -> IdosResult<Keypair> {
+    if keypair_bytes.len() == 64 {
+        // Full keypair (secret + public) - Use first 32 bytes as secret key
+        let secret_bytes: [u8; 32] = keypair_bytes[..32].try_into().unwrap();
+        Ok(Keypair::new_from_array(secret_bytes))
+    } else if keypair_bytes.len() == 32 {
+        // Just secret key
+        let secret_bytes: [u8; 32] = keypair_bytes.try_into().unwrap();
+        Ok(Keypair::new_from_array(secret_bytes))
+    } else {
+        Err(IdosError::Wallet(format!(
+            "Invalid keypair length: {}",
+            keypair_bytes.len()
+        )))
+    }
+}
+
+/// Serialize a signed transaction to wire bytes
+#[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
+fn serialize_transaction<T: serde::Serialize>(transaction: &T) -> IdosResult<Vec<u8>> {
+    // bincode v2.0 with serde compatibility - solana transactions implement serde::Serialize
+    let config = bincode::config::standard()
+        .with_little_endian()
+        .with_fixed_int_encoding();
+
+    bincode::serde::encode_to_vec(transaction, config).map_err(|e| {
+        IdosError::SerializationError(format!("Failed to serialize transaction: {}", e))
+    })
+}
+
+/// Append a compact-u16 (shortvec) length
+fn encode_compact_u16(mut value: usize, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        byte |= 0x80;
+        out.push(byte);
+    }
+}
+
 /// Transaction builder that can sign and serialize transactions
 #[cfg(feature = "crypto_solana")]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TransactionBuilder {
     pub instructions: Vec<TransactionInstruction>,
     pub fee_payer: [u8; 32],
     pub recent_blockhash: Option<String>,
+    pub version: TransactionVersion,
+    pub address_lookup_tables: Vec<AddressLookupTable>,
+    pub compute_unit_limit: Option<u32>,
+    pub compute_unit_price: Option<u64>,
 }
 
 #[cfg(feature = "crypto_solana")]
@@ -259,6 +776,10 @@ impl TransactionBuilder {
             instructions: Vec::new(),
             fee_payer,
             recent_blockhash: None,
+            version: TransactionVersion::Legacy,
+            address_lookup_tables: Vec::new(),
+            compute_unit_limit: None,
+            compute_unit_price: None,
         }
     }
 
@@ -274,6 +795,111 @@ impl TransactionBuilder {
         self
     }
 
+    /// Set the message format explicitly
+    pub fn set_version(&mut self, version: TransactionVersion) -> &mut Self {
+        self.version = version;
+        self
+    }
+
+    /// Add an address lookup table (switches the transaction to v0)
+    pub fn add_address_lookup_table(&mut self, table: AddressLookupTable) -> &mut Self {
+        self.address_lookup_tables.push(table);
+        self.version = TransactionVersion::V0;
+        self
+    }
+
+    /// Cap the compute units the transaction may consume
+    pub fn set_compute_unit_limit(&mut self, units: u32) -> &mut Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// Set the priority fee in micro-lamports per compute unit
+    pub fn set_compute_unit_price(&mut self, micro_lamports: u64) -> &mut Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    /// Compute budget instructions prepended to the transaction
+    pub fn compute_budget_instructions(&self) -> Vec<TransactionInstruction> {
+        let mut instructions = Vec::new();
+        if let Some(units) = self.compute_unit_limit {
+            instructions.push(build_set_compute_unit_limit_instruction(units));
+        }
+        if let Some(price) = self.compute_unit_price.filter(|p| *p > 0) {
+            instructions.push(build_set_compute_unit_price_instruction(price));
+        }
+        instructions
+    }
+
+    /// Number of instructions placed before the ones added with `add_instruction`
+    /// (instruction-introspection indices such as `sig_ix_index` must be shifted by this)
+    pub fn compute_budget_instruction_count(&self) -> usize {
+        self.compute_budget_instructions().len()
+    }
+
+    /// Writable accounts touched by the transaction (base58), used for fee estimation
+    pub fn writable_accounts(&self) -> Vec<String> {
+        let mut accounts: Vec<[u8; 32]> = vec![self.fee_payer];
+        for acc in self.instructions.iter().flat_map(|ix| &ix.accounts) {
+            if acc.is_writable && !accounts.contains(&acc.pubkey) {
+                accounts.push(acc.pubkey);
+            }
+        }
+        accounts
+            .iter()
+            .map(|a| bs58::encode(a).into_string())
+            .collect()
+    }
+
+    /// Signatures the network charges for: every signer plus Ed25519 precompile signatures
+    pub fn signature_count(&self) -> usize {
+        let mut signers: Vec<[u8; 32]> = vec![self.fee_payer];
+        for acc in self.instructions.iter().flat_map(|ix| &ix.accounts) {
+            if acc.is_signer && !signers.contains(&acc.pubkey) {
+                signers.push(acc.pubkey);
+            }
+        }
+
+        let ed25519_program = decode_pubkey(ED25519_PROGRAM_ID).ok();
+        let precompile_signatures: usize = self
+            .instructions
+            .iter()
+            .filter(|ix| Some(ix.program_id) == ed25519_program)
+            .map(|ix| ix.data.first().copied().unwrap_or(0) as usize)
+            .sum();
+
+        signers.len() + precompile_signatures
+    }
+
+    /// Fee the payer is charged: signature fees plus the priority fee of the compute budget
+    pub fn estimate_fee(&self) -> u64 {
+        let units = self.compute_unit_limit.map(u64::from).unwrap_or_else(|| {
+            (self.instructions.len() as u64 * DEFAULT_INSTRUCTION_COMPUTE_UNITS)
+                .min(MAX_COMPUTE_UNIT_LIMIT)
+        });
+        let price = self.compute_unit_price.unwrap_or(0);
+        let priority_fee = (price as u128 * units as u128).div_ceil(1_000_000) as u64;
+
+        estimate_transaction_fee(self.signature_count()) + priority_fee
+    }
+
+    /// Associated token accounts the transaction creates (the payer funds their rent)
+    pub fn created_token_account_count(&self) -> usize {
+        let ata_program = decode_pubkey(ASSOCIATED_TOKEN_PROGRAM_ID).ok();
+        self.instructions
+            .iter()
+            .filter(|ix| Some(ix.program_id) == ata_program)
+            .count()
+    }
+
+    /// Compute budget instructions followed by the user instructions
+    fn all_instructions(&self) -> Vec<TransactionInstruction> {
+        let mut instructions = self.compute_budget_instructions();
+        instructions.extend(self.instructions.iter().cloned());
+        instructions
+    }
+
     /// Sign the transaction with the given keypair
     /// Returns the signed transaction bytes serialized to base64 (ready for RPC)
     pub fn sign_and_serialize(&self, keypair_bytes: &[u8]) -> IdosResult<String> {
@@ -288,21 +914,23 @@ impl TransactionBuilder {
             .as_ref()
             .ok_or_else(|| IdosError::Wallet("Recent blockhash not set".to_string()))?;
 
-        // Parse keypair from bytes
-        let keypair = if keypair_bytes.len() == 64 {
-            // Full keypair (secret + public) - Use first 32 bytes as secret key
-            let secret_bytes: [u8; 32] = keypair_bytes[..32].try_into().unwrap();
-            Keypair::new_from_array(secret_bytes)
-        } else if keypair_bytes.len() == 32 {
-            // Just secret key
-            let secret_bytes: [u8; 32] = keypair_bytes.try_into().unwrap();
-            Keypair::new_from_array(secret_bytes)
-        } else {
+        let serialized = self.sign_to_bytes(keypair_bytes, blockhash_str)?;
+
+        if serialized.len() > PACKET_DATA_SIZE {
             return Err(IdosError::Wallet(format!(
-                "Invalid keypair length: {}",
-                keypair_bytes.len()
+                "Transaction too large: {} bytes (max {}). Add an address lookup table to shrink it",
+                serialized.len(),
+                PACKET_DATA_SIZE
             )));
-        };
+        }
+
+        Ok(general_purpose::STANDARD.encode(&serialized))
+    }
+
+    /// Sign with solana-sdk and return the wire bytes (native)
+    #[cfg(not(target_arch = "wasm32"))]
+    fn sign_to_bytes(&self, keypair_bytes: &[u8], blockhash_str: &str) -> IdosResult<Vec<u8>> {
+        let keypair = parse_keypair(keypair_bytes)?;
 
         // Parse blockhash
         let blockhash = blockhash_str
@@ -311,29 +939,180 @@ impl TransactionBuilder {
 
         // Convert instructions to solana-sdk format
         let solana_instructions: Vec<solana_sdk::instruction::Instruction> = self
-            .instructions
+            .all_instructions()
             .iter()
             .map(to_solana_instruction)
             .collect();
 
-        // Create and sign transaction
-        let mut transaction =
-            SolanaTransaction::new_with_payer(&solana_instructions, Some(&keypair.pubkey()));
+        match self.version {
+            TransactionVersion::Legacy => {
+                // Create and sign transaction
+                let mut transaction = SolanaTransaction::new_with_payer(
+                    &solana_instructions,
+                    Some(&keypair.pubkey()),
+                );
+
+                transaction.message.recent_blockhash = blockhash;
+                transaction.sign(&[&keypair], blockhash);
+
+                serialize_transaction(&transaction)
+            }
+            TransactionVersion::V0 => {
+                let lookup_tables: Vec<AddressLookupTableAccount> = self
+                    .address_lookup_tables
+                    .iter()
+                    .map(|table| AddressLookupTableAccount {
+                        key: Pubkey::new_from_array(table.key),
+                        addresses: table
+                            .addresses
+                            .iter()
+                            .map(|a| Pubkey::new_from_array(*a))
+                            .collect(),
+                    })
+                    .collect();
+
+                let message = v0::Message::try_compile(
+                    &keypair.pubkey(),
+                    &solana_instructions,
+                    &lookup_tables,
+                    blockhash,
+                )
+                .map_err(|e| IdosError::Wallet(format!("Failed to compile v0 message: {}", e)))?;
+
+                let transaction =
+                    VersionedTransaction::try_new(VersionedMessage::V0(message), &[&keypair])
+                        .map_err(|e| {
+                            IdosError::Wallet(format!("Failed to sign transaction: {}", e))
+                        })?;
+
+                serialize_transaction(&transaction)
+            }
+        }
+    }
 
-        transaction.message.recent_blockhash = blockhash;
-        transaction.sign(&[&keypair], blockhash);
+    /// Sign the compiled legacy message with ed25519-dalek and return the wire bytes (WASM)
+    #[cfg(target_arch = "wasm32")]
+    fn sign_to_bytes(&self, keypair_bytes: &[u8], _blockhash_str: &str) -> IdosResult<Vec<u8>> {
+        use ed25519_dalek::{Signer as _, SigningKey};
 
-        // Serialize to bytes then base64 (bincode v2.0 with serde compatibility)
-        // Note: Solana Transaction implements serde::Serialize, so we use serde module
-        let config = bincode::config::standard()
-            .with_little_endian()
-            .with_fixed_int_encoding();
+        if self.version == TransactionVersion::V0 {
+            return Err(IdosError::PlatformNotSupported(
+                "Versioned transactions are not supported on WASM".to_string(),
+            ));
+        }
 
-        let serialized = bincode::serde::encode_to_vec(&transaction, config).map_err(|e| {
-            IdosError::SerializationError(format!("Failed to serialize transaction: {}", e))
-        })?;
+        let secret: [u8; 32] = keypair_bytes
+            .get(..32)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                IdosError::Wallet(format!("Invalid keypair length: {}", keypair_bytes.len()))
+            })?;
+        let signing_key = SigningKey::from_bytes(&secret);
 
-        Ok(general_purpose::STANDARD.encode(&serialized))
+        if signing_key.verifying_key().to_bytes() != self.fee_payer {
+            return Err(IdosError::Wallet(
+                "Keypair does not match the fee payer".to_string(),
+            ));
+        }
+
+        let message = self.serialize_message()?;
+        let signature = signing_key.sign(&message);
+
+        let mut serialized = Vec::with_capacity(1 + 64 + message.len());
+        encode_compact_u16(1, &mut serialized);
+        serialized.extend_from_slice(&signature.to_bytes());
+        serialized.extend_from_slice(&message);
+        Ok(serialized)
+    }
+
+    /// Legacy transaction with zeroed signature slots, for wallets that sign full transactions
+    /// (wallet-standard `solana:signAndSendTransaction`)
+    pub fn serialize_unsigned(&self) -> IdosResult<Vec<u8>> {
+        let message = self.serialize_message()?;
+        // First header byte is the number of required signatures
+        let signers = message[0] as usize;
+
+        let mut serialized = Vec::with_capacity(1 + 64 * signers + message.len());
+        encode_compact_u16(signers, &mut serialized);
+        serialized.resize(serialized.len() + 64 * signers, 0);
+        serialized.extend_from_slice(&message);
+        Ok(serialized)
+    }
+
+    /// Compile the legacy message (header, account keys, blockhash, instructions)
+    /// These are the bytes a wallet signs, e.g. for Phantom's `signAndSendTransaction`
+    pub fn serialize_message(&self) -> IdosResult<Vec<u8>> {
+        let blockhash: [u8; 32] = self
+            .recent_blockhash
+            .as_deref()
+            .ok_or_else(|| IdosError::Wallet("Recent blockhash not set".to_string()))
+            .and_then(|hash| {
+                decode_pubkey(hash)
+                    .map_err(|_| IdosError::Wallet(format!("Invalid blockhash: {}", hash)))
+            })?;
+
+        let instructions = self.all_instructions();
+
+        // Unique keys with merged signer/writable flags, fee payer first
+        let mut keys: Vec<([u8; 32], bool, bool)> = vec![(self.fee_payer, true, true)];
+        let mut add_key = |pubkey: [u8; 32], is_signer: bool, is_writable: bool| match keys
+            .iter_mut()
+            .find(|(key, _, _)| *key == pubkey)
+        {
+            Some(entry) => {
+                entry.1 |= is_signer;
+                entry.2 |= is_writable;
+            }
+            None => keys.push((pubkey, is_signer, is_writable)),
+        };
+        for ix in &instructions {
+            for acc in &ix.accounts {
+                add_key(acc.pubkey, acc.is_signer, acc.is_writable);
+            }
+            add_key(ix.program_id, false, false);
+        }
+
+        // Writable signers, readonly signers, writable non-signers, readonly non-signers
+        keys[1..].sort_by_key(|(_, is_signer, is_writable)| (!is_signer, !is_writable));
+
+        if keys.len() > 256 {
+            return Err(IdosError::Wallet(format!(
+                "Too many accounts in transaction: {}",
+                keys.len()
+            )));
+        }
+
+        let num_signers = keys.iter().filter(|(_, s, _)| *s).count();
+        let num_readonly_signed = keys.iter().filter(|(_, s, w)| *s && !*w).count();
+        let num_readonly_unsigned = keys.iter().filter(|(_, s, w)| !*s && !*w).count();
+        let index_of =
+            |pubkey: &[u8; 32]| keys.iter().position(|(key, _, _)| key == pubkey).unwrap() as u8;
+
+        let mut message = vec![
+            num_signers as u8,
+            num_readonly_signed as u8,
+            num_readonly_unsigned as u8,
+        ];
+
+        encode_compact_u16(keys.len(), &mut message);
+        for (key, _, _) in &keys {
+            message.extend_from_slice(key);
+        }
+
+        message.extend_from_slice(&blockhash);
+
+        encode_compact_u16(instructions.len(), &mut message);
+        for ix in &instructions {
+            message.push(index_of(&ix.program_id));
+            encode_compact_u16(ix.accounts.len(), &mut message);
+            for acc in &ix.accounts {
+                message.push(index_of(&acc.pubkey));
+            }
+            encode_compact_u16(ix.data.len(), &mut message);
+            message.extend_from_slice(&ix.data);
+        }
+
+        Ok(message)
     }
 
     /// Get the transaction size estimate in bytes (for fee calculation)
@@ -345,24 +1124,57 @@ impl TransactionBuilder {
         // - Account keys: variable
         // - Instructions: variable
 
-        let base_size = 64 + 3 + 32;
+        let mut base_size = 64 + 3 + 32;
+        let instructions = self.all_instructions();
 
         // Estimate account keys (unique pubkeys)
         let mut unique_pubkeys = std::collections::HashSet::new();
         unique_pubkeys.insert(self.fee_payer);
 
-        for ix in &self.instructions {
+        for ix in &instructions {
             unique_pubkeys.insert(ix.program_id);
             for acc in &ix.accounts {
                 unique_pubkeys.insert(acc.pubkey);
             }
         }
 
-        let accounts_size = unique_pubkeys.len() * 32;
+        let accounts_size = match self.version {
+            TransactionVersion::Legacy => unique_pubkeys.len() * 32,
+            TransactionVersion::V0 => {
+                // Version prefix + lookup table headers (key + two index lengths)
+                base_size += 1 + self.address_lookup_tables.len() * (32 + 2);
+
+                // Fee payer, signers and invoked programs must stay static; other
+                // accounts found in a lookup table cost a 1-byte index instead of a key
+                let static_keys: std::collections::HashSet<[u8; 32]> = instructions
+                    .iter()
+                    .flat_map(|ix| {
+                        std::iter::once(ix.program_id).chain(
+                            ix.accounts
+                                .iter()
+                                .filter(|acc| acc.is_signer)
+                                .map(|acc| acc.pubkey),
+                        )
+                    })
+                    .chain(std::iter::once(self.fee_payer))
+                    .collect();
+
+                unique_pubkeys
+                    .iter()
+                    .map(|key| {
+                        let in_table = self.address_lookup_tables.iter().any(|t| t.contains(key));
+                        if in_table && !static_keys.contains(key) {
+                            1
+                        } else {
+                            32
+                        }
+                    })
+                    .sum()
+            }
+        };
 
         // Estimate instructions size
-        let instructions_size: usize = self
-            .instructions
+        let instructions_size: usize = instructions
             .iter()
             .map(|ix| {
                 1 + // program_id index
@@ -377,6 +1189,93 @@ impl TransactionBuilder {
     }
 }
 
+/// Fetch and parse an address lookup table account
+#[cfg(feature = "crypto_solana")]
+pub async fn fetch_address_lookup_table(
+    rpc_url: &str,
+    address: &str,
+) -> IdosResult<AddressLookupTable> {
+    let key_bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|_| IdosError::InvalidInput("Invalid lookup table address".to_string()))?;
+    let key: [u8; 32] = key_bytes
+        .try_into()
+        .map_err(|_| IdosError::InvalidInput("Invalid lookup table address".to_string()))?;
+
+    let params = serde_json::json!([address, { "encoding": "base64" }]);
+    let response: AccountInfoResponse =
+        super::helper::send_solana_rpc_request(rpc_url, "getAccountInfo", params).await?;
+
+    let account = response.value.ok_or_else(|| {
+        IdosError::InvalidInput(format!("Address lookup table {} not found", address))
+    })?;
+
+    if account.owner != ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+        return Err(IdosError::InvalidInput(format!(
+            "Account {} is not an address lookup table",
+            address
+        )));
+    }
+
+    let data = general_purpose::STANDARD
+        .decode(&account.data.0)
+        .map_err(|e| IdosError::SerializationError(format!("Invalid account data: {}", e)))?;
+
+    AddressLookupTable::from_account_data(key, &data)
+}
+
+/// Fetch every lookup table in the list
+#[cfg(feature = "crypto_solana")]
+pub async fn fetch_address_lookup_tables(
+    rpc_url: &str,
+    addresses: &[String],
+) -> IdosResult<Vec<AddressLookupTable>> {
+    let mut tables = Vec::with_capacity(addresses.len());
+    for address in addresses {
+        tables.push(fetch_address_lookup_table(rpc_url, address).await?);
+    }
+    Ok(tables)
+}
+
+/// Fetch recent prioritization fees paid for transactions touching the given accounts
+#[cfg(feature = "crypto_solana")]
+pub async fn get_recent_prioritization_fees(
+    rpc_url: &str,
+    writable_accounts: &[String],
+) -> IdosResult<Vec<PrioritizationFee>> {
+    let params = serde_json::json!([writable_accounts]);
+    super::helper::send_solana_rpc_request(rpc_url, "getRecentPrioritizationFees", params).await
+}
+
+/// Estimate a compute unit price (micro-lamports) from recent prioritization fees
+/// Picks the given percentile of recent non-zero fees, capped at `max_micro_lamports`
+#[cfg(feature = "crypto_solana")]
+pub async fn estimate_priority_fee(
+    rpc_url: &str,
+    writable_accounts: &[String],
+    percentile: u8,
+    max_micro_lamports: u64,
+) -> IdosResult<u64> {
+    let fees = get_recent_prioritization_fees(rpc_url, writable_accounts).await?;
+    Ok(priority_fee_percentile(&fees, percentile).min(max_micro_lamports))
+}
+
+/// Percentile of non-zero prioritization fees (0 when there are none)
+fn priority_fee_percentile(fees: &[PrioritizationFee], percentile: u8) -> u64 {
+    let mut values: Vec<u64> = fees
+        .iter()
+        .map(|f| f.prioritization_fee)
+        .filter(|f| *f > 0)
+        .collect();
+    if values.is_empty() {
+        return 0;
+    }
+
+    values.sort_unstable();
+    let rank = (values.len() - 1) * percentile.min(100) as usize / 100;
+    values[rank]
+}
+
 /// Estimate transaction fees for Solana
 /// Solana uses a deterministic fee model based on signatures
 #[cfg(feature = "crypto_solana")]
@@ -394,34 +1293,17 @@ pub fn estimate_transaction_fee(num_signatures: usize) -> u64 {
 pub async fn simulate_transaction(
     rpc_url: &str,
     transaction_base64: &str,
+    commitment: Commitment,
 ) -> IdosResult<SimulationResult> {
-    let client = reqwest::Client::new();
-    let request = SimulateTransactionRequest {
-        jsonrpc: "2.0".to_string(),
-        id: 1,
-        method: "simulateTransaction".to_string(),
-        params: (
-            transaction_base64.to_string(),
-            SimulateConfig {
-                encoding: "base64".to_string(),
-                commitment: Some("processed".to_string()),
-            },
-        ),
+    let config = SimulateConfig {
+        encoding: "base64".to_string(),
+        commitment: Some(commitment),
     };
+    let params = serde_json::json!([transaction_base64, config]);
+    let result: SimulateTransactionResult =
+        super::helper::send_solana_rpc_request(rpc_url, "simulateTransaction", params).await?;
 
-    let response = client
-        .post(rpc_url)
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| IdosError::NetworkError(format!("Simulation request failed: {}", e)))?
-        .json::<SimulateTransactionResponse>()
-        .await
-        .map_err(|e| {
-            IdosError::NetworkError(format!("Failed to parse simulation response: {}", e))
-        })?;
-
-    let value = response.result.value;
+    let value = result.value;
 
     Ok(SimulationResult {
         success: value.err.is_none(),
@@ -432,66 +1314,261 @@ pub async fn simulate_transaction(
 }
 
 /// Get recent blockhash from Solana RPC
-#[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
-pub async fn get_recent_blockhash(rpc_url: &str) -> IdosResult<String> {
-    let client = reqwest::Client::new();
-    let request = GetBlockhashRequest {
-        jsonrpc: "2.0".to_string(),
-        id: 1,
-        method: "getLatestBlockhash".to_string(),
-        params: vec![serde_json::json!({
-            "commitment": "finalized"
-        })],
-    };
+#[cfg(feature = "crypto_solana")]
+pub async fn get_recent_blockhash(rpc_url: &str, commitment: Commitment) -> IdosResult<String> {
+    let params = serde_json::json!([{ "commitment": commitment }]);
+    let result: BlockhashResult =
+        super::helper::send_solana_rpc_request(rpc_url, "getLatestBlockhash", params).await?;
+    Ok(result.value.blockhash)
+}
 
-    let response = client
-        .post(rpc_url)
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| IdosError::NetworkError(format!("Blockhash request failed: {}", e)))?
-        .json::<GetBlockhashResponse>()
+/// Lamports an account of `data_len` bytes must hold to be rent exempt
+#[cfg(feature = "crypto_solana")]
+pub async fn get_minimum_balance_for_rent_exemption(
+    rpc_url: &str,
+    data_len: usize,
+) -> IdosResult<u64> {
+    let params = serde_json::json!([data_len]);
+    super::helper::send_solana_rpc_request(rpc_url, "getMinimumBalanceForRentExemption", params)
         .await
-        .map_err(|e| {
-            IdosError::NetworkError(format!("Failed to parse blockhash response: {}", e))
-        })?;
-
-    Ok(response.result.value.blockhash)
 }
 
 /// Send a signed transaction to Solana RPC
-#[cfg(all(feature = "crypto_solana", not(target_arch = "wasm32")))]
+/// RPC errors (including failed preflight simulation) are returned as `NetworkError`
+#[cfg(feature = "crypto_solana")]
 pub async fn send_transaction(
     rpc_url: &str,
     transaction_base64: &str,
     skip_preflight: bool,
+    preflight_commitment: Commitment,
 ) -> IdosResult<String> {
-    let client = reqwest::Client::new();
-    let request = SendTransactionRequest {
-        jsonrpc: "2.0".to_string(),
-        id: 1,
-        method: "sendTransaction".to_string(),
-        params: (
-            transaction_base64.to_string(),
-            SendTransactionConfig {
-                encoding: "base64".to_string(),
-                skip_preflight,
-                preflight_commitment: "processed".to_string(),
-            },
-        ),
+    let params = serde_json::json!([
+        transaction_base64,
+        {
+            "encoding": "base64",
+            "skipPreflight": skip_preflight,
+            "preflightCommitment": preflight_commitment
+        }
+    ]);
+    super::helper::send_solana_rpc_request(rpc_url, "sendTransaction", params).await
+}
+
+/// Whether a send failed because the transaction's blockhash expired
+/// (re-signing with a fresh blockhash can succeed)
+pub fn is_blockhash_expired(error: &IdosError) -> bool {
+    let message = match error {
+        IdosError::NetworkError(message) | IdosError::TransactionReverted(message) => message,
+        _ => return false,
     };
 
-    let response = client
-        .post(rpc_url)
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| IdosError::NetworkError(format!("Send transaction request failed: {}", e)))?
-        .json::<SendTransactionResponse>()
-        .await
-        .map_err(|e| {
-            IdosError::NetworkError(format!("Failed to parse send transaction response: {}", e))
-        })?;
+    [
+        "Blockhash not found",
+        "BlockhashNotFound",
+        "block height exceeded",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_table_from_account_data() {
+        let mut data = vec![0u8; LOOKUP_TABLE_META_SIZE];
+        data.extend_from_slice(&[1u8; 32]);
+        data.extend_from_slice(&[2u8; 32]);
+
+        let table = AddressLookupTable::from_account_data([9u8; 32], &data).unwrap();
+        assert_eq!(table.addresses, vec![[1u8; 32], [2u8; 32]]);
+        assert!(table.contains(&[2u8; 32]));
+
+        assert!(AddressLookupTable::from_account_data([9u8; 32], &data[..70]).is_err());
+    }
+
+    #[test]
+    fn test_transfer_sol_instruction_layout() {
+        let ix = build_transfer_sol_instruction(&[1u8; 32], &[2u8; 32], 1_500);
+        assert_eq!(ix.program_id, decode_pubkey(SYSTEM_PROGRAM_ID).unwrap());
+        assert_eq!(ix.data, [2, 0, 0, 0, 220, 5, 0, 0, 0, 0, 0, 0]);
+        assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+        assert!(!ix.accounts[1].is_signer && ix.accounts[1].is_writable);
+    }
+
+    #[test]
+    fn test_merkle_tree_canopy_depth() {
+        let (max_buffer_size, max_depth, canopy_depth) = (8usize, 14usize, 3u32);
+        let path_size = 32 * max_depth + 40;
+        let tree_size = 24 + max_buffer_size * path_size + path_size;
+        let canopy_size = 32 * ((1 << (canopy_depth + 1)) - 2);
+
+        let mut data = vec![0u8; MERKLE_TREE_HEADER_SIZE + tree_size + canopy_size];
+        data[2..6].copy_from_slice(&(max_buffer_size as u32).to_le_bytes());
+        data[6..10].copy_from_slice(&(max_depth as u32).to_le_bytes());
+
+        assert_eq!(merkle_tree_canopy_depth(&data).unwrap(), canopy_depth);
+        assert_eq!(
+            merkle_tree_canopy_depth(&data[..MERKLE_TREE_HEADER_SIZE + tree_size]).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_serialize_legacy_message() {
+        let payer = [1u8; 32];
+        let mut builder = TransactionBuilder::new(payer);
+        builder
+            .add_instruction(build_transfer_sol_instruction(&payer, &[2u8; 32], 10))
+            .set_recent_blockhash(&bs58::encode([7u8; 32]).into_string());
+
+        let message = builder.serialize_message().unwrap();
+
+        // 1 signer, 0 readonly signed, 1 readonly unsigned (system program)
+        assert_eq!(&message[..3], &[1, 0, 1]);
+        assert_eq!(message[3], 3);
+        assert_eq!(&message[4..36], &payer);
+        assert_eq!(&message[36..68], &[2u8; 32]);
+        assert_eq!(&message[68..100], &[0u8; 32]);
+        assert_eq!(&message[100..132], &[7u8; 32]);
+        // 1 instruction: program index 2, accounts [0, 1], 12 bytes of data
+        assert_eq!(&message[132..137], &[1, 2, 2, 0, 1]);
+        assert_eq!(message[137], 12);
+        assert_eq!(message.len(), 138 + 12);
+
+        let unsigned = builder.serialize_unsigned().unwrap();
+        assert_eq!(unsigned[0], 1);
+        assert_eq!(&unsigned[1..65], &[0u8; 64]);
+        assert_eq!(&unsigned[65..], &message[..]);
+    }
 
-    Ok(response.result)
+    #[test]
+    fn test_is_blockhash_expired() {
+        assert!(is_blockhash_expired(&IdosError::NetworkError(
+            "Solana RPC Error: Transaction simulation failed: Blockhash not found".to_string()
+        )));
+        assert!(!is_blockhash_expired(&IdosError::NetworkError(
+            "Solana RPC Error: insufficient funds".to_string()
+        )));
+        assert!(!is_blockhash_expired(&IdosError::Wallet(
+            "Blockhash not found".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_encode_compact_u16() {
+        let encode = |value| {
+            let mut out = Vec::new();
+            encode_compact_u16(value, &mut out);
+            out
+        };
+        assert_eq!(encode(0), vec![0]);
+        assert_eq!(encode(0x7f), vec![0x7f]);
+        assert_eq!(encode(0x80), vec![0x80, 0x01]);
+        assert_eq!(encode(0x3fff), vec![0xff, 0x7f]);
+    }
+
+    #[test]
+    fn test_compute_budget_instructions_are_prepended() {
+        let mut builder = TransactionBuilder::new([1u8; 32]);
+        builder
+            .add_instruction(TransactionInstruction {
+                program_id: [2u8; 32],
+                accounts: vec![AccountMeta::writable([3u8; 32], false)],
+                data: vec![],
+            })
+            .set_compute_unit_limit(200_000)
+            .set_compute_unit_price(5_000);
+
+        let budget = builder.compute_budget_instructions();
+        assert_eq!(builder.compute_budget_instruction_count(), 2);
+        assert_eq!(
+            budget[0].data,
+            [&[2u8][..], &200_000u32.to_le_bytes()].concat()
+        );
+        assert_eq!(
+            budget[1].data,
+            [&[3u8][..], &5_000u64.to_le_bytes()].concat()
+        );
+        assert_eq!(builder.all_instructions().len(), 3);
+    }
+
+    #[test]
+    fn test_estimate_fee_counts_signatures_and_priority_fee() {
+        let payer = [1u8; 32];
+        let mut builder = TransactionBuilder::new(payer);
+        builder
+            .add_instruction(build_transfer_sol_instruction(&payer, &[2u8; 32], 10))
+            .add_instruction(
+                build_create_associated_token_account_instruction(
+                    &payer,
+                    &[2u8; 32],
+                    &[3u8; 32],
+                    TokenProgram::Spl,
+                )
+                .unwrap(),
+            );
+        assert_eq!(builder.signature_count(), 1);
+        assert_eq!(builder.created_token_account_count(), 1);
+        assert_eq!(builder.estimate_fee(), 5_000);
+
+        // 1000 micro-lamports per unit over 2 default instruction budgets
+        builder.set_compute_unit_price(1_000);
+        assert_eq!(builder.estimate_fee(), 5_400);
+
+        builder
+            .set_compute_unit_limit(10_000)
+            .set_compute_unit_price(250_000);
+        assert_eq!(builder.estimate_fee(), 7_500);
+    }
+
+    #[test]
+    fn test_token_2022_associated_token_account() {
+        let (wallet, mint) = ([2u8; 32], [3u8; 32]);
+        let ata = derive_associated_token_account(&wallet, &mint, TokenProgram::Token2022).unwrap();
+        assert_ne!(
+            ata,
+            derive_associated_token_account(&wallet, &mint, TokenProgram::Spl).unwrap()
+        );
+
+        let create_ix = build_create_associated_token_account_instruction(
+            &[1u8; 32],
+            &wallet,
+            &mint,
+            TokenProgram::Token2022,
+        )
+        .unwrap();
+        assert_eq!(create_ix.accounts[1].pubkey, ata);
+        assert_eq!(
+            create_ix.accounts[5].pubkey,
+            decode_pubkey(TOKEN_2022_PROGRAM_ID).unwrap()
+        );
+
+        let transfer_ix = build_transfer_checked_with_fee_instruction(
+            &ata, &mint, &[4u8; 32], &wallet, 100, 6, 1,
+        )
+        .unwrap();
+        assert_eq!(
+            transfer_ix.program_id,
+            decode_pubkey(TOKEN_2022_PROGRAM_ID).unwrap()
+        );
+        assert_eq!(&transfer_ix.data[..2], &[26, 1]);
+        assert_eq!(transfer_ix.data.len(), 2 + 8 + 1 + 8);
+    }
+
+    #[test]
+    fn test_priority_fee_percentile_ignores_zero_fees() {
+        let fees: Vec<PrioritizationFee> = [0, 100, 300, 200, 0, 400]
+            .iter()
+            .enumerate()
+            .map(|(slot, fee)| PrioritizationFee {
+                slot: slot as u64,
+                prioritization_fee: *fee,
+            })
+            .collect();
+
+        assert_eq!(priority_fee_percentile(&fees, 50), 200);
+        assert_eq!(priority_fee_percentile(&fees, 100), 400);
+        assert_eq!(priority_fee_percentile(&[], 75), 0);
+    }
 }
@@ -0,0 +1,391 @@
+/// Browser wallet discovery and adapters (WASM only)
+/// Wallets registered through the wallet-standard protocol are preferred,
+/// injected providers (window.phantom.solana, window.solflare, ...) are the fallback
+use super::dto::{SolanaBrowserWallet, SolanaSignedMessage};
+use crate::{IdosError, IdosResult};
+use base64::Engine;
+use std::cell::{Cell, RefCell};
+use wasm_bindgen::{closure::Closure, prelude::*, JsCast};
+
+/// Injected providers: wallet name and path below `window`
+const INJECTED_PROVIDERS: &[(&str, &[&str])] = &[
+    ("Phantom", &["phantom", "solana"]),
+    ("Solflare", &["solflare"]),
+    ("Backpack", &["backpack"]),
+    ("OKX Wallet", &["okxwallet", "solana"]),
+    // Legacy injection point, also used by other Phantom-compatible wallets
+    ("Phantom", &["solana"]),
+];
+
+thread_local! {
+    static STANDARD_WALLETS: RefCell<Vec<JsValue>> = const { RefCell::new(Vec::new()) };
+    static REGISTRY_READY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A discovered wallet and the API used to talk to it
+enum WalletAdapter {
+    /// wallet-standard `Wallet` object
+    Standard(JsValue),
+    /// Phantom-compatible provider object
+    Injected(JsValue),
+}
+
+/// Read a property, treating undefined and null as missing
+fn get(target: &JsValue, key: &str) -> Option<JsValue> {
+    js_sys::Reflect::get(target, &JsValue::from_str(key))
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+fn set(target: &JsValue, key: &str, value: &JsValue) {
+    js_sys::Reflect::set(target, &JsValue::from_str(key), value).ok();
+}
+
+/// Call `target[method](...args)`, awaiting the result when it's a promise
+async fn call_method(target: &JsValue, method: &str, args: &[JsValue]) -> IdosResult<JsValue> {
+    let function = get(target, method)
+        .and_then(|f| f.dyn_into::<js_sys::Function>().ok())
+        .ok_or_else(|| {
+            IdosError::PlatformNotSupported(format!("Wallet does not support {}", method))
+        })?;
+
+    let result = function
+        .apply(target, &args.iter().collect::<js_sys::Array>())
+        .map_err(|e| IdosError::NetworkError(format!("Wallet {} failed: {:?}", method, e)))?;
+
+    match result.dyn_into::<js_sys::Promise>() {
+        Ok(promise) => wasm_bindgen_futures::JsFuture::from(promise)
+            .await
+            .map_err(|e| IdosError::NetworkError(format!("Wallet {} failed: {:?}", method, e))),
+        Err(value) => Ok(value),
+    }
+}
+
+/// Join the wallet-standard registry
+/// Wallets that are already loaded register on `app-ready`, later ones send `register-wallet`
+fn init_registry() {
+    if REGISTRY_READY.with(|ready| ready.replace(true)) {
+        return;
+    }
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let register = Closure::<dyn FnMut(JsValue) -> JsValue>::new(|wallet: JsValue| {
+        STANDARD_WALLETS.with(|wallets| {
+            let mut wallets = wallets.borrow_mut();
+            let name = wallet_name(&wallet);
+            if !wallets.iter().any(|known| wallet_name(known) == name) {
+                wallets.push(wallet);
+            }
+        });
+        // The registry hands back an unregister callback; wallets are kept for the session
+        js_sys::Function::new_no_args("").into()
+    });
+    let api: JsValue = js_sys::Object::new().into();
+    set(&api, "register", register.as_ref());
+    register.forget();
+
+    let listener_api = api.clone();
+    let on_register =
+        Closure::<dyn FnMut(web_sys::CustomEvent)>::new(move |event: web_sys::CustomEvent| {
+            if let Ok(callback) = event.detail().dyn_into::<js_sys::Function>() {
+                callback.call1(&JsValue::UNDEFINED, &listener_api).ok();
+            }
+        });
+    window
+        .add_event_listener_with_callback(
+            "wallet-standard:register-wallet",
+            on_register.as_ref().unchecked_ref(),
+        )
+        .ok();
+    on_register.forget();
+
+    let init = web_sys::CustomEventInit::new();
+    init.set_detail(&api);
+    if let Ok(event) =
+        web_sys::CustomEvent::new_with_event_init_dict("wallet-standard:app-ready", &init)
+    {
+        window.dispatch_event(&event).ok();
+    }
+}
+
+fn wallet_name(wallet: &JsValue) -> Option<String> {
+    get(wallet, "name").and_then(|name| name.as_string())
+}
+
+/// wallet-standard wallet that supports a Solana chain and connecting
+fn supports_solana(wallet: &JsValue) -> bool {
+    let on_solana = get(wallet, "chains")
+        .map(|chains| js_sys::Array::from(&chains))
+        .is_some_and(|chains| {
+            chains
+                .iter()
+                .any(|chain| chain.as_string().is_some_and(|c| c.starts_with("solana:")))
+        });
+    let can_connect = get(wallet, "features")
+        .and_then(|features| get(&features, "standard:connect"))
+        .is_some();
+
+    on_solana && can_connect
+}
+
+fn standard_wallets() -> Vec<JsValue> {
+    init_registry();
+    STANDARD_WALLETS.with(|wallets| {
+        wallets
+            .borrow()
+            .iter()
+            .filter(|wallet| supports_solana(wallet))
+            .cloned()
+            .collect()
+    })
+}
+
+fn injected_provider(name: &str) -> Option<JsValue> {
+    let window: JsValue = web_sys::window()?.into();
+
+    INJECTED_PROVIDERS
+        .iter()
+        .filter(|(provider_name, _)| *provider_name == name)
+        .find_map(|(_, path)| {
+            path.iter()
+                .try_fold(window.clone(), |target, key| get(&target, key))
+        })
+}
+
+fn find_wallet(name: &str) -> IdosResult<WalletAdapter> {
+    if let Some(wallet) = standard_wallets()
+        .into_iter()
+        .find(|wallet| wallet_name(wallet).as_deref() == Some(name))
+    {
+        return Ok(WalletAdapter::Standard(wallet));
+    }
+
+    injected_provider(name)
+        .map(WalletAdapter::Injected)
+        .ok_or_else(|| {
+            IdosError::PlatformNotSupported(format!("Solana wallet {} is not available", name))
+        })
+}
+
+/// Wallets installed in the browser, wallet-standard ones first
+pub fn available_wallets() -> Vec<SolanaBrowserWallet> {
+    let mut wallets: Vec<SolanaBrowserWallet> = standard_wallets()
+        .iter()
+        .filter_map(|wallet| {
+            Some(SolanaBrowserWallet {
+                name: wallet_name(wallet)?,
+                icon: get(wallet, "icon").and_then(|icon| icon.as_string()),
+                wallet_standard: true,
+            })
+        })
+        .collect();
+
+    for (name, _) in INJECTED_PROVIDERS {
+        if !wallets.iter().any(|wallet| wallet.name == *name) && injected_provider(name).is_some() {
+            wallets.push(SolanaBrowserWallet {
+                name: name.to_string(),
+                icon: None,
+                wallet_standard: false,
+            });
+        }
+    }
+
+    wallets
+}
+
+/// Connect a wallet by name and return its address
+pub async fn connect_wallet(name: &str) -> IdosResult<String> {
+    match find_wallet(name)? {
+        WalletAdapter::Standard(wallet) => {
+            let connect = get(&wallet, "features")
+                .and_then(|features| get(&features, "standard:connect"))
+                .ok_or_else(|| {
+                    IdosError::PlatformNotSupported(format!("{} cannot connect", name))
+                })?;
+            let result = call_method(&connect, "connect", &[]).await?;
+
+            get(&result, "accounts")
+                .map(|accounts| js_sys::Array::from(&accounts).get(0))
+                .and_then(|account| get(&account, "address"))
+                .and_then(|address| address.as_string())
+                .ok_or_else(|| IdosError::NetworkError("Wallet returned no accounts".to_string()))
+        }
+        WalletAdapter::Injected(provider) => {
+            call_method(&provider, "connect", &[]).await?;
+
+            // Phantom returns the key from connect, Solflare only sets it on the provider
+            let public_key = get(&provider, "publicKey")
+                .ok_or_else(|| IdosError::NetworkError("Failed to get public key".to_string()))?;
+            call_method(&public_key, "toString", &[])
+                .await?
+                .as_string()
+                .ok_or_else(|| {
+                    IdosError::NetworkError("Failed to convert public key to string".to_string())
+                })
+        }
+    }
+}
+
+/// Sign and send a serialized transaction through wallet-standard
+async fn standard_sign_and_send(
+    wallet: &JsValue,
+    chain: &str,
+    transaction: &[u8],
+) -> IdosResult<String> {
+    let feature = get(wallet, "features")
+        .and_then(|features| get(&features, "solana:signAndSendTransaction"))
+        .ok_or_else(|| {
+            IdosError::PlatformNotSupported("Wallet cannot send transactions".to_string())
+        })?;
+    let account = get(wallet, "accounts")
+        .map(|accounts| js_sys::Array::from(&accounts).get(0))
+        .filter(|account| !account.is_undefined())
+        .ok_or_else(|| IdosError::Wallet("Wallet is not connected".to_string()))?;
+
+    let input: JsValue = js_sys::Object::new().into();
+    set(&input, "account", &account);
+    set(&input, "chain", &JsValue::from_str(chain));
+    set(
+        &input,
+        "transaction",
+        &js_sys::Uint8Array::from(transaction).into(),
+    );
+
+    let outputs = call_method(&feature, "signAndSendTransaction", &[input]).await?;
+    let signature = get(&js_sys::Array::from(&outputs).get(0), "signature")
+        .map(|signature| js_sys::Uint8Array::new(&signature).to_vec())
+        .ok_or_else(|| IdosError::NetworkError("Failed to get signature".to_string()))?;
+
+    Ok(bs58::encode(signature).into_string())
+}
+
+/// Read the signature of an injected provider's send result
+fn injected_signature(result: &JsValue) -> IdosResult<String> {
+    get(result, "signature")
+        .and_then(|signature| signature.as_string())
+        .ok_or_else(|| IdosError::NetworkError("Failed to get signature".to_string()))
+}
+
+/// Sign and send a base64 serialized transaction
+pub async fn send_transaction(
+    name: &str,
+    chain: &str,
+    transaction_base64: &str,
+) -> IdosResult<String> {
+    match find_wallet(name)? {
+        WalletAdapter::Standard(wallet) => {
+            let transaction = base64::engine::general_purpose::STANDARD
+                .decode(transaction_base64)
+                .map_err(|e| {
+                    IdosError::SerializationError(format!("Invalid transaction: {}", e))
+                })?;
+            standard_sign_and_send(&wallet, chain, &transaction).await
+        }
+        WalletAdapter::Injected(provider) => {
+            let tx_js = serde_wasm_bindgen::to_value(&serde_json::json!({
+                "transaction": transaction_base64
+            }))
+            .map_err(|e| IdosError::SerializationError(e.to_string()))?;
+
+            let result = call_method(&provider, "signAndSendTransaction", &[tx_js]).await?;
+            injected_signature(&result)
+        }
+    }
+}
+
+/// Sign and send an unsigned legacy transaction (zeroed signature slots + message)
+pub async fn sign_and_send_unsigned(
+    name: &str,
+    chain: &str,
+    unsigned_transaction: &[u8],
+) -> IdosResult<String> {
+    match find_wallet(name)? {
+        WalletAdapter::Standard(wallet) => {
+            standard_sign_and_send(&wallet, chain, unsigned_transaction).await
+        }
+        WalletAdapter::Injected(provider) => {
+            // The provider `request` API takes the base58 message without signature slots
+            let signers = unsigned_transaction.first().copied().unwrap_or(0) as usize;
+            let message = unsigned_transaction
+                .get(1 + 64 * signers..)
+                .ok_or_else(|| {
+                    IdosError::SerializationError("Invalid unsigned transaction".to_string())
+                })?;
+
+            let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                "method": "signAndSendTransaction",
+                "params": {
+                    "message": bs58::encode(message).into_string()
+                }
+            }))
+            .map_err(|e| IdosError::SerializationError(e.to_string()))?;
+
+            let result = call_method(&provider, "request", &[args]).await?;
+            injected_signature(&result)
+        }
+    }
+}
+
+/// Sign an off-chain message with the connected wallet
+pub async fn sign_message(name: &str, message: &[u8]) -> IdosResult<SolanaSignedMessage> {
+    let message_js: JsValue = js_sys::Uint8Array::from(message).into();
+
+    match find_wallet(name)? {
+        WalletAdapter::Standard(wallet) => {
+            let feature = get(&wallet, "features")
+                .and_then(|features| get(&features, "solana:signMessage"))
+                .ok_or_else(|| {
+                    IdosError::PlatformNotSupported(format!("{} cannot sign messages", name))
+                })?;
+            let account = get(&wallet, "accounts")
+                .map(|accounts| js_sys::Array::from(&accounts).get(0))
+                .filter(|account| !account.is_undefined())
+                .ok_or_else(|| IdosError::Wallet("Wallet is not connected".to_string()))?;
+            let public_key = get(&account, "address")
+                .and_then(|address| address.as_string())
+                .ok_or_else(|| IdosError::Wallet("Wallet account has no address".to_string()))?;
+
+            let input: JsValue = js_sys::Object::new().into();
+            set(&input, "account", &account);
+            set(&input, "message", &message_js);
+
+            let outputs = call_method(&feature, "signMessage", &[input]).await?;
+            let signature = get(&js_sys::Array::from(&outputs).get(0), "signature")
+                .map(|signature| js_sys::Uint8Array::new(&signature).to_vec())
+                .ok_or_else(|| IdosError::NetworkError("Failed to get signature".to_string()))?;
+
+            Ok(SolanaSignedMessage {
+                public_key,
+                signature: bs58::encode(signature).into_string(),
+            })
+        }
+        WalletAdapter::Injected(provider) => {
+            let result = call_method(
+                &provider,
+                "signMessage",
+                &[message_js, JsValue::from_str("utf8")],
+            )
+            .await?;
+
+            let signature = get(&result, "signature")
+                .map(|signature| js_sys::Uint8Array::new(&signature).to_vec())
+                .ok_or_else(|| IdosError::NetworkError("Failed to get signature".to_string()))?;
+            let public_key = get(&result, "publicKey")
+                .or_else(|| get(&provider, "publicKey"))
+                .ok_or_else(|| IdosError::NetworkError("Failed to get public key".to_string()))?;
+            let public_key = call_method(&public_key, "toString", &[])
+                .await?
+                .as_string()
+                .ok_or_else(|| {
+                    IdosError::NetworkError("Failed to convert public key to string".to_string())
+                })?;
+
+            Ok(SolanaSignedMessage {
+                public_key,
+                signature: bs58::encode(signature).into_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,64 @@
+/// Deep links Bevy plugin
+use super::dto::DeepLinkReceived;
+use super::handler::DeepLinksHandler;
+use bevy::prelude::*;
+
+/// Reads the launch URL at startup and delivers deep links as `DeepLinkReceived` messages
+/// WASM: the page URL; native: a `<scheme>://` command-line argument (desktop protocol handlers)
+/// Mobile platform layers pass URLs in with `DeepLinksHandler::handle_launch_url` / `handle_url`
+#[derive(Default)]
+pub struct DeepLinksPlugin {
+    schemes: Vec<String>,
+}
+
+impl DeepLinksPlugin {
+    /// Only accept links with this custom scheme (can be called more than once)
+    pub fn with_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.schemes.push(scheme.into());
+        self
+    }
+}
+
+impl Plugin for DeepLinksPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DeepLinksHandler::new(self.schemes.clone()))
+            .add_message::<DeepLinkReceived>()
+            .add_systems(PreStartup, (capture_launch_url, forward_deep_links).chain())
+            .add_systems(PreUpdate, forward_deep_links);
+    }
+}
+
+/// Queue the URL the game was started with
+fn capture_launch_url(handler: Res<DeepLinksHandler>) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        if let Some(href) = web_sys::window().and_then(|window| window.location().href().ok()) {
+            // A plain page load is not a deep link
+            if href.contains('?') || href.contains("#/") {
+                handler.handle_launch_url(&href);
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // Web URLs in arguments are more likely config than a deep link
+        let custom_scheme = |arg: &String| {
+            super::dto::DeepLink::parse(arg)
+                .is_some_and(|link| link.scheme != "http" && link.scheme != "https")
+        };
+        if let Some(arg) = std::env::args().skip(1).find(custom_scheme) {
+            handler.handle_launch_url(&arg);
+        }
+    }
+}
+
+/// Forward queued deep links as messages
+fn forward_deep_links(
+    handler: Res<DeepLinksHandler>,
+    mut received: MessageWriter<DeepLinkReceived>,
+) {
+    for link in handler.drain_received() {
+        received.write(link);
+    }
+}
@@ -0,0 +1,198 @@
+/// Deep link parsing
+use bevy::prelude::Message;
+use std::collections::HashMap;
+
+/// URL split into its parts, with the query percent-decoded
+/// `mygame://offer/summer?src=push` -> scheme "mygame", path ["offer", "summer"], params {src: push}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeepLink {
+    pub url: String,
+    pub scheme: String,
+    /// Path segments; for custom schemes the host is the first segment
+    pub path: Vec<String>,
+    pub params: HashMap<String, String>,
+}
+
+impl DeepLink {
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let (scheme, rest) = url.split_once("://")?;
+        if scheme.is_empty()
+            || !scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        {
+            return None;
+        }
+        let scheme = scheme.to_ascii_lowercase();
+
+        // Query params can also arrive in the fragment (`#/offer?id=1` single-page routing)
+        let (rest, fragment) = rest.split_once('#').unwrap_or((rest, ""));
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (fragment_path, fragment_query) = fragment.split_once('?').unwrap_or((fragment, ""));
+
+        let mut segments: Vec<&str> = path.split('/').collect();
+        if scheme == "http" || scheme == "https" {
+            // Drop the host of web links
+            segments.remove(0);
+        }
+        segments.extend(fragment_path.split('/'));
+        let path = segments
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .map(percent_decode)
+            .collect();
+
+        let params = query
+            .split('&')
+            .chain(fragment_query.split('&'))
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (
+                    percent_decode(&key.replace('+', " ")),
+                    percent_decode(&value.replace('+', " ")),
+                )
+            })
+            .collect();
+
+        Some(Self {
+            url: url.to_string(),
+            scheme,
+            path,
+            params,
+        })
+    }
+
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// What a deep link asks the game to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLinkAction {
+    /// `/offer/<id>` or `?offer=<id>`
+    OpenOffer { offer_id: String },
+    /// `/clan/<id>` or `?clan=<id>`
+    JoinClan { clan_id: String },
+    /// `/ref/<code>`, `?ref=<code>` or `?referral=<code>`
+    ApplyReferral { code: String },
+    /// Anything else; route it with the parsed link
+    Custom(DeepLink),
+}
+
+impl DeepLinkAction {
+    pub fn from_link(link: &DeepLink) -> Self {
+        let first = link.path.first().map(String::as_str);
+        let second = || link.path.get(1).cloned();
+
+        match first {
+            Some("offer" | "offers") if second().is_some() => {
+                return Self::OpenOffer {
+                    offer_id: second().unwrap_or_default(),
+                };
+            }
+            Some("clan" | "clans") if second().is_some() => {
+                return Self::JoinClan {
+                    clan_id: second().unwrap_or_default(),
+                };
+            }
+            Some("ref" | "referral" | "invite") if second().is_some() => {
+                return Self::ApplyReferral {
+                    code: second().unwrap_or_default(),
+                };
+            }
+            _ => {}
+        }
+
+        if let Some(offer_id) = link.param("offer") {
+            Self::OpenOffer {
+                offer_id: offer_id.to_string(),
+            }
+        } else if let Some(clan_id) = link.param("clan") {
+            Self::JoinClan {
+                clan_id: clan_id.to_string(),
+            }
+        } else if let Some(code) = link.param("ref").or_else(|| link.param("referral")) {
+            Self::ApplyReferral {
+                code: code.to_string(),
+            }
+        } else {
+            Self::Custom(link.clone())
+        }
+    }
+}
+
+/// A deep link opened the game (`launch`) or was delivered while it was running
+#[derive(Message, Debug, Clone)]
+pub struct DeepLinkReceived {
+    pub action: DeepLinkAction,
+    pub link: DeepLink,
+    pub launch: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deep_link_actions() {
+        let link = DeepLink::parse("MyGame://offer/summer%20sale?src=push").unwrap();
+        assert_eq!(link.scheme, "mygame");
+        assert_eq!(link.param("src"), Some("push"));
+        assert_eq!(
+            DeepLinkAction::from_link(&link),
+            DeepLinkAction::OpenOffer {
+                offer_id: "summer sale".to_string()
+            }
+        );
+
+        let web = DeepLink::parse("https://play.example.com/game/?ref=ABC123&utm=x").unwrap();
+        assert_eq!(web.path, vec!["game"]);
+        assert_eq!(
+            DeepLinkAction::from_link(&web),
+            DeepLinkAction::ApplyReferral {
+                code: "ABC123".to_string()
+            }
+        );
+
+        let spa = DeepLink::parse("https://play.example.com/#/clan/42").unwrap();
+        assert_eq!(
+            DeepLinkAction::from_link(&spa),
+            DeepLinkAction::JoinClan {
+                clan_id: "42".to_string()
+            }
+        );
+
+        let custom = DeepLink::parse("mygame://settings").unwrap();
+        assert!(matches!(
+            DeepLinkAction::from_link(&custom),
+            DeepLinkAction::Custom(_)
+        ));
+        assert!(DeepLink::parse("not a url").is_none());
+    }
+}
@@ -0,0 +1,62 @@
+/// Deep link handler: queues URLs handed over by the platform layer
+use super::dto::*;
+use bevy::prelude::Resource;
+use std::sync::{Arc, Mutex};
+
+#[derive(Resource, Clone, Default)]
+pub struct DeepLinksHandler {
+    /// Custom schemes the game registered; empty accepts any scheme
+    schemes: Vec<String>,
+    /// Links waiting for DeepLinksPlugin
+    received: Arc<Mutex<Vec<DeepLinkReceived>>>,
+}
+
+impl DeepLinksHandler {
+    pub fn new(schemes: Vec<String>) -> Self {
+        Self {
+            schemes: schemes.iter().map(|s| s.to_ascii_lowercase()).collect(),
+            received: Arc::default(),
+        }
+    }
+
+    /// Whether `link` uses a registered scheme (web links are always accepted)
+    pub fn accepts(&self, link: &DeepLink) -> bool {
+        self.schemes.is_empty()
+            || link.scheme == "http"
+            || link.scheme == "https"
+            || self.schemes.contains(&link.scheme)
+    }
+
+    /// URL the game was launched with (cold start)
+    pub fn handle_launch_url(&self, url: &str) -> Option<DeepLinkAction> {
+        self.push(url, true)
+    }
+
+    /// URL delivered while the game was running (resume, `onNewIntent`, `application:openURL:`)
+    /// Returns the parsed action, or `None` if the URL isn't a deep link for this game
+    pub fn handle_url(&self, url: &str) -> Option<DeepLinkAction> {
+        self.push(url, false)
+    }
+
+    fn push(&self, url: &str, launch: bool) -> Option<DeepLinkAction> {
+        let link = DeepLink::parse(url).filter(|link| self.accepts(link))?;
+        let action = DeepLinkAction::from_link(&link);
+
+        if let Ok(mut received) = self.received.lock() {
+            received.push(DeepLinkReceived {
+                action: action.clone(),
+                link,
+                launch,
+            });
+        }
+        Some(action)
+    }
+
+    /// Take the links received since the last call
+    pub fn drain_received(&self) -> Vec<DeepLinkReceived> {
+        self.received
+            .lock()
+            .map(|mut received| std::mem::take(&mut *received))
+            .unwrap_or_default()
+    }
+}
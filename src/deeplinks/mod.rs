@@ -0,0 +1,8 @@
+/// Deep link module: launch/resume URLs parsed into typed actions
+pub mod deeplinks_plugin;
+pub mod dto;
+pub mod handler;
+
+pub use deeplinks_plugin::DeepLinksPlugin;
+pub use dto::*;
+pub use handler::DeepLinksHandler;
@@ -11,8 +11,15 @@ pub enum IdosError {
     #[error("Network error: {0}")]
     Network(String),
 
-    #[error("API error: {0}")]
-    Api(String),
+    #[error("API error ({code}): {message}")]
+    Api {
+        code: ApiErrorCode,
+        message: String,
+        /// Status of the failed response; `None` when the request itself succeeded
+        http_status: Option<u16>,
+        /// Error code as sent by the backend, e.g. for codes that resolve to a generic `code`
+        error_code: Option<String>,
+    },
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
@@ -29,6 +36,18 @@ pub enum IdosError {
     #[error("Crypto wallet error: {0}")]
     Wallet(String),
 
+    #[error("Wallet is locked")]
+    WalletLocked,
+
+    #[error("Wallet {0} is watch-only and cannot sign")]
+    WatchOnly(String),
+
+    #[error("Biometric unlock unavailable: {0}")]
+    BiometricUnavailable(String),
+
+    #[error("Transaction reverted: {0}")]
+    TransactionReverted(String),
+
     #[error("Not supported on this platform: {0}")]
     PlatformNotSupported(String),
 
@@ -50,4 +69,255 @@ pub enum IdosError {
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    #[error("Insufficient SOL for fees: {required} lamports required, {available} available")]
+    InsufficientSolForFees { required: u64, available: u64 },
+
+    #[error("Referral rejected: {0}")]
+    ReferralRejected(ReferralRejection),
+}
+
+impl IdosError {
+    /// `Api` error without a specific code, e.g. for an unexpected backend payload
+    pub fn api(message: impl Into<String>) -> Self {
+        Self::Api {
+            code: ApiErrorCode::Unknown,
+            message: message.into(),
+            http_status: None,
+            error_code: None,
+        }
+    }
+
+    /// Error for a failed HTTP response
+    /// `body` is the response text; JSON bodies with an error code and message are used when present
+    pub fn from_response(http_status: u16, body: &str) -> Self {
+        let json: Option<serde_json::Value> = serde_json::from_str(body).ok();
+        let field = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                json.as_ref()?
+                    .get(*name)
+                    .and_then(|value| value.as_str())
+                    .map(str::to_string)
+            })
+        };
+
+        let raw_code = field(&["ErrorCode", "errorCode", "Error", "error", "Code", "code"]);
+        let message = field(&["Message", "message", "ErrorMessage", "errorMessage"])
+            .or_else(|| raw_code.clone())
+            .unwrap_or_else(|| format!("HTTP {}", http_status));
+
+        Self::Api {
+            code: ApiErrorCode::resolve(raw_code.as_deref(), http_status),
+            message,
+            http_status: Some(http_status),
+            error_code: raw_code,
+        }
+    }
+
+    /// Backend error code, for `Api` errors
+    pub fn api_code(&self) -> Option<&ApiErrorCode> {
+        match self {
+            Self::Api { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+}
+
+/// Backend error code without case or separators, for matching
+fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Stable error codes for failed backend requests
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ApiErrorCode {
+    #[error("session expired")]
+    AuthExpired,
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("forbidden")]
+    Forbidden,
+
+    #[error("not found")]
+    NotFound,
+
+    #[error("validation failed")]
+    ValidationFailed,
+
+    #[error("insufficient funds")]
+    InsufficientFunds,
+
+    #[error("conflict")]
+    Conflict,
+
+    #[error("rate limited")]
+    RateLimited,
+
+    #[error("server error")]
+    ServerError,
+
+    /// Code sent by the backend that has no variant yet
+    #[error("{0}")]
+    Other(String),
+
+    #[error("unknown")]
+    Unknown,
+}
+
+impl ApiErrorCode {
+    /// Map a backend error code
+    /// Case and separators are ignored, so `SESSION_EXPIRED` and `SessionExpired` match
+    pub fn from_code(code: &str) -> Self {
+        match normalize_code(code).as_str() {
+            "authexpired" | "sessionexpired" | "tokenexpired" | "sessionticketexpired" => {
+                Self::AuthExpired
+            }
+            "unauthorized" | "notauthenticated" | "invalidsessionticket" => Self::Unauthorized,
+            "forbidden" | "accessdenied" | "notallowed" => Self::Forbidden,
+            "notfound" | "itemnotfound" | "usernotfound" | "accountnotfound" => Self::NotFound,
+            "validationfailed" | "validationerror" | "invalidparams" | "invalidrequest"
+            | "invalidinput" => Self::ValidationFailed,
+            "insufficientfunds" | "insufficientbalance" | "insufficientcurrency" => {
+                Self::InsufficientFunds
+            }
+            "conflict" | "alreadyexists" | "duplicate" => Self::Conflict,
+            "ratelimited" | "toomanyrequests" | "throttled" => Self::RateLimited,
+            "servererror" | "internalerror" | "internalservererror" => Self::ServerError,
+            _ => Self::Other(code.to_string()),
+        }
+    }
+
+    /// Code implied by an HTTP status alone
+    pub fn from_status(http_status: u16) -> Self {
+        match http_status {
+            400 | 422 => Self::ValidationFailed,
+            401 => Self::Unauthorized,
+            402 => Self::InsufficientFunds,
+            403 => Self::Forbidden,
+            404 => Self::NotFound,
+            409 => Self::Conflict,
+            429 => Self::RateLimited,
+            500..=599 => Self::ServerError,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Prefer a known backend code, then the status, then the raw code
+    fn resolve(code: Option<&str>, http_status: u16) -> Self {
+        match code.map(Self::from_code) {
+            Some(Self::Other(raw)) => match Self::from_status(http_status) {
+                Self::Unknown => Self::Other(raw),
+                from_status => from_status,
+            },
+            Some(code) => code,
+            None => Self::from_status(http_status),
+        }
+    }
+}
+
+/// Why the backend refused a referral code (anti-abuse checks)
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ReferralRejection {
+    #[error("code does not exist")]
+    InvalidCode,
+
+    #[error("players can't use their own code")]
+    SelfReferral,
+
+    #[error("a referral code was already applied to this account")]
+    AlreadyReferred,
+
+    #[error("this device was already used with another account")]
+    DeviceAlreadyUsed,
+
+    #[error("codes can only be entered by new accounts")]
+    EntryWindowExpired,
+
+    #[error("too many attempts, try again later")]
+    RateLimited,
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl ReferralRejection {
+    /// Map a backend error code
+    /// Case and separators are ignored, like `ApiErrorCode::from_code`
+    pub fn from_code(code: &str) -> Self {
+        match normalize_code(code).as_str() {
+            "invalidcode" => Self::InvalidCode,
+            "selfreferral" => Self::SelfReferral,
+            "alreadyreferred" => Self::AlreadyReferred,
+            "devicealreadyused" => Self::DeviceAlreadyUsed,
+            "entrywindowexpired" => Self::EntryWindowExpired,
+            "ratelimited" => Self::RateLimited,
+            _ => Self::Other(code.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_from_response() {
+        let error = IdosError::from_response(
+            400,
+            r#"{"ErrorCode":"INSUFFICIENT_FUNDS","Message":"Not enough gold"}"#,
+        );
+        assert_eq!(error.api_code(), Some(&ApiErrorCode::InsufficientFunds));
+        assert_eq!(
+            error.to_string(),
+            "API error (insufficient funds): Not enough gold"
+        );
+
+        let error = IdosError::from_response(429, "Too Many Requests");
+        assert_eq!(error.api_code(), Some(&ApiErrorCode::RateLimited));
+
+        let error = IdosError::from_response(401, r#"{"error":"SessionExpired"}"#);
+        assert_eq!(error.api_code(), Some(&ApiErrorCode::AuthExpired));
+
+        let error = IdosError::from_response(418, r#"{"code":"TEAPOT"}"#);
+        assert_eq!(
+            error.api_code(),
+            Some(&ApiErrorCode::Other("TEAPOT".to_string()))
+        );
+
+        // The backend's own code is kept when the status decides `code`
+        let error = IdosError::from_response(
+            400,
+            r#"{"ErrorCode":"SELF_REFERRAL","Message":"That's your own code"}"#,
+        );
+        assert_eq!(error.api_code(), Some(&ApiErrorCode::ValidationFailed));
+        assert!(matches!(
+            error,
+            IdosError::Api { error_code: Some(ref code), .. } if code == "SELF_REFERRAL"
+        ));
+    }
+
+    #[test]
+    fn test_referral_rejection_from_code() {
+        assert_eq!(
+            ReferralRejection::from_code("SELF_REFERRAL"),
+            ReferralRejection::SelfReferral
+        );
+        assert_eq!(
+            ReferralRejection::from_code("SelfReferral"),
+            ReferralRejection::SelfReferral
+        );
+        assert_eq!(
+            ReferralRejection::from_code("device-already-used"),
+            ReferralRejection::DeviceAlreadyUsed
+        );
+        assert_eq!(
+            ReferralRejection::from_code("NOPE"),
+            ReferralRejection::Other("NOPE".to_string())
+        );
+    }
 }
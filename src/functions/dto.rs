@@ -0,0 +1,46 @@
+/// Data Transfer Objects for server functions
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Request to execute a server-side function
+/// `FunctionName` is the game's own script name, not an SDK operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ExecuteFunctionRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(skip_serializing_if = "Value::is_null")]
+    pub function_parameter: Value,
+}
+
+/// Error thrown by the script itself (as opposed to a transport/API failure)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct FunctionError {
+    pub error: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub stack_trace: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ExecuteFunctionResult {
+    pub function_name: String,
+    /// Whatever the script returned; `null` if it returned nothing
+    #[serde(default)]
+    pub function_result: Value,
+    #[serde(default)]
+    pub execution_time_milliseconds: u64,
+    /// Lines the script logged, when the backend has script logging on
+    #[serde(default)]
+    pub logs: Vec<String>,
+    #[serde(default)]
+    pub error: Option<FunctionError>,
+}
@@ -0,0 +1,22 @@
+/// Functions Bevy plugin
+use super::handler::FunctionsHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+
+pub struct FunctionsPlugin;
+
+impl Plugin for FunctionsPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize functions handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = FunctionsHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<FunctionsHandler>.in_set(SessionSync),
+        );
+    }
+}
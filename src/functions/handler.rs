@@ -0,0 +1,111 @@
+/// Functions handler: run the game's server-side scripts with the player's session
+use super::dto::*;
+use crate::{IdosClient, IdosError, IdosResult};
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+#[derive(Resource, Clone)]
+pub struct FunctionsHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+}
+
+impl FunctionsHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    /// Run `function_name` and decode its return value into `R`
+    /// Use `()` as `params` for functions that take none
+    pub async fn invoke<P: Serialize, R: DeserializeOwned>(
+        &self,
+        function_name: &str,
+        params: &P,
+    ) -> IdosResult<R> {
+        let params = serde_json::to_value(params)?;
+        let result = self.execute(function_name, params).await?;
+        serde_json::from_value(result.function_result).map_err(|err| {
+            IdosError::SerializationError(format!(
+                "Unexpected result from function {}: {}",
+                function_name, err
+            ))
+        })
+    }
+
+    /// Run `function_name` and return its result as untyped JSON
+    pub async fn invoke_value(&self, function_name: &str, params: Value) -> IdosResult<Value> {
+        Ok(self.execute(function_name, params).await?.function_result)
+    }
+
+    /// Run `function_name` and return the full response, including script logs
+    /// Script errors are returned as `IdosError::Api`
+    pub async fn execute(
+        &self,
+        function_name: &str,
+        params: Value,
+    ) -> IdosResult<ExecuteFunctionResult> {
+        if function_name.trim().is_empty() {
+            return Err(IdosError::InvalidInput(
+                "Function name can't be empty".to_string(),
+            ));
+        }
+
+        let request = ExecuteFunctionRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            function_parameter: params,
+        };
+
+        let endpoint = "functions/ExecuteFunction";
+        let result: ExecuteFunctionResult = self.client.post(endpoint, &request).await?;
+
+        if self.client.config().debug {
+            for line in &result.logs {
+                debug!("[{}] {}", function_name, line);
+            }
+        }
+
+        if let Some(error) = &result.error {
+            return Err(IdosError::api(format!(
+                "Function {} failed: {} {}",
+                function_name, error.error, error.message
+            )));
+        }
+        Ok(result)
+    }
+}
+
+crate::session::session_aware!(FunctionsHandler);
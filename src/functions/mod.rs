@@ -0,0 +1,9 @@
+/// Functions module: invoke server-side scripts (CloudScript) by name
+/// For authoritative game logic that has no dedicated SDK endpoint yet
+pub mod dto;
+pub mod functions_plugin;
+pub mod handler;
+
+pub use dto::*;
+pub use functions_plugin::FunctionsPlugin;
+pub use handler::FunctionsHandler;
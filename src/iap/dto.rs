@@ -1,4 +1,5 @@
 /// Data Transfer Objects for In-App Purchases
+use bevy::prelude::Message;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -56,3 +57,88 @@ pub enum PurchaseStatus {
 pub struct GetProductsResponse {
     pub products: Vec<Product>,
 }
+
+// Store receipts
+
+/// Store that issued a receipt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Store {
+    AppStore,
+    GooglePlay,
+    Steam,
+}
+
+/// Proof of a store purchase, as handed over by the platform SDK
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "store", rename_all = "snake_case")]
+pub enum StoreReceipt {
+    /// Base64 App Store receipt (`appStoreReceiptURL` contents)
+    AppStore {
+        product_id: String,
+        receipt_data: String,
+    },
+    /// Google Play Billing purchase token
+    GooglePlay {
+        product_id: String,
+        package_name: String,
+        purchase_token: String,
+    },
+    /// Steam microtransaction order, finalized by the backend
+    Steam {
+        product_id: String,
+        order_id: String,
+    },
+}
+
+impl StoreReceipt {
+    pub fn store(&self) -> Store {
+        match self {
+            StoreReceipt::AppStore { .. } => Store::AppStore,
+            StoreReceipt::GooglePlay { .. } => Store::GooglePlay,
+            StoreReceipt::Steam { .. } => Store::Steam,
+        }
+    }
+
+    pub fn product_id(&self) -> &str {
+        match self {
+            StoreReceipt::AppStore { product_id, .. }
+            | StoreReceipt::GooglePlay { product_id, .. }
+            | StoreReceipt::Steam { product_id, .. } => product_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidateReceiptRequest {
+    pub receipt: StoreReceipt,
+    pub platform: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidateReceiptResponse {
+    pub valid: bool,
+    /// Store transaction id, used to spot replayed receipts
+    pub transaction_id: Option<String>,
+    /// Why the store or backend refused the receipt
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub granted_items: Vec<String>,
+}
+
+/// The backend accepted a store receipt and granted the product
+#[derive(Message, Debug, Clone)]
+pub struct PurchaseVerified {
+    pub store: Store,
+    pub product_id: String,
+    pub transaction_id: Option<String>,
+    pub granted_items: Vec<String>,
+}
+
+/// A store receipt was refused or couldn't be validated
+#[derive(Message, Debug, Clone)]
+pub struct PurchaseRejected {
+    pub store: Store,
+    pub product_id: String,
+    pub reason: String,
+}
@@ -1,19 +1,46 @@
 pub struct IapPlugin;
 use bevy::prelude::*;
 
-use super::dto::{Product, PurchaseResponse};
+use super::dto::{Product, PurchaseRejected, PurchaseResponse, PurchaseVerified};
 use super::handler::IapHandler;
+use super::receipt::{ReceiptOutcome, ReceiptValidator};
 
 impl Plugin for IapPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<IapEvent>()
-            .add_systems(Startup, setup_iap);
+            .add_message::<PurchaseVerified>()
+            .add_message::<PurchaseRejected>()
+            .add_systems(Startup, setup_iap)
+            .add_systems(Update, forward_receipt_outcomes);
     }
 }
 
 fn setup_iap(mut commands: Commands, client: Res<crate::IdosClient>) {
     let handler = IapHandler::new(client.clone());
     commands.insert_resource(handler);
+    commands.insert_resource(ReceiptValidator::new(client.clone()));
+}
+
+/// Forward receipt validation results as messages
+fn forward_receipt_outcomes(
+    validator: Option<Res<ReceiptValidator>>,
+    mut verified: MessageWriter<PurchaseVerified>,
+    mut rejected: MessageWriter<PurchaseRejected>,
+) {
+    let Some(validator) = validator else {
+        return;
+    };
+
+    for outcome in validator.drain_outcomes() {
+        match outcome {
+            ReceiptOutcome::Verified(event) => {
+                verified.write(event);
+            }
+            ReceiptOutcome::Rejected(event) => {
+                rejected.write(event);
+            }
+        }
+    }
 }
 
 #[derive(Message, Debug)]
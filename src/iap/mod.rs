@@ -2,4 +2,6 @@
 pub mod dto;
 pub mod handler;
 pub mod iap_plugin;
+pub mod receipt;
 pub use dto::*;
+pub use receipt::{ReceiptOutcome, ReceiptValidator};
@@ -0,0 +1,90 @@
+/// Store receipt validation
+/// Receipts from the App Store, Google Play and Steam are checked by the backend,
+/// and the outcome is delivered as `PurchaseVerified` / `PurchaseRejected` messages by IapPlugin
+use super::dto::*;
+use crate::{IdosClient, IdosResult};
+use bevy::prelude::Resource;
+use std::sync::{Arc, Mutex};
+
+/// Outcome of a validation, queued until IapPlugin writes it as a message
+#[derive(Debug, Clone)]
+pub enum ReceiptOutcome {
+    Verified(PurchaseVerified),
+    Rejected(PurchaseRejected),
+}
+
+#[derive(Resource, Clone)]
+pub struct ReceiptValidator {
+    client: IdosClient,
+    outcomes: Arc<Mutex<Vec<ReceiptOutcome>>>,
+}
+
+impl ReceiptValidator {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            outcomes: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Ask the backend to validate a receipt with its store
+    /// A refused receipt is `Ok` with `valid: false`; errors mean the check itself failed
+    pub async fn validate(&self, receipt: &StoreReceipt) -> IdosResult<ValidateReceiptResponse> {
+        let request = ValidateReceiptRequest {
+            receipt: receipt.clone(),
+            platform: platform().to_string(),
+        };
+
+        self.client.post("iap/validate", &request).await
+    }
+
+    /// Validate in the background and report the outcome as a message
+    /// Failed checks are reported as `PurchaseRejected` so the purchase flow always ends
+    pub fn submit(&self, receipt: StoreReceipt) {
+        let validator = self.clone();
+        let validate = async move {
+            let outcome = match validator.validate(&receipt).await {
+                Ok(response) if response.valid => ReceiptOutcome::Verified(PurchaseVerified {
+                    store: receipt.store(),
+                    product_id: receipt.product_id().to_string(),
+                    transaction_id: response.transaction_id,
+                    granted_items: response.granted_items,
+                }),
+                Ok(response) => ReceiptOutcome::Rejected(PurchaseRejected {
+                    store: receipt.store(),
+                    product_id: receipt.product_id().to_string(),
+                    reason: response
+                        .reason
+                        .unwrap_or_else(|| "Receipt is not valid".to_string()),
+                }),
+                Err(e) => ReceiptOutcome::Rejected(PurchaseRejected {
+                    store: receipt.store(),
+                    product_id: receipt.product_id().to_string(),
+                    reason: e.to_string(),
+                }),
+            };
+
+            if let Ok(mut outcomes) = validator.outcomes.lock() {
+                outcomes.push(outcome);
+            }
+        };
+
+        crate::tasks::spawn(validate);
+    }
+
+    /// Take the outcomes collected since the last call
+    pub fn drain_outcomes(&self) -> Vec<ReceiptOutcome> {
+        self.outcomes
+            .lock()
+            .map(|mut outcomes| std::mem::take(&mut *outcomes))
+            .unwrap_or_default()
+    }
+}
+
+fn platform() -> &'static str {
+    if cfg!(target_arch = "wasm32") {
+        "web"
+    } else {
+        std::env::consts::OS
+    }
+}
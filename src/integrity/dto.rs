@@ -0,0 +1,65 @@
+/// Data Transfer Objects for Integrity
+use serde::{Deserialize, Serialize};
+
+/// Tamper signals reported to the backend; `None` means the check isn't available here
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct IntegritySignals {
+    pub platform: String,
+    pub sdk_version: String,
+    pub debugger_attached: Option<bool>,
+    /// Local clock minus server clock, in seconds
+    pub clock_skew_seconds: Option<i64>,
+    /// SHA-256 of the running executable (native only)
+    pub binary_hash: Option<String>,
+}
+
+/// Request to the integrity endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AttestRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signals: Option<IntegritySignals>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ServerTimeResult {
+    /// Unix timestamp
+    pub server_time: i64,
+}
+
+/// How much the backend trusts this client after weighing its signals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrustLevel {
+    Trusted,
+    /// Requests are accepted but may be reviewed or rate limited
+    Suspicious,
+    /// Sensitive requests will be rejected
+    Untrusted,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Short-lived token attached to score submits and currency spends
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct IntegrityToken {
+    pub token: String,
+    /// Unix timestamp
+    pub expires_at: i64,
+    pub trust_level: TrustLevel,
+}
+
+impl IntegrityToken {
+    pub fn is_valid_at(&self, now: i64) -> bool {
+        now < self.expires_at
+    }
+}
@@ -0,0 +1,154 @@
+/// Integrity handler: reports tamper signals and keeps an integrity token fresh
+use super::dto::*;
+use super::signals;
+use crate::{IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Tokens are refreshed this long before they expire
+const REFRESH_MARGIN_SECONDS: i64 = 60;
+
+#[derive(Resource, Clone)]
+pub struct IntegrityHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    clock_skew: Arc<Mutex<Option<i64>>>,
+    binary_hash: Arc<OnceLock<Option<String>>>,
+    token: Arc<Mutex<Option<IntegrityToken>>>,
+}
+
+impl IntegrityHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            clock_skew: Arc::new(Mutex::new(None)),
+            binary_hash: Arc::new(OnceLock::new()),
+            token: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        if let Ok(mut token) = self.token.lock() {
+            *token = None;
+        }
+    }
+
+    pub fn is_logged_in(&self) -> bool {
+        self.user_id.is_some() && self.session_ticket.is_some()
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    fn request(&self, function_name: &str) -> IdosResult<AttestRequest> {
+        Ok(AttestRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            signals: None,
+        })
+    }
+
+    /// Compare the local clock with the server's; returns local minus server in seconds
+    pub async fn measure_clock_skew(&self) -> IdosResult<i64> {
+        let request = self.request("GetServerTime")?;
+        let endpoint = "integrity/GetServerTime";
+        let result: ServerTimeResult = self.client.post(endpoint, &request).await?;
+
+        let skew = chrono::Utc::now().timestamp() - result.server_time;
+        if let Ok(mut clock_skew) = self.clock_skew.lock() {
+            *clock_skew = Some(skew);
+        }
+        Ok(skew)
+    }
+
+    /// Current tamper signals (clock skew is the last measured value)
+    pub fn collect_signals(&self) -> IntegritySignals {
+        IntegritySignals {
+            platform: signals::platform().to_string(),
+            sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+            debugger_attached: signals::debugger_attached(),
+            clock_skew_seconds: self.clock_skew.lock().ok().and_then(|skew| *skew),
+            binary_hash: self.binary_hash.get_or_init(signals::binary_hash).clone(),
+        }
+    }
+
+    /// Send signals to the backend and get a fresh integrity token
+    pub async fn attest(&self) -> IdosResult<IntegrityToken> {
+        let skew_known = self
+            .clock_skew
+            .lock()
+            .map(|skew| skew.is_some())
+            .unwrap_or(false);
+        if !skew_known {
+            self.measure_clock_skew().await.ok();
+        }
+
+        let request = AttestRequest {
+            signals: Some(self.collect_signals()),
+            ..self.request("Attest")?
+        };
+        let endpoint = "integrity/Attest";
+        let token: IntegrityToken = self.client.post(endpoint, &request).await?;
+
+        if let Ok(mut current) = self.token.lock() {
+            *current = Some(token.clone());
+        }
+        Ok(token)
+    }
+
+    /// Token to attach to sensitive requests, if one is valid at `now`
+    pub fn current_token(&self, now: i64) -> Option<String> {
+        self.token
+            .lock()
+            .ok()
+            .and_then(|token| token.clone())
+            .filter(|token| token.is_valid_at(now))
+            .map(|token| token.token)
+    }
+
+    /// Trust level from the last attestation
+    pub fn trust_level(&self) -> Option<TrustLevel> {
+        self.token
+            .lock()
+            .ok()
+            .and_then(|token| token.as_ref().map(|token| token.trust_level))
+    }
+
+    /// Whether the token is missing or about to expire
+    pub fn needs_refresh(&self, now: i64) -> bool {
+        self.token
+            .lock()
+            .map(|token| {
+                token
+                    .as_ref()
+                    .is_none_or(|token| !token.is_valid_at(now + REFRESH_MARGIN_SECONDS))
+            })
+            .unwrap_or(true)
+    }
+}
+
+crate::session::session_aware!(IntegrityHandler);
@@ -0,0 +1,76 @@
+/// Integrity Bevy plugin
+use super::handler::IntegrityHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+pub struct IntegrityPlugin;
+
+impl Plugin for IntegrityPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize integrity handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = IntegrityHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<IntegrityHandler>.in_set(SessionSync),
+        );
+
+        app.add_systems(
+            Update,
+            (
+                refresh_integrity_token.run_if(on_timer(Duration::from_secs(30))),
+                attach_integrity_token,
+            ),
+        );
+    }
+}
+
+/// Attest again before the token expires
+fn refresh_integrity_token(handler: Option<Res<IntegrityHandler>>) {
+    let Some(handler) = handler else {
+        return;
+    };
+    if !handler.is_logged_in() || !handler.needs_refresh(chrono::Utc::now().timestamp()) {
+        return;
+    }
+
+    let h = handler.clone();
+    crate::tasks::spawn(async move {
+        h.attest().await.ok();
+    });
+}
+
+/// Hand the current token to the handlers that send sensitive requests
+#[allow(unused_variables)]
+fn attach_integrity_token(
+    handler: Option<Res<IntegrityHandler>>,
+    #[cfg(feature = "leaderboard")] leaderboard: Option<
+        ResMut<crate::leaderboard::LeaderboardHandler>,
+    >,
+    #[cfg(feature = "inventory")] inventory: Option<ResMut<crate::inventory::InventoryHandler>>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+    let token = handler.current_token(chrono::Utc::now().timestamp());
+
+    #[cfg(feature = "leaderboard")]
+    if let Some(mut leaderboard) = leaderboard {
+        if leaderboard.integrity_token() != token.as_deref() {
+            leaderboard.set_integrity_token(token.clone());
+        }
+    }
+
+    #[cfg(feature = "inventory")]
+    if let Some(mut inventory) = inventory {
+        if inventory.integrity_token() != token.as_deref() {
+            inventory.set_integrity_token(token.clone());
+        }
+    }
+}
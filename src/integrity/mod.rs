@@ -0,0 +1,9 @@
+/// Integrity module: tamper signals and backend-issued integrity tokens for sensitive requests
+pub mod dto;
+pub mod handler;
+pub mod integrity_plugin;
+pub mod signals;
+
+pub use dto::*;
+pub use handler::IntegrityHandler;
+pub use integrity_plugin::IntegrityPlugin;
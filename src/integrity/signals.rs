@@ -0,0 +1,56 @@
+//! Local tamper checks
+//! Each returns `None` when the platform gives no reliable answer
+
+/// Whether a debugger or tracer is attached to the process
+pub fn debugger_attached() -> Option<bool> {
+    #[cfg(target_os = "linux")]
+    {
+        // Covers Android too: TracerPid is non-zero under gdb, lldb, strace and frida
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix("TracerPid:"))
+            .and_then(|pid| pid.trim().parse::<u32>().ok())
+            .map(|pid| pid != 0)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// SHA-256 (hex) of the running executable
+/// Reads the whole binary, so call it once and keep the result
+pub fn binary_hash() -> Option<String> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use sha2::{Digest, Sha256};
+        use std::io::Read;
+
+        let mut file = std::fs::File::open(std::env::current_exe().ok()?).ok()?;
+        let mut hasher = Sha256::new();
+        let mut buffer = [0u8; 64 * 1024];
+        loop {
+            let read = file.read(&mut buffer).ok()?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+        Some(hex::encode(hasher.finalize()))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+}
+
+pub fn platform() -> &'static str {
+    if cfg!(target_arch = "wasm32") {
+        "web"
+    } else {
+        std::env::consts::OS
+    }
+}
@@ -0,0 +1,172 @@
+/// Persistent inventory cache
+/// Kept per player in Storage so balances are available before the first fetch after a restart
+use super::dto::{GetUserInventoryResult, InventoryChange};
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InventoryCache {
+    /// item_id -> quantity
+    pub items: HashMap<String, i32>,
+    /// currency_id -> amount
+    pub virtual_currency: HashMap<String, i32>,
+    /// item_instance_id -> item_id, so consumed instances can update `items`
+    pub instances: HashMap<String, String>,
+}
+
+impl InventoryCache {
+    fn storage_key(user_id: &str) -> String {
+        format!("cache_{}", user_id)
+    }
+
+    /// Cache saved for `user_id`, or an empty one
+    pub fn load(storage: &Storage, user_id: &str) -> Self {
+        storage
+            .get(&Self::storage_key(user_id))
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, storage: &Storage, user_id: &str) {
+        if let Ok(json) = serde_json::to_string(self) {
+            storage.set(&Self::storage_key(user_id), &json).ok();
+        }
+    }
+
+    /// Replace the cache with a server inventory, returning what changed
+    pub fn replace(&mut self, result: &GetUserInventoryResult) -> Vec<InventoryChange> {
+        let mut items: HashMap<String, i32> = HashMap::new();
+        let mut instances = HashMap::new();
+        for item in &result.inventory {
+            *items.entry(item.item_id.clone()).or_insert(0) += item.remaining_uses.unwrap_or(1);
+            if let Some(instance_id) = &item.item_instance_id {
+                instances.insert(instance_id.clone(), item.item_id.clone());
+            }
+        }
+
+        let mut changes = diff(&self.items, &items, |item_id, previous, current| {
+            InventoryChange::Item {
+                item_id,
+                previous,
+                current,
+            }
+        });
+        changes.extend(diff(
+            &self.virtual_currency,
+            &result.virtual_currency,
+            |currency_id, previous, current| InventoryChange::Currency {
+                currency_id,
+                previous,
+                current,
+            },
+        ));
+
+        self.items = items;
+        self.virtual_currency = result.virtual_currency.clone();
+        self.instances = instances;
+        changes
+    }
+
+    pub fn adjust_item(&mut self, item_id: &str, delta: i32) -> InventoryChange {
+        let amount = self.items.entry(item_id.to_string()).or_insert(0);
+        let previous = *amount;
+        *amount += delta;
+        InventoryChange::Item {
+            item_id: item_id.to_string(),
+            previous,
+            current: *amount,
+        }
+    }
+
+    pub fn adjust_currency(&mut self, currency_id: &str, delta: i32) -> InventoryChange {
+        let amount = self
+            .virtual_currency
+            .entry(currency_id.to_string())
+            .or_insert(0);
+        let previous = *amount;
+        *amount += delta;
+        InventoryChange::Currency {
+            currency_id: currency_id.to_string(),
+            previous,
+            current: *amount,
+        }
+    }
+}
+
+/// Entries whose amount differs between `before` and `after`
+fn diff(
+    before: &HashMap<String, i32>,
+    after: &HashMap<String, i32>,
+    change: impl Fn(String, i32, i32) -> InventoryChange,
+) -> Vec<InventoryChange> {
+    let mut changes: Vec<InventoryChange> = after
+        .iter()
+        .filter(|(id, amount)| before.get(*id).copied().unwrap_or(0) != **amount)
+        .map(|(id, amount)| change(id.clone(), before.get(id).copied().unwrap_or(0), *amount))
+        .collect();
+    changes.extend(
+        before
+            .iter()
+            .filter(|(id, amount)| !after.contains_key(*id) && **amount != 0)
+            .map(|(id, amount)| change(id.clone(), *amount, 0)),
+    );
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_replace_reports_changes() {
+        let mut cache = InventoryCache::default();
+        cache.adjust_item("potion", 2);
+        cache.adjust_item("shield", 1);
+        cache.adjust_currency("GOLD", 100);
+
+        let result: GetUserInventoryResult = serde_json::from_value(json!({
+            "Inventory": [
+                { "ItemId": "potion", "ItemInstanceId": "i1", "RemainingUses": 3 },
+                { "ItemId": "sword", "ItemInstanceId": "i2" }
+            ],
+            "VirtualCurrency": { "GOLD": 100, "GEMS": 5 }
+        }))
+        .unwrap();
+
+        let mut changes = cache.replace(&result);
+        changes.sort_by_key(|change| format!("{:?}", change));
+        assert_eq!(
+            changes,
+            vec![
+                InventoryChange::Currency {
+                    currency_id: "GEMS".to_string(),
+                    previous: 0,
+                    current: 5
+                },
+                InventoryChange::Item {
+                    item_id: "potion".to_string(),
+                    previous: 2,
+                    current: 3
+                },
+                InventoryChange::Item {
+                    item_id: "shield".to_string(),
+                    previous: 1,
+                    current: 0
+                },
+                InventoryChange::Item {
+                    item_id: "sword".to_string(),
+                    previous: 0,
+                    current: 1
+                },
+            ]
+        );
+        assert_eq!(
+            cache.instances.get("i1").map(String::as_str),
+            Some("potion")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 /// Data Transfer Objects for Inventory
+use bevy::prelude::Message;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -58,6 +59,8 @@ pub struct SubtractVirtualCurrencyRequest {
     #[serde(rename = "CurrencyID")]
     pub currency_id: String,
     pub amount: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrity_token: Option<String>,
 }
 
 /// Request to grant items to user
@@ -97,3 +100,42 @@ pub struct ConsumeItemResponse {
     pub item_instance_id: String,
     pub remaining_uses: i32,
 }
+
+/// One cached amount that changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryChange {
+    Item {
+        item_id: String,
+        previous: i32,
+        current: i32,
+    },
+    Currency {
+        currency_id: String,
+        previous: i32,
+        current: i32,
+    },
+}
+
+/// Why the cached inventory changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InventoryChangeReason {
+    /// Restored from storage after login
+    Restored,
+    /// Fetched from the server
+    Refreshed,
+    /// Applied locally while the server request is in flight
+    Optimistic,
+    /// An optimistic update undone after the server refused it
+    RolledBack,
+    /// Granted elsewhere (mail, ad rewards)
+    Credited,
+    /// Bought from the catalog with virtual currency
+    Purchased,
+}
+
+/// The cached inventory changed; written by InventoryPlugin
+#[derive(Message, Debug, Clone)]
+pub struct InventoryChanged {
+    pub reason: InventoryChangeReason,
+    pub changes: Vec<InventoryChange>,
+}
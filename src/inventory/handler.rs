@@ -1,42 +1,86 @@
 /// Inventory handler for items and virtual currency management
+/// The cache is persisted per player and updated optimistically; changes are
+/// delivered as `InventoryChanged` messages by InventoryPlugin
+use super::cache::InventoryCache;
 use super::dto::*;
-use crate::{IdosClient, IdosError, IdosResult};
+use crate::{storage::Storage, IdosClient, IdosError, IdosResult};
 use bevy::prelude::Resource;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 #[derive(Resource, Clone)]
 pub struct InventoryHandler {
     client: IdosClient,
     user_id: Option<String>,
     session_ticket: Option<String>,
-    // Cached inventory data
-    items: HashMap<String, i32>,            // item_id -> quantity
-    virtual_currency: HashMap<String, i32>, // currency_id -> amount
+    /// Attached to sensitive requests (see the integrity module)
+    integrity_token: Option<String>,
+    /// Cached inventory data, shared with clones running requests
+    cache: Arc<Mutex<InventoryCache>>,
+    storage: Storage,
+    /// Changes not yet written as `InventoryChanged` messages
+    changes: Arc<Mutex<Vec<InventoryChanged>>>,
 }
 
 impl InventoryHandler {
     pub fn new(client: IdosClient) -> Self {
+        let storage = Storage::cache(client.config(), "inventory_");
         Self {
             client,
             user_id: None,
             session_ticket: None,
-            items: HashMap::new(),
-            virtual_currency: HashMap::new(),
+            integrity_token: None,
+            cache: Arc::new(Mutex::new(InventoryCache::default())),
+            storage,
+            changes: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
     /// Set user authentication info (call after login)
+    /// Restores the inventory cached for this player by a previous run
     pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        let restored = InventoryCache::load(&self.storage, &user_id);
+        let changes = restored
+            .items
+            .iter()
+            .map(|(item_id, amount)| InventoryChange::Item {
+                item_id: item_id.clone(),
+                previous: 0,
+                current: *amount,
+            })
+            .chain(
+                restored
+                    .virtual_currency
+                    .iter()
+                    .map(|(currency_id, amount)| InventoryChange::Currency {
+                        currency_id: currency_id.clone(),
+                        previous: 0,
+                        current: *amount,
+                    }),
+            )
+            .collect();
+
+        *self.cache_mut() = restored;
         self.user_id = Some(user_id);
         self.session_ticket = Some(session_ticket);
+        self.push_changes(InventoryChangeReason::Restored, changes);
     }
 
     /// Clear authentication info (call on logout)
+    /// The persisted cache is kept for the player's next login
     pub fn clear_auth(&mut self) {
         self.user_id = None;
         self.session_ticket = None;
-        self.items.clear();
-        self.virtual_currency.clear();
+        *self.cache_mut() = InventoryCache::default();
+    }
+
+    /// Token attached to sensitive requests; kept current by IntegrityPlugin
+    pub fn set_integrity_token(&mut self, token: Option<String>) {
+        self.integrity_token = token;
+    }
+
+    pub fn integrity_token(&self) -> Option<&str> {
+        self.integrity_token.as_deref()
     }
 
     fn get_user_id(&self) -> IdosResult<String> {
@@ -51,8 +95,46 @@ impl InventoryHandler {
             .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
     }
 
+    fn cache_mut(&self) -> std::sync::MutexGuard<'_, InventoryCache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Apply cache updates, persist them and queue an `InventoryChanged`
+    fn update(
+        &self,
+        reason: InventoryChangeReason,
+        apply: impl FnOnce(&mut InventoryCache) -> Vec<InventoryChange>,
+    ) {
+        let changes = {
+            let mut cache = self.cache_mut();
+            let changes = apply(&mut cache);
+            if let Some(user_id) = &self.user_id {
+                cache.save(&self.storage, user_id);
+            }
+            changes
+        };
+        self.push_changes(reason, changes);
+    }
+
+    fn push_changes(&self, reason: InventoryChangeReason, changes: Vec<InventoryChange>) {
+        if changes.is_empty() {
+            return;
+        }
+        if let Ok(mut queued) = self.changes.lock() {
+            queued.push(InventoryChanged { reason, changes });
+        }
+    }
+
+    /// Take the changes collected since the last call
+    pub fn drain_changes(&self) -> Vec<InventoryChanged> {
+        self.changes
+            .lock()
+            .map(|mut changes| std::mem::take(&mut *changes))
+            .unwrap_or_default()
+    }
+
     /// Get user's complete inventory from server
-    pub async fn get_inventory(&mut self) -> IdosResult<GetUserInventoryResult> {
+    pub async fn get_inventory(&self) -> IdosResult<GetUserInventoryResult> {
         use serde::Serialize;
 
         #[derive(Serialize)]
@@ -83,32 +165,24 @@ impl InventoryHandler {
     }
 
     /// Update local cache from inventory result
-    fn update_cache(&mut self, result: &GetUserInventoryResult) {
-        self.items.clear();
-        self.virtual_currency.clear();
-
-        // Cache items
-        for item in &result.inventory {
-            let count = self.items.get(&item.item_id).unwrap_or(&0);
-            let remaining_uses = item.remaining_uses.unwrap_or(1);
-            self.items
-                .insert(item.item_id.clone(), count + remaining_uses);
-        }
-
-        // Cache virtual currency
-        for (currency_id, amount) in &result.virtual_currency {
-            self.virtual_currency.insert(currency_id.clone(), *amount);
-        }
+    fn update_cache(&self, result: &GetUserInventoryResult) {
+        self.update(InventoryChangeReason::Refreshed, |cache| {
+            cache.replace(result)
+        });
     }
 
     /// Get item amount from cache (call get_inventory first to refresh)
     pub fn get_item_amount(&self, item_id: &str) -> i32 {
-        *self.items.get(item_id).unwrap_or(&0)
+        self.cache_mut().items.get(item_id).copied().unwrap_or(0)
     }
 
     /// Get virtual currency amount from cache (call get_inventory first to refresh)
     pub fn get_virtual_currency_amount(&self, currency_id: &str) -> i32 {
-        *self.virtual_currency.get(currency_id).unwrap_or(&0)
+        self.cache_mut()
+            .virtual_currency
+            .get(currency_id)
+            .copied()
+            .unwrap_or(0)
     }
 
     /// Check if user has specific item
@@ -122,8 +196,9 @@ impl InventoryHandler {
     }
 
     /// Subtract virtual currency (server-side operation)
+    /// The cached balance drops immediately and is restored if the server refuses
     pub async fn subtract_virtual_currency(
-        &mut self,
+        &self,
         currency_id: &str,
         amount: i32,
     ) -> IdosResult<String> {
@@ -145,22 +220,28 @@ impl InventoryHandler {
             client_session_ticket: self.get_session_ticket()?,
             currency_id: currency_id.to_string(),
             amount,
+            integrity_token: self.integrity_token.clone(),
         };
 
-        let endpoint = "inventory/subtract-currency";
-        let response: String = self.client.post(endpoint, &request).await?;
+        self.update(InventoryChangeReason::Optimistic, |cache| {
+            vec![cache.adjust_currency(currency_id, -amount)]
+        });
 
-        // Update local cache
-        if let Some(current) = self.virtual_currency.get_mut(currency_id) {
-            *current -= amount;
+        let endpoint = "inventory/subtract-currency";
+        let response: Result<String, _> = self.client.post(endpoint, &request).await;
+        if response.is_err() {
+            self.update(InventoryChangeReason::RolledBack, |cache| {
+                vec![cache.adjust_currency(currency_id, amount)]
+            });
         }
 
-        Ok(response)
+        response
     }
 
     /// Grant items to user (server-side operation)
+    /// Items show up in the cache immediately and are removed again if the server refuses
     pub async fn grant_items(
-        &mut self,
+        &self,
         item_ids: Vec<String>,
         catalog_version: Option<String>,
     ) -> IdosResult<Vec<ItemInstance>> {
@@ -173,21 +254,46 @@ impl InventoryHandler {
             catalog_version,
         };
 
+        self.update(InventoryChangeReason::Optimistic, |cache| {
+            item_ids
+                .iter()
+                .map(|item_id| cache.adjust_item(item_id, 1))
+                .collect()
+        });
+
         let endpoint = "inventory/grant-items";
-        let result: Vec<ItemInstance> = self.client.post(endpoint, &request).await?;
+        let result: Vec<ItemInstance> = match self.client.post(endpoint, &request).await {
+            Ok(result) => result,
+            Err(e) => {
+                self.update(InventoryChangeReason::RolledBack, |cache| {
+                    item_ids
+                        .iter()
+                        .map(|item_id| cache.adjust_item(item_id, -1))
+                        .collect()
+                });
+                return Err(e);
+            }
+        };
 
-        // Update local cache
-        for item_id in item_ids {
-            let count = self.items.get(&item_id).unwrap_or(&0);
-            self.items.insert(item_id, count + 1);
+        // Remember the new instances so they can be consumed
+        {
+            let mut cache = self.cache_mut();
+            for item in &result {
+                if let Some(instance_id) = &item.item_instance_id {
+                    cache
+                        .instances
+                        .insert(instance_id.clone(), item.item_id.clone());
+                }
+            }
         }
 
         Ok(result)
     }
 
     /// Consume an item (reduce remaining uses or remove)
+    /// Instances known to the cache are deducted immediately and restored if the server refuses
     pub async fn consume_item(
-        &mut self,
+        &self,
         item_instance_id: &str,
         consume_count: i32,
     ) -> IdosResult<ConsumeItemResponse> {
@@ -200,22 +306,76 @@ impl InventoryHandler {
             consume_count,
         };
 
+        let item_id = self.cache_mut().instances.get(item_instance_id).cloned();
+        if let Some(item_id) = &item_id {
+            self.update(InventoryChangeReason::Optimistic, |cache| {
+                vec![cache.adjust_item(item_id, -consume_count)]
+            });
+        }
+
         let endpoint = "inventory/consume-item";
-        let response: ConsumeItemResponse = self.client.post(endpoint, &request).await?;
+        let response: ConsumeItemResponse = match self.client.post(endpoint, &request).await {
+            Ok(response) => response,
+            Err(e) => {
+                if let Some(item_id) = &item_id {
+                    self.update(InventoryChangeReason::RolledBack, |cache| {
+                        vec![cache.adjust_item(item_id, consume_count)]
+                    });
+                }
+                return Err(e);
+            }
+        };
 
-        // Note: Cache update requires knowing item_id from item_instance_id
-        // In production, you'd track instance_id -> item_id mapping
+        if response.remaining_uses <= 0 {
+            self.cache_mut().instances.remove(item_instance_id);
+        }
 
         Ok(response)
     }
 
+    /// Add items granted elsewhere (mail, rewards) to the cache without refetching
+    pub fn credit_item(&self, item_id: &str, amount: i32) {
+        self.update(InventoryChangeReason::Credited, |cache| {
+            vec![cache.adjust_item(item_id, amount)]
+        });
+    }
+
+    /// Add currency granted elsewhere (mail, rewards) to the cache without refetching
+    pub fn credit_currency(&self, currency_id: &str, amount: i32) {
+        self.update(InventoryChangeReason::Credited, |cache| {
+            vec![cache.adjust_currency(currency_id, amount)]
+        });
+    }
+
+    /// Apply a catalog purchase the server already made: deduct the price and add the
+    /// granted `(item_id, item_instance_id)` pairs
+    pub fn record_purchase(
+        &self,
+        currency_id: &str,
+        price: i32,
+        granted: &[(String, Option<String>)],
+    ) {
+        self.update(InventoryChangeReason::Purchased, |cache| {
+            let mut changes = vec![cache.adjust_currency(currency_id, -price)];
+            for (item_id, instance_id) in granted {
+                changes.push(cache.adjust_item(item_id, 1));
+                if let Some(instance_id) = instance_id {
+                    cache.instances.insert(instance_id.clone(), item_id.clone());
+                }
+            }
+            changes
+        });
+    }
+
     /// Get all cached items
-    pub fn get_all_items(&self) -> &HashMap<String, i32> {
-        &self.items
+    pub fn get_all_items(&self) -> HashMap<String, i32> {
+        self.cache_mut().items.clone()
     }
 
     /// Get all cached virtual currencies
-    pub fn get_all_currencies(&self) -> &HashMap<String, i32> {
-        &self.virtual_currency
+    pub fn get_all_currencies(&self) -> HashMap<String, i32> {
+        self.cache_mut().virtual_currency.clone()
     }
 }
+
+crate::session::session_aware!(InventoryHandler);
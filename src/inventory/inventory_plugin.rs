@@ -1,5 +1,7 @@
 /// Inventory Bevy plugin
+use super::dto::InventoryChanged;
 use super::handler::InventoryHandler;
+use crate::session::{sync_session, SessionSync};
 use crate::IdosClient;
 use bevy::prelude::*;
 
@@ -12,5 +14,27 @@ impl Plugin for InventoryPlugin {
             let handler = InventoryHandler::new(client.clone());
             app.insert_resource(handler);
         }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<InventoryHandler>.in_set(SessionSync),
+        );
+
+        app.add_message::<InventoryChanged>()
+            .add_systems(Update, forward_inventory_changes);
+    }
+}
+
+/// Forward cache changes as messages so UI can react without polling
+fn forward_inventory_changes(
+    handler: Option<Res<InventoryHandler>>,
+    mut changed: MessageWriter<InventoryChanged>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for change in handler.drain_changes() {
+        changed.write(change);
     }
 }
@@ -1,8 +1,10 @@
 /// Inventory module for item and currency management
+pub mod cache;
 pub mod dto;
 pub mod handler;
 pub mod inventory_plugin;
 
+pub use cache::InventoryCache;
 pub use dto::*;
 pub use handler::InventoryHandler;
 pub use inventory_plugin::InventoryPlugin;
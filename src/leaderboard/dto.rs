@@ -142,4 +142,78 @@ pub struct UpdateStatisticRequest {
     pub client_session_ticket: String,
     pub statistic_name: String,
     pub value: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrity_token: Option<String>,
+    /// Single-use value so a captured submission can't be replayed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// Unix timestamp (seconds) of the submission
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    /// Hex HMAC-SHA256 of `signing_payload` with the key provisioned at login
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl UpdateStatisticRequest {
+    /// Bytes covered by the signature: statistic, value, nonce, timestamp and session ticket,
+    /// newline-separated
+    pub fn signing_payload(&self) -> String {
+        format!(
+            "{}\n{}\n{}\n{}\n{}",
+            self.statistic_name,
+            self.value,
+            self.nonce.as_deref().unwrap_or_default(),
+            self.timestamp.unwrap_or_default(),
+            self.client_session_ticket
+        )
+    }
+
+    /// Set a fresh nonce and timestamp and sign the request with `key`
+    pub fn sign(&mut self, key: &[u8], nonce: String, timestamp: i64) {
+        use hmac::{Hmac, Mac};
+
+        self.nonce = Some(nonce);
+        self.timestamp = Some(timestamp);
+        let mut mac =
+            Hmac::<sha2::Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+        mac.update(self.signing_payload().as_bytes());
+        self.signature = Some(hex::encode(mac.finalize().into_bytes()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_statistic_request() {
+        let mut request = UpdateStatisticRequest {
+            title_id: "title".to_string(),
+            build_key: String::new(),
+            user_id: "user-1".to_string(),
+            client_session_ticket: "ticket-1".to_string(),
+            statistic_name: "high_score".to_string(),
+            value: 1200,
+            integrity_token: None,
+            nonce: None,
+            timestamp: None,
+            signature: None,
+        };
+        let unsigned = serde_json::to_value(&request).unwrap();
+        assert!(unsigned.get("Signature").is_none());
+
+        request.sign(b"secret-key", "nonce-1".to_string(), 1_700_000_000);
+        assert_eq!(
+            request.signing_payload(),
+            "high_score\n1200\nnonce-1\n1700000000\nticket-1"
+        );
+        let signed = serde_json::to_value(&request).unwrap();
+        assert_eq!(signed["Nonce"], "nonce-1");
+        assert_eq!(signed["Timestamp"], 1_700_000_000);
+        assert_eq!(
+            signed["Signature"],
+            "1e20769d6c61ee3dbcb2c347d4d9537e27643725dfc0bdba9a6636e9d3c22aeb"
+        );
+    }
 }
@@ -1,6 +1,7 @@
 /// Leaderboard handler for competitive rankings
 use super::dto::*;
 use crate::{IdosClient, IdosError, IdosResult};
+use base64::{engine::general_purpose, Engine as _};
 use bevy::prelude::Resource;
 
 #[derive(Resource, Clone)]
@@ -8,6 +9,10 @@ pub struct LeaderboardHandler {
     client: IdosClient,
     user_id: Option<String>,
     session_ticket: Option<String>,
+    /// Attached to sensitive requests (see the integrity module)
+    integrity_token: Option<String>,
+    /// HMAC key provisioned at login; when set, statistic updates are signed
+    signing_key: Option<Vec<u8>>,
 }
 
 impl LeaderboardHandler {
@@ -16,6 +21,8 @@ impl LeaderboardHandler {
             client,
             user_id: None,
             session_ticket: None,
+            integrity_token: None,
+            signing_key: None,
         }
     }
 
@@ -29,6 +36,31 @@ impl LeaderboardHandler {
     pub fn clear_auth(&mut self) {
         self.user_id = None;
         self.session_ticket = None;
+        self.signing_key = None;
+    }
+
+    /// Token attached to sensitive requests; kept current by IntegrityPlugin
+    pub fn set_integrity_token(&mut self, token: Option<String>) {
+        self.integrity_token = token;
+    }
+
+    pub fn integrity_token(&self) -> Option<&str> {
+        self.integrity_token.as_deref()
+    }
+
+    /// Sign statistic updates with this base64 key (`AuthResponse::statistic_signing_key`)
+    /// LeaderboardPlugin sets it on `LoginSucceeded`; None switches back to unsigned updates
+    pub fn set_signing_key(&mut self, key: Option<&str>) -> IdosResult<()> {
+        self.signing_key = key
+            .map(|key| general_purpose::STANDARD.decode(key))
+            .transpose()
+            .map_err(|e| IdosError::InvalidInput(format!("Invalid signing key: {}", e)))?;
+        Ok(())
+    }
+
+    /// Whether statistic updates are signed
+    pub fn is_signing(&self) -> bool {
+        self.signing_key.is_some()
     }
 
     fn get_user_id(&self) -> IdosResult<String> {
@@ -82,15 +114,28 @@ impl LeaderboardHandler {
 
     /// Update player's statistic value (score)
     /// Note: In production, score updates usually happen on server-side to prevent cheating
+    /// With a signing key the request carries a nonce, timestamp and HMAC so the backend can
+    /// reject tampered or replayed submissions
     pub async fn update_statistic(&self, statistic_name: &str, value: i32) -> IdosResult<String> {
-        let request = UpdateStatisticRequest {
+        let mut request = UpdateStatisticRequest {
             title_id: self.client.game_id().to_string(),
             build_key: String::new(),
             user_id: self.get_user_id()?,
             client_session_ticket: self.get_session_ticket()?,
             statistic_name: statistic_name.to_string(),
             value,
+            integrity_token: self.integrity_token.clone(),
+            nonce: None,
+            timestamp: None,
+            signature: None,
         };
+        if let Some(key) = &self.signing_key {
+            request.sign(
+                key,
+                uuid::Uuid::new_v4().to_string(),
+                chrono::Utc::now().timestamp(),
+            );
+        }
 
         let endpoint = "statistics/update";
         self.client.post(endpoint, &request).await
@@ -134,3 +179,5 @@ impl LeaderboardHandler {
         false
     }
 }
+
+crate::session::session_aware!(LeaderboardHandler);
@@ -1,5 +1,6 @@
 /// Leaderboard Bevy plugin
 use super::handler::LeaderboardHandler;
+use crate::session::{sync_session, SessionSync};
 use crate::IdosClient;
 use bevy::prelude::*;
 
@@ -12,5 +13,33 @@ impl Plugin for LeaderboardPlugin {
             let handler = LeaderboardHandler::new(client.clone());
             app.insert_resource(handler);
         }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<LeaderboardHandler>.in_set(SessionSync),
+        );
+
+        #[cfg(feature = "auth")]
+        app.add_message::<crate::auth::dto::LoginSucceeded>()
+            .add_systems(Update, provision_signing_key);
+    }
+}
+
+/// Take the statistic signing key from the login response
+#[cfg(feature = "auth")]
+fn provision_signing_key(
+    handler: Option<ResMut<LeaderboardHandler>>,
+    mut succeeded: MessageReader<crate::auth::dto::LoginSucceeded>,
+) {
+    let Some(mut handler) = handler else {
+        return;
+    };
+
+    for crate::auth::dto::LoginSucceeded(response) in succeeded.read() {
+        let key = response.statistic_signing_key();
+        if let Err(e) = handler.set_signing_key(key.as_deref()) {
+            warn!("Ignoring statistic signing key: {}", e);
+            handler.set_signing_key(None).ok();
+        }
     }
 }
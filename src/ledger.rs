@@ -0,0 +1,201 @@
+/// Ledger device transport shared by the Ethereum signer and in-game hardware wallets
+/// APDU framing, status words and BIP-32 path encoding common to the Ledger coin apps
+use crate::{IdosError, IdosResult};
+use async_trait::async_trait;
+
+/// Ledger HID/WebUSB report size in bytes
+pub const LEDGER_PACKET_SIZE: usize = 64;
+
+/// Largest APDU data field
+pub(crate) const MAX_CHUNK_SIZE: usize = 255;
+
+/// Instruction class used by the Ethereum and Solana apps
+const LEDGER_CLA: u8 = 0xE0;
+const LEDGER_CHANNEL: u16 = 0x0101;
+const LEDGER_TAG_APDU: u8 = 0x05;
+
+/// Raw APDU exchange with a Ledger device
+///
+/// Implement this over `hidapi` on desktop or WebUSB/WebHID in the browser.
+/// Transports that only move 64-byte reports can use [`wrap_hid_apdu`] and
+/// [`unwrap_hid_response`] for the Ledger framing.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait LedgerTransport: Send + Sync {
+    /// Send a full APDU command and return the response including the status word
+    async fn exchange(&self, apdu: &[u8]) -> IdosResult<Vec<u8>>;
+}
+
+/// Serialize a BIP-32 path ("m/44'/60'/0'/0/0") into the Ledger wire format
+pub fn encode_derivation_path(path: &str) -> IdosResult<Vec<u8>> {
+    let components: Vec<&str> = path
+        .trim_start_matches("m/")
+        .split('/')
+        .filter(|c| !c.is_empty())
+        .collect();
+
+    if components.is_empty() || components.len() > 10 {
+        return Err(IdosError::InvalidInput(format!(
+            "Invalid derivation path: {}",
+            path
+        )));
+    }
+
+    let mut bytes = vec![components.len() as u8];
+    for component in components {
+        let (index, hardened) = match component.strip_suffix('\'') {
+            Some(index) => (index, true),
+            None => (component, false),
+        };
+
+        let mut value: u32 = index.parse().map_err(|_| {
+            IdosError::InvalidInput(format!("Invalid derivation path component: {}", component))
+        })?;
+        if hardened {
+            value |= 0x8000_0000;
+        }
+        bytes.extend_from_slice(&value.to_be_bytes());
+    }
+
+    Ok(bytes)
+}
+
+/// Split an APDU into 64-byte Ledger HID/WebUSB reports
+pub fn wrap_hid_apdu(apdu: &[u8]) -> Vec<[u8; LEDGER_PACKET_SIZE]> {
+    let mut data = Vec::with_capacity(apdu.len() + 2);
+    data.extend_from_slice(&(apdu.len() as u16).to_be_bytes());
+    data.extend_from_slice(apdu);
+
+    let chunk_size = LEDGER_PACKET_SIZE - 5;
+    data.chunks(chunk_size)
+        .enumerate()
+        .map(|(sequence, chunk)| {
+            let mut packet = [0u8; LEDGER_PACKET_SIZE];
+            packet[0..2].copy_from_slice(&LEDGER_CHANNEL.to_be_bytes());
+            packet[2] = LEDGER_TAG_APDU;
+            packet[3..5].copy_from_slice(&(sequence as u16).to_be_bytes());
+            packet[5..5 + chunk.len()].copy_from_slice(chunk);
+            packet
+        })
+        .collect()
+}
+
+/// Reassemble a response from 64-byte Ledger HID/WebUSB reports
+///
+/// Returns `Ok(None)` while more reports are needed.
+pub fn unwrap_hid_response(packets: &[[u8; LEDGER_PACKET_SIZE]]) -> IdosResult<Option<Vec<u8>>> {
+    let mut payload = Vec::new();
+    let mut expected_len = None;
+
+    for (sequence, packet) in packets.iter().enumerate() {
+        if u16::from_be_bytes([packet[0], packet[1]]) != LEDGER_CHANNEL
+            || packet[2] != LEDGER_TAG_APDU
+            || u16::from_be_bytes([packet[3], packet[4]]) as usize != sequence
+        {
+            return Err(IdosError::Wallet("Malformed Ledger HID packet".to_string()));
+        }
+
+        let body = if sequence == 0 {
+            expected_len = Some(u16::from_be_bytes([packet[5], packet[6]]) as usize);
+            &packet[7..]
+        } else {
+            &packet[5..]
+        };
+        payload.extend_from_slice(body);
+    }
+
+    match expected_len {
+        Some(len) if payload.len() >= len => {
+            payload.truncate(len);
+            Ok(Some(payload))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub(crate) fn build_apdu(ins: u8, p1: u8, p2: u8, data: &[u8]) -> Vec<u8> {
+    let mut apdu = Vec::with_capacity(5 + data.len());
+    apdu.extend_from_slice(&[LEDGER_CLA, ins, p1, p2, data.len() as u8]);
+    apdu.extend_from_slice(data);
+    apdu
+}
+
+/// Send `payload` in APDU-sized chunks (P1 0x00 first, 0x80 after) and return the last response
+/// Framing used by the Ethereum app's signing commands
+pub(crate) async fn exchange_chunked(
+    transport: &dyn LedgerTransport,
+    ins: u8,
+    p2: u8,
+    payload: &[u8],
+) -> IdosResult<Vec<u8>> {
+    if payload.is_empty() {
+        return Err(IdosError::InvalidInput(
+            "Cannot sign an empty payload".to_string(),
+        ));
+    }
+
+    // Avoid a trailing 3-byte chunk (LedgerHQ/app-ethereum#409)
+    let chunk_size = (1..=MAX_CHUNK_SIZE)
+        .rev()
+        .find(|size| payload.len() % size != 3)
+        .unwrap_or(MAX_CHUNK_SIZE);
+
+    let mut response = Vec::new();
+    for (index, chunk) in payload.chunks(chunk_size).enumerate() {
+        let p1 = if index == 0 { 0x00 } else { 0x80 };
+        let apdu = build_apdu(ins, p1, p2, chunk);
+        response = check_status(transport.exchange(&apdu).await?)?;
+    }
+
+    Ok(response)
+}
+
+/// Strip the status word, mapping device errors to readable messages
+pub(crate) fn check_status(mut response: Vec<u8>) -> IdosResult<Vec<u8>> {
+    if response.len() < 2 {
+        return Err(IdosError::Wallet("Empty response from Ledger".to_string()));
+    }
+
+    let sw = u16::from_be_bytes([response[response.len() - 2], response[response.len() - 1]]);
+    response.truncate(response.len() - 2);
+
+    match sw {
+        0x9000 => Ok(response),
+        0x6985 => Err(IdosError::Wallet(
+            "Request rejected on Ledger device".to_string(),
+        )),
+        0x5515 => Err(IdosError::Wallet("Ledger device is locked".to_string())),
+        0x6a80 => Err(IdosError::Wallet(
+            "Ledger rejected the data (enable blind signing for contract calls)".to_string(),
+        )),
+        0x6d00 | 0x6e00 | 0x6e01 => Err(IdosError::Wallet(
+            "Open the Ethereum or Solana app on the Ledger device".to_string(),
+        )),
+        other => Err(IdosError::Wallet(format!(
+            "Ledger returned status 0x{:04x}",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hid_framing_roundtrip() {
+        let mut response = vec![0xAB; 150];
+        response.extend_from_slice(&[0x90, 0x00]);
+
+        let packets = wrap_hid_apdu(&response);
+        assert_eq!(packets.len(), 3);
+        assert!(unwrap_hid_response(&packets[..2]).unwrap().is_none());
+        assert_eq!(unwrap_hid_response(&packets).unwrap().unwrap(), response);
+    }
+
+    #[test]
+    fn test_check_status() {
+        assert_eq!(check_status(vec![1, 2, 0x90, 0x00]).unwrap(), vec![1, 2]);
+        assert!(check_status(vec![0x69, 0x85]).is_err());
+    }
+}
@@ -9,7 +9,25 @@
 //! - **Analytics**: Event tracking and user behavior analytics
 //! - **Leaderboards**: Global and regional leaderboards
 //! - **Inventory**: Item management and virtual currency
+//! - **Catalog**: Item catalog and virtual-currency stores with purchases
 //! - **Marketplace**: Player-to-player trading
+//! - **Notifications**: Push token registration, topics, opened-notification events and in-app messages
+//! - **Achievements**: Progress tracking with offline caching, unlocks and rewards
+//! - **Quests**: Daily, weekly and event missions with typed objectives
+//! - **Referrals**: Invite codes, referred-friend milestones and rewards
+//! - **News**: Localized announcements with read state and unread counts
+//! - **Mailbox**: Server-sent mail with claimable item and currency attachments
+//! - **Ads**: Rewarded and interstitial ads with server-side reward verification
+//! - **Deep Links**: Launch/resume URLs parsed into typed actions
+//! - **Localization**: Remote string tables with caching and hot reload
+//! - **Integrity**: Tamper signals and integrity tokens for sensitive requests
+//! - **Support**: Help center tickets with attached diagnostics
+//! - **Moderation**: Profanity and name checks with a local wordlist fallback
+//! - **Profiles**: Public player profiles with badges and NFT showcases
+//! - **Tournaments**: Bracketed tournaments with match pairings and placement rewards
+//! - **Functions**: Server-side script (CloudScript) invocation with typed results
+//! - **Presence**: Online/away/in-match status with rich status strings for friends
+//! - **Remote Config**: Server-driven tuning values and A/B test assignments with caching
 //! - **Crypto Wallets**: Ethereum and Solana wallet integration
 //!
 //! # Quick Start
@@ -33,7 +51,9 @@
 pub mod client;
 pub mod config;
 pub mod error;
+pub mod session;
 pub mod storage;
+pub mod tasks;
 
 // Feature-gated modules
 #[cfg(feature = "auth")]
@@ -51,9 +71,63 @@ pub mod leaderboard;
 #[cfg(feature = "inventory")]
 pub mod inventory;
 
+#[cfg(feature = "catalog")]
+pub mod catalog;
+
 #[cfg(feature = "marketplace")]
 pub mod marketplace;
 
+#[cfg(feature = "notifications")]
+pub mod notifications;
+
+#[cfg(feature = "achievements")]
+pub mod achievements;
+
+#[cfg(feature = "quests")]
+pub mod quests;
+
+#[cfg(feature = "referrals")]
+pub mod referrals;
+
+#[cfg(feature = "news")]
+pub mod news;
+
+#[cfg(feature = "mailbox")]
+pub mod mailbox;
+
+#[cfg(feature = "ads")]
+pub mod ads;
+
+#[cfg(feature = "deeplinks")]
+pub mod deeplinks;
+
+#[cfg(feature = "localization")]
+pub mod localization;
+
+#[cfg(feature = "integrity")]
+pub mod integrity;
+
+#[cfg(feature = "support")]
+pub mod support;
+
+#[cfg(feature = "moderation")]
+pub mod moderation;
+
+#[cfg(feature = "profiles")]
+pub mod profiles;
+
+#[cfg(feature = "tournaments")]
+pub mod tournaments;
+
+#[cfg(feature = "functions")]
+pub mod functions;
+
+#[cfg(feature = "presence")]
+pub mod presence;
+
+#[cfg(feature = "remote_config")]
+pub mod remote_config;
+
 #[cfg(feature = "crypto_ethereum")]
 pub mod crypto_ethereum;
 
@@ -63,13 +137,21 @@ pub mod crypto_solana;
 #[cfg(feature = "wallet")]
 pub mod wallet;
 
+#[cfg(any(feature = "crypto_ethereum", feature = "wallet"))]
+pub mod ledger;
+
+#[cfg(any(feature = "crypto_ethereum", feature = "crypto_solana"))]
+pub mod transaction_history;
+
 // Re-exports
 pub use analytics::AnalyticsPlugin;
 pub use auth::auth_plugin::AuthPlugin;
-pub use client::IdosClient;
+pub use client::{ErrorHook, IdosClient};
 pub use config::IdosConfig;
-pub use error::{IdosError, IdosResult};
+pub use error::{ApiErrorCode, IdosError, IdosResult, ReferralRejection};
 pub use iap::iap_plugin::IapPlugin;
+pub use session::AuthSession;
+pub use tasks::{IdosTask, IdosTasks};
 
 use bevy::prelude::*;
 
@@ -93,6 +175,13 @@ impl Plugin for IdosGamesPlugin {
         let client = IdosClient::new(self.config.clone());
         app.insert_resource(client);
 
+        // Login session shared by every feature plugin
+        app.init_resource::<AuthSession>();
+
+        // Async task bridge used by every feature plugin
+        app.init_resource::<IdosTasks>()
+            .add_systems(PreUpdate, tasks::apply_finished_tasks);
+
         // Add feature-specific plugins
         #[cfg(feature = "auth")]
         app.add_plugins(AuthPlugin);
@@ -109,10 +198,62 @@ impl Plugin for IdosGamesPlugin {
         #[cfg(feature = "inventory")]
         app.add_plugins(inventory::InventoryPlugin);
 
+        #[cfg(feature = "catalog")]
+        app.add_plugins(catalog::CatalogPlugin);
+
         #[cfg(feature = "marketplace")]
         app.add_plugins(marketplace::MarketplacePlugin);
 
+        #[cfg(feature = "notifications")]
+        app.add_plugins(notifications::NotificationsPlugin);
+
+        #[cfg(feature = "achievements")]
+        app.add_plugins(achievements::AchievementsPlugin);
+
+        #[cfg(feature = "quests")]
+        app.add_plugins(quests::QuestsPlugin);
+
+        #[cfg(feature = "referrals")]
+        app.add_plugins(referrals::ReferralsPlugin);
+
+        #[cfg(feature = "news")]
+        app.add_plugins(news::NewsPlugin);
+
+        #[cfg(feature = "mailbox")]
+        app.add_plugins(mailbox::MailboxPlugin);
+
+        #[cfg(feature = "ads")]
+        app.add_plugins(ads::AdsPlugin);
+
+        #[cfg(feature = "localization")]
+        app.add_plugins(localization::LocalizationPlugin);
+
+        #[cfg(feature = "integrity")]
+        app.add_plugins(integrity::IntegrityPlugin);
+
+        #[cfg(feature = "support")]
+        app.add_plugins(support::SupportPlugin);
+
+        #[cfg(feature = "moderation")]
+        app.add_plugins(moderation::ModerationPlugin);
+
+        #[cfg(feature = "profiles")]
+        app.add_plugins(profiles::ProfilesPlugin);
+
+        #[cfg(feature = "tournaments")]
+        app.add_plugins(tournaments::TournamentsPlugin);
+
+        #[cfg(feature = "functions")]
+        app.add_plugins(functions::FunctionsPlugin);
+
+        #[cfg(feature = "presence")]
+        app.add_plugins(presence::PresencePlugin);
+
+        #[cfg(feature = "remote_config")]
+        app.add_plugins(remote_config::RemoteConfigPlugin);
+
         // Note: Crypto wallet plugins (Ethereum, Solana) must be added manually
         // with their respective blockchain settings. They are not auto-added here.
+        // The same goes for DeepLinksPlugin, which takes the game's URL schemes.
     }
 }
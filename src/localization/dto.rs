@@ -0,0 +1,86 @@
+/// Data Transfer Objects for Localization
+use bevy::prelude::Message;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Translated strings for one locale
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StringTable {
+    pub locale: String,
+    pub version: String,
+    #[serde(default)]
+    pub strings: HashMap<String, String>,
+}
+
+impl StringTable {
+    /// Look up `key` and fill `{name}` placeholders from `args`
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> Option<String> {
+        let template = self.strings.get(key)?;
+        let mut text = template.clone();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        Some(text)
+    }
+}
+
+/// Request for the current table version of a locale
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StringTableRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    pub locale: String,
+}
+
+/// Where to download the table for a locale
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StringTableInfo {
+    /// Locale actually served (the title default when the requested one isn't translated)
+    pub locale: String,
+    pub version: String,
+    /// CDN URL of the `StringTable` JSON
+    #[serde(rename = "URL")]
+    pub url: String,
+}
+
+/// The active string table changed (locale switch or a new remote version)
+/// Re-render text when this arrives
+#[derive(Message, Debug, Clone)]
+pub struct LocalizationChanged {
+    pub locale: String,
+    pub version: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_placeholders() {
+        let table = StringTable {
+            locale: "en".to_string(),
+            version: "3".to_string(),
+            strings: HashMap::from([(
+                "shop.buy".to_string(),
+                "Buy {item} for {price} gems".to_string(),
+            )]),
+        };
+
+        assert_eq!(
+            table
+                .format("shop.buy", &[("item", "Sword"), ("price", "100")])
+                .as_deref(),
+            Some("Buy Sword for 100 gems")
+        );
+        assert_eq!(
+            table.format("shop.buy", &[]).as_deref(),
+            Some("Buy {item} for {price} gems")
+        );
+        assert!(table.format("missing", &[]).is_none());
+    }
+}
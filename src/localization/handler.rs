@@ -0,0 +1,149 @@
+/// Localization handler: downloads, caches and looks up translated strings
+use super::dto::*;
+use crate::{storage::Storage, IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::sync::{Arc, Mutex};
+
+#[derive(Resource, Clone)]
+pub struct LocalizationHandler {
+    client: IdosClient,
+    storage: Storage,
+    locale: Arc<Mutex<Option<String>>>,
+    table: Arc<Mutex<StringTable>>,
+    /// Table changes waiting for LocalizationPlugin
+    changed: Arc<Mutex<Vec<LocalizationChanged>>>,
+}
+
+impl LocalizationHandler {
+    pub fn new(client: IdosClient) -> Self {
+        let storage = Storage::cache(client.config(), "localization_");
+        Self {
+            client,
+            storage,
+            locale: Arc::new(Mutex::new(None)),
+            table: Arc::new(Mutex::new(StringTable::default())),
+            changed: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Translate `key`, filling `{name}` placeholders from `args`
+    /// Returns the key itself when there is no translation
+    pub fn t(&self, key: &str, args: &[(&str, &str)]) -> String {
+        self.table
+            .lock()
+            .ok()
+            .and_then(|table| table.format(key, args))
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Requested locale, `None` until `set_locale` is called
+    pub fn locale(&self) -> Option<String> {
+        self.locale.lock().ok().and_then(|locale| locale.clone())
+    }
+
+    /// Version of the active table
+    pub fn version(&self) -> String {
+        self.table
+            .lock()
+            .map(|table| table.version.clone())
+            .unwrap_or_default()
+    }
+
+    /// Switch to `locale` (e.g. "en", "pt-BR")
+    /// The cached table is used right away; the remote one replaces it when newer
+    pub async fn set_locale(&self, locale: &str) -> IdosResult<()> {
+        if let Ok(mut current) = self.locale.lock() {
+            *current = Some(locale.to_string());
+        }
+        if let Some(cached) = self.load_cached(locale) {
+            self.activate(cached);
+        }
+        self.refresh().await.map(|_| ())
+    }
+
+    /// Download the table for the current locale if the remote version changed
+    /// Returns true if a new table was activated
+    pub async fn refresh(&self) -> IdosResult<bool> {
+        let locale = self
+            .locale()
+            .ok_or_else(|| IdosError::Config("No locale set".to_string()))?;
+
+        let request = StringTableRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: "GetStringTable".to_string(),
+            locale: locale.clone(),
+        };
+        let endpoint = "localization/GetStringTable";
+        let info: StringTableInfo = self.client.post(endpoint, &request).await?;
+
+        let current = self
+            .table
+            .lock()
+            .map(|table| (table.locale.clone(), table.version.clone()))
+            .unwrap_or_default();
+        if current == (info.locale.clone(), info.version.clone()) {
+            return Ok(false);
+        }
+
+        let table = match self.load_cached(&info.locale) {
+            Some(cached) if cached.version == info.version => cached,
+            _ => {
+                let response = reqwest::Client::new().get(&info.url).send().await?;
+                if !response.status().is_success() {
+                    return Err(IdosError::from_response(
+                        response.status().as_u16(),
+                        &response.text().await.unwrap_or_default(),
+                    ));
+                }
+                let mut table: StringTable = response.json().await?;
+                table.locale = info.locale.clone();
+                table.version = info.version.clone();
+                self.save_cached(&info.locale, &table);
+                table
+            }
+        };
+
+        // The locale may have been switched while downloading
+        if self.locale().as_deref() != Some(locale.as_str()) {
+            return Ok(false);
+        }
+        self.activate(table);
+        Ok(true)
+    }
+
+    fn activate(&self, table: StringTable) {
+        let changed = LocalizationChanged {
+            locale: table.locale.clone(),
+            version: table.version.clone(),
+        };
+        if let Ok(mut current) = self.table.lock() {
+            *current = table;
+        }
+        if let Ok(mut queue) = self.changed.lock() {
+            queue.push(changed);
+        }
+    }
+
+    fn load_cached(&self, locale: &str) -> Option<StringTable> {
+        self.storage
+            .get(&format!("table_{}", locale))
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+    }
+
+    fn save_cached(&self, locale: &str, table: &StringTable) {
+        if let Ok(json) = serde_json::to_string(table) {
+            self.storage.set(&format!("table_{}", locale), &json).ok();
+        }
+    }
+
+    /// Take the table changes since the last call
+    pub fn drain_changed(&self) -> Vec<LocalizationChanged> {
+        self.changed
+            .lock()
+            .map(|mut changed| std::mem::take(&mut *changed))
+            .unwrap_or_default()
+    }
+}
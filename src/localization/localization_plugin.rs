@@ -0,0 +1,59 @@
+/// Localization Bevy plugin
+use super::dto::LocalizationChanged;
+use super::handler::LocalizationHandler;
+use crate::IdosClient;
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+/// How often the remote table version is checked for hot reload
+const VERSION_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize localization handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = LocalizationHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_message::<LocalizationChanged>().add_systems(
+            Update,
+            (
+                check_table_version.run_if(on_timer(VERSION_CHECK_INTERVAL)),
+                forward_localization_changes,
+            ),
+        );
+    }
+}
+
+/// Pick up new remote table versions without a restart
+fn check_table_version(handler: Option<Res<LocalizationHandler>>) {
+    let Some(handler) = handler else {
+        return;
+    };
+    if handler.locale().is_none() {
+        return;
+    }
+
+    let h = handler.clone();
+    crate::tasks::spawn(async move {
+        h.refresh().await.ok();
+    });
+}
+
+/// Forward table changes as messages
+fn forward_localization_changes(
+    handler: Option<Res<LocalizationHandler>>,
+    mut changed: MessageWriter<LocalizationChanged>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for change in handler.drain_changed() {
+        changed.write(change);
+    }
+}
@@ -0,0 +1,8 @@
+/// Localization module: remote string tables with caching and hot reload
+pub mod dto;
+pub mod handler;
+pub mod localization_plugin;
+
+pub use dto::*;
+pub use handler::LocalizationHandler;
+pub use localization_plugin::LocalizationPlugin;
@@ -0,0 +1,152 @@
+/// Data Transfer Objects for Mailbox
+use bevy::prelude::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Item or virtual currency attached to a mail
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "Type")]
+pub enum MailAttachment {
+    #[serde(rename_all = "PascalCase")]
+    Item {
+        #[serde(rename = "ItemID")]
+        item_id: String,
+        amount: i32,
+    },
+    #[serde(rename_all = "PascalCase")]
+    Currency {
+        #[serde(rename = "CurrencyID")]
+        currency_id: String,
+        amount: i32,
+    },
+}
+
+/// Mail sent to the player (compensation, event rewards, announcements)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MailMessage {
+    #[serde(rename = "MailID")]
+    pub mail_id: String,
+    #[serde(default)]
+    pub sender: Option<String>,
+    pub subject: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub attachments: Vec<MailAttachment>,
+    /// Unix timestamp
+    pub sent_at: i64,
+    /// Unix timestamp after which the mail and its attachments are gone
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    #[serde(default)]
+    pub read: bool,
+    #[serde(default)]
+    pub claimed: bool,
+}
+
+impl MailMessage {
+    pub fn has_unclaimed_attachments(&self) -> bool {
+        !self.claimed && !self.attachments.is_empty()
+    }
+
+    pub fn is_expired_at(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|end| now >= end)
+    }
+
+    /// Parse mail pushed over a realtime channel
+    /// Accepts the mail itself or an envelope with a `Mail` field
+    pub fn from_realtime(payload: &Value) -> Option<Self> {
+        let mail = payload.get("Mail").unwrap_or(payload);
+        serde_json::from_value(mail.clone()).ok()
+    }
+}
+
+/// The player's mailbox
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetMailResult {
+    #[serde(default)]
+    pub mail: Vec<MailMessage>,
+}
+
+/// Request to the mailbox endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MailboxRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(rename = "MailIDs", skip_serializing_if = "Vec::is_empty")]
+    pub mail_ids: Vec<String>,
+}
+
+/// Attachments granted by a claim, per mail
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ClaimMailResult {
+    #[serde(rename = "ClaimedMailIDs", default)]
+    pub claimed_mail_ids: Vec<String>,
+    #[serde(default)]
+    pub granted: Vec<MailAttachment>,
+}
+
+/// New mail arrived (realtime push or a fetch found mail not seen before)
+#[derive(Message, Debug, Clone)]
+pub struct NewMail {
+    pub mail_id: String,
+    pub subject: String,
+    pub sender: Option<String>,
+    pub has_attachments: bool,
+}
+
+/// Attachments were claimed; the backend has already added them to the inventory
+#[derive(Message, Debug, Clone)]
+pub struct MailAttachmentsClaimed {
+    pub mail_ids: Vec<String>,
+    pub granted: Vec<MailAttachment>,
+}
+
+/// Mailbox event queued by `MailboxHandler` until MailboxPlugin writes it as a message
+#[derive(Debug, Clone)]
+pub enum MailEvent {
+    New(NewMail),
+    Claimed(MailAttachmentsClaimed),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_realtime_mail() {
+        let mail = MailMessage::from_realtime(&json!({
+            "Type": "NewMail",
+            "Mail": {
+                "MailID": "m1",
+                "Subject": "Maintenance compensation",
+                "SentAt": 1700000000,
+                "Attachments": [
+                    { "Type": "Currency", "CurrencyID": "GEM", "Amount": 50 },
+                    { "Type": "Item", "ItemID": "energy_potion", "Amount": 3 }
+                ]
+            }
+        }))
+        .unwrap();
+        assert_eq!(mail.mail_id, "m1");
+        assert!(mail.has_unclaimed_attachments());
+        assert_eq!(
+            mail.attachments[0],
+            MailAttachment::Currency {
+                currency_id: "GEM".to_string(),
+                amount: 50
+            }
+        );
+        assert!(MailMessage::from_realtime(&json!({ "Type": "Ping" })).is_none());
+    }
+}
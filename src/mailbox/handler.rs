@@ -0,0 +1,232 @@
+/// Mailbox handler: list, read, claim and delete server-sent mail
+use super::dto::*;
+use crate::{IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+#[derive(Resource, Clone)]
+pub struct MailboxHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    /// Mail from the last fetch plus anything pushed since, newest first
+    mail: Arc<Mutex<Vec<MailMessage>>>,
+    /// Mail IDs already announced; `None` until the first fetch after login
+    seen: Arc<Mutex<Option<HashSet<String>>>>,
+    /// Events waiting for MailboxPlugin
+    events: Arc<Mutex<Vec<MailEvent>>>,
+}
+
+impl MailboxHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            mail: Arc::new(Mutex::new(Vec::new())),
+            seen: Arc::new(Mutex::new(None)),
+            events: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        if let Ok(mut mail) = self.mail.lock() {
+            mail.clear();
+        }
+        if let Ok(mut seen) = self.seen.lock() {
+            *seen = None;
+        }
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    async fn post<R: serde::de::DeserializeOwned>(
+        &self,
+        function_name: &str,
+        mail_ids: Vec<String>,
+    ) -> IdosResult<R> {
+        let request = MailboxRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            mail_ids,
+        };
+
+        let endpoint = format!("mailbox/{}", function_name);
+        self.client.post(&endpoint, &request).await
+    }
+
+    /// Fetch the player's mailbox
+    /// Mail not seen before emits `NewMail`, except on the first fetch after login
+    pub async fn fetch_mail(&self) -> IdosResult<Vec<MailMessage>> {
+        let mut result: GetMailResult = self.post("GetMail", Vec::new()).await?;
+        result.mail.sort_by_key(|m| std::cmp::Reverse(m.sent_at));
+
+        if let Ok(mut seen) = self.seen.lock() {
+            match seen.as_mut() {
+                Some(seen) => {
+                    for mail in &result.mail {
+                        if seen.insert(mail.mail_id.clone()) && !mail.read {
+                            self.push_event(MailEvent::New(Self::new_mail(mail)));
+                        }
+                    }
+                }
+                None => {
+                    *seen = Some(result.mail.iter().map(|m| m.mail_id.clone()).collect());
+                }
+            }
+        }
+        if let Ok(mut mail) = self.mail.lock() {
+            *mail = result.mail.clone();
+        }
+
+        Ok(result.mail)
+    }
+
+    /// Mail pushed by a realtime channel (websocket, push notification data)
+    /// Call from the game's connection with the message payload; returns false if it isn't mail
+    pub fn handle_realtime_message(&self, payload: &serde_json::Value) -> bool {
+        let Some(mail) = MailMessage::from_realtime(payload) else {
+            return false;
+        };
+
+        let is_new = self
+            .seen
+            .lock()
+            .map(|mut seen| {
+                seen.get_or_insert_with(HashSet::new)
+                    .insert(mail.mail_id.clone())
+            })
+            .unwrap_or(true);
+        if is_new {
+            self.push_event(MailEvent::New(Self::new_mail(&mail)));
+            if let Ok(mut cached) = self.mail.lock() {
+                cached.insert(0, mail);
+            }
+        }
+        true
+    }
+
+    fn new_mail(mail: &MailMessage) -> NewMail {
+        NewMail {
+            mail_id: mail.mail_id.clone(),
+            subject: mail.subject.clone(),
+            sender: mail.sender.clone(),
+            has_attachments: mail.has_unclaimed_attachments(),
+        }
+    }
+
+    /// Cached mail that hasn't expired at `now` (unix seconds)
+    pub fn mail(&self, now: i64) -> Vec<MailMessage> {
+        self.mail
+            .lock()
+            .map(|mail| {
+                mail.iter()
+                    .filter(|m| !m.is_expired_at(now))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn unread_count(&self, now: i64) -> usize {
+        self.mail(now).iter().filter(|m| !m.read).count()
+    }
+
+    pub async fn mark_read(&self, mail_id: &str) -> IdosResult<String> {
+        let result = self.post("MarkMailRead", vec![mail_id.to_string()]).await?;
+        self.update(&[mail_id.to_string()], |mail| mail.read = true);
+        Ok(result)
+    }
+
+    /// Claim the attachments of one mail into the inventory
+    pub async fn claim_attachments(&self, mail_id: &str) -> IdosResult<ClaimMailResult> {
+        self.claim(vec![mail_id.to_string()]).await
+    }
+
+    /// Claim every unexpired mail with unclaimed attachments
+    pub async fn claim_all(&self, now: i64) -> IdosResult<ClaimMailResult> {
+        let mail_ids: Vec<String> = self
+            .mail(now)
+            .into_iter()
+            .filter(|m| m.has_unclaimed_attachments())
+            .map(|m| m.mail_id)
+            .collect();
+        if mail_ids.is_empty() {
+            return Ok(ClaimMailResult::default());
+        }
+        self.claim(mail_ids).await
+    }
+
+    async fn claim(&self, mail_ids: Vec<String>) -> IdosResult<ClaimMailResult> {
+        let result: ClaimMailResult = self.post("ClaimMailAttachments", mail_ids).await?;
+
+        self.update(&result.claimed_mail_ids, |mail| {
+            mail.claimed = true;
+            mail.read = true;
+        });
+        if !result.claimed_mail_ids.is_empty() {
+            self.push_event(MailEvent::Claimed(MailAttachmentsClaimed {
+                mail_ids: result.claimed_mail_ids.clone(),
+                granted: result.granted.clone(),
+            }));
+        }
+
+        Ok(result)
+    }
+
+    /// Delete mail; unclaimed attachments are lost
+    pub async fn delete_mail(&self, mail_id: &str) -> IdosResult<String> {
+        let result = self.post("DeleteMail", vec![mail_id.to_string()]).await?;
+        if let Ok(mut mail) = self.mail.lock() {
+            mail.retain(|m| m.mail_id != mail_id);
+        }
+        Ok(result)
+    }
+
+    fn update(&self, mail_ids: &[String], apply: impl Fn(&mut MailMessage)) {
+        if let Ok(mut mail) = self.mail.lock() {
+            mail.iter_mut()
+                .filter(|m| mail_ids.contains(&m.mail_id))
+                .for_each(apply);
+        }
+    }
+
+    fn push_event(&self, event: MailEvent) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
+    }
+
+    /// Take the events queued since the last call
+    pub fn drain_events(&self) -> Vec<MailEvent> {
+        self.events
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default()
+    }
+}
+
+crate::session::session_aware!(MailboxHandler);
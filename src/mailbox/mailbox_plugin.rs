@@ -0,0 +1,84 @@
+/// Mailbox Bevy plugin
+use super::dto::{MailAttachmentsClaimed, MailEvent, NewMail};
+use super::handler::MailboxHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+
+pub struct MailboxPlugin;
+
+impl Plugin for MailboxPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize mailbox handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = MailboxHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<MailboxHandler>.in_set(SessionSync),
+        );
+
+        app.add_message::<NewMail>()
+            .add_message::<MailAttachmentsClaimed>()
+            .add_systems(Update, forward_mail_events);
+
+        #[cfg(feature = "inventory")]
+        app.add_systems(
+            Update,
+            credit_claimed_attachments.after(forward_mail_events),
+        );
+    }
+}
+
+/// Forward mailbox events as messages
+fn forward_mail_events(
+    handler: Option<Res<MailboxHandler>>,
+    mut new_mail: MessageWriter<NewMail>,
+    mut claimed: MessageWriter<MailAttachmentsClaimed>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for event in handler.drain_events() {
+        match event {
+            MailEvent::New(event) => {
+                new_mail.write(event);
+            }
+            MailEvent::Claimed(event) => {
+                claimed.write(event);
+            }
+        }
+    }
+}
+
+/// Add claimed attachments to the cached inventory so it matches the server without a refetch
+#[cfg(feature = "inventory")]
+fn credit_claimed_attachments(
+    inventory: Option<Res<crate::inventory::InventoryHandler>>,
+    mut claimed: MessageReader<MailAttachmentsClaimed>,
+) {
+    use super::dto::MailAttachment;
+
+    let Some(inventory) = inventory else {
+        return;
+    };
+
+    for event in claimed.read() {
+        for attachment in &event.granted {
+            match attachment {
+                MailAttachment::Item { item_id, amount } => {
+                    inventory.credit_item(item_id, *amount);
+                }
+                MailAttachment::Currency {
+                    currency_id,
+                    amount,
+                } => {
+                    inventory.credit_currency(currency_id, *amount);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+/// Mailbox module: server-sent mail with claimable item and currency attachments
+pub mod dto;
+pub mod handler;
+pub mod mailbox_plugin;
+
+pub use dto::*;
+pub use handler::MailboxHandler;
+pub use mailbox_plugin::MailboxPlugin;
@@ -253,3 +253,5 @@ impl MarketplaceHandler {
         .await
     }
 }
+
+crate::session::session_aware!(MarketplaceHandler);
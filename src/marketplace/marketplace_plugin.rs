@@ -1,4 +1,5 @@
 use super::handler::MarketplaceHandler;
+use crate::session::{sync_session, SessionSync};
 use crate::IdosClient;
 /// Marketplace Bevy plugin
 use bevy::prelude::*;
@@ -12,5 +13,10 @@ impl Plugin for MarketplacePlugin {
             let handler = MarketplaceHandler::new(client.clone());
             app.insert_resource(handler);
         }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<MarketplaceHandler>.in_set(SessionSync),
+        );
     }
 }
@@ -0,0 +1,51 @@
+/// Data Transfer Objects for Moderation
+use serde::{Deserialize, Serialize};
+
+/// Where the text will be shown; names are checked more strictly than chat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModerationContext {
+    DisplayName,
+    GuildName,
+    ChatMessage,
+}
+
+/// Request to check text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CheckTextRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<ModerationContext>,
+}
+
+/// Verdict on a piece of text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CheckTextResult {
+    pub allowed: bool,
+    /// Text with offending words masked (chat); `None` when nothing was masked
+    #[serde(default)]
+    pub filtered_text: Option<String>,
+    #[serde(default)]
+    pub reasons: Vec<String>,
+    /// Decided by the local wordlist because the backend couldn't be reached
+    #[serde(skip)]
+    pub offline: bool,
+}
+
+/// Blocked words for the local filter
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetWordlistResult {
+    pub version: String,
+    #[serde(default)]
+    pub words: Vec<String>,
+}
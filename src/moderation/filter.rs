@@ -0,0 +1,119 @@
+/// Local wordlist filter for offline validation
+/// Catches common evasions (case, leetspeak, separators) but is no substitute for the backend check
+use std::collections::HashSet;
+
+/// Entries at least this long are also matched inside words and across separators
+const MIN_SUBSTRING_LEN: usize = 4;
+
+#[derive(Debug, Clone, Default)]
+pub struct WordFilter {
+    words: HashSet<String>,
+}
+
+impl WordFilter {
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            words: words
+                .into_iter()
+                .map(|w| normalize(w.as_ref()))
+                .filter(|w| !w.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Blocked words found in `text`
+    pub fn matches(&self, text: &str) -> Vec<String> {
+        let mut found: Vec<String> = tokens(text)
+            .filter_map(|(_, token)| {
+                let normalized = normalize(token);
+                self.words.contains(&normalized).then_some(normalized)
+            })
+            .collect();
+
+        let compact = normalize(text);
+        found.extend(
+            self.words
+                .iter()
+                .filter(|w| w.len() >= MIN_SUBSTRING_LEN && compact.contains(w.as_str()))
+                .cloned(),
+        );
+
+        found.sort();
+        found.dedup();
+        found
+    }
+
+    pub fn is_clean(&self, text: &str) -> bool {
+        self.matches(text).is_empty()
+    }
+
+    /// Replace blocked words with `*`
+    pub fn censor(&self, text: &str) -> String {
+        let mut censored = text.to_string();
+        for (start, token) in tokens(text) {
+            let normalized = normalize(token);
+            let blocked = self.words.contains(&normalized)
+                || self
+                    .words
+                    .iter()
+                    .any(|w| w.len() >= MIN_SUBSTRING_LEN && normalized.contains(w.as_str()));
+            if blocked {
+                censored.replace_range(
+                    start..start + token.len(),
+                    &"*".repeat(token.chars().count()),
+                );
+            }
+        }
+        censored
+    }
+}
+
+/// Whitespace-separated tokens without surrounding punctuation, with their byte offsets
+fn tokens(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_whitespace()
+        .map(|token| token.trim_matches(|c| ",.?;:\"'()".contains(c)))
+        .filter(|token| !token.is_empty())
+        .map(move |token| (token.as_ptr() as usize - text.as_ptr() as usize, token))
+}
+
+/// Lowercase, undo leetspeak and drop everything that isn't a letter
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '0' => 'o',
+            '1' | '!' | '|' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            c => c,
+        })
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_filter() {
+        let filter = WordFilter::new(["noob", "scam"]);
+
+        assert!(filter.is_clean("good game everyone"));
+        assert_eq!(filter.matches("total N00B"), vec!["noob"]);
+        assert_eq!(filter.matches("free gems s.c.a.m"), vec!["scam"]);
+        assert_eq!(filter.matches("xXnoobslayerXx"), vec!["noob"]);
+        assert_eq!(filter.censor("you n00b, gg"), "you ****, gg");
+        assert!(WordFilter::default().is_clean("anything"));
+    }
+}
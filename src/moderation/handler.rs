@@ -0,0 +1,171 @@
+/// Moderation handler: backend text checks with the local wordlist as offline fallback
+use super::dto::*;
+use super::filter::WordFilter;
+use crate::{storage::Storage, IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::sync::{Arc, RwLock};
+
+#[derive(Resource, Clone)]
+pub struct ModerationHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    storage: Storage,
+    filter: Arc<RwLock<WordFilter>>,
+}
+
+impl ModerationHandler {
+    pub fn new(client: IdosClient) -> Self {
+        let storage = Storage::cache(client.config(), "moderation_");
+        // Wordlist from the last sync, so offline checks work after a restart
+        let filter = storage
+            .get("wordlist")
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str::<GetWordlistResult>(&json).ok())
+            .map(|list| WordFilter::new(list.words))
+            .unwrap_or_default();
+
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            storage,
+            filter: Arc::new(RwLock::new(filter)),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    /// Replace the local wordlist (e.g. one bundled with the game)
+    pub fn set_wordlist<I, S>(&self, words: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        if let Ok(mut filter) = self.filter.write() {
+            *filter = WordFilter::new(words);
+        }
+    }
+
+    /// Download the title's wordlist and keep it for offline checks
+    pub async fn sync_wordlist(&self) -> IdosResult<String> {
+        let request = self.request("GetWordlist", None, None)?;
+        let endpoint = "moderation/GetWordlist";
+        let result: GetWordlistResult = self.client.post(endpoint, &request).await?;
+
+        if let Ok(json) = serde_json::to_string(&result) {
+            self.storage.set("wordlist", &json).ok();
+        }
+        self.set_wordlist(&result.words);
+        Ok(result.version)
+    }
+
+    /// Check text with the backend; uses the local wordlist when offline
+    pub async fn check_text(
+        &self,
+        text: &str,
+        context: ModerationContext,
+    ) -> IdosResult<CheckTextResult> {
+        let request = self.request("CheckText", Some(text.to_string()), Some(context))?;
+        let endpoint = "moderation/CheckText";
+
+        match self.client.post(endpoint, &request).await {
+            Ok(result) => Ok(result),
+            Err(IdosError::Http(_) | IdosError::Network(_) | IdosError::NetworkError(_)) => {
+                Ok(self.check_locally(text, context))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Check a display name before setting it
+    pub async fn check_display_name(&self, name: &str) -> IdosResult<CheckTextResult> {
+        self.check_text(name, ModerationContext::DisplayName).await
+    }
+
+    /// Check a guild/clan name before creating or renaming
+    pub async fn check_guild_name(&self, name: &str) -> IdosResult<CheckTextResult> {
+        self.check_text(name, ModerationContext::GuildName).await
+    }
+
+    /// Check a chat message; `filtered_text` holds the masked version
+    pub async fn check_chat_message(&self, message: &str) -> IdosResult<CheckTextResult> {
+        self.check_text(message, ModerationContext::ChatMessage)
+            .await
+    }
+
+    /// Verdict from the local wordlist only
+    /// Names are rejected outright; chat is allowed with offending words masked
+    pub fn check_locally(&self, text: &str, context: ModerationContext) -> CheckTextResult {
+        let Ok(filter) = self.filter.read() else {
+            return CheckTextResult {
+                allowed: true,
+                filtered_text: None,
+                reasons: Vec::new(),
+                offline: true,
+            };
+        };
+
+        let matches = filter.matches(text);
+        let reasons = matches
+            .iter()
+            .map(|word| format!("Blocked word: {}", word))
+            .collect();
+        match context {
+            ModerationContext::ChatMessage => CheckTextResult {
+                allowed: true,
+                filtered_text: (!matches.is_empty()).then(|| filter.censor(text)),
+                reasons,
+                offline: true,
+            },
+            ModerationContext::DisplayName | ModerationContext::GuildName => CheckTextResult {
+                allowed: matches.is_empty(),
+                filtered_text: None,
+                reasons,
+                offline: true,
+            },
+        }
+    }
+
+    fn request(
+        &self,
+        function_name: &str,
+        text: Option<String>,
+        context: Option<ModerationContext>,
+    ) -> IdosResult<CheckTextRequest> {
+        Ok(CheckTextRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            text,
+            context,
+        })
+    }
+}
+
+crate::session::session_aware!(ModerationHandler);
@@ -0,0 +1,10 @@
+/// Moderation module: profanity and name checks, with a local wordlist fallback
+pub mod dto;
+pub mod filter;
+pub mod handler;
+pub mod moderation_plugin;
+
+pub use dto::*;
+pub use filter::WordFilter;
+pub use handler::ModerationHandler;
+pub use moderation_plugin::ModerationPlugin;
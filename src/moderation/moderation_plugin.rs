@@ -0,0 +1,22 @@
+/// Moderation Bevy plugin
+use super::handler::ModerationHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+
+pub struct ModerationPlugin;
+
+impl Plugin for ModerationPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize moderation handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = ModerationHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<ModerationHandler>.in_set(SessionSync),
+        );
+    }
+}
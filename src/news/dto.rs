@@ -0,0 +1,69 @@
+/// Data Transfer Objects for News
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+/// Announcement from the title's news feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NewsArticle {
+    #[serde(rename = "ArticleID")]
+    pub article_id: String,
+    pub title: String,
+    pub body: String,
+    #[serde(rename = "ImageURL", default)]
+    pub image_url: Option<String>,
+    /// Button text for the call to action
+    #[serde(default)]
+    pub cta_label: Option<String>,
+    /// Deep link opened by the call to action
+    #[serde(default)]
+    pub cta_deep_link: Option<String>,
+    /// Unix timestamp the article becomes visible (immediately if absent)
+    #[serde(default)]
+    pub publish_at: Option<i64>,
+    /// Unix timestamp the article is hidden (never if absent)
+    #[serde(default)]
+    pub expire_at: Option<i64>,
+    /// Higher is shown first
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl NewsArticle {
+    /// Whether the article is inside its scheduling window at `now` (unix seconds)
+    pub fn is_live_at(&self, now: i64) -> bool {
+        self.publish_at.is_none_or(|start| now >= start)
+            && self.expire_at.is_none_or(|end| now < end)
+    }
+}
+
+/// News feed in the requested language
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetNewsResult {
+    #[serde(default)]
+    pub articles: Vec<NewsArticle>,
+    /// Language the backend actually served (falls back to the title default)
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// Request to the news endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NewsRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    /// BCP 47 language tag, e.g. "en" or "pt-BR"
+    pub language: String,
+}
+
+/// Number of live articles the player hasn't read, kept up to date by NewsPlugin
+/// Use it for badge UI
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NewsUnreadCount(pub usize);
@@ -0,0 +1,231 @@
+/// News handler: announcement feed and per-player read state
+/// Read state is stored locally so badges survive restarts
+use super::dto::*;
+use crate::{storage::Storage, IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// Read markers older than this are forgotten (90 days)
+const READ_MARKER_TTL_SECS: i64 = 90 * 24 * 60 * 60;
+/// Most read markers kept per player; the oldest are dropped first
+const MAX_READ_MARKERS: usize = 500;
+
+/// Read markers as saved in Storage
+/// Older versions saved a plain list of article IDs
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredReadMarkers {
+    /// Article ID -> unix timestamp (seconds) it was read
+    ReadAt(BTreeMap<String, i64>),
+    Ids(Vec<String>),
+}
+
+#[derive(Resource, Clone)]
+pub struct NewsHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    storage: Storage,
+    /// Articles from the last fetch, highest priority first
+    articles: Arc<Mutex<Vec<NewsArticle>>>,
+    /// Article ID -> unix timestamp (seconds) it was read
+    read: Arc<Mutex<BTreeMap<String, i64>>>,
+}
+
+impl NewsHandler {
+    pub fn new(client: IdosClient) -> Self {
+        let storage = Storage::cache(client.config(), "news_");
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            storage,
+            articles: Arc::new(Mutex::new(Vec::new())),
+            read: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    /// Loads the player's read state
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        let now = chrono::Utc::now().timestamp();
+        let read = match self
+            .storage
+            .get(&Self::read_key(&user_id))
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+        {
+            Some(StoredReadMarkers::ReadAt(read)) => read,
+            Some(StoredReadMarkers::Ids(ids)) => ids.into_iter().map(|id| (id, now)).collect(),
+            None => BTreeMap::new(),
+        };
+        if let Ok(mut current) = self.read.lock() {
+            *current = read;
+        }
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        if let Ok(mut articles) = self.articles.lock() {
+            articles.clear();
+        }
+        if let Ok(mut read) = self.read.lock() {
+            read.clear();
+        }
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    fn read_key(user_id: &str) -> String {
+        format!("read_{}", user_id)
+    }
+
+    /// Fetch the news feed in `language` (e.g. "en", "pt-BR")
+    pub async fn fetch_news(&self, language: &str) -> IdosResult<GetNewsResult> {
+        let request = NewsRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: "GetNews".to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            language: language.to_string(),
+        };
+
+        let endpoint = "news/GetNews";
+        let mut result: GetNewsResult = self.client.post(endpoint, &request).await?;
+        result
+            .articles
+            .sort_by_key(|a| std::cmp::Reverse(a.priority));
+
+        if let Ok(mut articles) = self.articles.lock() {
+            *articles = result.articles.clone();
+        }
+
+        // Read markers of articles missing from this feed are kept: the player may switch
+        // language, and old markers are pruned by age when saving
+        Ok(result)
+    }
+
+    /// Articles from the last fetch that are live at `now` (unix seconds)
+    pub fn articles(&self, now: i64) -> Vec<NewsArticle> {
+        self.articles
+            .lock()
+            .map(|articles| {
+                articles
+                    .iter()
+                    .filter(|a| a.is_live_at(now))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn is_read(&self, article_id: &str) -> bool {
+        self.read
+            .lock()
+            .map(|read| read.contains_key(article_id))
+            .unwrap_or(false)
+    }
+
+    /// Live articles the player hasn't read
+    pub fn unread_count(&self, now: i64) -> usize {
+        let Ok(read) = self.read.lock() else {
+            return 0;
+        };
+        self.articles(now)
+            .iter()
+            .filter(|a| !read.contains_key(&a.article_id))
+            .count()
+    }
+
+    pub fn mark_read(&self, article_id: &str) {
+        let inserted = self
+            .read
+            .lock()
+            .map(|mut read| {
+                read.insert(article_id.to_string(), chrono::Utc::now().timestamp())
+                    .is_none()
+            })
+            .unwrap_or(false);
+        if inserted {
+            self.save_read();
+        }
+    }
+
+    /// Mark every live article as read
+    pub fn mark_all_read(&self, now: i64) {
+        let articles = self.articles(now);
+        if let Ok(mut read) = self.read.lock() {
+            for article in articles {
+                read.entry(article.article_id).or_insert(now);
+            }
+        }
+        self.save_read();
+    }
+
+    fn save_read(&self) {
+        let Some(user_id) = &self.user_id else {
+            return;
+        };
+        let Ok(mut read) = self.read.lock() else {
+            return;
+        };
+        prune_read_markers(&mut read, chrono::Utc::now().timestamp());
+        if let Ok(json) = serde_json::to_string(&StoredReadMarkers::ReadAt(read.clone())) {
+            self.storage.set(&Self::read_key(user_id), &json).ok();
+        }
+    }
+}
+
+crate::session::session_aware!(NewsHandler);
+
+/// Drop markers older than the TTL, then the oldest beyond `MAX_READ_MARKERS`
+fn prune_read_markers(read: &mut BTreeMap<String, i64>, now: i64) {
+    read.retain(|_, read_at| now - *read_at < READ_MARKER_TTL_SECS);
+    if read.len() > MAX_READ_MARKERS {
+        let mut read_at: Vec<i64> = read.values().copied().collect();
+        read_at.sort_unstable();
+        let cutoff = read_at[read.len() - MAX_READ_MARKERS];
+        read.retain(|_, at| *at >= cutoff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_markers() {
+        let legacy: StoredReadMarkers = serde_json::from_str(r#"["a","b"]"#).unwrap();
+        assert!(matches!(legacy, StoredReadMarkers::Ids(ids) if ids == ["a", "b"]));
+
+        let now = 1_700_000_000;
+        let mut read: BTreeMap<String, i64> = (0..MAX_READ_MARKERS + 10)
+            .map(|i| (format!("article_{}", i), now - i as i64))
+            .collect();
+        read.insert("expired".to_string(), now - READ_MARKER_TTL_SECS);
+        prune_read_markers(&mut read, now);
+
+        assert_eq!(read.len(), MAX_READ_MARKERS);
+        assert!(read.contains_key("article_0"));
+        assert!(!read.contains_key(&format!("article_{}", MAX_READ_MARKERS)));
+        assert!(!read.contains_key("expired"));
+    }
+}
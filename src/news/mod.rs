@@ -0,0 +1,8 @@
+/// News module: localized announcement feeds with per-player read state
+pub mod dto;
+pub mod handler;
+pub mod news_plugin;
+
+pub use dto::*;
+pub use handler::NewsHandler;
+pub use news_plugin::NewsPlugin;
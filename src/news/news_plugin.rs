@@ -0,0 +1,40 @@
+/// News Bevy plugin
+use super::dto::NewsUnreadCount;
+use super::handler::NewsHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+pub struct NewsPlugin;
+
+impl Plugin for NewsPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize news handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = NewsHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(PreUpdate, sync_session::<NewsHandler>.in_set(SessionSync));
+
+        app.init_resource::<NewsUnreadCount>().add_systems(
+            Update,
+            update_unread_count.run_if(on_timer(Duration::from_secs(1))),
+        );
+    }
+}
+
+/// Recount unread articles (new fetches, reads and scheduling windows)
+fn update_unread_count(handler: Option<Res<NewsHandler>>, mut count: ResMut<NewsUnreadCount>) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    let unread = handler.unread_count(chrono::Utc::now().timestamp());
+    // Only touch the resource when it changed so `is_changed` drives badge UI
+    if count.0 != unread {
+        count.0 = unread;
+    }
+}
@@ -0,0 +1,295 @@
+/// Data Transfer Objects for Push Notifications
+use bevy::prelude::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Push service that issued the device token
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PushPlatform {
+    /// Firebase Cloud Messaging (Android, and iOS via Firebase)
+    Fcm,
+    /// Apple Push Notification service
+    Apns,
+    /// Browser Push API; the token is the serialized `PushSubscription` JSON
+    WebPush,
+}
+
+/// Request to register or unregister a device push token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RegisterDeviceRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    pub platform: PushPlatform,
+    pub push_token: String,
+}
+
+/// Request to subscribe to or unsubscribe from a topic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TopicSubscriptionRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    pub topic: String,
+}
+
+/// Topics the player is subscribed to
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetTopicsResult {
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
+/// The player opened the game from a push notification
+/// Written by NotificationsPlugin; `deep_link` is meant for deep-link routing
+#[derive(Message, Debug, Clone, Default, PartialEq)]
+pub struct NotificationOpened {
+    pub notification_id: Option<String>,
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub deep_link: Option<String>,
+    /// Remaining custom data fields of the payload
+    pub data: HashMap<String, Value>,
+}
+
+impl NotificationOpened {
+    /// Parse an FCM `data`/APNs `userInfo`/Web Push payload
+    /// Title and body are read from `notification`/`aps.alert` when not at the top level
+    pub fn from_payload(payload: &Value) -> Self {
+        let mut data: HashMap<String, Value> = payload
+            .as_object()
+            .map(|o| o.clone().into_iter().collect())
+            .unwrap_or_default();
+
+        let mut take = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| data.remove(*key))
+                .and_then(|value| match value {
+                    Value::String(s) => Some(s),
+                    Value::Number(n) => Some(n.to_string()),
+                    _ => None,
+                })
+        };
+        let notification_id = take(&["notification_id", "notificationId", "id"]);
+        let deep_link = take(&["deep_link", "deepLink", "link", "url"]);
+        let mut title = take(&["title"]);
+        let mut body = take(&["body"]);
+
+        // Nested display fields: FCM `notification`, APNs `aps.alert`
+        let alert = data
+            .remove("notification")
+            .or_else(|| data.remove("aps").and_then(|aps| aps.get("alert").cloned()));
+        match alert {
+            Some(Value::Object(alert)) => {
+                let field = |key: &str| alert.get(key).and_then(Value::as_str).map(str::to_string);
+                title = title.or_else(|| field("title"));
+                body = body.or_else(|| field("body"));
+            }
+            Some(Value::String(alert)) => body = body.or(Some(alert)),
+            _ => {}
+        }
+
+        Self {
+            notification_id,
+            title,
+            body,
+            deep_link,
+            data,
+        }
+    }
+}
+
+/// In-app message held by the backend until acknowledged
+/// Delivered by polling or forwarded from a push/realtime payload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InAppMessage {
+    #[serde(rename = "MessageID")]
+    pub message_id: String,
+    /// Payload type, e.g. "RewardGrant" or "Maintenance"
+    #[serde(rename = "Type", default)]
+    pub kind: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Unix timestamp
+    #[serde(default)]
+    pub sent_at: i64,
+    /// Unix timestamp after which the message is no longer shown
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    /// Type-specific fields, read through `payload`
+    #[serde(default)]
+    pub data: Value,
+}
+
+/// Typed content of an in-app message
+#[derive(Debug, Clone, PartialEq)]
+pub enum InAppPayload {
+    /// Items and currency the backend granted (already in the server inventory)
+    RewardGrant {
+        items: HashMap<String, i32>,
+        currencies: HashMap<String, i32>,
+    },
+    /// Scheduled downtime; `ends_at` is unknown for open-ended maintenance
+    Maintenance {
+        starts_at: i64,
+        ends_at: Option<i64>,
+    },
+    /// Text announcement, optionally linking somewhere in the game
+    Announcement { deep_link: Option<String> },
+    /// Message type the SDK doesn't know; see `InAppMessage::data`
+    Custom,
+}
+
+impl InAppMessage {
+    /// Parse a message pushed over a realtime channel or in a push data payload
+    /// Accepts the message itself or an envelope with a `Message` field
+    pub fn from_realtime(payload: &Value) -> Option<Self> {
+        let message = payload.get("Message").unwrap_or(payload);
+        serde_json::from_value(message.clone()).ok()
+    }
+
+    pub fn is_expired_at(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|end| now >= end)
+    }
+
+    pub fn payload(&self) -> InAppPayload {
+        let amounts = |key: &str| -> HashMap<String, i32> {
+            self.data
+                .get(key)
+                .and_then(|value| serde_json::from_value(value.clone()).ok())
+                .unwrap_or_default()
+        };
+
+        match self.kind.as_str() {
+            "RewardGrant" => InAppPayload::RewardGrant {
+                items: amounts("Items"),
+                currencies: amounts("VirtualCurrency"),
+            },
+            "Maintenance" => match self.data.get("StartsAt").and_then(Value::as_i64) {
+                Some(starts_at) => InAppPayload::Maintenance {
+                    starts_at,
+                    ends_at: self.data.get("EndsAt").and_then(Value::as_i64),
+                },
+                None => InAppPayload::Custom,
+            },
+            "Announcement" => InAppPayload::Announcement {
+                deep_link: self
+                    .data
+                    .get("DeepLink")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            },
+            _ => InAppPayload::Custom,
+        }
+    }
+}
+
+/// Request to the in-app message endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InAppMessagesRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(rename = "MessageIDs", skip_serializing_if = "Vec::is_empty")]
+    pub message_ids: Vec<String>,
+}
+
+/// Messages not acknowledged yet
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetInAppMessagesResult {
+    #[serde(default)]
+    pub messages: Vec<InAppMessage>,
+}
+
+/// A new in-app message arrived (poll or realtime push); written by NotificationsPlugin
+#[derive(Message, Debug, Clone)]
+pub struct InAppMessageReceived {
+    pub message: InAppMessage,
+    pub payload: InAppPayload,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_notification_payloads() {
+        let fcm = NotificationOpened::from_payload(&json!({
+            "notification": { "title": "Raid boss", "body": "Starts in 5 minutes" },
+            "deep_link": "idosgames://event/raid",
+            "event_id": 42
+        }));
+        assert_eq!(fcm.title.as_deref(), Some("Raid boss"));
+        assert_eq!(fcm.body.as_deref(), Some("Starts in 5 minutes"));
+        assert_eq!(fcm.deep_link.as_deref(), Some("idosgames://event/raid"));
+        assert_eq!(fcm.data.get("event_id"), Some(&json!(42)));
+
+        let apns = NotificationOpened::from_payload(&json!({
+            "aps": { "alert": "Your energy is full" },
+            "id": 7
+        }));
+        assert_eq!(apns.body.as_deref(), Some("Your energy is full"));
+        assert_eq!(apns.notification_id.as_deref(), Some("7"));
+        assert!(apns.data.is_empty());
+    }
+
+    #[test]
+    fn test_in_app_message_payloads() {
+        let reward = InAppMessage::from_realtime(&json!({
+            "Message": {
+                "MessageID": "msg1",
+                "Type": "RewardGrant",
+                "Title": "Thanks for playing",
+                "SentAt": 1700000000,
+                "Data": { "Items": { "chest": 1 }, "VirtualCurrency": { "GEMS": 50 } }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            reward.payload(),
+            InAppPayload::RewardGrant {
+                items: HashMap::from([("chest".to_string(), 1)]),
+                currencies: HashMap::from([("GEMS".to_string(), 50)]),
+            }
+        );
+
+        let maintenance = InAppMessage::from_realtime(&json!({
+            "MessageID": "msg2",
+            "Type": "Maintenance",
+            "ExpiresAt": 1700007200,
+            "Data": { "StartsAt": 1700003600 }
+        }))
+        .unwrap();
+        assert_eq!(
+            maintenance.payload(),
+            InAppPayload::Maintenance {
+                starts_at: 1700003600,
+                ends_at: None
+            }
+        );
+        assert!(maintenance.is_expired_at(1700007200));
+        assert!(InAppMessage::from_realtime(&json!({ "Type": "Ping" })).is_none());
+    }
+}
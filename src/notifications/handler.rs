@@ -0,0 +1,272 @@
+/// Notifications handler for push token registration, topic subscriptions and in-app messages
+use super::dto::*;
+use crate::{IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Default interval of the in-app message poll used while no push device is registered
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Resource, Clone)]
+pub struct NotificationsHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    /// Token registered with the backend, kept to unregister on logout
+    device: Arc<Mutex<Option<(PushPlatform, String)>>>,
+    /// Opened notifications waiting for NotificationsPlugin
+    opened: Arc<Mutex<Vec<NotificationOpened>>>,
+    /// In-app message IDs already announced this session
+    seen_messages: Arc<Mutex<HashSet<String>>>,
+    /// New in-app messages waiting for NotificationsPlugin
+    messages: Arc<Mutex<Vec<InAppMessageReceived>>>,
+    /// How often NotificationsPlugin polls for in-app messages without push (None = never)
+    poll_interval: Option<Duration>,
+}
+
+impl NotificationsHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            device: Arc::new(Mutex::new(None)),
+            opened: Arc::new(Mutex::new(Vec::new())),
+            seen_messages: Arc::new(Mutex::new(HashSet::new())),
+            messages: Arc::new(Mutex::new(Vec::new())),
+            poll_interval: Some(DEFAULT_POLL_INTERVAL),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout, after `unregister_device`)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        if let Ok(mut seen) = self.seen_messages.lock() {
+            seen.clear();
+        }
+    }
+
+    pub fn is_logged_in(&self) -> bool {
+        self.user_id.is_some()
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    /// Register the device push token issued by FCM/APNs/Web Push for this player
+    /// Call again whenever the platform rotates the token
+    pub async fn register_device(&self, platform: PushPlatform, token: &str) -> IdosResult<String> {
+        if token.is_empty() {
+            return Err(IdosError::InvalidInput(
+                "Push token cannot be empty".to_string(),
+            ));
+        }
+
+        let result = self
+            .post_device("RegisterDevice", platform, token.to_string())
+            .await?;
+        if let Ok(mut device) = self.device.lock() {
+            *device = Some((platform, token.to_string()));
+        }
+        Ok(result)
+    }
+
+    /// Stop sending pushes to this device (e.g. on logout)
+    pub async fn unregister_device(&self) -> IdosResult<String> {
+        let registered = self.device.lock().ok().and_then(|device| device.clone());
+        let (platform, token) = registered
+            .ok_or_else(|| IdosError::InvalidInput("No device registered".to_string()))?;
+
+        let result = self
+            .post_device("UnregisterDevice", platform, token)
+            .await?;
+        if let Ok(mut device) = self.device.lock() {
+            *device = None;
+        }
+        Ok(result)
+    }
+
+    /// Platform and token last registered from this client
+    pub fn registered_device(&self) -> Option<(PushPlatform, String)> {
+        self.device.lock().ok().and_then(|device| device.clone())
+    }
+
+    async fn post_device(
+        &self,
+        function_name: &str,
+        platform: PushPlatform,
+        push_token: String,
+    ) -> IdosResult<String> {
+        let request = RegisterDeviceRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            platform,
+            push_token,
+        };
+
+        let endpoint = format!("notifications/{}", function_name);
+        self.client.post(&endpoint, &request).await
+    }
+
+    /// Subscribe the player to a topic (e.g. "events", "clan_123")
+    pub async fn subscribe_topic(&self, topic: &str) -> IdosResult<String> {
+        self.post_topic("SubscribeTopic", topic).await
+    }
+
+    /// Unsubscribe the player from a topic
+    pub async fn unsubscribe_topic(&self, topic: &str) -> IdosResult<String> {
+        self.post_topic("UnsubscribeTopic", topic).await
+    }
+
+    /// Topics the player is subscribed to
+    pub async fn get_topics(&self) -> IdosResult<Vec<String>> {
+        let result: GetTopicsResult = self.post_topic("GetTopics", "").await?;
+        Ok(result.topics)
+    }
+
+    async fn post_topic<R: serde::de::DeserializeOwned>(
+        &self,
+        function_name: &str,
+        topic: &str,
+    ) -> IdosResult<R> {
+        let request = TopicSubscriptionRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            topic: topic.to_string(),
+        };
+
+        let endpoint = format!("notifications/{}", function_name);
+        self.client.post(&endpoint, &request).await
+    }
+
+    /// Report that the game was opened from a notification
+    /// Call from the platform layer with the notification's data payload
+    pub fn handle_notification_opened(&self, payload: &serde_json::Value) {
+        if let Ok(mut opened) = self.opened.lock() {
+            opened.push(NotificationOpened::from_payload(payload));
+        }
+    }
+
+    /// Fetch in-app messages the player hasn't acknowledged
+    /// Messages not seen before this session are announced as `InAppMessageReceived`
+    pub async fn fetch_messages(&self) -> IdosResult<Vec<InAppMessage>> {
+        let result: GetInAppMessagesResult = self.post_messages("GetMessages", Vec::new()).await?;
+        for message in &result.messages {
+            self.announce(message.clone());
+        }
+        Ok(result.messages)
+    }
+
+    /// Mark messages as handled so the backend stops returning them
+    pub async fn acknowledge_messages(&self, message_ids: &[String]) -> IdosResult<String> {
+        if message_ids.is_empty() {
+            return Err(IdosError::InvalidInput(
+                "No message IDs to acknowledge".to_string(),
+            ));
+        }
+        self.post_messages("AcknowledgeMessages", message_ids.to_vec())
+            .await
+    }
+
+    async fn post_messages<R: serde::de::DeserializeOwned>(
+        &self,
+        function_name: &str,
+        message_ids: Vec<String>,
+    ) -> IdosResult<R> {
+        let request = InAppMessagesRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            message_ids,
+        };
+
+        let endpoint = format!("notifications/{}", function_name);
+        self.client.post(&endpoint, &request).await
+    }
+
+    /// In-app message delivered by push or a realtime channel
+    /// Call from the platform layer with the data payload; returns false if it isn't a message
+    pub fn handle_realtime_message(&self, payload: &serde_json::Value) -> bool {
+        match InAppMessage::from_realtime(payload) {
+            Some(message) => {
+                self.announce(message);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn announce(&self, message: InAppMessage) {
+        let is_new = self
+            .seen_messages
+            .lock()
+            .map(|mut seen| seen.insert(message.message_id.clone()))
+            .unwrap_or(true);
+        if !is_new {
+            return;
+        }
+
+        if let Ok(mut messages) = self.messages.lock() {
+            let payload = message.payload();
+            messages.push(InAppMessageReceived { message, payload });
+        }
+    }
+
+    /// Take the in-app messages received since the last call
+    pub fn drain_messages(&self) -> Vec<InAppMessageReceived> {
+        self.messages
+            .lock()
+            .map(|mut messages| std::mem::take(&mut *messages))
+            .unwrap_or_default()
+    }
+
+    /// Change how often in-app messages are polled while no push device is registered
+    /// (None turns polling off, e.g. when messages arrive through your own realtime channel)
+    pub fn set_poll_interval(&mut self, interval: Option<Duration>) {
+        self.poll_interval = interval;
+    }
+
+    /// Interval NotificationsPlugin polls at right now; None while push delivers messages
+    pub fn poll_interval(&self) -> Option<Duration> {
+        if self.registered_device().is_some() {
+            return None;
+        }
+        self.poll_interval
+    }
+
+    /// Take the opened notifications collected since the last call
+    pub fn drain_opened(&self) -> Vec<NotificationOpened> {
+        self.opened
+            .lock()
+            .map(|mut opened| std::mem::take(&mut *opened))
+            .unwrap_or_default()
+    }
+}
+
+crate::session::session_aware!(NotificationsHandler);
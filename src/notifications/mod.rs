@@ -0,0 +1,8 @@
+/// Push notifications module: device registration, topics, opened notifications and in-app messages
+pub mod dto;
+pub mod handler;
+pub mod notifications_plugin;
+
+pub use dto::*;
+pub use handler::NotificationsHandler;
+pub use notifications_plugin::NotificationsPlugin;
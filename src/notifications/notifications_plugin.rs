@@ -0,0 +1,87 @@
+/// Push notifications Bevy plugin
+use super::dto::{InAppMessageReceived, NotificationOpened};
+use super::handler::NotificationsHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+use std::time::Duration;
+
+pub struct NotificationsPlugin;
+
+impl Plugin for NotificationsPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize notifications handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = NotificationsHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<NotificationsHandler>.in_set(SessionSync),
+        );
+
+        app.add_message::<NotificationOpened>()
+            .add_message::<InAppMessageReceived>()
+            .add_systems(PreUpdate, forward_opened_notifications)
+            .add_systems(Update, (poll_in_app_messages, forward_in_app_messages));
+    }
+}
+
+/// Forward notifications reported by the platform layer as messages
+fn forward_opened_notifications(
+    handler: Option<Res<NotificationsHandler>>,
+    mut opened: MessageWriter<NotificationOpened>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for notification in handler.drain_opened() {
+        opened.write(notification);
+    }
+}
+
+/// Fallback for platforms without push: fetch in-app messages every `poll_interval`
+fn poll_in_app_messages(
+    handler: Option<Res<NotificationsHandler>>,
+    time: Res<Time>,
+    mut since_poll: Local<Duration>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+    let Some(interval) = handler.poll_interval() else {
+        return;
+    };
+    if !handler.is_logged_in() {
+        return;
+    }
+
+    *since_poll += time.delta();
+    if *since_poll < interval {
+        return;
+    }
+    *since_poll = Duration::ZERO;
+
+    let h = handler.clone();
+    crate::tasks::spawn(async move {
+        if let Err(e) = h.fetch_messages().await {
+            log::debug!("In-app message poll failed: {}", e);
+        }
+    });
+}
+
+/// Forward new in-app messages as messages
+fn forward_in_app_messages(
+    handler: Option<Res<NotificationsHandler>>,
+    mut received: MessageWriter<InAppMessageReceived>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for message in handler.drain_messages() {
+        received.write(message);
+    }
+}
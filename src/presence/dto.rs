@@ -0,0 +1,139 @@
+/// Data Transfer Objects for Presence
+use bevy::prelude::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PresenceStatus {
+    Online,
+    Away,
+    InMatch,
+    #[default]
+    #[serde(other)]
+    Offline,
+}
+
+impl PresenceStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            PresenceStatus::Online => "Online",
+            PresenceStatus::Away => "Away",
+            PresenceStatus::InMatch => "In match",
+            PresenceStatus::Offline => "Offline",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlayerPresence {
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    #[serde(default)]
+    pub status: PresenceStatus,
+    /// What the player is doing, e.g. "In ranked match"
+    #[serde(default)]
+    pub activity: Option<String>,
+    /// Unix timestamp the activity started, for elapsed time
+    #[serde(default)]
+    pub activity_started_at: Option<i64>,
+    /// Unix timestamp
+    #[serde(default)]
+    pub last_seen_at: i64,
+}
+
+impl PlayerPresence {
+    /// Text for friend lists: "In ranked match, 7:32", "Away", "Offline"
+    pub fn rich_status(&self, now: i64) -> String {
+        if self.status == PresenceStatus::Offline {
+            return PresenceStatus::Offline.label().to_string();
+        }
+        let Some(activity) = &self.activity else {
+            return self.status.label().to_string();
+        };
+        match self.activity_started_at {
+            Some(started) => {
+                let elapsed = (now - started).max(0);
+                format!("{}, {}:{:02}", activity, elapsed / 60, elapsed % 60)
+            }
+            None => activity.clone(),
+        }
+    }
+
+    /// Parse presence pushed over a realtime channel
+    /// Accepts the presence itself or an envelope with a `Presence` field
+    pub fn from_realtime(payload: &Value) -> Option<Self> {
+        let presence = payload.get("Presence").unwrap_or(payload);
+        serde_json::from_value(presence.clone()).ok()
+    }
+}
+
+/// Request to the presence endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PresenceRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<PresenceStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity_started_at: Option<i64>,
+    #[serde(rename = "UserIDs", skip_serializing_if = "Vec::is_empty")]
+    pub user_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetPresenceResult {
+    #[serde(default)]
+    pub presence: Vec<PlayerPresence>,
+}
+
+/// Another player's presence changed (status or activity)
+#[derive(Message, Debug, Clone)]
+pub struct PresenceChanged {
+    /// `None` the first time this player's presence is seen
+    pub previous: Option<PresenceStatus>,
+    pub presence: PlayerPresence,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rich_status() {
+        let presence = PlayerPresence::from_realtime(&json!({
+            "Type": "PresenceChanged",
+            "Presence": {
+                "UserID": "friend",
+                "Status": "InMatch",
+                "Activity": "In ranked match",
+                "ActivityStartedAt": 1000
+            }
+        }))
+        .unwrap();
+        assert_eq!(presence.rich_status(1452), "In ranked match, 7:32");
+
+        let away = PlayerPresence {
+            status: PresenceStatus::Away,
+            activity: None,
+            ..presence.clone()
+        };
+        assert_eq!(away.rich_status(1452), "Away");
+
+        let offline = PlayerPresence {
+            status: PresenceStatus::Offline,
+            ..presence
+        };
+        assert_eq!(offline.rich_status(1452), "Offline");
+    }
+}
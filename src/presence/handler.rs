@@ -0,0 +1,255 @@
+/// Presence handler: publishes the player's status and tracks subscribed players
+/// Updates for others arrive through `handle_realtime_message` or `get_presence`
+use super::dto::*;
+use crate::{IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+#[derive(Resource, Clone)]
+pub struct PresenceHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    /// The player's own presence, as last published
+    own: Arc<Mutex<Option<PlayerPresence>>>,
+    /// Subscribed players' presence by user ID
+    others: Arc<Mutex<HashMap<String, PlayerPresence>>>,
+    subscribed: Arc<Mutex<HashSet<String>>>,
+    /// Events waiting for PresencePlugin
+    events: Arc<Mutex<Vec<PresenceChanged>>>,
+}
+
+impl PresenceHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            own: Arc::new(Mutex::new(None)),
+            others: Arc::new(Mutex::new(HashMap::new())),
+            subscribed: Arc::new(Mutex::new(HashSet::new())),
+            events: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        if let Ok(mut own) = self.own.lock() {
+            *own = None;
+        }
+        if let Ok(mut others) = self.others.lock() {
+            others.clear();
+        }
+        if let Ok(mut subscribed) = self.subscribed.lock() {
+            subscribed.clear();
+        }
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    fn request(&self, function_name: &str) -> IdosResult<PresenceRequest> {
+        Ok(PresenceRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            status: None,
+            activity: None,
+            activity_started_at: None,
+            user_ids: Vec::new(),
+        })
+    }
+
+    /// Publish the player's status and optional activity ("In ranked match")
+    /// The activity timer restarts only when the activity text changes
+    pub async fn set_status(
+        &self,
+        status: PresenceStatus,
+        activity: Option<&str>,
+    ) -> IdosResult<PlayerPresence> {
+        let now = chrono::Utc::now().timestamp();
+        let previous = self.own_presence();
+        let activity_started_at = match (&previous, activity) {
+            (Some(prev), Some(activity)) if prev.activity.as_deref() == Some(activity) => {
+                prev.activity_started_at
+            }
+            (_, Some(_)) => Some(now),
+            (_, None) => None,
+        };
+
+        let presence = PlayerPresence {
+            user_id: self.get_user_id()?,
+            status,
+            activity: activity.map(str::to_string),
+            activity_started_at,
+            last_seen_at: now,
+        };
+        self.publish(&presence).await?;
+
+        if let Ok(mut own) = self.own.lock() {
+            *own = Some(presence.clone());
+        }
+        Ok(presence)
+    }
+
+    /// Re-send the current status so the backend keeps the player online
+    /// Does nothing until `set_status` has been called
+    pub async fn heartbeat(&self) -> IdosResult<()> {
+        let Some(presence) = self.own_presence() else {
+            return Ok(());
+        };
+        self.publish(&presence).await
+    }
+
+    async fn publish(&self, presence: &PlayerPresence) -> IdosResult<()> {
+        let request = PresenceRequest {
+            status: Some(presence.status),
+            activity: presence.activity.clone(),
+            activity_started_at: presence.activity_started_at,
+            ..self.request("SetPresence")?
+        };
+
+        let endpoint = "presence/SetPresence";
+        let _: serde_json::Value = self.client.post(endpoint, &request).await?;
+        Ok(())
+    }
+
+    /// The player's own presence, as last published
+    pub fn own_presence(&self) -> Option<PlayerPresence> {
+        self.own.lock().ok().and_then(|own| own.clone())
+    }
+
+    /// Query players' presence once, without subscribing
+    pub async fn get_presence(&self, user_ids: &[String]) -> IdosResult<Vec<PlayerPresence>> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let request = PresenceRequest {
+            user_ids: user_ids.to_vec(),
+            ..self.request("GetPresence")?
+        };
+        let endpoint = "presence/GetPresence";
+        let result: GetPresenceResult = self.client.post(endpoint, &request).await?;
+
+        for presence in &result.presence {
+            self.update(presence.clone());
+        }
+        Ok(result.presence)
+    }
+
+    /// Receive realtime presence changes for these players (e.g. the friend list)
+    /// Returns their current presence
+    pub async fn subscribe(&self, user_ids: &[String]) -> IdosResult<Vec<PlayerPresence>> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let request = PresenceRequest {
+            user_ids: user_ids.to_vec(),
+            ..self.request("SubscribePresence")?
+        };
+        let endpoint = "presence/SubscribePresence";
+        let result: GetPresenceResult = self.client.post(endpoint, &request).await?;
+
+        if let Ok(mut subscribed) = self.subscribed.lock() {
+            subscribed.extend(user_ids.iter().cloned());
+        }
+        for presence in &result.presence {
+            self.update(presence.clone());
+        }
+        Ok(result.presence)
+    }
+
+    pub async fn unsubscribe(&self, user_ids: &[String]) -> IdosResult<()> {
+        let request = PresenceRequest {
+            user_ids: user_ids.to_vec(),
+            ..self.request("UnsubscribePresence")?
+        };
+        let endpoint = "presence/UnsubscribePresence";
+        let _: serde_json::Value = self.client.post(endpoint, &request).await?;
+
+        if let Ok(mut subscribed) = self.subscribed.lock() {
+            for user_id in user_ids {
+                subscribed.remove(user_id);
+            }
+        }
+        if let Ok(mut others) = self.others.lock() {
+            others.retain(|user_id, _| !user_ids.contains(user_id));
+        }
+        Ok(())
+    }
+
+    /// Cached presence of another player
+    pub fn presence_of(&self, user_id: &str) -> Option<PlayerPresence> {
+        self.others
+            .lock()
+            .ok()
+            .and_then(|others| others.get(user_id).cloned())
+    }
+
+    /// Presence pushed by a realtime channel
+    /// Call from the game's connection with the message payload; returns false if it isn't presence
+    pub fn handle_realtime_message(&self, payload: &serde_json::Value) -> bool {
+        let Some(presence) = PlayerPresence::from_realtime(payload) else {
+            return false;
+        };
+        if self.user_id.as_deref() == Some(presence.user_id.as_str()) {
+            return true;
+        }
+        self.update(presence);
+        true
+    }
+
+    /// Store a player's presence and queue `PresenceChanged` if it differs
+    fn update(&self, presence: PlayerPresence) {
+        let Ok(mut others) = self.others.lock() else {
+            return;
+        };
+
+        let previous = others.get(&presence.user_id);
+        let changed = previous.is_none_or(|prev| {
+            prev.status != presence.status || prev.activity != presence.activity
+        });
+        if changed {
+            let event = PresenceChanged {
+                previous: previous.map(|prev| prev.status),
+                presence: presence.clone(),
+            };
+            if let Ok(mut events) = self.events.lock() {
+                events.push(event);
+            }
+        }
+        others.insert(presence.user_id.clone(), presence);
+    }
+
+    /// Take the events queued since the last call
+    pub fn drain_events(&self) -> Vec<PresenceChanged> {
+        self.events
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default()
+    }
+}
+
+crate::session::session_aware!(PresenceHandler);
@@ -0,0 +1,8 @@
+/// Presence module: the player's online status and friends' presence with rich status strings
+pub mod dto;
+pub mod handler;
+pub mod presence_plugin;
+
+pub use dto::*;
+pub use handler::PresenceHandler;
+pub use presence_plugin::PresencePlugin;
@@ -0,0 +1,65 @@
+/// Presence Bevy plugin
+use super::dto::PresenceChanged;
+use super::handler::PresenceHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+/// How often the player's status is re-sent; the backend marks players offline after missing a few
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
+pub struct PresencePlugin;
+
+impl Plugin for PresencePlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize presence handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = PresenceHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<PresenceHandler>.in_set(SessionSync),
+        );
+
+        app.add_message::<PresenceChanged>().add_systems(
+            Update,
+            (
+                send_presence_heartbeat.run_if(on_timer(HEARTBEAT_INTERVAL)),
+                forward_presence_changes,
+            ),
+        );
+    }
+}
+
+/// Keep the player online while the game runs
+fn send_presence_heartbeat(handler: Option<Res<PresenceHandler>>) {
+    let Some(handler) = handler else {
+        return;
+    };
+    if handler.own_presence().is_none() {
+        return;
+    }
+
+    let h = handler.clone();
+    crate::tasks::spawn(async move {
+        h.heartbeat().await.ok();
+    });
+}
+
+/// Forward presence changes as messages
+fn forward_presence_changes(
+    handler: Option<Res<PresenceHandler>>,
+    mut changed: MessageWriter<PresenceChanged>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for change in handler.drain_events() {
+        changed.write(change);
+    }
+}
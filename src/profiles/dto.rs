@@ -0,0 +1,70 @@
+/// Data Transfer Objects for Profiles
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProfileBadge {
+    #[serde(rename = "BadgeID")]
+    pub badge_id: String,
+    pub name: String,
+    #[serde(rename = "IconURL", default)]
+    pub icon_url: Option<String>,
+}
+
+/// NFT a player pinned to their profile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ShowcasedNft {
+    /// "Ethereum" or "Solana"
+    pub network: String,
+    /// ERC-721/1155 contract address, or the Solana mint
+    pub contract: String,
+    /// Token ID (Ethereum only)
+    #[serde(rename = "TokenID", default)]
+    pub token_id: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(rename = "ImageURL", default)]
+    pub image_url: Option<String>,
+}
+
+/// What other players can see about a player
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PublicProfile {
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub display_name: String,
+    #[serde(rename = "AvatarURL", default)]
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub level: u32,
+    #[serde(default)]
+    pub badges: Vec<ProfileBadge>,
+    #[serde(default)]
+    pub showcase: Vec<ShowcasedNft>,
+}
+
+/// Request to the profile endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProfilesRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(rename = "UserIDs", skip_serializing_if = "Vec::is_empty")]
+    pub user_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub showcase: Option<Vec<ShowcasedNft>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetProfilesResult {
+    #[serde(default)]
+    pub profiles: Vec<PublicProfile>,
+}
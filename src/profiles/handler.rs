@@ -0,0 +1,163 @@
+/// Profiles handler with batched lookups and a short-lived cache
+/// Meant for rendering leaderboards and friend lists without one request per row
+use super::dto::*;
+use crate::{ApiErrorCode, IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Profiles requested per call
+const BATCH_SIZE: usize = 50;
+/// Cached profiles are reused for this long
+const CACHE_TTL_SECONDS: i64 = 300;
+
+#[derive(Resource, Clone)]
+pub struct ProfilesHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    /// user ID -> (profile, fetched at)
+    cache: Arc<Mutex<HashMap<String, (PublicProfile, i64)>>>,
+}
+
+impl ProfilesHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.clear();
+        }
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    /// Cached profile, even if stale; for drawing rows while a refresh is in flight
+    pub fn cached_profile(&self, user_id: &str) -> Option<PublicProfile> {
+        self.cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(user_id).map(|(profile, _)| profile.clone()))
+    }
+
+    pub async fn get_profile(&self, user_id: &str) -> IdosResult<PublicProfile> {
+        self.get_profiles(&[user_id.to_string()])
+            .await?
+            .remove(user_id)
+            .ok_or_else(|| IdosError::Api {
+                code: ApiErrorCode::NotFound,
+                message: format!("Profile {} not found", user_id),
+                http_status: None,
+                error_code: None,
+            })
+    }
+
+    /// Profiles for `user_ids`; fresh cache entries are reused and the rest fetched in batches
+    /// Unknown players are missing from the map
+    pub async fn get_profiles(
+        &self,
+        user_ids: &[String],
+    ) -> IdosResult<HashMap<String, PublicProfile>> {
+        let now = chrono::Utc::now().timestamp();
+        let mut profiles = HashMap::new();
+        let mut missing = Vec::new();
+
+        if let Ok(cache) = self.cache.lock() {
+            for user_id in user_ids {
+                match cache.get(user_id) {
+                    Some((profile, fetched_at)) if now - fetched_at < CACHE_TTL_SECONDS => {
+                        profiles.insert(user_id.clone(), profile.clone());
+                    }
+                    _ if !missing.contains(user_id) => missing.push(user_id.clone()),
+                    _ => {}
+                }
+            }
+        }
+
+        for batch in missing.chunks(BATCH_SIZE) {
+            let request = self.request("GetPublicProfiles", batch.to_vec(), None)?;
+            let endpoint = "profiles/GetPublicProfiles";
+            let result: GetProfilesResult = self.client.post(endpoint, &request).await?;
+
+            if let Ok(mut cache) = self.cache.lock() {
+                for profile in &result.profiles {
+                    cache.insert(profile.user_id.clone(), (profile.clone(), now));
+                }
+            }
+            profiles.extend(
+                result
+                    .profiles
+                    .into_iter()
+                    .map(|profile| (profile.user_id.clone(), profile)),
+            );
+        }
+
+        Ok(profiles)
+    }
+
+    /// Replace the NFTs shown on the player's own profile
+    pub async fn update_showcase(&self, showcase: Vec<ShowcasedNft>) -> IdosResult<PublicProfile> {
+        let request = self.request("UpdateShowcase", Vec::new(), Some(showcase))?;
+        let endpoint = "profiles/UpdateShowcase";
+        let profile: PublicProfile = self.client.post(endpoint, &request).await?;
+
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(
+                profile.user_id.clone(),
+                (profile.clone(), chrono::Utc::now().timestamp()),
+            );
+        }
+        Ok(profile)
+    }
+
+    /// Drop a cached profile so the next lookup refetches it
+    pub fn invalidate(&self, user_id: &str) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.remove(user_id);
+        }
+    }
+
+    fn request(
+        &self,
+        function_name: &str,
+        user_ids: Vec<String>,
+        showcase: Option<Vec<ShowcasedNft>>,
+    ) -> IdosResult<ProfilesRequest> {
+        Ok(ProfilesRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            user_ids,
+            showcase,
+        })
+    }
+}
+
+crate::session::session_aware!(ProfilesHandler);
@@ -0,0 +1,8 @@
+/// Profiles module: other players' public profiles and the player's own showcase
+pub mod dto;
+pub mod handler;
+pub mod profiles_plugin;
+
+pub use dto::*;
+pub use handler::ProfilesHandler;
+pub use profiles_plugin::ProfilesPlugin;
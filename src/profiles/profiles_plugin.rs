@@ -0,0 +1,22 @@
+/// Profiles Bevy plugin
+use super::handler::ProfilesHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+
+pub struct ProfilesPlugin;
+
+impl Plugin for ProfilesPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize profiles handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = ProfilesHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<ProfilesHandler>.in_set(SessionSync),
+        );
+    }
+}
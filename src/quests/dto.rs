@@ -0,0 +1,229 @@
+/// Data Transfer Objects for Quests
+use bevy::prelude::Message;
+use serde::{Deserialize, Serialize};
+
+/// How a quest rotates
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum QuestCategory {
+    Daily,
+    Weekly,
+    /// Limited-time event quest; ends at `Quest::ends_at`
+    Event,
+}
+
+/// What an objective measures
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "Kind")]
+pub enum ObjectiveKind {
+    /// Reported values add up (e.g. "win 3 matches")
+    Count {
+        #[serde(rename = "Target")]
+        target: i64,
+    },
+    /// Highest reported value counts (e.g. "reach level 10")
+    ReachValue {
+        #[serde(rename = "Target")]
+        target: i64,
+    },
+    /// Done once reported (e.g. "visit the shop")
+    Boolean,
+}
+
+/// One objective of a quest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct QuestObjective {
+    #[serde(rename = "ObjectiveID")]
+    pub objective_id: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(flatten)]
+    pub kind: ObjectiveKind,
+    #[serde(default)]
+    pub progress: i64,
+}
+
+impl QuestObjective {
+    pub fn target(&self) -> i64 {
+        match self.kind {
+            ObjectiveKind::Count { target } | ObjectiveKind::ReachValue { target } => target,
+            ObjectiveKind::Boolean => 1,
+        }
+    }
+
+    /// Progress after reporting `value`, following the objective's kind
+    pub fn progress_after(&self, value: i64) -> i64 {
+        match self.kind {
+            ObjectiveKind::Count { .. } => self.progress.saturating_add(value.max(0)),
+            ObjectiveKind::ReachValue { .. } => self.progress.max(value),
+            ObjectiveKind::Boolean => self.progress.max((value != 0) as i64),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.progress >= self.target()
+    }
+}
+
+/// Item or virtual currency granted for a completed quest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct QuestReward {
+    #[serde(rename = "ItemID")]
+    pub item_id: String,
+    pub amount: u32,
+}
+
+/// An active quest with the player's progress
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct Quest {
+    #[serde(rename = "QuestID")]
+    pub quest_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub category: QuestCategory,
+    pub objectives: Vec<QuestObjective>,
+    #[serde(default)]
+    pub rewards: Vec<QuestReward>,
+    /// Unix seconds
+    #[serde(default)]
+    pub starts_at: Option<i64>,
+    /// Unix seconds; the quest rotates out after this
+    #[serde(default)]
+    pub ends_at: Option<i64>,
+    #[serde(default)]
+    pub claimed: bool,
+}
+
+impl Quest {
+    pub fn is_complete(&self) -> bool {
+        self.objectives.iter().all(QuestObjective::is_complete)
+    }
+
+    /// Completed and not claimed yet
+    pub fn is_claimable(&self) -> bool {
+        self.is_complete() && !self.claimed
+    }
+
+    /// Whether the quest is running at `now` (unix seconds)
+    pub fn is_active_at(&self, now: i64) -> bool {
+        self.starts_at.is_none_or(|start| now >= start) && self.ends_at.is_none_or(|end| now < end)
+    }
+
+    /// Seconds until the quest ends (None if it doesn't expire)
+    pub fn seconds_remaining(&self, now: i64) -> Option<i64> {
+        self.ends_at.map(|end| (end - now).max(0))
+    }
+}
+
+/// Active quests and when the next rotation happens
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetQuestsResult {
+    #[serde(default)]
+    pub quests: Vec<Quest>,
+    /// Unix seconds of the next daily/weekly reset
+    #[serde(default)]
+    pub next_rotation: Option<i64>,
+}
+
+/// Request to get quests or claim a quest reward
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct QuestsRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(rename = "QuestID", skip_serializing_if = "Option::is_none")]
+    pub quest_id: Option<String>,
+}
+
+/// Request to report progress on an objective
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReportQuestProgressRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(rename = "QuestID")]
+    pub quest_id: String,
+    #[serde(rename = "ObjectiveID")]
+    pub objective_id: String,
+    /// Count: amount to add; ReachValue: value reached; Boolean: non-zero when done
+    pub value: i64,
+}
+
+/// Rewards granted for a claimed quest
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ClaimQuestRewardResult {
+    #[serde(default)]
+    pub rewards: Vec<QuestReward>,
+}
+
+/// All objectives of a quest are complete; its reward can be claimed
+#[derive(Message, Debug, Clone)]
+pub struct QuestCompleted {
+    pub quest_id: String,
+    pub name: String,
+}
+
+/// Quests expired or the daily/weekly reset passed; fetch quests again
+#[derive(Message, Debug, Clone)]
+pub struct QuestsRotated {
+    /// Quests that ended since the last check
+    pub expired: Vec<String>,
+}
+
+/// Quest event queued by the handler until QuestsPlugin writes it as a message
+#[derive(Debug, Clone)]
+pub enum QuestEvent {
+    Completed(QuestCompleted),
+    Rotated(QuestsRotated),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn objective(kind: ObjectiveKind, progress: i64) -> QuestObjective {
+        QuestObjective {
+            objective_id: "o".to_string(),
+            description: String::new(),
+            kind,
+            progress,
+        }
+    }
+
+    #[test]
+    fn test_objective_kinds() {
+        let count = objective(ObjectiveKind::Count { target: 3 }, 2);
+        assert_eq!(count.progress_after(2), 4);
+        assert_eq!(count.progress_after(-5), 2);
+
+        let reach = objective(ObjectiveKind::ReachValue { target: 10 }, 7);
+        assert_eq!(reach.progress_after(5), 7);
+        assert_eq!(reach.progress_after(12), 12);
+
+        let boolean = objective(ObjectiveKind::Boolean, 0);
+        assert_eq!(boolean.progress_after(1), 1);
+        assert!(!boolean.is_complete());
+
+        let parsed: QuestObjective = serde_json::from_str(
+            r#"{"ObjectiveID":"wins","Kind":"Count","Target":3,"Progress":1}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.kind, ObjectiveKind::Count { target: 3 });
+        assert_eq!(parsed.progress, 1);
+    }
+}
@@ -0,0 +1,256 @@
+/// Quests handler: active missions, progress reports and reward claims
+use super::dto::*;
+use crate::{IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::sync::{Arc, Mutex};
+
+#[derive(Resource, Clone)]
+pub struct QuestsHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    /// Quests from the last fetch, updated by progress reports
+    quests: Arc<Mutex<Vec<Quest>>>,
+    next_rotation: Arc<Mutex<Option<i64>>>,
+    /// Events waiting for QuestsPlugin
+    events: Arc<Mutex<Vec<QuestEvent>>>,
+}
+
+impl QuestsHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            quests: Arc::new(Mutex::new(Vec::new())),
+            next_rotation: Arc::new(Mutex::new(None)),
+            events: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        if let Ok(mut quests) = self.quests.lock() {
+            quests.clear();
+        }
+        if let Ok(mut next_rotation) = self.next_rotation.lock() {
+            *next_rotation = None;
+        }
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    /// Fetch the player's active quests and the next rotation time
+    pub async fn fetch_quests(&self) -> IdosResult<GetQuestsResult> {
+        let request = QuestsRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: "GetQuests".to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            quest_id: None,
+        };
+
+        let endpoint = "quests/GetQuests";
+        let result: GetQuestsResult = self.client.post(endpoint, &request).await?;
+
+        if let Ok(mut quests) = self.quests.lock() {
+            *quests = result.quests.clone();
+        }
+        if let Ok(mut next_rotation) = self.next_rotation.lock() {
+            *next_rotation = result.next_rotation;
+        }
+        Ok(result)
+    }
+
+    /// Cached quests that are running now, optionally of one category
+    pub fn active_quests(&self, category: Option<QuestCategory>) -> Vec<Quest> {
+        let now = chrono::Utc::now().timestamp();
+        self.quests
+            .lock()
+            .map(|quests| {
+                quests
+                    .iter()
+                    .filter(|q| q.is_active_at(now))
+                    .filter(|q| category.is_none_or(|c| q.category == c))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Cached quests whose reward can be claimed
+    pub fn claimable_quests(&self) -> Vec<Quest> {
+        self.active_quests(None)
+            .into_iter()
+            .filter(Quest::is_claimable)
+            .collect()
+    }
+
+    /// Unix seconds of the next daily/weekly reset, from the last fetch
+    pub fn next_rotation(&self) -> Option<i64> {
+        self.next_rotation.lock().ok().and_then(|next| *next)
+    }
+
+    /// Report progress on an objective; see `ReportQuestProgressRequest::value`
+    /// Returns the quest as updated by the server
+    pub async fn report_progress(
+        &self,
+        quest_id: &str,
+        objective_id: &str,
+        value: i64,
+    ) -> IdosResult<Quest> {
+        let cached = self.cached_quest(quest_id)?;
+        if !cached
+            .objectives
+            .iter()
+            .any(|o| o.objective_id == objective_id)
+        {
+            return Err(IdosError::InvalidInput(format!(
+                "Quest '{}' has no objective '{}'",
+                quest_id, objective_id
+            )));
+        }
+
+        let request = ReportQuestProgressRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: "ReportProgress".to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            quest_id: quest_id.to_string(),
+            objective_id: objective_id.to_string(),
+            value,
+        };
+
+        let endpoint = "quests/ReportProgress";
+        let quest: Quest = self.client.post(endpoint, &request).await?;
+
+        if !cached.is_complete() && quest.is_complete() {
+            self.push_event(QuestEvent::Completed(QuestCompleted {
+                quest_id: quest.quest_id.clone(),
+                name: quest.name.clone(),
+            }));
+        }
+        self.replace_quest(quest.clone());
+        Ok(quest)
+    }
+
+    /// Claim the reward of a completed quest
+    pub async fn claim_reward(&self, quest_id: &str) -> IdosResult<ClaimQuestRewardResult> {
+        let mut quest = self.cached_quest(quest_id)?;
+        if !quest.is_claimable() {
+            return Err(IdosError::InvalidInput(format!(
+                "Quest '{}' is not complete or already claimed",
+                quest_id
+            )));
+        }
+
+        let request = QuestsRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: "ClaimQuestReward".to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            quest_id: Some(quest_id.to_string()),
+        };
+
+        let endpoint = "quests/ClaimQuestReward";
+        let result: ClaimQuestRewardResult = self.client.post(endpoint, &request).await?;
+
+        quest.claimed = true;
+        self.replace_quest(quest);
+        Ok(result)
+    }
+
+    fn cached_quest(&self, quest_id: &str) -> IdosResult<Quest> {
+        self.quests
+            .lock()
+            .ok()
+            .and_then(|quests| quests.iter().find(|q| q.quest_id == quest_id).cloned())
+            .ok_or_else(|| {
+                IdosError::InvalidInput(format!(
+                    "Unknown quest '{}' (call fetch_quests first)",
+                    quest_id
+                ))
+            })
+    }
+
+    fn replace_quest(&self, quest: Quest) {
+        if let Ok(mut quests) = self.quests.lock() {
+            if let Some(cached) = quests.iter_mut().find(|q| q.quest_id == quest.quest_id) {
+                *cached = quest;
+            }
+        }
+    }
+
+    /// Drop quests that ended and detect the daily/weekly reset
+    /// Queues `QuestsRotated` when anything rotated; returns whether it did
+    pub fn check_rotation(&self, now: i64) -> bool {
+        let expired: Vec<String> = self
+            .quests
+            .lock()
+            .map(|mut quests| {
+                let expired = quests
+                    .iter()
+                    .filter(|q| q.ends_at.is_some_and(|end| now >= end))
+                    .map(|q| q.quest_id.clone())
+                    .collect();
+                quests.retain(|q| q.ends_at.is_none_or(|end| now < end));
+                expired
+            })
+            .unwrap_or_default();
+
+        let reset = self
+            .next_rotation
+            .lock()
+            .map(|mut next| match *next {
+                Some(at) if now >= at => {
+                    *next = None;
+                    true
+                }
+                _ => false,
+            })
+            .unwrap_or(false);
+
+        if expired.is_empty() && !reset {
+            return false;
+        }
+        self.push_event(QuestEvent::Rotated(QuestsRotated { expired }));
+        true
+    }
+
+    fn push_event(&self, event: QuestEvent) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
+    }
+
+    /// Take the quest events collected since the last call
+    pub fn drain_events(&self) -> Vec<QuestEvent> {
+        self.events
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default()
+    }
+}
+
+crate::session::session_aware!(QuestsHandler);
@@ -0,0 +1,8 @@
+/// Quests module: daily, weekly and event missions with typed objectives
+pub mod dto;
+pub mod handler;
+pub mod quests_plugin;
+
+pub use dto::*;
+pub use handler::QuestsHandler;
+pub use quests_plugin::QuestsPlugin;
@@ -0,0 +1,62 @@
+/// Quests Bevy plugin
+use super::dto::{QuestCompleted, QuestEvent, QuestsRotated};
+use super::handler::QuestsHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+pub struct QuestsPlugin;
+
+impl Plugin for QuestsPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize quests handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = QuestsHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(PreUpdate, sync_session::<QuestsHandler>.in_set(SessionSync));
+
+        app.add_message::<QuestCompleted>()
+            .add_message::<QuestsRotated>()
+            .add_systems(
+                Update,
+                (
+                    check_quest_rotation.run_if(on_timer(Duration::from_secs(1))),
+                    forward_quest_events,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Expire ended quests and notice daily/weekly resets
+fn check_quest_rotation(handler: Option<Res<QuestsHandler>>) {
+    if let Some(handler) = handler {
+        handler.check_rotation(chrono::Utc::now().timestamp());
+    }
+}
+
+/// Forward quest events as messages
+fn forward_quest_events(
+    handler: Option<Res<QuestsHandler>>,
+    mut completed: MessageWriter<QuestCompleted>,
+    mut rotated: MessageWriter<QuestsRotated>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for event in handler.drain_events() {
+        match event {
+            QuestEvent::Completed(event) => {
+                completed.write(event);
+            }
+            QuestEvent::Rotated(event) => {
+                rotated.write(event);
+            }
+        }
+    }
+}
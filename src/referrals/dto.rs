@@ -0,0 +1,108 @@
+/// Data Transfer Objects for Referrals
+use serde::{Deserialize, Serialize};
+
+/// The player's own referral code and who referred them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReferralInfo {
+    pub referral_code: String,
+    /// Shareable link that applies the code on first launch
+    #[serde(default)]
+    pub referral_link: Option<String>,
+    #[serde(default)]
+    pub referred_count: u32,
+    /// User ID of the player whose code was applied to this account
+    #[serde(rename = "ReferredBy", default)]
+    pub referred_by: Option<String>,
+}
+
+/// Item or virtual currency granted by a referral milestone
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReferralReward {
+    #[serde(rename = "ItemID")]
+    pub item_id: String,
+    pub amount: u32,
+}
+
+/// Goal a referred friend has to reach (e.g. "reach level 10") to reward the referrer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReferralMilestone {
+    #[serde(rename = "MilestoneID")]
+    pub milestone_id: String,
+    #[serde(default)]
+    pub description: String,
+    pub reached: bool,
+    pub claimed: bool,
+    #[serde(default)]
+    pub rewards: Vec<ReferralReward>,
+}
+
+/// A friend who joined with the player's code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReferredFriend {
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    #[serde(default)]
+    pub user_name: Option<String>,
+    #[serde(default)]
+    pub milestones: Vec<ReferralMilestone>,
+}
+
+/// Friends referred by the player
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetReferredFriendsResult {
+    #[serde(default)]
+    pub friends: Vec<ReferredFriend>,
+}
+
+impl GetReferredFriendsResult {
+    /// `(friend user ID, milestone)` pairs reached but not claimed
+    pub fn claimable(&self) -> Vec<(&str, &ReferralMilestone)> {
+        self.friends
+            .iter()
+            .flat_map(|friend| {
+                friend
+                    .milestones
+                    .iter()
+                    .filter(|m| m.reached && !m.claimed)
+                    .map(move |m| (friend.user_id.as_str(), m))
+            })
+            .collect()
+    }
+}
+
+/// Request to the referral endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReferralRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referral_code: Option<String>,
+    #[serde(rename = "FriendUserID", skip_serializing_if = "Option::is_none")]
+    pub friend_user_id: Option<String>,
+    #[serde(rename = "MilestoneID", skip_serializing_if = "Option::is_none")]
+    pub milestone_id: Option<String>,
+}
+
+/// Outcome of applying a code or claiming a milestone
+/// `error_code` is set when an anti-abuse check rejected the request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReferralActionResult {
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub error_code: Option<String>,
+    #[serde(default)]
+    pub rewards: Vec<ReferralReward>,
+}
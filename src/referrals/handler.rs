@@ -0,0 +1,191 @@
+/// Referrals handler for invite codes and referral rewards
+use super::dto::*;
+use crate::{IdosClient, IdosError, IdosResult, ReferralRejection};
+use bevy::prelude::Resource;
+
+#[derive(Resource, Clone)]
+pub struct ReferralsHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+}
+
+impl ReferralsHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    fn request(&self, function_name: &str) -> IdosResult<ReferralRequest> {
+        Ok(ReferralRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            referral_code: None,
+            friend_user_id: None,
+            milestone_id: None,
+        })
+    }
+
+    /// The player's referral code and share link
+    pub async fn get_referral_info(&self) -> IdosResult<ReferralInfo> {
+        let request = self.request("GetReferralInfo")?;
+        let endpoint = "referrals/GetReferralInfo";
+        self.client.post(endpoint, &request).await
+    }
+
+    /// Apply a friend's referral code to this account
+    /// Anti-abuse rejections are returned as `IdosError::ReferralRejected`, whether the backend
+    /// reports them in a successful response or as a failed request
+    pub async fn submit_referral_code(&self, code: &str) -> IdosResult<ReferralActionResult> {
+        let code = code.trim();
+        if code.is_empty() {
+            return Err(IdosError::ReferralRejected(ReferralRejection::InvalidCode));
+        }
+
+        let request = ReferralRequest {
+            referral_code: Some(code.to_uppercase()),
+            ..self.request("SubmitReferralCode")?
+        };
+        let endpoint = "referrals/SubmitReferralCode";
+        Self::check(self.client.post(endpoint, &request).await)
+    }
+
+    /// Friends who joined with the player's code and their milestone progress
+    pub async fn get_referred_friends(&self) -> IdosResult<GetReferredFriendsResult> {
+        let request = self.request("GetReferredFriends")?;
+        let endpoint = "referrals/GetReferredFriends";
+        self.client.post(endpoint, &request).await
+    }
+
+    /// Claim the reward for a milestone reached by a referred friend
+    pub async fn claim_referral_reward(
+        &self,
+        friend_user_id: &str,
+        milestone_id: &str,
+    ) -> IdosResult<ReferralActionResult> {
+        let request = ReferralRequest {
+            friend_user_id: Some(friend_user_id.to_string()),
+            milestone_id: Some(milestone_id.to_string()),
+            ..self.request("ClaimReferralReward")?
+        };
+        let endpoint = "referrals/ClaimReferralReward";
+        Self::check(self.client.post(endpoint, &request).await)
+    }
+
+    /// Turn anti-abuse failures into `ReferralRejected`
+    /// They arrive as `error_code` in a 2xx body, or as a failed request carrying a referral
+    /// error code; other failures are returned unchanged
+    fn check(result: IdosResult<ReferralActionResult>) -> IdosResult<ReferralActionResult> {
+        match result {
+            Ok(ReferralActionResult {
+                error_code: Some(code),
+                ..
+            }) => Err(IdosError::ReferralRejected(ReferralRejection::from_code(
+                &code,
+            ))),
+            Ok(result) => Ok(result),
+            Err(error) => Err(Self::rejection(&error).map_or(error, IdosError::ReferralRejected)),
+        }
+    }
+
+    /// Referral rejection carried by a failed request, if its error code is a referral code
+    fn rejection(error: &IdosError) -> Option<ReferralRejection> {
+        let IdosError::Api {
+            error_code: Some(code),
+            ..
+        } = error
+        else {
+            return None;
+        };
+
+        match ReferralRejection::from_code(code) {
+            ReferralRejection::Other(_) => None,
+            rejection => Some(rejection),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_maps_rejections() {
+        let rejected = ReferralsHandler::check(Err(IdosError::from_response(
+            400,
+            r#"{"ErrorCode":"SELF_REFERRAL","Message":"That's your own code"}"#,
+        )));
+        assert!(matches!(
+            rejected,
+            Err(IdosError::ReferralRejected(ReferralRejection::SelfReferral))
+        ));
+
+        let rejected = ReferralsHandler::check(Err(IdosError::from_response(
+            409,
+            r#"{"ErrorCode":"AlreadyReferred"}"#,
+        )));
+        assert!(matches!(
+            rejected,
+            Err(IdosError::ReferralRejected(
+                ReferralRejection::AlreadyReferred
+            ))
+        ));
+
+        let rejected = ReferralsHandler::check(Ok(ReferralActionResult {
+            message: None,
+            error_code: Some("device_already_used".to_string()),
+            rewards: Vec::new(),
+        }));
+        assert!(matches!(
+            rejected,
+            Err(IdosError::ReferralRejected(
+                ReferralRejection::DeviceAlreadyUsed
+            ))
+        ));
+
+        // Unrelated failures stay as they are
+        let failed = ReferralsHandler::check(Err(IdosError::from_response(
+            401,
+            r#"{"ErrorCode":"SESSION_EXPIRED"}"#,
+        )));
+        assert!(matches!(
+            failed,
+            Err(IdosError::Api {
+                code: crate::ApiErrorCode::AuthExpired,
+                ..
+            })
+        ));
+    }
+}
+
+crate::session::session_aware!(ReferralsHandler);
@@ -0,0 +1,8 @@
+/// Referral module: invite codes, referred-friend milestones and rewards
+pub mod dto;
+pub mod handler;
+pub mod referrals_plugin;
+
+pub use dto::*;
+pub use handler::ReferralsHandler;
+pub use referrals_plugin::ReferralsPlugin;
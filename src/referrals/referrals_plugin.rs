@@ -0,0 +1,22 @@
+/// Referrals Bevy plugin
+use super::handler::ReferralsHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+
+pub struct ReferralsPlugin;
+
+impl Plugin for ReferralsPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize referrals handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = ReferralsHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<ReferralsHandler>.in_set(SessionSync),
+        );
+    }
+}
@@ -0,0 +1,179 @@
+/// Data Transfer Objects for Remote Config
+use bevy::prelude::Message;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Experiment (A/B test) the player is enrolled in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ExperimentAssignment {
+    #[serde(rename = "ExperimentID")]
+    pub experiment_id: String,
+    /// Variant the player was assigned ("control", "b", ...)
+    pub variant: String,
+}
+
+/// Title configuration with the player's experiment overrides already applied
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RemoteConfig {
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub values: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub experiments: Vec<ExperimentAssignment>,
+}
+
+impl RemoteConfig {
+    /// Boolean value; the strings "true" and "false" are accepted too
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.values.get(key)? {
+            serde_json::Value::Bool(value) => Some(*value),
+            serde_json::Value::String(value) => value.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Integer value; numeric strings are accepted too
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        match self.values.get(key)? {
+            serde_json::Value::Number(value) => value.as_i64(),
+            serde_json::Value::String(value) => value.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Number value; numeric strings are accepted too
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        match self.values.get(key)? {
+            serde_json::Value::Number(value) => value.as_f64(),
+            serde_json::Value::String(value) => value.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn get_string(&self, key: &str) -> Option<String> {
+        match self.values.get(key)? {
+            serde_json::Value::String(value) => Some(value.clone()),
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// Value deserialized into `T`; JSON stored as a string is parsed first
+    pub fn get_json<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        match self.values.get(key)? {
+            serde_json::Value::String(json) => serde_json::from_str(json).ok(),
+            value => serde_json::from_value(value.clone()).ok(),
+        }
+    }
+
+    /// Variant of `experiment_id` the player is in, None when not enrolled
+    pub fn variant(&self, experiment_id: &str) -> Option<&str> {
+        self.experiments
+            .iter()
+            .find(|e| e.experiment_id == experiment_id)
+            .map(|e| e.variant.as_str())
+    }
+
+    /// Keys added, removed or changed compared to `previous`, sorted
+    pub fn changed_keys(&self, previous: &RemoteConfig) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .values
+            .iter()
+            .filter(|(key, value)| previous.values.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .chain(
+                previous
+                    .values
+                    .keys()
+                    .filter(|key| !self.values.contains_key(*key))
+                    .cloned(),
+            )
+            .collect();
+        keys.sort();
+        keys
+    }
+}
+
+/// Remote config as saved in Storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedRemoteConfig {
+    pub config: RemoteConfig,
+    /// Unix timestamp (seconds) of the fetch
+    pub fetched_at: i64,
+}
+
+/// Request for the title's remote config
+/// Without a player the title defaults are returned, with no experiment assignments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RemoteConfigRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_session_ticket: Option<String>,
+}
+
+/// New remote config values or experiment assignments were activated
+#[derive(Message, Debug, Clone)]
+pub struct RemoteConfigUpdated {
+    pub version: Option<String>,
+    /// Keys whose values changed
+    pub changed_keys: Vec<String>,
+    /// Whether the experiment assignments changed
+    pub experiments_changed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_typed_getters() {
+        let config: RemoteConfig = serde_json::from_value(json!({
+            "Version": "7",
+            "Values": {
+                "double_xp": true,
+                "max_lives": "5",
+                "drop_rate": 0.25,
+                "shop": { "featured": ["sword", "shield"] },
+                "banner": "{\"color\":\"red\"}"
+            },
+            "Experiments": [{ "ExperimentID": "onboarding", "Variant": "b" }]
+        }))
+        .unwrap();
+
+        assert_eq!(config.get_bool("double_xp"), Some(true));
+        assert_eq!(config.get_i64("max_lives"), Some(5));
+        assert_eq!(config.get_f64("drop_rate"), Some(0.25));
+        assert_eq!(config.get_bool("max_lives"), None);
+        assert_eq!(config.get_i64("missing"), None);
+
+        #[derive(Deserialize)]
+        struct Shop {
+            featured: Vec<String>,
+        }
+        let shop: Shop = config.get_json("shop").unwrap();
+        assert_eq!(shop.featured, ["sword", "shield"]);
+        let banner: serde_json::Value = config.get_json("banner").unwrap();
+        assert_eq!(banner["color"], "red");
+
+        assert_eq!(config.variant("onboarding"), Some("b"));
+        assert_eq!(config.variant("pricing"), None);
+
+        let mut previous = config.clone();
+        previous.values.remove("banner");
+        previous.values.insert("max_lives".to_string(), json!(3));
+        previous.values.insert("old_flag".to_string(), json!(true));
+        assert_eq!(
+            config.changed_keys(&previous),
+            ["banner", "max_lives", "old_flag"]
+        );
+    }
+}
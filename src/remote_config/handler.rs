@@ -0,0 +1,231 @@
+/// Remote config handler: fetches, caches and reads server-driven configuration
+/// The cached config is used right away at startup and refetched once it is older than the TTL
+use super::dto::*;
+use crate::{storage::Storage, IdosClient, IdosResult};
+use bevy::prelude::Resource;
+use serde::de::DeserializeOwned;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Default age after which the cached config is refetched
+pub const DEFAULT_CONFIG_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Storage key of the config fetched without a player
+const TITLE_CONFIG_KEY: &str = "config_title";
+
+#[derive(Resource, Clone)]
+pub struct RemoteConfigHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    storage: Storage,
+    ttl: Duration,
+    config: Arc<Mutex<Option<CachedRemoteConfig>>>,
+    /// Set while a fetch is in flight so RemoteConfigPlugin doesn't start another
+    fetching: Arc<AtomicBool>,
+    /// Updates waiting for RemoteConfigPlugin
+    updated: Arc<Mutex<Vec<RemoteConfigUpdated>>>,
+}
+
+impl RemoteConfigHandler {
+    pub fn new(client: IdosClient) -> Self {
+        let storage = Storage::cache(client.config(), "remote_config_");
+        let handler = Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            storage,
+            ttl: DEFAULT_CONFIG_TTL,
+            config: Arc::new(Mutex::new(None)),
+            fetching: Arc::new(AtomicBool::new(false)),
+            updated: Arc::new(Mutex::new(Vec::new())),
+        };
+        handler.load_cached();
+        handler
+    }
+
+    /// Change how long a fetched config stays fresh
+    pub fn set_ttl(&mut self, ttl: Duration) {
+        self.ttl = ttl;
+    }
+
+    /// Set user authentication info (call after login)
+    /// Switches to the player's cached config, which includes their experiment assignments
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+        self.load_cached();
+    }
+
+    /// Clear authentication info (call on logout)
+    /// Falls back to the title config
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        self.load_cached();
+    }
+
+    fn cache_key(&self) -> String {
+        match &self.user_id {
+            Some(user_id) => format!("config_{}", user_id),
+            None => TITLE_CONFIG_KEY.to_string(),
+        }
+    }
+
+    /// Activate the cached config for the current player, if any
+    fn load_cached(&self) {
+        let cached: Option<CachedRemoteConfig> = self
+            .storage
+            .get(&self.cache_key())
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok());
+        if let Some(cached) = cached {
+            self.activate(cached);
+        } else if let Ok(mut config) = self.config.lock() {
+            // Not fetched yet for this player; keep the values but force a refetch
+            if let Some(config) = config.as_mut() {
+                config.fetched_at = 0;
+            }
+        }
+    }
+
+    /// Current config (empty until the first fetch or cache load)
+    pub fn config(&self) -> RemoteConfig {
+        self.config
+            .lock()
+            .ok()
+            .and_then(|config| config.as_ref().map(|cached| cached.config.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Whether the config is missing or older than the TTL at `now` (unix seconds)
+    pub fn is_stale(&self, now: i64) -> bool {
+        let fetched_at = self
+            .config
+            .lock()
+            .ok()
+            .and_then(|config| config.as_ref().map(|cached| cached.fetched_at));
+        match fetched_at {
+            Some(fetched_at) => now - fetched_at >= self.ttl.as_secs() as i64,
+            None => true,
+        }
+    }
+
+    /// Whether a fetch is in flight
+    pub fn is_fetching(&self) -> bool {
+        self.fetching.load(Ordering::SeqCst)
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.read(|config| config.get_bool(key))
+    }
+
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.read(|config| config.get_i64(key))
+    }
+
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.read(|config| config.get_f64(key))
+    }
+
+    pub fn get_string(&self, key: &str) -> Option<String> {
+        self.read(|config| config.get_string(key))
+    }
+
+    pub fn get_json<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.read(|config| config.get_json(key))
+    }
+
+    /// Variant of an experiment the player is in, None when not enrolled
+    pub fn variant(&self, experiment_id: &str) -> Option<String> {
+        self.read(|config| config.variant(experiment_id).map(str::to_string))
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&RemoteConfig) -> Option<T>) -> Option<T> {
+        let config = self.config.lock().ok()?;
+        f(&config.as_ref()?.config)
+    }
+
+    /// Download the config and activate it
+    /// Returns true if values or experiment assignments changed
+    pub async fn fetch(&self) -> IdosResult<bool> {
+        self.fetching.store(true, Ordering::SeqCst);
+        let result = self.fetch_inner().await;
+        self.fetching.store(false, Ordering::SeqCst);
+        result
+    }
+
+    async fn fetch_inner(&self) -> IdosResult<bool> {
+        let user_id = self.user_id.clone();
+        let request = RemoteConfigRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: "GetRemoteConfig".to_string(),
+            user_id: user_id.clone(),
+            client_session_ticket: self.session_ticket.clone(),
+        };
+
+        let endpoint = "remoteconfig/GetRemoteConfig";
+        let config: RemoteConfig = self.client.post(endpoint, &request).await?;
+        let cached = CachedRemoteConfig {
+            config,
+            fetched_at: chrono::Utc::now().timestamp(),
+        };
+        if let Ok(json) = serde_json::to_string(&cached) {
+            self.storage.set(&self.cache_key(), &json).ok();
+        }
+
+        // The player may have logged out while fetching
+        if self.user_id != user_id {
+            return Ok(false);
+        }
+        Ok(self.activate(cached))
+    }
+
+    /// Fetch only if the config is stale
+    pub async fn refresh_if_stale(&self) -> IdosResult<bool> {
+        if !self.is_stale(chrono::Utc::now().timestamp()) {
+            return Ok(false);
+        }
+        self.fetch().await
+    }
+
+    /// Replace the active config, queueing `RemoteConfigUpdated` if anything changed
+    fn activate(&self, cached: CachedRemoteConfig) -> bool {
+        let Ok(mut current) = self.config.lock() else {
+            return false;
+        };
+        let previous = current
+            .as_ref()
+            .map(|c| c.config.clone())
+            .unwrap_or_default();
+        let changed_keys = cached.config.changed_keys(&previous);
+        let experiments_changed = cached.config.experiments != previous.experiments;
+        let version = cached.config.version.clone();
+        *current = Some(cached);
+
+        if changed_keys.is_empty() && !experiments_changed {
+            return false;
+        }
+        if let Ok(mut updated) = self.updated.lock() {
+            updated.push(RemoteConfigUpdated {
+                version,
+                changed_keys,
+                experiments_changed,
+            });
+        }
+        true
+    }
+
+    /// Take the updates since the last call
+    pub fn drain_updated(&self) -> Vec<RemoteConfigUpdated> {
+        self.updated
+            .lock()
+            .map(|mut updated| std::mem::take(&mut *updated))
+            .unwrap_or_default()
+    }
+}
+
+crate::session::session_aware!(RemoteConfigHandler);
@@ -0,0 +1,8 @@
+/// Remote config module: server-driven tuning values and A/B test assignments
+pub mod dto;
+pub mod handler;
+pub mod remote_config_plugin;
+
+pub use dto::*;
+pub use handler::RemoteConfigHandler;
+pub use remote_config_plugin::RemoteConfigPlugin;
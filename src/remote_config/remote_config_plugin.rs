@@ -0,0 +1,69 @@
+/// Remote Config Bevy plugin
+use super::dto::RemoteConfigUpdated;
+use super::handler::RemoteConfigHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+/// How often the cached config's age is checked against the TTL
+const STALE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+pub struct RemoteConfigPlugin;
+
+impl Plugin for RemoteConfigPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize remote config handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = RemoteConfigHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<RemoteConfigHandler>.in_set(SessionSync),
+        );
+
+        app.add_message::<RemoteConfigUpdated>()
+            .add_systems(Startup, refresh_stale_config)
+            .add_systems(
+                Update,
+                (
+                    refresh_stale_config.run_if(on_timer(STALE_CHECK_INTERVAL)),
+                    forward_config_updates,
+                ),
+            );
+    }
+}
+
+/// Refetch the config once it is older than the TTL (or after the player changed)
+fn refresh_stale_config(handler: Option<Res<RemoteConfigHandler>>) {
+    let Some(handler) = handler else {
+        return;
+    };
+    if handler.is_fetching() || !handler.is_stale(chrono::Utc::now().timestamp()) {
+        return;
+    }
+
+    let h = handler.clone();
+    crate::tasks::spawn(async move {
+        if let Err(e) = h.fetch().await {
+            warn!("Failed to fetch remote config: {}", e);
+        }
+    });
+}
+
+/// Forward config updates as messages
+fn forward_config_updates(
+    handler: Option<Res<RemoteConfigHandler>>,
+    mut updated: MessageWriter<RemoteConfigUpdated>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for update in handler.drain_updated() {
+        updated.write(update);
+    }
+}
@@ -0,0 +1,141 @@
+/// Shared login session
+/// AuthPlugin keeps it current; feature plugins copy it into their handlers so games don't
+/// have to call `set_auth` on each one after login
+use bevy::prelude::*;
+
+/// Credentials of the logged-in player
+/// Updated by AuthPlugin on login, refresh and logout; can also be set by games using their own auth
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuthSession {
+    pub user_id: Option<String>,
+    pub session_ticket: Option<String>,
+}
+
+impl AuthSession {
+    pub fn new(user_id: String, session_ticket: String) -> Self {
+        Self {
+            user_id: Some(user_id),
+            session_ticket: Some(session_ticket),
+        }
+    }
+
+    pub fn is_logged_in(&self) -> bool {
+        self.user_id.is_some() && self.session_ticket.is_some()
+    }
+}
+
+/// Systems that copy `AuthSession` into handlers
+/// Runs in `PreUpdate`, so handlers have the new credentials before `LoginSucceeded` is read
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionSync;
+
+/// Handler that sends the player's credentials with its requests
+pub trait SessionAware: Resource {
+    fn set_auth(&mut self, user_id: String, session_ticket: String);
+    fn clear_auth(&mut self);
+    /// Player the handler is set up for
+    fn auth_user_id(&self) -> Option<&str>;
+    /// Swap in a refreshed ticket without resetting per-player state
+    fn set_session_ticket(&mut self, session_ticket: String);
+}
+
+/// Implement `SessionAware` for a handler with `user_id`/`session_ticket` fields
+/// and inherent `set_auth`/`clear_auth` methods
+macro_rules! session_aware {
+    ($handler:ty) => {
+        impl $crate::session::SessionAware for $handler {
+            fn set_auth(&mut self, user_id: String, session_ticket: String) {
+                <$handler>::set_auth(self, user_id, session_ticket)
+            }
+
+            fn clear_auth(&mut self) {
+                <$handler>::clear_auth(self)
+            }
+
+            fn auth_user_id(&self) -> Option<&str> {
+                self.user_id.as_deref()
+            }
+
+            fn set_session_ticket(&mut self, session_ticket: String) {
+                self.session_ticket = Some(session_ticket);
+            }
+        }
+    };
+}
+pub(crate) use session_aware;
+
+/// Copy `AuthSession` into handler `H` when either changes
+/// Credentials set by hand with `set_auth` are kept until the first logout
+pub(crate) fn sync_session<H: SessionAware>(
+    session: Option<Res<AuthSession>>,
+    handler: Option<ResMut<H>>,
+) {
+    let (Some(session), Some(mut handler)) = (session, handler) else {
+        return;
+    };
+    if !session.is_changed() && !handler.is_added() {
+        return;
+    }
+
+    match (&session.user_id, &session.session_ticket) {
+        (Some(user_id), Some(session_ticket)) => {
+            if handler.auth_user_id() == Some(user_id.as_str()) {
+                handler.set_session_ticket(session_ticket.clone());
+            } else {
+                handler.set_auth(user_id.clone(), session_ticket.clone());
+            }
+        }
+        _ if !session.is_added() => handler.clear_auth(),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct TestHandler {
+        user_id: Option<String>,
+        session_ticket: Option<String>,
+        logins: u32,
+    }
+
+    impl TestHandler {
+        fn set_auth(&mut self, user_id: String, session_ticket: String) {
+            self.user_id = Some(user_id);
+            self.session_ticket = Some(session_ticket);
+            self.logins += 1;
+        }
+
+        fn clear_auth(&mut self) {
+            self.user_id = None;
+            self.session_ticket = None;
+        }
+    }
+
+    session_aware!(TestHandler);
+
+    #[test]
+    fn test_sync_session() {
+        let mut app = App::new();
+        app.init_resource::<AuthSession>()
+            .init_resource::<TestHandler>()
+            .add_systems(Update, sync_session::<TestHandler>);
+        app.update();
+
+        *app.world_mut().resource_mut::<AuthSession>() =
+            AuthSession::new("player".into(), "t1".into());
+        app.update();
+        app.world_mut().resource_mut::<AuthSession>().session_ticket = Some("t2".into());
+        app.update();
+
+        let handler = app.world().resource::<TestHandler>();
+        assert_eq!(handler.session_ticket.as_deref(), Some("t2"));
+        assert_eq!(handler.logins, 1);
+
+        *app.world_mut().resource_mut::<AuthSession>() = AuthSession::default();
+        app.update();
+        assert!(app.world().resource::<TestHandler>().user_id.is_none());
+    }
+}
@@ -1,28 +1,76 @@
 /// Platform-agnostic storage abstraction
-#[cfg(target_arch = "wasm32")]
 use crate::{IdosError, IdosResult};
 
-#[cfg(not(target_arch = "wasm32"))]
-use crate::IdosResult;
-
 #[cfg(target_arch = "wasm32")]
 use web_sys::window;
 
+use crate::IdosConfig;
+
 /// Storage interface that works on both native and WASM
 #[derive(Clone)]
 pub struct Storage {
     prefix: String,
+    /// Directory for file-backed storage on native (no-op when unset)
+    #[cfg(not(target_arch = "wasm32"))]
+    dir: Option<std::path::PathBuf>,
 }
 
 impl Storage {
     pub fn new(prefix: String) -> Self {
-        Self { prefix }
+        Self {
+            prefix,
+            #[cfg(not(target_arch = "wasm32"))]
+            dir: None,
+        }
+    }
+
+    /// File-backed storage: each key is a file in `dir`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_directory(prefix: String, dir: std::path::PathBuf) -> Self {
+        Self {
+            prefix,
+            dir: Some(dir),
+        }
+    }
+
+    /// Persistent cache storage for the SDK config
+    /// Uses `cache_dir` on native and localStorage (with `storage_prefix`) on WASM
+    pub fn cache(config: &IdosConfig, name: &str) -> Self {
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self::new(format!("{}{}", config.platform.wasm.storage_prefix, name))
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            match &config.platform.native.cache_dir {
+                Some(dir) => Self::with_directory(name.to_string(), dir.clone()),
+                None => Self::new(name.to_string()),
+            }
+        }
     }
 
     fn key(&self, key: &str) -> String {
         format!("{}{}", self.prefix, key)
     }
 
+    /// File path for a key, keeping file names portable
+    #[cfg(not(target_arch = "wasm32"))]
+    fn file_path(&self, full_key: &str) -> Option<std::path::PathBuf> {
+        let file_name: String = full_key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        self.dir.as_ref().map(|dir| dir.join(file_name))
+    }
+
     /// Store a value
     pub fn set(&self, key: &str, value: &str) -> IdosResult<()> {
         let full_key = self.key(key);
@@ -44,9 +92,15 @@ impl Storage {
 
         #[cfg(not(target_arch = "wasm32"))]
         {
-            // For native, we could use a file-based system or memory
-            // For now, just log it (in a real implementation, use a proper storage)
-            let _ = (full_key, value);
+            // Without a directory, native storage is a no-op
+            if let Some(path) = self.file_path(&full_key) {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .map_err(|e| IdosError::Unknown(format!("Storage error: {}", e)))?;
+                }
+                std::fs::write(path, value)
+                    .map_err(|e| IdosError::Unknown(format!("Storage error: {}", e)))?;
+            }
             Ok(())
         }
     }
@@ -71,9 +125,12 @@ impl Storage {
 
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let _ = full_key;
-            // For native, return None for now
-            Ok(None)
+            match self.file_path(&full_key) {
+                Some(path) if path.exists() => std::fs::read_to_string(path)
+                    .map(Some)
+                    .map_err(|e| IdosError::Unknown(format!("Storage error: {}", e))),
+                _ => Ok(None),
+            }
         }
     }
 
@@ -97,7 +154,12 @@ impl Storage {
 
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let _ = key;
+            if let Some(path) = self.file_path(&self.key(key)) {
+                if path.exists() {
+                    std::fs::remove_file(path)
+                        .map_err(|e| IdosError::Unknown(format!("Storage error: {}", e)))?;
+                }
+            }
             Ok(())
         }
     }
@@ -133,7 +195,52 @@ impl Storage {
 
         #[cfg(not(target_arch = "wasm32"))]
         {
+            if let Some(dir) = &self.dir {
+                let Ok(entries) = std::fs::read_dir(dir) else {
+                    return Ok(());
+                };
+
+                for entry in entries.flatten() {
+                    if entry
+                        .file_name()
+                        .to_string_lossy()
+                        .starts_with(&self.prefix)
+                    {
+                        std::fs::remove_file(entry.path()).ok();
+                    }
+                }
+            }
             Ok(())
         }
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_backed_storage() {
+        let dir = std::env::temp_dir().join(format!("idos_storage_test_{}", std::process::id()));
+        let storage = Storage::with_directory("test_".to_string(), dir.clone());
+
+        storage.set("key:1", "value").unwrap();
+        assert_eq!(storage.get("key:1").unwrap(), Some("value".to_string()));
+
+        storage.remove("key:1").unwrap();
+        assert_eq!(storage.get("key:1").unwrap(), None);
+
+        storage.set("a", "1").unwrap();
+        storage.clear().unwrap();
+        assert_eq!(storage.get("a").unwrap(), None);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_storage_without_directory_is_noop() {
+        let storage = Storage::new("test_".to_string());
+        storage.set("key", "value").unwrap();
+        assert_eq!(storage.get("key").unwrap(), None);
+    }
+}
@@ -0,0 +1,114 @@
+/// Data Transfer Objects for Support
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TicketCategory {
+    Bug,
+    Payment,
+    Account,
+    Gameplay,
+    Feedback,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TicketStatus {
+    Open,
+    /// Support replied and is waiting for the player
+    AwaitingPlayer,
+    Resolved,
+    Closed,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplyAuthor {
+    Player,
+    Support,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TicketReply {
+    pub author: ReplyAuthor,
+    pub message: String,
+    /// Unix timestamp
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SupportTicket {
+    #[serde(rename = "TicketID")]
+    pub ticket_id: String,
+    pub category: TicketCategory,
+    pub subject: String,
+    pub description: String,
+    pub status: TicketStatus,
+    /// Unix timestamp
+    pub created_at: i64,
+    /// Unix timestamp
+    pub updated_at: i64,
+    #[serde(default)]
+    pub replies: Vec<TicketReply>,
+}
+
+/// Device and session details attached to a ticket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SupportDiagnostics {
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    #[serde(rename = "GameID")]
+    pub game_id: String,
+    pub platform: String,
+    pub arch: String,
+    pub sdk_version: String,
+    /// Most recent log lines, oldest first
+    #[serde(default)]
+    pub recent_logs: Vec<String>,
+}
+
+/// Request to create a ticket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CreateTicketRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    pub category: TicketCategory,
+    pub subject: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<SupportDiagnostics>,
+}
+
+/// Request to read tickets or reply to one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TicketRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(rename = "TicketID", skip_serializing_if = "Option::is_none")]
+    pub ticket_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The player's tickets, newest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetTicketsResult {
+    #[serde(default)]
+    pub tickets: Vec<SupportTicket>,
+}
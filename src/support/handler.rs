@@ -0,0 +1,153 @@
+/// Support handler for tickets and replies
+use super::dto::*;
+use super::logs::RecentLogs;
+use crate::{IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+
+/// Log lines attached to a ticket
+const DIAGNOSTIC_LOG_LINES: usize = 100;
+
+#[derive(Resource, Clone)]
+pub struct SupportHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    logs: Option<RecentLogs>,
+}
+
+impl SupportHandler {
+    pub fn new(client: IdosClient, logs: Option<RecentLogs>) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            logs,
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    /// Player, device and recent log details for a ticket
+    /// Logs are only available when `support_log_layer` is installed
+    pub fn diagnostics(&self) -> IdosResult<SupportDiagnostics> {
+        Ok(SupportDiagnostics {
+            user_id: self.get_user_id()?,
+            game_id: self.client.game_id().to_string(),
+            platform: if cfg!(target_arch = "wasm32") {
+                "web".to_string()
+            } else {
+                std::env::consts::OS.to_string()
+            },
+            arch: std::env::consts::ARCH.to_string(),
+            sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+            recent_logs: self
+                .logs
+                .as_ref()
+                .map(|logs| logs.latest(DIAGNOSTIC_LOG_LINES))
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Open a ticket; `attach_diagnostics` adds `diagnostics()` to it
+    pub async fn create_ticket(
+        &self,
+        category: TicketCategory,
+        subject: &str,
+        description: &str,
+        attach_diagnostics: bool,
+    ) -> IdosResult<SupportTicket> {
+        if subject.trim().is_empty() || description.trim().is_empty() {
+            return Err(IdosError::InvalidInput(
+                "Subject and description are required".to_string(),
+            ));
+        }
+
+        let request = CreateTicketRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: "CreateTicket".to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            category,
+            subject: subject.trim().to_string(),
+            description: description.trim().to_string(),
+            diagnostics: if attach_diagnostics {
+                Some(self.diagnostics()?)
+            } else {
+                None
+            },
+        };
+
+        let endpoint = "support/CreateTicket";
+        self.client.post(endpoint, &request).await
+    }
+
+    /// The player's ticket history
+    pub async fn get_tickets(&self) -> IdosResult<Vec<SupportTicket>> {
+        let result: GetTicketsResult = self.post("GetTickets", None, None).await?;
+        Ok(result.tickets)
+    }
+
+    /// One ticket with its full reply thread
+    pub async fn get_ticket(&self, ticket_id: &str) -> IdosResult<SupportTicket> {
+        self.post("GetTicket", Some(ticket_id.to_string()), None)
+            .await
+    }
+
+    /// Add a player reply; returns the updated ticket
+    pub async fn reply(&self, ticket_id: &str, message: &str) -> IdosResult<SupportTicket> {
+        if message.trim().is_empty() {
+            return Err(IdosError::InvalidInput("Reply is empty".to_string()));
+        }
+        self.post(
+            "ReplyToTicket",
+            Some(ticket_id.to_string()),
+            Some(message.trim().to_string()),
+        )
+        .await
+    }
+
+    async fn post<R: serde::de::DeserializeOwned>(
+        &self,
+        function_name: &str,
+        ticket_id: Option<String>,
+        message: Option<String>,
+    ) -> IdosResult<R> {
+        let request = TicketRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            ticket_id,
+            message,
+        };
+
+        let endpoint = format!("support/{}", function_name);
+        self.client.post(&endpoint, &request).await
+    }
+}
+
+crate::session::session_aware!(SupportHandler);
@@ -0,0 +1,98 @@
+/// Recent log lines kept in memory for support tickets
+use bevy::log::tracing_subscriber::{layer::Context, registry::Registry, Layer};
+use bevy::log::BoxedLayer;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+const MAX_LINES: usize = 200;
+
+/// Ring buffer of the latest INFO/WARN/ERROR lines
+#[derive(Resource, Clone, Default)]
+pub struct RecentLogs {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl RecentLogs {
+    pub fn push(&self, line: String) {
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() == MAX_LINES {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+        }
+    }
+
+    /// Up to `count` latest lines, oldest first
+    pub fn latest(&self, count: usize) -> Vec<String> {
+        self.lines
+            .lock()
+            .map(|lines| {
+                let skip = lines.len().saturating_sub(count);
+                lines.iter().skip(skip).cloned().collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// `LogPlugin::custom_layer` that records log lines for support tickets
+/// ```ignore
+/// DefaultPlugins.set(LogPlugin { custom_layer: support_log_layer, ..default() })
+/// ```
+pub fn support_log_layer(app: &mut App) -> Option<BoxedLayer> {
+    let logs = RecentLogs::default();
+    app.insert_resource(logs.clone());
+    Some(Box::new(RecentLogsLayer { logs }))
+}
+
+struct RecentLogsLayer {
+    logs: RecentLogs,
+}
+
+impl Layer<Registry> for RecentLogsLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, Registry>) {
+        let metadata = event.metadata();
+        if *metadata.level() > tracing::Level::INFO {
+            return;
+        }
+
+        let mut message = MessageVisitor(String::new());
+        event.record(&mut message);
+        self.logs.push(format!(
+            "[{} {}] {}",
+            metadata.level(),
+            metadata.target(),
+            message.0
+        ));
+    }
+}
+
+struct MessageVisitor(String);
+
+impl tracing::field::Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{:?}", value);
+        } else {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_logs_ring_buffer() {
+        let logs = RecentLogs::default();
+        for i in 0..MAX_LINES + 5 {
+            logs.push(format!("line {}", i));
+        }
+
+        let latest = logs.latest(3);
+        assert_eq!(latest, vec!["line 202", "line 203", "line 204"]);
+        assert_eq!(logs.latest(usize::MAX).len(), MAX_LINES);
+        assert_eq!(logs.latest(usize::MAX)[0], "line 5");
+    }
+}
@@ -0,0 +1,10 @@
+/// Support module: in-game help center tickets with attached diagnostics
+pub mod dto;
+pub mod handler;
+pub mod logs;
+pub mod support_plugin;
+
+pub use dto::*;
+pub use handler::SupportHandler;
+pub use logs::{support_log_layer, RecentLogs};
+pub use support_plugin::SupportPlugin;
@@ -0,0 +1,25 @@
+/// Support Bevy plugin
+use super::handler::SupportHandler;
+use super::logs::RecentLogs;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+
+pub struct SupportPlugin;
+
+impl Plugin for SupportPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize support handler when client is available
+        // RecentLogs is present if LogPlugin was built with `support_log_layer`
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let logs = app.world().get_resource::<RecentLogs>().cloned();
+            let handler = SupportHandler::new(client.clone(), logs);
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<SupportHandler>.in_set(SessionSync),
+        );
+    }
+}
@@ -0,0 +1,215 @@
+/// Async task bridge
+/// Spawns SDK futures on the right executor (the browser event loop on WASM, tokio on
+/// native) and hands their results back to Bevy systems through a channel
+use bevy::prelude::*;
+use crossbeam_channel::{Receiver, Sender};
+use std::future::Future;
+
+/// `Send` on native, where futures may run on another thread; nothing on WASM
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send> MaybeSend for T {}
+
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSend for T {}
+
+/// Run a future in the background
+/// Native uses the current tokio runtime when there is one, otherwise the SDK's own
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + MaybeSend + 'static,
+{
+    #[cfg(target_arch = "wasm32")]
+    {
+        wasm_bindgen_futures::spawn_local(future);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(future);
+            }
+            Err(_) => {
+                runtime().spawn(future);
+            }
+        }
+    }
+}
+
+/// Sleep without blocking the executor (setTimeout on WASM, tokio natively)
+pub async fn sleep(duration: std::time::Duration) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let millis = duration.as_millis() as i32;
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            let window = web_sys::window().unwrap();
+            window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis)
+                .ok();
+        });
+        wasm_bindgen_futures::JsFuture::from(promise).await.ok();
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Runtime shared by SDK tasks started outside of tokio (e.g. from Bevy systems)
+#[cfg(not(target_arch = "wasm32"))]
+fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: std::sync::OnceLock<tokio::runtime::Runtime> = std::sync::OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("idos-sdk")
+            .enable_all()
+            .build()
+            .expect("Failed to start the SDK async runtime")
+    })
+}
+
+/// Work to run against the World once a task has finished
+type TaskCallback = Box<dyn FnOnce(&mut World) + Send>;
+
+/// Spawns async SDK calls from systems and delivers their results back into the World
+///
+/// ```ignore
+/// fn load_profile(tasks: Res<IdosTasks>, profiles: Res<ProfilesHandler>) {
+///     let profiles = profiles.clone();
+///     tasks.spawn_then(
+///         async move { profiles.get_profile("player").await },
+///         |result, world| {
+///             if let Ok(profile) = result {
+///                 world.insert_resource(ShownProfile(profile));
+///             }
+///         },
+///     );
+/// }
+/// ```
+#[derive(Resource, Clone)]
+pub struct IdosTasks {
+    callbacks: Sender<TaskCallback>,
+    finished: Receiver<TaskCallback>,
+}
+
+impl Default for IdosTasks {
+    fn default() -> Self {
+        let (callbacks, finished) = crossbeam_channel::unbounded();
+        Self {
+            callbacks,
+            finished,
+        }
+    }
+}
+
+impl IdosTasks {
+    /// Run a future in the background, ignoring its output
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + MaybeSend + 'static,
+    {
+        spawn(future);
+    }
+
+    /// Run a future, then call `then` with its output and the World
+    /// `then` runs on the main schedule (during `PreUpdate`), so it can touch any resource
+    pub fn spawn_then<T, F, C>(&self, future: F, then: C)
+    where
+        T: Send + 'static,
+        F: Future<Output = T> + MaybeSend + 'static,
+        C: FnOnce(T, &mut World) + Send + 'static,
+    {
+        let callbacks = self.callbacks.clone();
+        spawn(async move {
+            let output = future.await;
+            callbacks
+                .send(Box::new(move |world: &mut World| then(output, world)))
+                .ok();
+        });
+    }
+
+    /// Run a future and keep a handle to poll for its output from a system
+    pub fn spawn_result<T, F>(&self, future: F) -> IdosTask<T>
+    where
+        T: Send + 'static,
+        F: Future<Output = T> + MaybeSend + 'static,
+    {
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        spawn(async move {
+            sender.send(future.await).ok();
+        });
+        IdosTask { receiver }
+    }
+}
+
+/// Output of a task started with `IdosTasks::spawn_result`
+/// Can be kept in a component or resource and polled every frame
+#[derive(Component)]
+pub struct IdosTask<T> {
+    receiver: Receiver<T>,
+}
+
+impl<T: Send + 'static> IdosTask<T> {
+    /// The output, once the task has finished (returned only once)
+    pub fn poll(&self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// Runs the `spawn_then` callbacks of finished tasks
+pub(crate) fn apply_finished_tasks(world: &mut World) {
+    let Some(finished) = world
+        .get_resource::<IdosTasks>()
+        .map(|tasks| tasks.finished.clone())
+    else {
+        return;
+    };
+
+    for callback in finished.try_iter() {
+        callback(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource)]
+    struct Answer(u32);
+
+    #[test]
+    fn test_spawn_then_runs_in_world() {
+        let mut world = World::new();
+        let tasks = IdosTasks::default();
+        world.insert_resource(tasks.clone());
+
+        tasks.spawn_then(async { 42 }, |answer, world| {
+            world.insert_resource(Answer(answer));
+        });
+
+        let task = tasks.spawn_result(async { "done" });
+        let started = std::time::Instant::now();
+        while !world.contains_resource::<Answer>() {
+            assert!(started.elapsed() < std::time::Duration::from_secs(5));
+            apply_finished_tasks(&mut world);
+            std::thread::yield_now();
+        }
+
+        assert_eq!(world.resource::<Answer>().0, 42);
+        let started = std::time::Instant::now();
+        let output = loop {
+            if let Some(output) = task.poll() {
+                break output;
+            }
+            assert!(started.elapsed() < std::time::Duration::from_secs(5));
+            std::thread::yield_now();
+        };
+        assert_eq!(output, "done");
+    }
+}
@@ -0,0 +1,304 @@
+/// Data Transfer Objects for Tournaments
+use bevy::prelude::Message;
+use serde::{Deserialize, Serialize};
+
+/// Tournament lifecycle: Scheduled -> Registration -> InProgress -> Finished
+/// Any state before Finished can move to Cancelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TournamentState {
+    Scheduled,
+    Registration,
+    InProgress,
+    Finished,
+    Cancelled,
+    #[serde(other)]
+    Unknown,
+}
+
+impl TournamentState {
+    fn rank(self) -> u8 {
+        match self {
+            TournamentState::Scheduled => 0,
+            TournamentState::Registration => 1,
+            TournamentState::InProgress => 2,
+            TournamentState::Finished | TournamentState::Cancelled => 3,
+            TournamentState::Unknown => u8::MAX,
+        }
+    }
+
+    /// Whether the lifecycle allows moving from `self` to `next`
+    /// States can be skipped (a client may miss Registration entirely) but never go back
+    pub fn can_transition_to(self, next: TournamentState) -> bool {
+        match (self, next) {
+            (TournamentState::Unknown, _) | (_, TournamentState::Unknown) => false,
+            (TournamentState::Finished | TournamentState::Cancelled, _) => false,
+            (_, next) => next.rank() > self.rank(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TournamentReward {
+    #[serde(rename = "ItemID")]
+    pub item_id: String,
+    pub amount: u32,
+}
+
+/// Rewards for finishing between `min_place` and `max_place` (inclusive, 1 = winner)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlacementReward {
+    pub min_place: u32,
+    pub max_place: u32,
+    pub rewards: Vec<TournamentReward>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Tournament {
+    #[serde(rename = "TournamentID")]
+    pub tournament_id: String,
+    pub name: String,
+    pub state: TournamentState,
+    /// Unix timestamp
+    pub registration_opens_at: i64,
+    /// Unix timestamp
+    pub starts_at: i64,
+    /// 1-based; 0 before the first round
+    #[serde(default)]
+    pub current_round: u32,
+    pub total_rounds: u32,
+    pub max_players: u32,
+    #[serde(default)]
+    pub registered_players: u32,
+    #[serde(default)]
+    pub is_registered: bool,
+    /// The player's final place once Finished
+    #[serde(default)]
+    pub placement: Option<u32>,
+    #[serde(default)]
+    pub rewards_claimed: bool,
+    #[serde(default)]
+    pub placement_rewards: Vec<PlacementReward>,
+}
+
+impl Tournament {
+    /// Rewards for the player's placement, if any
+    pub fn rewards_for_placement(&self) -> Option<&[TournamentReward]> {
+        let place = self.placement?;
+        self.placement_rewards
+            .iter()
+            .find(|r| (r.min_place..=r.max_place).contains(&place))
+            .map(|r| r.rewards.as_slice())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchStatus {
+    /// Waiting for the previous round to decide the players
+    Pending,
+    /// Both players known; the match can be played
+    Ready,
+    InProgress,
+    Completed,
+    /// No opponent; the present player advances
+    Bye,
+    #[serde(other)]
+    Unknown,
+}
+
+/// One pairing in the bracket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TournamentMatch {
+    #[serde(rename = "MatchID")]
+    pub match_id: String,
+    pub round: u32,
+    #[serde(rename = "PlayerAID", default)]
+    pub player_a: Option<String>,
+    #[serde(rename = "PlayerBID", default)]
+    pub player_b: Option<String>,
+    #[serde(rename = "WinnerID", default)]
+    pub winner: Option<String>,
+    #[serde(default)]
+    pub score: Option<String>,
+    pub status: MatchStatus,
+}
+
+impl TournamentMatch {
+    pub fn involves(&self, user_id: &str) -> bool {
+        self.player_a.as_deref() == Some(user_id) || self.player_b.as_deref() == Some(user_id)
+    }
+
+    pub fn opponent_of(&self, user_id: &str) -> Option<&str> {
+        if self.player_a.as_deref() == Some(user_id) {
+            self.player_b.as_deref()
+        } else if self.player_b.as_deref() == Some(user_id) {
+            self.player_a.as_deref()
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Bracket {
+    #[serde(rename = "TournamentID")]
+    pub tournament_id: String,
+    #[serde(default)]
+    pub matches: Vec<TournamentMatch>,
+}
+
+impl Bracket {
+    pub fn round(&self, round: u32) -> impl Iterator<Item = &TournamentMatch> {
+        self.matches.iter().filter(move |m| m.round == round)
+    }
+
+    /// The player's latest match
+    pub fn match_for(&self, user_id: &str) -> Option<&TournamentMatch> {
+        self.matches
+            .iter()
+            .filter(|m| m.involves(user_id))
+            .max_by_key(|m| m.round)
+    }
+}
+
+/// Request to the tournament endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TournamentsRequest {
+    #[serde(rename = "TitleID")]
+    pub title_id: String,
+    pub build_key: String,
+    pub function_name: String,
+    #[serde(rename = "UserID")]
+    pub user_id: String,
+    pub client_session_ticket: String,
+    #[serde(rename = "TournamentID", skip_serializing_if = "Option::is_none")]
+    pub tournament_id: Option<String>,
+    #[serde(rename = "MatchID", skip_serializing_if = "Option::is_none")]
+    pub match_id: Option<String>,
+    #[serde(rename = "WinnerID", skip_serializing_if = "Option::is_none")]
+    pub winner_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetTournamentsResult {
+    #[serde(default)]
+    pub tournaments: Vec<Tournament>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ClaimPlacementRewardResult {
+    pub placement: u32,
+    #[serde(default)]
+    pub rewards: Vec<TournamentReward>,
+}
+
+/// A tournament moved to a new lifecycle state
+#[derive(Message, Debug, Clone)]
+pub struct TournamentStateChanged {
+    pub tournament_id: String,
+    pub from: TournamentState,
+    pub to: TournamentState,
+}
+
+/// A new round began
+#[derive(Message, Debug, Clone)]
+pub struct TournamentRoundStarted {
+    pub tournament_id: String,
+    pub round: u32,
+}
+
+/// The player's next match has both players and can be played
+#[derive(Message, Debug, Clone)]
+pub struct TournamentMatchReady {
+    pub tournament_id: String,
+    pub match_id: String,
+    pub round: u32,
+    /// `None` for a bye
+    pub opponent_id: Option<String>,
+}
+
+/// Event queued by `TournamentsHandler` until TournamentsPlugin writes it as a message
+#[derive(Debug, Clone)]
+pub enum TournamentEvent {
+    StateChanged(TournamentStateChanged),
+    RoundStarted(TournamentRoundStarted),
+    MatchReady(TournamentMatchReady),
+}
+
+/// Events for going from `old` to `new` (same tournament)
+pub fn tournament_transitions(old: &Tournament, new: &Tournament) -> Vec<TournamentEvent> {
+    let mut events = Vec::new();
+    if old.state != new.state && old.state.can_transition_to(new.state) {
+        events.push(TournamentEvent::StateChanged(TournamentStateChanged {
+            tournament_id: new.tournament_id.clone(),
+            from: old.state,
+            to: new.state,
+        }));
+    }
+    if new.state == TournamentState::InProgress {
+        for round in old.current_round + 1..=new.current_round {
+            events.push(TournamentEvent::RoundStarted(TournamentRoundStarted {
+                tournament_id: new.tournament_id.clone(),
+                round,
+            }));
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tournament(state: TournamentState, current_round: u32) -> Tournament {
+        Tournament {
+            tournament_id: "cup".to_string(),
+            name: "Weekend Cup".to_string(),
+            state,
+            registration_opens_at: 0,
+            starts_at: 0,
+            current_round,
+            total_rounds: 3,
+            max_players: 8,
+            registered_players: 8,
+            is_registered: true,
+            placement: None,
+            rewards_claimed: false,
+            placement_rewards: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_tournament_transitions() {
+        use TournamentState::*;
+
+        assert!(Scheduled.can_transition_to(InProgress));
+        assert!(Registration.can_transition_to(Cancelled));
+        assert!(!InProgress.can_transition_to(Registration));
+        assert!(!Finished.can_transition_to(Cancelled));
+
+        let events =
+            tournament_transitions(&tournament(Registration, 0), &tournament(InProgress, 2));
+        assert!(matches!(
+            &events[0],
+            TournamentEvent::StateChanged(e) if e.from == Registration && e.to == InProgress
+        ));
+        assert!(matches!(&events[1], TournamentEvent::RoundStarted(e) if e.round == 1));
+        assert!(matches!(&events[2], TournamentEvent::RoundStarted(e) if e.round == 2));
+
+        // Stale data never moves the state backwards
+        assert!(
+            tournament_transitions(&tournament(InProgress, 2), &tournament(Registration, 0))
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,235 @@
+/// Tournaments handler: registration, brackets, match results and placement rewards
+/// Fetches are compared with the previous state to emit round and state transitions
+use super::dto::*;
+use crate::{IdosClient, IdosError, IdosResult};
+use bevy::prelude::Resource;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+#[derive(Resource, Clone)]
+pub struct TournamentsHandler {
+    client: IdosClient,
+    user_id: Option<String>,
+    session_ticket: Option<String>,
+    tournaments: Arc<Mutex<HashMap<String, Tournament>>>,
+    /// Matches already announced with `TournamentMatchReady`
+    announced_matches: Arc<Mutex<HashSet<String>>>,
+    /// Events waiting for TournamentsPlugin
+    events: Arc<Mutex<Vec<TournamentEvent>>>,
+}
+
+impl TournamentsHandler {
+    pub fn new(client: IdosClient) -> Self {
+        Self {
+            client,
+            user_id: None,
+            session_ticket: None,
+            tournaments: Arc::new(Mutex::new(HashMap::new())),
+            announced_matches: Arc::new(Mutex::new(HashSet::new())),
+            events: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Set user authentication info (call after login)
+    pub fn set_auth(&mut self, user_id: String, session_ticket: String) {
+        self.user_id = Some(user_id);
+        self.session_ticket = Some(session_ticket);
+    }
+
+    /// Clear authentication info (call on logout)
+    pub fn clear_auth(&mut self) {
+        self.user_id = None;
+        self.session_ticket = None;
+        if let Ok(mut tournaments) = self.tournaments.lock() {
+            tournaments.clear();
+        }
+        if let Ok(mut announced) = self.announced_matches.lock() {
+            announced.clear();
+        }
+    }
+
+    fn get_user_id(&self) -> IdosResult<String> {
+        self.user_id
+            .clone()
+            .ok_or_else(|| IdosError::Auth("User not logged in".to_string()))
+    }
+
+    fn get_session_ticket(&self) -> IdosResult<String> {
+        self.session_ticket
+            .clone()
+            .ok_or_else(|| IdosError::Auth("No session ticket available".to_string()))
+    }
+
+    fn request(
+        &self,
+        function_name: &str,
+        tournament_id: Option<&str>,
+    ) -> IdosResult<TournamentsRequest> {
+        Ok(TournamentsRequest {
+            title_id: self.client.game_id().to_string(),
+            build_key: String::new(),
+            function_name: function_name.to_string(),
+            user_id: self.get_user_id()?,
+            client_session_ticket: self.get_session_ticket()?,
+            tournament_id: tournament_id.map(str::to_string),
+            match_id: None,
+            winner_id: None,
+            score: None,
+        })
+    }
+
+    /// Upcoming and running tournaments
+    pub async fn list_tournaments(&self) -> IdosResult<Vec<Tournament>> {
+        let request = self.request("GetTournaments", None)?;
+        let endpoint = "tournaments/GetTournaments";
+        let result: GetTournamentsResult = self.client.post(endpoint, &request).await?;
+
+        for tournament in &result.tournaments {
+            self.update(tournament.clone());
+        }
+        Ok(result.tournaments)
+    }
+
+    pub async fn get_tournament(&self, tournament_id: &str) -> IdosResult<Tournament> {
+        let request = self.request("GetTournament", Some(tournament_id))?;
+        let endpoint = "tournaments/GetTournament";
+        let tournament: Tournament = self.client.post(endpoint, &request).await?;
+
+        self.update(tournament.clone());
+        Ok(tournament)
+    }
+
+    /// Last fetched state of a tournament
+    pub fn cached_tournament(&self, tournament_id: &str) -> Option<Tournament> {
+        self.tournaments
+            .lock()
+            .ok()
+            .and_then(|tournaments| tournaments.get(tournament_id).cloned())
+    }
+
+    /// Store a fetched tournament and queue its transitions
+    /// Data older than what we have (state going backwards) is ignored
+    fn update(&self, tournament: Tournament) {
+        let Ok(mut tournaments) = self.tournaments.lock() else {
+            return;
+        };
+
+        if let Some(old) = tournaments.get(&tournament.tournament_id) {
+            if old.state != tournament.state && !old.state.can_transition_to(tournament.state) {
+                return;
+            }
+            for event in tournament_transitions(old, &tournament) {
+                self.push_event(event);
+            }
+        }
+        tournaments.insert(tournament.tournament_id.clone(), tournament);
+    }
+
+    pub async fn register(&self, tournament_id: &str) -> IdosResult<Tournament> {
+        let request = self.request("Register", Some(tournament_id))?;
+        let endpoint = "tournaments/Register";
+        let tournament: Tournament = self.client.post(endpoint, &request).await?;
+
+        self.update(tournament.clone());
+        Ok(tournament)
+    }
+
+    /// Withdraw before the tournament starts
+    pub async fn unregister(&self, tournament_id: &str) -> IdosResult<Tournament> {
+        let request = self.request("Unregister", Some(tournament_id))?;
+        let endpoint = "tournaments/Unregister";
+        let tournament: Tournament = self.client.post(endpoint, &request).await?;
+
+        self.update(tournament.clone());
+        Ok(tournament)
+    }
+
+    /// Bracket with every pairing so far
+    /// Emits `TournamentMatchReady` the first time the player's match becomes playable
+    pub async fn get_bracket(&self, tournament_id: &str) -> IdosResult<Bracket> {
+        let request = self.request("GetBracket", Some(tournament_id))?;
+        let endpoint = "tournaments/GetBracket";
+        let bracket: Bracket = self.client.post(endpoint, &request).await?;
+
+        let user_id = self.get_user_id()?;
+        if let Some(next) = bracket.match_for(&user_id) {
+            let ready = matches!(next.status, MatchStatus::Ready | MatchStatus::Bye);
+            let is_new = ready
+                && self
+                    .announced_matches
+                    .lock()
+                    .map(|mut announced| announced.insert(next.match_id.clone()))
+                    .unwrap_or(false);
+            if is_new {
+                self.push_event(TournamentEvent::MatchReady(TournamentMatchReady {
+                    tournament_id: tournament_id.to_string(),
+                    match_id: next.match_id.clone(),
+                    round: next.round,
+                    opponent_id: next.opponent_of(&user_id).map(str::to_string),
+                }));
+            }
+        }
+
+        Ok(bracket)
+    }
+
+    /// Report the outcome of one of the player's matches
+    pub async fn report_match_result(
+        &self,
+        tournament_id: &str,
+        match_id: &str,
+        winner_id: &str,
+        score: Option<&str>,
+    ) -> IdosResult<TournamentMatch> {
+        let request = TournamentsRequest {
+            match_id: Some(match_id.to_string()),
+            winner_id: Some(winner_id.to_string()),
+            score: score.map(str::to_string),
+            ..self.request("ReportMatchResult", Some(tournament_id))?
+        };
+        let endpoint = "tournaments/ReportMatchResult";
+        self.client.post(endpoint, &request).await
+    }
+
+    /// Claim the reward for the player's final placement
+    pub async fn claim_placement_reward(
+        &self,
+        tournament_id: &str,
+    ) -> IdosResult<ClaimPlacementRewardResult> {
+        if let Some(tournament) = self.cached_tournament(tournament_id) {
+            if tournament.state != TournamentState::Finished {
+                return Err(IdosError::InvalidInput(format!(
+                    "Tournament {} hasn't finished",
+                    tournament_id
+                )));
+            }
+        }
+
+        let request = self.request("ClaimPlacementReward", Some(tournament_id))?;
+        let endpoint = "tournaments/ClaimPlacementReward";
+        let result: ClaimPlacementRewardResult = self.client.post(endpoint, &request).await?;
+
+        if let Ok(mut tournaments) = self.tournaments.lock() {
+            if let Some(tournament) = tournaments.get_mut(tournament_id) {
+                tournament.rewards_claimed = true;
+            }
+        }
+        Ok(result)
+    }
+
+    fn push_event(&self, event: TournamentEvent) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
+    }
+
+    /// Take the events queued since the last call
+    pub fn drain_events(&self) -> Vec<TournamentEvent> {
+        self.events
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default()
+    }
+}
+
+crate::session::session_aware!(TournamentsHandler);
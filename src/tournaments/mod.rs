@@ -0,0 +1,9 @@
+/// Tournaments module: scheduled bracket tournaments with registration, pairings and placement rewards
+/// Unlike leaderboard tournaments, players are paired into matches round by round
+pub mod dto;
+pub mod handler;
+pub mod tournaments_plugin;
+
+pub use dto::*;
+pub use handler::TournamentsHandler;
+pub use tournaments_plugin::TournamentsPlugin;
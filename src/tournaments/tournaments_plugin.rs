@@ -0,0 +1,56 @@
+/// Tournaments Bevy plugin
+use super::dto::{
+    TournamentEvent, TournamentMatchReady, TournamentRoundStarted, TournamentStateChanged,
+};
+use super::handler::TournamentsHandler;
+use crate::session::{sync_session, SessionSync};
+use crate::IdosClient;
+use bevy::prelude::*;
+
+pub struct TournamentsPlugin;
+
+impl Plugin for TournamentsPlugin {
+    fn build(&self, app: &mut App) {
+        // Initialize tournaments handler when client is available
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let handler = TournamentsHandler::new(client.clone());
+            app.insert_resource(handler);
+        }
+
+        app.add_systems(
+            PreUpdate,
+            sync_session::<TournamentsHandler>.in_set(SessionSync),
+        );
+
+        app.add_message::<TournamentStateChanged>()
+            .add_message::<TournamentRoundStarted>()
+            .add_message::<TournamentMatchReady>()
+            .add_systems(Update, forward_tournament_events);
+    }
+}
+
+/// Forward tournament events as messages
+fn forward_tournament_events(
+    handler: Option<Res<TournamentsHandler>>,
+    mut state_changed: MessageWriter<TournamentStateChanged>,
+    mut round_started: MessageWriter<TournamentRoundStarted>,
+    mut match_ready: MessageWriter<TournamentMatchReady>,
+) {
+    let Some(handler) = handler else {
+        return;
+    };
+
+    for event in handler.drain_events() {
+        match event {
+            TournamentEvent::StateChanged(event) => {
+                state_changed.write(event);
+            }
+            TournamentEvent::RoundStarted(event) => {
+                round_started.write(event);
+            }
+            TournamentEvent::MatchReady(event) => {
+                match_ready.write(event);
+            }
+        }
+    }
+}
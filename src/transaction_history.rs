@@ -0,0 +1,283 @@
+/// Transactions sent by the SDK on every chain, with their confirmation status
+/// Persisted to Storage so EthereumPlugin and SolanaPlugin resume polling them after a restart
+use crate::{storage::Storage, IdosClient, IdosError, IdosResult};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+const HISTORY_KEY: &str = "all";
+
+/// Confirmed and failed transactions kept; the oldest are dropped first
+const MAX_FINISHED_TRANSACTIONS: usize = 100;
+
+/// Chain a transaction was sent on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionChain {
+    /// EVM chain, by chain ID
+    Ethereum(i64),
+    Solana,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionStatus {
+    Pending,
+    Confirmed,
+    /// Reverted, or never landed
+    Failed,
+}
+
+/// Transaction hash (Ethereum) or signature (Solana) tracked by `TransactionHistory`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedTransaction {
+    pub id: String,
+    pub chain: TransactionChain,
+    /// What the SDK sent it for ("deposit", "withdrawal", "transfer", ...)
+    pub label: Option<String>,
+    pub status: TransactionStatus,
+    /// Why it failed
+    pub error: Option<String>,
+    /// Unix timestamp (seconds) when the transaction was sent
+    pub sent_at: i64,
+    /// Unix timestamp (seconds) of the last status change
+    pub updated_at: i64,
+}
+
+/// A tracked transaction was sent, confirmed or failed
+#[derive(Message, Debug, Clone)]
+pub struct TransactionStatusChanged {
+    pub transaction: TrackedTransaction,
+    /// None when the transaction was just sent
+    pub previous: Option<TransactionStatus>,
+}
+
+/// History of transactions sent by the SDK, shared by the Ethereum and Solana handlers
+#[derive(Resource, Clone)]
+pub struct TransactionHistory {
+    storage: Storage,
+    transactions: Arc<Mutex<Vec<TrackedTransaction>>>,
+    /// Status changes waiting for TransactionHistoryPlugin
+    changes: Arc<Mutex<Vec<TransactionStatusChanged>>>,
+}
+
+impl TransactionHistory {
+    /// Load the history saved in `storage`
+    pub fn new(storage: Storage) -> Self {
+        let transactions = storage
+            .get(HISTORY_KEY)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+
+        Self {
+            storage,
+            transactions: Arc::new(Mutex::new(transactions)),
+            changes: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Track a transaction that was just sent
+    pub fn record(&self, id: &str, chain: TransactionChain, label: Option<&str>) -> IdosResult<()> {
+        let now = chrono::Utc::now().timestamp();
+        let transaction = TrackedTransaction {
+            id: id.to_string(),
+            chain,
+            label: label.map(str::to_string),
+            status: TransactionStatus::Pending,
+            error: None,
+            sent_at: now,
+            updated_at: now,
+        };
+
+        let mut transactions = self.transactions.lock().unwrap_or_else(|e| e.into_inner());
+        transactions.retain(|t| t.id != id);
+        transactions.push(transaction.clone());
+        prune(&mut transactions);
+        self.push_change(transaction, None);
+        self.save(&transactions)
+    }
+
+    /// Move a tracked transaction to `status`; unknown transactions are ignored
+    pub fn set_status(
+        &self,
+        id: &str,
+        status: TransactionStatus,
+        error: Option<String>,
+    ) -> IdosResult<()> {
+        let mut transactions = self.transactions.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(transaction) = transactions.iter_mut().find(|t| t.id == id) else {
+            return Ok(());
+        };
+        if transaction.status == status {
+            return Ok(());
+        }
+
+        let previous = transaction.status;
+        transaction.status = status;
+        transaction.error = error;
+        transaction.updated_at = chrono::Utc::now().timestamp();
+        let transaction = transaction.clone();
+
+        prune(&mut transactions);
+        self.push_change(transaction, Some(previous));
+        self.save(&transactions)
+    }
+
+    pub fn get(&self, id: &str) -> Option<TrackedTransaction> {
+        self.transactions
+            .lock()
+            .ok()
+            .and_then(|transactions| transactions.iter().find(|t| t.id == id).cloned())
+    }
+
+    /// Every tracked transaction, newest first
+    pub fn transactions(&self) -> Vec<TrackedTransaction> {
+        self.transactions
+            .lock()
+            .map(|transactions| transactions.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Transactions on `chain` that haven't confirmed or failed yet, oldest first
+    pub fn pending(&self, chain: TransactionChain) -> Vec<TrackedTransaction> {
+        self.transactions
+            .lock()
+            .map(|transactions| {
+                transactions
+                    .iter()
+                    .filter(|t| t.chain == chain && t.status == TransactionStatus::Pending)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Forget confirmed and failed transactions
+    pub fn clear_finished(&self) -> IdosResult<()> {
+        let mut transactions = self.transactions.lock().unwrap_or_else(|e| e.into_inner());
+        transactions.retain(|t| t.status == TransactionStatus::Pending);
+        self.save(&transactions)
+    }
+
+    /// Take the status changes recorded since the last call
+    pub fn drain_changes(&self) -> Vec<TransactionStatusChanged> {
+        self.changes
+            .lock()
+            .map(|mut changes| std::mem::take(&mut *changes))
+            .unwrap_or_default()
+    }
+
+    fn push_change(&self, transaction: TrackedTransaction, previous: Option<TransactionStatus>) {
+        if let Ok(mut changes) = self.changes.lock() {
+            changes.push(TransactionStatusChanged {
+                transaction,
+                previous,
+            });
+        }
+    }
+
+    fn save(&self, transactions: &[TrackedTransaction]) -> IdosResult<()> {
+        if transactions.is_empty() {
+            return self.storage.remove(HISTORY_KEY);
+        }
+
+        let json = serde_json::to_string(transactions)
+            .map_err(|e| IdosError::SerializationError(e.to_string()))?;
+        self.storage.set(HISTORY_KEY, &json)
+    }
+}
+
+/// Drop the oldest finished transactions past `MAX_FINISHED_TRANSACTIONS`
+fn prune(transactions: &mut Vec<TrackedTransaction>) {
+    let finished = transactions
+        .iter()
+        .filter(|t| t.status != TransactionStatus::Pending)
+        .count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_TRANSACTIONS);
+
+    transactions.retain(|t| {
+        if excess > 0 && t.status != TransactionStatus::Pending {
+            excess -= 1;
+            return false;
+        }
+        true
+    });
+}
+
+/// Inserts the shared `TransactionHistory` and writes `TransactionStatusChanged` messages
+/// Added by EthereumPlugin and SolanaPlugin
+pub struct TransactionHistoryPlugin;
+
+impl Plugin for TransactionHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(client) = app.world().get_resource::<IdosClient>() {
+            let storage = Storage::cache(client.config(), "tx_history_");
+            app.insert_resource(TransactionHistory::new(storage));
+        }
+
+        app.add_message::<TransactionStatusChanged>()
+            .add_systems(Update, forward_status_changes);
+    }
+}
+
+/// Add TransactionHistoryPlugin unless another chain plugin already did,
+/// and return the shared history
+pub(crate) fn shared_history(app: &mut App) -> Option<TransactionHistory> {
+    if !app.is_plugin_added::<TransactionHistoryPlugin>() {
+        app.add_plugins(TransactionHistoryPlugin);
+    }
+    app.world().get_resource::<TransactionHistory>().cloned()
+}
+
+/// Forward status changes as messages
+fn forward_status_changes(
+    history: Option<Res<TransactionHistory>>,
+    mut changed: MessageWriter<TransactionStatusChanged>,
+) {
+    let Some(history) = history else {
+        return;
+    };
+
+    for change in history.drain_changes() {
+        changed.write(change);
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_round_trip() {
+        let dir = std::env::temp_dir().join(format!("idos_tx_history_test_{}", std::process::id()));
+        let history =
+            TransactionHistory::new(Storage::with_directory("tx_".to_string(), dir.clone()));
+
+        history
+            .record("0xab", TransactionChain::Ethereum(1), Some("deposit"))
+            .unwrap();
+        history
+            .record("5sig", TransactionChain::Solana, None)
+            .unwrap();
+        history
+            .set_status("0xab", TransactionStatus::Confirmed, None)
+            .unwrap();
+
+        let changes = history.drain_changes();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[2].previous, Some(TransactionStatus::Pending));
+        assert_eq!(changes[2].transaction.status, TransactionStatus::Confirmed);
+
+        // A restart sees the same history and keeps polling the pending signature
+        let reopened =
+            TransactionHistory::new(Storage::with_directory("tx_".to_string(), dir.clone()));
+        assert_eq!(reopened.transactions().len(), 2);
+        assert!(reopened.pending(TransactionChain::Ethereum(1)).is_empty());
+        assert_eq!(reopened.pending(TransactionChain::Solana)[0].id, "5sig");
+
+        reopened.clear_finished().unwrap();
+        assert!(reopened.get("0xab").is_none());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
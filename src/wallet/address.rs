@@ -0,0 +1,107 @@
+/// Ethereum address derivation and EIP-55 checksums
+use crate::{IdosError, IdosResult};
+use sha3::{Digest, Keccak256};
+
+/// Address of an uncompressed secp256k1 public key (64 bytes, without the 0x04 prefix)
+/// Last 20 bytes of keccak256(public_key), EIP-55 checksummed
+pub fn ethereum_address_from_public_key(public_key: &[u8]) -> String {
+    let hash = Keccak256::digest(public_key);
+    checksum_hex(&hex::encode(&hash[12..]))
+}
+
+/// EIP-55 checksummed form of an address (with or without 0x, any case)
+pub fn to_checksum_address(address: &str) -> IdosResult<String> {
+    let hex_part = strip_prefix(address);
+    if hex_part.len() != 40 || !hex_part.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(IdosError::InvalidInput(format!(
+            "Invalid Ethereum address: {}",
+            address
+        )));
+    }
+
+    Ok(checksum_hex(&hex_part.to_ascii_lowercase()))
+}
+
+/// Whether `address` is a well-formed Ethereum address
+/// All-lowercase and all-uppercase addresses carry no checksum; mixed case must match EIP-55
+pub fn is_valid_ethereum_address(address: &str) -> bool {
+    let Ok(checksummed) = to_checksum_address(address) else {
+        return false;
+    };
+
+    let hex_part = strip_prefix(address);
+    let has_lower = hex_part.bytes().any(|b| b.is_ascii_lowercase());
+    let has_upper = hex_part.bytes().any(|b| b.is_ascii_uppercase());
+    !(has_lower && has_upper) || checksummed[2..] == *hex_part
+}
+
+fn strip_prefix(address: &str) -> &str {
+    address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+        .unwrap_or(address)
+}
+
+/// Uppercase each letter whose nibble in keccak256(lowercase hex) is 8 or more
+fn checksum_hex(lower_hex: &str) -> String {
+    let hash = Keccak256::digest(lower_hex.as_bytes());
+
+    let mut checksummed = String::with_capacity(42);
+    checksummed.push_str("0x");
+    for (i, c) in lower_hex.chars().enumerate() {
+        let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+        if nibble >= 8 {
+            checksummed.push(c.to_ascii_uppercase());
+        } else {
+            checksummed.push(c);
+        }
+    }
+    checksummed
+}
+
+#[cfg(all(test, feature = "wallet"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_from_public_key() {
+        // Private key 0x4c0883a6...3f362318 from the web3.js account docs
+        let public_key = hex::decode(
+            "4e3b81af9c2234cad09d679ce6035ed1392347ce64ce405f5dcd36228a25de6e\
+             47fd35c4215d1edf53e6f83de344615ce719bdb0fd878f6ed76f06dd277956de",
+        )
+        .unwrap();
+
+        assert_eq!(
+            ethereum_address_from_public_key(&public_key),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+    }
+
+    #[test]
+    fn test_eip55_checksums() {
+        // Vectors from EIP-55
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            assert_eq!(
+                to_checksum_address(&address.to_lowercase()).unwrap(),
+                address
+            );
+            assert!(is_valid_ethereum_address(address));
+        }
+
+        assert!(is_valid_ethereum_address(
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+        ));
+        // One letter with the wrong case
+        assert!(!is_valid_ethereum_address(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"
+        ));
+        assert!(!is_valid_ethereum_address("0x5aAeb6053F3E94C9b9A09f3366"));
+        assert!(to_checksum_address("0xzzzzb6053f3e94c9b9a09f33669435e7ef1beaed").is_err());
+    }
+}
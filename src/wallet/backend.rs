@@ -0,0 +1,61 @@
+/// Key sources behind `WalletManager`
+/// A wallet's keys either live in the encrypted keystore or on a hardware device
+use super::dto::*;
+use super::signing;
+use crate::IdosResult;
+use async_trait::async_trait;
+
+/// Where a wallet's keys live and how it signs
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait WalletBackend: Send + Sync {
+    /// Wallet address (EIP-55 for Ethereum, base58 for Solana)
+    fn address(&self) -> String;
+
+    fn network(&self) -> BlockchainNetwork;
+
+    /// Whether signing happens on an external device (and may need player confirmation)
+    fn is_hardware(&self) -> bool {
+        false
+    }
+
+    /// Sign an arbitrary message (EIP-191 for Ethereum, Ed25519 for Solana)
+    async fn sign_message(&self, message: &[u8]) -> IdosResult<MessageSignature>;
+}
+
+/// Unlocked in-game wallet holding its keys in memory
+#[derive(Clone)]
+pub struct LocalWalletBackend {
+    wallet: WalletInfo,
+}
+
+impl LocalWalletBackend {
+    pub fn new(wallet: WalletInfo) -> Self {
+        Self { wallet }
+    }
+}
+
+impl std::fmt::Debug for LocalWalletBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalWalletBackend")
+            .field("address", &self.wallet.address)
+            .field("network", &self.wallet.network)
+            .finish()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl WalletBackend for LocalWalletBackend {
+    fn address(&self) -> String {
+        self.wallet.address.clone()
+    }
+
+    fn network(&self) -> BlockchainNetwork {
+        self.wallet.network
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> IdosResult<MessageSignature> {
+        signing::sign_message(&self.wallet, message)
+    }
+}
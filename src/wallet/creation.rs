@@ -1,5 +1,6 @@
 /// Wallet creation with BIP39 mnemonics and BIP44 key derivation
 /// Matches Unity SDK's WalletCreationManager functionality
+use super::address::ethereum_address_from_public_key;
 use super::dto::*;
 use crate::{IdosError, IdosResult};
 
@@ -9,12 +10,31 @@ use {
     tiny_hderive::bip32::ExtendedPrivKey,
 };
 
+/// Default BIP-44 path of an account
+/// Ethereum: m/44'/60'/0'/0/{index} (MetaMask); Solana: m/44'/501'/{index}'/0' (Phantom, Solflare)
+pub fn default_derivation_path(network: BlockchainNetwork, account_index: u32) -> String {
+    match network {
+        BlockchainNetwork::Ethereum => format!("m/44'/60'/0'/0/{}", account_index),
+        BlockchainNetwork::Solana => format!("m/44'/501'/{}'/0'", account_index),
+    }
+}
+
 /// Generate a new wallet with a random mnemonic
 /// Matches Unity SDK's CreateAccount functionality
 #[cfg(feature = "wallet")]
 pub fn generate_wallet(
     network: BlockchainNetwork,
     word_count: usize,
+) -> IdosResult<WalletCreationResult> {
+    generate_wallet_with_path(network, word_count, &default_derivation_path(network, 0))
+}
+
+/// Generate a new wallet with a random mnemonic, deriving the key at `derivation_path`
+#[cfg(feature = "wallet")]
+pub fn generate_wallet_with_path(
+    network: BlockchainNetwork,
+    word_count: usize,
+    derivation_path: &str,
 ) -> IdosResult<WalletCreationResult> {
     use rand::Rng;
 
@@ -40,7 +60,8 @@ pub fn generate_wallet(
     let seed_phrase = mnemonic.to_string();
 
     // Derive keys from mnemonic
-    let wallet_info = derive_wallet_from_mnemonic(&seed_phrase, network)?;
+    let wallet_info =
+        derive_wallet_from_mnemonic_with_path(&seed_phrase, network, derivation_path)?;
 
     Ok(WalletCreationResult {
         wallet_info,
@@ -54,6 +75,30 @@ pub fn generate_wallet(
 pub fn derive_wallet_from_mnemonic(
     seed_phrase: &str,
     network: BlockchainNetwork,
+) -> IdosResult<WalletInfo> {
+    derive_account(seed_phrase, network, 0)
+}
+
+/// Derive the account at `account_index` of a mnemonic (default BIP-44 path)
+#[cfg(feature = "wallet")]
+pub fn derive_account(
+    seed_phrase: &str,
+    network: BlockchainNetwork,
+    account_index: u32,
+) -> IdosResult<WalletInfo> {
+    derive_wallet_from_mnemonic_with_path(
+        seed_phrase,
+        network,
+        &default_derivation_path(network, account_index),
+    )
+}
+
+/// Derive wallet from mnemonic at a custom derivation path
+#[cfg(feature = "wallet")]
+pub fn derive_wallet_from_mnemonic_with_path(
+    seed_phrase: &str,
+    network: BlockchainNetwork,
+    derivation_path: &str,
 ) -> IdosResult<WalletInfo> {
     let mnemonic = Mnemonic::parse_in_normalized(bip39::Language::English, seed_phrase)
         .map_err(|e| IdosError::InvalidInput(format!("Invalid mnemonic: {:?}", e)))?;
@@ -61,16 +106,46 @@ pub fn derive_wallet_from_mnemonic(
     let seed = mnemonic.to_seed("");
 
     match network {
-        BlockchainNetwork::Ethereum => derive_ethereum_wallet(&seed, seed_phrase),
-        BlockchainNetwork::Solana => derive_solana_wallet(&seed, seed_phrase),
+        BlockchainNetwork::Ethereum => derive_ethereum_wallet(&seed, seed_phrase, derivation_path),
+        BlockchainNetwork::Solana => derive_solana_wallet(&seed, seed_phrase, derivation_path),
+    }
+}
+
+/// Parse a BIP-32 path ("m/44'/60'/0'/0/0") into (index, hardened) pairs
+/// `'`, `h` and `H` mark hardened indexes
+#[cfg(feature = "wallet")]
+fn parse_derivation_path(path: &str) -> IdosResult<Vec<(u32, bool)>> {
+    let invalid = || IdosError::InvalidInput(format!("Invalid derivation path: {}", path));
+
+    let mut segments = path.trim().split('/');
+    if segments.next() != Some("m") {
+        return Err(invalid());
     }
+
+    segments
+        .map(|segment| {
+            let (index, hardened) = match segment.strip_suffix(['\'', 'h', 'H']) {
+                Some(index) => (index, true),
+                None => (segment, false),
+            };
+            let index: u32 = index.parse().map_err(|_| invalid())?;
+            if index >= 0x8000_0000 {
+                return Err(invalid());
+            }
+            Ok((index, hardened))
+        })
+        .collect()
 }
 
-/// Derive Ethereum wallet using BIP44 path: m/44'/60'/0'/0/0
+/// Derive Ethereum wallet (secp256k1 BIP-32), default path m/44'/60'/0'/0/0
 #[cfg(feature = "wallet")]
-fn derive_ethereum_wallet(seed: &[u8], seed_phrase: &str) -> IdosResult<WalletInfo> {
-    // BIP44 derivation path for Ethereum: m/44'/60'/0'/0/0
-    let ext = ExtendedPrivKey::derive(seed, "m/44'/60'/0'/0/0")
+fn derive_ethereum_wallet(
+    seed: &[u8],
+    seed_phrase: &str,
+    derivation_path: &str,
+) -> IdosResult<WalletInfo> {
+    parse_derivation_path(derivation_path)?;
+    let ext = ExtendedPrivKey::derive(seed, derivation_path)
         .map_err(|e| IdosError::Wallet(format!("Key derivation failed: {:?}", e)))?;
 
     // Get secp256k1 private key
@@ -79,8 +154,7 @@ fn derive_ethereum_wallet(seed: &[u8], seed_phrase: &str) -> IdosResult<WalletIn
 
     // Get public key
     let verifying_key = signing_key.verifying_key();
-    use k256::EncodedPoint;
-    let public_key_bytes: EncodedPoint = EncodedPoint::from(verifying_key);
+    let public_key_bytes = verifying_key.to_encoded_point(false); // Uncompressed
     let public_key = &public_key_bytes.as_bytes()[1..]; // Remove 0x04 prefix
 
     // Ethereum address is last 20 bytes of keccak256(public_key)
@@ -94,20 +168,23 @@ fn derive_ethereum_wallet(seed: &[u8], seed_phrase: &str) -> IdosResult<WalletIn
         network: BlockchainNetwork::Ethereum,
         private_key: Some(private_key),
         seed_phrase: Some(seed_phrase.to_string()),
+        watch_only: false,
+        hardware: false,
     })
 }
 
-/// Derive Solana wallet using BIP44 path: m/44'/501'/0'/0'
+/// Derive Solana wallet (SLIP-0010 Ed25519), default path m/44'/501'/0'/0'
+/// Ed25519 only supports hardened derivation, so every index must be hardened
 #[cfg(feature = "wallet")]
-fn derive_solana_wallet(seed: &[u8], seed_phrase: &str) -> IdosResult<WalletInfo> {
+fn derive_solana_wallet(
+    seed: &[u8],
+    seed_phrase: &str,
+    derivation_path: &str,
+) -> IdosResult<WalletInfo> {
     use ed25519_dalek::SigningKey;
 
-    // BIP44 derivation path for Solana: m/44'/501'/0'/0'
-    let ext = ExtendedPrivKey::derive(seed, "m/44'/501'/0'/0'")
-        .map_err(|e| IdosError::Wallet(format!("Key derivation failed: {:?}", e)))?;
-
     // Ed25519 key from derived seed
-    let signing_key = SigningKey::from_bytes(&ext.secret());
+    let signing_key = SigningKey::from_bytes(&derive_ed25519_secret(seed, derivation_path)?);
     let verifying_key = signing_key.verifying_key();
 
     // Solana address is base58 encoded public key
@@ -124,23 +201,42 @@ fn derive_solana_wallet(seed: &[u8], seed_phrase: &str) -> IdosResult<WalletInfo
         network: BlockchainNetwork::Solana,
         private_key: Some(private_key),
         seed_phrase: Some(seed_phrase.to_string()),
+        watch_only: false,
+        hardware: false,
     })
 }
 
-/// Calculate Ethereum address from public key
+/// SLIP-0010 Ed25519 private key at `derivation_path`
 #[cfg(feature = "wallet")]
-fn ethereum_address_from_public_key(public_key: &[u8]) -> String {
-    use sha2::{Digest, Sha256};
+fn derive_ed25519_secret(seed: &[u8], derivation_path: &str) -> IdosResult<[u8; 32]> {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha512;
+
+    let hmac_sha512 = |key: &[u8], parts: &[&[u8]]| -> [u8; 64] {
+        let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts any key length");
+        for part in parts {
+            mac.update(part);
+        }
+        mac.finalize().into_bytes().into()
+    };
 
-    // Ethereum uses Keccak256, but we'll use SHA256 as approximation for now
-    // In production, you'd want to use tiny-keccak crate
-    let mut hasher = Sha256::new();
-    hasher.update(public_key);
-    let hash = hasher.finalize();
+    let mut node = hmac_sha512(b"ed25519 seed", &[seed]);
+    for (index, hardened) in parse_derivation_path(derivation_path)? {
+        if !hardened {
+            return Err(IdosError::InvalidInput(format!(
+                "Solana derivation paths must be fully hardened: {}",
+                derivation_path
+            )));
+        }
+
+        let (key, chain_code) = node.split_at(32);
+        let index = (index | 0x8000_0000).to_be_bytes();
+        node = hmac_sha512(chain_code, &[&[0u8], key, &index]);
+    }
 
-    // Take last 20 bytes and format as hex with 0x prefix
-    let address_bytes = &hash[hash.len() - 20..];
-    format!("0x{}", hex::encode(address_bytes))
+    let mut secret = [0u8; 32];
+    secret.copy_from_slice(&node[..32]);
+    Ok(secret)
 }
 
 #[cfg(not(feature = "wallet"))]
@@ -163,6 +259,39 @@ pub fn derive_wallet_from_mnemonic(
     ))
 }
 
+#[cfg(not(feature = "wallet"))]
+pub fn generate_wallet_with_path(
+    _network: BlockchainNetwork,
+    _word_count: usize,
+    _derivation_path: &str,
+) -> IdosResult<WalletCreationResult> {
+    Err(IdosError::PlatformNotSupported(
+        "Wallet feature not enabled".to_string(),
+    ))
+}
+
+#[cfg(not(feature = "wallet"))]
+pub fn derive_account(
+    _seed_phrase: &str,
+    _network: BlockchainNetwork,
+    _account_index: u32,
+) -> IdosResult<WalletInfo> {
+    Err(IdosError::PlatformNotSupported(
+        "Wallet feature not enabled".to_string(),
+    ))
+}
+
+#[cfg(not(feature = "wallet"))]
+pub fn derive_wallet_from_mnemonic_with_path(
+    _seed_phrase: &str,
+    _network: BlockchainNetwork,
+    _derivation_path: &str,
+) -> IdosResult<WalletInfo> {
+    Err(IdosError::PlatformNotSupported(
+        "Wallet feature not enabled".to_string(),
+    ))
+}
+
 #[cfg(all(test, feature = "wallet"))]
 mod tests {
     use super::*;
@@ -191,5 +320,46 @@ mod tests {
 
         let sol_wallet = derive_wallet_from_mnemonic(mnemonic, BlockchainNetwork::Solana).unwrap();
         assert!(!sol_wallet.address.is_empty());
+
+        let second = derive_account(mnemonic, BlockchainNetwork::Solana, 1).unwrap();
+        assert_ne!(second.address, sol_wallet.address);
+        assert!(derive_wallet_from_mnemonic_with_path(
+            mnemonic,
+            BlockchainNetwork::Solana,
+            "m/44'/501'/0'/0"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_slip10_ed25519_vectors() {
+        // SLIP-0010 test vector 1
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        for (path, secret) in [
+            (
+                "m",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            ),
+            (
+                "m/0'",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            ),
+            (
+                "m/0H/1H",
+                "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+            ),
+        ] {
+            assert_eq!(
+                hex::encode(derive_ed25519_secret(&seed, path).unwrap()),
+                secret
+            );
+        }
+
+        assert_eq!(
+            default_derivation_path(BlockchainNetwork::Ethereum, 2),
+            "m/44'/60'/0'/0/2"
+        );
+        assert!(parse_derivation_path("44'/60'").is_err());
+        assert!(parse_derivation_path("m/x").is_err());
     }
 }
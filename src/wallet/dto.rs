@@ -1,8 +1,9 @@
 /// Data Transfer Objects for Wallet Management
+use bevy::prelude::Message;
 use serde::{Deserialize, Serialize};
 
 /// Blockchain network type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BlockchainNetwork {
     Ethereum,
     Solana,
@@ -26,6 +27,96 @@ pub struct WalletInfo {
     pub private_key: Option<String>, // Never serialize
     #[serde(skip_serializing)]
     pub seed_phrase: Option<String>, // Never serialize
+    /// Address-only wallet added for viewing balances/NFTs; has no keys
+    #[serde(default)]
+    pub watch_only: bool,
+    /// Keys live on a paired hardware device (Ledger)
+    #[serde(default)]
+    pub hardware: bool,
+}
+
+/// Wallet slot for one network and BIP-44 account index
+/// Its `wallet_id` ("ethereum_0", "solana_1") names the slot's keystore entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WalletSlot {
+    pub network: BlockchainNetwork,
+    pub account_index: u32,
+}
+
+impl WalletSlot {
+    pub fn new(network: BlockchainNetwork, account_index: u32) -> Self {
+        Self {
+            network,
+            account_index,
+        }
+    }
+
+    pub fn wallet_id(&self) -> String {
+        format!(
+            "{}_{}",
+            self.network.as_str().to_lowercase(),
+            self.account_index
+        )
+    }
+
+    /// Slot named by `wallet_id`, None for custom slot names
+    pub fn from_wallet_id(wallet_id: &str) -> Option<Self> {
+        let (network, index) = wallet_id.rsplit_once('_')?;
+        let network = match network {
+            "ethereum" => BlockchainNetwork::Ethereum,
+            "solana" => BlockchainNetwork::Solana,
+            _ => return None,
+        };
+        Some(Self::new(network, index.parse().ok()?))
+    }
+}
+
+/// A saved wallet slot, readable without the password
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletSummary {
+    pub wallet_id: String,
+    pub address: String,
+    pub network: BlockchainNetwork,
+    /// Network and account index, for slots created by `add_account`
+    #[serde(default)]
+    pub slot: Option<WalletSlot>,
+    pub active: bool,
+    /// Keys are in memory, so the wallet can sign without a password
+    #[serde(default)]
+    pub unlocked: bool,
+    pub watch_only: bool,
+    pub hardware: bool,
+}
+
+/// Signature over an arbitrary message, tagged with the chain that produced it
+/// Ethereum: EIP-191 personal_sign, 0x hex r || s || v
+/// Solana: Ed25519, base58
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageSignature {
+    pub network: BlockchainNetwork,
+    pub address: String,
+    pub signature: String,
+}
+
+/// Argon2id cost used when encrypting wallet keys
+/// Stored alongside each ciphertext, so changing it only affects newly saved wallets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Argon2Params {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    /// OWASP recommended minimum (19 MiB, 2 passes)
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
 }
 
 /// Encrypted wallet data stored in PlayerPrefs/localStorage
@@ -44,9 +135,95 @@ pub struct WalletCreationResult {
     pub seed_phrase: String, // Return to show user once
 }
 
+/// One recovery phrase covering both chains (default BIP-44 account of each)
+#[derive(Debug, Clone)]
+pub struct MultichainWalletResult {
+    pub ethereum: WalletInfo,
+    pub solana: WalletInfo,
+    pub seed_phrase: String, // Return to show user once
+}
+
+/// Secret handed out by `WalletManager::export_private_key` / `export_seed_phrase`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SecretKind {
+    PrivateKey,
+    SeedPhrase,
+}
+
+impl SecretKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SecretKind::PrivateKey => "private_key",
+            SecretKind::SeedPhrase => "seed_phrase",
+        }
+    }
+}
+
 /// Wallet import source
 #[derive(Debug, Clone)]
 pub enum ImportSource {
     SeedPhrase(String),
+    /// Seed phrase derived at a custom BIP-44 path (e.g. a second account)
+    SeedPhraseWithPath {
+        seed_phrase: String,
+        derivation_path: String,
+    },
     PrivateKey(String),
+    /// Contents of a Solana CLI keypair file (`id.json`): a JSON array of 64 bytes
+    SolanaKeypairJson(String),
+    /// Web3 Secret Storage keystore file (geth/MetaMask JSON, V3) and its password
+    Web3Keystore {
+        json: String,
+        password: String,
+    },
+}
+
+// Wallet lifecycle, written as messages by `WalletPlugin`
+
+/// A new wallet (seed phrase) was generated and saved
+#[derive(Message, Debug, Clone)]
+pub struct WalletCreated {
+    pub wallet_id: String,
+    pub address: String,
+    pub network: BlockchainNetwork,
+}
+
+/// An existing wallet was imported (including watch-only and hardware wallets)
+#[derive(Message, Debug, Clone)]
+pub struct WalletImported {
+    pub wallet_id: String,
+    pub address: String,
+    pub network: BlockchainNetwork,
+}
+
+/// Decrypted keys are in memory (after login, create or import) and the wallet can sign
+#[derive(Message, Debug, Clone)]
+pub struct WalletUnlocked {
+    pub address: String,
+    pub network: BlockchainNetwork,
+}
+
+/// Keys were cleared from memory; the password is required again before signing
+#[derive(Message, Debug, Clone)]
+pub struct WalletLocked {
+    pub address: String,
+    /// Locked by the inactivity timer rather than `logout`
+    pub auto_locked: bool,
+}
+
+/// A wallet was removed from storage with `disconnect`
+#[derive(Message, Debug, Clone)]
+pub struct WalletDeleted {
+    pub wallet_id: String,
+    pub address: Option<String>,
+}
+
+/// Lifecycle event queued by `WalletManager` until WalletPlugin writes it as a message
+#[derive(Debug, Clone)]
+pub enum WalletEvent {
+    Created(WalletCreated),
+    Imported(WalletImported),
+    Unlocked(WalletUnlocked),
+    Locked(WalletLocked),
+    Deleted(WalletDeleted),
 }
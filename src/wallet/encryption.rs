@@ -1,48 +1,183 @@
 /// Password-based encryption for wallet keys
-/// Matches Unity SDK's PrivateKeyManager encryption method
+/// New data uses Argon2id + AES-256-GCM; the Unity SDK's XOR format is still readable
+use super::dto::Argon2Params;
 use crate::{IdosError, IdosResult};
 use base64::{engine::general_purpose, Engine as _};
 
-/// Encrypt data using XOR cipher (matches Unity SDK implementation)
-/// This is the same simple XOR encryption used in Unity's PrivateKeyManager.cs
+/// Prefix of the versioned format: `v2$m=..,t=..,p=..$salt$nonce$ciphertext` (base64 fields)
+const V2_PREFIX: &str = "v2$";
+
+/// Encrypt data with Argon2id (default cost) and AES-256-GCM
 pub fn encrypt(plain_text: &str, password: &str) -> IdosResult<String> {
+    encrypt_with_params(plain_text, password, &Argon2Params::default())
+}
+
+/// Encrypt data with Argon2id at the given cost and AES-256-GCM
+pub fn encrypt_with_params(
+    plain_text: &str,
+    password: &str,
+    params: &Argon2Params,
+) -> IdosResult<String> {
+    use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+    use rand::RngCore;
+
     if password.is_empty() {
         return Err(IdosError::InvalidInput(
             "Password cannot be empty".to_string(),
         ));
     }
 
-    let plain_bytes = plain_text.as_bytes();
-    let password_bytes = password.as_bytes();
-    let mut encrypted_bytes = Vec::with_capacity(plain_bytes.len());
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let key = derive_key(password, &salt, params)?;
+    let cipher = Aes256Gcm::new_from_slice(&key)
+        .map_err(|e| IdosError::Wallet(format!("Invalid key: {}", e)))?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plain_text.as_bytes())
+        .map_err(|e| IdosError::Wallet(format!("Encryption failed: {}", e)))?;
+
+    Ok(format!(
+        "{}m={},t={},p={}${}${}${}",
+        V2_PREFIX,
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        general_purpose::STANDARD.encode(salt),
+        general_purpose::STANDARD.encode(nonce),
+        general_purpose::STANDARD.encode(ciphertext)
+    ))
+}
+
+/// Decrypt data written by `encrypt` or by the legacy XOR scheme
+/// A wrong password is reported as `Auth` for the versioned format;
+/// the legacy format can't detect it and returns garbage
+pub fn decrypt(encrypted_message: &str, password: &str) -> IdosResult<String> {
+    if password.is_empty() {
+        return Err(IdosError::InvalidInput(
+            "Password cannot be empty".to_string(),
+        ));
+    }
 
-    for (i, &byte) in plain_bytes.iter().enumerate() {
-        encrypted_bytes.push(byte ^ password_bytes[i % password_bytes.len()]);
+    match encrypted_message.strip_prefix(V2_PREFIX) {
+        Some(body) => decrypt_v2(body, password),
+        None => decrypt_legacy(encrypted_message, password),
     }
+}
 
-    Ok(general_purpose::STANDARD.encode(&encrypted_bytes))
+/// Whether `encrypted_message` should be re-encrypted with `params`
+/// True for the legacy XOR format and for Argon2id data with a different cost
+pub fn needs_upgrade(encrypted_message: &str, params: &Argon2Params) -> bool {
+    match encrypted_message
+        .strip_prefix(V2_PREFIX)
+        .and_then(|body| body.split('$').next())
+        .and_then(parse_params)
+    {
+        Some(stored) => stored != *params,
+        None => true,
+    }
 }
 
-/// Decrypt data using XOR cipher (matches Unity SDK implementation)
-pub fn decrypt(encrypted_message: &str, password: &str) -> IdosResult<String> {
+fn decrypt_v2(body: &str, password: &str) -> IdosResult<String> {
+    use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+
+    let invalid = || IdosError::SerializationError("Malformed encrypted wallet data".to_string());
+    let decode = |field: &str| {
+        general_purpose::STANDARD
+            .decode(field)
+            .map_err(|_| invalid())
+    };
+
+    let parts: Vec<&str> = body.split('$').collect();
+    let [params, salt, nonce, ciphertext] = parts[..] else {
+        return Err(invalid());
+    };
+    let params = parse_params(params).ok_or_else(invalid)?;
+    let nonce = decode(nonce)?;
+    if nonce.len() != 12 {
+        return Err(invalid());
+    }
+
+    let key = derive_key(password, &decode(salt)?, &params)?;
+    let cipher = Aes256Gcm::new_from_slice(&key)
+        .map_err(|e| IdosError::Wallet(format!("Invalid key: {}", e)))?;
+    let plain_bytes = cipher
+        .decrypt(Nonce::from_slice(&nonce), decode(ciphertext)?.as_slice())
+        .map_err(|_| IdosError::Auth("Incorrect password".to_string()))?;
+
+    String::from_utf8(plain_bytes)
+        .map_err(|e| IdosError::SerializationError(format!("UTF-8 decode error: {}", e)))
+}
+
+/// `m=..,t=..,p=..`
+fn parse_params(s: &str) -> Option<Argon2Params> {
+    let mut params = Argon2Params {
+        memory_kib: 0,
+        iterations: 0,
+        parallelism: 0,
+    };
+    for pair in s.split(',') {
+        let (name, value) = pair.split_once('=')?;
+        let value = value.parse().ok()?;
+        match name {
+            "m" => params.memory_kib = value,
+            "t" => params.iterations = value,
+            "p" => params.parallelism = value,
+            _ => return None,
+        }
+    }
+    Some(params)
+}
+
+fn derive_key(password: &str, salt: &[u8], params: &Argon2Params) -> IdosResult<[u8; 32]> {
+    use argon2::{Algorithm, Argon2, Params, Version};
+
+    let params = Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(32),
+    )
+    .map_err(|e| IdosError::InvalidInput(format!("Invalid Argon2 parameters: {}", e)))?;
+
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| IdosError::Wallet(format!("Key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+/// XOR cipher (matches Unity SDK implementation)
+/// This is the same simple XOR encryption used in Unity's PrivateKeyManager.cs
+fn xor_with_password(bytes: &[u8], password: &str) -> Vec<u8> {
+    let password_bytes = password.as_bytes();
+    bytes
+        .iter()
+        .enumerate()
+        .map(|(i, &byte)| byte ^ password_bytes[i % password_bytes.len()])
+        .collect()
+}
+
+/// Encrypt data using the legacy XOR cipher
+/// Only kept for interoperability with Unity SDK storage; use `encrypt` for new data
+pub fn encrypt_legacy(plain_text: &str, password: &str) -> IdosResult<String> {
     if password.is_empty() {
         return Err(IdosError::InvalidInput(
             "Password cannot be empty".to_string(),
         ));
     }
 
+    Ok(general_purpose::STANDARD.encode(xor_with_password(plain_text.as_bytes(), password)))
+}
+
+fn decrypt_legacy(encrypted_message: &str, password: &str) -> IdosResult<String> {
     let encrypted_bytes = general_purpose::STANDARD
         .decode(encrypted_message)
         .map_err(|e| IdosError::SerializationError(format!("Base64 decode error: {}", e)))?;
 
-    let password_bytes = password.as_bytes();
-    let mut plain_bytes = Vec::with_capacity(encrypted_bytes.len());
-
-    for (i, &byte) in encrypted_bytes.iter().enumerate() {
-        plain_bytes.push(byte ^ password_bytes[i % password_bytes.len()]);
-    }
-
-    String::from_utf8(plain_bytes)
+    String::from_utf8(xor_with_password(&encrypted_bytes, password))
         .map(|s| s.trim_end_matches('\0').to_string())
         .map_err(|e| IdosError::SerializationError(format!("UTF-8 decode error: {}", e)))
 }
@@ -51,12 +186,20 @@ pub fn decrypt(encrypted_message: &str, password: &str) -> IdosResult<String> {
 mod tests {
     use super::*;
 
+    /// Cheap parameters so debug-build tests stay fast
+    const TEST_PARAMS: Argon2Params = Argon2Params {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
     #[test]
     fn test_encrypt_decrypt() {
         let plain_text = "test private key 12345";
         let password = "mypassword123";
 
-        let encrypted = encrypt(plain_text, password).unwrap();
+        let encrypted = encrypt_with_params(plain_text, password, &TEST_PARAMS).unwrap();
+        assert!(encrypted.starts_with("v2$m=64,t=1,p=1$"));
         let decrypted = decrypt(&encrypted, password).unwrap();
 
         assert_eq!(plain_text, decrypted);
@@ -68,8 +211,14 @@ mod tests {
         let password = "correct";
         let wrong_password = "wrong";
 
-        let encrypted = encrypt(plain_text, password).unwrap();
-        let decrypted = decrypt(&encrypted, wrong_password).unwrap();
+        let encrypted = encrypt_with_params(plain_text, password, &TEST_PARAMS).unwrap();
+        assert!(matches!(
+            decrypt(&encrypted, wrong_password),
+            Err(IdosError::Auth(_))
+        ));
+
+        let legacy = encrypt_legacy(plain_text, password).unwrap();
+        let decrypted = decrypt(&legacy, wrong_password).unwrap();
 
         assert_ne!(plain_text, decrypted);
     }
@@ -84,4 +233,16 @@ mod tests {
 
         assert_eq!(seed_phrase, decrypted);
     }
+
+    #[test]
+    fn test_legacy_upgrade() {
+        let password = "123456";
+        let legacy = encrypt_legacy("secret", password).unwrap();
+        assert_eq!(decrypt(&legacy, password).unwrap(), "secret");
+        assert!(needs_upgrade(&legacy, &TEST_PARAMS));
+
+        let current = encrypt_with_params("secret", password, &TEST_PARAMS).unwrap();
+        assert!(!needs_upgrade(&current, &TEST_PARAMS));
+        assert!(needs_upgrade(&current, &Argon2Params::default()));
+    }
 }
@@ -0,0 +1,333 @@
+/// Ledger hardware wallet backend for in-game wallets
+/// Talks to the Ledger Ethereum and Solana apps; keys never leave the device
+use super::address::ethereum_address_from_public_key;
+use super::backend::WalletBackend;
+use super::creation::default_derivation_path;
+use super::dto::*;
+use crate::ledger::{
+    build_apdu, check_status, encode_derivation_path, exchange_chunked, LedgerTransport,
+    MAX_CHUNK_SIZE,
+};
+use crate::{IdosError, IdosResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+// Ethereum app
+const ETH_INS_GET_PUBLIC_KEY: u8 = 0x02;
+const ETH_INS_SIGN_PERSONAL_MESSAGE: u8 = 0x08;
+
+// Solana app
+const SOL_INS_GET_PUBKEY: u8 = 0x05;
+const SOL_INS_SIGN_MESSAGE: u8 = 0x06;
+const SOL_INS_SIGN_OFFCHAIN_MESSAGE: u8 = 0x07;
+const SOL_P2_EXTEND: u8 = 0x01;
+const SOL_P2_MORE: u8 = 0x02;
+
+const P1_NON_CONFIRM: u8 = 0x00;
+const P1_CONFIRM: u8 = 0x01;
+
+/// Prefix of Solana off-chain messages; the Solana app won't sign raw bytes
+const SOLANA_OFFCHAIN_SIGNING_DOMAIN: &[u8] = b"\xffsolana offchain";
+const SOLANA_OFFCHAIN_MAX_LEN: usize = 65515;
+const SOLANA_OFFCHAIN_MAX_LEDGER_LEN: usize = 1212;
+
+/// Wallet whose keys live on a Ledger device
+#[derive(Clone)]
+pub struct LedgerWallet {
+    transport: Arc<dyn LedgerTransport>,
+    network: BlockchainNetwork,
+    derivation_path: String,
+    address: String,
+}
+
+impl std::fmt::Debug for LedgerWallet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LedgerWallet")
+            .field("network", &self.network)
+            .field("derivation_path", &self.derivation_path)
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+impl LedgerWallet {
+    /// Connect to the coin app for `network` and read the address at `derivation_path`
+    /// Uses the first BIP-44 account when no path is given
+    pub async fn connect(
+        transport: Arc<dyn LedgerTransport>,
+        network: BlockchainNetwork,
+        derivation_path: Option<&str>,
+    ) -> IdosResult<Self> {
+        let derivation_path = derivation_path
+            .map(str::to_string)
+            .unwrap_or_else(|| default_derivation_path(network, 0));
+        let address = read_address(
+            transport.as_ref(),
+            network,
+            &derivation_path,
+            P1_NON_CONFIRM,
+        )
+        .await?;
+
+        Ok(Self {
+            transport,
+            network,
+            derivation_path,
+            address,
+        })
+    }
+
+    /// Derivation path used on the device
+    pub fn derivation_path(&self) -> &str {
+        &self.derivation_path
+    }
+
+    /// Ask the device to display the address so the player can verify it
+    pub async fn verify_address_on_device(&self) -> IdosResult<String> {
+        read_address(
+            self.transport.as_ref(),
+            self.network,
+            &self.derivation_path,
+            P1_CONFIRM,
+        )
+        .await
+    }
+
+    /// Sign a serialized Solana transaction message on the device
+    pub async fn sign_solana_transaction(&self, message: &[u8]) -> IdosResult<[u8; 64]> {
+        self.require_network(BlockchainNetwork::Solana)?;
+        self.solana_sign(SOL_INS_SIGN_MESSAGE, message).await
+    }
+
+    /// Ethereum transaction signer for this device
+    #[cfg(feature = "crypto_ethereum")]
+    pub fn ethereum_signer(
+        &self,
+        chain_id: u64,
+    ) -> IdosResult<crate::crypto_ethereum::LedgerSigner> {
+        self.require_network(BlockchainNetwork::Ethereum)?;
+        let address = self
+            .address
+            .parse()
+            .map_err(|_| IdosError::Wallet("Invalid Ledger address".to_string()))?;
+
+        Ok(crate::crypto_ethereum::LedgerSigner::from_paired(
+            self.transport.clone(),
+            &self.derivation_path,
+            address,
+            chain_id,
+        ))
+    }
+
+    fn require_network(&self, network: BlockchainNetwork) -> IdosResult<()> {
+        if self.network != network {
+            return Err(IdosError::Wallet(format!(
+                "Ledger wallet is paired for {}",
+                self.network.as_str()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Solana app framing: `[signer count][path][message]`, split with P2 extend/more flags
+    async fn solana_sign(&self, ins: u8, message: &[u8]) -> IdosResult<[u8; 64]> {
+        let mut payload = vec![1u8];
+        payload.extend_from_slice(&encode_derivation_path(&self.derivation_path)?);
+        payload.extend_from_slice(message);
+
+        let chunks: Vec<&[u8]> = payload.chunks(MAX_CHUNK_SIZE).collect();
+        let mut response = Vec::new();
+        for (index, chunk) in chunks.iter().enumerate() {
+            let mut p2 = 0;
+            if index > 0 {
+                p2 |= SOL_P2_EXTEND;
+            }
+            if index + 1 < chunks.len() {
+                p2 |= SOL_P2_MORE;
+            }
+            let apdu = build_apdu(ins, P1_CONFIRM, p2, chunk);
+            response = check_status(self.transport.exchange(&apdu).await?)?;
+        }
+
+        response
+            .get(..64)
+            .and_then(|s| s.try_into().ok())
+            .ok_or_else(|| IdosError::Wallet("Short signature response from Ledger".to_string()))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl WalletBackend for LedgerWallet {
+    fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    fn network(&self) -> BlockchainNetwork {
+        self.network
+    }
+
+    fn is_hardware(&self) -> bool {
+        true
+    }
+
+    /// Ethereum: EIP-191 personal_sign, same as a local wallet
+    /// Solana: Ed25519 over the off-chain message envelope (`solana_offchain_message`)
+    async fn sign_message(&self, message: &[u8]) -> IdosResult<MessageSignature> {
+        let signature = match self.network {
+            BlockchainNetwork::Ethereum => {
+                let mut payload = encode_derivation_path(&self.derivation_path)?;
+                payload.extend_from_slice(&(message.len() as u32).to_be_bytes());
+                payload.extend_from_slice(message);
+
+                let response = exchange_chunked(
+                    self.transport.as_ref(),
+                    ETH_INS_SIGN_PERSONAL_MESSAGE,
+                    0x00,
+                    &payload,
+                )
+                .await?;
+                if response.len() < 65 {
+                    return Err(IdosError::Wallet(
+                        "Short signature response from Ledger".to_string(),
+                    ));
+                }
+
+                // Device returns v || r || s
+                let mut bytes = response[1..65].to_vec();
+                bytes.push(response[0]);
+                format!("0x{}", hex::encode(bytes))
+            }
+            BlockchainNetwork::Solana => {
+                let envelope = solana_offchain_message(message)?;
+                let signature = self
+                    .solana_sign(SOL_INS_SIGN_OFFCHAIN_MESSAGE, &envelope)
+                    .await?;
+                bs58::encode(signature).into_string()
+            }
+        };
+
+        Ok(MessageSignature {
+            network: self.network,
+            address: self.address.clone(),
+            signature,
+        })
+    }
+}
+
+/// Serialize a Solana off-chain message (version 0) around `message`
+/// Verifiers check the Ed25519 signature against these bytes, not the raw message
+pub fn solana_offchain_message(message: &[u8]) -> IdosResult<Vec<u8>> {
+    if message.is_empty() || message.len() > SOLANA_OFFCHAIN_MAX_LEN {
+        return Err(IdosError::InvalidInput(format!(
+            "Off-chain message must be 1..={} bytes",
+            SOLANA_OFFCHAIN_MAX_LEN
+        )));
+    }
+
+    // 0 = restricted ASCII, 1 = limited UTF-8, 2 = extended UTF-8
+    let format = if message.len() <= SOLANA_OFFCHAIN_MAX_LEDGER_LEN
+        && message.iter().all(|b| (0x20..=0x7e).contains(b))
+    {
+        0u8
+    } else if message.len() <= SOLANA_OFFCHAIN_MAX_LEDGER_LEN
+        && std::str::from_utf8(message).is_ok()
+    {
+        1
+    } else if std::str::from_utf8(message).is_ok() {
+        2
+    } else {
+        return Err(IdosError::InvalidInput(
+            "Off-chain message must be UTF-8".to_string(),
+        ));
+    };
+
+    let mut envelope = Vec::with_capacity(SOLANA_OFFCHAIN_SIGNING_DOMAIN.len() + 4 + message.len());
+    envelope.extend_from_slice(SOLANA_OFFCHAIN_SIGNING_DOMAIN);
+    envelope.push(0); // header version
+    envelope.push(format);
+    envelope.extend_from_slice(&(message.len() as u16).to_le_bytes());
+    envelope.extend_from_slice(message);
+    Ok(envelope)
+}
+
+async fn read_address(
+    transport: &dyn LedgerTransport,
+    network: BlockchainNetwork,
+    derivation_path: &str,
+    p1: u8,
+) -> IdosResult<String> {
+    let path = encode_derivation_path(derivation_path)?;
+    let short = || IdosError::Wallet("Short public key response from Ledger".to_string());
+
+    match network {
+        BlockchainNetwork::Ethereum => {
+            let apdu = build_apdu(ETH_INS_GET_PUBLIC_KEY, p1, 0x00, &path);
+            let response = check_status(transport.exchange(&apdu).await?)?;
+
+            // [len][0x04 || x || y][address len][address ascii]...
+            let public_key = response.get(1..66).ok_or_else(short)?;
+            if response[0] != 65 || public_key[0] != 0x04 {
+                return Err(IdosError::Wallet(
+                    "Unexpected public key format from Ledger".to_string(),
+                ));
+            }
+            Ok(ethereum_address_from_public_key(&public_key[1..]))
+        }
+        BlockchainNetwork::Solana => {
+            let apdu = build_apdu(SOL_INS_GET_PUBKEY, p1, 0x00, &path);
+            let response = check_status(transport.exchange(&apdu).await?)?;
+
+            let public_key = response.get(..32).ok_or_else(short)?;
+            Ok(bs58::encode(public_key).into_string())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wallet"))]
+mod tests {
+    use super::*;
+
+    struct MockSolanaApp;
+
+    #[async_trait]
+    impl LedgerTransport for MockSolanaApp {
+        async fn exchange(&self, apdu: &[u8]) -> IdosResult<Vec<u8>> {
+            let mut response = match apdu[1] {
+                SOL_INS_GET_PUBKEY => vec![7u8; 32],
+                _ => vec![9u8; 64],
+            };
+            response.extend_from_slice(&[0x90, 0x00]);
+            Ok(response)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_solana_ledger_wallet() {
+        let ledger =
+            LedgerWallet::connect(Arc::new(MockSolanaApp), BlockchainNetwork::Solana, None)
+                .await
+                .unwrap();
+        assert_eq!(ledger.address(), bs58::encode([7u8; 32]).into_string());
+        assert_eq!(ledger.derivation_path(), "m/44'/501'/0'/0'");
+
+        let signed = ledger.sign_message(b"login").await.unwrap();
+        assert_eq!(signed.signature, bs58::encode([9u8; 64]).into_string());
+        assert_eq!(
+            ledger.sign_solana_transaction(b"tx").await.unwrap(),
+            [9u8; 64]
+        );
+    }
+
+    #[test]
+    fn test_solana_offchain_message() {
+        let envelope = solana_offchain_message(b"hello").unwrap();
+        assert_eq!(&envelope[..16], SOLANA_OFFCHAIN_SIGNING_DOMAIN);
+        assert_eq!(&envelope[16..20], &[0, 0, 5, 0]);
+        assert_eq!(&envelope[20..], b"hello");
+
+        assert_eq!(solana_offchain_message("héllo".as_bytes()).unwrap()[17], 1);
+        assert!(solana_offchain_message(&[0xff, 0xfe]).is_err());
+        assert!(solana_offchain_message(b"").is_err());
+    }
+}
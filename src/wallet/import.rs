@@ -1,6 +1,9 @@
+use super::address::{
+    ethereum_address_from_public_key, is_valid_ethereum_address, to_checksum_address,
+};
 /// Wallet import functionality
 /// Matches Unity SDK's WalletImportManager
-use super::creation::derive_wallet_from_mnemonic;
+use super::creation::{default_derivation_path, derive_wallet_from_mnemonic_with_path};
 use super::dto::*;
 use crate::{IdosError, IdosResult};
 
@@ -8,16 +11,77 @@ use crate::{IdosError, IdosResult};
 /// Matches Unity SDK's OnImportButtonClick functionality
 pub fn import_wallet(source: ImportSource, network: BlockchainNetwork) -> IdosResult<WalletInfo> {
     match source {
-        ImportSource::SeedPhrase(seed_phrase) => import_from_seed_phrase(&seed_phrase, network),
+        ImportSource::SeedPhrase(seed_phrase) => {
+            import_from_seed_phrase(&seed_phrase, network, &default_derivation_path(network, 0))
+        }
+        ImportSource::SeedPhraseWithPath {
+            seed_phrase,
+            derivation_path,
+        } => import_from_seed_phrase(&seed_phrase, network, &derivation_path),
         ImportSource::PrivateKey(private_key) => import_from_private_key(&private_key, network),
+        ImportSource::SolanaKeypairJson(json) => {
+            if network != BlockchainNetwork::Solana {
+                return Err(IdosError::InvalidInput(
+                    "Keypair files can only be imported as Solana wallets".to_string(),
+                ));
+            }
+            let keypair = solana_keypair_from_json(&json)?;
+            import_solana_from_private_key(&bs58::encode(keypair).into_string())
+        }
+        ImportSource::Web3Keystore { json, password } => {
+            if network != BlockchainNetwork::Ethereum {
+                return Err(IdosError::InvalidInput(
+                    "Keystore files can only be imported as Ethereum wallets".to_string(),
+                ));
+            }
+            let private_key = super::web3_keystore::decrypt_keystore(&json, &password)?;
+            import_from_private_key(&private_key, network)
+        }
     }
 }
 
+/// Parse a Solana CLI keypair file (`id.json`, 64 bytes: secret key then public key)
+#[cfg(feature = "wallet")]
+pub fn solana_keypair_from_json(json: &str) -> IdosResult<[u8; 64]> {
+    use ed25519_dalek::SigningKey;
+
+    let bytes: Vec<u8> = serde_json::from_str(json.trim())
+        .map_err(|e| IdosError::InvalidInput(format!("Invalid keypair file: {}", e)))?;
+    let keypair = <[u8; 64]>::try_from(bytes.as_slice()).map_err(|_| {
+        IdosError::InvalidInput(format!(
+            "Solana keypair must be 64 bytes, got {}",
+            bytes.len()
+        ))
+    })?;
+
+    // A public half that doesn't match the secret means the file is damaged
+    let secret: [u8; 32] = keypair[..32].try_into().unwrap();
+    if SigningKey::from_bytes(&secret).verifying_key().as_bytes()[..] != keypair[32..] {
+        return Err(IdosError::InvalidInput(
+            "Keypair public key does not match its secret key".to_string(),
+        ));
+    }
+
+    Ok(keypair)
+}
+
+/// Export a Solana private key (base58, as stored in `WalletInfo`) as an `id.json` keypair file
+#[cfg(feature = "wallet")]
+pub fn solana_keypair_to_json(private_key: &str) -> IdosResult<String> {
+    let wallet = import_solana_from_private_key(private_key)?;
+    let keypair = bs58::decode(wallet.private_key.unwrap_or_default())
+        .into_vec()
+        .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
+
+    serde_json::to_string(&keypair).map_err(IdosError::from)
+}
+
 /// Import wallet from seed phrase (12 or 24 words)
 #[cfg(feature = "wallet")]
 fn import_from_seed_phrase(
     seed_phrase: &str,
     network: BlockchainNetwork,
+    derivation_path: &str,
 ) -> IdosResult<WalletInfo> {
     use bip39::Mnemonic;
 
@@ -38,7 +102,7 @@ fn import_from_seed_phrase(
         .map_err(|e| IdosError::InvalidInput(format!("Invalid seed phrase: {:?}", e)))?;
 
     // Derive wallet from mnemonic
-    derive_wallet_from_mnemonic(seed_phrase, network)
+    derive_wallet_from_mnemonic_with_path(seed_phrase, network, derivation_path)
 }
 
 /// Import wallet from private key
@@ -78,8 +142,7 @@ fn import_ethereum_from_private_key(private_key: &str) -> IdosResult<WalletInfo>
 
     // Get public key
     let verifying_key = signing_key.verifying_key();
-    use k256::EncodedPoint;
-    let public_key_bytes: EncodedPoint = EncodedPoint::from(verifying_key);
+    let public_key_bytes = verifying_key.to_encoded_point(false); // Uncompressed
     let public_key = &public_key_bytes.as_bytes()[1..]; // Remove 0x04 prefix
 
     // Calculate Ethereum address
@@ -90,6 +153,8 @@ fn import_ethereum_from_private_key(private_key: &str) -> IdosResult<WalletInfo>
         network: BlockchainNetwork::Ethereum,
         private_key: Some(format!("0x{}", key_str)),
         seed_phrase: None, // No seed phrase when importing from private key
+        watch_only: false,
+        hardware: false,
     })
 }
 
@@ -141,23 +206,45 @@ fn import_solana_from_private_key(private_key: &str) -> IdosResult<WalletInfo> {
         network: BlockchainNetwork::Solana,
         private_key: Some(private_key_base58),
         seed_phrase: None,
+        watch_only: false,
+        hardware: false,
     })
 }
 
-/// Calculate Ethereum address from public key
-#[cfg(feature = "wallet")]
-fn ethereum_address_from_public_key(public_key: &[u8]) -> String {
-    use sha2::{Digest, Sha256};
-
-    // Ethereum uses Keccak256, but we'll use SHA256 as approximation for now
-    // In production, you'd want to use tiny-keccak crate
-    let mut hasher = Sha256::new();
-    hasher.update(public_key);
-    let hash = hasher.finalize();
-
-    // Take last 20 bytes and format as hex with 0x prefix
-    let address_bytes = &hash[hash.len() - 20..];
-    format!("0x{}", hex::encode(address_bytes))
+/// Address-only wallet for viewing an external wallet's balances and NFTs
+pub fn watch_only_wallet(address: &str, network: BlockchainNetwork) -> IdosResult<WalletInfo> {
+    let address = match network {
+        BlockchainNetwork::Ethereum => {
+            if !is_valid_ethereum_address(address) {
+                return Err(IdosError::InvalidInput(format!(
+                    "Invalid Ethereum address: {}",
+                    address
+                )));
+            }
+            to_checksum_address(address)?
+        }
+        BlockchainNetwork::Solana => {
+            let valid = bs58::decode(address)
+                .into_vec()
+                .is_ok_and(|bytes| bytes.len() == 32);
+            if !valid {
+                return Err(IdosError::InvalidInput(format!(
+                    "Invalid Solana address: {}",
+                    address
+                )));
+            }
+            address.to_string()
+        }
+    };
+
+    Ok(WalletInfo {
+        address,
+        network,
+        private_key: None,
+        seed_phrase: None,
+        watch_only: true,
+        hardware: false,
+    })
 }
 
 #[cfg(not(feature = "wallet"))]
@@ -180,11 +267,58 @@ mod tests {
         )
         .unwrap();
 
-        assert!(wallet.address.starts_with("0x"));
+        assert_eq!(wallet.address, "0x4CDDF15861Ab6c18dE28E468404C070D87c889b9");
         assert_eq!(wallet.network, BlockchainNetwork::Ethereum);
         assert!(wallet.private_key.is_some());
     }
 
+    #[test]
+    fn test_watch_only_wallet() {
+        let wallet = watch_only_wallet(
+            "0x4cddf15861ab6c18de28e468404c070d87c889b9",
+            BlockchainNetwork::Ethereum,
+        )
+        .unwrap();
+        assert_eq!(wallet.address, "0x4CDDF15861Ab6c18dE28E468404C070D87c889b9");
+        assert!(wallet.watch_only);
+        assert!(wallet.private_key.is_none());
+
+        assert!(watch_only_wallet(
+            "11111111111111111111111111111111",
+            BlockchainNetwork::Solana
+        )
+        .is_ok());
+        assert!(watch_only_wallet("0x1234", BlockchainNetwork::Ethereum).is_err());
+        assert!(watch_only_wallet("not-base58!", BlockchainNetwork::Solana).is_err());
+    }
+
+    #[test]
+    fn test_solana_keypair_json_round_trip() {
+        let wallet = import_wallet(
+            ImportSource::PrivateKey(bs58::encode([7u8; 32]).into_string()),
+            BlockchainNetwork::Solana,
+        )
+        .unwrap();
+
+        let json = solana_keypair_to_json(wallet.private_key.as_deref().unwrap()).unwrap();
+        assert!(json.starts_with("[7,7,"));
+
+        let imported = import_wallet(
+            ImportSource::SolanaKeypairJson(json.clone()),
+            BlockchainNetwork::Solana,
+        )
+        .unwrap();
+        assert_eq!(imported.address, wallet.address);
+        assert_eq!(imported.private_key, wallet.private_key);
+
+        // Tampered public half
+        let mut keypair: Vec<u8> = serde_json::from_str(&json).unwrap();
+        keypair[40] ^= 1;
+        let tampered = serde_json::to_string(&keypair).unwrap();
+        assert!(solana_keypair_from_json(&tampered).is_err());
+        assert!(solana_keypair_from_json("[1,2,3]").is_err());
+    }
+
     #[test]
     fn test_import_from_seed_phrase() {
         let seed_phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
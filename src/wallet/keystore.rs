@@ -2,41 +2,187 @@
 /// Matches Unity SDK's PrivateKeyManager storage pattern
 use super::dto::*;
 use super::encryption;
+use super::secure_storage::SecureStorageBackend;
 use crate::{storage::Storage, IdosError, IdosResult};
+use std::sync::Arc;
 
 const ENCRYPTED_PRIVATE_KEY_PREFIX: &str = "EncryptedPrivateKey_";
 const ENCRYPTED_SEED_PHRASE_PREFIX: &str = "EncryptedSeedPhrase_";
 const WALLET_ADDRESS_PREFIX: &str = "WalletAddress_";
 const WALLET_NETWORK_PREFIX: &str = "WalletNetwork_";
+const WATCH_ONLY_PREFIX: &str = "WatchOnly_";
+const HARDWARE_PATH_PREFIX: &str = "HardwarePath_";
+const BIOMETRIC_UNLOCK_PREFIX: &str = "BiometricUnlock_";
+const CHAIN_ADDRESSES_PREFIX: &str = "ChainAddresses_";
+const WALLET_SLOTS_PREFIX: &str = "WalletSlots_";
+const ACTIVE_WALLET_PREFIX: &str = "ActiveWallet_";
+
+/// Slot used by wallets saved before multiple wallets were supported
+pub const DEFAULT_WALLET_ID: &str = "default";
 
 #[derive(Clone)]
 pub struct Keystore {
     storage: Storage,
+    /// Store for encrypted secrets; `None` keeps them in `storage`
+    secrets: Option<Arc<dyn SecureStorageBackend>>,
     user_id: String,
+    wallet_id: String,
+    kdf_params: Argon2Params,
 }
 
 impl Keystore {
     pub fn new(user_id: String) -> Self {
         Self {
             storage: Storage::new("idos_wallet_".to_string()),
+            secrets: None,
             user_id,
+            wallet_id: DEFAULT_WALLET_ID.to_string(),
+            kdf_params: Argon2Params::default(),
+        }
+    }
+
+    /// Keystore over explicit storage (tests use a temporary directory)
+    #[cfg(test)]
+    pub(crate) fn with_storage(user_id: String, storage: Storage) -> Self {
+        Self {
+            storage,
+            ..Self::new(user_id)
+        }
+    }
+
+    /// Keep encrypted secrets in `backend` instead of the regular storage
+    /// Secrets already in the regular storage are still read and move over on the next save
+    pub fn set_secure_backend(&mut self, backend: Arc<dyn SecureStorageBackend>) {
+        self.secrets = Some(backend);
+    }
+
+    /// Argon2id cost for keys saved from now on
+    pub fn set_kdf_params(&mut self, params: Argon2Params) {
+        self.kdf_params = params;
+    }
+
+    /// Keystore for another wallet slot of the same user
+    pub fn for_wallet(&self, wallet_id: &str) -> IdosResult<Self> {
+        validate_wallet_id(wallet_id)?;
+        Ok(Self {
+            storage: self.storage.clone(),
+            secrets: self.secrets.clone(),
+            user_id: self.user_id.clone(),
+            wallet_id: wallet_id.to_string(),
+            kdf_params: self.kdf_params,
+        })
+    }
+
+    /// Wallet slot this keystore reads and writes
+    pub fn wallet_id(&self) -> &str {
+        &self.wallet_id
+    }
+
+    /// Storage key for this slot; the default slot keeps the original key layout
+    fn slot_key(&self, prefix: &str) -> String {
+        if self.wallet_id == DEFAULT_WALLET_ID {
+            format!("{}{}", prefix, self.user_id)
+        } else {
+            format!("{}{}_{}", prefix, self.user_id, self.wallet_id)
         }
     }
 
     fn private_key_key(&self) -> String {
-        format!("{}{}", ENCRYPTED_PRIVATE_KEY_PREFIX, self.user_id)
+        self.slot_key(ENCRYPTED_PRIVATE_KEY_PREFIX)
     }
 
     fn seed_phrase_key(&self) -> String {
-        format!("{}{}", ENCRYPTED_SEED_PHRASE_PREFIX, self.user_id)
+        self.slot_key(ENCRYPTED_SEED_PHRASE_PREFIX)
     }
 
     fn wallet_address_key(&self) -> String {
-        format!("{}{}", WALLET_ADDRESS_PREFIX, self.user_id)
+        self.slot_key(WALLET_ADDRESS_PREFIX)
     }
 
     fn wallet_network_key(&self) -> String {
-        format!("{}{}", WALLET_NETWORK_PREFIX, self.user_id)
+        self.slot_key(WALLET_NETWORK_PREFIX)
+    }
+
+    fn watch_only_key(&self) -> String {
+        self.slot_key(WATCH_ONLY_PREFIX)
+    }
+
+    fn hardware_path_key(&self) -> String {
+        self.slot_key(HARDWARE_PATH_PREFIX)
+    }
+
+    fn biometric_unlock_key(&self) -> String {
+        self.slot_key(BIOMETRIC_UNLOCK_PREFIX)
+    }
+
+    fn chain_addresses_key(&self) -> String {
+        self.slot_key(CHAIN_ADDRESSES_PREFIX)
+    }
+
+    fn get_secret(&self, key: &str) -> IdosResult<Option<String>> {
+        if let Some(secrets) = &self.secrets {
+            if let Some(value) = secrets.get(key)? {
+                return Ok(Some(value));
+            }
+        }
+        self.storage.get(key)
+    }
+
+    /// Write a secret; with a secure backend, any copy left in the regular storage is removed
+    fn set_secret(&self, key: &str, value: &str) -> IdosResult<()> {
+        match &self.secrets {
+            Some(secrets) => {
+                secrets.set(key, value)?;
+                self.storage.remove(key)
+            }
+            None => self.storage.set(key, value),
+        }
+    }
+
+    fn remove_secret(&self, key: &str) -> IdosResult<()> {
+        if let Some(secrets) = &self.secrets {
+            secrets.remove(key)?;
+        }
+        self.storage.remove(key)
+    }
+
+    fn wallet_slots_key(&self) -> String {
+        format!("{}{}", WALLET_SLOTS_PREFIX, self.user_id)
+    }
+
+    fn active_wallet_key(&self) -> String {
+        format!("{}{}", ACTIVE_WALLET_PREFIX, self.user_id)
+    }
+
+    /// IDs of all wallet slots saved for this user
+    pub fn list_wallet_ids(&self) -> IdosResult<Vec<String>> {
+        let mut ids: Vec<String> = match self.storage.get(&self.wallet_slots_key())? {
+            Some(json) => serde_json::from_str(&json)?,
+            None => Vec::new(),
+        };
+
+        // Wallets saved before slots existed aren't in the index
+        if !ids.iter().any(|id| id == DEFAULT_WALLET_ID)
+            && self.for_wallet(DEFAULT_WALLET_ID)?.has_wallet()?
+        {
+            ids.insert(0, DEFAULT_WALLET_ID.to_string());
+        }
+
+        Ok(ids)
+    }
+
+    fn set_wallet_ids(&self, ids: &[String]) -> IdosResult<()> {
+        self.storage
+            .set(&self.wallet_slots_key(), &serde_json::to_string(ids)?)
+    }
+
+    /// Slot selected by the player, persisted across sessions
+    pub fn active_wallet_id(&self) -> IdosResult<Option<String>> {
+        self.storage.get(&self.active_wallet_key())
+    }
+
+    pub fn set_active_wallet_id(&self, wallet_id: &str) -> IdosResult<()> {
+        self.storage.set(&self.active_wallet_key(), wallet_id)
     }
 
     /// Save wallet (encrypts private key and seed phrase)
@@ -49,16 +195,86 @@ impl Keystore {
     ) -> IdosResult<()> {
         // Encrypt and save private key
         if let Some(private_key) = &wallet_info.private_key {
-            let encrypted_private_key = encryption::encrypt(private_key, password)?;
-            self.storage
-                .set(&self.private_key_key(), &encrypted_private_key)?;
+            let encrypted_private_key =
+                encryption::encrypt_with_params(private_key, password, &self.kdf_params)?;
+            self.set_secret(&self.private_key_key(), &encrypted_private_key)?;
         }
 
         // Encrypt and save seed phrase (if available)
         if let Some(seed) = seed_phrase.or(wallet_info.seed_phrase.as_deref()) {
-            let encrypted_seed_phrase = encryption::encrypt(seed, password)?;
-            self.storage
-                .set(&self.seed_phrase_key(), &encrypted_seed_phrase)?;
+            let encrypted_seed_phrase =
+                encryption::encrypt_with_params(seed, password, &self.kdf_params)?;
+            self.set_secret(&self.seed_phrase_key(), &encrypted_seed_phrase)?;
+        }
+
+        self.save_address(wallet_info)
+    }
+
+    /// Re-encrypt the private key and seed phrase of a loaded wallet under a new password
+    /// Both values are encrypted before anything is written; if a write fails, the
+    /// previous ciphertexts are restored so the old password keeps working
+    pub fn change_password(&self, wallet_info: &WalletInfo, new_password: &str) -> IdosResult<()> {
+        let private_key = wallet_info
+            .private_key
+            .as_deref()
+            .ok_or_else(|| IdosError::Wallet("Private key not found".to_string()))?;
+
+        let mut updates = vec![(
+            self.private_key_key(),
+            encryption::encrypt_with_params(private_key, new_password, &self.kdf_params)?,
+        )];
+        if let Some(seed) = &wallet_info.seed_phrase {
+            updates.push((
+                self.seed_phrase_key(),
+                encryption::encrypt_with_params(seed, new_password, &self.kdf_params)?,
+            ));
+        }
+
+        let previous = updates
+            .iter()
+            .map(|(key, _)| Ok((key.clone(), self.get_secret(key)?)))
+            .collect::<IdosResult<Vec<_>>>()?;
+
+        for (key, value) in &updates {
+            if let Err(e) = self.set_secret(key, value) {
+                for (key, old_value) in &previous {
+                    let _ = match old_value {
+                        Some(old_value) => self.set_secret(key, old_value),
+                        None => self.remove_secret(key),
+                    };
+                }
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Save an address-only wallet; nothing is encrypted since there are no keys
+    pub fn save_watch_only(&self, wallet_info: &WalletInfo) -> IdosResult<()> {
+        self.remove_secret(&self.private_key_key())?;
+        self.remove_secret(&self.seed_phrase_key())?;
+        self.storage.set(&self.watch_only_key(), "true")?;
+        self.save_address(wallet_info)
+    }
+
+    /// Save a hardware wallet: its address and the derivation path used on the device
+    pub fn save_hardware(&self, wallet_info: &WalletInfo, derivation_path: &str) -> IdosResult<()> {
+        self.remove_secret(&self.private_key_key())?;
+        self.remove_secret(&self.seed_phrase_key())?;
+        self.storage
+            .set(&self.hardware_path_key(), derivation_path)?;
+        self.save_address(wallet_info)
+    }
+
+    fn save_address(&self, wallet_info: &WalletInfo) -> IdosResult<()> {
+        // A new wallet in this slot replaces any multichain identity
+        self.storage.remove(&self.chain_addresses_key())?;
+        if !wallet_info.watch_only {
+            self.storage.remove(&self.watch_only_key())?;
+        }
+        if !wallet_info.hardware {
+            self.storage.remove(&self.hardware_path_key())?;
         }
 
         // Save wallet address and network (not encrypted)
@@ -67,6 +283,12 @@ impl Keystore {
         self.storage
             .set(&self.wallet_network_key(), wallet_info.network.as_str())?;
 
+        let mut ids = self.list_wallet_ids()?;
+        if !ids.contains(&self.wallet_id) {
+            ids.push(self.wallet_id.clone());
+            self.set_wallet_ids(&ids)?;
+        }
+
         Ok(())
     }
 
@@ -79,20 +301,25 @@ impl Keystore {
             None => return Ok(None),
         };
 
-        let network_str = self
-            .storage
-            .get(&self.wallet_network_key())?
-            .unwrap_or_else(|| "Ethereum".to_string());
-
-        let network = match network_str.as_str() {
-            "Ethereum" => BlockchainNetwork::Ethereum,
-            "Solana" => BlockchainNetwork::Solana,
-            _ => BlockchainNetwork::Ethereum,
-        };
+        let network = self.get_wallet_network()?;
+
+        // Watch-only and hardware wallets have no password
+        let watch_only = self.is_watch_only()?;
+        let hardware = self.hardware_derivation_path()?.is_some();
+        if watch_only || hardware {
+            return Ok(Some(WalletInfo {
+                address,
+                network,
+                private_key: None,
+                seed_phrase: None,
+                watch_only,
+                hardware,
+            }));
+        }
 
         // Decrypt private key
         let private_key =
-            if let Some(encrypted) = self.storage.get(&self.private_key_key())? {
+            if let Some(encrypted) = self.get_secret(&self.private_key_key())? {
                 Some(encryption::decrypt(&encrypted, password).map_err(|_| {
                     IdosError::Auth("Incorrect password for private key".to_string())
                 })?)
@@ -102,7 +329,7 @@ impl Keystore {
 
         // Decrypt seed phrase (optional)
         let seed_phrase =
-            if let Some(encrypted) = self.storage.get(&self.seed_phrase_key())? {
+            if let Some(encrypted) = self.get_secret(&self.seed_phrase_key())? {
                 Some(encryption::decrypt(&encrypted, password).map_err(|_| {
                     IdosError::Auth("Incorrect password for seed phrase".to_string())
                 })?)
@@ -115,9 +342,33 @@ impl Keystore {
             network,
             private_key,
             seed_phrase,
+            watch_only: false,
+            hardware: false,
         }))
     }
 
+    /// Whether stored keys use the legacy XOR format or a different Argon2id cost,
+    /// or still sit in the regular storage while a secure backend is set
+    /// Re-save the wallet after a successful unlock to upgrade it
+    pub fn needs_upgrade(&self) -> IdosResult<bool> {
+        for key in [self.private_key_key(), self.seed_phrase_key()] {
+            if self.secrets.is_some() && self.storage.get(&key)?.is_some() {
+                return Ok(true);
+            }
+            if let Some(encrypted) = self.get_secret(&key)? {
+                if encryption::needs_upgrade(&encrypted, &self.kdf_params) {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Replace the stored address (e.g. after fixing how it is derived)
+    pub fn set_wallet_address(&self, address: &str) -> IdosResult<()> {
+        self.storage.set(&self.wallet_address_key(), address)
+    }
+
     /// Check if wallet exists for this user
     pub fn has_wallet(&self) -> IdosResult<bool> {
         Ok(self.storage.get(&self.wallet_address_key())?.is_some())
@@ -128,17 +379,104 @@ impl Keystore {
         self.storage.get(&self.wallet_address_key())
     }
 
+    /// Whether this slot holds an address-only wallet
+    pub fn is_watch_only(&self) -> IdosResult<bool> {
+        Ok(self.storage.get(&self.watch_only_key())?.is_some())
+    }
+
+    /// Device derivation path if this slot holds a hardware wallet
+    pub fn hardware_derivation_path(&self) -> IdosResult<Option<String>> {
+        self.storage.get(&self.hardware_path_key())
+    }
+
+    /// Record the addresses a multichain seed phrase derives on each chain
+    pub fn save_chain_addresses(&self, wallets: &[&WalletInfo]) -> IdosResult<()> {
+        let addresses: std::collections::HashMap<&str, &str> = wallets
+            .iter()
+            .map(|w| (w.network.as_str(), w.address.as_str()))
+            .collect();
+        self.storage.set(
+            &self.chain_addresses_key(),
+            &serde_json::to_string(&addresses)?,
+        )
+    }
+
+    /// Address of this slot's multichain wallet on `network` (None for single-chain wallets)
+    pub fn chain_address(&self, network: BlockchainNetwork) -> IdosResult<Option<String>> {
+        let Some(json) = self.storage.get(&self.chain_addresses_key())? else {
+            return Ok(None);
+        };
+        let mut addresses: std::collections::HashMap<String, String> = serde_json::from_str(&json)?;
+        Ok(addresses.remove(network.as_str()))
+    }
+
+    /// ID under which `UnlockProvider` keeps this slot's wrapping key
+    pub fn biometric_key_id(&self) -> String {
+        format!("idos_wallet_{}", self.biometric_unlock_key())
+    }
+
+    /// Password sealed with the biometric wrapping key, if biometric unlock is enabled
+    pub fn biometric_unlock(&self) -> IdosResult<Option<String>> {
+        self.get_secret(&self.biometric_unlock_key())
+    }
+
+    pub fn save_biometric_unlock(&self, wrapped_password: &str) -> IdosResult<()> {
+        self.set_secret(&self.biometric_unlock_key(), wrapped_password)
+    }
+
+    pub fn remove_biometric_unlock(&self) -> IdosResult<()> {
+        self.remove_secret(&self.biometric_unlock_key())
+    }
+
+    /// Get wallet network without password (defaults to Ethereum)
+    pub fn get_wallet_network(&self) -> IdosResult<BlockchainNetwork> {
+        let network_str = self
+            .storage
+            .get(&self.wallet_network_key())?
+            .unwrap_or_else(|| "Ethereum".to_string());
+
+        Ok(match network_str.as_str() {
+            "Solana" => BlockchainNetwork::Solana,
+            _ => BlockchainNetwork::Ethereum,
+        })
+    }
+
     /// Delete wallet
     /// Matches Unity SDK's Disconnect functionality
     pub fn delete_wallet(&self) -> IdosResult<()> {
-        self.storage.remove(&self.private_key_key())?;
-        self.storage.remove(&self.seed_phrase_key())?;
+        self.remove_secret(&self.private_key_key())?;
+        self.remove_secret(&self.seed_phrase_key())?;
         self.storage.remove(&self.wallet_address_key())?;
         self.storage.remove(&self.wallet_network_key())?;
+        self.storage.remove(&self.watch_only_key())?;
+        self.storage.remove(&self.hardware_path_key())?;
+        self.remove_secret(&self.biometric_unlock_key())?;
+        self.storage.remove(&self.chain_addresses_key())?;
+
+        let mut ids = self.list_wallet_ids()?;
+        ids.retain(|id| id != &self.wallet_id);
+        self.set_wallet_ids(&ids)?;
         Ok(())
     }
 }
 
+/// Wallet IDs become part of storage keys (and file names on native)
+fn validate_wallet_id(wallet_id: &str) -> IdosResult<()> {
+    let valid = !wallet_id.is_empty()
+        && wallet_id.len() <= 32
+        && wallet_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(IdosError::InvalidInput(format!(
+            "Invalid wallet id '{}': use up to 32 letters, digits, '-' or '_'",
+            wallet_id
+        )))
+    }
+}
+
 #[cfg(not(feature = "wallet"))]
 pub struct Keystore;
 
@@ -148,6 +486,34 @@ impl Keystore {
         Self
     }
 
+    pub fn for_wallet(&self, _wallet_id: &str) -> IdosResult<Self> {
+        Ok(Self)
+    }
+
+    pub fn set_kdf_params(&mut self, _params: Argon2Params) {}
+
+    pub fn set_secure_backend(&mut self, _backend: Arc<dyn SecureStorageBackend>) {}
+
+    pub fn needs_upgrade(&self) -> IdosResult<bool> {
+        Ok(false)
+    }
+
+    pub fn wallet_id(&self) -> &str {
+        DEFAULT_WALLET_ID
+    }
+
+    pub fn list_wallet_ids(&self) -> IdosResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    pub fn active_wallet_id(&self) -> IdosResult<Option<String>> {
+        Ok(None)
+    }
+
+    pub fn set_active_wallet_id(&self, _wallet_id: &str) -> IdosResult<()> {
+        Ok(())
+    }
+
     pub fn save_wallet(
         &self,
         _wallet_info: &WalletInfo,
@@ -165,6 +531,46 @@ impl Keystore {
         ))
     }
 
+    pub fn change_password(
+        &self,
+        _wallet_info: &WalletInfo,
+        _new_password: &str,
+    ) -> IdosResult<()> {
+        Err(IdosError::PlatformNotSupported(
+            "Wallet feature not enabled".to_string(),
+        ))
+    }
+
+    pub fn save_watch_only(&self, _wallet_info: &WalletInfo) -> IdosResult<()> {
+        Err(IdosError::PlatformNotSupported(
+            "Wallet feature not enabled".to_string(),
+        ))
+    }
+
+    pub fn is_watch_only(&self) -> IdosResult<bool> {
+        Ok(false)
+    }
+
+    pub fn save_hardware(
+        &self,
+        _wallet_info: &WalletInfo,
+        _derivation_path: &str,
+    ) -> IdosResult<()> {
+        Err(IdosError::PlatformNotSupported(
+            "Wallet feature not enabled".to_string(),
+        ))
+    }
+
+    pub fn hardware_derivation_path(&self) -> IdosResult<Option<String>> {
+        Ok(None)
+    }
+
+    pub fn set_wallet_address(&self, _address: &str) -> IdosResult<()> {
+        Err(IdosError::PlatformNotSupported(
+            "Wallet feature not enabled".to_string(),
+        ))
+    }
+
     pub fn has_wallet(&self) -> IdosResult<bool> {
         Ok(false)
     }
@@ -173,6 +579,38 @@ impl Keystore {
         Ok(None)
     }
 
+    pub fn get_wallet_network(&self) -> IdosResult<BlockchainNetwork> {
+        Ok(BlockchainNetwork::Ethereum)
+    }
+
+    pub fn save_chain_addresses(&self, _wallets: &[&WalletInfo]) -> IdosResult<()> {
+        Err(IdosError::PlatformNotSupported(
+            "Wallet feature not enabled".to_string(),
+        ))
+    }
+
+    pub fn chain_address(&self, _network: BlockchainNetwork) -> IdosResult<Option<String>> {
+        Ok(None)
+    }
+
+    pub fn biometric_key_id(&self) -> String {
+        String::new()
+    }
+
+    pub fn biometric_unlock(&self) -> IdosResult<Option<String>> {
+        Ok(None)
+    }
+
+    pub fn save_biometric_unlock(&self, _wrapped_password: &str) -> IdosResult<()> {
+        Err(IdosError::PlatformNotSupported(
+            "Wallet feature not enabled".to_string(),
+        ))
+    }
+
+    pub fn remove_biometric_unlock(&self) -> IdosResult<()> {
+        Ok(())
+    }
+
     pub fn delete_wallet(&self) -> IdosResult<()> {
         Ok(())
     }
@@ -191,6 +629,8 @@ mod tests {
             network: BlockchainNetwork::Ethereum,
             private_key: Some("0xdeadbeef".to_string()),
             seed_phrase: Some("test seed phrase".to_string()),
+            watch_only: false,
+            hardware: false,
         };
 
         let password = "testpassword123";
@@ -216,4 +656,120 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_change_password() {
+        let dir = std::env::temp_dir().join(format!("idos_keystore_{}", std::process::id()));
+        let keystore = Keystore {
+            storage: Storage::with_directory("idos_wallet_".to_string(), dir.clone()),
+            ..Keystore::new("change_password_user".to_string())
+        };
+        let wallet_info = WalletInfo {
+            address: "0x1234567890abcdef".to_string(),
+            network: BlockchainNetwork::Ethereum,
+            private_key: Some("0xdeadbeef".to_string()),
+            seed_phrase: Some("test seed phrase".to_string()),
+            watch_only: false,
+            hardware: false,
+        };
+
+        keystore
+            .save_wallet(&wallet_info, None, "old_password")
+            .unwrap();
+        keystore
+            .change_password(&wallet_info, "new_password")
+            .unwrap();
+
+        assert!(matches!(
+            keystore.load_wallet("old_password"),
+            Err(IdosError::Auth(_))
+        ));
+        let loaded = keystore.load_wallet("new_password").unwrap().unwrap();
+        assert_eq!(loaded.private_key, wallet_info.private_key);
+        assert_eq!(loaded.seed_phrase, wallet_info.seed_phrase);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[derive(Default)]
+    struct MemoryBackend(std::sync::Mutex<std::collections::HashMap<String, String>>);
+
+    impl SecureStorageBackend for MemoryBackend {
+        fn name(&self) -> &str {
+            "memory"
+        }
+
+        fn get(&self, key: &str) -> IdosResult<Option<String>> {
+            Ok(self.0.lock().unwrap().get(key).cloned())
+        }
+
+        fn set(&self, key: &str, value: &str) -> IdosResult<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        fn remove(&self, key: &str) -> IdosResult<()> {
+            self.0.lock().unwrap().remove(key);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_secure_backend_migration() {
+        let dir = std::env::temp_dir().join(format!("idos_keystore_sb_{}", std::process::id()));
+        let storage = Storage::with_directory("idos_wallet_".to_string(), dir.clone());
+        let mut keystore = Keystore::with_storage("secure_user".to_string(), storage.clone());
+        let wallet_info = WalletInfo {
+            address: "0x1234567890abcdef".to_string(),
+            network: BlockchainNetwork::Ethereum,
+            private_key: Some("0xdeadbeef".to_string()),
+            seed_phrase: None,
+            watch_only: false,
+            hardware: false,
+        };
+        keystore
+            .save_wallet(&wallet_info, None, "password")
+            .unwrap();
+        assert!(storage.get(&keystore.private_key_key()).unwrap().is_some());
+
+        // Existing file secrets stay readable and move to the backend on the next save
+        let backend = Arc::new(MemoryBackend::default());
+        keystore.set_secure_backend(backend.clone());
+        assert!(keystore.needs_upgrade().unwrap());
+        let loaded = keystore.load_wallet("password").unwrap().unwrap();
+        keystore.save_wallet(&loaded, None, "password").unwrap();
+
+        assert!(!keystore.needs_upgrade().unwrap());
+        assert!(storage.get(&keystore.private_key_key()).unwrap().is_none());
+        assert!(backend.get(&keystore.private_key_key()).unwrap().is_some());
+        assert_eq!(
+            keystore
+                .load_wallet("password")
+                .unwrap()
+                .unwrap()
+                .private_key,
+            wallet_info.private_key
+        );
+
+        keystore.delete_wallet().unwrap();
+        assert!(backend.get(&keystore.private_key_key()).unwrap().is_none());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_wallet_slot_keys() {
+        let keystore = Keystore::new("slot_user".to_string());
+        assert_eq!(keystore.wallet_address_key(), "WalletAddress_slot_user");
+
+        let cold = keystore.for_wallet("cold").unwrap();
+        assert_eq!(cold.wallet_address_key(), "WalletAddress_slot_user_cold");
+        assert_eq!(cold.wallet_id(), "cold");
+
+        assert!(keystore.for_wallet("").is_err());
+        assert!(keystore.for_wallet("../evil").is_err());
+    }
 }
@@ -1,9 +1,27 @@
 use super::dto::*;
 /// Wallet Manager - Main interface for wallet operations
 /// Matches Unity SDK's WalletManager behavior
-use super::{creation, import, keystore::Keystore};
+use super::{
+    backend::{LocalWalletBackend, WalletBackend},
+    creation,
+    hardware::LedgerWallet,
+    import,
+    keystore::Keystore,
+    reauth::ReauthProvider,
+    secure_storage::SecureStorageBackend,
+    signing,
+    unlock::{self, UnlockProvider},
+    web3_keystore,
+};
 use crate::{IdosError, IdosResult};
 use bevy::prelude::*;
+use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+use std::time::Duration;
+use zeroize::Zeroizing;
 
 /// Wallet Manager Resource
 /// Manages wallet state and operations for both Ethereum and Solana
@@ -13,15 +31,316 @@ pub struct WalletManager {
     keystore: Keystore,
     current_wallet: Option<WalletInfo>,
     current_network: BlockchainNetwork,
+    /// Unlocked wallets of the other slots, by wallet ID; cleared when the wallet locks
+    unlocked_slots: HashMap<String, WalletInfo>,
+    /// Connected device when the current wallet is a hardware wallet
+    hardware: Option<LedgerWallet>,
+    /// Lock the wallet after this long without signing or key access
+    auto_lock_after: Option<Duration>,
+    /// Milliseconds since the last wallet activity
+    idle_ms: Arc<AtomicU64>,
+    /// Platform biometrics guarding the wrapping key for password-less login
+    unlock_provider: Option<Arc<dyn UnlockProvider>>,
+    /// Lifecycle events waiting for WalletPlugin
+    events: Arc<Mutex<Vec<WalletEvent>>>,
+    /// Extra verification before secrets are exported
+    reauth_provider: Option<Arc<dyn ReauthProvider>>,
+    /// Receives an audit event for every exported secret
+    #[cfg(feature = "analytics")]
+    audit_analytics: Option<crate::analytics::handler::AnalyticsHandler>,
 }
 
 impl WalletManager {
     pub fn new(user_id: String, default_network: BlockchainNetwork) -> Self {
+        let keystore = Keystore::new(user_id);
+
+        // Reopen the wallet slot the player last selected
+        let keystore = match keystore.active_wallet_id() {
+            Ok(Some(wallet_id)) => keystore.for_wallet(&wallet_id).unwrap_or(keystore),
+            _ => keystore,
+        };
+
         Self {
-            keystore: Keystore::new(user_id),
+            keystore,
             current_wallet: None,
             current_network: default_network,
+            unlocked_slots: HashMap::new(),
+            hardware: None,
+            auto_lock_after: None,
+            idle_ms: Arc::new(AtomicU64::new(0)),
+            unlock_provider: None,
+            events: Arc::new(Mutex::new(Vec::new())),
+            reauth_provider: None,
+            #[cfg(feature = "analytics")]
+            audit_analytics: None,
+        }
+    }
+
+    /// Keep encrypted keys and seed phrases in `backend` (e.g. `OsKeychainBackend`)
+    /// instead of plain storage; wallets saved before move over on their next `login`
+    pub fn with_storage_backend(mut self, backend: Arc<dyn SecureStorageBackend>) -> Self {
+        self.keystore.set_secure_backend(backend);
+        self
+    }
+
+    /// Argon2id cost for wallets saved from now on
+    /// Existing wallets are re-encrypted with it on their next `login`
+    pub fn set_kdf_params(&mut self, params: Argon2Params) {
+        self.keystore.set_kdf_params(params);
+    }
+
+    /// Lock the wallet (clear decrypted keys) after `timeout` without activity
+    /// Needs `WalletPlugin`, which writes a `WalletLocked` message when it happens
+    pub fn set_auto_lock(&mut self, timeout: Option<Duration>) {
+        self.auto_lock_after = timeout;
+        self.touch();
+    }
+
+    /// Reset the inactivity timer
+    pub fn touch(&self) {
+        self.idle_ms.store(0, Ordering::Relaxed);
+    }
+
+    /// Advance the inactivity timer; returns the address if the wallet was just locked
+    /// Only wallets holding decrypted keys are locked
+    pub fn tick_auto_lock(&mut self, delta: Duration) -> Option<String> {
+        let timeout = self.auto_lock_after?;
+        let wallet = self
+            .current_wallet
+            .as_ref()
+            .filter(|w| w.private_key.is_some())?;
+
+        let idle = self
+            .idle_ms
+            .fetch_add(delta.as_millis() as u64, Ordering::Relaxed)
+            + delta.as_millis() as u64;
+        if idle < timeout.as_millis() as u64 {
+            return None;
+        }
+
+        let address = wallet.address.clone();
+        self.lock(true);
+        info!("Wallet auto-locked after {:?} of inactivity", timeout);
+        Some(address)
+    }
+
+    /// Take the lifecycle events collected since the last call
+    pub fn drain_events(&self) -> Vec<WalletEvent> {
+        self.events
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default()
+    }
+
+    fn push_event(&self, event: WalletEvent) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
+    }
+
+    /// Queue `WalletCreated`/`WalletImported` and, when keys are in memory, `WalletUnlocked`
+    fn push_added(&self, wallet_info: &WalletInfo, created: bool) {
+        let wallet_id = self.keystore.wallet_id().to_string();
+        let address = wallet_info.address.clone();
+        let network = wallet_info.network;
+        self.push_event(if created {
+            WalletEvent::Created(WalletCreated {
+                wallet_id,
+                address,
+                network,
+            })
+        } else {
+            WalletEvent::Imported(WalletImported {
+                wallet_id,
+                address,
+                network,
+            })
+        });
+        if wallet_info.private_key.is_some() {
+            self.push_unlocked(wallet_info);
+        }
+    }
+
+    fn push_unlocked(&self, wallet_info: &WalletInfo) {
+        self.push_event(WalletEvent::Unlocked(WalletUnlocked {
+            address: wallet_info.address.clone(),
+            network: wallet_info.network,
+        }));
+    }
+
+    /// Clear the in-memory wallets of every slot, queueing `WalletLocked` if the active one could sign
+    fn lock(&mut self, auto_locked: bool) {
+        self.unlocked_slots.clear();
+        self.lock_current(auto_locked);
+    }
+
+    fn lock_current(&mut self, auto_locked: bool) {
+        let paired = self.hardware.take().is_some();
+        if let Some(wallet) = self.current_wallet.take() {
+            if wallet.private_key.is_some() || paired {
+                self.push_event(WalletEvent::Locked(WalletLocked {
+                    address: wallet.address,
+                    auto_locked,
+                }));
+            }
+        }
+    }
+
+    /// ID of the wallet slot that create/import/login operate on
+    pub fn active_wallet_id(&self) -> &str {
+        self.keystore.wallet_id()
+    }
+
+    /// List all wallets saved for this user
+    pub fn list_wallets(&self) -> IdosResult<Vec<WalletSummary>> {
+        let mut wallets = Vec::new();
+        for wallet_id in self.keystore.list_wallet_ids()? {
+            let keystore = self.keystore.for_wallet(&wallet_id)?;
+            if let Some(address) = keystore.get_wallet_address()? {
+                let active = wallet_id == self.active_wallet_id();
+                let unlocked = if active {
+                    self.current_wallet
+                        .as_ref()
+                        .is_some_and(|w| w.private_key.is_some())
+                } else {
+                    self.unlocked_slots.contains_key(&wallet_id)
+                };
+                wallets.push(WalletSummary {
+                    slot: WalletSlot::from_wallet_id(&wallet_id),
+                    active,
+                    unlocked,
+                    watch_only: keystore.is_watch_only()?,
+                    hardware: keystore.hardware_derivation_path()?.is_some(),
+                    network: keystore.get_wallet_network()?,
+                    wallet_id,
+                    address,
+                });
+            }
+        }
+        Ok(wallets)
+    }
+
+    /// Switch to another saved wallet
+    /// A wallet unlocked earlier in the session becomes current right away; otherwise call
+    /// `login` with the new wallet's password. The previous wallet stays unlocked in the background
+    pub fn set_active(&mut self, wallet_id: &str) -> IdosResult<()> {
+        let keystore = self.keystore.for_wallet(wallet_id)?;
+        if !keystore.has_wallet()? {
+            return Err(IdosError::Wallet(format!(
+                "No wallet named '{}'",
+                wallet_id
+            )));
+        }
+
+        self.select_slot(keystore)?;
+        if self.current_wallet.is_none() {
+            self.current_network = self.keystore.get_wallet_network()?;
+        }
+        Ok(())
+    }
+
+    /// Switch to the wallet slot of `network` and `account_index`
+    /// Returns the wallet when it is already unlocked, None when `login` is needed
+    pub fn select_active(
+        &mut self,
+        network: BlockchainNetwork,
+        account_index: u32,
+    ) -> IdosResult<Option<WalletInfo>> {
+        self.set_active(&WalletSlot::new(network, account_index).wallet_id())?;
+        Ok(self
+            .current_wallet
+            .clone()
+            .filter(|w| w.private_key.is_some()))
+    }
+
+    /// Derive the account at `account_index` on `network` from the unlocked wallet's seed
+    /// phrase and save it in its own slot, encrypted with the same password
+    /// The new account becomes active and the previous wallet stays unlocked in the background,
+    /// so a player can hold Ethereum and Solana wallets without re-importing the seed phrase
+    pub fn add_account(
+        &mut self,
+        network: BlockchainNetwork,
+        account_index: u32,
+        password: &str,
+    ) -> IdosResult<WalletInfo> {
+        let seed_phrase = self
+            .signing_wallet()?
+            .seed_phrase
+            .clone()
+            .map(Zeroizing::new)
+            .ok_or_else(|| {
+                IdosError::Wallet("Wallet was imported from a private key".to_string())
+            })?;
+        if !self.verify_password(password)? {
+            return Err(IdosError::Auth("Incorrect password".to_string()));
         }
+
+        let slot = WalletSlot::new(network, account_index);
+        let keystore = self.keystore.for_wallet(&slot.wallet_id())?;
+        if keystore.has_wallet()? {
+            return Err(IdosError::InvalidInput(format!(
+                "Wallet '{}' already exists",
+                slot.wallet_id()
+            )));
+        }
+
+        let wallet_info = creation::derive_account(&seed_phrase, network, account_index)?;
+        keystore.save_wallet(&wallet_info, Some(seed_phrase.as_str()), password)?;
+
+        self.select_slot(keystore)?;
+        self.current_wallet = Some(wallet_info.clone());
+        self.current_network = network;
+        self.touch();
+        self.push_added(&wallet_info, true);
+
+        info!(
+            "Added {} account {}: {}",
+            network.as_str(),
+            account_index,
+            wallet_info.address
+        );
+
+        Ok(wallet_info)
+    }
+
+    /// Select an empty wallet slot so the next create/import is saved next to existing wallets
+    pub fn new_wallet_slot(&mut self, wallet_id: &str) -> IdosResult<()> {
+        let keystore = self.keystore.for_wallet(wallet_id)?;
+        if keystore.has_wallet()? {
+            return Err(IdosError::InvalidInput(format!(
+                "Wallet '{}' already exists",
+                wallet_id
+            )));
+        }
+
+        self.select_slot(keystore)
+    }
+
+    fn select_slot(&mut self, keystore: Keystore) -> IdosResult<()> {
+        keystore.set_active_wallet_id(keystore.wallet_id())?;
+
+        // Software wallets stay unlocked in the background; hardware wallets disconnect
+        if self.hardware.is_none() {
+            if let Some(wallet) = self.current_wallet.take() {
+                if wallet.private_key.is_some() {
+                    self.unlocked_slots
+                        .insert(self.active_wallet_id().to_string(), wallet);
+                } else {
+                    self.current_wallet = Some(wallet);
+                }
+            }
+        }
+        let restored = self.unlocked_slots.remove(keystore.wallet_id());
+        self.lock_current(false);
+        self.keystore = keystore;
+
+        if let Some(wallet) = restored {
+            self.current_network = wallet.network;
+            self.current_wallet = Some(wallet);
+            self.touch();
+        }
+        info!("Active wallet slot: {}", self.active_wallet_id());
+        Ok(())
     }
 
     /// Get current wallet address
@@ -30,20 +349,113 @@ impl WalletManager {
         self.current_wallet.as_ref().map(|w| w.address.clone())
     }
 
-    /// Get current private key (only when unlocked)
-    /// Matches Unity SDK's WalletManager.PrivateKey
-    pub fn private_key(&self) -> Option<String> {
-        self.current_wallet
-            .as_ref()
-            .and_then(|w| w.private_key.clone())
+    /// Export the active wallet's private key after checking the password again
+    /// Runs the `ReauthProvider`, if set, and records an audit analytics event
+    pub async fn export_private_key(&mut self, password: &str) -> IdosResult<Zeroizing<String>> {
+        self.export_secret(password, SecretKind::PrivateKey).await
+    }
+
+    /// Export the active wallet's seed phrase after checking the password again
+    /// Runs the `ReauthProvider`, if set, and records an audit analytics event
+    pub async fn export_seed_phrase(&mut self, password: &str) -> IdosResult<Zeroizing<String>> {
+        self.export_secret(password, SecretKind::SeedPhrase).await
+    }
+
+    /// Extra verification run by `export_private_key` / `export_seed_phrase`
+    pub fn set_reauth_provider(&mut self, provider: Arc<dyn ReauthProvider>) {
+        self.reauth_provider = Some(provider);
+    }
+
+    /// Send a `wallet_secret_exported` event for every export
+    #[cfg(feature = "analytics")]
+    pub fn set_audit_analytics(&mut self, analytics: crate::analytics::handler::AnalyticsHandler) {
+        self.audit_analytics = Some(analytics);
+    }
+
+    async fn export_secret(
+        &mut self,
+        password: &str,
+        kind: SecretKind,
+    ) -> IdosResult<Zeroizing<String>> {
+        self.touch();
+        // Decrypt from storage so the password is checked even while the wallet is unlocked
+        let WalletInfo {
+            address,
+            network,
+            private_key,
+            seed_phrase,
+            ..
+        } = self.load_verified(password)?;
+        let private_key = private_key.map(Zeroizing::new);
+        let seed_phrase = seed_phrase.map(Zeroizing::new);
+        if private_key.is_none() {
+            return Err(IdosError::Wallet("Wallet has no private key".to_string()));
+        }
+
+        let secret = match kind {
+            SecretKind::PrivateKey => private_key,
+            SecretKind::SeedPhrase => seed_phrase,
+        }
+        .ok_or_else(|| IdosError::Wallet("Wallet was imported from a private key".to_string()))?;
+
+        if let Some(reauth) = &self.reauth_provider {
+            reauth.reauthenticate(kind).await?;
+        }
+
+        warn!("Exported {} of wallet {}", kind.as_str(), address);
+        #[cfg(feature = "analytics")]
+        if let Some(analytics) = &self.audit_analytics {
+            let mut properties = std::collections::HashMap::new();
+            properties.insert("kind".to_string(), kind.as_str().into());
+            properties.insert("address".to_string(), address.into());
+            properties.insert("network".to_string(), network.as_str().into());
+            properties.insert("wallet_id".to_string(), self.keystore.wallet_id().into());
+            analytics
+                .track_event("wallet_secret_exported", properties)
+                .await?;
+        }
+
+        Ok(secret)
     }
 
-    /// Get current seed phrase (only when unlocked)
-    /// Matches Unity SDK's WalletManager.SeedPhrase
-    pub fn seed_phrase(&self) -> Option<String> {
+    /// Export the unlocked Solana wallet as a Solana CLI keypair file (`id.json`)
+    pub fn export_solana_keypair_json(&self) -> IdosResult<String> {
+        let wallet = self.signing_wallet()?;
+        if wallet.network != BlockchainNetwork::Solana {
+            return Err(IdosError::Wallet(
+                "Only Solana wallets can be exported as keypair files".to_string(),
+            ));
+        }
+
+        let private_key = wallet
+            .private_key
+            .as_deref()
+            .ok_or_else(|| IdosError::Wallet("Wallet has no private key".to_string()))?;
+        import::solana_keypair_to_json(private_key)
+    }
+
+    /// Export the unlocked Ethereum wallet as a Web3 Secret Storage keystore file
+    /// The file can be opened by geth, MetaMask and other Ethereum tooling
+    pub fn export_keystore_json(&self, password: &str) -> IdosResult<String> {
+        let wallet = self.signing_wallet()?;
+        if wallet.network != BlockchainNetwork::Ethereum {
+            return Err(IdosError::Wallet(
+                "Only Ethereum wallets can be exported as keystore files".to_string(),
+            ));
+        }
+
+        let private_key = wallet
+            .private_key
+            .as_deref()
+            .ok_or_else(|| IdosError::Wallet("Wallet has no private key".to_string()))?;
+        web3_keystore::encrypt_keystore(private_key, &wallet.address, password)
+    }
+
+    /// Whether the unlocked wallet was created or imported from a seed phrase
+    pub fn has_seed_phrase(&self) -> bool {
         self.current_wallet
             .as_ref()
-            .and_then(|w| w.seed_phrase.clone())
+            .is_some_and(|w| w.seed_phrase.is_some())
     }
 
     /// Check if wallet is connected/unlocked
@@ -51,6 +463,112 @@ impl WalletManager {
         self.current_wallet.is_some()
     }
 
+    /// Whether the current wallet is address-only and can't sign
+    pub fn is_watch_only(&self) -> bool {
+        self.current_wallet.as_ref().is_some_and(|w| w.watch_only)
+    }
+
+    /// Add an external address (e.g. a MetaMask or Phantom wallet) to view its balances and NFTs
+    /// Saved in the active slot without a password; signing returns `IdosError::WatchOnly`
+    pub fn add_watch_only(&mut self, address: &str) -> IdosResult<WalletInfo> {
+        let wallet_info = import::watch_only_wallet(address, self.current_network)?;
+
+        self.keystore.save_watch_only(&wallet_info)?;
+        self.current_wallet = Some(wallet_info.clone());
+        self.hardware = None;
+        self.push_added(&wallet_info, false);
+
+        info!(
+            "Added watch-only {} wallet: {}",
+            self.current_network.as_str(),
+            wallet_info.address
+        );
+
+        Ok(wallet_info)
+    }
+
+    /// Use a connected Ledger as the active slot's wallet
+    /// Saved without a password; after `login`, pair the device again to sign
+    pub fn pair_hardware_wallet(&mut self, ledger: LedgerWallet) -> IdosResult<WalletInfo> {
+        let wallet_info = WalletInfo {
+            address: ledger.address(),
+            network: ledger.network(),
+            private_key: None,
+            seed_phrase: None,
+            watch_only: false,
+            hardware: true,
+        };
+
+        self.keystore
+            .save_hardware(&wallet_info, ledger.derivation_path())?;
+        self.current_wallet = Some(wallet_info.clone());
+        self.current_network = wallet_info.network;
+        self.hardware = Some(ledger);
+        self.push_added(&wallet_info, false);
+
+        info!(
+            "Paired {} hardware wallet: {}",
+            self.current_network.as_str(),
+            wallet_info.address
+        );
+
+        Ok(wallet_info)
+    }
+
+    /// Derivation path to reconnect the active slot's hardware wallet with
+    pub fn hardware_derivation_path(&self) -> IdosResult<Option<String>> {
+        self.keystore.hardware_derivation_path()
+    }
+
+    /// Whether the current wallet's keys live on a hardware device
+    pub fn is_hardware(&self) -> bool {
+        self.current_wallet.as_ref().is_some_and(|w| w.hardware)
+    }
+
+    /// Signing backend of the current wallet: in-memory keys or the paired device
+    /// None while locked, for watch-only wallets, or before the hardware device is paired
+    pub fn backend(&self) -> Option<Arc<dyn WalletBackend>> {
+        self.touch();
+        if let Some(ledger) = &self.hardware {
+            return Some(Arc::new(ledger.clone()));
+        }
+
+        self.current_wallet
+            .as_ref()
+            .filter(|w| w.private_key.is_some())
+            .map(|w| Arc::new(LocalWalletBackend::new(w.clone())) as Arc<dyn WalletBackend>)
+    }
+
+    /// Sign an arbitrary message with the unlocked wallet
+    /// EIP-191 for Ethereum wallets, Ed25519 for Solana wallets
+    /// Hardware wallets sign asynchronously through `backend()`
+    pub fn sign_message(&self, message: &[u8]) -> IdosResult<MessageSignature> {
+        self.touch();
+        let wallet = self
+            .current_wallet
+            .as_ref()
+            .ok_or(IdosError::WalletLocked)?;
+        signing::sign_message(wallet, message)
+    }
+
+    /// Unlocked wallet that holds keys
+    fn signing_wallet(&self) -> IdosResult<&WalletInfo> {
+        self.touch();
+        let wallet = self
+            .current_wallet
+            .as_ref()
+            .ok_or_else(|| IdosError::Wallet("Wallet is locked".to_string()))?;
+        if wallet.watch_only {
+            return Err(IdosError::WatchOnly(wallet.address.clone()));
+        }
+        if wallet.hardware {
+            return Err(IdosError::Wallet(
+                "Hardware wallet keys never leave the device".to_string(),
+            ));
+        }
+        Ok(wallet)
+    }
+
     /// Get current network
     pub fn current_network(&self) -> BlockchainNetwork {
         self.current_network
@@ -67,6 +585,17 @@ impl WalletManager {
         &mut self,
         password: &str,
         word_count: usize,
+    ) -> IdosResult<WalletCreationResult> {
+        let path = creation::default_derivation_path(self.current_network, 0);
+        self.create_wallet_with_path(password, word_count, &path)
+    }
+
+    /// Create a new wallet with random mnemonic, deriving the key at a custom BIP-44 path
+    pub fn create_wallet_with_path(
+        &mut self,
+        password: &str,
+        word_count: usize,
+        derivation_path: &str,
     ) -> IdosResult<WalletCreationResult> {
         if password.len() < 6 {
             return Err(IdosError::InvalidInput(
@@ -75,7 +604,8 @@ impl WalletManager {
         }
 
         // Generate new wallet
-        let result = creation::generate_wallet(self.current_network, word_count)?;
+        let result =
+            creation::generate_wallet_with_path(self.current_network, word_count, derivation_path)?;
 
         // Save encrypted wallet
         self.keystore
@@ -83,6 +613,9 @@ impl WalletManager {
 
         // Set as current wallet
         self.current_wallet = Some(result.wallet_info.clone());
+        self.hardware = None;
+        self.touch();
+        self.push_added(&result.wallet_info, true);
 
         info!(
             "Created new {} wallet: {}",
@@ -93,6 +626,99 @@ impl WalletManager {
         Ok(result)
     }
 
+    /// Create one seed phrase with an Ethereum and a Solana identity (account 0 of each)
+    /// The current network's identity becomes active; use `switch_chain` for the other
+    pub fn create_multichain_wallet(
+        &mut self,
+        password: &str,
+        word_count: usize,
+    ) -> IdosResult<MultichainWalletResult> {
+        if password.len() < 6 {
+            return Err(IdosError::InvalidInput(
+                "Password must be at least 6 characters".to_string(),
+            ));
+        }
+
+        let primary = creation::generate_wallet(self.current_network, word_count)?;
+        let other_network = match self.current_network {
+            BlockchainNetwork::Ethereum => BlockchainNetwork::Solana,
+            BlockchainNetwork::Solana => BlockchainNetwork::Ethereum,
+        };
+        let other = creation::derive_wallet_from_mnemonic(&primary.seed_phrase, other_network)?;
+
+        self.keystore
+            .save_wallet(&primary.wallet_info, Some(&primary.seed_phrase), password)?;
+        self.keystore
+            .save_chain_addresses(&[&primary.wallet_info, &other])?;
+
+        self.current_wallet = Some(primary.wallet_info.clone());
+        self.hardware = None;
+        self.touch();
+        self.push_added(&primary.wallet_info, true);
+
+        info!(
+            "Created multichain wallet: {} / {}",
+            primary.wallet_info.address, other.address
+        );
+
+        let (ethereum, solana) = match self.current_network {
+            BlockchainNetwork::Ethereum => (primary.wallet_info, other),
+            BlockchainNetwork::Solana => (other, primary.wallet_info),
+        };
+        Ok(MultichainWalletResult {
+            ethereum,
+            solana,
+            seed_phrase: primary.seed_phrase,
+        })
+    }
+
+    /// Address of the active slot on `network`, readable without the password
+    /// Single-chain wallets only have an address on their own network
+    pub fn chain_address(&self, network: BlockchainNetwork) -> IdosResult<Option<String>> {
+        if let Some(address) = self.keystore.chain_address(network)? {
+            return Ok(Some(address));
+        }
+        if self.keystore.has_wallet()? && self.keystore.get_wallet_network()? == network {
+            return self.keystore.get_wallet_address();
+        }
+        Ok(None)
+    }
+
+    /// Make the unlocked multichain wallet's identity on `network` current
+    /// Keys are re-derived from the seed phrase; `login` always starts on the primary chain
+    pub fn switch_chain(&mut self, network: BlockchainNetwork) -> IdosResult<WalletInfo> {
+        let wallet = self.signing_wallet()?;
+        if wallet.network == network {
+            return Ok(wallet.clone());
+        }
+
+        let expected = self.keystore.chain_address(network)?.ok_or_else(|| {
+            IdosError::Wallet(format!("Wallet has no {} identity", network.as_str()))
+        })?;
+        let seed_phrase = wallet
+            .seed_phrase
+            .as_deref()
+            .ok_or_else(|| IdosError::Wallet("Seed phrase not available".to_string()))?;
+
+        let derived = creation::derive_wallet_from_mnemonic(seed_phrase, network)?;
+        if derived.address != expected {
+            return Err(IdosError::Wallet(format!(
+                "Derived {} address does not match the stored one",
+                network.as_str()
+            )));
+        }
+
+        self.current_wallet = Some(derived.clone());
+        self.current_network = network;
+        info!(
+            "Switched to {} identity: {}",
+            network.as_str(),
+            derived.address
+        );
+
+        Ok(derived)
+    }
+
     /// Import wallet from seed phrase or private key
     /// Matches Unity SDK's WalletImportManager.OnImportButtonClick
     pub fn import_wallet(
@@ -111,8 +737,14 @@ impl WalletManager {
 
         // Extract seed phrase if it was from seed phrase import
         let seed_phrase = match source {
-            ImportSource::SeedPhrase(ref phrase) => Some(phrase.as_str()),
-            ImportSource::PrivateKey(_) => None,
+            ImportSource::SeedPhrase(ref phrase)
+            | ImportSource::SeedPhraseWithPath {
+                seed_phrase: ref phrase,
+                ..
+            } => Some(phrase.as_str()),
+            ImportSource::PrivateKey(_)
+            | ImportSource::SolanaKeypairJson(_)
+            | ImportSource::Web3Keystore { .. } => None,
         };
 
         // Save encrypted wallet
@@ -121,6 +753,9 @@ impl WalletManager {
 
         // Set as current wallet
         self.current_wallet = Some(wallet_info.clone());
+        self.hardware = None;
+        self.touch();
+        self.push_added(&wallet_info, false);
 
         info!(
             "Imported {} wallet: {}",
@@ -131,35 +766,226 @@ impl WalletManager {
         Ok(wallet_info)
     }
 
+    /// Derive another account from the unlocked wallet's seed phrase (default BIP-44 path)
+    /// The account is returned for display or signing; the active wallet doesn't change
+    pub fn derive_account(&self, account_index: u32) -> IdosResult<WalletInfo> {
+        let wallet = self.signing_wallet()?;
+        let seed_phrase = wallet.seed_phrase.as_deref().ok_or_else(|| {
+            IdosError::Wallet("Wallet was imported from a private key".to_string())
+        })?;
+
+        creation::derive_account(seed_phrase, wallet.network, account_index)
+    }
+
+    /// Derive the account at a custom BIP-44 path (e.g. "m/44'/60'/1'/0/0" for Ledger Live
+    /// style Ethereum accounts) from the unlocked wallet's seed phrase
+    pub fn derive_account_at_path(&self, derivation_path: &str) -> IdosResult<WalletInfo> {
+        let wallet = self.signing_wallet()?;
+        let seed_phrase = wallet.seed_phrase.as_deref().ok_or_else(|| {
+            IdosError::Wallet("Wallet was imported from a private key".to_string())
+        })?;
+
+        creation::derive_wallet_from_mnemonic_with_path(
+            seed_phrase,
+            wallet.network,
+            derivation_path,
+        )
+    }
+
     /// Login to existing wallet with password
     /// Matches Unity SDK's InGameWallet.Login
     pub fn login(&mut self, password: &str) -> IdosResult<WalletInfo> {
-        let wallet_info = self
-            .keystore
-            .load_wallet(password)?
-            .ok_or_else(|| IdosError::Wallet("No wallet found".to_string()))?;
+        let wallet_info = self.load_verified(password)?;
+
+        if wallet_info.private_key.is_some() && self.keystore.needs_upgrade()? {
+            // Only the ciphertexts are rewritten; `save_wallet` would also reset the
+            // slot's metadata, dropping a multichain wallet's other identity
+            self.keystore.change_password(&wallet_info, password)?;
+            info!("Upgraded wallet encryption to Argon2id");
+        }
 
         self.current_wallet = Some(wallet_info.clone());
         self.current_network = wallet_info.network;
+        self.touch();
+        self.push_unlocked(&wallet_info);
 
         info!("Logged into wallet: {}", wallet_info.address);
 
         Ok(wallet_info)
     }
 
+    /// Change the password protecting the active wallet
+    /// Re-encrypts the private key and seed phrase; on failure the old password still works
+    pub fn change_password(&mut self, old_password: &str, new_password: &str) -> IdosResult<()> {
+        if new_password.len() < 6 {
+            return Err(IdosError::InvalidInput(
+                "Password must be at least 6 characters".to_string(),
+            ));
+        }
+        if self.keystore.is_watch_only()? || self.keystore.hardware_derivation_path()?.is_some() {
+            return Err(IdosError::Wallet(
+                "Watch-only and hardware wallets have no password".to_string(),
+            ));
+        }
+
+        let wallet_info = self.load_verified(old_password)?;
+        self.keystore.change_password(&wallet_info, new_password)?;
+        // The sealed password is stale now; re-enabling needs a biometric prompt
+        self.keystore.remove_biometric_unlock()?;
+
+        info!("Changed password for wallet: {}", wallet_info.address);
+        Ok(())
+    }
+
+    /// Platform biometrics used by `enable_biometric_unlock` and `login_with_biometrics`
+    pub fn set_unlock_provider(&mut self, provider: Arc<dyn UnlockProvider>) {
+        self.unlock_provider = Some(provider);
+    }
+
+    /// Whether the active slot can be unlocked with biometrics
+    pub fn has_biometric_unlock(&self) -> IdosResult<bool> {
+        Ok(self.unlock_provider.is_some() && self.keystore.biometric_unlock()?.is_some())
+    }
+
+    /// Seal the wallet password with a new device-bound key kept behind biometrics
+    pub async fn enable_biometric_unlock(&mut self, password: &str) -> IdosResult<()> {
+        let provider = self.available_unlock_provider().await?;
+
+        let wallet_info = self.load_verified(password)?;
+        if wallet_info.private_key.is_none() {
+            return Err(IdosError::Wallet(
+                "Watch-only and hardware wallets have no password".to_string(),
+            ));
+        }
+
+        let wrapping_key = unlock::generate_wrapping_key();
+        let wrapped = unlock::wrap_password(password, &wrapping_key)?;
+        provider
+            .store_secret(&self.keystore.biometric_key_id(), &wrapping_key)
+            .await?;
+        self.keystore.save_biometric_unlock(&wrapped)?;
+
+        info!(
+            "Enabled biometric unlock for wallet: {}",
+            wallet_info.address
+        );
+        Ok(())
+    }
+
+    /// Forget the sealed password and the device-bound key
+    pub async fn disable_biometric_unlock(&mut self) -> IdosResult<()> {
+        self.keystore.remove_biometric_unlock()?;
+        if let Some(provider) = &self.unlock_provider {
+            provider
+                .delete_secret(&self.keystore.biometric_key_id())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Login after a biometric check instead of typing the password
+    /// `BiometricUnavailable` means the game should fall back to `login(password)`
+    pub async fn login_with_biometrics(&mut self) -> IdosResult<WalletInfo> {
+        let provider = self.available_unlock_provider().await?;
+        let wrapped = self.keystore.biometric_unlock()?.ok_or_else(|| {
+            IdosError::BiometricUnavailable("not enabled for this wallet".to_string())
+        })?;
+
+        let wrapping_key = match provider
+            .load_secret(&self.keystore.biometric_key_id())
+            .await
+        {
+            Ok(Some(key)) => key,
+            Ok(None) => {
+                // Key was removed from the device (e.g. biometrics re-enrolled)
+                self.keystore.remove_biometric_unlock()?;
+                return Err(IdosError::BiometricUnavailable(
+                    "device key missing".to_string(),
+                ));
+            }
+            Err(e) => return Err(IdosError::BiometricUnavailable(e.to_string())),
+        };
+
+        let login = unlock::unwrap_password(&wrapped, &wrapping_key)
+            .and_then(|password| self.login(&password));
+        match login {
+            Err(IdosError::Auth(_)) => {
+                self.keystore.remove_biometric_unlock()?;
+                Err(IdosError::BiometricUnavailable(
+                    "stored password is out of date".to_string(),
+                ))
+            }
+            result => result,
+        }
+    }
+
+    async fn available_unlock_provider(&self) -> IdosResult<Arc<dyn UnlockProvider>> {
+        let provider = self
+            .unlock_provider
+            .clone()
+            .ok_or_else(|| IdosError::BiometricUnavailable("no unlock provider set".to_string()))?;
+        if !provider.is_available().await {
+            return Err(IdosError::BiometricUnavailable(
+                "biometrics not available on this device".to_string(),
+            ));
+        }
+        Ok(provider)
+    }
+
+    /// Decrypt the stored wallet and check the key matches its address
+    fn load_verified(&mut self, password: &str) -> IdosResult<WalletInfo> {
+        let mut wallet_info = self
+            .keystore
+            .load_wallet(password)?
+            .ok_or_else(|| IdosError::Wallet("No wallet found".to_string()))?;
+
+        if let Some(private_key) = wallet_info.private_key.clone() {
+            // The legacy XOR format can't detect a wrong password; a garbled key won't derive the address
+            let derived =
+                import::import_wallet(ImportSource::PrivateKey(private_key), wallet_info.network)
+                    .map_err(|_| IdosError::Auth("Incorrect password".to_string()))?;
+
+            if derived.address != wallet_info.address {
+                // Older versions stored Ethereum addresses hashed with SHA-256 instead of Keccak-256
+                if wallet_info.network != BlockchainNetwork::Ethereum {
+                    return Err(IdosError::Auth("Incorrect password".to_string()));
+                }
+                warn!(
+                    "Correcting stored wallet address {} to {}",
+                    wallet_info.address, derived.address
+                );
+                self.keystore.set_wallet_address(&derived.address)?;
+                wallet_info.address = derived.address;
+            }
+        }
+
+        Ok(wallet_info)
+    }
+
     /// Logout (clear in-memory wallet data but keep encrypted storage)
     /// Matches Unity SDK's WalletManager.NulledPrivateKey
     pub fn logout(&mut self) {
-        self.current_wallet = None;
+        self.lock(false);
         info!("Logged out from wallet");
     }
 
-    /// Disconnect (delete wallet completely)
+    /// Disconnect (delete the active wallet completely)
     /// Matches Unity SDK's WalletManager.Disconnect
+    /// Another saved wallet, if any, becomes active (still locked)
     pub fn disconnect(&mut self) -> IdosResult<()> {
+        let address = self.keystore.get_wallet_address()?;
         self.keystore.delete_wallet()?;
         self.current_wallet = None;
+        self.hardware = None;
+        self.push_event(WalletEvent::Deleted(WalletDeleted {
+            wallet_id: self.keystore.wallet_id().to_string(),
+            address,
+        }));
         info!("Wallet disconnected and deleted");
+
+        if let Some(next) = self.keystore.list_wallet_ids()?.into_iter().next() {
+            self.set_active(&next)?;
+        }
         Ok(())
     }
 
@@ -197,8 +1023,409 @@ impl WalletManager {
     }
 }
 
+/// Lets `EthereumWalletService::with_wallet` sign without copying the key out
+#[cfg(feature = "crypto_ethereum")]
+impl crate::crypto_ethereum::EthereumSignerSource for WalletManager {
+    fn ethereum_signer(&self, chain_id: u64) -> IdosResult<crate::crypto_ethereum::EthereumSigner> {
+        use ethers::signers::{LocalWallet, Signer};
+
+        self.touch();
+
+        let wallet = self
+            .current_wallet
+            .as_ref()
+            .ok_or(IdosError::WalletLocked)?;
+        if wallet.watch_only {
+            return Err(IdosError::WatchOnly(wallet.address.clone()));
+        }
+        if wallet.network != BlockchainNetwork::Ethereum {
+            return Err(IdosError::Wallet(
+                "Current wallet is not an Ethereum wallet".to_string(),
+            ));
+        }
+        if wallet.hardware {
+            let ledger = self.hardware.as_ref().ok_or_else(|| {
+                IdosError::Wallet("Connect the hardware wallet to sign".to_string())
+            })?;
+            return Ok(crate::crypto_ethereum::EthereumSigner::Ledger(
+                ledger.ethereum_signer(chain_id)?,
+            ));
+        }
+
+        let private_key = wallet
+            .private_key
+            .as_deref()
+            .ok_or(IdosError::WalletLocked)?;
+        let local_wallet: LocalWallet = private_key
+            .trim_start_matches("0x")
+            .parse()
+            .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
+
+        Ok(crate::crypto_ethereum::EthereumSigner::Local(
+            local_wallet.with_chain_id(chain_id),
+        ))
+    }
+}
+
+/// Lets `AuthHandler::login_solana_wallet` sign in with the unlocked Solana wallet
+#[cfg(feature = "crypto_solana")]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl crate::crypto_solana::SolanaMessageSigner for WalletManager {
+    async fn solana_address(&self) -> IdosResult<String> {
+        let wallet = self
+            .current_wallet
+            .as_ref()
+            .ok_or(IdosError::WalletLocked)?;
+        if wallet.network != BlockchainNetwork::Solana {
+            return Err(IdosError::Wallet(
+                "Current wallet is not a Solana wallet".to_string(),
+            ));
+        }
+        Ok(wallet.address.clone())
+    }
+
+    async fn sign_solana_message(
+        &self,
+        message: &[u8],
+    ) -> IdosResult<crate::crypto_solana::SolanaSignedMessage> {
+        self.solana_address().await?;
+        if let Some(wallet) = self.current_wallet.as_ref().filter(|w| w.watch_only) {
+            return Err(IdosError::WatchOnly(wallet.address.clone()));
+        }
+
+        // Goes through the Ledger when one is connected
+        let backend = self.backend().ok_or(IdosError::WalletLocked)?;
+        let signed = backend.sign_message(message).await?;
+        Ok(crate::crypto_solana::SolanaSignedMessage {
+            public_key: signed.address,
+            signature: signed.signature,
+        })
+    }
+}
+
 impl Default for WalletManager {
     fn default() -> Self {
         Self::new("default_user".to_string(), BlockchainNetwork::Ethereum)
     }
 }
+
+#[cfg(all(test, feature = "wallet"))]
+mod tests {
+    use super::*;
+    use crate::storage::Storage;
+    use crate::wallet::import::import_wallet;
+    use std::collections::HashMap;
+    use std::sync::{atomic::AtomicBool, Mutex};
+
+    /// Manager persisting to a temporary directory, with cheap key derivation
+    fn temp_manager(name: &str) -> (WalletManager, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("idos_{}_{}", name, std::process::id()));
+        let user_id = format!("{}_user", name);
+        let mut manager = WalletManager {
+            keystore: Keystore::with_storage(
+                user_id.clone(),
+                Storage::with_directory("idos_wallet_".to_string(), dir.clone()),
+            ),
+            ..WalletManager::new(user_id, BlockchainNetwork::Ethereum)
+        };
+        manager.set_kdf_params(Argon2Params {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        });
+        (manager, dir)
+    }
+
+    #[derive(Default)]
+    struct MockBiometrics {
+        secrets: Mutex<HashMap<String, Vec<u8>>>,
+        reject: AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl UnlockProvider for MockBiometrics {
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        async fn store_secret(&self, key_id: &str, secret: &[u8]) -> IdosResult<()> {
+            self.secrets
+                .lock()
+                .unwrap()
+                .insert(key_id.to_string(), secret.to_vec());
+            Ok(())
+        }
+
+        async fn load_secret(&self, key_id: &str) -> IdosResult<Option<Vec<u8>>> {
+            if self.reject.load(Ordering::Relaxed) {
+                return Err(IdosError::Auth("Fingerprint not recognized".to_string()));
+            }
+            Ok(self.secrets.lock().unwrap().get(key_id).cloned())
+        }
+
+        async fn delete_secret(&self, key_id: &str) -> IdosResult<()> {
+            self.secrets.lock().unwrap().remove(key_id);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_auto_lock() {
+        let mut manager = WalletManager::new("auto_lock".to_string(), BlockchainNetwork::Solana);
+        manager.current_wallet = Some(
+            import_wallet(
+                ImportSource::PrivateKey(bs58::encode([7u8; 32]).into_string()),
+                BlockchainNetwork::Solana,
+            )
+            .unwrap(),
+        );
+        let address = manager.wallet_address();
+
+        // Disabled by default
+        assert_eq!(manager.tick_auto_lock(Duration::from_secs(3600)), None);
+
+        manager.set_auto_lock(Some(Duration::from_secs(60)));
+        assert_eq!(manager.tick_auto_lock(Duration::from_secs(40)), None);
+        manager.sign_message(b"still here").unwrap();
+        assert_eq!(manager.tick_auto_lock(Duration::from_secs(40)), None);
+        assert_eq!(manager.tick_auto_lock(Duration::from_secs(40)), address);
+        assert!(matches!(
+            manager.drain_events().as_slice(),
+            [WalletEvent::Locked(WalletLocked {
+                auto_locked: true,
+                ..
+            })]
+        ));
+
+        assert!(!manager.is_connected());
+        assert!(matches!(
+            manager.sign_message(b"locked"),
+            Err(IdosError::WalletLocked)
+        ));
+        assert_eq!(manager.tick_auto_lock(Duration::from_secs(3600)), None);
+    }
+
+    #[tokio::test]
+    async fn test_biometric_unlock() {
+        let (mut manager, dir) = temp_manager("biometric");
+        let address = manager
+            .create_wallet("password1", 12)
+            .unwrap()
+            .wallet_info
+            .address;
+
+        let biometrics = Arc::new(MockBiometrics::default());
+        manager.set_unlock_provider(biometrics.clone());
+        manager.enable_biometric_unlock("password1").await.unwrap();
+        assert!(manager.has_biometric_unlock().unwrap());
+
+        manager.logout();
+        let unlocked = manager.login_with_biometrics().await.unwrap();
+        assert_eq!(unlocked.address, address);
+        assert!(manager.backend().is_some());
+
+        // A failed prompt falls back to the password
+        manager.logout();
+        biometrics.reject.store(true, Ordering::Relaxed);
+        assert!(matches!(
+            manager.login_with_biometrics().await,
+            Err(IdosError::BiometricUnavailable(_))
+        ));
+        manager.login("password1").unwrap();
+
+        manager.change_password("password1", "password2").unwrap();
+        assert!(!manager.has_biometric_unlock().unwrap());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_multichain_wallet() {
+        let (mut manager, dir) = temp_manager("multichain");
+
+        let created = manager.create_multichain_wallet("password1", 12).unwrap();
+        assert_eq!(
+            created.solana.address,
+            creation::derive_wallet_from_mnemonic(&created.seed_phrase, BlockchainNetwork::Solana)
+                .unwrap()
+                .address
+        );
+        assert_eq!(
+            manager.chain_address(BlockchainNetwork::Solana).unwrap(),
+            Some(created.solana.address.clone())
+        );
+        assert_eq!(
+            manager.chain_address(BlockchainNetwork::Ethereum).unwrap(),
+            Some(created.ethereum.address.clone())
+        );
+
+        manager.logout();
+        manager.login("password1").unwrap();
+        let solana = manager.switch_chain(BlockchainNetwork::Solana).unwrap();
+        assert_eq!(solana.address, created.solana.address);
+        assert_eq!(
+            manager.sign_message(b"hi").unwrap().network,
+            BlockchainNetwork::Solana
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_wallet_slots() {
+        let (mut manager, dir) = temp_manager("slots");
+        let created = manager.create_wallet("password1", 12).unwrap();
+
+        assert!(manager
+            .add_account(BlockchainNetwork::Solana, 0, "wrong_password")
+            .is_err());
+        let solana = manager
+            .add_account(BlockchainNetwork::Solana, 0, "password1")
+            .unwrap();
+        assert_eq!(manager.active_wallet_id(), "solana_0");
+        assert_eq!(manager.current_network(), BlockchainNetwork::Solana);
+
+        let wallets = manager.list_wallets().unwrap();
+        assert_eq!(wallets.len(), 2);
+        assert!(wallets.iter().all(|w| w.unlocked));
+        assert!(wallets
+            .iter()
+            .any(|w| w.active && w.slot == Some(WalletSlot::new(BlockchainNetwork::Solana, 0))));
+
+        // Switching back needs no password
+        manager.set_active("default").unwrap();
+        assert_eq!(
+            manager.sign_message(b"hi").unwrap().address,
+            created.wallet_info.address
+        );
+        let restored = manager
+            .select_active(BlockchainNetwork::Solana, 0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.address, solana.address);
+
+        // Locking clears every slot
+        manager.logout();
+        manager.set_active("default").unwrap();
+        assert!(manager
+            .select_active(BlockchainNetwork::Solana, 0)
+            .unwrap()
+            .is_none());
+        assert_eq!(manager.login("password1").unwrap().address, solana.address);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_derive_account_at_path() {
+        let (mut manager, dir) = temp_manager("derive_path");
+        manager.create_wallet("password1", 12).unwrap();
+
+        let second = manager.derive_account(2).unwrap();
+        let by_path = manager
+            .derive_account_at_path(&creation::default_derivation_path(
+                BlockchainNetwork::Ethereum,
+                2,
+            ))
+            .unwrap();
+        assert_eq!(by_path.address, second.address);
+        assert_ne!(
+            manager
+                .derive_account_at_path("m/44'/60'/2'/0/0")
+                .unwrap()
+                .address,
+            second.address
+        );
+        assert!(manager.derive_account_at_path("44/60").is_err());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_login_upgrade_keeps_chain_addresses() {
+        let (mut manager, dir) = temp_manager("upgrade");
+        let created = manager.create_multichain_wallet("password1", 12).unwrap();
+        manager.logout();
+
+        // A stronger cost makes every stored ciphertext outdated
+        manager.set_kdf_params(Argon2Params {
+            memory_kib: 128,
+            iterations: 2,
+            parallelism: 1,
+        });
+        assert!(manager.keystore.needs_upgrade().unwrap());
+        manager.login("password1").unwrap();
+        assert!(!manager.keystore.needs_upgrade().unwrap());
+
+        assert_eq!(
+            manager.chain_address(BlockchainNetwork::Solana).unwrap(),
+            Some(created.solana.address.clone())
+        );
+        assert_eq!(
+            manager
+                .switch_chain(BlockchainNetwork::Solana)
+                .unwrap()
+                .address,
+            created.solana.address
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_export_requires_password() {
+        let (mut manager, dir) = temp_manager("export");
+        let created = manager.create_wallet("password1", 12).unwrap();
+
+        assert!(matches!(
+            manager.export_seed_phrase("wrong_password").await,
+            Err(IdosError::Auth(_))
+        ));
+        let seed = manager.export_seed_phrase("password1").await.unwrap();
+        assert_eq!(seed.as_str(), created.seed_phrase);
+        let key = manager.export_private_key("password1").await.unwrap();
+        assert_eq!(Some(key.to_string()), created.wallet_info.private_key);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_lifecycle_events() {
+        let (mut manager, dir) = temp_manager("events");
+
+        let address = manager
+            .create_wallet("password1", 12)
+            .unwrap()
+            .wallet_info
+            .address;
+        assert!(matches!(
+            manager.drain_events().as_slice(),
+            [WalletEvent::Created(_), WalletEvent::Unlocked(_)]
+        ));
+
+        manager.logout();
+        manager.logout();
+        manager.login("password1").unwrap();
+        assert!(matches!(
+            manager.drain_events().as_slice(),
+            [
+                WalletEvent::Locked(WalletLocked {
+                    auto_locked: false,
+                    ..
+                }),
+                WalletEvent::Unlocked(_)
+            ]
+        ));
+
+        manager.disconnect().unwrap();
+        match manager.drain_events().as_slice() {
+            [WalletEvent::Deleted(deleted)] => {
+                assert_eq!(deleted.address.as_deref(), Some(address.as_str()))
+            }
+            events => panic!("unexpected events: {:?}", events),
+        }
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
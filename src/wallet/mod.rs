@@ -1,12 +1,31 @@
 /// In-game wallet management module
 /// Provides HD wallet creation, import, and secure storage for both Ethereum and Solana
 /// Matches Unity SDK's NewWallet functionality
+pub mod address;
+pub mod backend;
 pub mod creation;
 pub mod dto;
 pub mod encryption;
+pub mod hardware;
 pub mod import;
 pub mod keystore;
 pub mod manager;
+pub mod reauth;
+pub mod secure_storage;
+pub mod signing;
+pub mod unlock;
+pub mod wallet_plugin;
+pub mod web3_keystore;
 
+pub use address::{is_valid_ethereum_address, to_checksum_address};
+pub use backend::{LocalWalletBackend, WalletBackend};
 pub use dto::*;
+pub use hardware::LedgerWallet;
 pub use manager::WalletManager;
+pub use reauth::ReauthProvider;
+#[cfg(all(feature = "os_keychain", not(target_arch = "wasm32")))]
+pub use secure_storage::OsKeychainBackend;
+pub use secure_storage::SecureStorageBackend;
+pub use unlock::UnlockProvider;
+pub use wallet_plugin::WalletPlugin;
+pub use zeroize::Zeroizing;
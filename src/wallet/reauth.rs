@@ -0,0 +1,13 @@
+/// Re-authentication before wallet secrets are exported
+use super::dto::SecretKind;
+use crate::IdosResult;
+use async_trait::async_trait;
+
+/// Extra check run after the wallet password when a secret is exported
+/// e.g. signing in to the game backend again, or a 2FA prompt
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait ReauthProvider: Send + Sync {
+    /// Return an error to block the export
+    async fn reauthenticate(&self, kind: SecretKind) -> IdosResult<()>;
+}
@@ -0,0 +1,83 @@
+/// Secure storage backends for wallet secrets
+/// Only the encrypted private key, seed phrase and biometric blob go through the backend;
+/// addresses and wallet slots stay in the regular `Storage`
+use crate::IdosResult;
+
+/// Where the keystore keeps encrypted secrets
+/// Set one with `WalletManager::with_storage_backend`; without one, secrets use `Storage`
+pub trait SecureStorageBackend: Send + Sync {
+    /// Human-readable store name for logs and settings screens
+    fn name(&self) -> &str;
+
+    /// `None` if nothing is stored under `key`
+    fn get(&self, key: &str) -> IdosResult<Option<String>>;
+
+    fn set(&self, key: &str, value: &str) -> IdosResult<()>;
+
+    /// Removing a missing key is not an error
+    fn remove(&self, key: &str) -> IdosResult<()>;
+}
+
+/// The operating system's credential store:
+/// macOS Keychain, Windows Credential Manager, or the Linux Secret Service (GNOME Keyring, KWallet)
+#[cfg(all(feature = "os_keychain", not(target_arch = "wasm32")))]
+#[derive(Debug, Clone)]
+pub struct OsKeychainBackend {
+    /// Service name the entries are filed under, e.g. the game's bundle ID
+    service: String,
+}
+
+#[cfg(all(feature = "os_keychain", not(target_arch = "wasm32")))]
+impl OsKeychainBackend {
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    /// Whether the platform store can be reached (the Secret Service may not be running on Linux)
+    pub fn is_available(&self) -> bool {
+        self.get("idos_keychain_probe").is_ok()
+    }
+
+    fn entry(&self, key: &str) -> IdosResult<keyring::Entry> {
+        keyring::Entry::new(&self.service, key).map_err(keychain_error)
+    }
+}
+
+#[cfg(all(feature = "os_keychain", not(target_arch = "wasm32")))]
+impl SecureStorageBackend for OsKeychainBackend {
+    fn name(&self) -> &str {
+        if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+            "macOS Keychain"
+        } else if cfg!(target_os = "windows") {
+            "Windows Credential Manager"
+        } else {
+            "Secret Service"
+        }
+    }
+
+    fn get(&self, key: &str) -> IdosResult<Option<String>> {
+        match self.entry(key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(keychain_error(e)),
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) -> IdosResult<()> {
+        self.entry(key)?.set_password(value).map_err(keychain_error)
+    }
+
+    fn remove(&self, key: &str) -> IdosResult<()> {
+        match self.entry(key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(keychain_error(e)),
+        }
+    }
+}
+
+#[cfg(all(feature = "os_keychain", not(target_arch = "wasm32")))]
+fn keychain_error(e: keyring::Error) -> crate::IdosError {
+    crate::IdosError::Wallet(format!("OS keychain error: {}", e))
+}
@@ -0,0 +1,180 @@
+/// Message signing for in-game wallets
+/// EIP-191 personal_sign for Ethereum, Ed25519 for Solana
+use super::address::ethereum_address_from_public_key;
+use super::dto::*;
+use crate::{IdosError, IdosResult};
+use sha3::{Digest, Keccak256};
+
+/// keccak256("\x19Ethereum Signed Message:\n" + len(message) + message)
+pub fn eip191_hash(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes());
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+/// Sign `message` with the wallet's key
+pub fn sign_message(wallet: &WalletInfo, message: &[u8]) -> IdosResult<MessageSignature> {
+    if wallet.watch_only {
+        return Err(IdosError::WatchOnly(wallet.address.clone()));
+    }
+    if wallet.hardware {
+        return Err(IdosError::Wallet(
+            "Hardware wallets sign on the device; use WalletManager::backend()".to_string(),
+        ));
+    }
+    let private_key = wallet
+        .private_key
+        .as_deref()
+        .ok_or(IdosError::WalletLocked)?;
+
+    let signature = match wallet.network {
+        BlockchainNetwork::Ethereum => sign_ethereum_message(private_key, message)?,
+        BlockchainNetwork::Solana => sign_solana_message(private_key, message)?,
+    };
+
+    Ok(MessageSignature {
+        network: wallet.network,
+        address: wallet.address.clone(),
+        signature,
+    })
+}
+
+/// EIP-191 signature as 0x-prefixed hex r || s || v (v = 27/28), same as MetaMask personal_sign
+pub fn sign_ethereum_message(private_key: &str, message: &[u8]) -> IdosResult<String> {
+    use k256::ecdsa::SigningKey;
+
+    let key_bytes = hex::decode(private_key.trim_start_matches("0x"))
+        .map_err(|e| IdosError::InvalidInput(format!("Invalid hex private key: {}", e)))?;
+    if key_bytes.len() != 32 {
+        return Err(IdosError::InvalidInput(
+            "Ethereum private key must be 32 bytes".to_string(),
+        ));
+    }
+    let signing_key = SigningKey::from_bytes(key_bytes.as_slice().into())
+        .map_err(|e| IdosError::Wallet(format!("Invalid private key: {}", e)))?;
+
+    let (signature, recovery_id) = signing_key
+        .sign_prehash_recoverable(&eip191_hash(message))
+        .map_err(|e| IdosError::Wallet(format!("Signing failed: {}", e)))?;
+
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(27 + recovery_id.to_byte());
+    Ok(format!("0x{}", hex::encode(bytes)))
+}
+
+/// Address that produced an EIP-191 signature (EIP-55 checksummed)
+pub fn recover_ethereum_signer(message: &[u8], signature: &str) -> IdosResult<String> {
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+    let invalid = || IdosError::InvalidInput("Invalid Ethereum signature".to_string());
+    let bytes = hex::decode(signature.trim_start_matches("0x")).map_err(|_| invalid())?;
+    if bytes.len() != 65 {
+        return Err(invalid());
+    }
+
+    let signature = Signature::from_slice(&bytes[..64]).map_err(|_| invalid())?;
+    let recovery_id =
+        RecoveryId::from_byte(bytes[64].saturating_sub(27) % 2).ok_or_else(invalid)?;
+    let verifying_key =
+        VerifyingKey::recover_from_prehash(&eip191_hash(message), &signature, recovery_id)
+            .map_err(|_| invalid())?;
+
+    Ok(ethereum_address_from_public_key(
+        &verifying_key.to_encoded_point(false).as_bytes()[1..],
+    ))
+}
+
+/// Ed25519 signature, base58 encoded like Solana transaction signatures
+pub fn sign_solana_message(private_key: &str, message: &[u8]) -> IdosResult<String> {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let key_bytes = bs58::decode(private_key)
+        .into_vec()
+        .map_err(|e| IdosError::InvalidInput(format!("Invalid base58 private key: {}", e)))?;
+    let secret: [u8; 32] = key_bytes
+        .get(..32)
+        .and_then(|s| s.try_into().ok())
+        .ok_or_else(|| {
+            IdosError::InvalidInput("Solana private key must be 32 or 64 bytes".to_string())
+        })?;
+
+    let signature = SigningKey::from_bytes(&secret).sign(message);
+    Ok(bs58::encode(signature.to_bytes()).into_string())
+}
+
+/// Whether a base58 Ed25519 signature over `message` was made by `address`
+pub fn verify_solana_message(address: &str, message: &[u8], signature: &str) -> bool {
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    let Some(public_key) = bs58::decode(address)
+        .into_vec()
+        .ok()
+        .and_then(|b| <[u8; 32]>::try_from(b).ok())
+    else {
+        return false;
+    };
+    let Some(signature) = bs58::decode(signature)
+        .into_vec()
+        .ok()
+        .and_then(|b| <[u8; 64]>::try_from(b).ok())
+    else {
+        return false;
+    };
+
+    VerifyingKey::from_bytes(&public_key).is_ok_and(|key| {
+        key.verify(message, &Signature::from_bytes(&signature))
+            .is_ok()
+    })
+}
+
+#[cfg(all(test, feature = "wallet"))]
+mod tests {
+    use super::*;
+    use crate::wallet::import::import_wallet;
+
+    #[test]
+    fn test_ethereum_sign_and_recover() {
+        let wallet = import_wallet(
+            ImportSource::PrivateKey(
+                "4c0883a69102937d6231471b5dbb6204fe512961708279f8b1a3e79e5c8c4f8f".to_string(),
+            ),
+            BlockchainNetwork::Ethereum,
+        )
+        .unwrap();
+
+        let signed = sign_message(&wallet, b"Some data").unwrap();
+        assert_eq!(signed.network, BlockchainNetwork::Ethereum);
+        // Matches ethers `LocalWallet::sign_message`
+        assert_eq!(
+            signed.signature,
+            "0xc9e4d05d75d226f9408ad2c6f46dd5c37e12d01632eabff820ef197d7015980f0e009de708b62a700cc0b668aaed54c10f0fece9c46c998214bbb6cdd6e893281b"
+        );
+        assert_eq!(
+            recover_ethereum_signer(b"Some data", &signed.signature).unwrap(),
+            wallet.address
+        );
+    }
+
+    #[test]
+    fn test_solana_sign_and_verify() {
+        let wallet = import_wallet(
+            ImportSource::PrivateKey(bs58::encode([7u8; 32]).into_string()),
+            BlockchainNetwork::Solana,
+        )
+        .unwrap();
+
+        let signed = sign_message(&wallet, b"login").unwrap();
+        assert_eq!(signed.network, BlockchainNetwork::Solana);
+        assert!(verify_solana_message(
+            &wallet.address,
+            b"login",
+            &signed.signature
+        ));
+        assert!(!verify_solana_message(
+            &wallet.address,
+            b"other",
+            &signed.signature
+        ));
+    }
+}
@@ -0,0 +1,92 @@
+/// Biometric unlock for the keystore
+/// The wallet password is sealed with a random wrapping key that the platform keeps
+/// behind biometrics (Keychain/Secure Enclave, Android Keystore, WebAuthn, ...)
+use crate::{IdosError, IdosResult};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+
+/// Platform hook storing device-bound secrets behind a biometric check
+/// Implemented by the game for each platform it ships on
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait UnlockProvider: Send + Sync {
+    /// Whether biometrics are enrolled and usable on this device
+    async fn is_available(&self) -> bool;
+
+    /// Store `secret` under `key_id` so that reading it requires biometrics
+    async fn store_secret(&self, key_id: &str, secret: &[u8]) -> IdosResult<()>;
+
+    /// Prompt for biometrics and return the secret (`None` if nothing is stored)
+    /// A failed or cancelled prompt is an error
+    async fn load_secret(&self, key_id: &str) -> IdosResult<Option<Vec<u8>>>;
+
+    async fn delete_secret(&self, key_id: &str) -> IdosResult<()>;
+}
+
+/// Random 256-bit wrapping key
+pub fn generate_wrapping_key() -> [u8; 32] {
+    use rand::RngCore;
+
+    let mut key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut key);
+    key
+}
+
+/// Seal the password with AES-256-GCM: base64(nonce || ciphertext)
+pub fn wrap_password(password: &str, wrapping_key: &[u8]) -> IdosResult<String> {
+    use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+    use rand::RngCore;
+
+    let cipher = Aes256Gcm::new_from_slice(wrapping_key)
+        .map_err(|e| IdosError::Wallet(format!("Invalid wrapping key: {}", e)))?;
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), password.as_bytes())
+        .map_err(|e| IdosError::Wallet(format!("Encryption failed: {}", e)))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(general_purpose::STANDARD.encode(sealed))
+}
+
+/// Open a password sealed by `wrap_password`
+pub fn unwrap_password(wrapped: &str, wrapping_key: &[u8]) -> IdosResult<String> {
+    use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+
+    let sealed = general_purpose::STANDARD
+        .decode(wrapped)
+        .map_err(|_| IdosError::SerializationError("Malformed wrapped password".to_string()))?;
+    if sealed.len() < 12 {
+        return Err(IdosError::SerializationError(
+            "Malformed wrapped password".to_string(),
+        ));
+    }
+
+    let cipher = Aes256Gcm::new_from_slice(wrapping_key)
+        .map_err(|e| IdosError::Wallet(format!("Invalid wrapping key: {}", e)))?;
+    let plain_bytes = cipher
+        .decrypt(Nonce::from_slice(&sealed[..12]), &sealed[12..])
+        .map_err(|_| IdosError::Auth("Wrapping key does not match".to_string()))?;
+
+    String::from_utf8(plain_bytes)
+        .map_err(|e| IdosError::SerializationError(format!("UTF-8 decode error: {}", e)))
+}
+
+#[cfg(all(test, feature = "wallet"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_password() {
+        let key = generate_wrapping_key();
+        let wrapped = wrap_password("hunter22", &key).unwrap();
+        assert_eq!(unwrap_password(&wrapped, &key).unwrap(), "hunter22");
+
+        let other = generate_wrapping_key();
+        assert!(matches!(
+            unwrap_password(&wrapped, &other),
+            Err(IdosError::Auth(_))
+        ));
+    }
+}
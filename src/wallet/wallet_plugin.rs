@@ -0,0 +1,105 @@
+use super::dto::{
+    WalletCreated, WalletDeleted, WalletEvent, WalletImported, WalletLocked, WalletUnlocked,
+};
+use super::WalletManager;
+/// In-game wallet plugin
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Writes wallet lifecycle messages and runs auto-lock for a `WalletManager` resource
+#[derive(Default)]
+pub struct WalletPlugin {
+    /// Lock the wallet after this long without signing or key access
+    pub auto_lock_after: Option<Duration>,
+}
+
+impl WalletPlugin {
+    pub fn with_auto_lock(timeout: Duration) -> Self {
+        Self {
+            auto_lock_after: Some(timeout),
+        }
+    }
+}
+
+/// Auto-lock timeout applied to `WalletManager` once it is inserted
+#[derive(Resource)]
+struct AutoLockSetting(Option<Duration>);
+
+impl Plugin for WalletPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AutoLockSetting(self.auto_lock_after))
+            .add_message::<WalletCreated>()
+            .add_message::<WalletImported>()
+            .add_message::<WalletUnlocked>()
+            .add_message::<WalletLocked>()
+            .add_message::<WalletDeleted>()
+            .add_systems(
+                Update,
+                (
+                    apply_auto_lock_setting,
+                    auto_lock_wallet,
+                    forward_wallet_events,
+                )
+                    .chain(),
+            );
+
+        info!("Wallet Plugin initialized");
+    }
+}
+
+/// Hand the plugin's timeout to the manager the first time it appears
+fn apply_auto_lock_setting(
+    mut commands: Commands,
+    setting: Option<Res<AutoLockSetting>>,
+    manager: Option<ResMut<WalletManager>>,
+) {
+    let (Some(setting), Some(mut manager)) = (setting, manager) else {
+        return;
+    };
+
+    manager.set_auto_lock(setting.0);
+    commands.remove_resource::<AutoLockSetting>();
+}
+
+/// Lock the wallet after inactivity; `WalletLocked` is forwarded with the other events
+fn auto_lock_wallet(time: Res<Time>, manager: Option<ResMut<WalletManager>>) {
+    let Some(mut manager) = manager else {
+        return;
+    };
+
+    manager.tick_auto_lock(time.delta());
+}
+
+/// Forward the lifecycle events queued by the manager as messages
+fn forward_wallet_events(
+    manager: Option<Res<WalletManager>>,
+    mut created: MessageWriter<WalletCreated>,
+    mut imported: MessageWriter<WalletImported>,
+    mut unlocked: MessageWriter<WalletUnlocked>,
+    mut locked: MessageWriter<WalletLocked>,
+    mut deleted: MessageWriter<WalletDeleted>,
+) {
+    let Some(manager) = manager else {
+        return;
+    };
+
+    for event in manager.drain_events() {
+        match event {
+            WalletEvent::Created(event) => {
+                created.write(event);
+            }
+            WalletEvent::Imported(event) => {
+                imported.write(event);
+            }
+            WalletEvent::Unlocked(event) => {
+                unlocked.write(event);
+            }
+            WalletEvent::Locked(event) => {
+                locked.write(event);
+            }
+            WalletEvent::Deleted(event) => {
+                deleted.write(event);
+            }
+        }
+    }
+}
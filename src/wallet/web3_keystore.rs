@@ -0,0 +1,242 @@
+/// Web3 Secret Storage (keystore JSON V3) import and export
+/// The format used by geth, MetaMask and most Ethereum tooling
+/// https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/
+use crate::{IdosError, IdosResult};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+/// Scrypt cost of exported files (geth's "light" parameters, quick enough for games)
+const EXPORT_SCRYPT_LOG_N: u8 = 12;
+const EXPORT_SCRYPT_R: u32 = 8;
+const EXPORT_SCRYPT_P: u32 = 6;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeystoreFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    #[serde(alias = "Crypto")]
+    crypto: KeystoreCrypto,
+    id: String,
+    version: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeystoreCrypto {
+    cipher: String,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    #[serde(flatten)]
+    kdf: KdfParams,
+    mac: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+enum KdfParams {
+    Scrypt {
+        dklen: usize,
+        n: u64,
+        r: u32,
+        p: u32,
+        salt: String,
+    },
+    Pbkdf2 {
+        dklen: usize,
+        c: u32,
+        prf: String,
+        salt: String,
+    },
+}
+
+fn decode_hex(field: &str, value: &str) -> IdosResult<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| IdosError::InvalidInput(format!("Invalid keystore {}: {}", field, e)))
+}
+
+fn derive_key(kdf: &KdfParams, password: &str) -> IdosResult<Vec<u8>> {
+    match kdf {
+        KdfParams::Scrypt {
+            dklen,
+            n,
+            r,
+            p,
+            salt,
+        } => {
+            if !n.is_power_of_two() || *dklen < 32 {
+                return Err(IdosError::InvalidInput(
+                    "Unsupported keystore scrypt parameters".to_string(),
+                ));
+            }
+            let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p, *dklen)
+                .map_err(|e| IdosError::InvalidInput(format!("Invalid scrypt params: {}", e)))?;
+
+            let mut key = vec![0u8; *dklen];
+            scrypt::scrypt(
+                password.as_bytes(),
+                &decode_hex("salt", salt)?,
+                &params,
+                &mut key,
+            )
+            .map_err(|e| IdosError::Wallet(format!("Key derivation failed: {}", e)))?;
+            Ok(key)
+        }
+        KdfParams::Pbkdf2 {
+            dklen,
+            c,
+            prf,
+            salt,
+        } => {
+            if prf != "hmac-sha256" || *dklen < 32 {
+                return Err(IdosError::InvalidInput(format!(
+                    "Unsupported keystore pbkdf2 parameters: {}",
+                    prf
+                )));
+            }
+
+            let mut key = vec![0u8; *dklen];
+            pbkdf2::pbkdf2_hmac::<sha2::Sha256>(
+                password.as_bytes(),
+                &decode_hex("salt", salt)?,
+                *c,
+                &mut key,
+            );
+            Ok(key)
+        }
+    }
+}
+
+/// keccak256(derived_key[16..32] ++ ciphertext)
+fn keystore_mac(derived_key: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(&derived_key[16..32]);
+    hasher.update(ciphertext);
+    hasher.finalize().into()
+}
+
+/// AES-128-CTR is its own inverse
+fn aes_128_ctr(key: &[u8], iv: &[u8], data: &mut [u8]) -> IdosResult<()> {
+    use aes::cipher::{KeyIvInit, StreamCipher};
+
+    let mut cipher = ctr::Ctr128BE::<aes::Aes128>::new_from_slices(key, iv)
+        .map_err(|_| IdosError::InvalidInput("Invalid keystore IV".to_string()))?;
+    cipher.apply_keystream(data);
+    Ok(())
+}
+
+/// Decrypt a keystore file and return the private key (hex, without 0x)
+/// A wrong password is reported as `Auth`
+pub fn decrypt_keystore(json: &str, password: &str) -> IdosResult<String> {
+    let file: KeystoreFile = serde_json::from_str(json)
+        .map_err(|e| IdosError::InvalidInput(format!("Invalid keystore file: {}", e)))?;
+    if file.version != 3 {
+        return Err(IdosError::InvalidInput(format!(
+            "Unsupported keystore version {}",
+            file.version
+        )));
+    }
+    if file.crypto.cipher != "aes-128-ctr" {
+        return Err(IdosError::InvalidInput(format!(
+            "Unsupported keystore cipher {}",
+            file.crypto.cipher
+        )));
+    }
+
+    let derived_key = derive_key(&file.crypto.kdf, password)?;
+    let mut ciphertext = decode_hex("ciphertext", &file.crypto.ciphertext)?;
+    if keystore_mac(&derived_key, &ciphertext)[..] != decode_hex("mac", &file.crypto.mac)?[..] {
+        return Err(IdosError::Auth("Incorrect keystore password".to_string()));
+    }
+
+    aes_128_ctr(
+        &derived_key[..16],
+        &decode_hex("iv", &file.crypto.cipherparams.iv)?,
+        &mut ciphertext,
+    )?;
+    Ok(hex::encode(ciphertext))
+}
+
+/// Encrypt an Ethereum private key (hex) into a keystore file (scrypt, AES-128-CTR)
+pub fn encrypt_keystore(private_key: &str, address: &str, password: &str) -> IdosResult<String> {
+    use rand::RngCore;
+
+    if password.is_empty() {
+        return Err(IdosError::InvalidInput(
+            "Password cannot be empty".to_string(),
+        ));
+    }
+
+    let mut salt = [0u8; 32];
+    let mut iv = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut iv);
+
+    let kdf = KdfParams::Scrypt {
+        dklen: 32,
+        n: 1 << EXPORT_SCRYPT_LOG_N,
+        r: EXPORT_SCRYPT_R,
+        p: EXPORT_SCRYPT_P,
+        salt: hex::encode(salt),
+    };
+    let derived_key = derive_key(&kdf, password)?;
+
+    let mut ciphertext = decode_hex("private key", private_key)?;
+    aes_128_ctr(&derived_key[..16], &iv, &mut ciphertext)?;
+    let mac = keystore_mac(&derived_key, &ciphertext);
+
+    let file = KeystoreFile {
+        address: Some(address.trim_start_matches("0x").to_lowercase()),
+        crypto: KeystoreCrypto {
+            cipher: "aes-128-ctr".to_string(),
+            cipherparams: CipherParams {
+                iv: hex::encode(iv),
+            },
+            ciphertext: hex::encode(ciphertext),
+            kdf,
+            mac: hex::encode(mac),
+        },
+        id: uuid::Uuid::new_v4().to_string(),
+        version: 3,
+    };
+
+    Ok(serde_json::to_string(&file)?)
+}
+
+#[cfg(all(test, feature = "wallet"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pbkdf2_vector() {
+        // Test vector from the Web3 Secret Storage definition
+        let json = r#"{"crypto":{"cipher":"aes-128-ctr","cipherparams":{"iv":"6087dab2f9fdbbfaddc31a909735c1e6"},"ciphertext":"5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46","kdf":"pbkdf2","kdfparams":{"c":262144,"dklen":32,"prf":"hmac-sha256","salt":"ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"},"mac":"517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"},"id":"3198bc9c-6672-5ab3-d995-4942343ae5b6","version":3}"#;
+
+        assert_eq!(
+            decrypt_keystore(json, "testpassword").unwrap(),
+            "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+        );
+        assert!(matches!(
+            decrypt_keystore(json, "wrong"),
+            Err(IdosError::Auth(_))
+        ));
+    }
+
+    #[test]
+    fn test_scrypt_round_trip() {
+        let private_key = "4c0883a69102937d6231471b5dbb6204fe512961708279f8b1a3e79e5c8c4f8f";
+        let json = encrypt_keystore(
+            private_key,
+            "0x4CDDF15861Ab6c18dE28E468404C070D87c889b9",
+            "hunter22",
+        )
+        .unwrap();
+
+        assert!(json.contains(r#""address":"4cddf15861ab6c18de28e468404c070d87c889b9""#));
+        assert!(json.contains(r#""kdf":"scrypt""#));
+        assert_eq!(decrypt_keystore(&json, "hunter22").unwrap(), private_key);
+    }
+}